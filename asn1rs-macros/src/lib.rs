@@ -18,6 +18,20 @@ pub fn asn(attr: TokenStream, item: TokenStream) -> TokenStream {
     TokenStream::from(asn1rs_model::proc_macro::parse(attr.into(), item.into()))
 }
 
+/// Implemented by the `#[asn(..)]` attribute, which removes this derive, see
+/// `asn1rs::descriptor::schema`. Only reached if the `#[asn(..)]` attribute is missing or placed
+/// below the `#[derive(..)]` attribute.
+#[proc_macro_derive(Asn1Schema)]
+pub fn asn1_schema(input: TokenStream) -> TokenStream {
+    let input = parse_macro_input!(input as DeriveInput);
+    syn::Error::new(
+        input.ident.span(),
+        "Asn1Schema requires an #[asn(..)] attribute above the #[derive(..)] attribute",
+    )
+    .to_compile_error()
+    .into()
+}
+
 #[proc_macro_derive(ProtobufEq)]
 pub fn protobuf_eq(input: TokenStream) -> TokenStream {
     let output = derive_protobuf_eq::expand(parse_macro_input!(input as DeriveInput));
@@ -0,0 +1,154 @@
+//! [`Arbitrary`] implementations for the model types, so that the parser and the generators
+//! can be fuzzed with structured input instead of random bytes only.
+
+use crate::asn::{Charset, Range, Size, Tag};
+use crate::parse::{Location, Token};
+use arbitrary::{Arbitrary, Result, Unstructured};
+use std::fmt::{Debug, Display};
+
+/// Separators as recognized by the `Tokenizer`
+const SEPARATORS: [char; 13] = [
+    ':', ';', '=', '(', ')', '{', '}', '.', ',', '[', ']', '\'', '"',
+];
+
+/// Words that are meaningful for the parser, so that generated token streams get past the
+/// first few checks more often than random text would
+const KEYWORDS: [&str; 46] = [
+    "DEFINITIONS",
+    "AUTOMATIC",
+    "EXPLICIT",
+    "IMPLICIT",
+    "TAGS",
+    "BEGIN",
+    "END",
+    "IMPORTS",
+    "FROM",
+    "SEQUENCE",
+    "SET",
+    "OF",
+    "CHOICE",
+    "ENUMERATED",
+    "INTEGER",
+    "BOOLEAN",
+    "NULL",
+    "OCTET",
+    "BIT",
+    "STRING",
+    "UTF8String",
+    "IA5String",
+    "NumericString",
+    "PrintableString",
+    "VisibleString",
+    "TeletexString",
+    "VideotexString",
+    "GraphicString",
+    "ObjectDescriptor",
+    "OPTIONAL",
+    "DEFAULT",
+    "SIZE",
+    "MIN",
+    "MAX",
+    "UNIVERSAL",
+    "APPLICATION",
+    "PRIVATE",
+    "TRUE",
+    "FALSE",
+    "WITH",
+    "COMPONENTS",
+    "OBJECT",
+    "IDENTIFIER",
+    "PRESENT",
+    "ABSENT",
+    "H",
+];
+
+const IDENTIFIERS: [&str; 6] = ["Module", "Potato", "abc", "def", "my-field", "x1"];
+
+impl<'a> Arbitrary<'a> for Location {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Location::at(
+            u.int_in_range(1..=u16::MAX as usize)?,
+            u.int_in_range(1..=u16::MAX as usize)?,
+        ))
+    }
+}
+
+impl<'a> Arbitrary<'a> for Token {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let location = Location::arbitrary(u)?;
+        Ok(match u.int_in_range(0..=4)? {
+            0 | 1 => Token::Separator(location, *u.choose(&SEPARATORS)?),
+            2 => Token::Text(location, u.choose(&KEYWORDS)?.to_string()),
+            3 => Token::Text(location, u.choose(&IDENTIFIERS)?.to_string()),
+            _ => Token::Text(location, i64::arbitrary(u)?.to_string()),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Tag {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        let number = usize::arbitrary(u)?;
+        Ok(match u.int_in_range(0..=3)? {
+            0 => Tag::Universal(number),
+            1 => Tag::Application(number),
+            2 => Tag::ContextSpecific(number),
+            _ => Tag::Private(number),
+        })
+    }
+}
+
+impl<'a> Arbitrary<'a> for Charset {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(*u.choose(&[
+            Charset::Utf8,
+            Charset::Numeric,
+            Charset::Printable,
+            Charset::Ia5,
+            Charset::Visible,
+            Charset::Teletex,
+            Charset::Videotex,
+            Charset::Graphic,
+            Charset::ObjectDescriptor,
+        ])?)
+    }
+}
+
+impl<'a, T: Arbitrary<'a>> Arbitrary<'a> for Range<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(Range(
+            T::arbitrary(u)?,
+            T::arbitrary(u)?,
+            bool::arbitrary(u)?,
+        ))
+    }
+}
+
+impl<'a, T: Arbitrary<'a> + Display + Debug + Clone> Arbitrary<'a> for Size<T> {
+    fn arbitrary(u: &mut Unstructured<'a>) -> Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Size::Any,
+            1 => Size::Fix(T::arbitrary(u)?, bool::arbitrary(u)?),
+            _ => Size::Range(T::arbitrary(u)?, T::arbitrary(u)?, bool::arbitrary(u)?),
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Model;
+
+    #[test]
+    pub fn test_token_streams_do_not_panic_the_parser() {
+        let data = (0..4096_u32)
+            .map(|i| (i.wrapping_mul(2_654_435_761) >> 13) as u8)
+            .collect::<Vec<_>>();
+        let mut u = Unstructured::new(&data);
+        while !u.is_empty() {
+            let tokens = u.arbitrary::<Vec<Token>>().unwrap();
+            if let Ok(model) = Model::try_from(tokens) {
+                let _ = model.try_resolve();
+            }
+        }
+    }
+}
@@ -0,0 +1,59 @@
+use crate::asn::Charset;
+
+/// ITU-T X.680 | ISO/IEC 8824-1, 51.7: The characters a restricted character string is limited
+/// to by a `FROM` constraint. Only PER-visible (not extensible) constraints are kept, see
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 9.3.
+///
+/// The characters are sorted and deduplicated.
+/// ```rust
+/// use asn1rs_model::asn::PermittedAlphabet;
+/// let alphabet = PermittedAlphabet::new("CAB".chars().chain('A'..='D'));
+/// assert_eq!(&['A', 'B', 'C', 'D'], alphabet.chars());
+/// assert_eq!("from(\"ABCD\")", alphabet.to_constraint_string());
+/// ```
+#[derive(Debug, Clone, PartialOrd, PartialEq, Eq, Hash)]
+pub struct PermittedAlphabet(Vec<char>);
+
+impl PermittedAlphabet {
+    pub fn new(chars: impl IntoIterator<Item = char>) -> Self {
+        let mut chars = chars.into_iter().collect::<Vec<_>>();
+        chars.sort_unstable();
+        chars.dedup();
+        Self(chars)
+    }
+
+    /// The effective permitted alphabet of the given [`Charset`], ignoring any character that
+    /// is not valid for the charset
+    pub fn restricted_to(self, charset: Charset) -> Self {
+        Self(
+            self.0
+                .into_iter()
+                .filter(|c| charset.is_valid(*c))
+                .collect(),
+        )
+    }
+
+    pub fn chars(&self) -> &[char] {
+        &self.0[..]
+    }
+
+    pub fn contains(&self, char: char) -> bool {
+        self.0.binary_search(&char).is_ok()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn as_string(&self) -> String {
+        self.0.iter().collect()
+    }
+
+    pub fn to_constraint_string(&self) -> String {
+        format!("from({:?})", self.as_string())
+    }
+}
@@ -8,14 +8,18 @@ pub enum Charset {
     Numeric,
     /// ITU-T X.680 | ISO/IEC 8824-1, 43.3
     Printable,
-
-    // /// (Also T61String)
-    // Teletext,
-    // Videotext,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 41.1
+    /// (Also T61String)
+    Teletex,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 41.1
+    Videotex,
     /// Encoding as in ISO/IEC 646 (??)
     Ia5,
-
-    // GraphicsString,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 41.1
+    Graphic,
+    /// ITU-T X.680 | ISO/IEC 8824-1, 44.3
+    /// (`[UNIVERSAL 7] IMPLICIT GraphicString`)
+    ObjectDescriptor,
     /// ITU-T X.680 | ISO/IEC 8824-1, 43.3
     /// (Also ISO646String)
     Visible,
@@ -72,11 +76,48 @@ impl Charset {
             Charset::Utf8 => Tag::DEFAULT_UTF8_STRING,
             Charset::Numeric => Tag::DEFAULT_NUMERIC_STRING,
             Charset::Printable => Tag::DEFAULT_PRINTABLE_STRING,
+            Charset::Teletex => Tag::DEFAULT_TELETEXT_STRING,
+            Charset::Videotex => Tag::DEFAULT_VIDEOTEXT_STRING,
             Charset::Ia5 => Tag::DEFAULT_IA5_STRING,
+            Charset::Graphic => Tag::DEFAULT_GRAPHIC_STRING,
+            Charset::ObjectDescriptor => Tag::DEFAULT_OBJECT_DESCRIPTOR,
             Charset::Visible => Tag::DEFAULT_VISIBLE_STRING,
         }
     }
 
+    /// Whether the PER encoding of this string type uses a fixed number of bits per character,
+    /// see ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30. All other string types are encoded
+    /// as (unconstrained) octet strings.
+    /// ```rust
+    /// use asn1rs_model::asn::Charset;
+    /// assert!(Charset::Ia5.is_known_multiplier());
+    /// assert!(!Charset::Utf8.is_known_multiplier());
+    /// assert!(!Charset::Graphic.is_known_multiplier());
+    /// ```
+    pub const fn is_known_multiplier(self) -> bool {
+        matches!(
+            self,
+            Charset::Numeric | Charset::Printable | Charset::Ia5 | Charset::Visible
+        )
+    }
+
+    /// The (sorted) characters of a known-multiplier character string type, see
+    /// [`Charset::is_known_multiplier`]
+    /// ```rust
+    /// use asn1rs_model::asn::Charset;
+    /// assert_eq!(Some(Charset::NUMERIC_STRING_CHARACTERS), Charset::Numeric.known_multiplier_characters());
+    /// assert_eq!(None, Charset::Utf8.known_multiplier_characters());
+    /// ```
+    pub const fn known_multiplier_characters(self) -> Option<&'static str> {
+        match self {
+            Charset::Numeric => Some(Self::NUMERIC_STRING_CHARACTERS),
+            Charset::Printable => Some(Self::PRINTABLE_STRING_CHARACTERS),
+            Charset::Ia5 => Some(Self::IA5_STRING_CHARACTERS),
+            Charset::Visible => Some(Self::VISIBLE_STRING_CHARACTERS),
+            _ => None,
+        }
+    }
+
     pub fn find_invalid(self, str: &str) -> Option<(usize, char)> {
         str.chars()
             .enumerate()
@@ -85,12 +126,16 @@ impl Charset {
 
     pub const fn is_valid(self, char: char) -> bool {
         match self {
-            Charset::Utf8 => true,
+            Charset::Utf8 | Charset::Teletex | Charset::Videotex => true,
             Charset::Numeric => matches!(char, ' ' | '0'..='9'),
             Charset::Printable => {
                 matches!(char, ' ' | '\'' ..= ')' | '+' ..= ':' | '=' | '?' | 'A'..='Z' | 'a'..='z'  )
             }
             Charset::Ia5 => matches!(char as u32, 0_u32..=127),
+            Charset::Graphic | Charset::ObjectDescriptor => {
+                // no C0 and C1 control characters
+                !matches!(char as u32, 0_u32..=31 | 127_u32..=159)
+            }
             Charset::Visible => matches!(char as u32, 32_u32..=126),
         }
     }
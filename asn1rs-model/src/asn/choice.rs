@@ -72,6 +72,11 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for Choice<Unresolved>
             extension_after: None,
         };
 
+        // an empty placeholder, which has no value and is therefore never encoded
+        if iter.next_is_separator_and_eq('}') {
+            return Ok(choice);
+        }
+
         loop {
             if let Ok(extension_marker) = iter.next_if_separator_and_eq('.') {
                 if choice.variants.is_empty() || choice.extension_after.is_some() {
@@ -63,6 +63,46 @@ impl Enumerated {
     pub fn extension_after_index(&self) -> Option<usize> {
         self.extension_after
     }
+
+    /// The numbers of the variants, as declared or as assigned to the variants without one, see
+    /// ITU-T X.680 | ISO/IEC 8824-1, 20.3 and 20.5
+    pub fn numbers(&self) -> Vec<usize> {
+        let root_len = self
+            .extension_after
+            .map_or(self.variants.len(), |index| index + 1);
+        let (root, additions) = self.variants.split_at(root_len);
+        let declared = root
+            .iter()
+            .filter_map(EnumeratedVariant::number)
+            .collect::<Vec<_>>();
+        let mut numbers = Vec::with_capacity(self.variants.len());
+        let mut next = 0;
+
+        for variant in root {
+            let number = match variant.number {
+                Some(number) => number,
+                None => {
+                    while declared.contains(&next) || numbers.contains(&next) {
+                        next += 1;
+                    }
+                    next
+                }
+            };
+            numbers.push(number);
+        }
+
+        let mut next = 0;
+        for variant in additions {
+            let mut number = variant.number.unwrap_or(next);
+            while variant.number.is_none() && numbers[..root_len].contains(&number) {
+                number += 1;
+            }
+            next = number + 1;
+            numbers.push(number);
+        }
+
+        numbers
+    }
 }
 
 impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for Enumerated {
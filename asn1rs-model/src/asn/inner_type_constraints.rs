@@ -81,6 +81,7 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>> for ValueConstraint {
         while !(level == 0 && iter.peek_is_separator_eq(')')) {
             match iter.next_or_err()? {
                 Token::Text(_location, text) => string.push_str(&text),
+                Token::Comment(..) => {}
                 Token::Separator(_location, separator) => {
                     match separator {
                         '(' => level += 1,
@@ -9,8 +9,10 @@ use std::convert::TryFrom;
 use std::fmt::{Debug, Display};
 use std::iter::Peekable;
 
+const I64_MAX: i128 = i64::MAX as i128;
+
 #[derive(Default, Debug, Clone, PartialOrd, PartialEq, Eq)]
-pub struct Integer<T: Display + Debug + Clone = i64> {
+pub struct Integer<T: Display + Debug + Clone = i128> {
     pub range: Range<Option<T>>,
     pub constants: Vec<(String, i64)>,
 }
@@ -32,51 +34,178 @@ impl<T: Iterator<Item = Token>> TryFrom<&mut Peekable<T>>
     fn try_from(iter: &mut Peekable<T>) -> Result<Self, Self::Error> {
         let constants =
             Model::<Asn>::maybe_read_constants(iter, Model::<Asn>::constant_i64_parser)?;
-        let range = if iter.next_is_separator_and_eq('(') {
-            let start = iter.next_or_err()?;
-            iter.next_separator_eq_or_err('.')?;
-            iter.next_separator_eq_or_err('.')?;
-            let end = iter.next_or_err()?;
-            let extensible = if iter.next_is_separator_and_eq(',') {
-                iter.next_separator_eq_or_err('.')?;
-                iter.next_separator_eq_or_err('.')?;
-                iter.next_separator_eq_or_err('.')?;
-                true
-            } else {
-                false
-            };
-            iter.next_separator_eq_or_err(')')?;
-            let start = start
-                .text()
-                .filter(|txt| !txt.eq_ignore_ascii_case("MIN"))
-                .map(|t| match t.parse::<i64>() {
-                    Ok(lit) => LitOrRef::Lit(lit),
-                    Err(_) => LitOrRef::Ref(t.to_string()),
-                });
-
-            let end = end
-                .text()
-                .filter(|txt| !txt.eq_ignore_ascii_case("MAX"))
-                .map(|t| match t.parse::<i64>() {
-                    Ok(lit) => LitOrRef::Lit(lit),
-                    Err(_) => LitOrRef::Ref(t.to_string()),
-                });
-
-            match (start, end) {
-                (Some(LitOrRef::Lit(0)), None) | (None, Some(LitOrRef::Lit(i64::MAX))) => {
+        let mut effective = Effective::Range(None, None);
+        let mut extensible = false;
+
+        // ITU-T X.680 | ISO/IEC 8824-1, 49.8: serially applied constraints are intersected,
+        // but only the last one determines whether the type is extensible
+        while iter.next_is_separator_and_eq('(') {
+            let (constraint, constraint_extensible) = read_constraint(iter)?;
+            extensible = constraint_extensible && !matches!(constraint, Effective::NotVisible);
+            effective = effective.intersection(constraint)?;
+        }
+
+        let range = match effective {
+            Effective::NotVisible => Range(None, None, false),
+            Effective::Range(start, end) => match (start.map(|(v, _)| v), end.map(|(v, _)| v)) {
+                (Some(LitOrRef::Lit(0)), None) | (None, Some(LitOrRef::Lit(I64_MAX))) => {
                     Range(None, None, extensible)
                 }
                 (start, end) => Range(start, end, extensible),
-            }
-        } else {
-            Range(None, None, false)
+            },
         };
         Ok(Self { range, constants })
     }
 }
 
-impl TryResolve<i64, Integer<i64>> for Integer<LitOrRef<i64>> {
-    fn try_resolve(&self, resolver: &impl Resolver<i64>) -> Result<Integer<i64>, ResolveError> {
+/// A bound of a value range and the token it was read from, `None` for `MIN` and `MAX`
+type Bound = Option<(LitOrRef<i128>, Token)>;
+
+/// The effective constraint of (a part of) a constraint, ITU-T X.691 | ISO/IEC 8825-2:2015,
+/// chapter 9.3
+enum Effective {
+    Range(Bound, Bound),
+    /// Contains a constraint that is not PER-visible, see
+    /// [`Model::skip_not_per_visible_constraint`]
+    NotVisible,
+}
+
+impl Effective {
+    /// The smallest range that contains both ranges, not PER-visible if any part is not
+    fn union(self, other: Effective) -> Result<Effective, Error> {
+        match (self, other) {
+            (Effective::Range(min_a, max_a), Effective::Range(min_b, max_b)) => Ok(
+                Effective::Range(outer(min_a, min_b, false)?, outer(max_a, max_b, true)?),
+            ),
+            _ => Ok(Effective::NotVisible),
+        }
+    }
+
+    /// The range that satisfies both ranges, ignoring parts that are not PER-visible
+    fn intersection(self, other: Effective) -> Result<Effective, Error> {
+        match (self, other) {
+            (Effective::Range(min_a, max_a), Effective::Range(min_b, max_b)) => Ok(
+                Effective::Range(inner(min_a, min_b, false)?, inner(max_a, max_b, true)?),
+            ),
+            (Effective::NotVisible, other) | (other, Effective::NotVisible) => Ok(other),
+        }
+    }
+}
+
+/// The less restrictive bound, an absent bound is unrestricted
+fn outer(a: Bound, b: Bound, upper: bool) -> Result<Bound, Error> {
+    Ok(match (a, b) {
+        (Some(a), Some(b)) => Some(pick(a, b, upper)?),
+        _ => None,
+    })
+}
+
+/// The more restrictive bound, an absent bound is unrestricted
+fn inner(a: Bound, b: Bound, upper: bool) -> Result<Bound, Error> {
+    Ok(match (a, b) {
+        (Some(a), Some(b)) => Some(pick(a, b, !upper)?),
+        (a, None) | (None, a) => a,
+    })
+}
+
+fn pick(
+    a: (LitOrRef<i128>, Token),
+    b: (LitOrRef<i128>, Token),
+    greater: bool,
+) -> Result<(LitOrRef<i128>, Token), Error> {
+    match (&a.0, &b.0) {
+        (LitOrRef::Lit(lit_a), LitOrRef::Lit(lit_b)) => {
+            Ok(if (lit_a < lit_b) == greater { b } else { a })
+        }
+        (ref_a, ref_b) if ref_a == ref_b => Ok(a),
+        // value references are only resolved later on, so they cannot be compared yet
+        (LitOrRef::Ref(_), _) => Err(Error::invalid_range_value(a.1)),
+        (_, LitOrRef::Ref(_)) => Err(Error::invalid_range_value(b.1)),
+    }
+}
+
+/// Reads the content of a constraint (after the opening parenthesis) and whether it is
+/// extensible. The root of an extensible constraint is PER-visible, the additions are not.
+fn read_constraint<T: Iterator<Item = Token>>(
+    iter: &mut Peekable<T>,
+) -> Result<(Effective, bool), Error> {
+    let root = read_union(iter)?;
+    let extensible = if iter.next_is_separator_and_eq(',') {
+        iter.next_separator_eq_or_err('.')?;
+        iter.next_separator_eq_or_err('.')?;
+        iter.next_separator_eq_or_err('.')?;
+        if iter.next_is_separator_and_eq(',') {
+            let _additions = read_union(iter)?;
+        }
+        true
+    } else {
+        false
+    };
+    iter.next_separator_eq_or_err(')')?;
+    Ok((root, extensible))
+}
+
+fn read_union<T: Iterator<Item = Token>>(iter: &mut Peekable<T>) -> Result<Effective, Error> {
+    let mut effective = read_intersection(iter)?;
+    while iter.next_is_text_and_eq_ignore_case("|") || iter.next_is_text_and_eq_ignore_case("UNION")
+    {
+        effective = effective.union(read_intersection(iter)?)?;
+    }
+    Ok(effective)
+}
+
+fn read_intersection<T: Iterator<Item = Token>>(
+    iter: &mut Peekable<T>,
+) -> Result<Effective, Error> {
+    let mut effective = read_element(iter)?;
+    while iter.next_is_text_and_eq_ignore_case("^")
+        || iter.next_is_text_and_eq_ignore_case("INTERSECTION")
+    {
+        effective = effective.intersection(read_element(iter)?)?;
+    }
+    Ok(effective)
+}
+
+/// Reads a single value, a value range or a parenthesized union
+fn read_element<T: Iterator<Item = Token>>(iter: &mut Peekable<T>) -> Result<Effective, Error> {
+    if Model::<Asn<Unresolved>>::skip_not_per_visible_constraint(iter)? {
+        return Ok(Effective::NotVisible);
+    }
+    if iter.next_is_separator_and_eq('(') {
+        let effective = read_union(iter)?;
+        iter.next_separator_eq_or_err(')')?;
+        return Ok(effective);
+    }
+
+    let start = read_bound(iter, "MIN")?;
+    if iter.next_is_separator_and_eq('.') {
+        iter.next_separator_eq_or_err('.')?;
+        let end = read_bound(iter, "MAX")?;
+        Ok(Effective::Range(start, end))
+    } else {
+        Ok(Effective::Range(start.clone(), start))
+    }
+}
+
+fn read_bound<T: Iterator<Item = Token>>(
+    iter: &mut Peekable<T>,
+    unbounded: &str,
+) -> Result<Bound, Error> {
+    let token = iter.next_or_err()?;
+    let text = match token.text() {
+        Some(text) if text.eq_ignore_ascii_case(unbounded) => return Ok(None),
+        Some(text) => text.to_string(),
+        None => return Err(Error::unexpected_token(token)),
+    };
+    let value = match text.parse::<i128>() {
+        Ok(lit) => LitOrRef::Lit(lit),
+        Err(_) => LitOrRef::Ref(text),
+    };
+    Ok(Some((value, token)))
+}
+
+impl TryResolve<i128, Integer<i128>> for Integer<LitOrRef<i128>> {
+    fn try_resolve(&self, resolver: &impl Resolver<i128>) -> Result<Integer<i128>, ResolveError> {
         Ok(Integer {
             range: Range(
                 self.range
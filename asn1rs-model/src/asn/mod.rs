@@ -8,6 +8,7 @@ macro_rules! loop_ctrl_separator {
     };
 }
 
+mod alphabet;
 mod bit_string;
 mod charset;
 mod choice;
@@ -25,6 +26,7 @@ mod tag;
 mod tag_resolver;
 
 pub use crate::asn::bit_string::BitString;
+pub use alphabet::PermittedAlphabet;
 pub use charset::Charset;
 pub use choice::Choice;
 pub use choice::ChoiceVariant;
@@ -165,7 +167,8 @@ pub enum Type<RS: ResolveState = Resolved> {
     Boolean,
     /// ITU-T X.680 | ISO/IEC 8824-1, 19
     Integer(Integer<RS::RangeType>),
-    String(Size<RS::SizeType>, Charset),
+    /// ITU-T X.680 | ISO/IEC 8824-1, 40
+    String(Size<RS::SizeType>, Charset, Option<PermittedAlphabet>),
     /// ITU-T X.680 | ISO/IEC 8824-1, 23
     OctetString(Size<RS::SizeType>),
     /// ITU-T X.680 | ISO/IEC 8824-1, 22
@@ -208,7 +211,7 @@ impl Type {
 
 impl<RS: ResolveState> Type<RS> {
     pub fn unconstrained_utf8string() -> Self {
-        Self::String(Size::Any, Charset::Utf8)
+        Self::String(Size::Any, Charset::Utf8, None)
     }
 
     pub fn unconstrained_octetstring() -> Self {
@@ -278,7 +281,9 @@ impl Type<Unresolved> {
         Ok(match self {
             Type::Boolean => Type::Boolean,
             Type::Integer(integer) => Type::Integer(integer.try_resolve(resolver)?),
-            Type::String(size, charset) => Type::String(size.try_resolve(resolver)?, *charset),
+            Type::String(size, charset, alphabet) => {
+                Type::String(size.try_resolve(resolver)?, *charset, alphabet.clone())
+            }
             Type::OctetString(size) => Type::OctetString(size.try_resolve(resolver)?),
             Type::BitString(string) => Type::BitString(string.try_resolve(resolver)?),
             Type::Null => Type::Null,
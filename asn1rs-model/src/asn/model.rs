@@ -1,21 +1,41 @@
 use crate::asn::oid::{ObjectIdentifier, ObjectIdentifierComponent};
 use crate::asn::peekable::PeekableTokens;
+use crate::asn::resolve_scope::type_references;
 use crate::asn::resolve_scope::ResolveScope;
 use crate::asn::{Asn, ComponentTypeList, InnerTypeConstraints, Size, Tag, Type};
-use crate::asn::{BitString, Charset, Choice, Enumerated, Integer};
-use crate::model::{Field, Import, LiteralValue, Model, ValueReference};
+use crate::asn::{BitString, Charset, Choice, Enumerated, Integer, PermittedAlphabet};
+use crate::model::{Definition, Docs, Field, Import, LiteralValue, Model, ValueReference};
 use crate::parse::Location;
 use crate::parse::Token;
 use crate::parse::{Error, ErrorKind};
 use crate::resolve::{LitOrRef, ResolveState, Resolved, Resolver, Unresolved};
-use crate::rust::Rust;
+use crate::rust::{InlineNaming, Rust};
+use std::collections::HashSet;
 use std::convert::TryFrom;
 use std::iter::Peekable;
 use std::vec::IntoIter;
 
 impl Model<Asn<Unresolved>> {
     pub fn try_from(value: Vec<Token>) -> Result<Self, Error> {
+        Self::read_module(value, false).map(|(model, _)| model)
+    }
+
+    /// Like [`Model::try_from`], but a definition or value reference that cannot be parsed, for
+    /// example because it uses information object classes or parameterization, is skipped
+    /// instead of failing the whole module. Definitions referring to a skipped definition are
+    /// skipped as well. The reasons for skipping are returned as warnings.
+    pub fn try_from_lenient(value: Vec<Token>) -> Result<(Self, Vec<Error>), Error> {
+        Self::read_module(value, true)
+    }
+
+    fn read_module(value: Vec<Token>, lenient: bool) -> Result<(Self, Vec<Error>), Error> {
         let mut model = Model::default();
+        let mut warnings = Vec::new();
+        let mut skipped = Vec::new();
+        let (comments, value): (Vec<_>, Vec<_>) = value.into_iter().partition(Token::is_comment);
+        let boundaries = Self::assignment_boundaries(&value);
+        model.docs = Self::read_docs(&value, &comments, &boundaries);
+        let total = value.len();
         let mut iter = value.into_iter().peekable();
 
         model.name = Self::read_name(&mut iter)?;
@@ -23,28 +43,251 @@ impl Model<Asn<Unresolved>> {
         Self::skip_until_after_text_ignore_ascii_case(&mut iter, "BEGIN")?;
 
         while let Some(token) = iter.next() {
+            let position = total - iter.len() - 1;
             if token.eq_text_ignore_ascii_case("END") {
+                Self::skip_dependents(&mut model, skipped, &mut warnings);
                 model.make_names_nice();
-                return Ok(model);
+                return Ok((model, warnings));
             } else if token.eq_text_ignore_ascii_case("IMPORTS") {
                 Self::read_imports(&mut iter)?
                     .into_iter()
                     .for_each(|i| model.imports.push(i));
-            } else if iter.peek_is_separator_eq(':') {
-                model.definitions.push(Self::read_definition(
-                    &mut iter,
-                    token.into_text_or_else(Error::unexpected_token)?,
-                )?);
+                continue;
+            }
+
+            let name = token.text().map(str::to_string);
+            let assignment = if iter.peek_is_separator_eq(':') {
+                token
+                    .into_text_or_else(Error::unexpected_token)
+                    .and_then(|name| Self::read_definition(&mut iter, name))
+                    .map(Assignment::Type)
             } else {
-                model.value_references.push(Self::read_value_reference(
-                    &mut iter,
-                    token.into_text_or_else(Error::unexpected_token)?,
-                )?);
+                token
+                    .into_text_or_else(Error::unexpected_token)
+                    .and_then(|name| Self::read_value_reference(&mut iter, name))
+                    .map(Assignment::Value)
+            };
+
+            if !lenient {
+                assignment?.push_to(&mut model);
+                continue;
+            }
+
+            // the assignment must end where the next one begins, otherwise some of its
+            // tokens were not understood
+            let next = boundaries
+                .iter()
+                .copied()
+                .find(|boundary| *boundary > position)
+                .unwrap_or(total);
+            let consumed = total - iter.len();
+            let assignment = assignment.and_then(|assignment| match iter.peek() {
+                Some(token) if consumed < next => Err(Error::unexpected_token(token.clone())),
+                _ => Ok(assignment),
+            });
+
+            match assignment {
+                Ok(assignment) => assignment.push_to(&mut model),
+                Err(e) => {
+                    warnings.push(e);
+                    skipped.extend(name);
+                    while total - iter.len() < next && iter.next().is_some() {}
+                }
             }
         }
         Err(Error::unexpected_end_of_stream())
     }
 
+    /// The indices of the tokens at which a top-level assignment (`Name ::=`, `name Type ::=`
+    /// or `Name { Parameter } ::=`) or the `END` of the module begins
+    fn assignment_boundaries(tokens: &[Token]) -> Vec<usize> {
+        let mut boundaries = Vec::new();
+        let mut depth = 0_usize;
+        for (index, token) in tokens.iter().enumerate() {
+            if token.eq_separator('{') || token.eq_separator('(') || token.eq_separator('[') {
+                depth += 1;
+            } else if token.eq_separator('}') || token.eq_separator(')') || token.eq_separator(']')
+            {
+                depth = depth.saturating_sub(1);
+            } else if depth == 0 && token.eq_text_ignore_ascii_case("END") {
+                boundaries.push(index);
+            } else if depth == 0
+                && token.eq_separator(':')
+                && tokens.get(index + 1).is_some_and(|t| t.eq_separator(':'))
+                && tokens.get(index + 2).is_some_and(|t| t.eq_separator('='))
+            {
+                boundaries.extend(Self::assignment_start(tokens, index));
+            }
+        }
+        boundaries.sort_unstable();
+        boundaries
+    }
+
+    /// The comments on the lines right above a type assignment or one of its fields or variants
+    /// document the first one on the line, a comment at the end of the line documents the last
+    /// one. Decorative comments without any letters or digits are skipped, the members of
+    /// inlined types and of definitions written on a single line are not documented.
+    fn read_docs(tokens: &[Token], comments: &[Token], boundaries: &[usize]) -> Docs {
+        let mut docs = Docs::default();
+        if comments.is_empty() {
+            return docs;
+        }
+
+        let mut documentable = Vec::new();
+        for (index, start) in boundaries.iter().copied().enumerate() {
+            let end = boundaries.get(index + 1).copied().unwrap_or(tokens.len());
+            let name = match tokens[start].text() {
+                Some(name)
+                    if name.starts_with(|c: char| c.is_ascii_uppercase())
+                        && !name.eq_ignore_ascii_case("END") =>
+                {
+                    name
+                }
+                _ => continue, // a value assignment or the end of the module
+            };
+            let line = tokens[start].location().line();
+            documentable.push((line, name, None));
+            if tokens[end - 1].location().line() == line {
+                continue; // a one-liner, its members share the comments of the definition
+            }
+
+            let mut depth = 0_usize;
+            let mut expects_member = false;
+            for token in &tokens[start + 1..end] {
+                if token.eq_separator('{') || token.eq_separator('(') || token.eq_separator('[') {
+                    depth += 1;
+                    expects_member = depth == 1 && token.eq_separator('{');
+                } else if token.eq_separator('}')
+                    || token.eq_separator(')')
+                    || token.eq_separator(']')
+                {
+                    depth = depth.saturating_sub(1);
+                    expects_member = false;
+                } else if depth == 1 && token.eq_separator(',') {
+                    expects_member = true;
+                } else {
+                    if let Some(member) = token.text().filter(|_| expects_member) {
+                        documentable.push((token.location().line(), name, Some(member)));
+                    }
+                    expects_member = false;
+                }
+            }
+        }
+
+        let code_lines = tokens
+            .iter()
+            .map(|token| token.location().line())
+            .collect::<HashSet<_>>();
+        let comment = |line: usize| {
+            comments
+                .iter()
+                .find(|comment| comment.location().line() == line)
+                .and_then(Token::comment)
+        };
+        let is_prose = |text: &&str| text.chars().any(char::is_alphanumeric);
+
+        for (index, (line, name, member)) in documentable.iter().enumerate() {
+            let mut lines = Vec::new();
+            if index == 0 || documentable[index - 1].0 != *line {
+                let mut above = line - 1;
+                while above > 0 && !code_lines.contains(&above) {
+                    match comment(above) {
+                        Some(text) => lines.extend(Some(text).filter(is_prose)),
+                        None => break,
+                    }
+                    above -= 1;
+                }
+                lines.reverse();
+            }
+            if documentable
+                .get(index + 1)
+                .is_none_or(|next| next.0 != *line)
+            {
+                lines.extend(comment(*line).filter(is_prose));
+            }
+            if !lines.is_empty() {
+                docs.insert(*name, member.map(str::to_string), lines.join("\n"));
+            }
+        }
+        docs
+    }
+
+    /// The index of the first token of the assignment, whose `::=` is at the given index
+    fn assignment_start(tokens: &[Token], assignment: usize) -> Option<usize> {
+        let mut start = assignment.checked_sub(1)?;
+        if tokens[start].eq_separator('}') {
+            let mut depth = 0_usize;
+            loop {
+                if tokens[start].eq_separator('}') {
+                    depth += 1;
+                } else if tokens[start].eq_separator('{') {
+                    depth -= 1;
+                    if depth == 0 {
+                        break;
+                    }
+                }
+                start = start.checked_sub(1)?;
+            }
+            start = start.checked_sub(1)?;
+        }
+        let starts_with = |index: usize, predicate: fn(&char) -> bool| {
+            tokens[index]
+                .text()
+                .and_then(|text| text.chars().next())
+                .is_some_and(|c| predicate(&c))
+        };
+        // a value assignment `name Type ::=`, unless `name` is the value of the previous one
+        if let Some(name) = start.checked_sub(1) {
+            if starts_with(start, char::is_ascii_uppercase)
+                && starts_with(name, char::is_ascii_lowercase)
+                && !name
+                    .checked_sub(1)
+                    .is_some_and(|previous| tokens[previous].eq_separator('='))
+            {
+                start = name;
+            }
+        }
+        Some(start)
+    }
+
+    /// Removes the definitions and value references that refer to skipped definitions, until
+    /// none is left
+    fn skip_dependents(model: &mut Self, mut skipped: Vec<String>, warnings: &mut Vec<Error>) {
+        let skipped_reference = |r#type: &Type<Unresolved>, skipped: &[String]| {
+            let mut names = Vec::new();
+            type_references(r#type, &mut names);
+            names
+                .into_iter()
+                .find(|name| skipped.iter().any(|s| s.eq(name)))
+                .map(str::to_string)
+        };
+        while let Some((index, reference)) =
+            model
+                .definitions
+                .iter()
+                .enumerate()
+                .find_map(|(index, Definition(_, asn))| {
+                    skipped_reference(&asn.r#type, &skipped).map(|r| (index, r))
+                })
+        {
+            let Definition(name, _) = model.definitions.remove(index);
+            warnings.push(Error::refers_to_skipped_definition(name.clone(), reference));
+            skipped.push(name);
+        }
+        model
+            .value_references
+            .retain(|vr| match skipped_reference(&vr.role.r#type, &skipped) {
+                None => true,
+                Some(reference) => {
+                    warnings.push(Error::refers_to_skipped_definition(
+                        vr.name.clone(),
+                        reference,
+                    ));
+                    false
+                }
+            });
+    }
+
     fn read_name(iter: &mut Peekable<IntoIter<Token>>) -> Result<String, Error> {
         iter.next()
             .and_then(|token| token.into_text())
@@ -120,6 +363,10 @@ impl Model<Asn<Unresolved>> {
                 } else if token.eq_text_ignore_ascii_case("FROM") {
                     import.from = iter.next_text_or_err()?;
                     import.from_oid = Self::maybe_read_oid(iter)?;
+                    // the selection of successors or descendants does not change the module
+                    if iter.next_is_text_and_eq_ignore_case("WITH") {
+                        iter.next_text_eq_any_ignore_case_or_err(&["SUCCESSORS", "DESCENDANTS"])?;
+                    }
                     imports.push(import);
                     import = Import::default();
                 }
@@ -221,34 +468,27 @@ impl Model<Asn<Unresolved>> {
         iter: &mut Peekable<T>,
         delimiter: char,
     ) -> Result<String, ErrorKind> {
-        iter.next_separator_eq_or_err(delimiter)?;
-        let token = iter.next_or_err()?;
-
-        let first_text = token.text().unwrap_or_default();
+        let opening = iter.next_if_separator_and_eq(delimiter)?;
         let mut string = String::from(delimiter);
-        string.push_str(first_text);
-        let mut prev_loc = Location::at(
-            token.location().line(),
-            token.location().column() + first_text.chars().count(),
-        );
+        let mut prev_loc = Location::at(opening.location().line(), opening.location().column() + 1);
 
         loop {
-            match iter.next_or_err()? {
+            let token = iter.next_or_err()?;
+            // whitespace is not tokenized but must be preserved within the literal
+            for _ in prev_loc.column()..token.location().column() {
+                string.push(' ');
+            }
+            match token {
                 t if t.eq_separator(delimiter) => break,
                 Token::Text(loc, str) => {
-                    for _ in prev_loc.column()..loc.column() {
-                        string.push(' ');
-                    }
                     string.push_str(&str);
                     prev_loc = Location::at(loc.line(), loc.column() + str.chars().count())
                 }
                 Token::Separator(loc, char) => {
-                    for _ in prev_loc.column()..loc.column() {
-                        string.push(' ');
-                    }
                     string.push(char);
                     prev_loc = Location::at(loc.line(), loc.column() + 1)
                 }
+                t @ Token::Comment(..) => return Err(ErrorKind::UnexpectedToken(t)),
             }
         }
 
@@ -297,11 +537,15 @@ impl Model<Asn<Unresolved>> {
             "integer" => Type::Integer(Integer::try_from(iter)?),
             "boolean" => Type::Boolean,
             "null" => Type::Null,
-            "utf8string" => Type::String(Self::maybe_read_size(iter)?, Charset::Utf8),
-            "ia5string" => Type::String(Self::maybe_read_size(iter)?, Charset::Ia5),
-            "numericstring" => Type::String(Self::maybe_read_size(iter)?, Charset::Numeric),
-            "printablestring" => Type::String(Self::maybe_read_size(iter)?, Charset::Printable),
-            "visiblestring" => Type::String(Self::maybe_read_size(iter)?, Charset::Visible),
+            "utf8string" => Self::read_string(iter, Charset::Utf8)?,
+            "ia5string" => Self::read_string(iter, Charset::Ia5)?,
+            "numericstring" => Self::read_string(iter, Charset::Numeric)?,
+            "printablestring" => Self::read_string(iter, Charset::Printable)?,
+            "visiblestring" | "iso646string" => Self::read_string(iter, Charset::Visible)?,
+            "teletexstring" | "t61string" => Self::read_string(iter, Charset::Teletex)?,
+            "videotexstring" => Self::read_string(iter, Charset::Videotex)?,
+            "graphicstring" => Self::read_string(iter, Charset::Graphic)?,
+            "objectdescriptor" => Self::read_string(iter, Charset::ObjectDescriptor)?,
             "octet" => {
                 iter.next_text_eq_ignore_case_or_err("STRING")?;
                 Type::OctetString(Self::maybe_read_size(iter)?)
@@ -336,17 +580,173 @@ impl Model<Asn<Unresolved>> {
         }
     }
 
+    /// Reads the constraints of a restricted character string type. The size and the permitted
+    /// alphabet (ITU-T X.680 | ISO/IEC 8824-1, 51.5 and 51.7) can be given in any order, either
+    /// in one constraint combined by `^` or `INTERSECTION`, or as serial constraints.
+    fn read_string<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+        charset: Charset,
+    ) -> Result<Type<Unresolved>, Error> {
+        if iter.peek_is_text_eq_ignore_case("SIZE") {
+            return Ok(Type::String(Size::try_from(iter)?, charset, None));
+        }
+
+        let mut size = Size::Any;
+        let mut alphabet = None;
+
+        while iter.next_is_separator_and_eq('(') {
+            loop {
+                if iter.peek_is_text_eq_ignore_case("SIZE") {
+                    size = Size::try_from(&mut *iter)?;
+                } else if iter.next_is_text_and_eq_ignore_case("FROM") {
+                    alphabet = Self::read_permitted_alphabet(iter)?
+                        .map(|alphabet| alphabet.restricted_to(charset));
+                } else if !Self::skip_not_per_visible_constraint(iter)? {
+                    return Err(Error::unexpected_token(iter.next_or_err()?));
+                }
+
+                if !iter.next_is_text_and_eq_ignore_case("^")
+                    && !iter.next_is_text_and_eq_ignore_case("INTERSECTION")
+                {
+                    iter.next_separator_eq_or_err(')')?;
+                    break;
+                }
+            }
+        }
+
+        Ok(Type::String(size, charset, alphabet))
+    }
+
+    /// Reads the content of a `FROM` constraint, such as `("A".."Z" | "0".."9" | "-_")`.
+    /// Extensible permitted alphabet constraints are not PER-visible (ITU-T X.691 |
+    /// ISO/IEC 8825-2:2015, chapter 9.3.10) and are therefore skipped.
+    fn read_permitted_alphabet<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+    ) -> Result<Option<PermittedAlphabet>, Error> {
+        iter.next_separator_eq_or_err('(')?;
+        let mut chars = Vec::new();
+        let mut extensible = false;
+
+        loop {
+            let location = iter.peek_or_err()?.location();
+            let first = Self::read_string_literal(iter, '"')?;
+            if iter.next_is_separator_and_eq('.') {
+                iter.next_separator_eq_or_err('.')?;
+                let last = Self::read_string_literal(iter, '"')?;
+                match (Self::single_char(&first), Self::single_char(&last)) {
+                    (Some(first), Some(last)) if first <= last => chars.extend(first..=last),
+                    _ => {
+                        return Err(Error::invalid_range_value(Token::Text(
+                            location,
+                            format!("{}..{}", first, last),
+                        )))
+                    }
+                }
+            } else {
+                chars.extend(first[1..first.len() - 1].chars());
+            }
+
+            if iter.next_is_separator_and_eq(',') {
+                iter.next_separator_eq_or_err('.')?;
+                iter.next_separator_eq_or_err('.')?;
+                iter.next_separator_eq_or_err('.')?;
+                extensible = true;
+            }
+
+            if !iter.next_is_text_and_eq_ignore_case("|")
+                && !iter.next_is_text_and_eq_ignore_case("UNION")
+            {
+                iter.next_separator_eq_or_err(')')?;
+                break;
+            }
+        }
+
+        Ok(Some(PermittedAlphabet::new(chars)).filter(|_| !extensible))
+    }
+
+    /// The character of a quoted string literal that consists of exactly one character
+    fn single_char(literal: &str) -> Option<char> {
+        let mut chars = literal[1..literal.len() - 1].chars();
+        chars.next().filter(|_| chars.next().is_none())
+    }
+
+    /// Reads the size constraint, ignoring any constraint that is not PER-visible
     pub(crate) fn maybe_read_size<T: Iterator<Item = Token>>(
         iter: &mut Peekable<T>,
     ) -> Result<Size<<Unresolved as ResolveState>::SizeType>, Error> {
-        if iter.next_is_separator_and_eq('(') {
-            let result = Size::try_from(&mut *iter)?;
-            iter.next_separator_eq_or_err(')')?;
-            Ok(result)
-        } else if iter.peek_is_text_eq_ignore_case("SIZE") {
-            Size::try_from(iter)
-        } else {
-            Ok(Size::Any)
+        if iter.peek_is_text_eq_ignore_case("SIZE") {
+            return Size::try_from(iter);
+        }
+
+        let mut size = Size::Any;
+
+        while iter.next_is_separator_and_eq('(') {
+            loop {
+                if iter.peek_is_text_eq_ignore_case("SIZE") {
+                    size = Size::try_from(&mut *iter)?;
+                } else if !Self::skip_not_per_visible_constraint(iter)? {
+                    return Err(Error::unexpected_token(iter.next_or_err()?));
+                }
+
+                if !iter.next_is_text_and_eq_ignore_case("^")
+                    && !iter.next_is_text_and_eq_ignore_case("INTERSECTION")
+                {
+                    iter.next_separator_eq_or_err(')')?;
+                    break;
+                }
+            }
+        }
+
+        Ok(size)
+    }
+
+    /// Skips a constraint that is not PER-visible (ITU-T X.691 | ISO/IEC 8825-2:2015, chapter
+    /// 9.3): table and component relation constraints, user-defined constraints, contents
+    /// constraints, pattern constraints and inner subtyping. Such constraints must not affect
+    /// the encoding and are therefore not part of the model. Consumes everything up to, but
+    /// excluding, the next `)`, `,`, `|`, `^`, `UNION` or `INTERSECTION` on the same level.
+    ///
+    /// Returns `false` without consuming anything if the next constraint is PER-visible.
+    pub(crate) fn skip_not_per_visible_constraint<T: Iterator<Item = Token>>(
+        iter: &mut Peekable<T>,
+    ) -> Result<bool, Error> {
+        const NOT_PER_VISIBLE: [&str; 6] = [
+            "CONSTRAINED",
+            "CONTAINING",
+            "ENCODED",
+            "PATTERN",
+            "WITH",
+            "SETTINGS",
+        ];
+
+        if !iter.peek_is_separator_eq('{')
+            && !NOT_PER_VISIBLE
+                .iter()
+                .any(|keyword| iter.peek_is_text_eq_ignore_case(keyword))
+        {
+            return Ok(false);
+        }
+
+        let mut depth = 0_usize;
+        loop {
+            let token = iter.peek_or_err()?;
+            if depth == 0
+                && (token.eq_separator(')')
+                    || token.eq_separator(',')
+                    || ["|", "^", "UNION", "INTERSECTION"]
+                        .iter()
+                        .any(|t| token.eq_text(t)))
+            {
+                return Ok(true);
+            }
+            let token = iter.next_or_err()?;
+            if token.eq_separator('(') || token.eq_separator('{') {
+                depth += 1;
+            } else if token.eq_separator(')') || token.eq_separator('}') {
+                depth = depth
+                    .checked_sub(1)
+                    .ok_or_else(|| Error::unexpected_token(token))?;
+            }
         }
     }
 
@@ -440,6 +840,12 @@ impl Model<Asn<Resolved>> {
     pub fn to_rust_keep_names_with_scope(&self, scope: &[&Self]) -> Model<Rust> {
         Model::convert_asn_to_rust(self, scope, false)
     }
+
+    /// Like [`Model::to_rust_with_scope`], but names the types of inline definitions with the
+    /// given strategy
+    pub fn to_rust_with_naming(&self, scope: &[&Self], inline_naming: InlineNaming) -> Model<Rust> {
+        Model::convert_asn_to_rust_with_naming(self, scope, true, inline_naming)
+    }
 }
 
 impl<RS: ResolveState> Model<Asn<RS>> {
@@ -524,3 +930,17 @@ impl Field<Asn<Unresolved>> {
         })
     }
 }
+
+enum Assignment {
+    Type(Definition<Asn<Unresolved>>),
+    Value(ValueReference<Asn<Unresolved>>),
+}
+
+impl Assignment {
+    fn push_to(self, model: &mut Model<Asn<Unresolved>>) {
+        match self {
+            Assignment::Type(definition) => model.definitions.push(definition),
+            Assignment::Value(value_reference) => model.value_references.push(value_reference),
+        }
+    }
+}
@@ -1,7 +1,10 @@
 use crate::asn::{Asn, Type};
-use crate::model::{Definition, LiteralValue, Model, Target, ValueReference};
+use crate::model::{Definition, Import, LiteralValue, Model, Target, ValueReference};
 use crate::resolve::{Error, LitOrRef, Resolved, Resolver, Unresolved};
+use crate::rust::InlineNaming;
+use crate::validate::Issue;
 
+/// Resolves models that are spread over several modules (files) and `IMPORT` from each other
 #[derive(Default)]
 pub struct MultiModuleResolver {
     models: Vec<Model<Asn<Unresolved>>>,
@@ -12,9 +15,17 @@ impl MultiModuleResolver {
         self.models.push(model);
     }
 
+    /// Checks that every import and type reference can be resolved within the pushed models and
+    /// resolves them. The models are returned in dependency order: a model is placed after the
+    /// models it imports from, unless they import from each other.
     pub fn try_resolve_all(&self) -> Result<Vec<Model<Asn<Resolved>>>, Error> {
-        self.models
-            .iter()
+        for model in &self.models {
+            self.check_imports(model)?;
+            Self::check_type_references(model)?;
+        }
+
+        self.dependency_order()
+            .into_iter()
             .map(|model| {
                 ResolveScope {
                     model,
@@ -24,6 +35,156 @@ impl MultiModuleResolver {
             })
             .collect::<_>()
     }
+
+    /// The names of the given modules and of all modules that import from them, directly or
+    /// through other modules, in the order they were pushed. These are the modules whose
+    /// generated code is affected by changes to the given modules.
+    pub fn dependents(&self, modules: &[&str]) -> Vec<String> {
+        let mut affected = self
+            .models
+            .iter()
+            .map(|model| modules.contains(&model.name.as_str()))
+            .collect::<Vec<_>>();
+        let mut changed = true;
+        while changed {
+            changed = false;
+            for (index, model) in self.models.iter().enumerate() {
+                if !affected[index]
+                    && model.imports.iter().any(|import| {
+                        self.models
+                            .iter()
+                            .enumerate()
+                            .any(|(other, m)| affected[other] && provides(m, import))
+                    })
+                {
+                    affected[index] = true;
+                    changed = true;
+                }
+            }
+        }
+        self.models
+            .iter()
+            .zip(affected)
+            .filter(|(_, affected)| *affected)
+            .map(|(model, _)| model.name.clone())
+            .collect()
+    }
+
+    /// Validates each pushed model, see [`Model::validate`]
+    pub fn validate(&self) -> Vec<Issue> {
+        self.validate_with_naming(InlineNaming::default())
+    }
+
+    /// Validates each pushed model, see [`Model::validate_with_naming`]
+    pub fn validate_with_naming(&self, inline_naming: InlineNaming) -> Vec<Issue> {
+        let scope = self.models.iter().collect::<Vec<_>>();
+        self.models
+            .iter()
+            .flat_map(|model| model.validate_with_naming(&scope, inline_naming))
+            .collect()
+    }
+
+    fn check_imports(&self, model: &Model<Asn<Unresolved>>) -> Result<(), Error> {
+        for import in &model.imports {
+            let from = self
+                .models
+                .iter()
+                .find(|m| provides(m, import))
+                .ok_or_else(|| Error::UnknownModule(import.from.clone(), model.name.clone()))?;
+
+            for what in &import.what {
+                let known = from.definitions.iter().any(|d| d.name().eq(what))
+                    || from.value_references.iter().any(|vr| vr.name.eq(what))
+                    || from.imports.iter().any(|i| i.what.contains(what));
+                if !known {
+                    return Err(Error::UnknownImport(what.clone(), from.name.clone()));
+                }
+            }
+        }
+        Ok(())
+    }
+
+    fn check_type_references(model: &Model<Asn<Unresolved>>) -> Result<(), Error> {
+        let mut names = Vec::new();
+        for Definition(_, asn) in &model.definitions {
+            type_references(&asn.r#type, &mut names);
+        }
+        for vr in &model.value_references {
+            type_references(&vr.role.r#type, &mut names);
+        }
+
+        match names.into_iter().find(|name| {
+            !model.definitions.iter().any(|d| d.name().eq(*name))
+                && !model
+                    .imports
+                    .iter()
+                    .any(|i| i.what.iter().any(|w| w.eq(name)))
+        }) {
+            Some(name) => Err(Error::UnknownTypeReference(
+                name.to_string(),
+                model.name.clone(),
+            )),
+            None => Ok(()),
+        }
+    }
+
+    fn dependency_order(&self) -> Vec<&Model<Asn<Unresolved>>> {
+        let mut visited = vec![false; self.models.len()];
+        let mut ordered = Vec::with_capacity(self.models.len());
+        for index in 0..self.models.len() {
+            self.visit(index, &mut visited, &mut ordered);
+        }
+        ordered
+    }
+
+    fn visit<'a>(
+        &'a self,
+        index: usize,
+        visited: &mut [bool],
+        ordered: &mut Vec<&'a Model<Asn<Unresolved>>>,
+    ) {
+        if visited[index] {
+            return;
+        }
+        visited[index] = true;
+
+        let model = &self.models[index];
+        for import in &model.imports {
+            if let Some(dependency) = self.models.iter().position(|m| provides(m, import)) {
+                self.visit(dependency, visited, ordered);
+            }
+        }
+        ordered.push(model);
+    }
+}
+
+/// Whether the import refers to the given model, preferably by its object identifier
+fn provides(model: &Model<Asn<Unresolved>>, import: &Import) -> bool {
+    (model.oid.is_some() && model.oid.eq(&import.from_oid)) || model.name.eq(&import.from)
+}
+
+pub(crate) fn type_references<'a>(r#type: &'a Type<Unresolved>, names: &mut Vec<&'a str>) {
+    match r#type {
+        Type::Optional(inner)
+        | Type::Default(inner, _)
+        | Type::SequenceOf(inner, _)
+        | Type::SetOf(inner, _) => type_references(inner, names),
+        Type::Sequence(components) | Type::Set(components) => components
+            .fields
+            .iter()
+            .for_each(|field| type_references(&field.role.r#type, names)),
+        Type::Choice(choice) => choice
+            .variants()
+            .for_each(|variant| type_references(&variant.r#type, names)),
+        Type::TypeReference(name, _) => names.push(name),
+        Type::Boolean
+        | Type::Integer(_)
+        | Type::String(..)
+        | Type::OctetString(_)
+        | Type::BitString(_)
+        | Type::Null
+        | Type::Enumerated(_) => {}
+    }
 }
 
 pub struct ResolveScope<'a> {
@@ -48,6 +209,7 @@ impl<'a> ResolveScope<'a> {
             imports: self.model.imports.clone(),
             definitions: Vec::with_capacity(self.model.definitions.len()),
             value_references: Vec::with_capacity(self.model.value_references.len()),
+            docs: self.model.docs.clone(),
         };
 
         // copy over all value references
@@ -73,11 +235,7 @@ impl<'a> ResolveScope<'a> {
             .imports
             .iter()
             .find(|i| i.what.iter().any(|what| what.eq(item)))
-            .and_then(|import| {
-                self.scope.iter().find(|m| {
-                    (m.oid.is_some() && m.oid.eq(&import.from_oid)) || m.name.eq(&import.from)
-                })
-            })
+            .and_then(|import| self.scope.iter().find(|m| provides(m, import)))
     }
 
     fn value_reference(
@@ -131,13 +289,13 @@ impl Resolver<usize> for ResolveScope<'_> {
     }
 }
 
-impl Resolver<i64> for ResolveScope<'_> {
-    fn resolve(&self, lor: &LitOrRef<i64>) -> Result<i64, Error> {
+impl Resolver<i128> for ResolveScope<'_> {
+    fn resolve(&self, lor: &LitOrRef<i128>) -> Result<i128, Error> {
         match lor {
             LitOrRef::Lit(lit) => Ok(*lit),
             LitOrRef::Ref(name) => match self.value_reference(name).map(|vr| vr.value.to_integer())
             {
-                Some(Some(value)) => Ok(value),
+                Some(Some(value)) => Ok(i128::from(value)),
                 Some(None) => Err(Error::FailedToParseLiteral(format!("name: {}", name))),
                 None => Err(Error::FailedToResolveReference(name.clone())),
             },
@@ -168,3 +326,117 @@ impl Resolver<Type<Unresolved>> for ResolveScope<'_> {
         }
     }
 }
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn resolver(asn: &[&str]) -> MultiModuleResolver {
+        let mut resolver = MultiModuleResolver::default();
+        for asn in asn {
+            resolver.push(Model::try_from(Tokenizer.parse(asn)).expect("Failed to parse module"));
+        }
+        resolver
+    }
+
+    const TOP: &str = r"Top DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+        IMPORTS Middle, max-len FROM Middle;
+        Top ::= SEQUENCE (SIZE(1..max-len)) OF Middle
+        END";
+
+    const MIDDLE: &str = r"Middle DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+        IMPORTS Bottom, max-len FROM Bottom;
+        Middle ::= SEQUENCE { bottom Bottom }
+        END";
+
+    const BOTTOM: &str = r"Bottom DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+        max-len INTEGER ::= 4
+        Bottom ::= INTEGER (0..255)
+        END";
+
+    #[test]
+    fn test_dependents() {
+        let resolver = resolver(&[TOP, MIDDLE, BOTTOM]);
+        assert_eq!(
+            vec!["Top", "Middle", "Bottom"],
+            resolver.dependents(&["Bottom"])
+        );
+        assert_eq!(vec!["Top", "Middle"], resolver.dependents(&["Middle"]));
+        assert_eq!(vec!["Top"], resolver.dependents(&["Top", "Unknown"]));
+        assert!(resolver.dependents(&[]).is_empty());
+    }
+
+    #[test]
+    fn test_resolve_all_in_dependency_order() {
+        let models = resolver(&[TOP, MIDDLE, BOTTOM])
+            .try_resolve_all()
+            .expect("Failed to resolve");
+        assert_eq!(
+            vec!["Bottom", "Middle", "Top"],
+            models.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            Type::SequenceOf(
+                Box::new(Type::TypeReference("Middle".to_string(), None)),
+                crate::asn::Size::Range(1, 4, false)
+            ),
+            models[2].definitions[0].1.r#type
+        );
+    }
+
+    #[test]
+    fn test_mutual_imports_keep_their_order() {
+        let models = resolver(&[
+            r"First DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS Second FROM Other;
+            First ::= SEQUENCE { second Second OPTIONAL }
+            END",
+            r"Other DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS First FROM First;
+            Second ::= SEQUENCE { first First OPTIONAL }
+            END",
+        ])
+        .try_resolve_all()
+        .expect("Failed to resolve");
+        assert_eq!(
+            vec!["Other", "First"],
+            models.iter().map(|m| m.name.as_str()).collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    fn test_unknown_module() {
+        assert_eq!(
+            Error::UnknownModule("Bottom".to_string(), "Middle".to_string()),
+            resolver(&[TOP, MIDDLE]).try_resolve_all().unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_unknown_import() {
+        assert_eq!(
+            Error::UnknownImport("Bottom".to_string(), "Bottom".to_string()),
+            resolver(&[
+                MIDDLE,
+                r"Bottom DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                max-len INTEGER ::= 4
+                END"
+            ])
+            .try_resolve_all()
+            .unwrap_err()
+        );
+    }
+
+    #[test]
+    fn test_unknown_type_reference() {
+        assert_eq!(
+            Error::UnknownTypeReference("Missing".to_string(), "Bottom".to_string()),
+            resolver(&[r"Bottom DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Bottom ::= CHOICE { missing Missing }
+                END"])
+            .try_resolve_all()
+            .unwrap_err()
+        );
+    }
+}
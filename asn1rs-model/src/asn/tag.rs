@@ -53,6 +53,8 @@ impl Tag {
     pub const DEFAULT_SET: Tag = Tag::Universal(17);
     pub const DEFAULT_SET_OF: Tag = Tag::Universal(17);
 
+    /// ITU-T Rec. X.680, 44
+    pub const DEFAULT_OBJECT_DESCRIPTOR: Tag = Tag::Universal(7);
     /// ITU-T Rec. X.680, 41
     pub const DEFAULT_NUMERIC_STRING: Tag = Tag::Universal(18);
     /// ITU-T Rec. X.680, 41
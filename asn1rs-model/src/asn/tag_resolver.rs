@@ -1,22 +1,47 @@
 use crate::asn::Charset;
 use crate::asn::{Asn, Tag, TagProperty, Type};
 use crate::model::{Definition, Model};
+use crate::resolve::{ResolveState, Resolved};
 
-pub struct TagResolver<'a> {
-    model: &'a Model<Asn>,
-    scope: &'a [&'a Model<Asn>],
+pub struct TagResolver<'a, RS: ResolveState = Resolved> {
+    model: &'a Model<Asn<RS>>,
+    scope: &'a [&'a Model<Asn<RS>>],
+    resolving: Option<&'a Resolving<'a>>,
 }
 
-impl TagResolver<'_> {
-    pub const fn new<'a>(model: &'a Model<Asn>, scope: &'a [&'a Model<Asn>]) -> TagResolver<'a> {
-        TagResolver { model, scope }
+/// The chain of definitions whose tags are currently being resolved, to detect recursive
+/// types like an untagged CHOICE referring to itself
+struct Resolving<'a> {
+    model: &'a str,
+    name: &'a str,
+    outer: Option<&'a Resolving<'a>>,
+}
+
+impl Resolving<'_> {
+    fn contains(&self, model: &str, name: &str) -> bool {
+        (self.model == model && self.name == name)
+            || self.outer.is_some_and(|outer| outer.contains(model, name))
     }
+}
 
-    pub fn resolve_default(ty: &Type) -> Option<Tag> {
-        let model = Model::<Asn>::default();
+impl<RS: ResolveState> TagResolver<'_, RS> {
+    pub const fn new<'a>(
+        model: &'a Model<Asn<RS>>,
+        scope: &'a [&'a Model<Asn<RS>>],
+    ) -> TagResolver<'a, RS> {
+        TagResolver {
+            model,
+            scope,
+            resolving: None,
+        }
+    }
+
+    pub fn resolve_default(ty: &Type<RS>) -> Option<Tag> {
+        let model = Model::<Asn<RS>>::default();
         TagResolver {
             model: &model,
             scope: &[],
+            resolving: None,
         }
         .resolve_type_tag(ty)
     }
@@ -34,19 +59,43 @@ impl TagResolver<'_> {
                 TagResolver {
                     model,
                     scope: self.scope,
+                    resolving: self.resolving,
                 }
                 .resolve_tag(ty)
             })
             .or_else(|| {
                 self.model.definitions.iter().find(|d| d.0.eq(ty)).and_then(
-                    |Definition(_name, asn)| asn.tag.or_else(|| self.resolve_type_tag(&asn.r#type)),
+                    |Definition(_name, asn)| {
+                        asn.tag.or_else(|| {
+                            if self.is_resolving(ty) {
+                                // a recursive type without a tag of its own has no tag
+                                return None;
+                            }
+                            TagResolver {
+                                model: self.model,
+                                scope: self.scope,
+                                resolving: Some(&Resolving {
+                                    model: &self.model.name,
+                                    name: ty,
+                                    outer: self.resolving,
+                                }),
+                            }
+                            .resolve_type_tag(&asn.r#type)
+                        })
+                    },
                 )
             })
     }
 
+    /// Whether the tag of the definition with the given name is currently being resolved
+    fn is_resolving(&self, ty: &str) -> bool {
+        self.resolving
+            .is_some_and(|r| r.contains(&self.model.name, ty))
+    }
+
     /// ITU-T X.680 | ISO/IEC 8824-1, 8.6
     /// ITU-T X.680 | ISO/IEC 8824-1, 41, table 8
-    pub fn resolve_no_default(&self, ty: &Type) -> Option<Tag> {
+    pub fn resolve_no_default(&self, ty: &Type<RS>) -> Option<Tag> {
         let default = Self::resolve_default(ty);
         let resolved = self.resolve_type_tag(ty);
         resolved.filter(|r| default.ne(&Some(*r)))
@@ -54,18 +103,22 @@ impl TagResolver<'_> {
 
     /// ITU-T X.680 | ISO/IEC 8824-1, 8.6
     /// ITU-T X.680 | ISO/IEC 8824-1, 41, table 8
-    pub fn resolve_type_tag(&self, ty: &Type) -> Option<Tag> {
+    pub fn resolve_type_tag(&self, ty: &Type<RS>) -> Option<Tag> {
         match ty {
             Type::Boolean => Some(Tag::DEFAULT_BOOLEAN),
             Type::Integer(_) => Some(Tag::DEFAULT_INTEGER),
             Type::BitString(_) => Some(Tag::DEFAULT_BIT_STRING),
             Type::OctetString(_) => Some(Tag::DEFAULT_OCTET_STRING),
             Type::Enumerated(_) => Some(Tag::DEFAULT_ENUMERATED),
-            Type::String(_, Charset::Numeric) => Some(Tag::DEFAULT_NUMERIC_STRING),
-            Type::String(_, Charset::Printable) => Some(Tag::DEFAULT_PRINTABLE_STRING),
-            Type::String(_, Charset::Visible) => Some(Tag::DEFAULT_VISIBLE_STRING),
-            Type::String(_, Charset::Utf8) => Some(Tag::DEFAULT_UTF8_STRING),
-            Type::String(_, Charset::Ia5) => Some(Tag::DEFAULT_IA5_STRING),
+            Type::String(_, Charset::Numeric, _) => Some(Tag::DEFAULT_NUMERIC_STRING),
+            Type::String(_, Charset::Printable, _) => Some(Tag::DEFAULT_PRINTABLE_STRING),
+            Type::String(_, Charset::Visible, _) => Some(Tag::DEFAULT_VISIBLE_STRING),
+            Type::String(_, Charset::Utf8, _) => Some(Tag::DEFAULT_UTF8_STRING),
+            Type::String(_, Charset::Ia5, _) => Some(Tag::DEFAULT_IA5_STRING),
+            Type::String(_, Charset::Teletex, _) => Some(Tag::DEFAULT_TELETEXT_STRING),
+            Type::String(_, Charset::Videotex, _) => Some(Tag::DEFAULT_VIDEOTEXT_STRING),
+            Type::String(_, Charset::Graphic, _) => Some(Tag::DEFAULT_GRAPHIC_STRING),
+            Type::String(_, Charset::ObjectDescriptor, _) => Some(Tag::DEFAULT_OBJECT_DESCRIPTOR),
             Type::Null => Some(Tag::DEFAULT_NULL),
             Type::Optional(inner) => self.resolve_type_tag(inner),
             Type::Default(inner, ..) => self.resolve_type_tag(inner),
@@ -73,6 +126,9 @@ impl TagResolver<'_> {
             Type::SequenceOf(_, _) => Some(Tag::DEFAULT_SEQUENCE_OF),
             Type::Set(_) => Some(Tag::DEFAULT_SET),
             Type::SetOf(_, _) => Some(Tag::DEFAULT_SET_OF),
+            // an empty CHOICE has no value and therefore never an encoding, the tag reserved
+            // for the encoding rules (ITU-T X.680, 8.6) is only a placeholder
+            Type::Choice(choice) if choice.is_empty() => Some(Tag::Universal(0)),
             Type::Choice(choice) => {
                 let mut tags = choice
                     .variants()
@@ -82,6 +138,11 @@ impl TagResolver<'_> {
                             .map(|extension_after| extension_after + 1)
                             .unwrap_or_else(|| choice.len()),
                     )
+                    // a variant referring back to the CHOICE adds none but the tags of the others
+                    .filter(|v| {
+                        v.tag().is_some()
+                            || !matches!(v.r#type(), Type::TypeReference(name, None) if self.is_resolving(name))
+                    })
                     .map(|v| v.tag().or_else(|| self.resolve_type_tag(v.r#type())))
                     .collect::<Option<Vec<Tag>>>()?;
                 tags.sort();
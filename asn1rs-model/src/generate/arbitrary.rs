@@ -0,0 +1,287 @@
+use crate::asn::{Range, Size};
+use crate::generate::rust::GeneratorSupplement;
+use crate::generate::RustCodeGenerator;
+use crate::model::Definition;
+use crate::rust::{Rust, RustType};
+use codegen::Block;
+use codegen::Scope;
+use std::fmt::Display;
+
+const CRATE_ARBITRARY_PREFIX: &str = "::asn1rs::arbitrary::";
+
+/// Implements `::asn1rs::arbitrary::Arbitrary` for each generated type. The generated values
+/// respect the constraints of the ASN.1 definition (value ranges, sizes, charsets, consistent
+/// extension fields) and can therefore be used to test the round-trip of any codec, see
+/// `asn1rs::arbitrary::assert_uper_round_trip`.
+#[derive(Debug, Default)]
+pub struct ArbitraryImplGenerator;
+
+impl GeneratorSupplement<Rust> for ArbitraryImplGenerator {
+    fn add_imports(&self, _scope: &mut Scope) {}
+
+    fn impl_supplement(&self, scope: &mut Scope, Definition(name, rust): &Definition<Rust>) {
+        let function = scope
+            .new_impl(name)
+            .generic("'a")
+            .impl_trait(format!("{}Arbitrary<'a>", CRATE_ARBITRARY_PREFIX))
+            .new_fn("arbitrary")
+            .arg(
+                "u",
+                format!("&mut {}Unstructured<'a>", CRATE_ARBITRARY_PREFIX),
+            )
+            .ret(format!("{}Result<Self>", CRATE_ARBITRARY_PREFIX));
+
+        match rust {
+            Rust::Struct {
+                fields,
+                extension_after,
+                protobuf_unknown_fields,
+                ..
+            } => {
+                if extension_after.is_some() {
+                    // UPER requires all or none of the optional extension fields to be present
+                    function.line("let extensions = u.arbitrary::<bool>()?;");
+                }
+                let mut block = Block::new("Ok(Self");
+                for (index, field) in fields.iter().enumerate() {
+                    let is_extension = extension_after.is_some_and(|after| index > after);
+                    block.line(format!(
+                        "{}: {},",
+                        RustCodeGenerator::rust_field_name(field.name(), true),
+                        match field.r#type() {
+                            RustType::Option(inner) if is_extension => format!(
+                                "if extensions {{ Some({}) }} else {{ None }}",
+                                Self::value_expr(inner)
+                            ),
+                            other => Self::value_expr(other),
+                        }
+                    ));
+                }
+                if *protobuf_unknown_fields {
+                    block.line("unknown_fields: Default::default(),");
+                }
+                block.after(")");
+                function.push_block(block);
+            }
+            Rust::Enum(_) => {
+                function.line("Ok(*u.choose(&Self::variants())?)");
+            }
+            Rust::DataEnum(enumeration) => {
+                let mut block = Block::new(&format!(
+                    "Ok(match u.int_in_range(0..={})?",
+                    enumeration.len().saturating_sub(1)
+                ));
+                for (index, variant) in enumeration.variants().enumerate() {
+                    block.line(format!(
+                        "{} => Self::{}({}),",
+                        index,
+                        RustCodeGenerator::rust_variant_name(variant.name()),
+                        Self::value_expr(variant.r#type())
+                    ));
+                }
+                block.line("_ => unreachable!(),");
+                block.after(")");
+                function.push_block(block);
+            }
+            Rust::TupleStruct { r#type, .. } => {
+                function.line(format!("Ok(Self({}))", Self::value_expr(r#type)));
+            }
+        }
+    }
+}
+
+impl ArbitraryImplGenerator {
+    fn value_expr(rust: &RustType) -> String {
+        match rust {
+            RustType::Bool => "u.arbitrary::<bool>()?".to_string(),
+            RustType::I8(range) => Self::int_in_range(range),
+            RustType::U8(range) => Self::int_in_range(range),
+            RustType::I16(range) => Self::int_in_range(range),
+            RustType::U16(range) => Self::int_in_range(range),
+            RustType::I32(range) => Self::int_in_range(range),
+            RustType::U32(range) => Self::int_in_range(range),
+            RustType::I64(range) => Self::int_in_range(range),
+            RustType::U64(range) => Self::int_in_range(&Range(
+                range.min().unwrap_or_default(),
+                range.max().unwrap_or(i64::MAX as u64),
+                range.extensible(),
+            )),
+            RustType::I128(range) => Self::int_in_range(&Range(
+                range.min().unwrap_or(i128::MIN),
+                range.max().unwrap_or(i128::MAX),
+                range.extensible(),
+            )),
+            RustType::U128(range) => Self::int_in_range(&Range(
+                range.min().unwrap_or_default(),
+                range.max().unwrap_or(i128::MAX as u128),
+                range.extensible(),
+            )),
+            RustType::String(size, _, Some(alphabet)) => format!(
+                "{}string_from(u, {}, {:?})?",
+                CRATE_ARBITRARY_PREFIX,
+                Self::size_args(size),
+                alphabet.as_string()
+            ),
+            RustType::String(size, charset, None) => format!(
+                "{}string(u, {}, {}Charset::{:?})?",
+                CRATE_ARBITRARY_PREFIX,
+                Self::size_args(size),
+                CRATE_ARBITRARY_PREFIX,
+                charset
+            ),
+            RustType::VecU8(size) => format!(
+                "{}octets(u, {})?",
+                CRATE_ARBITRARY_PREFIX,
+                Self::size_args(size)
+            ),
+            RustType::Uuid => "Uuid::from_bytes(u.arbitrary()?)".to_string(),
+            RustType::BitVec(size) => format!(
+                "{}bit_vec(u, {})?",
+                CRATE_ARBITRARY_PREFIX,
+                Self::size_args(size)
+            ),
+            RustType::Vec(inner, size, _ordering) => {
+                let inner = Self::value_expr(inner);
+                format!(
+                    "{}vec(u, {}, |u| {})?",
+                    CRATE_ARBITRARY_PREFIX,
+                    Self::size_args(size),
+                    match inner.strip_suffix('?') {
+                        Some(fallible) => fallible.to_string(),
+                        None => format!("Ok({})", inner),
+                    }
+                )
+            }
+            RustType::Null => "Null".to_string(),
+            RustType::Option(inner) => format!(
+                "if u.arbitrary::<bool>()? {{ Some({}) }} else {{ None }}",
+                Self::value_expr(inner)
+            ),
+            RustType::Default(inner, ..) => Self::value_expr(inner),
+            RustType::Box(inner) => format!("Box::new({})", Self::value_expr(inner)),
+            RustType::Complex(name, _tag) => format!("u.arbitrary::<{}>()?", name),
+        }
+    }
+
+    fn int_in_range<T: Display>(Range(min, max, _extensible): &Range<T>) -> String {
+        format!("u.int_in_range({}..={})?", min, max)
+    }
+
+    fn size_args(size: &Size) -> String {
+        format!(
+            "{}, {}",
+            size.min().copied().unwrap_or_default(),
+            size.max()
+                .map(|max| format!("Some({})", max))
+                .unwrap_or_else(|| "None".to_string())
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::model::Model;
+    use crate::parse::Tokenizer;
+
+    fn generate(asn: &str) -> String {
+        let model = Model::try_from(Tokenizer::default().parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust();
+        RustCodeGenerator::from(model)
+            .to_string_with_generators(&[&ArbitraryImplGenerator])
+            .into_iter()
+            .next()
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    pub fn test_constraints_are_respected() {
+        let content = generate(
+            r#"ArbitraryTest DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE {
+                number INTEGER (-5..300),
+                name PrintableString (SIZE(1..8)),
+                values SEQUENCE SIZE(2) OF INTEGER (0..7),
+                flag BOOLEAN OPTIONAL,
+                ...,
+                extra UTF8String OPTIONAL
+            }
+
+            END
+        "#,
+        );
+
+        assert!(content.contains("impl<'a> ::asn1rs::arbitrary::Arbitrary<'a> for MyStruct {"));
+        assert!(content.contains("let extensions = u.arbitrary::<bool>()?;"));
+        assert!(content.contains("number: u.int_in_range(-5..=300)?,"));
+        assert!(content.contains(
+            "name: ::asn1rs::arbitrary::string(u, 1, Some(8), ::asn1rs::arbitrary::Charset::Printable)?,"
+        ));
+        assert!(content.contains(
+            "values: ::asn1rs::arbitrary::vec(u, 2, Some(2), |u| u.int_in_range(0..=7))?,"
+        ));
+        assert!(content.contains(
+            "flag: if u.arbitrary::<bool>()? { Some(u.arbitrary::<bool>()?) } else { None },"
+        ));
+        assert!(content.contains(
+            "extra: if extensions { Some(::asn1rs::arbitrary::string(u, 0, None, ::asn1rs::arbitrary::Charset::Utf8)?) } else { None },"
+        ));
+    }
+
+    #[test]
+    pub fn test_enum_and_choice() {
+        let content = generate(
+            r#"ArbitraryTest DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyEnum ::= ENUMERATED { abc, def }
+
+            MyChoice ::= CHOICE {
+                abc MyEnum,
+                def NULL
+            }
+
+            END
+        "#,
+        );
+
+        assert!(content.contains("Ok(*u.choose(&Self::variants())?)"));
+        assert!(content.contains("Ok(match u.int_in_range(0..=1)? {"));
+        assert!(content.contains("0 => Self::Abc(u.arbitrary::<MyEnum>()?),"));
+        assert!(content.contains("1 => Self::Def(Null),"));
+    }
+
+    #[test]
+    pub fn test_feature_gated() {
+        use crate::generate::Generator;
+
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"ArbitraryTest DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            MyStruct ::= SEQUENCE { flag BOOLEAN }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_generate_arbitrary_impls(true);
+        generator.set_arbitrary_feature(Some("fuzzing".to_string()));
+        let (_file_name, content) = generator.to_string().unwrap().into_iter().next().unwrap();
+
+        assert!(content.contains(
+            "#[cfg(feature = \"fuzzing\")]\nconst _: () = {\n    impl<'a> ::asn1rs::arbitrary::Arbitrary<'a> for MyStruct {\n"
+        ));
+        assert!(content
+            .contains("            Ok(Self {\n                flag: u.arbitrary::<bool>()?,"));
+        assert!(content.trim_end().ends_with("    }\n};"));
+    }
+}
@@ -0,0 +1,482 @@
+//! Writes a [`Model`] back as ASN.1 module, for example the model of the types annotated with
+//! `#[asn(..)]`, so that a specification can be derived from existing Rust types.
+//!
+//! Only the tags given in the model are written. Because the module header has to state a
+//! tagging environment, `AUTOMATIC TAGS` is written, which - as everywhere in this crate - does
+//! not add any tags to the definitions.
+
+use crate::asn::{Asn, Charset, ComponentTypeList, ObjectIdentifierComponent, Size, Tag, Type};
+use crate::generate::Generator;
+use crate::model::{Definition, LiteralValue, Model, ValueReference};
+use crate::resolve::Resolved;
+use std::fmt::Error as FmtError;
+use std::fmt::Write;
+
+#[derive(Debug)]
+pub enum Error {
+    Fmt(FmtError),
+}
+
+impl From<FmtError> for Error {
+    fn from(e: FmtError) -> Self {
+        Error::Fmt(e)
+    }
+}
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Default)]
+pub struct Asn1DefGenerator {
+    models: Vec<Model<Asn>>,
+}
+
+impl Generator<Asn> for Asn1DefGenerator {
+    type Error = Error;
+
+    fn add_model(&mut self, model: Model<Asn>) {
+        self.models.push(model);
+    }
+
+    fn models(&self) -> &[Model<Asn>] {
+        &self.models[..]
+    }
+
+    fn models_mut(&mut self) -> &mut [Model<Asn>] {
+        &mut self.models[..]
+    }
+
+    fn to_string(&self) -> Result<Vec<(String, String)>, <Self as Generator<Asn>>::Error> {
+        let mut files = Vec::new();
+        for model in &self.models {
+            files.push(Self::generate_file(model)?);
+        }
+        Ok(files)
+    }
+}
+
+impl Asn1DefGenerator {
+    pub fn generate_file(model: &Model<Asn>) -> Result<(String, String), Error> {
+        let file_name = format!("{}.asn1", model.name);
+        let mut content = String::new();
+        Self::append_header(&mut content, model)?;
+        Self::append_imports(&mut content, model)?;
+        for definition in &model.definitions {
+            Self::append_definition(&mut content, definition)?;
+            writeln!(content)?;
+        }
+        for value_reference in &model.value_references {
+            Self::append_value_reference(&mut content, value_reference)?;
+            writeln!(content)?;
+        }
+        writeln!(content, "END")?;
+        Ok((file_name, content))
+    }
+
+    pub fn append_header(target: &mut dyn Write, model: &Model<Asn>) -> Result<(), Error> {
+        write!(target, "{}", model.name)?;
+        if let Some(oid) = &model.oid {
+            write!(target, " {{")?;
+            for component in oid.iter() {
+                match component {
+                    ObjectIdentifierComponent::NameForm(name) => write!(target, " {}", name)?,
+                    ObjectIdentifierComponent::NumberForm(number) => write!(target, " {}", number)?,
+                    ObjectIdentifierComponent::NameAndNumberForm(name, number) => {
+                        write!(target, " {}({})", name, number)?
+                    }
+                }
+            }
+            write!(target, " }}")?;
+        }
+        writeln!(target, " DEFINITIONS AUTOMATIC TAGS ::=")?;
+        writeln!(target, "BEGIN")?;
+        writeln!(target)?;
+        Ok(())
+    }
+
+    pub fn append_imports(target: &mut dyn Write, model: &Model<Asn>) -> Result<(), Error> {
+        if model.imports.is_empty() {
+            return Ok(());
+        }
+        writeln!(target, "IMPORTS")?;
+        for import in &model.imports {
+            writeln!(
+                target,
+                "    {} FROM {}",
+                import.what.join(", "),
+                import.from
+            )?;
+        }
+        writeln!(target, "    ;")?;
+        writeln!(target)?;
+        Ok(())
+    }
+
+    /// Writes the type assignment, such as `Point ::= SEQUENCE { .. }`, without a trailing
+    /// empty line
+    pub fn append_definition(
+        target: &mut dyn Write,
+        Definition(name, asn): &Definition<Asn>,
+    ) -> Result<(), Error> {
+        write!(target, "{} ::= ", name)?;
+        Self::append_asn(target, asn, 0)?;
+        writeln!(target)?;
+        Ok(())
+    }
+
+    pub fn append_value_reference(
+        target: &mut dyn Write,
+        value_reference: &ValueReference<Asn>,
+    ) -> Result<(), Error> {
+        write!(target, "{} ", Self::identifier(&value_reference.name))?;
+        Self::append_asn(target, &value_reference.role, 0)?;
+        write!(target, " ::= ")?;
+        Self::append_literal(target, &value_reference.value)?;
+        writeln!(target)?;
+        Ok(())
+    }
+
+    /// Converts the definition to its ASN.1 type assignment, see
+    /// [`Asn1DefGenerator::append_definition`]
+    pub fn definition_to_string(definition: &Definition<Asn>) -> String {
+        let mut string = String::new();
+        Self::append_definition(&mut string, definition)
+            .expect("Writing to a String does not fail");
+        string
+    }
+
+    /// Converts the literal to ASN.1 value notation, such as `TRUE`, `"text"` or `'0AFF'H`
+    pub fn literal_to_string(literal: &LiteralValue) -> String {
+        let mut string = String::new();
+        Self::append_literal(&mut string, literal).expect("Writing to a String does not fail");
+        string
+    }
+
+    fn append_asn(target: &mut dyn Write, asn: &Asn, indent: usize) -> Result<(), Error> {
+        if let Some(tag) = asn.tag {
+            Self::append_tag(target, tag)?;
+        }
+        Self::append_type(target, &asn.r#type, indent)?;
+        if let Some(default) = &asn.default {
+            write!(target, " DEFAULT ")?;
+            Self::append_literal(target, default)?;
+        }
+        Ok(())
+    }
+
+    fn append_tag(target: &mut dyn Write, tag: Tag) -> Result<(), Error> {
+        match tag {
+            Tag::Universal(value) => write!(target, "[UNIVERSAL {}] ", value)?,
+            Tag::Application(value) => write!(target, "[APPLICATION {}] ", value)?,
+            Tag::ContextSpecific(value) => write!(target, "[{}] ", value)?,
+            Tag::Private(value) => write!(target, "[PRIVATE {}] ", value)?,
+        }
+        Ok(())
+    }
+
+    fn append_type(target: &mut dyn Write, r#type: &Type, indent: usize) -> Result<(), Error> {
+        match r#type {
+            Type::Boolean => write!(target, "BOOLEAN")?,
+            Type::Integer(integer) => {
+                write!(target, "INTEGER")?;
+                Self::append_named_numbers(target, integer.constants.iter())?;
+                let extensible = if integer.range.extensible() {
+                    ",..."
+                } else {
+                    ""
+                };
+                match (integer.range.min(), integer.range.max()) {
+                    (Some(min), Some(max)) if min == max => {
+                        write!(target, " ({}{})", min, extensible)?
+                    }
+                    (Some(min), Some(max)) => write!(target, " ({}..{}{})", min, max, extensible)?,
+                    (Some(min), None) => write!(target, " ({}..MAX{})", min, extensible)?,
+                    (None, Some(max)) => write!(target, " (MIN..{}{})", max, extensible)?,
+                    (None, None) if integer.range.extensible() => {
+                        write!(target, " (MIN..MAX,...)")?
+                    }
+                    (None, None) => {}
+                }
+            }
+            Type::String(size, charset, alphabet) => {
+                write!(target, "{}", Self::charset_type_name(*charset))?;
+                Self::append_size(target, size)?;
+                if let Some(alphabet) = alphabet {
+                    write!(
+                        target,
+                        " (FROM (\"{}\"))",
+                        alphabet.as_string().replace('"', "\"\"")
+                    )?;
+                }
+            }
+            Type::OctetString(size) => {
+                write!(target, "OCTET STRING")?;
+                Self::append_size(target, size)?;
+            }
+            Type::BitString(bit_string) => {
+                write!(target, "BIT STRING")?;
+                Self::append_named_numbers(target, bit_string.constants.iter())?;
+                Self::append_size(target, &bit_string.size)?;
+            }
+            Type::Null => write!(target, "NULL")?,
+            Type::Optional(inner) => {
+                Self::append_type(target, inner, indent)?;
+                write!(target, " OPTIONAL")?;
+            }
+            Type::Default(inner, default) => {
+                Self::append_type(target, inner, indent)?;
+                write!(target, " DEFAULT ")?;
+                Self::append_literal(target, default)?;
+            }
+            Type::Sequence(components) => {
+                write!(target, "SEQUENCE ")?;
+                Self::append_components(target, components, indent)?;
+            }
+            Type::SequenceOf(inner, size) => {
+                write!(target, "SEQUENCE")?;
+                Self::append_size(target, size)?;
+                write!(target, " OF ")?;
+                Self::append_type(target, inner, indent)?;
+            }
+            Type::Set(components) => {
+                write!(target, "SET ")?;
+                Self::append_components(target, components, indent)?;
+            }
+            Type::SetOf(inner, size) => {
+                write!(target, "SET")?;
+                Self::append_size(target, size)?;
+                write!(target, " OF ")?;
+                Self::append_type(target, inner, indent)?;
+            }
+            Type::Enumerated(enumerated) => {
+                let variants = enumerated
+                    .variants()
+                    .map(|variant| match variant.number() {
+                        Some(number) => format!("{}({})", Self::identifier(variant.name()), number),
+                        None => Self::identifier(variant.name()),
+                    })
+                    .collect::<Vec<_>>();
+                write!(target, "ENUMERATED ")?;
+                Self::append_block(target, variants, enumerated.extension_after_index(), indent)?;
+            }
+            Type::Choice(choice) => {
+                let variants = choice
+                    .variants()
+                    .map(|variant| {
+                        let mut line = Self::identifier(variant.name());
+                        line.push(' ');
+                        if let Some(tag) = variant.tag {
+                            Self::append_tag(&mut line, tag)?;
+                        }
+                        Self::append_type(&mut line, variant.r#type(), indent + 1)?;
+                        Ok(line)
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?;
+                write!(target, "CHOICE ")?;
+                Self::append_block(target, variants, choice.extension_after_index(), indent)?;
+            }
+            Type::TypeReference(name, _) => write!(target, "{}", name)?,
+        }
+        Ok(())
+    }
+
+    fn append_components(
+        target: &mut dyn Write,
+        components: &ComponentTypeList<Resolved>,
+        indent: usize,
+    ) -> Result<(), Error> {
+        let fields = components
+            .fields
+            .iter()
+            .map(|field| {
+                let mut line = Self::identifier(&field.name);
+                line.push(' ');
+                Self::append_asn(&mut line, &field.role, indent + 1)?;
+                Ok(line)
+            })
+            .collect::<Result<Vec<_>, Error>>()?;
+        Self::append_block(target, fields, components.extension_after, indent)
+    }
+
+    /// Writes the entries, one per line, enclosed in braces. The extension marker is placed
+    /// after the entry with the given index.
+    fn append_block(
+        target: &mut dyn Write,
+        mut entries: Vec<String>,
+        extension_after: Option<usize>,
+        indent: usize,
+    ) -> Result<(), Error> {
+        if let Some(index) = extension_after {
+            let index = (index + 1).min(entries.len());
+            entries.insert(index, "...".to_string());
+        }
+        if entries.is_empty() {
+            write!(target, "{{ }}")?;
+            return Ok(());
+        }
+        writeln!(target, "{{")?;
+        let last = entries.len() - 1;
+        for (index, entry) in entries.iter().enumerate() {
+            write!(target, "{:indent$}{}", "", entry, indent = (indent + 1) * 4)?;
+            writeln!(target, "{}", if index < last { "," } else { "" })?;
+        }
+        write!(target, "{:indent$}}}", "", indent = indent * 4)?;
+        Ok(())
+    }
+
+    fn append_named_numbers<'a, T: std::fmt::Display + 'a>(
+        target: &mut dyn Write,
+        constants: impl ExactSizeIterator<Item = &'a (String, T)>,
+    ) -> Result<(), Error> {
+        if constants.len() > 0 {
+            let constants = constants
+                .map(|(name, value)| format!("{}({})", Self::identifier(name), value))
+                .collect::<Vec<_>>();
+            write!(target, " {{ {} }}", constants.join(", "))?;
+        }
+        Ok(())
+    }
+
+    fn append_size(target: &mut dyn Write, size: &Size) -> Result<(), Error> {
+        const MAX: usize = i64::MAX as usize;
+        let extensible = if size.extensible() { ",..." } else { "" };
+        match size {
+            Size::Any => {}
+            Size::Fix(size, _) => write!(target, " (SIZE({}{}))", size, extensible)?,
+            Size::Range(min, MAX, _) => write!(target, " (SIZE({}..MAX{}))", min, extensible)?,
+            Size::Range(min, max, _) => write!(target, " (SIZE({}..{}{}))", min, max, extensible)?,
+        }
+        Ok(())
+    }
+
+    fn append_literal(target: &mut dyn Write, literal: &LiteralValue) -> Result<(), Error> {
+        match literal {
+            LiteralValue::Boolean(true) => write!(target, "TRUE")?,
+            LiteralValue::Boolean(false) => write!(target, "FALSE")?,
+            LiteralValue::String(string) => write!(target, "\"{}\"", string.replace('"', "\"\""))?,
+            LiteralValue::Integer(integer) => write!(target, "{}", integer)?,
+            LiteralValue::OctetString(bytes) => {
+                write!(target, "'")?;
+                for byte in bytes {
+                    write!(target, "{:02X}", byte)?;
+                }
+                write!(target, "'H")?;
+            }
+            LiteralValue::EnumeratedVariant(_, variant) => {
+                write!(target, "{}", Self::identifier(variant))?
+            }
+        }
+        Ok(())
+    }
+
+    fn charset_type_name(charset: Charset) -> &'static str {
+        match charset {
+            Charset::Utf8 => "UTF8String",
+            Charset::Numeric => "NumericString",
+            Charset::Printable => "PrintableString",
+            Charset::Teletex => "TeletexString",
+            Charset::Videotex => "VideotexString",
+            Charset::Ia5 => "IA5String",
+            Charset::Graphic => "GraphicString",
+            Charset::ObjectDescriptor => "ObjectDescriptor",
+            Charset::Visible => "VisibleString",
+        }
+    }
+
+    /// Converts the name of a field, variant or constant to an ASN.1 identifier, which starts
+    /// with a lowercase letter and uses hyphens instead of underscores. Rust names, such as
+    /// `msg_id`, `VeryLarge` or `SOME_BIT`, become `msg-id`, `veryLarge` and `some-bit`, which
+    /// are converted back to the same Rust names by the code generator.
+    pub fn identifier(name: &str) -> String {
+        let identifier = if name.chars().any(|c| c.is_ascii_lowercase()) {
+            let mut chars = name.chars();
+            chars
+                .next()
+                .map(|c| c.to_ascii_lowercase())
+                .into_iter()
+                .chain(chars)
+                .collect::<String>()
+        } else {
+            name.to_ascii_lowercase()
+        };
+        identifier.replace('_', "-")
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    #[test]
+    fn test_asn1_identifier() {
+        assert_eq!("msg-id", Asn1DefGenerator::identifier("msg_id"));
+        assert_eq!("veryLarge", Asn1DefGenerator::identifier("VeryLarge"));
+        assert_eq!("some-bit", Asn1DefGenerator::identifier("SOME_BIT"));
+        assert_eq!("is-ok", Asn1DefGenerator::identifier("is-ok"));
+    }
+
+    #[test]
+    fn test_generated_module_parses_to_the_same_model() {
+        let model = Model::try_from(Tokenizer.parse(
+            r#"Roundtrip { iso(1) 2 } DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            IMPORTS Other FROM OtherModule;
+
+            Kind ::= ENUMERATED { small, large(5), ..., huge }
+            Flags ::= BIT STRING { first(0), last(7) } (SIZE(8))
+            Message ::= [APPLICATION 3] SEQUENCE {
+                id INTEGER (0..255),
+                delta INTEGER (-5..MAX),
+                code INTEGER { ok(0), failed(1) } (0..7,...),
+                kind Kind DEFAULT large,
+                name IA5String (SIZE(1..32)) (FROM ("abc")),
+                raw OCTET STRING (SIZE(4)) DEFAULT 'CAFE0102'H,
+                tags SEQUENCE (SIZE(0..4)) OF UTF8String,
+                payload CHOICE {
+                    number [0] INTEGER,
+                    nested [PRIVATE 1] SET { flag BOOLEAN OPTIONAL, ... },
+                    ...
+                },
+                other Other OPTIONAL,
+                ...,
+                extra NULL
+            }
+            limit INTEGER ::= 42
+            END"#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap();
+
+        let (file_name, content) = Asn1DefGenerator::generate_file(&model).unwrap();
+        assert_eq!("Roundtrip.asn1", file_name);
+        let parsed = Model::try_from(Tokenizer.parse(&content))
+            .unwrap()
+            .try_resolve()
+            .unwrap();
+        assert_eq!(model.name, parsed.name, "{}", content);
+        assert_eq!(model.oid, parsed.oid, "{}", content);
+        assert_eq!(model.imports, parsed.imports, "{}", content);
+        assert_eq!(model.definitions, parsed.definitions, "{}", content);
+        assert_eq!(
+            model.value_references, parsed.value_references,
+            "{}",
+            content
+        );
+    }
+
+    #[test]
+    fn test_definition_to_string() {
+        let model = Model::try_from(Tokenizer.parse(
+            r"Simple DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Point ::= SEQUENCE { x INTEGER (0..255), label UTF8String OPTIONAL, ... }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap();
+        assert_eq!(
+            "Point ::= SEQUENCE {\n    x INTEGER (0..255),\n    label UTF8String OPTIONAL,\n    ...\n}\n",
+            Asn1DefGenerator::definition_to_string(&model.definitions[0])
+        );
+    }
+}
@@ -0,0 +1,126 @@
+//! Additional attributes, that the [`super::RustCodeGenerator`] emits verbatim on the generated
+//! types, fields and variants, such as `serde(rename_all = "camelCase")` or `cfg(feature = "x")`.
+//!
+//! The mapping file contains one attribute per line in the form `Type = attribute` for the type
+//! or `Type.member = attribute` for one of its fields or variants, where `Type` and `member` are
+//! the ASN.1 names of the type and the identifier of the field or variant. The attribute may be
+//! given with or without the surrounding `#[...]`. Empty lines and lines starting with `#` are
+//! ignored:
+//!
+//! ```text
+//! # for the REST gateway
+//! Message = serde(rename_all = "camelCase")
+//! Message.debugInfo = #[cfg(feature = "debug")]
+//! ```
+
+use super::mapping::{self, PerDefinition};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line with the given (1-based) number is not of the form `Type[.member] = attribute`
+    InvalidLine(usize, String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidLine(number, line) => write!(
+                f,
+                "Line {number} is not of the form 'Type[.member] = attribute': {line}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Attributes(PerDefinition<(Option<String>, String)>);
+
+impl Attributes {
+    pub fn parse(mapping: &str) -> Result<Self, Error> {
+        let mut attributes = Self::default();
+        for line in mapping::lines(mapping) {
+            let (definition, member, attribute) = line
+                .split()
+                .map(|(definition, member, attribute)| {
+                    let attribute = attribute
+                        .strip_prefix("#[")
+                        .and_then(|attribute| attribute.strip_suffix(']'))
+                        .unwrap_or(attribute)
+                        .trim();
+                    (definition, member, attribute)
+                })
+                .filter(|(_, _, attribute)| !attribute.is_empty())
+                .ok_or_else(|| Error::InvalidLine(line.number, line.content.to_string()))?;
+            attributes.add(definition, member.map(str::to_string), attribute);
+        }
+        Ok(attributes)
+    }
+
+    /// Adds the attribute - without the surrounding `#[...]` - to the given type or, if a member
+    /// is given, to its field or variant
+    pub fn add<D: Into<String>, A: Into<String>>(
+        &mut self,
+        definition: D,
+        member: Option<String>,
+        attribute: A,
+    ) {
+        self.0.push(&definition.into(), (member, attribute.into()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The `(member, attribute)` pairs of the given type, without a member for the attributes of
+    /// the type itself
+    pub fn of(&self, definition: &str) -> &[(Option<String>, String)] {
+        self.0.of(definition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse() {
+        let attributes = Attributes::parse(
+            r#"
+            # comment
+            Message = serde(rename_all = "camelCase")
+            Message.debugInfo = #[cfg(feature = "debug")]
+            Status.offline=doc(hidden)
+            "#,
+        )
+        .unwrap();
+        assert_eq!(
+            &[
+                (None, r#"serde(rename_all = "camelCase")"#.to_string()),
+                (
+                    Some("debugInfo".to_string()),
+                    r#"cfg(feature = "debug")"#.to_string()
+                )
+            ],
+            attributes.of("Message")
+        );
+        assert_eq!(
+            &[(Some("offline".to_string()), "doc(hidden)".to_string())],
+            attributes.of("Status")
+        );
+        assert!(attributes.of("Unknown").is_empty());
+    }
+
+    #[test]
+    pub fn test_parse_invalid_lines() {
+        assert_eq!(
+            Err(Error::InvalidLine(2, "Message.debugInfo".to_string())),
+            Attributes::parse("\nMessage.debugInfo\n")
+        );
+        assert_eq!(
+            Err(Error::InvalidLine(1, "Message = #[]".to_string())),
+            Attributes::parse("Message = #[]")
+        );
+    }
+}
@@ -0,0 +1,661 @@
+//! Generates C bindings per module for consumers of decoded values in C: a header `<module>.h`
+//! with plain C structs, enums and the prototypes of the encode, decode and free functions per
+//! type, and a Rust file `<module>_ffi.rs` with the matching `#[repr(C)]` mirror types and the
+//! `#[no_mangle] extern "C"` functions, which call into the runtime support of `asn1rs::ffi`.
+//!
+//! The Rust file is meant to be placed as sibling module of the generated Rust module (and of
+//! the modules it imports from), whose fields must be public, and the crate must enable the
+//! `ffi` feature of `asn1rs`. The declarations are compatible with the ones `cbindgen` derives
+//! from the mirror types, so either header can be used.
+//!
+//! | ASN.1                      | C                                        |
+//! |----------------------------|------------------------------------------|
+//! | `BOOLEAN`                  | `bool`                                   |
+//! | `INTEGER`                  | `int8_t` ... `uint64_t` as in Rust       |
+//! | strings                    | `AsnString`, NUL-terminated UTF-8        |
+//! | `OCTET STRING`             | `AsnBytes`                               |
+//! | `BIT STRING`               | `AsnBits`                                |
+//! | `NULL`                     | `uint8_t`                                |
+//! | `SEQUENCE OF`, `SET OF`    | `struct { T *data; size_t len; }`        |
+//! | `OPTIONAL`                 | `T *`, which is null if absent           |
+//! | recursive reference        | `T *`, never null, `T **` if optional    |
+//! | `ENUMERATED`               | `uint32_t` with a constant per variant   |
+//! | `CHOICE`                   | struct of the `tag` and a `value` union  |
+
+use crate::generate::rust::RustCodeGenerator;
+use crate::generate::Generator;
+use crate::model::{Definition, Model};
+use crate::rust::{rust_module_name, rust_struct_or_enum_name, Rust, RustType};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fmt::Write;
+
+/// Names that cannot be used as member names in C (or C++), an underscore is appended to them
+const C_KEYWORDS: [&str; 37] = [
+    "auto", "bool", "break", "case", "char", "class", "const", "continue", "default", "delete",
+    "do", "double", "else", "enum", "extern", "false", "float", "for", "goto", "if", "inline",
+    "int", "long", "new", "register", "restrict", "return", "short", "signed", "sizeof", "static",
+    "switch", "true", "typedef", "union", "unsigned", "void",
+];
+
+/// The types, codec ids and status codes shared by all generated headers, see `asn1rs::ffi`
+const COMMON_TYPES: &str = r#"#ifndef ASN1RS_COMMON_TYPES
+#define ASN1RS_COMMON_TYPES
+
+#define ASN1RS_CODEC_UPER 0
+#define ASN1RS_CODEC_DER 1
+#define ASN1RS_CODEC_PROTOBUF 2
+#define ASN1RS_CODEC_JER 3
+
+#define ASN1RS_OK 0
+#define ASN1RS_NULL_POINTER (-1)
+#define ASN1RS_UNKNOWN_CODEC (-2)
+#define ASN1RS_DECODE_FAILED (-3)
+#define ASN1RS_ENCODE_FAILED (-4)
+#define ASN1RS_INVALID_VALUE (-5)
+#define ASN1RS_BUFFER_TOO_SMALL (-6)
+
+typedef struct AsnString {
+    char *data;
+    size_t len;
+} AsnString;
+
+typedef struct AsnBytes {
+    uint8_t *data;
+    size_t len;
+} AsnBytes;
+
+typedef struct AsnBits {
+    uint8_t *data;
+    size_t len;
+    uint64_t bit_len;
+} AsnBits;
+
+#endif
+"#;
+
+#[derive(Debug, Default)]
+pub struct CGenerator {
+    models: Vec<Model<Rust>>,
+}
+
+impl Generator<Rust> for CGenerator {
+    type Error = Infallible;
+
+    fn add_model(&mut self, model: Model<Rust>) {
+        self.models.push(model);
+    }
+
+    fn models(&self) -> &[Model<Rust>] {
+        &self.models[..]
+    }
+
+    fn models_mut(&mut self) -> &mut [Model<Rust>] {
+        &mut self.models[..]
+    }
+
+    fn to_string(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self
+            .models
+            .iter()
+            .flat_map(|model| [Self::generate_header(model), Self::generate_ffi(model)])
+            .collect())
+    }
+}
+
+impl CGenerator {
+    pub fn header_file_name(model: &str) -> String {
+        format!("{}.h", RustCodeGenerator::rust_module_name(model))
+    }
+
+    pub fn ffi_file_name(model: &str) -> String {
+        format!("{}_ffi.rs", RustCodeGenerator::rust_module_name(model))
+    }
+
+    pub fn generate_header(model: &Model<Rust>) -> (String, String) {
+        let module = RustCodeGenerator::rust_module_name(&model.name);
+        let guard = format!("ASN1RS_{}_H", module.to_uppercase());
+        let mut out = String::new();
+
+        let _ = writeln!(
+            out,
+            "/* Generated by asn1rs from the ASN.1 module {}, implemented in {} */",
+            model.name,
+            Self::ffi_file_name(&model.name)
+        );
+        let _ = writeln!(out, "#ifndef {}\n#define {}\n", guard, guard);
+        out.push_str("#include <stdbool.h>\n#include <stddef.h>\n#include <stdint.h>\n\n");
+        for import in &model.imports {
+            let _ = writeln!(out, "#include \"{}\"", Self::header_file_name(&import.from));
+        }
+        if !model.imports.is_empty() {
+            out.push('\n');
+        }
+        out.push_str(COMMON_TYPES);
+        out.push_str("\n#ifdef __cplusplus\nextern \"C\" {\n#endif\n\n");
+
+        let mut forward = false;
+        for Definition(name, rust) in &model.definitions {
+            if matches!(rust, Rust::Struct { .. } | Rust::DataEnum(_)) {
+                let _ = writeln!(out, "typedef struct {} {};", name, name);
+                forward = true;
+            }
+        }
+        if forward {
+            out.push('\n');
+        }
+
+        for Definition(name, rust) in Self::dependency_order(model) {
+            Self::write_c_definition(&mut out, name, rust);
+            out.push('\n');
+        }
+
+        for Definition(name, _) in &model.definitions {
+            let prefix = Self::function_prefix(model, name);
+            let _ = writeln!(
+                out,
+                "int32_t {}_decode(uint8_t codec, const uint8_t *bytes, size_t len, {} *out);",
+                prefix, name
+            );
+            let _ = writeln!(
+                out,
+                "int32_t {}_encode(uint8_t codec, const {} *value, uint8_t *out, size_t capacity, size_t *written);",
+                prefix, name
+            );
+            let _ = writeln!(out, "void {}_free({} *value);", prefix, name);
+        }
+        if !model.definitions.is_empty() {
+            out.push('\n');
+        }
+
+        out.push_str("#ifdef __cplusplus\n}\n#endif\n\n");
+        let _ = writeln!(out, "#endif /* {} */", guard);
+        (Self::header_file_name(&model.name), out)
+    }
+
+    /// The definitions, where each one is placed after the ones it refers to, unless they refer
+    /// to each other
+    fn dependency_order(model: &Model<Rust>) -> Vec<&Definition<Rust>> {
+        fn visit<'a>(
+            model: &'a Model<Rust>,
+            definition: &'a Definition<Rust>,
+            visited: &mut HashSet<&'a str>,
+            ordered: &mut Vec<&'a Definition<Rust>>,
+        ) {
+            if !visited.insert(&definition.0) {
+                return;
+            }
+            let types: Vec<&RustType> = match &definition.1 {
+                Rust::Struct { fields, .. } => fields.iter().map(|f| f.r#type()).collect(),
+                Rust::DataEnum(data) => data.variants().map(|v| v.r#type()).collect(),
+                Rust::TupleStruct { r#type, .. } => vec![r#type],
+                Rust::Enum(_) => Vec::new(),
+            };
+            for r#type in types {
+                if let RustType::Complex(name, _) = r#type.as_inner_type() {
+                    if let Some(dependency) = model.definitions.iter().find(|d| &d.0 == name) {
+                        visit(model, dependency, visited, ordered);
+                    }
+                }
+            }
+            ordered.push(definition);
+        }
+
+        let mut visited = HashSet::new();
+        let mut ordered = Vec::with_capacity(model.definitions.len());
+        for definition in &model.definitions {
+            visit(model, definition, &mut visited, &mut ordered);
+        }
+        ordered
+    }
+
+    fn write_c_definition(out: &mut String, name: &str, rust: &Rust) {
+        match rust {
+            Rust::Struct { fields, .. } => {
+                let _ = writeln!(out, "struct {} {{", name);
+                for field in fields {
+                    let _ = writeln!(
+                        out,
+                        "    {};",
+                        Self::c_declaration(
+                            &Self::c_type(field.r#type()),
+                            &Self::member_name(field.name())
+                        )
+                    );
+                }
+                if fields.is_empty() {
+                    out.push_str("    uint8_t _empty;\n");
+                }
+                out.push_str("};\n");
+            }
+            Rust::Enum(plain) => {
+                let _ = writeln!(out, "typedef uint32_t {};\nenum {{", name);
+                for (index, variant) in plain.variants().enumerate() {
+                    let _ = writeln!(
+                        out,
+                        "    {}_{} = {},",
+                        name,
+                        RustCodeGenerator::rust_variant_name(variant),
+                        index
+                    );
+                }
+                out.push_str("};\n");
+            }
+            Rust::DataEnum(data) => {
+                let _ = writeln!(out, "struct {} {{\n    uint32_t tag;\n    union {{", name);
+                for variant in data.variants() {
+                    let _ = writeln!(
+                        out,
+                        "        {};",
+                        Self::c_declaration(
+                            &Self::c_type(variant.r#type()),
+                            &Self::member_name(variant.name())
+                        )
+                    );
+                }
+                out.push_str("    } value;\n};\nenum {\n");
+                for (index, variant) in data.variants().enumerate() {
+                    let _ = writeln!(
+                        out,
+                        "    {}_{} = {},",
+                        name,
+                        RustCodeGenerator::rust_variant_name(variant.name()),
+                        index
+                    );
+                }
+                out.push_str("};\n");
+            }
+            Rust::TupleStruct { r#type, .. } => {
+                let _ = writeln!(
+                    out,
+                    "typedef {};",
+                    Self::c_declaration(&Self::c_type(r#type), name)
+                );
+            }
+        }
+    }
+
+    fn c_type(r#type: &RustType) -> String {
+        match r#type {
+            RustType::Bool => "bool".into(),
+            RustType::I8(_) => "int8_t".into(),
+            RustType::U8(_) | RustType::Null => "uint8_t".into(),
+            RustType::I16(_) => "int16_t".into(),
+            RustType::U16(_) => "uint16_t".into(),
+            RustType::I32(_) => "int32_t".into(),
+            RustType::U32(_) => "uint32_t".into(),
+            RustType::I64(_) => "int64_t".into(),
+            RustType::U64(_) => "uint64_t".into(),
+            RustType::I128(_) => "__int128".into(),
+            RustType::U128(_) => "unsigned __int128".into(),
+            RustType::String(..) => "AsnString".into(),
+            RustType::VecU8(_) | RustType::Uuid => "AsnBytes".into(),
+            RustType::BitVec(_) => "AsnBits".into(),
+            RustType::Vec(inner, ..) => format!(
+                "struct {{ {}; size_t len; }}",
+                Self::c_declaration(&Self::c_type(inner), "*data")
+            ),
+            RustType::Option(inner) => {
+                format!("{}*", Self::c_declaration(&Self::c_type(inner), ""))
+            }
+            RustType::Default(inner, ..) => Self::c_type(inner),
+            RustType::Box(inner) => format!("{}*", Self::c_declaration(&Self::c_type(inner), "")),
+            RustType::Complex(name, _) => name.clone(),
+        }
+    }
+
+    /// Declares `name` with the type, without a space after pointer types
+    fn c_declaration(r#type: &str, name: &str) -> String {
+        if r#type.ends_with('*') {
+            format!("{}{}", r#type, name)
+        } else {
+            format!("{} {}", r#type, name)
+        }
+    }
+
+    /// The name of a field or of a CHOICE variant in the C declaration and in the mirror type
+    fn member_name(name: &str) -> String {
+        let mut name = RustCodeGenerator::rust_field_name(&rust_module_name(name, false), false);
+        if C_KEYWORDS.contains(&name.as_str()) || RustCodeGenerator::is_keyword(&name) {
+            name.push('_');
+        }
+        name
+    }
+
+    fn function_prefix(model: &Model<Rust>, name: &str) -> String {
+        format!(
+            "{}_{}",
+            RustCodeGenerator::rust_module_name(&model.name),
+            rust_module_name(name, false)
+        )
+    }
+
+    pub fn generate_ffi(model: &Model<Rust>) -> (String, String) {
+        let mut out = String::new();
+        let _ = writeln!(
+            out,
+            "// Generated by asn1rs from the ASN.1 module {}, declared in {}\n",
+            model.name,
+            Self::header_file_name(&model.name)
+        );
+        out.push_str("use asn1rs::ffi::FfiType;\n");
+
+        for Definition(name, rust) in &model.definitions {
+            let path = Self::rust_path(model, name);
+            out.push('\n');
+            match rust {
+                Rust::Struct { fields, .. } => {
+                    let _ = writeln!(out, "#[repr(C)]\npub struct {} {{", name);
+                    for field in fields {
+                        let _ = writeln!(
+                            out,
+                            "    pub {}: <{} as FfiType>::C,",
+                            Self::member_name(field.name()),
+                            Self::rust_type(model, field.r#type())
+                        );
+                    }
+                    if fields.is_empty() {
+                        out.push_str("    pub _empty: u8,\n");
+                    }
+                    out.push_str("}\n\n");
+
+                    let c = if fields.is_empty() { "_c" } else { "c" };
+                    let _ = writeln!(
+                        out,
+                        "impl FfiType for {} {{\n    type C = {};\n",
+                        path, name
+                    );
+                    let _ = writeln!(
+                        out,
+                        "    fn into_c(self) -> Self::C {{\n        {} {{",
+                        name
+                    );
+                    for field in fields {
+                        let _ = writeln!(
+                            out,
+                            "            {}: FfiType::into_c(self.{}),",
+                            Self::member_name(field.name()),
+                            RustCodeGenerator::rust_field_name(field.name(), true)
+                        );
+                    }
+                    if fields.is_empty() {
+                        out.push_str("            _empty: 0,\n");
+                    }
+                    out.push_str("        }\n    }\n\n");
+                    let _ = writeln!(
+                        out,
+                        "    unsafe fn from_c({}: &Self::C) -> Result<Self, i32> {{\n        Ok(Self {{",
+                        c
+                    );
+                    for field in fields {
+                        let _ = writeln!(
+                            out,
+                            "            {}: <{} as FfiType>::from_c(&c.{})?,",
+                            RustCodeGenerator::rust_field_name(field.name(), true),
+                            Self::rust_type(model, field.r#type()),
+                            Self::member_name(field.name())
+                        );
+                    }
+                    out.push_str("        })\n    }\n\n");
+                    let _ = writeln!(out, "    unsafe fn free_c({}: &mut Self::C) {{", c);
+                    for field in fields {
+                        let _ = writeln!(
+                            out,
+                            "        <{} as FfiType>::free_c(&mut c.{});",
+                            Self::rust_type(model, field.r#type()),
+                            Self::member_name(field.name())
+                        );
+                    }
+                    out.push_str("    }\n}\n");
+                }
+                Rust::Enum(plain) => {
+                    let _ = writeln!(out, "pub type {} = u32;\n", name);
+                    let _ = writeln!(
+                        out,
+                        "impl FfiType for {} {{\n    type C = {};\n",
+                        path, name
+                    );
+                    out.push_str("    fn into_c(self) -> Self::C {\n        match self {\n");
+                    for (index, variant) in plain.variants().enumerate() {
+                        let _ = writeln!(
+                            out,
+                            "            Self::{} => {},",
+                            RustCodeGenerator::rust_variant_name(variant),
+                            index
+                        );
+                    }
+                    out.push_str("        }\n    }\n\n");
+                    out.push_str(
+                        "    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {\n        match *c {\n",
+                    );
+                    for (index, variant) in plain.variants().enumerate() {
+                        let _ = writeln!(
+                            out,
+                            "            {} => Ok(Self::{}),",
+                            index,
+                            RustCodeGenerator::rust_variant_name(variant)
+                        );
+                    }
+                    out.push_str("            _ => Err(asn1rs::ffi::ASN1RS_INVALID_VALUE),\n");
+                    out.push_str("        }\n    }\n\n");
+                    out.push_str("    unsafe fn free_c(_c: &mut Self::C) {}\n}\n");
+                }
+                Rust::DataEnum(data) => {
+                    let _ = writeln!(
+                        out,
+                        "#[repr(C)]\npub struct {} {{\n    pub tag: u32,\n    pub value: {}Value,\n}}\n",
+                        name, name
+                    );
+                    let _ = writeln!(out, "#[repr(C)]\npub union {}Value {{", name);
+                    for variant in data.variants() {
+                        let _ = writeln!(
+                            out,
+                            "    pub {}: std::mem::ManuallyDrop<<{} as FfiType>::C>,",
+                            Self::member_name(variant.name()),
+                            Self::rust_type(model, variant.r#type())
+                        );
+                    }
+                    out.push_str("}\n\n");
+
+                    let _ = writeln!(
+                        out,
+                        "impl FfiType for {} {{\n    type C = {};\n",
+                        path, name
+                    );
+                    out.push_str("    fn into_c(self) -> Self::C {\n        match self {\n");
+                    for (index, variant) in data.variants().enumerate() {
+                        let _ = writeln!(
+                            out,
+                            "            Self::{}(value) => {} {{\n                tag: {},\n                value: {}Value {{\n                    {}: std::mem::ManuallyDrop::new(FfiType::into_c(value)),\n                }},\n            }},",
+                            RustCodeGenerator::rust_variant_name(variant.name()),
+                            name,
+                            index,
+                            name,
+                            Self::member_name(variant.name())
+                        );
+                    }
+                    out.push_str("        }\n    }\n\n");
+                    out.push_str(
+                        "    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {\n        match c.tag {\n",
+                    );
+                    for (index, variant) in data.variants().enumerate() {
+                        let _ = writeln!(
+                            out,
+                            "            {} => Ok(Self::{}(<{} as FfiType>::from_c(&c.value.{})?)),",
+                            index,
+                            RustCodeGenerator::rust_variant_name(variant.name()),
+                            Self::rust_type(model, variant.r#type()),
+                            Self::member_name(variant.name())
+                        );
+                    }
+                    out.push_str("            _ => Err(asn1rs::ffi::ASN1RS_INVALID_VALUE),\n");
+                    out.push_str("        }\n    }\n\n");
+                    out.push_str(
+                        "    unsafe fn free_c(c: &mut Self::C) {\n        match c.tag {\n",
+                    );
+                    for (index, variant) in data.variants().enumerate() {
+                        let _ = writeln!(
+                            out,
+                            "            {} => <{} as FfiType>::free_c(&mut c.value.{}),",
+                            index,
+                            Self::rust_type(model, variant.r#type()),
+                            Self::member_name(variant.name())
+                        );
+                    }
+                    out.push_str("            _ => {}\n        }\n    }\n}\n");
+                }
+                Rust::TupleStruct { r#type, .. } => {
+                    let inner = Self::rust_type(model, r#type);
+                    let _ = writeln!(out, "pub type {} = <{} as FfiType>::C;\n", name, inner);
+                    let _ = writeln!(
+                        out,
+                        "impl FfiType for {} {{\n    type C = {};\n",
+                        path, name
+                    );
+                    out.push_str("    fn into_c(self) -> Self::C {\n        FfiType::into_c(self.0)\n    }\n\n");
+                    let _ = writeln!(
+                        out,
+                        "    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {{\n        <{} as FfiType>::from_c(c).map(Self)\n    }}\n",
+                        inner
+                    );
+                    let _ = writeln!(
+                        out,
+                        "    unsafe fn free_c(c: &mut Self::C) {{\n        <{} as FfiType>::free_c(c)\n    }}\n}}",
+                        inner
+                    );
+                }
+            }
+
+            let prefix = Self::function_prefix(model, name);
+            let _ = writeln!(
+                out,
+                "\n/// # Safety\n///\n/// See `asn1rs::ffi::decode`\n#[no_mangle]\npub unsafe extern \"C\" fn {}_decode(codec: u8, bytes: *const u8, len: usize, out: *mut {}) -> i32 {{\n    asn1rs::ffi::decode::<{}>(codec, bytes, len, out)\n}}",
+                prefix, name, path
+            );
+            let _ = writeln!(
+                out,
+                "\n/// # Safety\n///\n/// See `asn1rs::ffi::encode`\n#[no_mangle]\npub unsafe extern \"C\" fn {}_encode(codec: u8, value: *const {}, out: *mut u8, capacity: usize, written: *mut usize) -> i32 {{\n    asn1rs::ffi::encode::<{}>(codec, value, out, capacity, written)\n}}",
+                prefix, name, path
+            );
+            let _ = writeln!(
+                out,
+                "\n/// # Safety\n///\n/// See `asn1rs::ffi::free`\n#[no_mangle]\npub unsafe extern \"C\" fn {}_free(value: *mut {}) {{\n    asn1rs::ffi::free::<{}>(value)\n}}",
+                prefix, name, path
+            );
+        }
+        (Self::ffi_file_name(&model.name), out)
+    }
+
+    /// The path of the generated Rust type, relative to the generated FFI module
+    fn rust_type(model: &Model<Rust>, r#type: &RustType) -> String {
+        match r#type {
+            RustType::BitVec(_) => "asn1rs::prelude::BitVec".into(),
+            RustType::Null => "asn1rs::prelude::Null".into(),
+            RustType::Vec(inner, ..) => format!("Vec<{}>", Self::rust_type(model, inner)),
+            RustType::Option(inner) => format!("Option<{}>", Self::rust_type(model, inner)),
+            RustType::Box(inner) => format!("Box<{}>", Self::rust_type(model, inner)),
+            RustType::Default(inner, ..) => Self::rust_type(model, inner),
+            RustType::Complex(name, _) => Self::rust_path(model, name),
+            other => other.to_string(),
+        }
+    }
+
+    /// The path of the definition, which is either defined in or imported by the model
+    fn rust_path(model: &Model<Rust>, name: &str) -> String {
+        let module = model
+            .imports
+            .iter()
+            .find(|import| {
+                import
+                    .what
+                    .iter()
+                    .any(|what| rust_struct_or_enum_name(what) == name)
+            })
+            .filter(|_| !model.definitions.iter().any(|d| d.0 == name))
+            .map_or(&model.name, |import| &import.from);
+        format!(
+            "super::{}::{}",
+            RustCodeGenerator::rust_module_name(module),
+            name
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn generate_header(asn: &str) -> String {
+        let model = Model::try_from(Tokenizer.parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust();
+        CGenerator::generate_header(&model).1
+    }
+
+    #[test]
+    pub fn test_definitions_are_declared_before_use() {
+        let header = generate_header(
+            r"Routes DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            IMPORTS Position FROM Geo;
+
+            Route ::= SEQUENCE {
+                waypoints  SEQUENCE OF Waypoint,
+                next       Route OPTIONAL,
+                int        INTEGER (-5..5)
+            }
+
+            Waypoint ::= SEQUENCE {
+                position  Position,
+                names     SEQUENCE OF UTF8String OPTIONAL
+            }
+
+            Empty ::= SEQUENCE { }
+
+            END",
+        );
+        let declarations = header
+            .split("extern \"C\" {\n#endif\n\n")
+            .nth(1)
+            .unwrap()
+            .split("int32_t")
+            .next()
+            .unwrap();
+        assert_eq!(
+            r"typedef struct Route Route;
+typedef struct Waypoint Waypoint;
+typedef struct Empty Empty;
+
+struct Waypoint {
+    Position position;
+    struct { AsnString *data; size_t len; } *names;
+};
+
+struct Route {
+    struct { Waypoint *data; size_t len; } waypoints;
+    Route **next;
+    int8_t int_;
+};
+
+struct Empty {
+    uint8_t _empty;
+};
+
+",
+            declarations
+        );
+        assert!(header.starts_with(
+            "/* Generated by asn1rs from the ASN.1 module routes, implemented in routes_ffi.rs */\n\
+            #ifndef ASN1RS_ROUTES_H\n#define ASN1RS_ROUTES_H\n"
+        ));
+        assert!(header.contains("#include <stdint.h>\n\n#include \"geo.h\"\n\n"));
+        assert!(header.contains(
+            "void routes_empty_free(Empty *value);\n\n#ifdef __cplusplus\n}\n#endif\n\n\
+            #endif /* ASN1RS_ROUTES_H */\n"
+        ));
+    }
+}
@@ -0,0 +1,392 @@
+//! Generates a dictionary per PDU in the format of AFL and libFuzzer (`-dict=...`). The
+//! dictionary contains the byte sequences that are interesting for the decoders of a PDU: the
+//! identifier octets of all (nested) types, the boundaries of the length determinants,
+//! enumeration and choice indices and the bounds of integer and size constraints. Mutating
+//! inputs with these tokens gets fuzzers past tag and length checks much faster than random
+//! bytes do.
+
+use crate::asn::{Tag, TagProperty};
+use crate::generate::Generator;
+use crate::model::{Definition, Model};
+use crate::rust::{rust_module_name, EncodingOrdering, Rust, RustType, UUID_SIZE};
+use std::collections::HashSet;
+use std::convert::Infallible;
+use std::fmt::Write;
+
+/// Lengths at which the DER length octets change their form or size, ITU-T X.690, 8.1.3
+const DER_LENGTH_BOUNDARIES: [(&str, &[u8]); 7] = [
+    ("der_length_127", &[0x7F]),
+    ("der_length_128", &[0x81, 0x80]),
+    ("der_length_255", &[0x81, 0xFF]),
+    ("der_length_256", &[0x82, 0x01, 0x00]),
+    ("der_length_65535", &[0x82, 0xFF, 0xFF]),
+    ("der_length_65536", &[0x83, 0x01, 0x00, 0x00]),
+    ("der_length_max", &[0x84, 0xFF, 0xFF, 0xFF, 0xFF]),
+];
+
+/// Lengths at which the unconstrained UPER length determinant changes its form,
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.9
+const UPER_LENGTH_BOUNDARIES: [(&str, &[u8]); 5] = [
+    ("uper_length_127", &[0x7F]),
+    ("uper_length_128", &[0x80, 0x80]),
+    ("uper_length_16383", &[0xBF, 0xFF]),
+    ("uper_fragment_16k", &[0xC1]),
+    ("uper_fragment_64k", &[0xC4]),
+];
+
+#[derive(Debug, Default)]
+pub struct FuzzDictionaryGenerator {
+    models: Vec<Model<Rust>>,
+}
+
+impl Generator<Rust> for FuzzDictionaryGenerator {
+    type Error = Infallible;
+
+    fn add_model(&mut self, model: Model<Rust>) {
+        self.models.push(model);
+    }
+
+    fn models(&self) -> &[Model<Rust>] {
+        &self.models[..]
+    }
+
+    fn models_mut(&mut self) -> &mut [Model<Rust>] {
+        &mut self.models[..]
+    }
+
+    fn to_string(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self
+            .models
+            .iter()
+            .flat_map(|model| {
+                model.definitions.iter().map(move |definition| {
+                    (
+                        Self::dictionary_file_name(&model.name, &definition.0),
+                        Self::generate_dictionary(model, definition),
+                    )
+                })
+            })
+            .collect())
+    }
+}
+
+impl FuzzDictionaryGenerator {
+    pub fn dictionary_file_name(model: &str, pdu: &str) -> String {
+        format!(
+            "{}_{}.dict",
+            rust_module_name(model, false),
+            rust_module_name(pdu, false)
+        )
+    }
+
+    /// The dictionary for the given PDU, including the entries of all types it refers to
+    /// within the same model
+    pub fn generate_dictionary(model: &Model<Rust>, pdu: &Definition<Rust>) -> String {
+        let mut dictionary = Dictionary::new(model);
+        for (name, bytes) in DER_LENGTH_BOUNDARIES
+            .iter()
+            .chain(UPER_LENGTH_BOUNDARIES.iter())
+        {
+            dictionary.add(name, bytes.to_vec());
+        }
+        dictionary.add_definition(&pdu.0, &pdu.1);
+
+        let mut content = String::new();
+        let _ = writeln!(
+            content,
+            "# Dictionary for {}.{}, generated by asn1rs",
+            model.name, pdu.0
+        );
+        for (name, bytes) in &dictionary.entries {
+            let _ = writeln!(content, "{}=\"{}\"", name, escape(bytes));
+        }
+        content
+    }
+}
+
+struct Dictionary<'a> {
+    model: &'a Model<Rust>,
+    entries: Vec<(String, Vec<u8>)>,
+    known: HashSet<Vec<u8>>,
+    visited: HashSet<&'a str>,
+}
+
+impl<'a> Dictionary<'a> {
+    fn new(model: &'a Model<Rust>) -> Self {
+        Self {
+            model,
+            entries: Vec::new(),
+            known: HashSet::new(),
+            visited: HashSet::new(),
+        }
+    }
+
+    /// Adds the entry, unless there is already one with the same bytes
+    fn add(&mut self, name: &str, bytes: Vec<u8>) {
+        if !bytes.is_empty() && self.known.insert(bytes.clone()) {
+            let name = name
+                .chars()
+                .map(|c| if c.is_ascii_alphanumeric() { c } else { '_' })
+                .collect();
+            self.entries.push((name, bytes));
+        }
+    }
+
+    fn add_definition(&mut self, name: &'a str, rust: &'a Rust) {
+        if !self.visited.insert(name) {
+            return;
+        }
+        match rust {
+            Rust::Struct {
+                ordering,
+                fields,
+                tag,
+                extension_after,
+                ..
+            } => {
+                let tag = tag.unwrap_or(match ordering {
+                    EncodingOrdering::Keep => Tag::DEFAULT_SEQUENCE,
+                    EncodingOrdering::Sort => Tag::DEFAULT_SET,
+                });
+                self.add(&format!("{}_tag", name), identifier(tag, true));
+                if extension_after.is_some() {
+                    // the extension bit is the first bit of an extensible SEQUENCE or SET
+                    self.add(&format!("{}_extended", name), vec![0x80]);
+                }
+                for field in fields {
+                    self.add_type(
+                        &format!("{}_{}", name, field.name()),
+                        field.r#type(),
+                        field.tag(),
+                    );
+                }
+            }
+            Rust::Enum(enumeration) => {
+                let tag = enumeration.tag().unwrap_or(Tag::DEFAULT_ENUMERATED);
+                for (index, variant) in enumeration.variants().enumerate() {
+                    let content = integer(index as i128);
+                    let mut tlv = identifier(tag, false);
+                    tlv.push(content.len() as u8);
+                    tlv.extend(content);
+                    self.add(&format!("{}_{}_index", name, variant), vec![index as u8]);
+                    self.add(&format!("{}_{}", name, variant), tlv);
+                }
+            }
+            Rust::DataEnum(enumeration) => {
+                for (index, variant) in enumeration.variants().enumerate() {
+                    let name = format!("{}_{}", name, variant.name());
+                    self.add(&format!("{}_index", name), vec![index as u8]);
+                    if let Some(tag) = variant.tag() {
+                        // CHOICE alternatives are explicitly tagged
+                        self.add(&format!("{}_tag", name), identifier(tag, true));
+                    }
+                    self.add_type(&name, variant.r#type(), None);
+                }
+            }
+            Rust::TupleStruct { r#type, tag, .. } => self.add_type(name, r#type, *tag),
+        }
+    }
+
+    fn add_type(&mut self, name: &str, r#type: &'a RustType, tag: Option<Tag>) {
+        if let Some(tag) = tag.or_else(|| r#type.tag()) {
+            let constructed = self.is_constructed(r#type);
+            self.add(&format!("{}_tag", name), identifier(tag, constructed));
+        }
+
+        if let Some(range) = r#type.integer_range_str() {
+            if let (Ok(min), Ok(max)) = (range.min().parse::<i128>(), range.max().parse::<i128>()) {
+                self.add(&format!("{}_min", name), integer(min));
+                self.add(&format!("{}_max", name), integer(max));
+                self.add(&format!("{}_above_max", name), integer(max + 1));
+            }
+        }
+
+        match r#type {
+            RustType::String(size, _, alphabet) => {
+                self.add_size(name, size.min(), size.max());
+                if let Some(alphabet) = alphabet {
+                    self.add(
+                        &format!("{}_alphabet", name),
+                        alphabet.as_string().into_bytes(),
+                    );
+                }
+            }
+            RustType::VecU8(size) | RustType::BitVec(size) => {
+                self.add_size(name, size.min(), size.max())
+            }
+            RustType::Uuid => self.add_size(name, UUID_SIZE.min(), UUID_SIZE.max()),
+            RustType::Vec(inner, size, _) => {
+                self.add_size(name, size.min(), size.max());
+                self.add_type(&format!("{}_item", name), inner, None);
+            }
+            RustType::Option(inner) | RustType::Default(inner, _) => {
+                self.add_type(name, inner, None)
+            }
+            RustType::Complex(complex, _) => {
+                if let Some(Definition(name, rust)) = self
+                    .model
+                    .definitions
+                    .iter()
+                    .find(|Definition(name, _)| name == complex)
+                {
+                    self.add_definition(name, rust);
+                }
+            }
+            _ => {}
+        }
+    }
+
+    fn is_constructed(&self, r#type: &RustType) -> bool {
+        match r#type.as_no_option() {
+            RustType::Vec(..) => true,
+            RustType::Complex(complex, _) => self
+                .model
+                .definitions
+                .iter()
+                .find(|Definition(name, _)| name == complex)
+                .is_some_and(|Definition(_, rust)| match rust {
+                    Rust::Struct { .. } | Rust::DataEnum(_) => true,
+                    Rust::Enum(_) => false,
+                    Rust::TupleStruct { r#type, .. } => self.is_constructed(r#type),
+                }),
+            _ => false,
+        }
+    }
+
+    fn add_size(&mut self, name: &str, min: Option<&usize>, max: Option<&usize>) {
+        if let Some(min) = min {
+            self.add(&format!("{}_size_min", name), integer(*min as i128));
+        }
+        if let Some(max) = max {
+            self.add(&format!("{}_size_max", name), integer(*max as i128));
+            self.add(
+                &format!("{}_size_above_max", name),
+                integer(*max as i128 + 1),
+            );
+        }
+    }
+}
+
+/// The identifier octets of the tag, ITU-T X.690, 8.1.2
+fn identifier(tag: Tag, constructed: bool) -> Vec<u8> {
+    let (class, number) = match tag {
+        Tag::Universal(number) => (0x00, number),
+        Tag::Application(number) => (0x40, number),
+        Tag::ContextSpecific(number) => (0x80, number),
+        Tag::Private(number) => (0xC0, number),
+    };
+    let first = class | if constructed { 0x20 } else { 0x00 };
+    if number < 31 {
+        vec![first | number as u8]
+    } else {
+        let mut octets = vec![first | 0x1F];
+        let groups = (usize::BITS - number.leading_zeros()).div_ceil(7) as usize;
+        for group in (0..groups).rev() {
+            let more = if group > 0 { 0x80 } else { 0x00 };
+            octets.push(more | ((number >> (group * 7)) & 0x7F) as u8);
+        }
+        octets
+    }
+}
+
+/// The minimal two's complement big-endian representation, as used by DER and UPER
+fn integer(value: i128) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let skip = bytes
+        .windows(2)
+        .take_while(|w| (w[0] == 0x00 && w[1] < 0x80) || (w[0] == 0xFF && w[1] >= 0x80))
+        .count();
+    bytes[skip..].to_vec()
+}
+
+fn escape(bytes: &[u8]) -> String {
+    bytes
+        .iter()
+        .map(|byte| match byte {
+            b'"' | b'\\' => format!("\\{}", char::from(*byte)),
+            0x20..=0x7E => char::from(*byte).to_string(),
+            _ => format!("\\x{:02x}", byte),
+        })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn dictionary(asn: &str, pdu: &str) -> String {
+        let model = Model::try_from(Tokenizer::default().parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust();
+        let definition = model
+            .definitions
+            .iter()
+            .find(|Definition(name, _)| name == pdu)
+            .unwrap();
+        FuzzDictionaryGenerator::generate_dictionary(&model, definition)
+    }
+
+    #[test]
+    fn test_nested_entries() {
+        let dictionary = dictionary(
+            r#"Messages DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+            Color ::= ENUMERATED { red, green, blue }
+
+            Message ::= [APPLICATION 40] SEQUENCE {
+                id INTEGER (-1..300),
+                color Color,
+                name UTF8String (SIZE(1..8)),
+                ...
+            }
+
+            END"#,
+            "Message",
+        );
+        let lines = dictionary.lines().collect::<Vec<_>>();
+        assert_eq!(
+            "# Dictionary for messages.Message, generated by asn1rs",
+            lines[0]
+        );
+        assert!(lines.contains(&"der_length_128=\"\\x81\\x80\""));
+        assert!(lines.contains(&"Message_tag=\"\\x7f(\""));
+        assert!(lines.contains(&"Message_extended=\"\\x80\""));
+        assert!(lines.contains(&"Message_id_min=\"\\xff\""));
+        assert!(lines.contains(&"Message_id_above_max=\"\\x01-\""));
+        assert!(lines.contains(&"Message_color_tag=\"\\x0a\""));
+        assert!(lines.contains(&"Color_Green=\"\\x0a\\x01\\x01\""));
+        assert!(lines.contains(&"Message_name_size_above_max=\"\\x09\""));
+    }
+
+    #[test]
+    fn test_file_names() {
+        assert_eq!(
+            "my_module_the_pdu.dict",
+            FuzzDictionaryGenerator::dictionary_file_name("MyModule", "ThePdu")
+        );
+    }
+
+    #[test]
+    fn test_identifier() {
+        assert_eq!(vec![0x30], identifier(Tag::DEFAULT_SEQUENCE, true));
+        assert_eq!(
+            vec![0x9F, 0x1F],
+            identifier(Tag::ContextSpecific(31), false)
+        );
+        assert_eq!(
+            vec![0x5F, 0x81, 0x00],
+            identifier(Tag::Application(128), false)
+        );
+    }
+
+    #[test]
+    fn test_integer() {
+        assert_eq!(vec![0x00], integer(0));
+        assert_eq!(vec![0x00, 0x80], integer(128));
+        assert_eq!(vec![0xFF], integer(-1));
+        assert_eq!(vec![0xFF, 0x7F], integer(-129));
+    }
+}
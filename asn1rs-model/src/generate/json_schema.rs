@@ -0,0 +1,607 @@
+//! Generates a JSON Schema (draft 2020-12) per module, which describes the JSON Encoding Rules
+//! (JER) representation of its definitions as written by the `JerWriter`. Each definition is
+//! placed in `$defs`, so that a value is validated against `<file>#/$defs/<Type>`, where
+//! `<Type>` is the name of the generated Rust type.
+//!
+//! Integer ranges become `minimum` and `maximum`, `SIZE` constraints become `minLength` and
+//! `maxLength` (for OCTET STRINGs in hex digits) or `minItems` and `maxItems`. Bounds of
+//! extensible constraints are omitted, as are `additionalProperties: false` and the `enum`
+//! values for extensible types, so that values of newer versions of a module are accepted.
+
+use crate::asn::Size;
+use crate::generate::walker::AsnDefWriter;
+use crate::generate::Generator;
+use crate::model::{Definition, LiteralValue, Model};
+use crate::rust::{rust_module_name, rust_struct_or_enum_name, Rust, RustType, UUID_SIZE};
+use std::convert::Infallible;
+use std::fmt::Write;
+
+pub const JSON_SCHEMA_DIALECT: &str = "https://json-schema.org/draft/2020-12/schema";
+
+/// OCTET STRINGs and the value of BIT STRINGs are written as hex digits
+const HEX_PATTERN: &str = "^([0-9a-fA-F]{2})*$";
+
+#[allow(clippy::module_name_repetitions)]
+#[derive(Debug, Default)]
+pub struct JsonSchemaGenerator {
+    models: Vec<Model<Rust>>,
+}
+
+impl Generator<Rust> for JsonSchemaGenerator {
+    type Error = Infallible;
+
+    fn add_model(&mut self, model: Model<Rust>) {
+        self.models.push(model);
+    }
+
+    fn models(&self) -> &[Model<Rust>] {
+        &self.models[..]
+    }
+
+    fn models_mut(&mut self) -> &mut [Model<Rust>] {
+        &mut self.models[..]
+    }
+
+    fn to_string(&self) -> Result<Vec<(String, String)>, Self::Error> {
+        Ok(self.models.iter().map(Self::generate_file).collect())
+    }
+}
+
+impl JsonSchemaGenerator {
+    pub fn schema_file_name(model: &str) -> String {
+        format!("{}.schema.json", rust_module_name(model, false))
+    }
+
+    pub fn generate_file(model: &Model<Rust>) -> (String, String) {
+        let file_name = Self::schema_file_name(&model.name);
+        let schema = Json::object(vec![
+            ("$schema", Json::string(JSON_SCHEMA_DIALECT)),
+            ("$id", Json::string(&file_name)),
+            ("title", Json::string(&model.name)),
+            (
+                "$defs",
+                Json::Object(
+                    model
+                        .definitions
+                        .iter()
+                        .map(|Definition(name, rust)| {
+                            (name.clone(), Self::definition_schema(model, rust))
+                        })
+                        .collect(),
+                ),
+            ),
+        ]);
+        let mut content = String::new();
+        schema.write(&mut content, 0);
+        content.push('\n');
+        (file_name, content)
+    }
+
+    fn definition_schema(model: &Model<Rust>, rust: &Rust) -> Json {
+        match rust {
+            Rust::Struct {
+                fields,
+                extension_after,
+                ..
+            } => {
+                let mut schema = vec![
+                    ("type", Json::string("object")),
+                    (
+                        "properties",
+                        Json::Object(
+                            fields
+                                .iter()
+                                .map(|field| {
+                                    (
+                                        AsnDefWriter::asn_identifier(field.name()),
+                                        Self::type_schema(model, field.r#type()),
+                                    )
+                                })
+                                .collect(),
+                        ),
+                    ),
+                ];
+                let required = fields
+                    .iter()
+                    .filter(|field| {
+                        !matches!(field.r#type(), RustType::Option(_) | RustType::Default(..))
+                    })
+                    .map(|field| Json::string(&AsnDefWriter::asn_identifier(field.name())))
+                    .collect::<Vec<_>>();
+                if !required.is_empty() {
+                    schema.push(("required", Json::Array(required)));
+                }
+                if extension_after.is_none() {
+                    schema.push(("additionalProperties", Json::Boolean(false)));
+                }
+                Json::object(schema)
+            }
+            Rust::Enum(plain) => {
+                let mut schema = vec![("type", Json::string("string"))];
+                if !plain.is_extensible() {
+                    schema.push((
+                        "enum",
+                        Json::Array(
+                            plain
+                                .variants()
+                                .map(|variant| Json::string(&AsnDefWriter::asn_identifier(variant)))
+                                .collect(),
+                        ),
+                    ));
+                }
+                Json::object(schema)
+            }
+            Rust::DataEnum(data) => {
+                let alternatives = data
+                    .variants()
+                    .map(|variant| {
+                        let name = AsnDefWriter::asn_identifier(variant.name());
+                        Json::object(vec![
+                            ("type", Json::string("object")),
+                            (
+                                "properties",
+                                Json::Object(vec![(
+                                    name.clone(),
+                                    Self::type_schema(model, variant.r#type()),
+                                )]),
+                            ),
+                            ("required", Json::Array(vec![Json::string(&name)])),
+                            ("additionalProperties", Json::Boolean(false)),
+                        ])
+                    })
+                    .collect::<Vec<_>>();
+                if data.is_extensible() {
+                    // unknown alternatives of newer versions have a single member as well
+                    Json::object(vec![
+                        ("type", Json::string("object")),
+                        ("minProperties", Json::number(1)),
+                        ("maxProperties", Json::number(1)),
+                    ])
+                } else {
+                    Json::object(vec![("oneOf", Json::Array(alternatives))])
+                }
+            }
+            Rust::TupleStruct { r#type, .. } => Self::type_schema(model, r#type),
+        }
+    }
+
+    fn type_schema(model: &Model<Rust>, r#type: &RustType) -> Json {
+        let integer = |min: Option<String>, max: Option<String>, extensible: bool| {
+            let mut schema = vec![("type", Json::string("integer"))];
+            if !extensible {
+                schema.extend(min.map(|min| ("minimum", Json::Number(min))));
+                schema.extend(max.map(|max| ("maximum", Json::Number(max))));
+            }
+            Json::object(schema)
+        };
+        match r#type {
+            RustType::Bool => Json::object(vec![("type", Json::string("boolean"))]),
+            RustType::I8(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::U8(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::I16(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::U16(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::I32(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::U32(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::I64(range) => integer(
+                Some(range.min().to_string()),
+                Some(range.max().to_string()),
+                range.extensible(),
+            ),
+            RustType::U64(range) => integer(
+                Some(range.min().unwrap_or_default().to_string()),
+                range.max().map(|max| max.to_string()),
+                range.extensible(),
+            ),
+            RustType::I128(range) => integer(
+                range.min().map(|min| min.to_string()),
+                range.max().map(|max| max.to_string()),
+                range.extensible(),
+            ),
+            RustType::U128(range) => integer(
+                Some(range.min().unwrap_or_default().to_string()),
+                range.max().map(|max| max.to_string()),
+                range.extensible(),
+            ),
+            RustType::String(size, ..) => {
+                let mut schema = vec![("type", Json::string("string"))];
+                Self::append_size(&mut schema, size, "minLength", "maxLength", 1);
+                Json::object(schema)
+            }
+            RustType::VecU8(size) => {
+                let mut schema = vec![
+                    ("type", Json::string("string")),
+                    ("pattern", Json::string(HEX_PATTERN)),
+                ];
+                Self::append_size(&mut schema, size, "minLength", "maxLength", 2);
+                Json::object(schema)
+            }
+            RustType::Uuid => Self::type_schema(model, &RustType::VecU8(UUID_SIZE)),
+            RustType::BitVec(size) => {
+                let mut length = vec![("type", Json::string("integer"))];
+                Self::append_size(&mut length, size, "minimum", "maximum", 1);
+                if size.min().is_none() {
+                    length.push(("minimum", Json::number(0)));
+                }
+                Json::object(vec![
+                    ("type", Json::string("object")),
+                    (
+                        "properties",
+                        Json::object(vec![
+                            (
+                                "value",
+                                Json::object(vec![
+                                    ("type", Json::string("string")),
+                                    ("pattern", Json::string(HEX_PATTERN)),
+                                ]),
+                            ),
+                            ("length", Json::object(length)),
+                        ]),
+                    ),
+                    (
+                        "required",
+                        Json::Array(vec![Json::string("value"), Json::string("length")]),
+                    ),
+                    ("additionalProperties", Json::Boolean(false)),
+                ])
+            }
+            RustType::Vec(inner, size, _) => {
+                let mut schema = vec![
+                    ("type", Json::string("array")),
+                    ("items", Self::type_schema(model, inner)),
+                ];
+                Self::append_size(&mut schema, size, "minItems", "maxItems", 1);
+                Json::object(schema)
+            }
+            RustType::Null => Json::object(vec![("type", Json::string("null"))]),
+            RustType::Option(inner) | RustType::Box(inner) => Self::type_schema(model, inner),
+            RustType::Default(inner, default) => {
+                let schema = Self::type_schema(model, inner);
+                match (schema, Self::literal(default)) {
+                    (Json::Object(mut members), Some(default)) => {
+                        members.push(("default".to_string(), default));
+                        Json::Object(members)
+                    }
+                    (schema, _) => schema,
+                }
+            }
+            RustType::Complex(name, _) => {
+                Json::object(vec![("$ref", Json::String(Self::reference(model, name)))])
+            }
+        }
+    }
+
+    /// The reference to the definition, which is either defined in or imported by the model
+    fn reference(model: &Model<Rust>, name: &str) -> String {
+        model
+            .imports
+            .iter()
+            .find(|import| {
+                import
+                    .what
+                    .iter()
+                    .any(|what| rust_struct_or_enum_name(what) == name)
+            })
+            .filter(|_| !model.definitions.iter().any(|d| d.0 == name))
+            .map(|import| format!("{}#/$defs/{}", Self::schema_file_name(&import.from), name))
+            .unwrap_or_else(|| format!("#/$defs/{}", name))
+    }
+
+    fn append_size(
+        schema: &mut Vec<(&'static str, Json)>,
+        size: &Size,
+        min_key: &'static str,
+        max_key: &'static str,
+        factor: usize,
+    ) {
+        if !size.extensible() {
+            schema.extend(size.min().map(|min| (min_key, Json::number(min * factor))));
+            schema.extend(size.max().map(|max| (max_key, Json::number(max * factor))));
+        }
+    }
+
+    fn literal(value: &LiteralValue) -> Option<Json> {
+        Some(match value {
+            LiteralValue::Boolean(value) => Json::Boolean(*value),
+            LiteralValue::String(value) => Json::string(value),
+            LiteralValue::Integer(value) => Json::number(value),
+            LiteralValue::OctetString(value) => {
+                Json::String(value.iter().fold(String::new(), |mut hex, byte| {
+                    let _ = write!(hex, "{:02x}", byte);
+                    hex
+                }))
+            }
+            LiteralValue::EnumeratedVariant(_, variant) => {
+                Json::String(AsnDefWriter::asn_identifier(variant))
+            }
+        })
+    }
+}
+
+/// A minimal JSON document with the members in the order they are written
+#[derive(Debug, Clone, PartialEq)]
+enum Json {
+    Object(Vec<(String, Json)>),
+    Array(Vec<Json>),
+    String(String),
+    Number(String),
+    Boolean(bool),
+}
+
+impl Json {
+    fn string(value: &str) -> Self {
+        Json::String(value.to_string())
+    }
+
+    fn number(value: impl ToString) -> Self {
+        Json::Number(value.to_string())
+    }
+
+    fn object(members: Vec<(&str, Json)>) -> Self {
+        Json::Object(
+            members
+                .into_iter()
+                .map(|(key, value)| (key.to_string(), value))
+                .collect(),
+        )
+    }
+
+    /// Writes the value indented by two spaces per level, arrays of primitives on a single line
+    fn write(&self, out: &mut String, level: usize) {
+        match self {
+            Json::Object(members) if members.is_empty() => out.push_str("{}"),
+            Json::Object(members) => {
+                out.push('{');
+                for (index, (key, value)) in members.iter().enumerate() {
+                    out.push_str(if index > 0 { ",\n" } else { "\n" });
+                    Self::indent(out, level + 1);
+                    Self::write_string(out, key);
+                    out.push_str(": ");
+                    value.write(out, level + 1);
+                }
+                out.push('\n');
+                Self::indent(out, level);
+                out.push('}');
+            }
+            Json::Array(items) if items.iter().all(Json::is_primitive) => {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    if index > 0 {
+                        out.push_str(", ");
+                    }
+                    item.write(out, level);
+                }
+                out.push(']');
+            }
+            Json::Array(items) => {
+                out.push('[');
+                for (index, item) in items.iter().enumerate() {
+                    out.push_str(if index > 0 { ",\n" } else { "\n" });
+                    Self::indent(out, level + 1);
+                    item.write(out, level + 1);
+                }
+                out.push('\n');
+                Self::indent(out, level);
+                out.push(']');
+            }
+            Json::String(value) => Self::write_string(out, value),
+            Json::Number(value) => out.push_str(value),
+            Json::Boolean(value) => out.push_str(if *value { "true" } else { "false" }),
+        }
+    }
+
+    fn write_string(out: &mut String, value: &str) {
+        out.push('"');
+        for c in value.chars() {
+            match c {
+                '"' => out.push_str("\\\""),
+                '\\' => out.push_str("\\\\"),
+                c if u32::from(c) < 0x20 => {
+                    let _ = write!(out, "\\u{:04x}", u32::from(c));
+                }
+                c => out.push(c),
+            }
+        }
+        out.push('"');
+    }
+
+    fn indent(out: &mut String, level: usize) {
+        for _ in 0..level {
+            out.push_str("  ");
+        }
+    }
+
+    fn is_primitive(&self) -> bool {
+        matches!(self, Json::String(_) | Json::Number(_) | Json::Boolean(_))
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn generate(asn: &str) -> String {
+        let model = Model::try_from(Tokenizer.parse(asn))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust();
+        JsonSchemaGenerator::generate_file(&model).1
+    }
+
+    #[test]
+    pub fn test_sequence_with_constraints() {
+        assert_eq!(
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "gateway.schema.json",
+  "title": "gateway",
+  "$defs": {
+    "Message": {
+      "type": "object",
+      "properties": {
+        "msgId": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 65535
+        },
+        "status": {
+          "$ref": "#/$defs/Status"
+        },
+        "payload": {
+          "type": "string",
+          "pattern": "^([0-9a-fA-F]{2})*$",
+          "minLength": 2,
+          "maxLength": 16
+        },
+        "tags": {
+          "type": "array",
+          "items": {
+            "type": "string",
+            "minLength": 0,
+            "maxLength": 12
+          },
+          "minItems": 1,
+          "maxItems": 4
+        },
+        "retries": {
+          "type": "integer",
+          "minimum": 0,
+          "maximum": 7,
+          "default": 3
+        },
+        "comment": {
+          "type": "string"
+        }
+      },
+      "required": ["msgId", "status", "payload", "tags"],
+      "additionalProperties": false
+    },
+    "Status": {
+      "type": "string",
+      "enum": ["idle", "busy", "offline"]
+    }
+  }
+}
+"##,
+            generate(
+                r"Gateway DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                Message ::= SEQUENCE {
+                    msg-id INTEGER (0..65535),
+                    status Status,
+                    payload OCTET STRING (SIZE(1..8)),
+                    tags SEQUENCE (SIZE(1..4)) OF IA5String (SIZE(0..12)),
+                    retries INTEGER (0..7) DEFAULT 3,
+                    comment UTF8String OPTIONAL
+                }
+                Status ::= ENUMERATED { idle, busy, offline }
+                END"
+            )
+        );
+    }
+
+    #[test]
+    pub fn test_choice_bit_string_and_extensibility() {
+        assert_eq!(
+            r##"{
+  "$schema": "https://json-schema.org/draft/2020-12/schema",
+  "$id": "shapes.schema.json",
+  "title": "shapes",
+  "$defs": {
+    "Shape": {
+      "oneOf": [
+        {
+          "type": "object",
+          "properties": {
+            "flags": {
+              "type": "object",
+              "properties": {
+                "value": {
+                  "type": "string",
+                  "pattern": "^([0-9a-fA-F]{2})*$"
+                },
+                "length": {
+                  "type": "integer",
+                  "minimum": 8,
+                  "maximum": 8
+                }
+              },
+              "required": ["value", "length"],
+              "additionalProperties": false
+            }
+          },
+          "required": ["flags"],
+          "additionalProperties": false
+        },
+        {
+          "type": "object",
+          "properties": {
+            "nothing": {
+              "type": "null"
+            }
+          },
+          "required": ["nothing"],
+          "additionalProperties": false
+        },
+        {
+          "type": "object",
+          "properties": {
+            "imported": {
+              "$ref": "other.schema.json#/$defs/Imported"
+            }
+          },
+          "required": ["imported"],
+          "additionalProperties": false
+        }
+      ]
+    },
+    "Open": {
+      "type": "object",
+      "properties": {
+        "value": {
+          "type": "integer"
+        }
+      },
+      "required": ["value"]
+    },
+    "Kind": {
+      "type": "string"
+    }
+  }
+}
+"##,
+            generate(
+                r"Shapes DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                IMPORTS Imported FROM Other;
+                Shape ::= CHOICE { flags BIT STRING (SIZE(8)), nothing NULL, imported Imported }
+                Open ::= SEQUENCE { value INTEGER (0..7,...), ... }
+                Kind ::= ENUMERATED { small, ... }
+                END"
+            )
+        );
+    }
+}
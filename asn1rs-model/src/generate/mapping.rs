@@ -0,0 +1,147 @@
+//! The line based format of the mapping files, that configure the generators per type, such as
+//! [`super::rename::Renames`] and [`super::attributes::Attributes`].
+//!
+//! Each line assigns a value to a type or to one of its members in the form
+//! `Type[.member] = value`, where `Type` and `member` are ASN.1 names. Empty lines and lines
+//! starting with `#` are ignored.
+
+use crate::rust::rust_struct_or_enum_name;
+use std::collections::HashMap;
+
+/// A line of a mapping file, that is neither empty nor a comment
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Line<'a> {
+    /// The 1-based number of the line
+    pub number: usize,
+    /// The content of the line, without surrounding whitespace
+    pub content: &'a str,
+}
+
+impl<'a> Line<'a> {
+    /// Splits the line of the form `Type[.member] = value` into the name of the type, the name of
+    /// the member and the value, all without surrounding whitespace. Returns `None` if the line is
+    /// not of this form or the names are not valid identifiers.
+    pub fn split(&self) -> Option<(&'a str, Option<&'a str>, &'a str)> {
+        let (target, value) = self.content.split_once('=')?;
+        let (definition, member) = match target.split_once('.') {
+            Some((definition, member)) => (definition.trim(), Some(member.trim())),
+            None => (target.trim(), None),
+        };
+        if is_identifier(definition) && member.into_iter().all(is_identifier) {
+            Some((definition, member, value.trim()))
+        } else {
+            None
+        }
+    }
+
+    /// Like [`Line::split`], but for lines which require a member
+    pub fn split_member(&self) -> Option<(&'a str, &'a str, &'a str)> {
+        match self.split()? {
+            (definition, Some(member), value) => Some((definition, member, value)),
+            (_, None, _) => None,
+        }
+    }
+}
+
+/// The lines of the mapping, that are neither empty nor comments
+pub fn lines(mapping: &str) -> impl Iterator<Item = Line<'_>> {
+    mapping
+        .lines()
+        .enumerate()
+        .map(|(index, line)| Line {
+            number: index + 1,
+            content: line.trim(),
+        })
+        .filter(|line| !line.content.is_empty() && !line.content.starts_with('#'))
+}
+
+/// Whether the name is a non-empty ASN.1 identifier or type reference
+pub fn is_identifier(name: &str) -> bool {
+    !name.is_empty()
+        && name
+            .chars()
+            .all(|c| c.is_ascii_alphanumeric() || c == '-' || c == '_')
+}
+
+/// The entries of a mapping grouped by the type they belong to, which can be looked up by the
+/// ASN.1 or the generated name of the type
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct PerDefinition<T>(HashMap<String, Vec<T>>);
+
+impl<T> Default for PerDefinition<T> {
+    fn default() -> Self {
+        Self(HashMap::default())
+    }
+}
+
+impl<T> PerDefinition<T> {
+    pub fn push(&mut self, definition: &str, entry: T) {
+        self.0
+            .entry(rust_struct_or_enum_name(definition))
+            .or_default()
+            .push(entry);
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn of(&self, definition: &str) -> &[T] {
+        self.0
+            .get(&rust_struct_or_enum_name(definition))
+            .map(Vec::as_slice)
+            .unwrap_or_default()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_lines_skip_empty_lines_and_comments() {
+        assert_eq!(
+            vec![
+                Line {
+                    number: 3,
+                    content: "Message.id = 1",
+                },
+                Line {
+                    number: 5,
+                    content: "Status=x",
+                }
+            ],
+            lines("\n  # comment\n  Message.id = 1  \n\nStatus=x").collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_split() {
+        let line = |content| Line { number: 1, content };
+        assert_eq!(
+            Some(("Message", Some("msg-id"), "a = b")),
+            line("Message . msg-id = a = b").split()
+        );
+        assert_eq!(Some(("Message", None, "")), line("Message =").split());
+        assert_eq!(None, line("Message").split());
+        assert_eq!(None, line("Message.msg Id = id").split());
+        assert_eq!(None, line("Message. = id").split());
+        assert_eq!(None, line(".id = id").split());
+        assert_eq!(None, line("Message = id").split_member());
+        assert_eq!(
+            Some(("Message", "id", "1")),
+            line("Message.id = 1").split_member()
+        );
+    }
+
+    #[test]
+    pub fn test_per_definition() {
+        let mut entries = PerDefinition::default();
+        assert!(entries.is_empty());
+        entries.push("my-message", 1);
+        entries.push("MyMessage", 2);
+        assert_eq!(&[1, 2], entries.of("my-message"));
+        assert_eq!(&[1, 2], entries.of("MyMessage"));
+        assert!(entries.of("Unknown").is_empty());
+    }
+}
@@ -1,10 +1,33 @@
+pub mod arbitrary;
+pub mod asn1;
+pub mod attributes;
+pub mod c;
+pub mod fuzz_dict;
+pub mod json_schema;
+pub(crate) mod mapping;
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
+pub mod pyo3;
+pub mod rename;
 pub mod rust;
 pub mod walker;
 
 pub use self::rust::RustCodeGenerator;
 
+/// The codecs a [`RustCodeGenerator`] generates the codec specific code for. The `Readable` and
+/// `Writable` impls of the generated types are generic over the reader and writer and shared by
+/// UPER, DER and JER, so only protobuf has specific code that can be left out: the field numbers,
+/// fixed size integers and unknown fields in the `#[asn(..)]` attributes as well as the gRPC
+/// services.
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub enum Codec {
+    Protobuf,
+}
+
+impl Codec {
+    pub const ALL: [Codec; 1] = [Codec::Protobuf];
+}
+
 use crate::model::{Model, Target};
 
 pub trait Generator<T: Target> {
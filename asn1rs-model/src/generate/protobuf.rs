@@ -2,6 +2,8 @@ use crate::asn::{ObjectIdentifier, ObjectIdentifierComponent};
 use crate::generate::Generator;
 use crate::model::Definition;
 use crate::model::Model;
+use crate::protobuf::numbers::FieldNumbers;
+use crate::protobuf::services::Services;
 use crate::protobuf::{Protobuf, ProtobufType};
 use crate::rust::rust_module_name;
 use std::fmt::Error as FmtError;
@@ -22,6 +24,8 @@ impl From<FmtError> for Error {
 #[derive(Debug, Default)]
 pub struct ProtobufDefGenerator {
     models: Vec<Model<Protobuf>>,
+    field_numbers: FieldNumbers,
+    services: Services,
 }
 
 impl Generator<Protobuf> for ProtobufDefGenerator {
@@ -42,21 +46,42 @@ impl Generator<Protobuf> for ProtobufDefGenerator {
     fn to_string(&self) -> Result<Vec<(String, String)>, <Self as Generator<Protobuf>>::Error> {
         let mut files = Vec::new();
         for model in &self.models {
-            files.push(Self::generate_file(model)?);
+            files.push(self.generate_file(model)?);
         }
         Ok(files)
     }
 }
 
 impl ProtobufDefGenerator {
-    pub fn generate_file(model: &Model<Protobuf>) -> Result<(String, String), Error> {
+    pub const fn field_numbers(&self) -> &FieldNumbers {
+        &self.field_numbers
+    }
+
+    /// Numbers the fields of the messages and the alternatives of the `oneof`s as given instead
+    /// of by their position, the numbers of removed fields are declared as `reserved`
+    pub fn set_field_numbers(&mut self, field_numbers: FieldNumbers) {
+        self.field_numbers = field_numbers;
+    }
+
+    pub const fn services(&self) -> &Services {
+        &self.services
+    }
+
+    /// Declares the given services in the files of the modules that define the request type of
+    /// their first method, see [`crate::protobuf::services`]
+    pub fn set_services(&mut self, services: Services) {
+        self.services = services;
+    }
+
+    pub fn generate_file(&self, model: &Model<Protobuf>) -> Result<(String, String), Error> {
         let file_name = Self::model_file_name(&model.name);
         let mut content = String::new();
         Self::append_header(&mut content, model)?;
         Self::append_imports(&mut content, model)?;
         for definition in &model.definitions {
-            Self::append_definition(&mut content, model, definition)?;
+            self.append_definition(&mut content, model, definition)?;
         }
+        self.append_services(&mut content, model)?;
         Ok((file_name, content))
     }
 
@@ -80,6 +105,7 @@ impl ProtobufDefGenerator {
     }
 
     pub fn append_definition(
+        &self,
         target: &mut dyn Write,
         model: &Model<Protobuf>,
         Definition(name, protobuf): &Definition<Protobuf>,
@@ -94,21 +120,58 @@ impl ProtobufDefGenerator {
             }
             Protobuf::Message(fields) => {
                 writeln!(target, "message {} {{", name)?;
-                for (prev_tag, (field_name, field_type)) in fields.iter().enumerate() {
-                    Self::append_field(target, model, field_name, field_type, prev_tag + 1)?;
+                // the alternatives of a oneof share the numbers with the fields of the message
+                let names = fields
+                    .iter()
+                    .flat_map(|(field_name, field_type)| match field_type {
+                        ProtobufType::OneOf(variants) => {
+                            variants.iter().map(|(name, _)| name.as_str()).collect()
+                        }
+                        _ => vec![field_name.as_str()],
+                    });
+                let mut numbers = self.field_numbers.assign(name, names.clone()).into_iter();
+                for (field_name, field_type) in fields {
+                    let tags = match field_type {
+                        ProtobufType::OneOf(variants) => numbers.by_ref().take(variants.len()),
+                        _ => numbers.by_ref().take(1),
+                    }
+                    .collect::<Vec<_>>();
+                    Self::append_field(target, model, field_name, field_type, &tags)?;
                 }
+                Self::append_reserved(target, &self.field_numbers.removed(name, names))?;
                 writeln!(target, "}}")?;
             }
         }
         Ok(())
     }
 
+    pub fn append_services(
+        &self,
+        target: &mut dyn Write,
+        model: &Model<Protobuf>,
+    ) -> Result<(), Error> {
+        for service in self.services.declared_in(model) {
+            writeln!(target, "service {} {{", service.name)?;
+            for method in &service.methods {
+                writeln!(
+                    target,
+                    "    rpc {}({}) returns ({});",
+                    method.name,
+                    Self::role_to_full_type(&ProtobufType::Complex(method.request.clone()), model),
+                    Self::role_to_full_type(&ProtobufType::Complex(method.response.clone()), model),
+                )?;
+            }
+            writeln!(target, "}}")?;
+        }
+        Ok(())
+    }
+
     pub fn append_field(
         target: &mut dyn Write,
         model: &Model<Protobuf>,
         name: &str,
         role: &ProtobufType,
-        tag: usize,
+        tags: &[u32],
     ) -> Result<(), Error> {
         writeln!(
             target,
@@ -118,24 +181,51 @@ impl ProtobufDefGenerator {
             if let ProtobufType::OneOf(variants) = role {
                 let mut inner = String::new();
                 writeln!(&mut inner, " {{")?;
-                for (index, (variant_name, variant_type)) in variants.iter().enumerate() {
+                for ((variant_name, variant_type), tag) in variants.iter().zip(tags) {
                     writeln!(
                         &mut inner,
                         "      {} {} = {};",
                         Self::role_to_full_type(variant_type, model),
                         variant_name,
-                        index + 1
+                        tag
                     )?;
                 }
                 write!(&mut inner, "    }}")?;
                 inner
             } else {
-                format!(" = {}", tag)
+                format!(" = {}", tags[0])
             }
         )?;
         Ok(())
     }
 
+    /// Declares the numbers and names of removed fields as `reserved`, so that they are not
+    /// reused by accident
+    pub fn append_reserved(target: &mut dyn Write, removed: &[(String, u32)]) -> Result<(), Error> {
+        if removed.is_empty() {
+            return Ok(());
+        }
+        writeln!(
+            target,
+            "    reserved {};",
+            removed
+                .iter()
+                .map(|(_, number)| number.to_string())
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        writeln!(
+            target,
+            "    reserved {};",
+            removed
+                .iter()
+                .map(|(name, _)| format!("\"{}\"", rust_module_name(name, false)))
+                .collect::<Vec<_>>()
+                .join(", ")
+        )?;
+        Ok(())
+    }
+
     pub fn append_variant(
         target: &mut dyn Write,
         base: &str,
@@ -175,6 +265,11 @@ impl ProtobufDefGenerator {
             ProtobufType::Repeated(inner) => {
                 format!("repeated {}", Self::role_to_full_type(inner, model))
             }
+            ProtobufType::Map(key, value) => format!(
+                "map<{}, {}>",
+                Self::role_to_full_type(key, model),
+                Self::role_to_full_type(value, model)
+            ),
             r => r.to_string(),
         }
     }
@@ -262,6 +357,9 @@ impl ProtobufDefGenerator {
 #[cfg(test)]
 mod tests {
     use super::*;
+    use crate::generate::RustCodeGenerator;
+    use crate::parse::Tokenizer;
+    use crate::protobuf::ToProtobufModel;
 
     #[test]
     fn test_protobuf_variant_name() {
@@ -269,4 +367,160 @@ mod tests {
         assert_eq!("ABC_DEF", ProtobufDefGenerator::variant_name("AbcDef"));
         assert_eq!("ABC_DEF", ProtobufDefGenerator::variant_name("ABcDef"));
     }
+
+    #[test]
+    fn test_field_numbers_and_reserved() {
+        let mut model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Message ::= SEQUENCE {
+                id INTEGER (0..255),
+                priority BOOLEAN,
+                payload Payload
+            }
+            Payload ::= CHOICE { raw OCTET STRING, text UTF8String }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let numbers = FieldNumbers::parse(
+            "Message.id = 1\n\
+            Message.legacyId = 2\n\
+            Message.payload = 5\n\
+            Payload.text = 3",
+        )
+        .unwrap();
+        numbers.apply(&mut model);
+
+        let (_file_name, rust) = RustCodeGenerator::from(model.clone())
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(rust.contains("#[asn(sequence, protobuf_numbers(1, 3, 5))]"));
+        assert!(rust.contains("#[asn(choice, protobuf_numbers(1, 3))]"));
+
+        let mut generator = ProtobufDefGenerator::default();
+        generator.set_field_numbers(numbers);
+        generator.add_model(model.to_protobuf());
+        let (_file_name, proto) = generator.to_string().unwrap().into_iter().next().unwrap();
+        assert!(proto.contains(
+            "message Message {\n    \
+            uint32 id = 1;\n    \
+            bool priority = 3;\n    \
+            Payload payload = 5;\n    \
+            reserved 2;\n    \
+            reserved \"legacy_id\";\n\
+            }"
+        ));
+        assert!(proto.contains("      bytes raw = 1;\n      string text = 3;\n"));
+    }
+
+    #[test]
+    fn test_services() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Ping ::= SEQUENCE { id INTEGER (0..255) }
+            Pong ::= SEQUENCE { id INTEGER (0..255) }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = ProtobufDefGenerator::default();
+        generator.set_services(
+            Services::parse("Echo.Ping = Ping -> Pong\nEcho.Pong = Pong -> Ping\nOther.X = A -> B")
+                .unwrap(),
+        );
+        generator.add_model(model.to_protobuf());
+        let (_file_name, proto) = generator.to_string().unwrap().into_iter().next().unwrap();
+        assert!(proto.ends_with(
+            "service Echo {\n    \
+            rpc Ping(Ping) returns (Pong);\n    \
+            rpc Pong(Pong) returns (Ping);\n\
+            }\n"
+        ));
+    }
+
+    #[test]
+    fn test_fixed_for_full_range_integers() {
+        let mut model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Sample ::= SEQUENCE {
+                hash INTEGER (0..4294967295),
+                offset INTEGER (-2147483648..2147483647) OPTIONAL,
+                timestamps SEQUENCE OF INTEGER (-9223372036854775808..9223372036854775807),
+                count INTEGER (0..65535)
+            }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        crate::protobuf::use_fixed_for_full_range_integers(&mut model);
+
+        let (_file_name, rust) = RustCodeGenerator::from(model.clone())
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(rust.contains("#[asn(sequence, protobuf_fixed(hash, offset, timestamps))]"));
+
+        let mut generator = ProtobufDefGenerator::default();
+        generator.add_model(model.to_protobuf());
+        let (_file_name, proto) = generator.to_string().unwrap().into_iter().next().unwrap();
+        assert!(proto.contains(
+            "message Sample {\n    \
+            fixed32 hash = 1;\n    \
+            sfixed32 offset = 2;\n    \
+            repeated sfixed64 timestamps = 3;\n    \
+            uint32 count = 4;\n\
+            }"
+        ));
+    }
+
+    #[test]
+    fn test_maps() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Inventory ::= SEQUENCE {
+                counts SEQUENCE OF SEQUENCE { key UTF8String, value INTEGER (0..255) },
+                items SEQUENCE OF Item,
+                pairs SEQUENCE OF SEQUENCE { key OCTET STRING, value BOOLEAN }
+            }
+            Item ::= SEQUENCE { key INTEGER (0..255), value Item2 }
+            Item2 ::= SEQUENCE { name UTF8String }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut protobuf = model.to_protobuf();
+        crate::protobuf::use_maps(&mut protobuf);
+
+        let mut generator = ProtobufDefGenerator::default();
+        generator.add_model(protobuf);
+        let (_file_name, proto) = generator.to_string().unwrap().into_iter().next().unwrap();
+        assert!(proto.contains(
+            "message Inventory {\n    \
+            map<string, uint32> counts = 1;\n    \
+            map<uint32, Item2> items = 2;\n    \
+            repeated InventoryPairs pairs = 3;\n\
+            }"
+        ));
+        assert!(proto.contains("message Item {\n    uint32 key = 1;\n    Item2 value = 2;\n}"));
+    }
 }
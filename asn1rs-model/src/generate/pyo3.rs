@@ -0,0 +1,280 @@
+use crate::generate::rust::GeneratorSupplement;
+use crate::model::Definition;
+use crate::rust::Rust;
+use codegen::Scope;
+
+const PYO3_PREFIX: &str = "::pyo3::";
+
+/// Wraps each generated type `Foo` in a `#[pyclass(name = "Foo")]` named `PyFoo` and adds the
+/// `#[pymodule]` function of the module, so that the types can be used from Python with the
+/// codecs of `asn1rs` underneath. The values are constructed from and converted to their JER
+/// representation, which `json.dumps` and `json.loads` turn into dicts, and the codec is selected
+/// by its id, see `asn1rs::codec::Codec::id`:
+///
+/// ```python
+/// import messages
+///
+/// pizza = messages.Pizza('{"size": 2, "topping": "notPineapple"}')
+/// uper = pizza.encode(0)
+/// assert messages.Pizza.decode(0, uper).to_jer() == pizza.to_jer()
+/// ```
+///
+/// The crate embedding the generated code depends on `pyo3` itself, `asn1rs` does not.
+#[derive(Debug, Default)]
+pub struct PyO3ImplGenerator {
+    feature: Option<String>,
+}
+
+impl PyO3ImplGenerator {
+    /// Gates the generated classes and functions behind `#[cfg(feature = "..")]` of the given
+    /// feature of the crate embedding the generated code
+    pub fn with_feature(feature: Option<String>) -> Self {
+        Self { feature }
+    }
+
+    pub fn class_name(name: &str) -> String {
+        format!("Py{}", name)
+    }
+
+    fn cfg(&self) -> Option<String> {
+        self.feature
+            .as_ref()
+            .map(|feature| format!("cfg(feature = \"{}\")", feature))
+    }
+}
+
+impl GeneratorSupplement<Rust> for PyO3ImplGenerator {
+    fn add_imports(&self, _scope: &mut Scope) {}
+
+    fn impl_supplement(&self, scope: &mut Scope, Definition(name, _rust): &Definition<Rust>) {
+        let class = Self::class_name(name);
+        let cfg = self.cfg();
+
+        let wrapper = scope
+            .new_struct(&class)
+            .vis("pub")
+            .doc(&format!("The Python class of [`{}`]", name))
+            .attr(format!("{}pyclass(name = \"{}\")", PYO3_PREFIX, name))
+            .tuple_field(name);
+        if let Some(cfg) = &cfg {
+            wrapper.attr(cfg);
+        }
+
+        let methods = scope
+            .new_impl(&class)
+            .r#macro(&format!("#[{}pymethods]", PYO3_PREFIX));
+        if let Some(cfg) = &cfg {
+            methods.r#macro(&format!("#[{}]", cfg));
+        }
+        methods
+            .new_fn("new")
+            .doc("Creates the value from its JER representation")
+            .attr("new")
+            .vis("pub")
+            .arg("json", "&str")
+            .ret(format!("{}PyResult<Self>", PYO3_PREFIX))
+            .line("::asn1rs::util::from_jer_str(json)")
+            .line("    .map(Self)")
+            .line("    .map_err(py_value_error)");
+        methods
+            .new_fn("decode")
+            .doc("Decodes the value with the codec of the given id")
+            .attr("staticmethod")
+            .vis("pub")
+            .arg("codec", "u8")
+            .arg("bytes", "&[u8]")
+            .ret(format!("{}PyResult<Self>", PYO3_PREFIX))
+            .line("py_codec(codec)?")
+            .line("    .decode(bytes)")
+            .line("    .map(Self)")
+            .line("    .map_err(py_value_error)");
+        methods
+            .new_fn("encode")
+            .doc("Encodes the value with the codec of the given id")
+            .vis("pub")
+            .arg_ref_self()
+            .arg("codec", "u8")
+            .ret(format!(
+                "{}PyResult<::std::borrow::Cow<'static, [u8]>>",
+                PYO3_PREFIX
+            ))
+            .line("py_codec(codec)?")
+            .line("    .encode(&self.0)")
+            .line("    .map(::std::borrow::Cow::Owned)")
+            .line("    .map_err(py_value_error)");
+        methods
+            .new_fn("to_jer")
+            .doc("The JER representation of the value")
+            .vis("pub")
+            .arg_ref_self()
+            .ret("String")
+            .line("::asn1rs::util::to_jer_string(&self.0)");
+        methods
+            .new_fn("__repr__")
+            .arg_ref_self()
+            .ret("String")
+            .line(format!("format!(\"{}({{}})\", self.to_jer())", name));
+
+        for (from, into, expr) in [
+            (name.as_str(), class.as_str(), "Self(value)"),
+            (class.as_str(), name.as_str(), "value.0"),
+        ] {
+            let conversion = scope
+                .new_impl(into)
+                .impl_trait(format!("::core::convert::From<{}>", from));
+            if let Some(cfg) = &cfg {
+                conversion.r#macro(&format!("#[{}]", cfg));
+            }
+            conversion
+                .new_fn("from")
+                .arg("value", from)
+                .ret("Self")
+                .line(expr);
+        }
+    }
+
+    fn impl_module_supplement(
+        &self,
+        scope: &mut Scope,
+        module: &str,
+        definitions: &[Definition<Rust>],
+    ) {
+        if definitions.is_empty() {
+            return;
+        }
+        let cfg = self.cfg();
+        let bound_module = format!("&{}Bound<'_, {}types::PyModule>", PYO3_PREFIX, PYO3_PREFIX);
+
+        let add_classes = scope
+            .new_fn("add_py_classes")
+            .doc("Adds the Python classes of the types of this module to the given Python module")
+            .vis("pub")
+            .arg("module", &bound_module)
+            .ret(format!("{}PyResult<()>", PYO3_PREFIX))
+            .line(format!("use {}types::PyModuleMethods;", PYO3_PREFIX));
+        for Definition(name, _) in definitions {
+            add_classes.line(format!(
+                "module.add_class::<{}>()?;",
+                Self::class_name(name)
+            ));
+        }
+        add_classes.line("Ok(())");
+        if let Some(cfg) = &cfg {
+            add_classes.attr(cfg);
+        }
+
+        let init = scope
+            .new_fn(module)
+            .doc(format!(
+                "The Python module `{}` with the classes of [`add_py_classes`]",
+                module
+            ))
+            .attr(&format!("{}pymodule", PYO3_PREFIX))
+            .vis("pub")
+            .arg("module", &bound_module)
+            .ret(format!("{}PyResult<()>", PYO3_PREFIX))
+            .line("add_py_classes(module)");
+        if let Some(cfg) = &cfg {
+            init.attr(cfg);
+        }
+
+        let codec = scope
+            .new_fn("py_codec")
+            .arg("id", "u8")
+            .ret(format!("{}PyResult<::asn1rs::codec::Codec>", PYO3_PREFIX))
+            .line("::asn1rs::codec::Codec::from_id(id)")
+            .line("    .ok_or(::asn1rs::codec::Error::UnknownCodec(id))")
+            .line("    .map_err(py_value_error)");
+        if let Some(cfg) = &cfg {
+            codec.attr(cfg);
+        }
+
+        let error = scope
+            .new_fn("py_value_error")
+            .arg("error", "impl ::std::fmt::Display")
+            .ret(format!("{}PyErr", PYO3_PREFIX))
+            .line(format!(
+                "{}exceptions::PyValueError::new_err(error.to_string())",
+                PYO3_PREFIX
+            ));
+        if let Some(cfg) = &cfg {
+            error.attr(cfg);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::generate::RustCodeGenerator;
+    use crate::model::Model;
+    use crate::parse::Tokenizer;
+
+    const ASN: &str = r#"PyTest DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        MyEnum ::= ENUMERATED { abc, def }
+
+        MyStruct ::= SEQUENCE {
+            number INTEGER (0..255),
+            kind MyEnum
+        }
+
+        END
+    "#;
+
+    fn generate(generator: &PyO3ImplGenerator) -> String {
+        let model = Model::try_from(Tokenizer::default().parse(ASN))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust();
+        RustCodeGenerator::from(model)
+            .to_string_with_generators(&[generator])
+            .into_iter()
+            .next()
+            .unwrap()
+            .1
+    }
+
+    #[test]
+    pub fn test_classes_and_module() {
+        let content = generate(&PyO3ImplGenerator::default());
+
+        assert!(content
+            .contains("#[::pyo3::pyclass(name = \"MyStruct\")]\npub struct PyMyStruct(MyStruct);"));
+        assert!(content.contains("#[::pyo3::pymethods]\nimpl PyMyStruct {"));
+        assert!(
+            content.contains("    #[new]\n    pub fn new(json: &str) -> ::pyo3::PyResult<Self> {")
+        );
+        assert!(content.contains(
+            "    #[staticmethod]\n    pub fn decode(codec: u8, bytes: &[u8]) -> ::pyo3::PyResult<Self> {"
+        ));
+        assert!(content.contains("format!(\"MyEnum({})\", self.to_jer())"));
+        assert!(content.contains("impl ::core::convert::From<PyMyEnum> for MyEnum {"));
+
+        assert!(content.contains("    module.add_class::<PyMyEnum>()?;\n    module.add_class::<PyMyStruct>()?;\n    Ok(())"));
+        assert!(content.contains(
+            "#[::pyo3::pymodule]\npub fn py_test(module: &::pyo3::Bound<'_, ::pyo3::types::PyModule>) -> ::pyo3::PyResult<()> {"
+        ));
+        assert!(!content.contains("cfg(feature"));
+    }
+
+    #[test]
+    pub fn test_feature_gated() {
+        let content = generate(&PyO3ImplGenerator::with_feature(Some("python".to_string())));
+
+        assert!(content.contains(
+            "#[::pyo3::pyclass(name = \"MyStruct\")]\n#[cfg(feature = \"python\")]\npub struct PyMyStruct(MyStruct);"
+        ));
+        assert!(content
+            .contains("#[::pyo3::pymethods]\n#[cfg(feature = \"python\")]\nimpl PyMyStruct {"));
+        assert!(content.contains(
+            "#[cfg(feature = \"python\")]\nimpl ::core::convert::From<MyStruct> for PyMyStruct {"
+        ));
+        assert!(
+            content.contains("#[::pyo3::pymodule]\n#[cfg(feature = \"python\")]\npub fn py_test(")
+        );
+        assert!(content.contains("#[cfg(feature = \"python\")]\nfn py_value_error("));
+    }
+}
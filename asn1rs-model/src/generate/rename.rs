@@ -0,0 +1,115 @@
+//! Renamed fields and variants between revisions of a schema, for which the
+//! [`super::RustCodeGenerator`] emits deprecated aliases, so that downstream code keeps compiling.
+//!
+//! The mapping file contains one rename per line in the form `Type.oldName = newName`, where
+//! `Type`, `oldName` and `newName` are the ASN.1 names of the type and the identifiers of the
+//! field or variant. Empty lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # renamed in revision 2
+//! Message.msgId = messageId
+//! Status.offline = unreachable
+//! ```
+
+use super::mapping::{self, PerDefinition};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line with the given (1-based) number is not of the form `Type.oldName = newName`
+    InvalidLine(usize, String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidLine(number, line) => write!(
+                f,
+                "Line {number} is not of the form 'Type.oldName = newName': {line}"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Renames(PerDefinition<(String, String)>);
+
+impl Renames {
+    pub fn parse(mapping: &str) -> Result<Self, Error> {
+        let mut renames = Self::default();
+        for line in mapping::lines(mapping) {
+            match line.split_member() {
+                Some((definition, old, new)) if mapping::is_identifier(new) => {
+                    renames.add(definition, old, new)
+                }
+                _ => return Err(Error::InvalidLine(line.number, line.content.to_string())),
+            }
+        }
+        Ok(renames)
+    }
+
+    pub fn add<D: Into<String>, O: Into<String>, N: Into<String>>(
+        &mut self,
+        definition: D,
+        old: O,
+        new: N,
+    ) {
+        self.0.push(&definition.into(), (old.into(), new.into()));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The `(old, new)` identifiers of the renamed fields or variants of the given type
+    pub fn of(&self, definition: &str) -> &[(String, String)] {
+        self.0.of(definition)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse() {
+        let renames = Renames::parse(
+            r"
+            # comment
+            Message.msgId = messageId
+            Message.kind=type
+            Status.offline = unreachable
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            &[
+                ("msgId".to_string(), "messageId".to_string()),
+                ("kind".to_string(), "type".to_string())
+            ],
+            renames.of("Message")
+        );
+        assert_eq!(
+            &[("offline".to_string(), "unreachable".to_string())],
+            renames.of("Status")
+        );
+        assert!(renames.of("Unknown").is_empty());
+    }
+
+    #[test]
+    pub fn test_parse_invalid_lines() {
+        assert_eq!(
+            Err(Error::InvalidLine(2, "Message.msgId".to_string())),
+            Renames::parse("\nMessage.msgId\n")
+        );
+        assert_eq!(
+            Err(Error::InvalidLine(1, "msgId = messageId".to_string())),
+            Renames::parse("msgId = messageId")
+        );
+        assert_eq!(
+            Err(Error::InvalidLine(1, "Message.msg Id = id".to_string())),
+            Renames::parse("Message.msg Id = id")
+        );
+    }
+}
@@ -1,10 +1,23 @@
-use crate::asn::{Tag, TagProperty, Type as AsnType, Type};
-use crate::generate::Generator;
-use crate::model::{Definition, Model};
-use crate::rust::{DataEnum, Field, Rust, RustType};
+use crate::asn::{
+    ObjectIdentifierComponent, PermittedAlphabet, Range, Tag, TagProperty, Type as AsnType, Type,
+};
+use crate::generate::arbitrary::ArbitraryImplGenerator;
+use crate::generate::attributes::Attributes;
+use crate::generate::pyo3::PyO3ImplGenerator;
+use crate::generate::rename::Renames;
+use crate::generate::walker::{
+    AsnDefWriter, CRATE_MODEL_PREFIX, CRATE_PER_PREFIX, CRATE_PROTOBUF_PREFIX, CRATE_RW_PREFIX,
+    CRATE_SYN_PREFIX,
+};
+use crate::generate::{Codec, Generator};
+use crate::model::{Definition, Docs, LiteralValue, Model};
+#[cfg(feature = "protobuf")]
+use crate::protobuf::services::Services;
+use crate::rust::{DataEnum, DataVariant, Field, Rust, RustType};
 use crate::rust::{EncodingOrdering, PlainEnum};
 use codegen::Block;
 use codegen::Enum;
+use codegen::Formatter;
 use codegen::Impl;
 use codegen::Scope;
 use codegen::Struct;
@@ -13,10 +26,18 @@ use std::collections::HashMap;
 use std::convert::Infallible;
 use std::fmt::Display;
 
-const KEYWORDS: [&str; 9] = [
-    "use", "mod", "const", "type", "pub", "enum", "struct", "impl", "trait",
+/// The strict and reserved keywords of all editions
+const KEYWORDS: [&str; 52] = [
+    "abstract", "as", "async", "await", "become", "box", "break", "const", "continue", "crate",
+    "do", "dyn", "else", "enum", "extern", "false", "final", "fn", "for", "gen", "if", "impl",
+    "in", "let", "loop", "macro", "match", "mod", "move", "mut", "override", "priv", "pub", "ref",
+    "return", "self", "Self", "static", "struct", "super", "trait", "true", "try", "type",
+    "typeof", "unsafe", "unsized", "use", "virtual", "where", "while", "yield",
 ];
 
+/// The keywords, that cannot be used as raw identifiers
+const NON_RAW_KEYWORDS: [&str; 4] = ["crate", "self", "Self", "super"];
+
 pub trait GeneratorSupplement<T> {
     fn add_imports(&self, scope: &mut Scope);
     fn impl_supplement(&self, scope: &mut Scope, definition: &Definition<T>);
@@ -30,6 +51,88 @@ pub trait GeneratorSupplement<T> {
     ) {
     }
     fn extend_impl_of_tuple(&self, _name: &str, _impl_scope: &mut Impl, _definition: &RustType) {}
+    /// Called once per module after [`GeneratorSupplement::impl_supplement`] was called for all
+    /// of its definitions, with the name of the generated rust module
+    fn impl_module_supplement(
+        &self,
+        _scope: &mut Scope,
+        _module: &str,
+        _definitions: &[Definition<T>],
+    ) {
+    }
+}
+
+/// Emits the supplements of the wrapped generator in a `#[cfg(feature = "..")] const _: () = {..}`
+/// block, so that a crate embedding the generated code can compile them out. Only the items of
+/// [`GeneratorSupplement::impl_supplement`] and [`GeneratorSupplement::impl_module_supplement`]
+/// are gated, which therefore must not declare named items that are used elsewhere.
+pub struct FeatureGated<'a, T> {
+    feature: &'a str,
+    supplement: &'a dyn GeneratorSupplement<T>,
+}
+
+impl<'a, T> FeatureGated<'a, T> {
+    pub fn new(feature: &'a str, supplement: &'a dyn GeneratorSupplement<T>) -> Self {
+        Self {
+            feature,
+            supplement,
+        }
+    }
+
+    fn push_gated(&self, scope: &mut Scope, gated: Scope) {
+        let code = gated.to_string();
+        if !code.trim().is_empty() {
+            let mut block = Block::new("const _: () =");
+            block.line(code.trim_end()).after(";");
+            let mut gated = format!("#[cfg(feature = \"{}\")]\n", self.feature);
+            let _ = block.fmt(&mut Formatter::new(&mut gated));
+            scope.raw(gated);
+        }
+    }
+}
+
+impl<T> GeneratorSupplement<T> for FeatureGated<'_, T> {
+    fn add_imports(&self, scope: &mut Scope) {
+        self.supplement.add_imports(scope)
+    }
+
+    fn impl_supplement(&self, scope: &mut Scope, definition: &Definition<T>) {
+        let mut gated = Scope::new();
+        self.supplement.impl_supplement(&mut gated, definition);
+        self.push_gated(scope, gated);
+    }
+
+    fn extend_impl_of_struct(&self, name: &str, impl_scope: &mut Impl, fields: &[Field]) {
+        self.supplement
+            .extend_impl_of_struct(name, impl_scope, fields)
+    }
+
+    fn extend_impl_of_enum(&self, name: &str, impl_scope: &mut Impl, enumeration: &PlainEnum) {
+        self.supplement
+            .extend_impl_of_enum(name, impl_scope, enumeration)
+    }
+
+    fn extend_impl_of_data_enum(&self, name: &str, impl_scope: &mut Impl, enumeration: &DataEnum) {
+        self.supplement
+            .extend_impl_of_data_enum(name, impl_scope, enumeration)
+    }
+
+    fn extend_impl_of_tuple(&self, name: &str, impl_scope: &mut Impl, definition: &RustType) {
+        self.supplement
+            .extend_impl_of_tuple(name, impl_scope, definition)
+    }
+
+    fn impl_module_supplement(
+        &self,
+        scope: &mut Scope,
+        module: &str,
+        definitions: &[Definition<T>],
+    ) {
+        let mut gated = Scope::new();
+        self.supplement
+            .impl_module_supplement(&mut gated, module, definitions);
+        self.push_gated(scope, gated);
+    }
 }
 
 #[allow(clippy::module_name_repetitions)]
@@ -41,6 +144,25 @@ pub struct RustCodeGenerator {
     local_attrs: HashMap<String, Vec<String>>,
     direct_field_access: bool,
     getter_and_setter: bool,
+    arbitrary_impls: bool,
+    pyo3_classes: bool,
+    repr_enums: bool,
+    non_exhaustive_enums: bool,
+    validated_integers: bool,
+    builders: bool,
+    views: bool,
+    display_and_from_str: bool,
+    auto_derives: bool,
+    optimize_code_size: bool,
+    renames: Renames,
+    attributes: Attributes,
+    arbitrary_feature: Option<String>,
+    pyo3_feature: Option<String>,
+    codecs: Vec<Codec>,
+    #[cfg(feature = "protobuf")]
+    services: Services,
+    #[cfg(feature = "protobuf")]
+    protobuf_feature: Option<String>,
 }
 
 impl From<Model<Rust>> for RustCodeGenerator {
@@ -60,6 +182,25 @@ impl Default for RustCodeGenerator {
             local_attrs: HashMap::new(),
             direct_field_access: true,
             getter_and_setter: false,
+            arbitrary_impls: false,
+            pyo3_classes: false,
+            repr_enums: false,
+            non_exhaustive_enums: false,
+            validated_integers: false,
+            builders: false,
+            views: false,
+            display_and_from_str: false,
+            auto_derives: false,
+            optimize_code_size: false,
+            renames: Renames::default(),
+            attributes: Attributes::default(),
+            arbitrary_feature: None,
+            pyo3_feature: None,
+            codecs: Codec::ALL.to_vec(),
+            #[cfg(feature = "protobuf")]
+            services: Services::default(),
+            #[cfg(feature = "protobuf")]
+            protobuf_feature: None,
         }
     }
 }
@@ -81,7 +222,23 @@ impl Generator<Rust> for RustCodeGenerator {
 
     #[inline]
     fn to_string(&self) -> Result<Vec<(String, String)>, Self::Error> {
-        Ok(self.to_string_without_generators())
+        let arbitrary = self
+            .arbitrary_feature
+            .as_deref()
+            .map(|feature| FeatureGated::new(feature, &ArbitraryImplGenerator));
+        let pyo3 = PyO3ImplGenerator::with_feature(self.pyo3_feature.clone());
+
+        let mut generators = Vec::<&dyn GeneratorSupplement<Rust>>::new();
+        if self.arbitrary_impls {
+            match &arbitrary {
+                Some(gated) => generators.push(gated),
+                None => generators.push(&ArbitraryImplGenerator),
+            }
+        }
+        if self.pyo3_classes {
+            generators.push(&pyo3);
+        }
+        Ok(self.to_string_with_generators(&generators))
     }
 }
 
@@ -119,6 +276,17 @@ impl RustCodeGenerator {
         self
     }
 
+    /// Adds the attribute - without the surrounding `#[...]` - to the given field or variant of
+    /// the type, for example `serde(skip)` or `cfg(feature = "x")`
+    pub fn add_member_attr<N: Into<String>, M: Into<String>, I: Into<String>>(
+        &mut self,
+        name: N,
+        member: M,
+        attr: I,
+    ) {
+        self.attributes.add(name, Some(member.into()), attr);
+    }
+
     pub const fn fields_are_pub(&self) -> bool {
         self.direct_field_access
     }
@@ -131,14 +299,207 @@ impl RustCodeGenerator {
         self.getter_and_setter
     }
 
+    /// Whether the fields of structs and the value of tuple structs get a getter, a mutable
+    /// getter and a setter, and the variants of data enums `is_..()`, `as_..()` and `into_..()`
     pub fn set_fields_have_getter_and_setter(&mut self, allow: bool) {
         self.getter_and_setter = allow;
     }
 
+    pub const fn generates_arbitrary_impls(&self) -> bool {
+        self.arbitrary_impls
+    }
+
+    /// Whether to implement `asn1rs::arbitrary::Arbitrary` for all generated types, see
+    /// [`ArbitraryImplGenerator`]. This requires the `arbitrary` feature of `asn1rs`.
+    pub fn set_generate_arbitrary_impls(&mut self, generate: bool) {
+        self.arbitrary_impls = generate;
+    }
+
+    pub fn arbitrary_feature(&self) -> Option<&str> {
+        self.arbitrary_feature.as_deref()
+    }
+
+    /// Gates the generated `Arbitrary` impls behind `#[cfg(feature = "..")]` of the given
+    /// feature of the crate embedding the generated code
+    pub fn set_arbitrary_feature(&mut self, feature: Option<String>) {
+        self.arbitrary_feature = feature;
+    }
+
+    pub const fn generates_pyo3_classes(&self) -> bool {
+        self.pyo3_classes
+    }
+
+    /// Whether to wrap all generated types in Python classes and add a `#[pymodule]` function
+    /// per module, see [`PyO3ImplGenerator`]. This requires a dependency on `pyo3`.
+    pub fn set_generate_pyo3_classes(&mut self, generate: bool) {
+        self.pyo3_classes = generate;
+    }
+
+    pub fn pyo3_feature(&self) -> Option<&str> {
+        self.pyo3_feature.as_deref()
+    }
+
+    /// Gates the generated Python classes and functions behind `#[cfg(feature = "..")]` of the
+    /// given feature of the crate embedding the generated code
+    pub fn set_pyo3_feature(&mut self, feature: Option<String>) {
+        self.pyo3_feature = feature;
+    }
+
+    pub const fn enums_have_repr(&self) -> bool {
+        self.repr_enums
+    }
+
+    /// Whether the enums of ENUMERATEDs have a `#[repr(..)]` of the smallest unsigned integer
+    /// type fitting their numbers, the numbers as explicit discriminants and implement
+    /// `TryFrom<u64>` and `From<_> for u64`
+    pub fn set_enums_have_repr(&mut self, repr: bool) {
+        self.repr_enums = repr;
+    }
+
+    pub const fn integers_are_validated(&self) -> bool {
+        self.validated_integers
+    }
+
+    /// Whether the transparent types of non-extensible constrained INTEGERs are validated
+    /// newtypes: the value is private and only constructed through `try_new()` and `TryFrom`,
+    /// which reject values outside the range
+    pub fn set_integers_validated(&mut self, validated: bool) {
+        self.validated_integers = validated;
+    }
+
+    pub const fn generates_builders(&self) -> bool {
+        self.builders
+    }
+
+    /// Whether a `FooBuilder` is generated for each struct `Foo` with fields, with a setter per
+    /// field and a `build()`, which returns the name of the first mandatory field not set as error
+    pub fn set_builders(&mut self, builders: bool) {
+        self.builders = builders;
+    }
+
+    pub const fn generates_views(&self) -> bool {
+        self.views
+    }
+
+    /// Whether a `FooView` with an `Option` per field and a `Foo::read_view(reader, fields)` is
+    /// generated for each SEQUENCE `Foo` with fields. `read_view` only keeps the fields whose ASN.1
+    /// identifier is listed, so that - for example - a dispatcher can route a large PDU by its
+    /// header without allocating the strings, lists and nested types of the remaining fields.
+    /// These are still read - as the codecs do not know the length of a value without reading
+    /// it - but dropped immediately.
+    pub fn set_views(&mut self, views: bool) {
+        self.views = views;
+    }
+
+    pub const fn enums_have_display_and_from_str(&self) -> bool {
+        self.display_and_from_str
+    }
+
+    /// Whether the enums of ENUMERATEDs and CHOICEs implement `Display` and `FromStr` with the
+    /// ASN.1 identifier of the variant. Parsing the identifier of a CHOICE alternative returns it
+    /// with the default value, like `variants()` does.
+    pub fn set_enums_have_display_and_from_str(&mut self, allow: bool) {
+        self.display_and_from_str = allow;
+    }
+
+    pub const fn extensible_enums_non_exhaustive(&self) -> bool {
+        self.non_exhaustive_enums
+    }
+
+    /// Whether the enums of extensible ENUMERATEDs and CHOICEs are `#[non_exhaustive]`, so that
+    /// matches in other crates need a wildcard arm and keep compiling when variants are added
+    pub fn set_extensible_enums_non_exhaustive(&mut self, non_exhaustive: bool) {
+        self.non_exhaustive_enums = non_exhaustive;
+    }
+
+    pub const fn auto_derives(&self) -> bool {
+        self.auto_derives
+    }
+
+    /// Whether `Copy`, `Eq`, `PartialOrd` and `Ord` are derived for each type whose fields or
+    /// variants all implement them, so for example not `Copy` if a field is a `String` or `Vec`
+    /// and not `Ord` if a field is a `BitVec`
+    pub fn set_auto_derives(&mut self, auto_derives: bool) {
+        self.auto_derives = auto_derives;
+    }
+
+    pub const fn optimize_code_size(&self) -> bool {
+        self.optimize_code_size
+    }
+
+    /// Whether the definitions are annotated with `#[asn(.., no_inline)]`, so that their
+    /// `Readable` and `Writable` impls are `#[inline(never)]`. Each type is then compiled once
+    /// per reader or writer instead of being inlined into every type that refers to it, which
+    /// reduces the size of the binary for nested schemas at the cost of the function calls.
+    pub fn set_optimize_code_size(&mut self, optimize_code_size: bool) {
+        self.optimize_code_size = optimize_code_size;
+    }
+
+    pub const fn renames(&self) -> &Renames {
+        &self.renames
+    }
+
+    /// For each renamed field or variant, deprecated alias getters and constructors with the
+    /// previous name are generated. If `Deserialize` is derived, `#[serde(alias = ..)]`
+    /// attributes accept the previous names as well.
+    pub fn set_renames(&mut self, renames: Renames) {
+        self.renames = renames;
+    }
+
+    pub fn codecs(&self) -> &[Codec] {
+        &self.codecs[..]
+    }
+
+    pub fn generates_codec(&self, codec: Codec) -> bool {
+        self.codecs.contains(&codec)
+    }
+
+    /// Restricts the codec specific code to the given codecs, all by default. Without
+    /// [`Codec::Protobuf`], the protobuf annotations of the models and the gRPC services are
+    /// omitted.
+    pub fn set_codecs(&mut self, codecs: &[Codec]) {
+        self.codecs = codecs.to_vec();
+    }
+
+    pub const fn attributes(&self) -> &Attributes {
+        &self.attributes
+    }
+
+    /// The attributes are emitted verbatim on the types, fields and variants in addition to the
+    /// ones of [`RustCodeGenerator::add_local_attr`] and [`RustCodeGenerator::add_member_attr`]
+    pub fn set_attributes(&mut self, attributes: Attributes) {
+        self.attributes = attributes;
+    }
+
+    /// For each service, a service trait, a client stub and a server dispatching the calls to
+    /// the service trait are generated in the module defining the request type of its first
+    /// method, see [`crate::protobuf::services`]
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_services(&mut self, services: Services) {
+        self.services = services;
+    }
+
+    #[cfg(feature = "protobuf")]
+    pub fn protobuf_feature(&self) -> Option<&str> {
+        self.protobuf_feature.as_deref()
+    }
+
+    /// Gates the generated gRPC service traits, clients and servers as well as the protobuf field
+    /// numbers and fixed size integers in the `#[asn(..)]` attributes behind
+    /// `#[cfg(feature = "..")]` of the given feature of the crate embedding the generated code
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_feature(&mut self, feature: Option<String>) {
+        self.protobuf_feature = feature;
+    }
+
     pub fn to_string_without_generators(&self) -> Vec<(String, String)> {
         self.to_string_with_generators(&[])
     }
 
+    /// Generates one file per model, in the order the models were added. The output only depends
+    /// on the models and the settings of this generator: definitions, fields and variants are
+    /// written in their ASN.1 order and the renames, attributes and docs are only looked up, so
+    /// generating twice results in the very same content.
     pub fn to_string_with_generators(
         &self,
         generators: &[&dyn GeneratorSupplement<Rust>],
@@ -154,6 +515,12 @@ impl RustCodeGenerator {
         model: &Model<Rust>,
         generators: &[&dyn GeneratorSupplement<Rust>],
     ) -> (String, String) {
+        let model = if self.generates_codec(Codec::Protobuf) {
+            Cow::Borrowed(model)
+        } else {
+            Cow::Owned(Self::without_protobuf(model))
+        };
+        let model = model.as_ref();
         let file = {
             let mut string = Self::rust_module_name(&model.name);
             string.push_str(".rs");
@@ -181,17 +548,290 @@ impl RustCodeGenerator {
         }
 
         for definition in &model.definitions {
-            self.add_definition(&mut scope, definition);
-            Self::impl_definition(&mut scope, definition, generators, self.getter_and_setter);
+            self.add_definition_with_docs(&mut scope, definition, &model.docs);
+            self.impl_definition(&mut scope, definition, generators);
+            self.impl_renamed_aliases(&mut scope, definition);
 
             generators
                 .iter()
                 .for_each(|g| g.impl_supplement(&mut scope, definition));
         }
 
+        let module = Self::rust_module_name(&model.name);
+        generators
+            .iter()
+            .for_each(|g| g.impl_module_supplement(&mut scope, &module, &model.definitions));
+
+        #[cfg(feature = "protobuf")]
+        if self.generates_codec(Codec::Protobuf) {
+            self.add_services(&mut scope, model);
+        }
+
         (file, scope.to_string())
     }
 
+    /// The content of a `mod.rs` declaring the modules of the given models and re-exporting the
+    /// types, whose names are unique among all models. With `oid_tree`, nested modules following
+    /// the object identifiers of the models re-export the modules as well, for example
+    /// `iso::member_body::my_module` for a module with the object identifier `{ iso(1)
+    /// member-body(2) }`. The arcs without name are called `oid_` and their number.
+    pub fn mod_file(models: &[&Model<Rust>], oid_tree: bool) -> String {
+        use std::collections::BTreeMap;
+        use std::fmt::Write;
+
+        /// An arc of the object identifiers
+        #[derive(Default)]
+        struct Node {
+            name: Option<String>,
+            number: Option<u64>,
+            children: Vec<Node>,
+            modules: Vec<String>,
+        }
+
+        impl Node {
+            fn child(&mut self, component: &ObjectIdentifierComponent) -> &mut Node {
+                let (name, number) = match component {
+                    ObjectIdentifierComponent::NameForm(name) => (Some(name), None),
+                    ObjectIdentifierComponent::NumberForm(number) => (None, Some(*number)),
+                    ObjectIdentifierComponent::NameAndNumberForm(name, number) => {
+                        (Some(name), Some(*number))
+                    }
+                };
+                let index = self.children.iter().position(|child| {
+                    match (child.number, number, &child.name, name) {
+                        (Some(a), Some(b), _, _) => a == b,
+                        (_, _, Some(a), Some(b)) => a == b,
+                        _ => false,
+                    }
+                });
+                let index = index.unwrap_or_else(|| {
+                    self.children.push(Node::default());
+                    self.children.len() - 1
+                });
+                let child = &mut self.children[index];
+                child.name = child.name.take().or_else(|| name.cloned());
+                child.number = child.number.or(number);
+                child
+            }
+
+            fn write(&self, code: &mut String, depth: usize) {
+                let indent = "    ".repeat(depth);
+                for module in &self.modules {
+                    let _ = writeln!(code, "{indent}pub use {}{module};", "super::".repeat(depth));
+                }
+                for child in &self.children {
+                    let name = match (&child.name, child.number) {
+                        (Some(name), _) => RustCodeGenerator::rust_module_name(name),
+                        (None, number) => format!("oid_{}", number.unwrap_or_default()),
+                    };
+                    let _ = writeln!(code, "{indent}pub mod {name} {{");
+                    child.write(code, depth + 1);
+                    let _ = writeln!(code, "{indent}}}");
+                }
+            }
+        }
+
+        let mut modules = models
+            .iter()
+            .map(|model| (Self::rust_module_name(&model.name), *model))
+            .collect::<Vec<_>>();
+        modules.sort_by(|a, b| a.0.cmp(&b.0));
+
+        let mut owners = BTreeMap::<&str, Vec<&str>>::new();
+        for (module, model) in &modules {
+            for Definition(name, _) in &model.definitions {
+                owners
+                    .entry(name.as_str())
+                    .or_default()
+                    .push(module.as_str());
+            }
+        }
+
+        let mut code = String::new();
+        for (module, _) in &modules {
+            let _ = writeln!(code, "pub mod {module};");
+        }
+        let _ = writeln!(code);
+        for (module, model) in &modules {
+            let unique = model
+                .definitions
+                .iter()
+                .map(|Definition(name, _)| name.as_str())
+                .filter(|name| owners.get(name).is_some_and(|owners| owners.len() == 1))
+                .collect::<Vec<_>>();
+            match &unique[..] {
+                [] => {}
+                [name] => {
+                    let _ = writeln!(code, "pub use self::{module}::{name};");
+                }
+                names => {
+                    let _ = writeln!(code, "pub use self::{module}::{{{}}};", names.join(", "));
+                }
+            }
+        }
+
+        if oid_tree {
+            let mut root = Node::default();
+            for (module, model) in &modules {
+                if let Some(oid) = &model.oid {
+                    oid.iter()
+                        .fold(&mut root, |node, component| node.child(component))
+                        .modules
+                        .push(module.clone());
+                }
+            }
+            if !root.children.is_empty() {
+                let _ = writeln!(code);
+                root.write(&mut code, 0);
+            }
+        }
+        code
+    }
+
+    /// A copy of the model without field numbers, fixed size integers and unknown fields
+    fn without_protobuf(model: &Model<Rust>) -> Model<Rust> {
+        let mut model = model.clone();
+        for Definition(_, rust) in &mut model.definitions {
+            rust.set_protobuf_unknown_fields(false);
+            match rust {
+                Rust::Struct { fields, .. } => fields.iter_mut().for_each(|field| {
+                    field.set_protobuf_number(None);
+                    field.set_protobuf_fixed(false);
+                }),
+                Rust::DataEnum(data) => data.variants_mut().for_each(|variant| {
+                    variant.set_protobuf_number(None);
+                    variant.set_protobuf_fixed(false);
+                }),
+                Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+            }
+        }
+        model
+    }
+
+    #[cfg(feature = "protobuf")]
+    fn add_services(&self, scope: &mut Scope, model: &Model<Rust>) {
+        use crate::generate::protobuf::ProtobufDefGenerator;
+        use crate::generate::walker::CRATE_GRPC_PREFIX;
+        use std::fmt::Write;
+
+        let package = ProtobufDefGenerator::model_to_package(&model.name, model.oid.as_ref());
+        let cfg = self
+            .protobuf_feature
+            .as_ref()
+            .map(|feature| format!("#[cfg(feature = \"{feature}\")]\n"))
+            .unwrap_or_default();
+        for service in self.services.declared_in(model) {
+            let name = &service.name;
+            let methods = service
+                .methods
+                .iter()
+                .map(|method| {
+                    (
+                        Self::rust_module_name(&method.name),
+                        method,
+                        service.path(&package, method),
+                    )
+                })
+                .collect::<Vec<_>>();
+            let mut code = String::new();
+
+            let _ = writeln!(code, "/// The gRPC service `{package}.{name}`");
+            let _ = writeln!(code, "{cfg}pub trait {name} {{");
+            let _ = writeln!(code, "    type Error;");
+            for (fn_name, method, _) in &methods {
+                let _ = writeln!(code);
+                let _ = writeln!(code, "    fn {fn_name}(");
+                let _ = writeln!(code, "        &self,");
+                let _ = writeln!(code, "        request: {},", method.request);
+                let _ = writeln!(
+                    code,
+                    "    ) -> impl ::core::future::Future<Output = Result<{}, Self::Error>> + Send;",
+                    method.response
+                );
+            }
+            let _ = writeln!(code, "}}");
+            let _ = writeln!(code);
+
+            let _ = writeln!(
+                code,
+                "/// The client of the gRPC service `{package}.{name}`"
+            );
+            let _ = writeln!(code, "{cfg}pub struct {name}Client<C> {{");
+            let _ = writeln!(code, "    pub channel: C,");
+            let _ = writeln!(code, "}}");
+            let _ = writeln!(code);
+            let _ = writeln!(
+                code,
+                "{cfg}impl<C: {CRATE_GRPC_PREFIX}Channel> {name}Client<C> {{"
+            );
+            let _ = writeln!(code, "    pub fn new(channel: C) -> Self {{");
+            let _ = writeln!(code, "        Self {{ channel }}");
+            let _ = writeln!(code, "    }}");
+            for (fn_name, method, path) in &methods {
+                let _ = writeln!(code);
+                let _ = writeln!(code, "    pub async fn {fn_name}(");
+                let _ = writeln!(code, "        &mut self,");
+                let _ = writeln!(code, "        request: &{},", method.request);
+                let _ = writeln!(
+                    code,
+                    "    ) -> Result<{}, {CRATE_GRPC_PREFIX}Error<C::Error>> {{",
+                    method.response
+                );
+                let _ = writeln!(
+                    code,
+                    "        {CRATE_GRPC_PREFIX}call(&mut self.channel, \"{path}\", request).await"
+                );
+                let _ = writeln!(code, "    }}");
+            }
+            let _ = writeln!(code, "}}");
+            let _ = writeln!(code);
+
+            let _ = writeln!(
+                code,
+                "/// Dispatches the calls of the gRPC service `{package}.{name}` to its implementation"
+            );
+            let _ = writeln!(code, "{cfg}pub struct {name}Server<S>(pub S);");
+            let _ = writeln!(code);
+            let _ = writeln!(code, "{cfg}impl<S: {name}> {name}Server<S> {{");
+            let _ = writeln!(
+                code,
+                "    pub const PATHS: &'static [&'static str] = &[{}];",
+                methods
+                    .iter()
+                    .map(|(_, _, path)| format!("\"{path}\""))
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            );
+            let _ = writeln!(code);
+            let _ = writeln!(
+                code,
+                "    /// Decodes the request, calls the method of the path and encodes the response, `None`"
+            );
+            let _ = writeln!(code, "    /// if the service has no method with the path");
+            let _ = writeln!(code, "    pub async fn handle(");
+            let _ = writeln!(code, "        &self,");
+            let _ = writeln!(code, "        path: &str,");
+            let _ = writeln!(code, "        request: &[u8],");
+            let _ = writeln!(
+                code,
+                "    ) -> Option<Result<Vec<u8>, {CRATE_GRPC_PREFIX}Error<S::Error>>> {{"
+            );
+            let _ = writeln!(code, "        match path {{");
+            for (fn_name, _, path) in &methods {
+                let _ = writeln!(
+                    code,
+                    "            \"{path}\" => Some({CRATE_GRPC_PREFIX}handle(request, |r| self.0.{fn_name}(r)).await),"
+                );
+            }
+            let _ = writeln!(code, "            _ => None,");
+            let _ = writeln!(code, "        }}");
+            let _ = writeln!(code, "    }}");
+            let _ = write!(code, "}}");
+
+            scope.raw(&code);
+        }
+    }
+
     fn fmt_const(name: &str, r#type: &RustType, value: &impl Display, indent: usize) -> String {
         format!(
             "{}pub const {}: {} = {};",
@@ -206,63 +846,143 @@ impl RustCodeGenerator {
         )
     }
 
-    pub fn add_definition(&self, scope: &mut Scope, Definition(name, rust): &Definition<Rust>) {
+    pub fn add_definition(&self, scope: &mut Scope, definition: &Definition<Rust>) {
+        self.add_definition_with_docs(scope, definition, &Docs::default())
+    }
+
+    /// Like [`RustCodeGenerator::add_definition`], but with the given docs as doc comments on the
+    /// type and on its fields or variants
+    pub fn add_definition_with_docs(
+        &self,
+        scope: &mut Scope,
+        Definition(name, rust): &Definition<Rust>,
+        docs: &Docs,
+    ) {
+        let member_docs = |member: &str| Self::doc_comment(docs.get(name, Some(member)));
+        let doc = Self::doc_comment(docs.get(name, None));
         match rust {
             Rust::Struct {
                 fields,
                 tag,
                 extension_after,
                 ordering,
+                protobuf_unknown_fields,
             } => {
-                scope.raw(&Self::asn_attribute(
-                    match ordering {
-                        EncodingOrdering::Keep => "sequence",
-                        EncodingOrdering::Sort => "set",
-                    },
-                    *tag,
-                    extension_after.map(|index| fields[index].name().to_string()),
-                    &[],
+                scope.raw(format!(
+                    "{}{}",
+                    doc,
+                    self.asn_attribute_protobuf_gated(|protobuf| {
+                        Self::asn_attribute_meta(
+                            self.asn_attribute_no_inline(Self::asn_attribute_definition_type(
+                                match ordering {
+                                    EncodingOrdering::Keep => "sequence",
+                                    EncodingOrdering::Sort => "set",
+                                },
+                                rust,
+                                protobuf,
+                            )),
+                            *tag,
+                            extension_after.map(|index| {
+                                fields
+                                    .get(index)
+                                    .map(|field| field.name().to_string())
+                                    .unwrap_or_default()
+                            }),
+                            &[],
+                        )
+                    })
                 ));
+                let attrs =
+                    |field: &str| self.member_attrs(name, field, crate::rust::rust_field_name);
+                let str_ct = self.new_struct(scope, name, true);
                 Self::add_struct(
-                    self.new_struct(scope, name),
+                    str_ct,
                     name,
                     fields,
                     self.direct_field_access,
-                )
+                    &attrs,
+                    &member_docs,
+                );
+                if *protobuf_unknown_fields {
+                    str_ct.field(
+                        &format!(
+                            "{}unknown_fields",
+                            if self.direct_field_access { "pub " } else { "" }
+                        ),
+                        format!("{}UnknownFields", CRATE_PROTOBUF_PREFIX),
+                    );
+                }
             }
             Rust::Enum(plain) => {
-                scope.raw(&Self::asn_attribute(
-                    "enumerated",
-                    plain.tag(),
-                    plain.extension_after_variant().cloned(),
-                    &[],
+                scope.raw(format!(
+                    "{}{}",
+                    doc,
+                    Self::asn_attribute(
+                        self.asn_attribute_no_inline("enumerated".to_string()),
+                        plain.tag(),
+                        plain.extension_after_variant().cloned(),
+                        &[],
+                    )
                 ));
-                Self::add_enum(
-                    self.new_enum(scope, name, true).derive("Default"),
-                    name,
-                    plain,
-                )
+                let attrs = |variant: &str| {
+                    self.member_attrs(name, variant, crate::rust::rust_variant_name)
+                };
+                let en_m = self.new_enum(scope, name, true).derive("Default");
+                if self.repr_enums {
+                    en_m.r#macro(&format!("#[repr({})]", Self::enum_repr(plain)));
+                }
+                if self.non_exhaustive_enums && plain.is_extensible() {
+                    en_m.r#macro("#[non_exhaustive]");
+                }
+                Self::add_enum(en_m, name, plain, self.repr_enums, &attrs, &member_docs)
             }
             Rust::DataEnum(data) => {
-                scope.raw(&Self::asn_attribute(
-                    "choice",
-                    data.tag(),
-                    data.extension_after_variant().map(|v| v.name().to_string()),
-                    &[],
+                scope.raw(format!(
+                    "{}{}",
+                    doc,
+                    self.asn_attribute_protobuf_gated(|protobuf| {
+                        Self::asn_attribute_meta(
+                            self.asn_attribute_no_inline(Self::asn_attribute_definition_type(
+                                "choice", rust, protobuf,
+                            )),
+                            data.tag(),
+                            data.extension_after_variant().map(|v| v.name().to_string()),
+                            &[],
+                        )
+                    })
                 ));
-                Self::add_data_enum(self.new_enum(scope, name, false), name, data)
+                let attrs = |variant: &str| {
+                    self.member_attrs(name, variant, crate::rust::rust_variant_name)
+                };
+                let en_m = self.new_enum(scope, name, false);
+                if self.non_exhaustive_enums && data.is_extensible() {
+                    en_m.r#macro("#[non_exhaustive]");
+                }
+                Self::add_data_enum(en_m, name, data, &attrs, &member_docs)
             }
             Rust::TupleStruct {
                 r#type,
                 tag,
                 constants,
             } => {
-                scope.raw(&Self::asn_attribute("transparent", *tag, None, &[]));
+                scope.raw(
+                    &(doc
+                        + &Self::asn_attribute(
+                            self.asn_attribute_no_inline("transparent".to_string()),
+                            *tag,
+                            None,
+                            &[],
+                        )),
+                );
+                let validated = Self::validated_integer_range(self.validated_integers, r#type);
+                let derive_default = validated
+                    .as_ref()
+                    .is_none_or(|range| Self::validated_integer_default(range).is_none());
                 Self::add_tuple_struct(
-                    self.new_struct(scope, name),
+                    self.new_struct(scope, name, derive_default),
                     name,
                     r#type,
-                    self.direct_field_access,
+                    self.direct_field_access && validated.is_none(),
                     None,
                     &constants[..],
                 )
@@ -270,17 +990,32 @@ impl RustCodeGenerator {
         }
     }
 
-    fn add_struct(str_ct: &mut Struct, _name: &str, fields: &[Field], pub_access: bool) {
+    /// A `///` line for each line of the doc
+    fn doc_comment(doc: Option<&str>) -> String {
+        doc.map(|doc| doc.lines().map(|line| format!("/// {}\n", line)).collect())
+            .unwrap_or_default()
+    }
+
+    fn add_struct(
+        str_ct: &mut Struct,
+        _name: &str,
+        fields: &[Field],
+        pub_access: bool,
+        attrs: &dyn Fn(&str) -> String,
+        docs: &dyn Fn(&str) -> String,
+    ) {
         for field in fields {
             str_ct.field(
                 &format!(
-                    "{} {}{}",
+                    "{}{} {}{}{}",
+                    docs(field.name()),
                     Self::asn_attribute(
                         Self::asn_attribute_type(&field.r#type().clone().into_asn()),
                         field.tag(),
                         None,
                         field.constants(),
                     ),
+                    attrs(field.name()),
                     if pub_access { "pub " } else { "" },
                     Self::rust_field_name(field.name(), true),
                 ),
@@ -289,28 +1024,58 @@ impl RustCodeGenerator {
         }
     }
 
-    fn add_enum(en_m: &mut Enum, _name: &str, rust_enum: &PlainEnum) {
+    fn add_enum(
+        en_m: &mut Enum,
+        _name: &str,
+        rust_enum: &PlainEnum,
+        repr: bool,
+        attrs: &dyn Fn(&str) -> String,
+        docs: &dyn Fn(&str) -> String,
+    ) {
+        let discriminants = Self::enum_discriminants(rust_enum);
         for (index, variant) in rust_enum.variants().enumerate() {
-            let name = Self::rust_variant_name(variant);
+            let number = rust_enum
+                .numbers()
+                .map(|numbers| format!("#[asn({})] ", numbers[index]))
+                .unwrap_or_default();
+            let name = format!(
+                "{}{}{}",
+                number,
+                attrs(variant),
+                Self::rust_variant_name(variant)
+            );
             let name = if index == 0 {
                 format!("#[default] {name}")
             } else {
                 name
             };
-            en_m.new_variant(&name);
+            let name = if repr {
+                format!("{name} = {}", discriminants[index])
+            } else {
+                name
+            };
+            en_m.new_variant(&(docs(variant) + &name));
         }
     }
 
-    fn add_data_enum(en_m: &mut Enum, _name: &str, enumeration: &DataEnum) {
+    fn add_data_enum(
+        en_m: &mut Enum,
+        _name: &str,
+        enumeration: &DataEnum,
+        attrs: &dyn Fn(&str) -> String,
+        docs: &dyn Fn(&str) -> String,
+    ) {
         for variant in enumeration.variants() {
             en_m.new_variant(&format!(
-                "{} {}({})",
+                "{}{} {}{}({})",
+                docs(variant.name()),
                 Self::asn_attribute(
                     Self::asn_attribute_type(&variant.r#type().clone().into_asn()),
                     variant.tag(),
                     None,
                     &[],
                 ),
+                attrs(variant.name()),
                 Self::rust_variant_name(variant.name()),
                 variant.r#type().to_string(),
             ));
@@ -345,7 +1110,20 @@ impl RustCodeGenerator {
         constants: &[(String, String)],
     ) -> String {
         format!(
-            "#[asn({})]",
+            "#[{}]",
+            Self::asn_attribute_meta(r#type, tag, extensible_after, constants)
+        )
+    }
+
+    /// The content of the `#[asn(..)]` attribute
+    fn asn_attribute_meta<T: ToString>(
+        r#type: T,
+        tag: Option<Tag>,
+        extensible_after: Option<String>,
+        constants: &[(String, String)],
+    ) -> String {
+        format!(
+            "asn({})",
             vec![
                 Some(r#type.to_string()),
                 tag.map(Self::asn_attribute_tag),
@@ -396,12 +1174,17 @@ impl RustCodeGenerator {
                     }
                 )],
             ),
-            Type::String(size, charset) => (
+            Type::String(size, charset, alphabet) => (
                 Cow::Owned(format!("{:?}string", charset).to_lowercase()),
-                vec![size.to_constraint_string()]
-                    .into_iter()
-                    .flatten()
-                    .collect(),
+                vec![
+                    size.to_constraint_string(),
+                    alphabet
+                        .as_ref()
+                        .map(PermittedAlphabet::to_constraint_string),
+                ]
+                .into_iter()
+                .flatten()
+                .collect(),
             ),
             Type::OctetString(size) => (
                 Cow::Borrowed("octet_string"),
@@ -478,62 +1261,359 @@ impl RustCodeGenerator {
         }
     }
 
-    fn asn_attribute_extensible_after(variant: String) -> String {
-        format!("extensible_after({})", variant)
+    /// The explicit protobuf field numbers, the fields with fixed size protobuf integers, the
+    /// fields represented as `uuid::Uuid` and the boxed fields are listed after the type of the
+    /// definition. Without `protobuf`, the field numbers and fixed size integers are left out.
+    fn asn_attribute_definition_type(r#type: &str, rust: &Rust, protobuf: bool) -> String {
+        let mut attribute = r#type.to_string();
+        if let Some(numbers) = rust.protobuf_numbers().filter(|_| protobuf) {
+            attribute.push_str(&format!(
+                ", protobuf_numbers({})",
+                numbers
+                    .iter()
+                    .map(ToString::to_string)
+                    .collect::<Vec<_>>()
+                    .join(", ")
+            ));
+        }
+        let fixed = rust.protobuf_fixed();
+        if protobuf && !fixed.is_empty() {
+            attribute.push_str(&format!(", protobuf_fixed({})", fixed.join(", ")));
+        }
+        if rust.protobuf_unknown_fields() {
+            attribute.push_str(", protobuf_unknown_fields");
+        }
+        let uuid = rust.uuid();
+        if !uuid.is_empty() {
+            attribute.push_str(&format!(", uuid({})", uuid.join(", ")));
+        }
+        let boxed = rust.boxed();
+        if !boxed.is_empty() {
+            attribute.push_str(&format!(", boxed({})", boxed.join(", ")));
+        }
+        attribute
     }
 
-    fn impl_definition(
-        scope: &mut Scope,
-        Definition(name, rust): &Definition<Rust>,
-        generators: &[&dyn GeneratorSupplement<Rust>],
-        getter_and_setter: bool,
-    ) {
-        match rust {
-            Rust::Struct {
-                fields,
-                tag: _,
-                extension_after: _,
-                ordering: _,
-            } => {
-                Self::impl_consts(
-                    scope,
-                    name,
-                    fields
-                        .iter()
-                        .map(|f| (f.name_type.0.as_str(), &f.name_type.1, &f.constants[..])),
+    /// Passes the protobuf field numbers and fixed size integers to `#[asn(..)]` only if the
+    /// feature of [`RustCodeGenerator::set_protobuf_feature`] is enabled. The unknown fields are
+    /// passed regardless, because the field holding them is always declared.
+    fn asn_attribute_protobuf_gated(&self, meta: impl Fn(bool) -> String) -> String {
+        #[cfg(feature = "protobuf")]
+        if let Some(feature) = &self.protobuf_feature {
+            let (with, without) = (meta(true), meta(false));
+            if with != without {
+                return format!(
+                    "#[cfg_attr(feature = \"{feature}\", {with})]\n#[cfg_attr(not(feature = \"{feature}\"), {without})]"
                 );
-                let implementation = Self::impl_struct(scope, name, fields, getter_and_setter);
-                for g in generators {
-                    g.extend_impl_of_struct(name, implementation, fields);
-                }
             }
-            Rust::Enum(r_enum) => {
-                let implementation = Self::impl_enum(scope, name, r_enum);
-                for g in generators {
+        }
+        format!("#[{}]", meta(true))
+    }
+
+    /// See [`RustCodeGenerator::set_optimize_code_size`]
+    fn asn_attribute_no_inline(&self, mut attribute: String) -> String {
+        if self.optimize_code_size {
+            attribute.push_str(", no_inline");
+        }
+        attribute
+    }
+
+    /// Without any field in the extension root, there is none to refer to
+    fn asn_attribute_extensible_after(variant: String) -> String {
+        if variant.is_empty() {
+            "extensible".to_string()
+        } else {
+            format!("extensible_after({})", variant)
+        }
+    }
+
+    fn derives_deserialize(&self, name: &str) -> bool {
+        self.global_derives
+            .iter()
+            .chain(self.local_derives.get(name).into_iter().flatten())
+            .any(|derive| derive.ends_with("Deserialize"))
+    }
+
+    /// The `#[serde(alias = ..)]` attributes for the previous names of a field or variant
+    fn serde_aliases(&self, name: &str, identifier: &str, to_rust: fn(&str) -> String) -> String {
+        if !self.derives_deserialize(name) {
+            return String::new();
+        }
+        let current = to_rust(identifier);
+        self.renames
+            .of(name)
+            .iter()
+            .filter(|(_, new)| to_rust(new) == current)
+            .map(|(old, _)| to_rust(old))
+            .filter(|previous| *previous != current)
+            .map(|previous| format!("#[serde(alias = \"{}\")] ", previous))
+            .collect()
+    }
+
+    /// The additional attributes of the given field or variant, followed by the serde aliases
+    fn member_attrs(&self, name: &str, identifier: &str, to_rust: fn(&str) -> String) -> String {
+        let current = to_rust(identifier);
+        self.attributes
+            .of(name)
+            .iter()
+            .filter(|(member, _)| member.as_deref().is_some_and(|m| to_rust(m) == current))
+            .map(|(_, attr)| format!("#[{}] ", attr))
+            .chain(Some(self.serde_aliases(name, identifier, to_rust)))
+            .collect()
+    }
+
+    /// The additional attributes of the given type itself
+    fn type_attrs<'a>(&'a self, name: &str) -> impl Iterator<Item = &'a String> + 'a {
+        self.local_attrs.get(name).into_iter().flatten().chain(
+            self.attributes
+                .of(name)
+                .iter()
+                .filter_map(|(member, attr)| Some(attr).filter(|_| member.is_none())),
+        )
+    }
+
+    /// Deprecated getters and setters for renamed fields, constants for renamed ENUMERATED
+    /// variants and constructors for renamed CHOICE alternatives
+    fn impl_renamed_aliases(&self, scope: &mut Scope, Definition(name, rust): &Definition<Rust>) {
+        let renames = self.renames.of(name);
+        if renames.is_empty() {
+            return;
+        }
+        match rust {
+            Rust::Struct { fields, .. } => {
+                let renamed = renames
+                    .iter()
+                    .filter_map(|(old, new)| {
+                        let new = crate::rust::rust_field_name(new);
+                        let field = fields.iter().find(|field| field.name() == new)?;
+                        Some((crate::rust::rust_field_name(old), field))
+                    })
+                    .filter(|(old, field)| old != field.name())
+                    .collect::<Vec<_>>();
+                if renamed.is_empty() {
+                    return;
+                }
+                let implementation = scope.new_impl(name);
+                for (old, field) in renamed {
+                    let new = Self::rust_field_name(field.name(), true);
+                    let deprecated = format!("deprecated(note = \"renamed to {}\")", new);
+                    let r#type = field.r#type().to_string();
+                    implementation
+                        .new_fn(&Self::rust_field_name(&old, true))
+                        .attr(&deprecated)
+                        .vis("pub")
+                        .arg_ref_self()
+                        .ret(format!("&{}", r#type))
+                        .line(format!("&self.{}", new));
+                    implementation
+                        .new_fn(&format!("{}_mut", old))
+                        .attr(&deprecated)
+                        .vis("pub")
+                        .arg_mut_self()
+                        .ret(format!("&mut {}", r#type))
+                        .line(format!("&mut self.{}", new));
+                    implementation
+                        .new_fn(&format!("set_{}", old))
+                        .attr(&deprecated)
+                        .vis("pub")
+                        .arg_mut_self()
+                        .arg("value", r#type)
+                        .line(format!("self.{} = value;", new));
+                }
+            }
+            Rust::Enum(plain) => {
+                let renamed = renames
+                    .iter()
+                    .map(|(old, new)| {
+                        (
+                            crate::rust::rust_variant_name(old),
+                            crate::rust::rust_variant_name(new),
+                        )
+                    })
+                    .filter(|(_, new)| {
+                        plain
+                            .variants()
+                            .any(|variant| Self::rust_variant_name(variant) == *new)
+                    })
+                    .filter(|(old, new)| old != new)
+                    .collect::<Vec<_>>();
+                if renamed.is_empty() {
+                    return;
+                }
+                scope.raw(format!("impl {} {{", name));
+                for (old, new) in renamed {
+                    scope.raw(format!(
+                        "    #[deprecated(note = \"renamed to {new}\")]\n    \
+                        #[allow(non_upper_case_globals)]\n    \
+                        pub const {old}: Self = Self::{new};"
+                    ));
+                }
+                scope.raw("}");
+            }
+            Rust::DataEnum(data) => {
+                let renamed = renames
+                    .iter()
+                    .filter_map(|(old, new)| {
+                        let new = crate::rust::rust_variant_name(new);
+                        let variant = data
+                            .variants()
+                            .find(|variant| Self::rust_variant_name(variant.name()) == new)?;
+                        Some((crate::rust::rust_variant_name(old), variant))
+                    })
+                    .filter(|(old, variant)| *old != Self::rust_variant_name(variant.name()))
+                    .collect::<Vec<_>>();
+                if renamed.is_empty() {
+                    return;
+                }
+                let implementation = scope.new_impl(name);
+                for (old, variant) in renamed {
+                    let new = Self::rust_variant_name(variant.name());
+                    implementation
+                        .new_fn(&old)
+                        .attr(&format!("deprecated(note = \"renamed to {}\")", new))
+                        .allow("non_snake_case")
+                        .vis("pub")
+                        .arg("value", variant.r#type().to_string())
+                        .ret("Self")
+                        .line(format!("Self::{}(value)", new));
+                }
+            }
+            Rust::TupleStruct { .. } => {}
+        }
+    }
+
+    fn impl_definition(
+        &self,
+        scope: &mut Scope,
+        Definition(name, rust): &Definition<Rust>,
+        generators: &[&dyn GeneratorSupplement<Rust>],
+    ) {
+        match rust {
+            Rust::Struct {
+                fields,
+                tag: _,
+                extension_after,
+                ordering,
+                protobuf_unknown_fields,
+            } => {
+                Self::impl_consts(
+                    scope,
+                    name,
+                    fields
+                        .iter()
+                        .map(|f| (f.name_type.0.as_str(), &f.name_type.1, &f.constants[..])),
+                );
+                let additions = extension_after
+                    .and_then(|index| fields.get(index + 1..))
+                    .unwrap_or_default();
+                if !additions.is_empty() {
+                    Self::impl_struct_extension_additions_const(scope, name, additions);
+                }
+                let implementation = Self::impl_struct(scope, name, fields, self.getter_and_setter);
+                if !additions.is_empty() {
+                    Self::impl_struct_extension_additions_fns(implementation, additions);
+                }
+                for g in generators {
+                    g.extend_impl_of_struct(name, implementation, fields);
+                }
+                if self.builders && !fields.is_empty() {
+                    Self::impl_struct_builder(scope, name, fields, *protobuf_unknown_fields);
+                }
+                if self.views && *ordering == EncodingOrdering::Keep && !fields.is_empty() {
+                    Self::impl_struct_view(scope, name, fields, *protobuf_unknown_fields);
+                }
+            }
+            Rust::Enum(r_enum) => {
+                let implementation = Self::impl_enum(scope, name, r_enum);
+                for g in generators {
                     g.extend_impl_of_enum(name, implementation, r_enum);
                 }
+                if self.repr_enums {
+                    Self::impl_enum_repr_conversions(scope, name, r_enum);
+                }
+                if self.display_and_from_str {
+                    let variants = r_enum
+                        .variants()
+                        .enumerate()
+                        .map(|(index, v)| {
+                            let variant = Self::rust_variant_name(v);
+                            let identifier = r_enum.identifier(index).unwrap_or(v);
+                            (identifier, format!("{}::{}", name, variant))
+                        })
+                        .collect::<Vec<_>>();
+                    Self::impl_enum_display_and_from_str(scope, name, &variants, "", "");
+                }
             }
             Rust::DataEnum(enumeration) => {
-                let implementation = Self::impl_data_enum(scope, name, enumeration);
+                Self::impl_data_enum_metadata_consts(scope, name, enumeration);
+                let implementation =
+                    Self::impl_data_enum(scope, name, enumeration, self.getter_and_setter);
                 for g in generators {
                     g.extend_impl_of_data_enum(name, implementation, enumeration);
                 }
-                Self::impl_data_enum_default(scope, name, enumeration);
+                // there is no value of an empty CHOICE to default to
+                if !enumeration.is_empty() {
+                    Self::impl_data_enum_default(scope, name, enumeration);
+                    Self::impl_data_enum_variant_kind(scope, name, enumeration);
+                }
+                // nor an identifier to display
+                if self.display_and_from_str && !enumeration.is_empty() {
+                    let variants = enumeration
+                        .variants()
+                        .enumerate()
+                        .map(|(index, v)| {
+                            let variant = Self::rust_variant_name(v.name());
+                            let identifier = enumeration.identifier(index).unwrap_or(v.name());
+                            (identifier, format!("{}::{}", name, variant))
+                        })
+                        .collect::<Vec<_>>();
+                    Self::impl_enum_display_and_from_str(
+                        scope,
+                        name,
+                        &variants,
+                        "(_)",
+                        "(Default::default())",
+                    );
+                }
             }
             Rust::TupleStruct {
                 r#type: inner,
                 tag: _,
                 constants,
             } => {
-                Self::impl_consts(scope, name, Some(("", inner, &constants[..])).into_iter());
-                let implementation = Self::impl_tuple_struct(scope, name, inner);
+                if inner.integer_range_str().is_some() {
+                    Self::impl_named_numbers(scope, name, constants);
+                } else {
+                    Self::impl_consts(scope, name, Some(("", inner, &constants[..])).into_iter());
+                }
+                let validated = Self::validated_integer_range(self.validated_integers, inner);
+                let implementation = Self::impl_tuple_struct(
+                    scope,
+                    name,
+                    inner,
+                    constants,
+                    self.getter_and_setter,
+                    validated.is_none(),
+                );
                 for g in generators {
                     g.extend_impl_of_tuple(name, implementation, inner);
                 }
-                Self::impl_tuple_struct_const_new(scope, name, inner);
-                Self::impl_tuple_struct_deref(scope, name, inner);
-                Self::impl_tuple_struct_deref_mut(scope, name, inner);
-                Self::impl_tuple_struct_from(scope, name, inner);
+                match validated {
+                    Some(range) => {
+                        Self::impl_tuple_struct_try_new(scope, name, inner, &range);
+                        Self::impl_tuple_struct_deref(scope, name, inner);
+                        Self::impl_tuple_struct_try_from(scope, name, inner);
+                        if let Some(default) = Self::validated_integer_default(&range) {
+                            Self::impl_tuple_struct_default(scope, name, default);
+                        }
+                    }
+                    None => {
+                        Self::impl_tuple_struct_const_new(scope, name, inner);
+                        Self::impl_tuple_struct_deref(scope, name, inner);
+                        Self::impl_tuple_struct_deref_mut(scope, name, inner);
+                        Self::impl_tuple_struct_from(scope, name, inner);
+                    }
+                }
             }
         }
     }
@@ -586,10 +1666,141 @@ impl RustCodeGenerator {
             .line("value.0");
     }
 
-    fn impl_tuple_struct<'a>(scope: &'a mut Scope, name: &str, rust: &RustType) -> &'a mut Impl {
+    /// The range of the inner INTEGER of a transparent type, if it is validated on construction
+    fn validated_integer_range(
+        validated_integers: bool,
+        inner: &RustType,
+    ) -> Option<Range<String>> {
+        match inner {
+            RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_)
+            | RustType::I128(_)
+            | RustType::U128(_)
+                if validated_integers =>
+            {
+                inner
+                    .integer_range_str()
+                    .filter(|range| !range.extensible())
+            }
+            _ => None,
+        }
+    }
+
+    /// The bound closest to zero, if zero - the derived default - is not within the range
+    fn validated_integer_default(range: &Range<String>) -> Option<&str> {
+        let parse = |bound: &str| bound.parse::<i128>().unwrap_or_default();
+        if parse(range.min()) > 0 {
+            Some(range.min())
+        } else if parse(range.max()) < 0 {
+            Some(range.max())
+        } else {
+            None
+        }
+    }
+
+    fn impl_tuple_struct_try_new(
+        scope: &mut Scope,
+        name: &str,
+        rust: &RustType,
+        range: &Range<String>,
+    ) {
+        scope
+            .new_impl(name)
+            .new_fn("try_new")
+            .vis("pub const")
+            .arg("value", rust.to_string())
+            .ret(format!("Result<Self, {}>", rust.to_string()))
+            .line(format!(
+                "if matches!(value, {}..={}) {{",
+                Self::format_number_nicely(range.min()),
+                Self::format_number_nicely(range.max()),
+            ))
+            .line("    Ok(Self(value))")
+            .line("} else {")
+            .line("    Err(value)")
+            .line("}");
+    }
+
+    fn impl_tuple_struct_try_from(scope: &mut Scope, name: &str, rust: &RustType) {
+        scope
+            .new_impl(name)
+            .impl_trait(format!("::core::convert::TryFrom<{}>", rust.to_string()))
+            .associate_type("Error", rust.to_string())
+            .new_fn("try_from")
+            .arg("value", rust.to_string())
+            .ret("Result<Self, Self::Error>")
+            .line("Self::try_new(value)");
+        scope
+            .new_impl(&rust.to_string())
+            .impl_trait(format!("::core::convert::From<{}>", name))
+            .new_fn("from")
+            .arg("value", name)
+            .ret("Self")
+            .line("value.0");
+    }
+
+    fn impl_tuple_struct_default(scope: &mut Scope, name: &str, default: &str) {
+        scope
+            .new_impl(name)
+            .impl_trait("Default")
+            .new_fn("default")
+            .ret("Self")
+            .line(format!("Self({})", Self::format_number_nicely(default)));
+    }
+
+    fn impl_tuple_struct<'a>(
+        scope: &'a mut Scope,
+        name: &str,
+        rust: &RustType,
+        constants: &[(String, String)],
+        getter_and_setter: bool,
+        mutable: bool,
+    ) -> &'a mut Impl {
         let implementation = scope.new_impl(name);
+        if getter_and_setter {
+            Self::impl_tuple_struct_value_get(implementation, rust);
+            // the value of a validated newtype is only changed through its constructors
+            if mutable {
+                Self::impl_tuple_struct_value_get_mut(implementation, rust);
+                Self::impl_tuple_struct_value_set(implementation, rust);
+            }
+        }
         Self::add_min_max_fn_if_applicable(implementation, None, rust);
+        Self::add_named_bit_fns_if_applicable(implementation, None, rust, constants);
+        implementation
+    }
+
+    fn impl_tuple_struct_value_get(implementation: &mut Impl, rust: &RustType) {
+        implementation
+            .new_fn("value")
+            .vis("pub")
+            .arg_ref_self()
+            .ret(format!("&{}", rust.to_string()))
+            .line("&self.0");
+    }
+
+    fn impl_tuple_struct_value_get_mut(implementation: &mut Impl, rust: &RustType) {
+        implementation
+            .new_fn("value_mut")
+            .vis("pub")
+            .arg_mut_self()
+            .ret(format!("&mut {}", rust.to_string()))
+            .line("&mut self.0");
+    }
+
+    fn impl_tuple_struct_value_set(implementation: &mut Impl, rust: &RustType) {
         implementation
+            .new_fn("set_value")
+            .vis("pub")
+            .arg_mut_self()
+            .arg("value", rust.to_string())
+            .line("self.0 = value;");
     }
 
     fn impl_struct<'a>(
@@ -608,10 +1819,158 @@ impl RustCodeGenerator {
             }
 
             Self::add_min_max_fn_if_applicable(implementation, Some(field.name()), field.r#type());
+            Self::add_named_bit_fns_if_applicable(
+                implementation,
+                Some(field.name()),
+                field.r#type(),
+                field.constants(),
+            );
         }
         implementation
     }
 
+    /// Optional fields and fields with a default value can be omitted, all other fields must be set
+    fn impl_struct_builder(
+        scope: &mut Scope,
+        name: &str,
+        fields: &[Field],
+        protobuf_unknown_fields: bool,
+    ) {
+        let builder = format!("{}Builder", name);
+        scope
+            .new_impl(name)
+            .new_fn("builder")
+            .vis("pub")
+            .ret(&builder)
+            .line(format!("{}::default()", builder));
+
+        let str_ct = scope
+            .new_struct(&builder)
+            .vis("pub")
+            .derive("Default")
+            .derive("Debug")
+            .derive("Clone");
+        for field in fields {
+            str_ct.field(
+                &Self::rust_field_name(field.name(), true),
+                format!("Option<{}>", field.r#type().clone().no_option().to_string()),
+            );
+        }
+
+        let implementation = scope.new_impl(&builder);
+        for field in fields {
+            let field_name = Self::rust_field_name(field.name(), true);
+            implementation
+                .new_fn(&field_name)
+                .vis("pub")
+                .arg_self()
+                .arg("value", field.r#type().clone().no_option().to_string())
+                .ret("Self")
+                .line(format!("Self {{ {}: Some(value), ..self }}", field_name));
+        }
+
+        let build = implementation
+            .new_fn("build")
+            .vis("pub")
+            .arg_self()
+            .ret(format!("Result<{}, &'static str>", name));
+        build.line(format!("Ok({} {{", name));
+        for field in fields {
+            let field_name = Self::rust_field_name(field.name(), true);
+            build.line(match field.r#type() {
+                RustType::Option(_) => format!("    {}: self.{},", field_name, field_name),
+                RustType::Default(inner, default) => format!(
+                    "    {}: self.{}.{},",
+                    field_name,
+                    field_name,
+                    Self::default_value_unwrap(inner, default)
+                ),
+                _ => format!(
+                    "    {}: self.{}.ok_or({:?})?,",
+                    field_name, field_name, field_name
+                ),
+            });
+        }
+        if protobuf_unknown_fields {
+            build.line("    unknown_fields: Default::default(),");
+        }
+        build.line("})");
+    }
+
+    /// The fields are read in the order of the definition, which is why SETs - which are encoded
+    /// in the canonical order of their tags - have no view
+    fn impl_struct_view(
+        scope: &mut Scope,
+        name: &str,
+        fields: &[Field],
+        protobuf_unknown_fields: bool,
+    ) {
+        let view = format!("{}View", name);
+        let str_ct = scope
+            .new_struct(&view)
+            .vis("pub")
+            .derive("Default")
+            .derive("Debug")
+            .derive("Clone")
+            .derive("PartialEq");
+        for field in fields {
+            str_ct.field(
+                &format!("pub {}", Self::rust_field_name(field.name(), true)),
+                format!("Option<{}>", field.r#type().clone().no_option().to_string()),
+            );
+        }
+
+        let read = scope
+            .new_impl(name)
+            .new_fn("read_view")
+            .vis("pub")
+            .generic(format!("R: {}Reader", CRATE_SYN_PREFIX))
+            .arg("reader", "&mut R")
+            .arg("fields", "&[&str]")
+            .ret(format!("Result<{}, R::Error>", view));
+        read.line(format!(
+            "reader.read_sequence::<Self, {}, _>(|reader| {{",
+            view
+        ));
+        read.line(format!("    let mut view = {}::default();", view));
+        for field in fields {
+            read.line(format!(
+                "    let value = AsnDef{}::read_value(reader)?;",
+                AsnDefWriter::combined_field_type_name(name, field.name())
+            ));
+            read.line(format!(
+                "    if fields.contains(&{:?}) {{",
+                AsnDefWriter::asn_identifier(field.name())
+            ));
+            let field_name = Self::rust_field_name(field.name(), true);
+            read.line(match field.r#type() {
+                RustType::Option(_) => format!("        view.{} = value;", field_name),
+                _ => format!("        view.{} = Some(value);", field_name),
+            });
+            read.line("    }");
+        }
+        if protobuf_unknown_fields {
+            read.line("    reader.read_protobuf_unknown_fields()?;");
+        }
+        read.line("    Ok(view)");
+        read.line("})");
+    }
+
+    /// Allocates the `DEFAULT` value of a field only if it is needed
+    fn default_value_unwrap(inner: &RustType, default: &LiteralValue) -> String {
+        let literal = default.as_rust_const_literal(true);
+        match inner {
+            RustType::Complex(r#type, _)
+                if !matches!(default, LiteralValue::EnumeratedVariant(..)) =>
+            {
+                format!("unwrap_or({}({}))", r#type, literal)
+            }
+            RustType::String(..) => format!("unwrap_or_else(|| {}.to_string())", literal),
+            RustType::VecU8(_) => format!("unwrap_or_else(|| {}.to_vec())", literal),
+            _ => format!("unwrap_or({})", literal),
+        }
+    }
+
     fn impl_consts<'a>(
         scope: &mut Scope,
         name: &str,
@@ -641,7 +2000,106 @@ impl RustCodeGenerator {
         }
     }
 
+    /// The named numbers of an INTEGER type are constants of the type itself, `name()` looks up
+    /// the name of a value (the first one, if several names have the same value)
+    fn impl_named_numbers(scope: &mut Scope, name: &str, constants: &[(String, String)]) {
+        if constants.is_empty() {
+            return;
+        }
+        let implementation = scope.new_impl(name);
+        for (constant, value) in constants {
+            implementation.associate_const(constant, "Self", format!("Self({})", value), "pub");
+        }
+        let mut match_block = Block::new("match self.0");
+        for (index, (constant, value)) in constants.iter().enumerate() {
+            if constants[..index].iter().all(|(_, other)| other != value) {
+                match_block.line(format!("{} => Some({:?}),", value, constant));
+            }
+        }
+        match_block.line("_ => None,");
+        implementation
+            .new_fn("name")
+            .vis("pub const")
+            .arg_ref_self()
+            .ret("Option<&'static str>")
+            .push_block(match_block);
+    }
+
+    /// Exposes the names of the fields after the extension marker, which are only present if
+    /// the peer knows the extended definition
+    fn impl_struct_extension_additions_const(scope: &mut Scope, name: &str, additions: &[Field]) {
+        scope.raw(format!("impl {} {{", name));
+        scope.raw(format!(
+            "    pub const EXTENSION_ADDITIONS: [&'static str; {}] = [{}];",
+            additions.len(),
+            additions
+                .iter()
+                .map(|f| format!("{:?}", f.name()))
+                .collect::<Vec<_>>()
+                .join(", ")
+        ));
+        scope.raw("}");
+    }
+
+    /// Distinguishes the extension additions from the `OPTIONAL` fields of the extension root
+    fn impl_struct_extension_additions_fns(implementation: &mut Impl, additions: &[Field]) {
+        let presence = additions
+            .iter()
+            .map(|field| {
+                let name = Self::rust_field_name(field.name(), true);
+                let condition = match field.r#type() {
+                    RustType::Default(inner, default) => match inner.as_ref() {
+                        RustType::Complex(r#type, _)
+                            if !matches!(default, LiteralValue::EnumeratedVariant(..)) =>
+                        {
+                            format!(
+                                "self.{} != {}({})",
+                                name,
+                                r#type,
+                                default.as_rust_const_literal(false)
+                            )
+                        }
+                        _ => format!("self.{} != {}", name, default.as_rust_const_literal(false)),
+                    },
+                    _ => format!("self.{}.is_some()", name),
+                };
+                (name, condition)
+            })
+            .collect::<Vec<_>>();
+
+        implementation
+            .new_fn("has_extension_additions")
+            .vis("pub")
+            .arg_ref_self()
+            .ret("bool")
+            .line(
+                presence
+                    .iter()
+                    .map(|(_, condition)| condition.as_str())
+                    .collect::<Vec<_>>()
+                    .join(" || "),
+            );
+
+        let present = implementation
+            .new_fn("present_extension_additions")
+            .vis("pub")
+            .arg_ref_self()
+            .ret("Vec<&'static str>")
+            .line("let mut present = Vec::new();");
+        for (name, condition) in &presence {
+            let mut block = Block::new(&format!("if {}", condition));
+            block.line(format!("present.push({:?});", name));
+            present.push_block(block);
+        }
+        present.line("present");
+    }
+
     fn impl_struct_field_get(implementation: &mut Impl, field_name: &str, field_type: &RustType) {
+        // a reference to the box itself is of no use
+        let field_type = match field_type {
+            RustType::Box(inner) => inner,
+            other => other,
+        };
         implementation
             .new_fn(&Self::rust_field_name(field_name, true))
             .vis("pub")
@@ -684,22 +2142,152 @@ impl RustCodeGenerator {
         Self::impl_enum_value_fn(implementation, name, r_enum);
         Self::impl_enum_values_fn(implementation, name, r_enum);
         Self::impl_enum_value_index_fn(implementation, name, r_enum);
+        if let Some(numbers) = r_enum.numbers() {
+            Self::impl_enum_number_fns(implementation, name, r_enum, numbers);
+        }
         implementation
     }
 
-    fn impl_enum_value_fn(implementation: &mut Impl, name: &str, r_enum: &PlainEnum) {
-        let value_fn = implementation
-            .new_fn("variant")
-            .vis("pub")
-            .arg("index", "usize")
-            .ret("Option<Self>");
+    /// The declared numbers or - if there are none - the indices of the variants
+    fn enum_discriminants(r_enum: &PlainEnum) -> Vec<usize> {
+        r_enum
+            .numbers()
+            .map(<[usize]>::to_vec)
+            .unwrap_or_else(|| (0..r_enum.len()).collect())
+    }
 
-        let mut block_match = Block::new("match index");
+    fn enum_repr(r_enum: &PlainEnum) -> &'static str {
+        let max = Self::enum_discriminants(r_enum)
+            .into_iter()
+            .max()
+            .unwrap_or_default() as u64;
+        if max > u64::from(u32::MAX) {
+            "u64"
+        } else if max > u64::from(u16::MAX) {
+            "u32"
+        } else if max > u64::from(u8::MAX) {
+            "u16"
+        } else {
+            "u8"
+        }
+    }
 
-        for (index, variant) in r_enum.variants().enumerate() {
-            block_match.line(format!(
-                "{} => Some({}::{}),",
-                index,
+    fn impl_enum_repr_conversions(scope: &mut Scope, name: &str, r_enum: &PlainEnum) {
+        let mut match_block = Block::new("match value");
+        for (variant, discriminant) in r_enum.variants().zip(Self::enum_discriminants(r_enum)) {
+            match_block.line(format!(
+                "{} => Ok({}::{}),",
+                discriminant,
+                name,
+                Self::rust_variant_name(variant)
+            ));
+        }
+        match_block.line("_ => Err(value),");
+
+        scope
+            .new_impl(name)
+            .impl_trait("::core::convert::TryFrom<u64>")
+            .associate_type("Error", "u64")
+            .new_fn("try_from")
+            .arg("value", "u64")
+            .ret("Result<Self, u64>")
+            .push_block(match_block);
+
+        scope
+            .new_impl("u64")
+            .impl_trait(format!("::core::convert::From<{}>", name))
+            .new_fn("from")
+            .arg("value", name)
+            .ret("Self")
+            .line("value as u64");
+    }
+
+    /// Maps the paths of the variants from and to their ASN.1 identifiers
+    fn impl_enum_display_and_from_str(
+        scope: &mut Scope,
+        name: &str,
+        variants: &[(&str, String)],
+        pattern_suffix: &str,
+        constructor_suffix: &str,
+    ) {
+        let mut display_block = Block::new("let identifier = match self");
+        let mut from_str_block = Block::new("match value");
+        for (identifier, path) in variants {
+            display_block.line(format!("{}{} => {:?},", path, pattern_suffix, identifier));
+            from_str_block.line(format!(
+                "{:?} => Ok({}{}),",
+                identifier, path, constructor_suffix
+            ));
+        }
+        display_block.after(";");
+        from_str_block.line("_ => Err(value.to_string()),");
+
+        scope
+            .new_impl(name)
+            .impl_trait("::core::fmt::Display")
+            .new_fn("fmt")
+            .arg_ref_self()
+            .arg("f", "&mut ::core::fmt::Formatter<'_>")
+            .ret("::core::fmt::Result")
+            .push_block(display_block)
+            .line("f.write_str(identifier)");
+
+        scope
+            .new_impl(name)
+            .impl_trait("::core::str::FromStr")
+            .associate_type("Err", "String")
+            .new_fn("from_str")
+            .arg("value", "&str")
+            .ret("Result<Self, Self::Err>")
+            .push_block(from_str_block);
+    }
+
+    /// The numbers declared in the ASN.1 definition, which - unlike the index - are not what
+    /// UPER serializes
+    fn impl_enum_number_fns(
+        implementation: &mut Impl,
+        name: &str,
+        r_enum: &PlainEnum,
+        numbers: &[usize],
+    ) {
+        let mut value_block = Block::new("match self");
+        let mut from_value_block = Block::new("match value");
+
+        for (variant, number) in r_enum.variants().zip(numbers) {
+            let variant = Self::rust_variant_name(variant);
+            value_block.line(format!("{}::{} => {},", name, variant, number));
+            from_value_block.line(format!("{} => Some({}::{}),", number, name, variant));
+        }
+        from_value_block.line("_ => None,");
+
+        implementation
+            .new_fn("value")
+            .vis("pub const")
+            .arg_self()
+            .ret("usize")
+            .push_block(value_block);
+
+        implementation
+            .new_fn("from_value")
+            .vis("pub const")
+            .arg("value", "usize")
+            .ret("Option<Self>")
+            .push_block(from_value_block);
+    }
+
+    fn impl_enum_value_fn(implementation: &mut Impl, name: &str, r_enum: &PlainEnum) {
+        let value_fn = implementation
+            .new_fn("variant")
+            .vis("pub")
+            .arg("index", "usize")
+            .ret("Option<Self>");
+
+        let mut block_match = Block::new("match index");
+
+        for (index, variant) in r_enum.variants().enumerate() {
+            block_match.line(format!(
+                "{} => Some({}::{}),",
+                index,
                 name,
                 Self::rust_variant_name(variant)
             ));
@@ -744,10 +2332,42 @@ impl RustCodeGenerator {
         ordinal_fn.push_block(block);
     }
 
+    /// Exposes the names, types and tags of the alternatives as const arrays, in the same order
+    /// as returned by `variants()` and indexed by `value_index()`
+    fn impl_data_enum_metadata_consts(scope: &mut Scope, name: &str, enumeration: &DataEnum) {
+        let len = enumeration.len();
+        let join = |f: &dyn Fn(&DataVariant) -> String| {
+            enumeration.variants().map(f).collect::<Vec<_>>().join(", ")
+        };
+
+        scope.raw(format!("impl {} {{", name));
+        scope.raw(format!(
+            "    pub const VARIANT_NAMES: [&'static str; {}] = [{}];",
+            len,
+            join(&|v| format!("{:?}", Self::rust_variant_name(v.name())))
+        ));
+        scope.raw(format!(
+            "    pub const VARIANT_TYPES: [&'static str; {}] = [{}];",
+            len,
+            join(&|v| format!("{:?}", v.r#type().to_string()))
+        ));
+        scope.raw(format!(
+            "    pub const VARIANT_TAGS: [Option<{}Tag>; {}] = [{}];",
+            CRATE_MODEL_PREFIX,
+            len,
+            join(&|v| match v.tag().or_else(|| v.r#type().tag()) {
+                Some(tag) => format!("Some({}Tag::{:?})", CRATE_MODEL_PREFIX, tag),
+                None => "None".to_string(),
+            })
+        ));
+        scope.raw("}");
+    }
+
     fn impl_data_enum<'a>(
         scope: &'a mut Scope,
         name: &str,
         enumeration: &DataEnum,
+        getter_and_setter: bool,
     ) -> &'a mut Impl {
         let implementation = scope.new_impl(name);
 
@@ -756,12 +2376,69 @@ impl RustCodeGenerator {
 
         for variant in enumeration.variants() {
             let field_name = Self::rust_module_name(variant.name());
+            if getter_and_setter {
+                Self::impl_data_enum_variant_accessors(
+                    implementation,
+                    name,
+                    enumeration,
+                    variant,
+                    &field_name,
+                );
+            }
             Self::add_min_max_fn_if_applicable(implementation, Some(&field_name), variant.r#type());
         }
 
         implementation
     }
 
+    fn impl_data_enum_variant_accessors(
+        implementation: &mut Impl,
+        name: &str,
+        enumeration: &DataEnum,
+        variant: &DataVariant,
+        field_name: &str,
+    ) {
+        let variant_name = Self::rust_variant_name(variant.name());
+        let pattern = |binding: &str| format!("{}::{}({})", name, variant_name, binding);
+        // a second arm would be unreachable
+        let exhaustive = enumeration.len() == 1;
+        let (r#type, value) = match variant.r#type() {
+            RustType::Box(inner) => (inner.to_string(), "value.as_ref()"),
+            other => (other.to_string(), "value"),
+        };
+
+        implementation
+            .new_fn(&format!("is_{}", field_name))
+            .vis("pub")
+            .arg_ref_self()
+            .ret("bool")
+            .line(format!("matches!(self, {})", pattern("_")));
+
+        let mut block = Block::new("match self");
+        block.line(format!("{} => Some({}),", pattern("value"), value));
+        if !exhaustive {
+            block.line("_ => None,");
+        }
+        implementation
+            .new_fn(&format!("as_{}", field_name))
+            .vis("pub")
+            .arg_ref_self()
+            .ret(format!("Option<&{}>", r#type))
+            .push_block(block);
+
+        let mut block = Block::new("match self");
+        block.line(format!("{} => Ok(value),", pattern("value")));
+        if !exhaustive {
+            block.line("other => Err(other),");
+        }
+        implementation
+            .new_fn(&format!("into_{}", field_name))
+            .vis("pub")
+            .arg_self()
+            .ret(format!("Result<{}, Self>", variant.r#type().to_string()))
+            .push_block(block);
+    }
+
     fn impl_data_enum_values_fn(implementation: &mut Impl, name: &str, enumeration: &DataEnum) {
         let values_fn = implementation
             .new_fn("variants")
@@ -790,7 +2467,12 @@ impl RustCodeGenerator {
             .vis("pub")
             .ret("usize");
 
-        let mut block = Block::new("match self");
+        // an empty match on a reference is not exhaustive, but on the uninhabited type it is
+        let mut block = Block::new(if enumeration.is_empty() {
+            "match *self"
+        } else {
+            "match self"
+        });
         enumeration
             .variants()
             .enumerate()
@@ -806,6 +2488,63 @@ impl RustCodeGenerator {
         ordinal_fn.push_block(block);
     }
 
+    /// The `FooVariantKind` tells the alternative of a `Foo` without its value, which
+    /// `Foo::peek_variant` reads from an uPER message before - and without - decoding it
+    fn impl_data_enum_variant_kind(scope: &mut Scope, name: &str, enumeration: &DataEnum) {
+        let kind = format!("{}VariantKind", name);
+        let kind_enum = scope
+            .new_enum(&kind)
+            .vis("pub")
+            .derive("Debug")
+            .derive("Copy")
+            .derive("Clone")
+            .derive("PartialEq")
+            .derive("Eq")
+            .derive("Hash");
+        for variant in enumeration.variants() {
+            kind_enum.new_variant(Self::rust_variant_name(variant.name()));
+        }
+
+        let implementation = scope.new_impl(name);
+
+        let mut block = Block::new("match self");
+        for variant in enumeration.variants() {
+            let variant = Self::rust_variant_name(variant.name());
+            block.line(format!(
+                "{}::{}(_) => {}::{},",
+                name, variant, kind, variant
+            ));
+        }
+        implementation
+            .new_fn("variant_kind")
+            .vis("pub")
+            .arg_ref_self()
+            .ret(&kind)
+            .push_block(block);
+
+        let mut block = Block::new("match reader.peek_choice_index::<Self>()?");
+        for (index, variant) in enumeration.variants().enumerate() {
+            block.line(format!(
+                "{} => Ok({}::{}),",
+                index,
+                kind,
+                Self::rust_variant_name(variant.name())
+            ));
+        }
+        block.line(format!(
+            "index => Err({}ErrorKind::InvalidChoiceIndex(index, {}).into()),",
+            CRATE_PER_PREFIX,
+            enumeration.len()
+        ));
+        implementation
+            .new_fn("peek_variant")
+            .vis("pub")
+            .generic(format!("B: {}unaligned::ScopedBitRead", CRATE_PER_PREFIX))
+            .arg("reader", format!("&mut {}UperReader<B>", CRATE_RW_PREFIX))
+            .ret(format!("Result<{}, {}Error>", kind, CRATE_PER_PREFIX))
+            .push_block(block);
+    }
+
     fn impl_data_enum_default(scope: &mut Scope, name: &str, enumeration: &DataEnum) {
         scope
             .new_impl(name)
@@ -843,6 +2582,47 @@ impl RustCodeGenerator {
         }
     }
 
+    /// The named bits of a BIT STRING can be tested and assigned through `is_<bit>()` and
+    /// `set_<bit>(bool)`, which refer to the constants of the bit positions
+    fn add_named_bit_fns_if_applicable(
+        implementation: &mut Impl,
+        field_name: Option<&str>,
+        field_type: &RustType,
+        constants: &[(String, String)],
+    ) {
+        if !matches!(field_type, RustType::BitVec(_)) {
+            return;
+        }
+        let (prefix, field) = if let Some(field_name) = field_name {
+            (
+                format!("{}_", field_name),
+                Self::rust_field_name(field_name, true),
+            )
+        } else {
+            (String::new(), "0".to_string())
+        };
+        for (constant, _value) in constants {
+            let bit = format!("{}{}", prefix, constant.to_lowercase());
+            let constant = format!("Self::{}", bit.to_uppercase());
+            implementation
+                .new_fn(&format!("is_{}", bit))
+                .vis("pub")
+                .arg_ref_self()
+                .ret("bool")
+                .line(format!("self.{}.is_bit_set({})", field, constant));
+            implementation
+                .new_fn(&format!("set_{}", bit))
+                .vis("pub")
+                .arg_mut_self()
+                .arg("value", "bool")
+                .line("if value {")
+                .line(format!("    self.{}.set_bit({});", field, constant))
+                .line("} else {")
+                .line(format!("    self.{}.reset_bit({});", field, constant))
+                .line("}");
+        }
+    }
+
     fn format_number_nicely(string: &str) -> String {
         let mut out = String::with_capacity(string.len() * 2);
         let mut pos = (3 - string.len() % 3) % 3;
@@ -858,19 +2638,25 @@ impl RustCodeGenerator {
         out
     }
 
+    /// With `check_for_keywords`, a keyword is escaped as raw identifier, such as `r#type`, so
+    /// that serde and the `#[asn(..)]` macro still see the ASN.1 identifier. The keywords, that
+    /// cannot be raw identifiers (`self`, `Self`, `super` and `crate`), get an `_` appended.
     pub fn rust_field_name(name: &str, check_for_keywords: bool) -> String {
         let mut name = name.replace('-', "_");
-        if check_for_keywords {
-            for keyword in &KEYWORDS {
-                if keyword.eq(&name) {
-                    name.push('_');
-                    return name;
-                }
+        if check_for_keywords && Self::is_keyword(&name) {
+            if NON_RAW_KEYWORDS.contains(&name.as_str()) {
+                name.push('_');
+            } else {
+                name.insert_str(0, "r#");
             }
         }
         name
     }
 
+    pub fn is_keyword(name: &str) -> bool {
+        KEYWORDS.contains(&name)
+    }
+
     pub fn rust_variant_name(name: &str) -> String {
         let mut out = String::new();
         let mut next_upper = true;
@@ -915,56 +2701,192 @@ impl RustCodeGenerator {
         out
     }
 
-    fn new_struct<'a>(&self, scope: &'a mut Scope, name: &str) -> &'a mut Struct {
-        let str_ct = scope
-            .new_struct(name)
-            .vis("pub")
-            .derive("Default")
-            .derive("Debug")
-            .derive("Clone")
-            .derive("PartialEq")
-            .derive("Hash");
-        self.global_derives.iter().for_each(|derive| {
-            str_ct.derive(derive);
-        });
-        if let Some(local_derives) = self.local_derives.get(name) {
-            local_derives.iter().for_each(|derive| {
-                str_ct.derive(derive);
-            });
+    fn new_struct<'a>(
+        &self,
+        scope: &'a mut Scope,
+        name: &str,
+        derive_default: bool,
+    ) -> &'a mut Struct {
+        let str_ct = scope.new_struct(name).vis("pub");
+        if derive_default {
+            str_ct.derive("Default");
         }
-        if let Some(local_attrs) = self.local_attrs.get(name) {
-            local_attrs.iter().for_each(|attr| {
-                str_ct.attr(attr);
-            });
+        const FIXED: [&str; 4] = ["Debug", "Clone", "PartialEq", "Hash"];
+        for derive in FIXED {
+            str_ct.derive(derive);
         }
+        self.additional_derives(name, &FIXED).for_each(|derive| {
+            str_ct.derive(derive);
+        });
+        self.type_attrs(name).for_each(|attr| {
+            str_ct.attr(attr);
+        });
         str_ct
     }
 
     fn new_enum<'a>(&self, scope: &'a mut Scope, name: &str, c_enum: bool) -> &'a mut Enum {
-        let en_m = scope
-            .new_enum(name)
-            .vis("pub")
-            .derive("Debug")
-            .derive("Clone")
-            .derive("PartialEq")
-            .derive("Hash");
-        if c_enum {
-            en_m.derive("Copy").derive("PartialOrd").derive("Eq");
+        let en_m = scope.new_enum(name).vis("pub");
+        let fixed: &[&str] = if c_enum {
+            &[
+                "Debug",
+                "Clone",
+                "PartialEq",
+                "Hash",
+                "Copy",
+                "PartialOrd",
+                "Eq",
+            ]
+        } else {
+            &["Debug", "Clone", "PartialEq", "Hash"]
+        };
+        for derive in fixed {
+            en_m.derive(derive);
         }
-        self.global_derives.iter().for_each(|derive| {
+        self.additional_derives(name, fixed).for_each(|derive| {
             en_m.derive(derive);
         });
-        if let Some(local_derives) = self.local_derives.get(name) {
-            local_derives.iter().for_each(|derive| {
-                en_m.derive(derive);
-            });
+        self.type_attrs(name).for_each(|attr| {
+            en_m.r#macro(&format!("#[{attr}]")); // Workaround for missing `.attr` for enums in codegen
+        });
+        en_m
+    }
+
+    /// The automatic derives, which are neither in the given fixed derives nor in the global or
+    /// local derives, followed by the global and local derives of the given type
+    fn additional_derives<'a>(
+        &'a self,
+        name: &str,
+        fixed: &[&str],
+    ) -> impl Iterator<Item = &'a str> + 'a {
+        let global_and_local = self
+            .global_derives
+            .iter()
+            .chain(self.local_derives.get(name).into_iter().flatten())
+            .map(String::as_str);
+        let derivable = if self.auto_derives {
+            self.derivable(name, &mut Vec::new())
+        } else {
+            Derivable::NONE
+        };
+        let automatic = [
+            ("Copy", derivable.copy),
+            ("Eq", derivable.eq),
+            ("PartialOrd", derivable.ord),
+            ("Ord", derivable.ord),
+        ]
+        .iter()
+        .filter(|(derive, derivable)| {
+            *derivable
+                && !fixed.contains(derive)
+                && !global_and_local.clone().any(|other| other.eq(*derive))
+        })
+        .map(|(derive, _)| *derive)
+        .collect::<Vec<_>>();
+        automatic.into_iter().chain(global_and_local)
+    }
+
+    /// The traits all fields or variants of the given type implement, types referring to each
+    /// other are only limited by the types of their other fields or variants
+    fn derivable(&self, name: &str, visiting: &mut Vec<String>) -> Derivable {
+        if visiting.iter().any(|other| other == name) {
+            return Derivable::ALL;
         }
-        if let Some(local_attrs) = self.local_attrs.get(name) {
-            local_attrs.iter().for_each(|attr| {
-                en_m.r#macro(&format!("#[{attr}]")); // Workaround for missing `.attr` for enums in codegen
-            });
+        let definition = self
+            .models
+            .iter()
+            .flat_map(|model| model.definitions.iter())
+            .find(|definition| definition.0 == name);
+        let Some(Definition(_, rust)) = definition else {
+            return Derivable::NONE;
+        };
+        visiting.push(name.to_string());
+        let derivable = match rust {
+            Rust::Struct {
+                fields,
+                protobuf_unknown_fields,
+                ..
+            } => fields.iter().fold(
+                if *protobuf_unknown_fields {
+                    Derivable::NOT_COPY
+                } else {
+                    Derivable::ALL
+                },
+                |derivable, field| derivable.and(self.derivable_type(field.r#type(), visiting)),
+            ),
+            Rust::Enum(_) => Derivable::ALL,
+            Rust::DataEnum(data) => data.variants().fold(Derivable::ALL, |derivable, variant| {
+                derivable.and(self.derivable_type(variant.r#type(), visiting))
+            }),
+            Rust::TupleStruct { r#type, .. } => self.derivable_type(r#type, visiting),
+        };
+        visiting.pop();
+        derivable
+    }
+
+    fn derivable_type(&self, r#type: &RustType, visiting: &mut Vec<String>) -> Derivable {
+        match r#type {
+            RustType::Bool
+            | RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_)
+            | RustType::I128(_)
+            | RustType::U128(_)
+            | RustType::Uuid
+            | RustType::Null => Derivable::ALL,
+            RustType::String(..) | RustType::VecU8(_) => Derivable::NOT_COPY,
+            RustType::BitVec(_) => Derivable {
+                copy: false,
+                eq: true,
+                ord: false,
+            },
+            RustType::Vec(inner, ..) | RustType::Box(inner) => {
+                Derivable::NOT_COPY.and(self.derivable_type(inner, visiting))
+            }
+            RustType::Option(inner) | RustType::Default(inner, _) => {
+                self.derivable_type(inner, visiting)
+            }
+            RustType::Complex(name, _) => self.derivable(name, visiting),
+        }
+    }
+}
+
+/// The traits, that can be derived for a type in addition to the fixed derives, see
+/// [`RustCodeGenerator::set_auto_derives`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+struct Derivable {
+    copy: bool,
+    eq: bool,
+    /// `PartialOrd` and `Ord`
+    ord: bool,
+}
+
+impl Derivable {
+    const ALL: Self = Self {
+        copy: true,
+        eq: true,
+        ord: true,
+    };
+    const NOT_COPY: Self = Self {
+        copy: false,
+        ..Self::ALL
+    };
+    const NONE: Self = Self {
+        copy: false,
+        eq: false,
+        ord: false,
+    };
+
+    fn and(self, other: Self) -> Self {
+        Self {
+            copy: self.copy && other.copy,
+            eq: self.eq && other.eq,
+            ord: self.ord && other.ord,
         }
-        en_m
     }
 }
 
@@ -1051,8 +2973,16 @@ pub(crate) mod tests {
             pub struct MyTuple(#[asn(integer(0..255), const(ABC(8), BERND(9)))] pub u8);
             
             impl MyTuple {
-                pub const ABC: u8 = 8;
-                pub const BERND: u8 = 9;
+                pub const ABC: Self = Self(8);
+                pub const BERND: Self = Self(9);
+
+                pub const fn name(&self) -> Option<&'static str> {
+                    match self.0 {
+                        8 => Some("ABC"),
+                        9 => Some("BERND"),
+                        _ => None,
+                    }
+                }
             }
             
         "#,
@@ -1061,14 +2991,14 @@ pub(crate) mod tests {
     }
 
     #[test]
-    pub fn test_struct_local_derive() {
+    pub fn test_validated_integer_tuple() {
         let model = Model::try_from(Tokenizer::default().parse(
-            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            r#"BasicInteger DEFINITIONS AUTOMATIC TAGS ::=
             BEGIN
 
-            MyStruct ::= SEQUENCE {
-                myField BOOLEAN
-            }
+            Level ::= INTEGER (1..10)
+
+            Extensible ::= INTEGER (1..10, ...)
 
             END
         "#,
@@ -1079,7 +3009,7 @@ pub(crate) mod tests {
         .to_rust();
 
         let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
-        generator.add_local_derive("MyStruct", "MyDerive");
+        generator.set_integers_validated(true);
         let (_file_name, file_content) = generator
             .to_string_without_generators()
             .into_iter()
@@ -1090,29 +3020,73 @@ pub(crate) mod tests {
             r#"
             use asn1rs::prelude::*;
 
-            #[asn(sequence)]
-            #[derive(Default, Debug, Clone, PartialEq, Hash, MyDerive)]
-            pub struct MyStruct {
-                #[asn(boolean)] pub my_field: bool,
+            #[asn(transparent)]
+            #[derive(Debug, Clone, PartialEq, Hash)]
+            pub struct Level(#[asn(integer(1..10))] u8);
+
+            impl Level {
+                pub const fn value_min() -> u8 {
+                    1
+                }
+
+                pub const fn value_max() -> u8 {
+                    10
+                }
             }
 
-            impl MyStruct {
+            impl Level {
+                pub const fn try_new(value: u8) -> Result<Self, u8> {
+                    if matches!(value, 1..=10) {
+                        Ok(Self(value))
+                    } else {
+                        Err(value)
+                    }
+                }
+            }
+
+            impl ::core::ops::Deref for Level {
+                type Target = u8;
+
+                fn deref(&self) -> &u8 {
+                    &self.0
+                }
+            }
+
+            impl ::core::convert::TryFrom<u8> for Level {
+                type Error = u8;
+
+                fn try_from(value: u8) -> Result<Self, Self::Error> {
+                    Self::try_new(value)
+                }
+            }
+
+            impl ::core::convert::From<Level> for u8 {
+                fn from(value: Level) -> Self {
+                    value.0
+                }
+            }
+
+            impl Default for Level {
+                fn default() -> Self {
+                    Self(1)
+                }
             }
+
+            #[asn(transparent)]
+            #[derive(Default, Debug, Clone, PartialEq, Hash)]
+            pub struct Extensible(#[asn(integer(1..10,...))] pub u64);
         "#,
             &file_content,
         );
     }
 
     #[test]
-    pub fn test_enum_local_derive() {
+    pub fn test_bit_string_tuple_named_bits() {
         let model = Model::try_from(Tokenizer::default().parse(
-            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            r#"BasicBitString DEFINITIONS AUTOMATIC TAGS ::=
             BEGIN
 
-            MyEnum ::= ENUMERATED {
-                a,
-                b
-            }
+            Flags ::= BIT STRING { flagA(0), flagB(5) }
 
             END
         "#,
@@ -1122,9 +3096,8 @@ pub(crate) mod tests {
         .unwrap()
         .to_rust();
 
-        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
-        generator.add_local_derive("MyEnum", "MyDerive");
-        let (_file_name, file_content) = generator
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .without_additional_global_derives()
             .to_string_without_generators()
             .into_iter()
             .next()
@@ -1134,26 +3107,130 @@ pub(crate) mod tests {
             r#"
             use asn1rs::prelude::*;
 
-            #[asn(enumerated)]
-            #[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, MyDerive, Default)]
-            pub enum MyEnum {
-                #[default] A,
-                B,
-            }
+            #[asn(transparent)]
+            #[derive(Default, Debug, Clone, PartialEq, Hash)]
+            pub struct Flags(#[asn(bit_string(), const(FLAG_A(0), FLAG_B(5)))] pub BitVec);
+
+            impl Flags {
+                pub const FLAG_A: u64 = 0;
+                pub const FLAG_B: u64 = 5;
+            }
+
+            impl Flags {
+                pub fn is_flag_a(&self) -> bool {
+                    self.0.is_bit_set(Self::FLAG_A)
+                }
+
+                pub fn set_flag_a(&mut self, value: bool) {
+                    if value {
+                        self.0.set_bit(Self::FLAG_A);
+                    } else {
+                        self.0.reset_bit(Self::FLAG_A);
+                    }
+                }
+
+        "#,
+            &file_content,
+        );
+    }
+
+    #[test]
+    pub fn test_struct_local_derive() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE {
+                myField BOOLEAN
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.add_local_derive("MyStruct", "MyDerive");
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+
+            #[asn(sequence)]
+            #[derive(Default, Debug, Clone, PartialEq, Hash, MyDerive)]
+            pub struct MyStruct {
+                #[asn(boolean)] pub my_field: bool,
+            }
+
+            impl MyStruct {
+            }
+        "#,
+            &file_content,
+        );
+    }
+
+    #[test]
+    pub fn test_enum_with_numbers() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyEnum ::= ENUMERATED {
+                a(4),
+                b(1),
+                c
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .without_additional_global_derives()
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+
+            #[asn(enumerated)]
+            #[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, Default)]
+            pub enum MyEnum {
+                #[default] #[asn(4)] A,
+                #[asn(1)] B,
+                #[asn(0)] C,
+            }
 
             impl MyEnum {
                 pub fn variant(index: usize) -> Option<Self> {
                     match index {
                         0 => Some(MyEnum::A),
                         1 => Some(MyEnum::B),
+                        2 => Some(MyEnum::C),
                         _ => None,
                     }
                 }
 
-                pub const fn variants() -> [Self; 2] {
+                pub const fn variants() -> [Self; 3] {
                     [
                         MyEnum::A,
                         MyEnum::B,
+                        MyEnum::C,
                     ]
                 }
 
@@ -1161,6 +3238,24 @@ pub(crate) mod tests {
                     match self {
                         MyEnum::A => 0,
                         MyEnum::B => 1,
+                        MyEnum::C => 2,
+                    }
+                }
+
+                pub const fn value(self) -> usize {
+                    match self {
+                        MyEnum::A => 4,
+                        MyEnum::B => 1,
+                        MyEnum::C => 0,
+                    }
+                }
+
+                pub const fn from_value(value: usize) -> Option<Self> {
+                    match value {
+                        4 => Some(MyEnum::A),
+                        1 => Some(MyEnum::B),
+                        0 => Some(MyEnum::C),
+                        _ => None,
                     }
                 }
             }
@@ -1170,13 +3265,14 @@ pub(crate) mod tests {
     }
 
     #[test]
-    pub fn test_struct_local_attr() {
+    pub fn test_enum_repr() {
         let model = Model::try_from(Tokenizer::default().parse(
             r#"Test DEFINITIONS AUTOMATIC TAGS ::=
             BEGIN
 
-            MyStruct ::= SEQUENCE {
-                myField BOOLEAN
+            MyEnum ::= ENUMERATED {
+                a(300),
+                b
             }
 
             END
@@ -1188,7 +3284,7 @@ pub(crate) mod tests {
         .to_rust();
 
         let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
-        generator.add_local_attr("MyStruct", "my_attr");
+        generator.set_enums_have_repr(true);
         let (_file_name, file_content) = generator
             .to_string_without_generators()
             .into_iter()
@@ -1199,14 +3295,69 @@ pub(crate) mod tests {
             r#"
             use asn1rs::prelude::*;
 
-            #[asn(sequence)]
-            #[derive(Default, Debug, Clone, PartialEq, Hash)]
-            #[my_attr]
-            pub struct MyStruct {
-                #[asn(boolean)] pub my_field: bool,
+            #[asn(enumerated)]
+            #[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, Default)]
+            #[repr(u16)]
+            pub enum MyEnum {
+                #[default] #[asn(300)] A = 300,
+                #[asn(0)] B = 0,
             }
 
-            impl MyStruct {
+            impl MyEnum {
+                pub fn variant(index: usize) -> Option<Self> {
+                    match index {
+                        0 => Some(MyEnum::A),
+                        1 => Some(MyEnum::B),
+                        _ => None,
+                    }
+                }
+
+                pub const fn variants() -> [Self; 2] {
+                    [
+                        MyEnum::A,
+                        MyEnum::B,
+                    ]
+                }
+
+                pub fn value_index(self) -> usize {
+                    match self {
+                        MyEnum::A => 0,
+                        MyEnum::B => 1,
+                    }
+                }
+
+                pub const fn value(self) -> usize {
+                    match self {
+                        MyEnum::A => 300,
+                        MyEnum::B => 0,
+                    }
+                }
+
+                pub const fn from_value(value: usize) -> Option<Self> {
+                    match value {
+                        300 => Some(MyEnum::A),
+                        0 => Some(MyEnum::B),
+                        _ => None,
+                    }
+                }
+            }
+
+            impl ::core::convert::TryFrom<u64> for MyEnum {
+                type Error = u64;
+
+                fn try_from(value: u64) -> Result<Self, u64> {
+                    match value {
+                        300 => Ok(MyEnum::A),
+                        0 => Ok(MyEnum::B),
+                        _ => Err(value),
+                    }
+                }
+            }
+
+            impl ::core::convert::From<MyEnum> for u64 {
+                fn from(value: MyEnum) -> Self {
+                    value as u64
+                }
             }
         "#,
             &file_content,
@@ -1214,14 +3365,125 @@ pub(crate) mod tests {
     }
 
     #[test]
-    pub fn test_enum_local_attr() {
+    pub fn test_non_exhaustive_enums() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Open ::= ENUMERATED { a, b, ... }
+            Closed ::= ENUMERATED { a, b }
+            OpenChoice ::= CHOICE { a BOOLEAN, ..., b INTEGER }
+            ClosedChoice ::= CHOICE { a BOOLEAN, b INTEGER }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.set_extensible_enums_non_exhaustive(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("#[non_exhaustive]\npub enum Open {"));
+        assert!(file_content.contains("Default)]\npub enum Closed {"));
+        assert!(file_content.contains("#[non_exhaustive]\npub enum OpenChoice {"));
+        assert!(file_content.contains("Hash)]\npub enum ClosedChoice {"));
+    }
+
+    #[test]
+    pub fn test_optimize_code_size() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Inner ::= SEQUENCE { a INTEGER (0..255) }
+            Kind ::= ENUMERATED { x, y }
+            Either ::= CHOICE { inner Inner, kind Kind }
+            Wrapped ::= Inner
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.set_optimize_code_size(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("#[asn(sequence, no_inline)]\n"));
+        assert!(file_content.contains("#[asn(enumerated, no_inline)]\n"));
+        assert!(file_content.contains("#[asn(choice, no_inline)]\n"));
+        assert!(file_content.contains("#[asn(transparent, no_inline)]\n"));
+    }
+
+    #[test]
+    pub fn test_auto_derives() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Small ::= SEQUENCE { a INTEGER (0..255), b BOOLEAN OPTIONAL, c NULL }
+            Named ::= SEQUENCE { name UTF8String, small Small }
+            Bits ::= SEQUENCE { bits BIT STRING }
+            Kind ::= ENUMERATED { x, y }
+            Either ::= CHOICE { small Small, kind Kind }
+            Tree ::= SEQUENCE { children SEQUENCE OF Tree }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.set_auto_derives(true);
+        generator.add_local_derive("Named", "Eq");
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains(
+            "#[derive(Default, Debug, Clone, PartialEq, Hash, Copy, Eq, PartialOrd, Ord)]\npub struct Small {"
+        ));
+        assert!(file_content.contains(
+            "#[derive(Default, Debug, Clone, PartialEq, Hash, PartialOrd, Ord, Eq)]\npub struct Named {"
+        ));
+        assert!(file_content
+            .contains("#[derive(Default, Debug, Clone, PartialEq, Hash, Eq)]\npub struct Bits {"));
+        assert!(file_content.contains(
+            "#[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, Ord, Default)]\npub enum Kind {"
+        ));
+        assert!(file_content.contains(
+            "#[derive(Debug, Clone, PartialEq, Hash, Copy, Eq, PartialOrd, Ord)]\npub enum Either {"
+        ));
+        assert!(file_content.contains(
+            "#[derive(Default, Debug, Clone, PartialEq, Hash, Eq, PartialOrd, Ord)]\npub struct Tree {"
+        ));
+    }
+
+    #[test]
+    pub fn test_enum_local_derive() {
         let model = Model::try_from(Tokenizer::default().parse(
             r#"Test DEFINITIONS AUTOMATIC TAGS ::=
             BEGIN
+
             MyEnum ::= ENUMERATED {
                 a,
                 b
             }
+
             END
         "#,
         ))
@@ -1229,8 +3491,9 @@ pub(crate) mod tests {
         .try_resolve()
         .unwrap()
         .to_rust();
+
         let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
-        generator.add_local_attr("MyEnum", "my_attr");
+        generator.add_local_derive("MyEnum", "MyDerive");
         let (_file_name, file_content) = generator
             .to_string_without_generators()
             .into_iter()
@@ -1240,13 +3503,14 @@ pub(crate) mod tests {
         assert_starts_with_lines(
             r#"
             use asn1rs::prelude::*;
+
             #[asn(enumerated)]
-            #[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, Default)]
-            #[my_attr]
+            #[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, MyDerive, Default)]
             pub enum MyEnum {
                 #[default] A,
                 B,
             }
+
             impl MyEnum {
                 pub fn variant(index: usize) -> Option<Self> {
                     match index {
@@ -1255,12 +3519,14 @@ pub(crate) mod tests {
                         _ => None,
                     }
                 }
+
                 pub const fn variants() -> [Self; 2] {
                     [
                         MyEnum::A,
                         MyEnum::B,
                     ]
                 }
+
                 pub fn value_index(self) -> usize {
                     match self {
                         MyEnum::A => 0,
@@ -1272,4 +3538,847 @@ pub(crate) mod tests {
             &file_content,
         );
     }
+
+    #[test]
+    pub fn test_struct_local_attr() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+
+            MyStruct ::= SEQUENCE {
+                myField BOOLEAN
+            }
+
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.add_local_attr("MyStruct", "my_attr");
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+
+            #[asn(sequence)]
+            #[derive(Default, Debug, Clone, PartialEq, Hash)]
+            #[my_attr]
+            pub struct MyStruct {
+                #[asn(boolean)] pub my_field: bool,
+            }
+
+            impl MyStruct {
+            }
+        "#,
+            &file_content,
+        );
+    }
+
+    #[test]
+    pub fn test_member_attrs() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            MyStruct ::= SEQUENCE {
+                myField BOOLEAN,
+                debugInfo UTF8String
+            }
+            MyChoice ::= CHOICE {
+                a BOOLEAN,
+                b-value BOOLEAN
+            }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.set_attributes(
+            Attributes::parse(
+                r#"
+                MyStruct = serde(rename_all = "camelCase")
+                MyStruct.debugInfo = #[cfg(feature = "debug")]
+                MyChoice.b-value = serde(rename = "b")
+                "#,
+            )
+            .unwrap(),
+        );
+        generator.add_member_attr("MyStruct", "debug_info", "serde(skip)");
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains(
+            "#[derive(Default, Debug, Clone, PartialEq, Hash)]\n#[serde(rename_all = \"camelCase\")]\npub struct MyStruct {"
+        ));
+        assert!(file_content.contains("#[asn(boolean)] pub my_field: bool,"));
+        assert!(file_content.contains(
+            "#[asn(utf8string)] #[cfg(feature = \"debug\")] #[serde(skip)] pub debug_info: String,"
+        ));
+        assert!(file_content.contains("#[asn(boolean)] A(bool),"));
+        assert!(file_content.contains("#[asn(boolean)] #[serde(rename = \"b\")] BValue(bool),"));
+    }
+
+    #[test]
+    pub fn test_deterministic_output() {
+        let generate = || {
+            let model = Model::try_from(Tokenizer::default().parse_with_comments(
+                r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+                BEGIN
+                -- the first
+                First ::= SEQUENCE { a BOOLEAN, b INTEGER, c UTF8String, d BOOLEAN }
+                Second ::= CHOICE { e BOOLEAN, f INTEGER, g UTF8String } -- the second
+                Third ::= ENUMERATED { h, i, j, k, l }
+                Fourth ::= SEQUENCE OF First
+                END
+            "#,
+            ))
+            .unwrap()
+            .try_resolve()
+            .unwrap()
+            .to_rust();
+            let mut generator = RustCodeGenerator::from(model);
+            generator.set_attributes(
+                Attributes::parse(
+                    "First = doc(hidden)\nFirst.c = doc(hidden)\nSecond.f = doc(hidden)\nThird.k = doc(hidden)",
+                )
+                .unwrap(),
+            );
+            for (index, member) in ["a", "b", "d"].iter().enumerate() {
+                generator.add_member_attr("First", *member, format!("doc(alias = \"x{index}\")"));
+            }
+            generator.to_string_without_generators()
+        };
+
+        let expected = generate();
+        for _ in 0..10 {
+            assert_eq!(expected, generate());
+        }
+    }
+
+    #[test]
+    pub fn test_enum_local_attr() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            MyEnum ::= ENUMERATED {
+                a,
+                b
+            }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.add_local_attr("MyEnum", "my_attr");
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_starts_with_lines(
+            r#"
+            use asn1rs::prelude::*;
+            #[asn(enumerated)]
+            #[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, Default)]
+            #[my_attr]
+            pub enum MyEnum {
+                #[default] A,
+                B,
+            }
+            impl MyEnum {
+                pub fn variant(index: usize) -> Option<Self> {
+                    match index {
+                        0 => Some(MyEnum::A),
+                        1 => Some(MyEnum::B),
+                        _ => None,
+                    }
+                }
+                pub const fn variants() -> [Self; 2] {
+                    [
+                        MyEnum::A,
+                        MyEnum::B,
+                    ]
+                }
+                pub fn value_index(self) -> usize {
+                    match self {
+                        MyEnum::A => 0,
+                        MyEnum::B => 1,
+                    }
+                }
+            }
+        "#,
+            &file_content,
+        );
+    }
+
+    #[test]
+    pub fn test_struct_extension_additions() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            MyStruct ::= SEQUENCE {
+                abc UTF8String OPTIONAL,
+                ...,
+                def INTEGER (0..255),
+                ghi BOOLEAN DEFAULT TRUE
+            }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content
+            .contains(r#"pub const EXTENSION_ADDITIONS: [&'static str; 2] = ["def", "ghi"];"#));
+        assert!(file_content.contains("self.def.is_some() || self.ghi != true"));
+        assert!(!file_content.contains("self.abc.is_some()"));
+    }
+
+    #[test]
+    pub fn test_struct_builder() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Color ::= ENUMERATED { red, green }
+            MyStruct ::= SEQUENCE {
+                abc UTF8String OPTIONAL,
+                def INTEGER (0..255),
+                ghi Color DEFAULT green,
+                jkl UTF8String DEFAULT "none"
+            }
+            Empty ::= SEQUENCE { }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_builders(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("pub struct MyStructBuilder {"));
+        assert!(file_content.contains("ghi: Option<Color>,"));
+        assert!(file_content.contains("pub fn def(self, value: u8) -> Self {"));
+        assert!(file_content.contains("pub fn build(self) -> Result<MyStruct, &'static str> {"));
+        assert!(file_content.contains("abc: self.abc,"));
+        assert!(file_content.contains(r#"def: self.def.ok_or("def")?,"#));
+        assert!(file_content.contains("ghi: self.ghi.unwrap_or(Color::Green),"));
+        assert!(file_content.contains(r#"jkl: self.jkl.unwrap_or_else(|| "none".to_string()),"#));
+        assert!(!file_content.contains("EmptyBuilder"));
+    }
+
+    #[test]
+    pub fn test_struct_view() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            MyStruct ::= SEQUENCE {
+                abc UTF8String OPTIONAL,
+                def-ghi INTEGER (0..255)
+            }
+            MySet ::= SET { abc UTF8String }
+            Empty ::= SEQUENCE { }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_views(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("pub struct MyStructView {"));
+        assert!(file_content.contains("pub abc: Option<String>,"));
+        assert!(file_content.contains("pub def_ghi: Option<u8>,"));
+        assert!(file_content.contains(
+            "pub fn read_view<R: ::asn1rs::descriptor::Reader>(reader: &mut R, fields: &[&str]) -> Result<MyStructView, R::Error> {"
+        ));
+        assert!(
+            file_content.contains("let value = AsnDefMyStructFieldDefGhi::read_value(reader)?;")
+        );
+        assert!(file_content.contains(r#"if fields.contains(&"defGhi") {"#));
+        assert!(file_content.contains("view.abc = value;"));
+        assert!(file_content.contains("view.def_ghi = Some(value);"));
+        assert!(!file_content.contains("MySetView"));
+        assert!(!file_content.contains("EmptyView"));
+    }
+
+    #[test]
+    pub fn test_data_enum_and_tuple_struct_accessors() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Expr ::= CHOICE {
+                literal INTEGER (0..255),
+                negated Expr
+            }
+            Single ::= CHOICE { only BOOLEAN }
+            Name ::= UTF8String
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_fields_have_getter_and_setter(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("matches!(self, Expr::Literal(_))"));
+        assert!(file_content.contains("pub fn as_negated(&self) -> Option<&Expr> {"));
+        assert!(file_content.contains("Expr::Negated(value) => Some(value.as_ref()),"));
+        assert!(file_content.contains("pub fn into_negated(self) -> Result<Box<Expr>, Self> {"));
+        assert!(file_content.contains("other => Err(other),"));
+        // only the alternatives of Expr have a fallback arm
+        assert_eq!(2, file_content.matches("_ => None,").count());
+        assert!(file_content.contains("pub fn value(&self) -> &String {"));
+        assert!(file_content.contains("pub fn set_value(&mut self, value: String) {"));
+    }
+
+    #[test]
+    pub fn test_data_enum_variant_kind() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Message ::= CHOICE { ping NULL, data OCTET STRING, ..., close BOOLEAN }
+            Empty ::= CHOICE { }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content
+            .contains("pub enum MessageVariantKind {\n    Ping,\n    Data,\n    Close,\n}"));
+        assert!(file_content.contains("pub fn variant_kind(&self) -> MessageVariantKind {"));
+        assert!(file_content.contains("Message::Close(_) => MessageVariantKind::Close,"));
+        assert!(file_content.contains("pub fn peek_variant<B: ::asn1rs::protocol::per::unaligned::ScopedBitRead>(reader: &mut ::asn1rs::rw::UperReader<B>) -> Result<MessageVariantKind, ::asn1rs::protocol::per::Error> {"));
+        assert!(file_content.contains("2 => Ok(MessageVariantKind::Close),"));
+        assert!(file_content.contains(
+            "index => Err(::asn1rs::protocol::per::ErrorKind::InvalidChoiceIndex(index, 3).into()),"
+        ));
+        assert!(!file_content.contains("EmptyVariantKind"));
+    }
+
+    #[test]
+    pub fn test_enum_display_and_from_str() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Color ::= ENUMERATED { dark-red, green }
+            Shape ::= CHOICE { circle-radius INTEGER (0..255), name UTF8String }
+            Empty ::= CHOICE { }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_enums_have_display_and_from_str(true);
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("impl ::core::fmt::Display for Color {"));
+        assert!(file_content.contains(r#"Color::DarkRed => "dark-red","#));
+        assert!(file_content.contains(r#""dark-red" => Ok(Color::DarkRed),"#));
+        assert!(file_content.contains(r#"Shape::CircleRadius(_) => "circle-radius","#));
+        assert!(file_content
+            .contains(r#""circle-radius" => Ok(Shape::CircleRadius(Default::default())),"#));
+        assert!(!file_content.contains("impl ::core::fmt::Display for Empty {"));
+    }
+
+    #[test]
+    pub fn test_choice_metadata_consts() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            MyChoice ::= CHOICE {
+                abc UTF8String,
+                def INTEGER (0..255),
+                ghi [APPLICATION 7] NULL
+            }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content
+            .contains(r#"pub const VARIANT_NAMES: [&'static str; 3] = ["Abc", "Def", "Ghi"];"#));
+        assert!(file_content
+            .contains(r#"pub const VARIANT_TYPES: [&'static str; 3] = ["String", "u8", "Null"];"#));
+        assert!(file_content.contains(
+            "pub const VARIANT_TAGS: [Option<::asn1rs::model::asn::Tag>; 3] = [\
+            Some(::asn1rs::model::asn::Tag::Universal(12)), \
+            Some(::asn1rs::model::asn::Tag::Universal(2)), \
+            Some(::asn1rs::model::asn::Tag::Application(7))];"
+        ));
+    }
+
+    #[test]
+    pub fn test_keyword_field_names() {
+        assert_eq!("r#type", RustCodeGenerator::rust_field_name("type", true));
+        assert_eq!("r#async", RustCodeGenerator::rust_field_name("async", true));
+        assert_eq!("self_", RustCodeGenerator::rust_field_name("self", true));
+        assert_eq!("type", RustCodeGenerator::rust_field_name("type", false));
+        assert_eq!(
+            "type_id",
+            RustCodeGenerator::rust_field_name("type-id", true)
+        );
+    }
+
+    #[test]
+    pub fn test_renamed_aliases() {
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Message ::= SEQUENCE {
+                message-id INTEGER (0..255),
+                status Status,
+                payload Payload
+            }
+            Status ::= ENUMERATED { idle, unreachable }
+            Payload ::= CHOICE { raw OCTET STRING, text UTF8String }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model).without_additional_global_derives();
+        generator.add_global_derive("serde::Deserialize");
+        generator.set_renames(
+            Renames::parse(
+                "Message.msgId = message-id\n\
+                Message.unknown = missing\n\
+                Status.offline = unreachable\n\
+                Payload.binary = raw",
+            )
+            .unwrap(),
+        );
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content
+            .contains(r#"#[asn(integer(0..255))] #[serde(alias = "msg_id")] pub message_id: u8,"#));
+        assert!(file_content.contains(
+            r#"#[deprecated(note = "renamed to message_id")]
+    pub fn msg_id(&self) -> &u8 {
+        &self.message_id
+    }"#
+        ));
+        assert!(file_content.contains("pub fn set_msg_id(&mut self, value: u8) {"));
+        assert!(!file_content.contains("missing"));
+        assert!(file_content.contains(r#"#[serde(alias = "Offline")] Unreachable,"#));
+        assert!(file_content.contains(
+            r#"    #[deprecated(note = "renamed to Unreachable")]
+    #[allow(non_upper_case_globals)]
+    pub const Offline: Self = Self::Unreachable;"#
+        ));
+        assert!(file_content
+            .contains(r#"#[asn(octet_string)] #[serde(alias = "Binary")] Raw(Vec<u8>),"#));
+        assert!(file_content.contains(
+            r#"#[allow(non_snake_case)]
+    #[deprecated(note = "renamed to Raw")]
+    pub fn Binary(value: Vec<u8>) -> Self {
+        Self::Raw(value)
+    }"#
+        ));
+
+        // without a Deserialize derive, there are no serde attributes
+        generator.global_derives.clear();
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(!file_content.contains("serde"));
+        assert!(file_content.contains("pub const Offline: Self = Self::Unreachable;"));
+    }
+
+    #[test]
+    pub fn test_protobuf_unknown_fields() {
+        let mut model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Message ::= SEQUENCE {
+                id INTEGER (0..255)
+            }
+            Payload ::= CHOICE { raw OCTET STRING, text UTF8String }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        model
+            .definitions
+            .iter_mut()
+            .for_each(|Definition(_, rust)| rust.set_protobuf_unknown_fields(true));
+
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("#[asn(sequence, protobuf_unknown_fields)]"));
+        assert!(file_content
+            .contains("pub unknown_fields: ::asn1rs::protocol::protobuf::UnknownFields,"));
+        assert!(file_content.contains("#[asn(choice)]"));
+    }
+
+    #[test]
+    pub fn test_codecs_without_protobuf() {
+        let mut model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Ping ::= SEQUENCE { id INTEGER (0..255), hash INTEGER (0..4294967295) }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        model
+            .definitions
+            .iter_mut()
+            .for_each(|Definition(_, rust)| {
+                rust.set_protobuf_numbers(&[2, 1]);
+                rust.set_protobuf_fixed(&["hash"]);
+                rust.set_protobuf_unknown_fields(true);
+            });
+
+        let mut generator = RustCodeGenerator::from(model);
+        assert!(generator.generates_codec(Codec::Protobuf));
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(file_content.contains(
+            "#[asn(sequence, protobuf_numbers(2, 1), protobuf_fixed(hash), protobuf_unknown_fields)]"
+        ));
+
+        generator.set_codecs(&[]);
+        assert!(!generator.generates_codec(Codec::Protobuf));
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+        assert!(file_content.contains("#[asn(sequence)]"));
+        assert!(!file_content.contains("protobuf"));
+        assert!(!file_content.contains("unknown_fields"));
+    }
+
+    #[test]
+    pub fn test_mod_file() {
+        let models = [
+            "ModA { iso(1) member-body(2) de(276) a(1) } DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Shared ::= INTEGER (0..255)
+            OnlyA ::= SEQUENCE { s Shared }
+            END",
+            "ModB { iso member-body(2) 276 2 } DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            OnlyB ::= BOOLEAN
+            END",
+            "ModC DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Shared ::= BOOLEAN
+            END",
+        ]
+        .iter()
+        .map(|asn| {
+            Model::try_from(Tokenizer::default().parse(asn))
+                .unwrap()
+                .try_resolve()
+                .unwrap()
+                .to_rust()
+        })
+        .collect::<Vec<_>>();
+        let models = models.iter().rev().collect::<Vec<_>>();
+
+        assert_eq!(
+            "pub mod mod_a;\n\
+            pub mod mod_b;\n\
+            pub mod mod_c;\n\
+            \n\
+            pub use self::mod_a::OnlyA;\n\
+            pub use self::mod_b::OnlyB;\n",
+            RustCodeGenerator::mod_file(&models, false)
+        );
+        assert!(RustCodeGenerator::mod_file(&models, true).ends_with(
+            "pub use self::mod_b::OnlyB;\n\
+            \n\
+            pub mod iso {\n\
+            \x20   pub mod member_body {\n\
+            \x20       pub mod de {\n\
+            \x20           pub mod a {\n\
+            \x20               pub use super::super::super::super::mod_a;\n\
+            \x20           }\n\
+            \x20           pub mod oid_2 {\n\
+            \x20               pub use super::super::super::super::mod_b;\n\
+            \x20           }\n\
+            \x20       }\n\
+            \x20   }\n\
+            }\n"
+        ));
+    }
+
+    #[test]
+    pub fn test_uuid() {
+        let mut model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Session ::= SEQUENCE {
+                id OCTET STRING (SIZE(16)),
+                parent OCTET STRING (SIZE(16)) OPTIONAL,
+                token OCTET STRING (SIZE(8))
+            }
+            Key ::= CHOICE { id OCTET STRING (SIZE(16)), name UTF8String }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        crate::rust::use_uuid_for_16_byte_octet_strings(&mut model);
+
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("#[asn(sequence, uuid(id, parent))]"));
+        assert!(file_content.contains("pub id: Uuid,"));
+        assert!(file_content.contains("pub parent: Option<Uuid>,"));
+        assert!(file_content.contains("pub token: Vec<u8>,"));
+        assert!(file_content.contains("#[asn(choice, uuid(Id))]"));
+        assert!(file_content.contains("Id(Uuid),"));
+    }
+
+    #[test]
+    pub fn test_doc_comments() {
+        let model = Model::try_from(Tokenizer::default().parse_with_comments(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            -- A person, as registered
+            -- at the front desk
+            Person ::= SEQUENCE {
+                -- The full name
+                name UTF8String,
+                age INTEGER (0..150) OPTIONAL -- in years
+            }
+            ------------------------------------
+            Value ::= CHOICE { -- a number or a text
+                number INTEGER, -- only positive
+                text UTF8String
+            }
+            Color ::= ENUMERATED { red, green } -- no docs for the variants
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let (_file_name, file_content) = RustCodeGenerator::from(model)
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content
+            .contains("/// A person, as registered\n/// at the front desk\n#[asn(sequence)]"));
+        assert!(file_content.contains("/// The full name\n    #[asn(utf8string)] pub name"));
+        assert!(file_content.contains("/// in years\n    #[asn(optional(integer(0..150)))]"));
+        assert!(file_content.contains("/// a number or a text\n#[asn(choice)]"));
+        assert!(file_content.contains("/// only positive\n    #[asn(integer(min..max))] Number"));
+        assert!(file_content.contains("/// no docs for the variants\n#[asn(enumerated)]"));
+        assert_eq!(7, file_content.matches("/// ").count());
+    }
+
+    #[test]
+    #[cfg(feature = "protobuf")]
+    pub fn test_protobuf_services() {
+        use crate::protobuf::services::Services;
+
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Ping ::= SEQUENCE { id INTEGER (0..255) }
+            Pong ::= SEQUENCE { id INTEGER (0..255) }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_protobuf_services(
+            Services::parse("Echo.Ping = Ping -> Pong\nOther.Pong = Pong -> Pong").unwrap(),
+        );
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains("pub trait Echo {"));
+        assert!(file_content.contains(
+            "    fn ping(\n        &self,\n        request: Ping,\n    ) -> \
+            impl ::core::future::Future<Output = Result<Pong, Self::Error>> + Send;"
+        ));
+        assert!(file_content.contains("impl<C: ::asn1rs::grpc::Channel> EchoClient<C> {"));
+        assert!(file_content.contains(
+            "::asn1rs::grpc::call(&mut self.channel, \"/test.Echo/Ping\", request).await"
+        ));
+        assert!(file_content.contains("impl<S: Echo> EchoServer<S> {"));
+        assert!(file_content.contains("pub trait Other {"));
+    }
+
+    #[test]
+    #[cfg(feature = "protobuf")]
+    pub fn test_protobuf_annotations_feature_gated() {
+        let mut model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Ping ::= SEQUENCE { id INTEGER (0..255), hash INTEGER (0..4294967295) }
+            Pong ::= SEQUENCE { id INTEGER (0..255) }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+        model.definitions[0].1.set_protobuf_numbers(&[2, 1]);
+        model.definitions[0].1.set_protobuf_fixed(&["hash"]);
+
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_protobuf_feature(Some("proto".to_string()));
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert!(file_content.contains(
+            "#[cfg_attr(feature = \"proto\", asn(sequence, protobuf_numbers(2, 1), protobuf_fixed(hash)))]\n\
+             #[cfg_attr(not(feature = \"proto\"), asn(sequence))]\n\n\
+             #[derive(Default, Debug, Clone, PartialEq, Hash)]\n\
+             pub struct Ping {"
+        ));
+        assert!(file_content.contains(
+            "#[asn(sequence)]\n\n#[derive(Default, Debug, Clone, PartialEq, Hash)]\npub struct Pong {"
+        ));
+    }
+
+    #[test]
+    #[cfg(feature = "protobuf")]
+    pub fn test_protobuf_services_feature_gated() {
+        use crate::protobuf::services::Services;
+
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"Test DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Ping ::= SEQUENCE { id INTEGER (0..255) }
+            END
+        "#,
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let mut generator = RustCodeGenerator::from(model);
+        generator.set_protobuf_services(Services::parse("Echo.Ping = Ping -> Ping").unwrap());
+        generator.set_protobuf_feature(Some("grpc".to_string()));
+        let (_file_name, file_content) = generator
+            .to_string_without_generators()
+            .into_iter()
+            .next()
+            .unwrap();
+
+        assert_eq!(
+            5,
+            file_content.matches("#[cfg(feature = \"grpc\")]\n").count()
+        );
+        assert!(file_content.contains("#[cfg(feature = \"grpc\")]\npub trait Echo {"));
+        assert!(file_content.contains("#[cfg(feature = \"grpc\")]\npub struct EchoClient<C> {"));
+        assert!(file_content.contains("#[cfg(feature = \"grpc\")]\nimpl<S: Echo> EchoServer<S> {"));
+    }
 }
@@ -4,12 +4,16 @@ extern crate strum_macros;
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
 
+#[cfg(feature = "arbitrary")]
+mod arbitrary;
+
 pub mod asn;
 pub mod generate;
 pub mod parse;
 pub mod proc_macro;
 pub mod resolve;
 pub mod rust;
+pub mod validate;
 
 mod model;
 
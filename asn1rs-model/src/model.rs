@@ -1,4 +1,5 @@
 use crate::asn::ObjectIdentifier;
+use std::collections::HashMap;
 use std::fmt::Debug;
 
 #[derive(Debug, Clone)]
@@ -8,6 +9,7 @@ pub struct Model<T: Target> {
     pub imports: Vec<Import>,
     pub definitions: Vec<Definition<T::DefinitionType>>,
     pub value_references: Vec<ValueReference<T::ValueReferenceType>>,
+    pub docs: Docs,
 }
 
 pub trait Target {
@@ -23,10 +25,32 @@ impl<T: Target> Default for Model<T> {
             imports: Default::default(),
             definitions: Default::default(),
             value_references: Vec::default(),
+            docs: Docs::default(),
         }
     }
 }
 
+/// The comments of the definitions and of their fields and variants, by the name of the
+/// definition and - for a field or variant - the name of the member
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Docs(HashMap<(String, Option<String>), String>);
+
+impl Docs {
+    pub fn insert<D: Into<String>>(&mut self, definition: D, member: Option<String>, doc: String) {
+        self.0.insert((definition.into(), member), doc);
+    }
+
+    pub fn get(&self, definition: &str, member: Option<&str>) -> Option<&str> {
+        self.0
+            .get(&(definition.to_string(), member.map(str::to_string)))
+            .map(String::as_str)
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+}
+
 #[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
 pub struct ValueReference<T> {
     pub name: String,
@@ -1077,6 +1101,7 @@ pub(crate) mod tests {
             fields,
             tag,
             extension_after: _,
+            protobuf_unknown_fields: _,
         } = rust.definitions[0].value()
         {
             assert_eq!("Implicit", rust.definitions[0].0.as_str());
@@ -1239,12 +1264,12 @@ pub(crate) mod tests {
             &[
                 ValueReference {
                     name: "utf8".to_string(),
-                    role: Type::String(Size::Any, Charset::Utf8).untagged(),
+                    role: Type::String(Size::Any, Charset::Utf8, None).untagged(),
                     value: LiteralValue::String("häw äre yöu .. .. doing".to_string())
                 },
                 ValueReference {
                     name: "ia5".to_string(),
-                    role: Type::String(Size::Any, Charset::Ia5).untagged(),
+                    role: Type::String(Size::Any, Charset::Ia5, None).untagged(),
                     value: LiteralValue::String("how are you".to_string())
                 }
             ],
@@ -1374,4 +1399,152 @@ pub(crate) mod tests {
             &model.definitions[..]
         );
     }
+
+    #[test]
+    pub fn test_permitted_alphabet() {
+        use crate::asn::PermittedAlphabet;
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Upper ::= IA5String (FROM ("A".."Z"))
+                Id ::= IA5String (SIZE(1..4) ^ FROM ("0".."7" | "-_"))
+                Serial ::= IA5String (SIZE(2)) (FROM (" AB"))
+                Restricted ::= NumericString (FROM ("0".."9" | "X"))
+                Extensible ::= IA5String (FROM ("A".."Z", ...))
+
+                END"#,
+        ))
+        .expect("Failed to load model")
+        .try_resolve()
+        .expect("Failed to resolve");
+        assert_eq!(
+            vec![
+                Type::String(
+                    Size::Any,
+                    Charset::Ia5,
+                    Some(PermittedAlphabet::new('A'..='Z'))
+                ),
+                Type::String(
+                    Size::Range(1, 4, false),
+                    Charset::Ia5,
+                    Some(PermittedAlphabet::new("-01234567_".chars()))
+                ),
+                Type::String(
+                    Size::Fix(2, false),
+                    Charset::Ia5,
+                    Some(PermittedAlphabet::new(" AB".chars()))
+                ),
+                Type::String(
+                    Size::Any,
+                    Charset::Numeric,
+                    Some(PermittedAlphabet::new('0'..='9'))
+                ),
+                Type::String(Size::Any, Charset::Ia5, None),
+            ],
+            model
+                .definitions
+                .iter()
+                .map(|Definition(_, asn)| asn.r#type.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_only_per_visible_constraints_are_applied() {
+        use crate::asn::{Integer, Range};
+        let model = Model::try_from(Tokenizer::default().parse(
+            r#"SomeName DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+
+                Union ::= INTEGER (1..10 | 20)
+                Serial ::= INTEGER (0..100) (5..MAX)
+                Additions ::= INTEGER (1..10, ..., 20..30)
+                Single ::= INTEGER (5)
+                Table ::= INTEGER ({Objects}{@id})
+                UserDefined ::= INTEGER (1..4 ^ CONSTRAINED BY { MyCheck })
+                UnionNotVisible ::= INTEGER (1..10 | CONSTRAINED BY {})
+                LastNotExtensible ::= INTEGER (1..10, ...) (1..5)
+                Contents ::= OCTET STRING (CONTAINING Union ENCODED BY der)
+                SizeAndContents ::= OCTET STRING (SIZE(1..8) ^ CONTAINING Union)
+                Pattern ::= UTF8String (PATTERN "[a-z]+")
+                Inner ::= SEQUENCE (SIZE(2)) (WITH COMPONENT (1..7)) OF INTEGER
+
+                END"#,
+        ))
+        .expect("Failed to load model")
+        .try_resolve()
+        .expect("Failed to resolve");
+        let integer =
+            |min, max, extensible| Type::Integer(Integer::with_range(Range(min, max, extensible)));
+        assert_eq!(
+            vec![
+                integer(Some(1), Some(20), false),
+                integer(Some(5), Some(100), false),
+                integer(Some(1), Some(10), true),
+                integer(Some(5), Some(5), false),
+                integer(None, None, false),
+                integer(Some(1), Some(4), false),
+                integer(None, None, false),
+                integer(Some(1), Some(5), false),
+                Type::OctetString(Size::Any),
+                Type::OctetString(Size::Range(1, 8, false)),
+                Type::String(Size::Any, Charset::Utf8, None),
+                Type::SequenceOf(
+                    Box::new(Type::unconstrained_integer().into()),
+                    Size::Fix(2, false)
+                ),
+            ],
+            model
+                .definitions
+                .iter()
+                .map(|Definition(_, asn)| asn.r#type.clone())
+                .collect::<Vec<_>>()
+        );
+    }
+
+    #[test]
+    pub fn test_lenient_parsing_skips_unsupported_definitions() {
+        let asn = r"Lenient DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+        IMPORTS Other FROM OtherModule WITH SUCCESSORS;
+
+        MY-CLASS ::= CLASS { &id INTEGER UNIQUE } WITH SYNTAX { ID &id }
+        Container { Type } ::= SEQUENCE { value Type }
+        Simple ::= SEQUENCE { small INTEGER (0..255) }
+        Dependent ::= SEQUENCE { inner Container }
+        maxItems INTEGER ::= 16
+        Indirect ::= SEQUENCE OF Dependent
+
+        END";
+
+        assert!(Model::try_from(Tokenizer::default().parse(asn)).is_err());
+
+        let (model, warnings) = Model::try_from_lenient(Tokenizer::default().parse(asn)).unwrap();
+        assert_eq!(
+            vec![Import {
+                what: vec!["Other".to_string()],
+                from: "Other".to_string(),
+                from_oid: None,
+            }],
+            model.imports
+        );
+        assert_eq!(
+            vec!["Simple"],
+            model
+                .definitions
+                .iter()
+                .map(Definition::name)
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(1, model.value_references.len());
+        assert_eq!("maxItems", model.value_references[0].name);
+        assert_eq!(
+            vec![
+                "At line 5, column 28 an unexpected token was encountered: '{'".to_string(),
+                "At line 6, column 19 expected text, but instead got: '{'".to_string(),
+                "Dependent was skipped because it refers to the skipped Container".to_string(),
+                "Indirect was skipped because it refers to the skipped Dependent".to_string(),
+            ],
+            warnings.iter().map(Error::to_string).collect::<Vec<_>>()
+        );
+    }
 }
@@ -1,4 +1,4 @@
-use crate::parse::Token;
+use crate::parse::{Location, Token};
 use backtrace::Backtrace;
 use std::error;
 use std::fmt::{Debug, Display, Formatter};
@@ -20,6 +20,8 @@ pub enum ErrorKind {
     InvalidIntText(Token),
     UnsupportedLiteral(Token),
     InvalidLiteral(Token),
+    /// The definition (first) refers to a definition (second) that was skipped in lenient mode
+    RefersToSkippedDefinition(String, String),
 }
 
 pub struct Error {
@@ -27,6 +29,30 @@ pub struct Error {
     backtrace: Backtrace,
 }
 
+impl ErrorKind {
+    /// A short code that identifies the kind of the error and does not change between versions
+    pub const fn code(&self) -> &'static str {
+        match self {
+            ErrorKind::ExpectedText(_) => "P0001",
+            ErrorKind::ExpectedTextGot(_, _) => "P0002",
+            ErrorKind::ExpectedSeparator(_) => "P0003",
+            ErrorKind::ExpectedSeparatorGot(_, _) => "P0004",
+            ErrorKind::UnexpectedToken(_) => "P0005",
+            ErrorKind::MissingModuleName => "P0006",
+            ErrorKind::UnexpectedEndOfStream => "P0007",
+            ErrorKind::InvalidRangeValue(_) => "P0008",
+            ErrorKind::InvalidNumberForEnumVariant(_) => "P0009",
+            ErrorKind::InvalidValueForConstant(_) => "P0010",
+            ErrorKind::InvalidTag(_) => "P0011",
+            ErrorKind::InvalidPositionForExtensionMarker(_) => "P0012",
+            ErrorKind::InvalidIntText(_) => "P0013",
+            ErrorKind::UnsupportedLiteral(_) => "P0014",
+            ErrorKind::InvalidLiteral(_) => "P0015",
+            ErrorKind::RefersToSkippedDefinition(_, _) => "P0016",
+        }
+    }
+}
+
 impl From<ErrorKind> for Error {
     fn from(kind: ErrorKind) -> Self {
         Error {
@@ -99,6 +125,106 @@ impl Error {
         ErrorKind::UnsupportedLiteral(token).into()
     }
 
+    pub fn refers_to_skipped_definition(name: String, skipped: String) -> Self {
+        ErrorKind::RefersToSkippedDefinition(name, skipped).into()
+    }
+
+    pub fn kind(&self) -> &ErrorKind {
+        &self.kind
+    }
+
+    pub fn code(&self) -> &'static str {
+        self.kind.code()
+    }
+
+    pub fn location(&self) -> Option<Location> {
+        self.token().map(Token::location)
+    }
+
+    /// Describes the tokens that were expected instead of the offending one, if known
+    pub fn expected(&self) -> Vec<String> {
+        match &self.kind {
+            ErrorKind::ExpectedText(_) => vec!["text".to_string()],
+            ErrorKind::ExpectedTextGot(text, _) => vec![format!("\"{}\"", text)],
+            ErrorKind::ExpectedSeparator(_) => vec!["separator".to_string()],
+            ErrorKind::ExpectedSeparatorGot(separator, _) => vec![format!("'{}'", separator)],
+            ErrorKind::MissingModuleName => vec!["module name".to_string()],
+            ErrorKind::InvalidNumberForEnumVariant(_)
+            | ErrorKind::InvalidTag(_)
+            | ErrorKind::InvalidIntText(_) => vec!["number".to_string()],
+            _ => Vec::new(),
+        }
+    }
+
+    pub fn diagnostic(&self) -> Diagnostic {
+        Diagnostic {
+            code: self.code(),
+            message: self.message(),
+            location: self.location(),
+            end: self.token().map(Token::end),
+            expected: self.expected(),
+        }
+    }
+
+    /// The description of the error without its location
+    fn message(&self) -> String {
+        match &self.kind {
+            ErrorKind::ExpectedText(token) => {
+                format!("expected text, but instead got: {}", token)
+            }
+            ErrorKind::ExpectedTextGot(text, token) => format!(
+                "expected a text like \"{}\", but instead got: {}",
+                text, token
+            ),
+            ErrorKind::ExpectedSeparator(token) => {
+                format!("expected separator, but instead got: {}", token)
+            }
+            ErrorKind::ExpectedSeparatorGot(separator, token) => format!(
+                "expected a separator like '{}', but instead got: {}",
+                separator, token
+            ),
+            ErrorKind::UnexpectedToken(token) => {
+                format!("an unexpected token was encountered: {}", token)
+            }
+            ErrorKind::MissingModuleName => {
+                "The ASN definition is missing the module name".to_string()
+            }
+            ErrorKind::UnexpectedEndOfStream => "Unexpected end of stream or file".to_string(),
+            ErrorKind::InvalidRangeValue(token) => {
+                format!("an unexpected range value was encountered: {}", token)
+            }
+            ErrorKind::InvalidNumberForEnumVariant(token) => format!(
+                "an invalid value for an enum variant was encountered: {}",
+                token
+            ),
+            ErrorKind::InvalidValueForConstant(token) => format!(
+                "an invalid value for an constant value was encountered: {}",
+                token
+            ),
+            ErrorKind::InvalidTag(token) => {
+                format!("an invalid value for a tag was encountered: {}", token)
+            }
+            ErrorKind::InvalidPositionForExtensionMarker(_) => {
+                "an extension marker is present, which this is not allowed at that position"
+                    .to_string()
+            }
+            ErrorKind::InvalidIntText(token) => {
+                format!("a number was expected but instead got: {}", token)
+            }
+            ErrorKind::UnsupportedLiteral(token) => format!(
+                "an (yet) unsupported value reference literal was discovered: {}",
+                token
+            ),
+            ErrorKind::InvalidLiteral(token) => {
+                format!("an invalid literal was discovered: {}", token)
+            }
+            ErrorKind::RefersToSkippedDefinition(name, skipped) => format!(
+                "{} was skipped because it refers to the skipped {}",
+                name, skipped
+            ),
+        }
+    }
+
     fn backtrace(&self) -> &Backtrace {
         &self.backtrace
     }
@@ -120,6 +246,7 @@ impl Error {
             ErrorKind::InvalidIntText(t) => Some(t),
             ErrorKind::UnsupportedLiteral(t) => Some(t),
             ErrorKind::InvalidLiteral(t) => Some(t),
+            ErrorKind::RefersToSkippedDefinition(_, _) => None,
         }
     }
 }
@@ -136,103 +263,76 @@ impl Debug for Error {
 
 impl Display for Error {
     fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
-        match &self.kind {
-            ErrorKind::ExpectedText(token) => write!(
-                f,
-                "At line {}, column {} expected text, but instead got: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
-            ),
-            ErrorKind::ExpectedTextGot(text, token) => write!(
-                f,
-                "At line {}, column {} expected a text like \"{}\", but instead got: {}",
-                token.location().line(),
-                token.location().column(),
-                text,
-                token,
-            ),
-            ErrorKind::ExpectedSeparator(token) => write!(
+        match self.location() {
+            Some(location) => write!(
                 f,
-                "At line {}, column {} expected separator, but instead got: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
+                "At line {}, column {} {}",
+                location.line(),
+                location.column(),
+                self.message()
             ),
-            ErrorKind::ExpectedSeparatorGot(separator, token) => write!(
-                f,
-                "At line {}, column {} expected a separator like '{}', but instead got: {}",
-                token.location().line(),
-                token.location().column(),
-                separator,
-                token,
-            ),
-            ErrorKind::UnexpectedToken(token) => write!(
-                f,
-                "At line {}, column {} an unexpected token was encountered: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
-            ),
-            ErrorKind::MissingModuleName => {
-                writeln!(f, "The ASN definition is missing the module name")
-            }
-            ErrorKind::UnexpectedEndOfStream => write!(f, "Unexpected end of stream or file"),
-            ErrorKind::InvalidRangeValue(token) => write!(
-                f,
-                "At line {}, column {} an unexpected range value was encountered: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
-            ),
-            ErrorKind::InvalidNumberForEnumVariant(token) => write!(
-                f,
-                "At line {}, column {} an invalid value for an enum variant was encountered: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
-            ),
-            ErrorKind::InvalidValueForConstant(token) => write!(
-                f,
-                "At line {}, column {} an invalid value for an constant value was encountered: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
-            ),
-            ErrorKind::InvalidTag(token) => write!(
-                f,
-                "At line {}, column {} an invalid value for a tag was encountered: {}",
-                token.location().line(),
-                token.location().column(),
-                token,
-            ),
-            ErrorKind::InvalidPositionForExtensionMarker(token) => write!(
-                f,
-                "At line {}, column {} an extension marker is present, which this is not allowed at that position",
-                token.location().line(),
-                token.location().column(),
-            ),
-            ErrorKind::InvalidIntText(token) => write!(
-                f,
-                "At line {}, column {} a number was expected but instead got: {}",
-                token.location().line(),
-                token.location().column(),
-                token
-            ),
-            ErrorKind::UnsupportedLiteral(token) => write!(
-                f,
-                "At line {}, column {} an (yet) unsupported value reference literal was discovered: {}",
-                token.location().line(),
-                token.location().column(),
-                token
-            ),
-            ErrorKind::InvalidLiteral(token) => write!(
+            None => write!(f, "{}", self.message()),
+        }
+    }
+}
+
+/// A structured description of a parse [`Error`], for example for editor integrations.
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    /// The stable code of the [`ErrorKind`], see [`ErrorKind::code`]
+    pub code: &'static str,
+    pub message: String,
+    /// The first character of the offending token, if the error refers to one
+    pub location: Option<Location>,
+    /// The location right after the last character of the offending token
+    pub end: Option<Location>,
+    pub expected: Vec<String>,
+}
+
+impl Diagnostic {
+    /// Renders the source line of the offending token with the token being underlined, similar
+    /// to the diagnostics of `rustc`:
+    ///
+    /// ```text
+    ///   |
+    /// 3 |     id  INTEGR,
+    ///   |         ^^^^^^
+    /// ```
+    pub fn snippet(&self, source: &str) -> Option<String> {
+        let location = self.location?;
+        let line = source.lines().nth(location.line().checked_sub(1)?)?;
+        let length = self
+            .end
+            .map(|end| end.column().saturating_sub(location.column()))
+            .unwrap_or_default()
+            .max(1);
+        let gutter = " ".repeat(location.line().to_string().len());
+        // keep tabs in the indentation of the marker, so that it is aligned with the token
+        let indentation = line
+            .chars()
+            .take(location.column().saturating_sub(1))
+            .map(|c| if c == '\t' { '\t' } else { ' ' })
+            .collect::<String>();
+        Some(format!(
+            "{gutter} |\n{} | {}\n{gutter} | {indentation}{}",
+            location.line(),
+            line,
+            "^".repeat(length)
+        ))
+    }
+}
+
+impl Display for Diagnostic {
+    fn fmt(&self, f: &mut Formatter) -> std::fmt::Result {
+        write!(f, "error[{}]: {}", self.code, self.message)?;
+        if let Some(location) = self.location {
+            write!(
                 f,
-                "At line {}, column {} an invalid literal was discovered: {}",
-                token.location().line(),
-                token.location().column(),
-                token
-            ),
+                " (line {}, column {})",
+                location.line(),
+                location.column()
+            )?;
         }
+        Ok(())
     }
 }
@@ -3,6 +3,7 @@ mod location;
 mod token;
 mod tokenizer;
 
+pub use error::Diagnostic;
 pub use error::Error;
 pub use error::ErrorKind;
 pub use location::Location;
@@ -257,4 +258,82 @@ mod tests {
             Token::Text(Location::default(), String::default()).into_separator_or_else(|_| ())
         );
     }
+
+    #[test]
+    pub fn test_token_end() {
+        assert_eq!(
+            Location::at(3, 9),
+            Token::Text(Location::at(3, 3), "INTEGR".to_string()).end()
+        );
+        assert_eq!(
+            Location::at(1, 2),
+            Token::Separator(Location::at(1, 1), '{').end()
+        );
+    }
+
+    #[test]
+    pub fn test_error_diagnostic() {
+        let error = Error::expected_separator('{', Token::Text(Location::at(2, 3), "id".into()));
+        assert_eq!("P0004", error.code());
+        assert_eq!(Some(Location::at(2, 3)), error.location());
+        assert_eq!(
+            "At line 2, column 3 expected a separator like '{', but instead got: \"id\"",
+            error.to_string()
+        );
+        assert_eq!(
+            Diagnostic {
+                code: "P0004",
+                message: "expected a separator like '{', but instead got: \"id\"".to_string(),
+                location: Some(Location::at(2, 3)),
+                end: Some(Location::at(2, 5)),
+                expected: vec!["'{'".to_string()],
+            },
+            error.diagnostic()
+        );
+    }
+
+    #[test]
+    pub fn test_error_diagnostic_without_location() {
+        let diagnostic = Error::unexpected_end_of_stream().diagnostic();
+        assert_eq!("P0007", diagnostic.code);
+        assert_eq!(None, diagnostic.location);
+        assert_eq!(None, diagnostic.snippet("A DEFINITIONS"));
+    }
+
+    #[test]
+    pub fn test_diagnostic_snippet() {
+        let source = "A DEFINITIONS ::= BEGIN\n\tId ::= INTEGR\nEND";
+        let error = Error::unexpected_token(Token::Text(Location::at(2, 9), "INTEGR".into()));
+        assert_eq!(
+            Some("  |\n2 | \tId ::= INTEGR\n  | \t       ^^^^^^".to_string()),
+            error.diagnostic().snippet(source)
+        );
+        let error = Error::unexpected_token(Token::Separator(Location::at(12, 1), '}'));
+        assert_eq!(None, error.diagnostic().snippet(source));
+    }
+
+    #[test]
+    pub fn test_comments_only_with_comments() {
+        let source = "A ::= INTEGER -- the a --\n-- standalone\nB--inline\n";
+        assert!(Tokenizer.parse(source).iter().all(|t| !t.is_comment()));
+
+        let result = Tokenizer.parse_with_comments(source);
+        let mut iter = result.into_iter();
+        assert!(iter.next().unwrap().eq_text("A"));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator(':'));
+        assert!(iter.next().unwrap().eq_separator('='));
+        assert!(iter.next().unwrap().eq_text("INTEGER"));
+        assert_eq!(
+            Token::Comment(Location::at(1, 15), "the a".into()),
+            iter.next().unwrap()
+        );
+        assert_eq!(
+            Token::Comment(Location::at(2, 1), "standalone".into()),
+            iter.next().unwrap()
+        );
+        assert!(iter.next().unwrap().eq_text("B"));
+        assert_eq!(Some("inline"), iter.next().unwrap().comment());
+        assert!(iter.next().is_none());
+    }
 }
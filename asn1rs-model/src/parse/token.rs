@@ -5,6 +5,10 @@ use std::fmt::{Display, Formatter};
 pub enum Token {
     Text(Location, String),
     Separator(Location, char),
+    /// The text of a `--` comment, only returned by [`Tokenizer::parse_with_comments`]
+    ///
+    /// [`Tokenizer::parse_with_comments`]: crate::parse::Tokenizer::parse_with_comments
+    Comment(Location, String),
 }
 
 impl From<char> for Token {
@@ -24,6 +28,7 @@ impl Display for Token {
         match self {
             Token::Text(_, text) => write!(f, "\"{}\"", text),
             Token::Separator(_, separator) => write!(f, "\'{}\'", separator),
+            Token::Comment(_, comment) => write!(f, "-- {}", comment),
         }
     }
 }
@@ -46,9 +51,21 @@ impl Token {
         match self {
             Token::Text(location, _) => *location,
             Token::Separator(location, _) => *location,
+            Token::Comment(location, _) => *location,
         }
     }
 
+    /// The location right after the last character of this token, tokens never span lines
+    pub fn end(&self) -> Location {
+        let location = self.location();
+        let length = match self {
+            Token::Text(_, text) => text.chars().count(),
+            Token::Separator(_, _) => 1,
+            Token::Comment(_, comment) => comment.chars().count() + 2,
+        };
+        Location::at(location.line(), location.column() + length)
+    }
+
     pub fn eq_text(&self, text: &str) -> bool {
         self.text().map(|t| t.eq(text)).unwrap_or(false)
     }
@@ -77,6 +94,17 @@ impl Token {
         }
     }
 
+    pub fn comment(&self) -> Option<&str> {
+        match self {
+            Token::Comment(_, comment) => Some(comment),
+            _ => None,
+        }
+    }
+
+    pub fn is_comment(&self) -> bool {
+        self.comment().is_some()
+    }
+
     pub fn is_text(&self) -> bool {
         self.text().is_some()
     }
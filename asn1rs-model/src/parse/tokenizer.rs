@@ -11,6 +11,17 @@ impl Tokenizer {
     /// Ignore multi-line comments defined with /*  */.
     /// Comment terminates when a matching "*/" has been found for each "/*"
     pub fn parse(&self, asn: &str) -> Vec<Token> {
+        self.parse_with_comments(asn)
+            .into_iter()
+            .filter(|token| !token.is_comment())
+            .collect()
+    }
+
+    /// Like [`Tokenizer::parse`], but single-line comments are returned as [`Token::Comment`],
+    /// from which [`Model::try_from`] documents the definitions, fields and variants
+    ///
+    /// [`Model::try_from`]: crate::model::Model::try_from
+    pub fn parse_with_comments(&self, asn: &str) -> Vec<Token> {
         let mut previous = None;
         let mut tokens = Vec::new();
         let mut nest_lvl = 0; // Nest level of the comments
@@ -52,7 +63,20 @@ impl Tokenizer {
                     && content_iterator.peek().map(|&(_, ch)| ch) == Some('-')
                 {
                     content_iterator.next(); // remove second '-'
-                    break; // ignore rest of the line
+                    if let Some(token) = previous.take() {
+                        tokens.push(token);
+                    }
+                    let comment = content_iterator.map(|(_, ch)| ch).collect::<String>();
+                    let comment = comment.trim();
+                    tokens.push(Token::Comment(
+                        Location::at(line_0 + 1, column_0 + 1),
+                        comment
+                            .strip_suffix("--")
+                            .unwrap_or(comment)
+                            .trim()
+                            .to_string(),
+                    ));
+                    break; // the comment is the rest of the line
                 }
                 match char {
                     '/' if content_iterator.peek().map(|&(_, ch)| ch) == Some('*') => {
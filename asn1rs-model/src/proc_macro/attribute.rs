@@ -3,13 +3,14 @@ use super::range::ident_or_literal_or_punct;
 use super::range::IntegerRange;
 use super::tag::AttrTag;
 use crate::asn::{Charset, Choice, ChoiceVariant, Enumerated, EnumeratedVariant};
-use crate::asn::{Range, Size, Tag, Type};
+use crate::asn::{PermittedAlphabet, Range, Size, Tag, Type};
 use crate::model::LiteralValue;
 use std::fmt::Debug;
 use std::fmt::Display;
 use std::marker::PhantomData;
 use std::ops::Deref;
 use std::str::FromStr;
+use syn::ext::IdentExt;
 use syn::parenthesized;
 use syn::parse::{Parse, ParseBuffer, ParseStream};
 use syn::token;
@@ -21,7 +22,21 @@ pub(crate) struct AsnAttribute<C: Context> {
     pub(crate) tag: Option<Tag>,
     pub(crate) consts: Vec<ConstLit>,
     pub(crate) extensible_after: Option<String>,
+    /// Extensible without any field in the extension root
+    pub(crate) extensible: bool,
     pub(crate) default_value: Option<LiteralValue>,
+    /// The protobuf field numbers of the fields or variants, in order
+    pub(crate) protobuf_numbers: Vec<u32>,
+    /// The fields or variants whose integers are encoded as protobuf `fixed32`/`fixed64`
+    pub(crate) protobuf_fixed: Vec<String>,
+    /// Whether the struct retains the unknown fields of a protobuf message in `unknown_fields`
+    pub(crate) protobuf_unknown_fields: bool,
+    /// The fields or variants whose OCTET STRING (SIZE(16)) is represented as `uuid::Uuid`
+    pub(crate) uuid: Vec<String>,
+    /// The fields or variants whose referred type is boxed
+    pub(crate) boxed: Vec<String>,
+    /// Whether the `Readable` and `Writable` impls are `#[inline(never)]`
+    pub(crate) no_inline: bool,
     _c: PhantomData<C>,
 }
 
@@ -32,7 +47,14 @@ impl<C: Context> AsnAttribute<C> {
             tag: None,
             consts: Vec::default(),
             extensible_after: None,
+            extensible: false,
             default_value: None,
+            protobuf_numbers: Vec::default(),
+            protobuf_fixed: Vec::default(),
+            protobuf_unknown_fields: false,
+            uuid: Vec::default(),
+            boxed: Vec::default(),
+            no_inline: false,
             _c: Default::default(),
         }
     }
@@ -61,7 +83,53 @@ impl<C: Context> Parse for AsnAttribute<C> {
                     parenthesized!(content in input);
                     let ident = content
                         .step(|s| s.ident().ok_or_else(|| content.error("Not a valid ident")))?;
-                    asn.extensible_after = Some(ident.to_string());
+                    asn.extensible_after = Some(ident.unraw().to_string());
+                }
+                "extensible" if C::EXTENSIBLE_AFTER => {
+                    asn.extensible = true;
+                }
+                "protobuf_numbers" if C::PROTOBUF => {
+                    let content;
+                    parenthesized!(content in input);
+                    let numbers = content
+                        .parse_terminated(syn::LitInt::parse, Token![,])?
+                        .into_iter()
+                        .map(|number| number.base10_parse::<u32>())
+                        .collect::<syn::Result<Vec<_>>>()?;
+                    asn.protobuf_numbers = numbers;
+                }
+                "protobuf_fixed" if C::PROTOBUF => {
+                    let content;
+                    parenthesized!(content in input);
+                    asn.protobuf_fixed = content
+                        .parse_terminated(syn::Ident::parse, Token![,])?
+                        .into_iter()
+                        .map(|ident| ident.unraw().to_string())
+                        .collect();
+                }
+                "protobuf_unknown_fields" if C::PROTOBUF => {
+                    asn.protobuf_unknown_fields = true;
+                }
+                "uuid" if C::UUID => {
+                    let content;
+                    parenthesized!(content in input);
+                    asn.uuid = content
+                        .parse_terminated(syn::Ident::parse, Token![,])?
+                        .into_iter()
+                        .map(|ident| ident.unraw().to_string())
+                        .collect();
+                }
+                "boxed" if C::BOXED => {
+                    let content;
+                    parenthesized!(content in input);
+                    asn.boxed = content
+                        .parse_terminated(syn::Ident::parse, Token![,])?
+                        .into_iter()
+                        .map(|ident| ident.unraw().to_string())
+                        .collect();
+                }
+                "no_inline" if C::NO_INLINE => {
+                    asn.no_inline = true;
                 }
                 "const" if C::CONSTS => {
                     let content;
@@ -117,7 +185,8 @@ fn parse_type_pre_stepped<'a>(
             let charset = &string[..len - "string".chars().count()];
             let charset = Charset::from_str(charset)
                 .map_err(|_| input.error(format!("Unexpected charset '{}'", charset)))?;
-            parse_opt_size_or_any(input).map(|size| Type::String(size, charset))
+            parse_string_constraints(input)
+                .map(|(size, alphabet)| Type::String(size, charset, alphabet))
         }
         "integer" => {
             if input.is_empty() {
@@ -263,6 +332,35 @@ fn parse_opt_size_or_any(input: ParseStream) -> syn::Result<Size> {
     }
 }
 
+fn parse_string_constraints(input: ParseStream) -> syn::Result<(Size, Option<PermittedAlphabet>)> {
+    let mut size = Size::Any;
+    let mut alphabet = None;
+    if !input.is_empty() && input.peek(token::Paren) {
+        let content;
+        parenthesized!(content in input);
+        while !content.is_empty() {
+            let ident = parse_ident(&content, "Expected size or from")?.to_lowercase();
+            if "size".eq(&ident) {
+                let size_content;
+                parenthesized!(size_content in content);
+                size = Size::parse(&size_content)?;
+            } else if "from".eq(&ident) {
+                let from_content;
+                parenthesized!(from_content in content);
+                let chars = from_content.parse::<syn::LitStr>()?.value();
+                alphabet = Some(PermittedAlphabet::new(chars.chars()));
+            } else {
+                return Err(input.error(format!(
+                    "Invalid identifier, expected none, size or from but got: {}",
+                    ident
+                )));
+            }
+            eof_or_comma(&content, "Expected eof or comma")?;
+        }
+    }
+    Ok((size, alphabet))
+}
+
 fn eof_or_comma<T: Display>(input: &ParseBuffer, msg: T) -> syn::Result<()> {
     if !input.cursor().eof() && !input.peek(syn::token::Comma) {
         Err(input.error(msg))
@@ -320,6 +418,15 @@ pub trait Context: Debug {
     const EXTENSIBLE_AFTER: bool;
     const TAGGABLE: bool;
     const CONSTS: bool;
+    /// Whether `protobuf_numbers(..)`, `protobuf_fixed(..)` and `protobuf_unknown_fields` are
+    /// allowed
+    const PROTOBUF: bool = false;
+    /// Whether `uuid(..)` is allowed
+    const UUID: bool = false;
+    /// Whether `boxed(..)` is allowed
+    const BOXED: bool = false;
+    /// Whether `no_inline` is allowed
+    const NO_INLINE: bool = false;
 }
 
 impl Context for Choice {
@@ -366,6 +473,10 @@ impl Context for DefinitionHeader {
     const EXTENSIBLE_AFTER: bool = true;
     const TAGGABLE: bool = true;
     const CONSTS: bool = false;
+    const PROTOBUF: bool = true;
+    const UUID: bool = true;
+    const BOXED: bool = true;
+    const NO_INLINE: bool = true;
 }
 
 impl Deref for DefinitionHeader {
@@ -8,6 +8,7 @@ mod tag;
 use crate::asn::{Choice, ChoiceVariant, Enumerated, EnumeratedVariant};
 use crate::asn::{ComponentTypeList, TagProperty, TagResolver, Type};
 use crate::resolve::Resolved;
+use crate::rust::UUID_SIZE;
 use attribute::AsnAttribute;
 use attribute::{Context, DefinitionHeader, Transparent};
 use constants::ConstLit;
@@ -15,6 +16,7 @@ use proc_macro2::TokenStream;
 use quote::quote;
 use std::convert::Infallible;
 use std::str::FromStr;
+use syn::ext::IdentExt;
 use syn::spanned::Spanned;
 use syn::{Attribute, Item};
 
@@ -23,6 +25,9 @@ pub use inline::asn_to_rust;
 
 pub type AsnModelType = crate::asn::Asn<Resolved>;
 
+/// The field of a struct, that retains the unknown fields of a protobuf message
+const UNKNOWN_FIELDS: &str = "unknown_fields";
+
 pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
     if cfg!(feature = "debug-proc-macro") {
         println!();
@@ -32,7 +37,7 @@ pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
         println!();
     }
 
-    let (definition, item) = match parse_asn_definition(attr, item) {
+    let (definition, attributes, mut item) = match parse_asn_definition_and_rust(attr, item) {
         Ok(v) => v,
         Err(e) => {
             println!("Errör: {}", e);
@@ -52,11 +57,15 @@ pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
         println!();
     }
 
-    let additional_impl = expand(definition);
+    let schema_impl = take_derive(&mut item, "Asn1Schema")
+        .then(|| definition.as_ref().map(expand_asn1_schema))
+        .flatten();
+    let additional_impl = expand(definition, &attributes);
 
     let result = quote! {
         #item
         #(#additional_impl)*
+        #schema_impl
     };
 
     if cfg!(feature = "debug-proc-macro") {
@@ -68,7 +77,29 @@ pub fn parse(attr: TokenStream, item: TokenStream) -> TokenStream {
     result
 }
 
-pub fn expand(definition: Option<Definition<AsnModelType>>) -> Vec<TokenStream> {
+/// The attributes of a definition, that only affect its Rust representation
+#[derive(Debug, Default)]
+pub struct RustAttributes {
+    /// The field numbers of the fields or variants, in order, unless empty
+    pub numbers: Vec<u32>,
+    /// The fields or variants whose integers are encoded as `fixed32`/`fixed64`
+    pub fixed: Vec<String>,
+    /// Whether the struct retains the unknown fields of a message in its `unknown_fields` field
+    pub unknown_fields: bool,
+    /// The fields or variants whose OCTET STRING (SIZE(16)) is represented as `uuid::Uuid`
+    pub uuid: Vec<String>,
+    /// The fields or variants whose referred type is boxed
+    pub boxed: Vec<String>,
+    /// Whether the `Readable` and `Writable` impls are `#[inline(never)]`, see
+    /// [`AsnDefWriter::stringify_with_inline`](crate::generate::walker::AsnDefWriter::stringify_with_inline)
+    pub no_inline: bool,
+}
+
+/// The Rust attributes are assigned to the fields or variants of the definition
+pub fn expand(
+    definition: Option<Definition<AsnModelType>>,
+    attributes: &RustAttributes,
+) -> Vec<TokenStream> {
     let mut additional_impl: Vec<TokenStream> = Vec::default();
     let mut model: Model<AsnModelType> = Model {
         name: "__proc_macro".to_string(),
@@ -76,6 +107,7 @@ pub fn expand(definition: Option<Definition<AsnModelType>>) -> Vec<TokenStream>
     };
 
     if let Some(definition) = definition {
+        let name = definition.0.clone();
         model.definitions.push(definition);
         use crate::generate::walker::AsnDefWriter;
 
@@ -85,18 +117,96 @@ pub fn expand(definition: Option<Definition<AsnModelType>>) -> Vec<TokenStream>
             println!("---------- parsed definition to rust end ----------");
             println!();
         }
+        let mut rust = model.to_rust_keep_names();
+        if let Some(Definition(_, rust)) = rust.definitions.iter_mut().find(|d| d.0 == name) {
+            rust.set_protobuf_numbers(&attributes.numbers);
+            rust.set_protobuf_fixed(&attributes.fixed);
+            rust.set_protobuf_unknown_fields(attributes.unknown_fields);
+            rust.set_uuid(&attributes.uuid);
+            rust.set_boxed(&attributes.boxed);
+        }
         additional_impl.push(
-            TokenStream::from_str(&AsnDefWriter::stringify(&model.to_rust_keep_names())).unwrap(),
+            TokenStream::from_str(&AsnDefWriter::stringify_with_inline(
+                &rust,
+                !attributes.no_inline,
+            ))
+            .unwrap(),
         );
     }
 
     additional_impl
 }
 
+/// Implements `Asn1Schema` with the ASN.1 type assignment of the given definition. Tags that
+/// equal the tag the type has anyway (as resolved for a `CHOICE`) are not written.
+pub fn expand_asn1_schema(definition: &Definition<AsnModelType>) -> TokenStream {
+    use crate::generate::asn1::Asn1DefGenerator;
+    let mut definition = definition.clone();
+    if definition.1.tag == TagResolver::resolve_default(&definition.1.r#type) {
+        definition.1.tag = None;
+    }
+    let name = proc_macro2::Ident::new(&definition.0, proc_macro2::Span::call_site());
+    let assignment = Asn1DefGenerator::definition_to_string(&definition);
+    quote! {
+        impl ::asn1rs::descriptor::Asn1Schema for #name {
+            const ASN1_DEFINITION: &'static str = #assignment;
+        }
+    }
+}
+
+/// Removes the derive macro with the given name from the `#[derive(..)]` attributes of the item
+/// and returns whether it was present. This allows the `#[asn(..)]` attribute to implement
+/// traits that depend on the (already removed) `#[asn(..)]` attributes of the fields.
+fn take_derive(item: &mut Item, name: &str) -> bool {
+    let attributes = match item {
+        Item::Struct(strct) => &mut strct.attrs,
+        Item::Enum(enm) => &mut enm.attrs,
+        _ => return false,
+    };
+    let mut found = false;
+    attributes.retain_mut(|attribute| {
+        if !attribute.path().is_ident("derive") {
+            return true;
+        }
+        let Ok(paths) = attribute.parse_args_with(
+            syn::punctuated::Punctuated::<syn::Path, syn::Token![,]>::parse_terminated,
+        ) else {
+            return true;
+        };
+        let len = paths.len();
+        let paths = paths
+            .into_iter()
+            .filter(|path| path.segments.last().is_none_or(|s| s.ident != name))
+            .collect::<Vec<_>>();
+        if paths.len() == len {
+            return true;
+        }
+        found = true;
+        *attribute = syn::parse_quote!(#[derive(#(#paths),*)]);
+        !paths.is_empty()
+    });
+    found
+}
+
 pub fn parse_asn_definition(
     attr: TokenStream,
     item: TokenStream,
 ) -> Result<(Option<Definition<AsnModelType>>, Item), TokenStream> {
+    parse_asn_definition_and_rust(attr, item)
+        .map(|(definition, _attributes, item)| (definition, item))
+}
+
+/// Like [`parse_asn_definition`], but also returns the Rust attributes. The field numbers of
+/// `protobuf_numbers(..)` must match the number of fields or variants, `protobuf_fixed(..)`
+/// must only list fields or variants of an integer type, `uuid(..)` only those of an
+/// OCTET STRING (SIZE(16)) without a default value and `boxed(..)` only those referring to another
+/// type, also if optional. With `protobuf_unknown_fields`, the `unknown_fields` field of a
+/// struct is not part of the definition.
+#[allow(clippy::type_complexity)]
+fn parse_asn_definition_and_rust(
+    attr: TokenStream,
+    item: TokenStream,
+) -> Result<(Option<Definition<AsnModelType>>, RustAttributes, Item), TokenStream> {
     let item_span = item.span();
     let attr_span = attr.span();
 
@@ -119,7 +229,7 @@ pub fn parse_asn_definition(
         println!("Matching item {:?}", item);
     }
 
-    match item {
+    let (definition, item) = match item {
         Item::Struct(strct) if asn.primary.eq_ignore_ascii_case("sequence") => {
             parse_sequence_or_set(strct, &asn, attr_span, Type::Sequence)
         }
@@ -136,6 +246,182 @@ pub fn parse_asn_definition(
             parse_choice(enm, &asn, attr_span)
         }
         item => Ok((None, item)),
+    }?;
+
+    if !asn.protobuf_numbers.is_empty() {
+        let count = match definition.as_ref().map(|d| &d.1.r#type) {
+            Some(Type::Sequence(list)) | Some(Type::Set(list)) => list.fields.len(),
+            Some(Type::Choice(choice)) => choice.len(),
+            _ => compile_err_ts(
+                attr_span,
+                "protobuf_numbers(..) is only valid for a sequence, set or choice",
+            )
+            .map(|_| 0)?,
+        };
+        if count != asn.protobuf_numbers.len() {
+            compile_err_ts(
+                attr_span,
+                format!(
+                    "Expected {} protobuf field numbers but got {}",
+                    count,
+                    asn.protobuf_numbers.len()
+                ),
+            )?;
+        }
+        let numbers = &asn.protobuf_numbers;
+        if let Some(number) = numbers
+            .iter()
+            .enumerate()
+            .find(|(index, number)| **number == 0 || numbers[..*index].contains(number))
+            .map(|(_, number)| number)
+        {
+            compile_err_ts(
+                attr_span,
+                format!("Invalid or repeated protobuf field number: {}", number),
+            )?;
+        }
+    }
+
+    if asn.protobuf_unknown_fields
+        && !matches!(
+            definition.as_ref().map(|d| &d.1.r#type),
+            Some(Type::Sequence(_)) | Some(Type::Set(_))
+        )
+    {
+        compile_err_ts(
+            attr_span,
+            "protobuf_unknown_fields is only valid for a sequence or set",
+        )?;
+    }
+
+    for name in &asn.protobuf_fixed {
+        match field_type(definition.as_ref(), name, "protobuf_fixed", attr_span)? {
+            None => {
+                compile_err_ts(
+                    attr_span,
+                    format!("Unknown field for protobuf_fixed: {}", name),
+                )?;
+            }
+            Some(r#type) if !is_integer(r#type) => {
+                compile_err_ts(
+                    attr_span,
+                    format!(
+                        "protobuf_fixed(..) requires an integer, but {} is not",
+                        name
+                    ),
+                )?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    for name in &asn.uuid {
+        match field_type(definition.as_ref(), name, "uuid", attr_span)? {
+            None => {
+                compile_err_ts(attr_span, format!("Unknown field for uuid: {}", name))?;
+            }
+            Some(r#type) if !is_uuid(r#type) => {
+                compile_err_ts(
+                    attr_span,
+                    format!(
+                        "uuid(..) requires an OCTET STRING (SIZE(16)), but {} is not",
+                        name
+                    ),
+                )?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    for name in &asn.boxed {
+        match field_type(definition.as_ref(), name, "boxed", attr_span)? {
+            None => {
+                compile_err_ts(attr_span, format!("Unknown field for boxed: {}", name))?;
+            }
+            Some(r#type) if !is_reference(r#type) => {
+                compile_err_ts(
+                    attr_span,
+                    format!(
+                        "boxed(..) requires a reference to another type, but {} is not",
+                        name
+                    ),
+                )?;
+            }
+            Some(_) => {}
+        }
+    }
+
+    Ok((
+        definition,
+        RustAttributes {
+            numbers: asn.protobuf_numbers,
+            fixed: asn.protobuf_fixed,
+            unknown_fields: asn.protobuf_unknown_fields,
+            uuid: asn.uuid,
+            boxed: asn.boxed,
+            no_inline: asn.no_inline,
+        },
+        item,
+    ))
+}
+
+/// The type of the field of a sequence or set or of the variant of a choice with the given name
+fn field_type<'a>(
+    definition: Option<&'a Definition<AsnModelType>>,
+    name: &str,
+    attribute: &str,
+    attr_span: proc_macro2::Span,
+) -> Result<Option<&'a Type>, TokenStream> {
+    match definition.map(|d| &d.1.r#type) {
+        Some(Type::Sequence(list)) | Some(Type::Set(list)) => Ok(list
+            .fields
+            .iter()
+            .find(|field| field.name == *name)
+            .map(|field| &field.role.r#type)),
+        Some(Type::Choice(choice)) => Ok(choice
+            .variants()
+            .find(|variant| variant.name() == name)
+            .map(ChoiceVariant::r#type)),
+        _ => compile_err_ts(
+            attr_span,
+            format!(
+                "{}(..) is only valid for a sequence, set or choice",
+                attribute
+            ),
+        )
+        .map(|_| None),
+    }
+}
+
+/// Whether the type is an integer, also if optional, with a default value or a list of integers
+fn is_integer(r#type: &Type) -> bool {
+    match r#type {
+        Type::Integer(_) => true,
+        Type::Optional(inner)
+        | Type::Default(inner, _)
+        | Type::SequenceOf(inner, _)
+        | Type::SetOf(inner, _) => is_integer(inner),
+        _ => false,
+    }
+}
+
+/// Whether the type is an OCTET STRING (SIZE(16)), also if optional or a list of such
+fn is_uuid(r#type: &Type) -> bool {
+    match r#type {
+        Type::OctetString(size) => *size == UUID_SIZE,
+        Type::Optional(inner) | Type::SequenceOf(inner, _) | Type::SetOf(inner, _) => {
+            is_uuid(inner)
+        }
+        _ => false,
+    }
+}
+
+/// Whether the type is a reference to another type, also if optional
+fn is_reference(r#type: &Type) -> bool {
+    match r#type {
+        Type::TypeReference(..) => true,
+        Type::Optional(inner) => is_reference(inner),
+        _ => false,
     }
 }
 
@@ -145,9 +431,28 @@ fn parse_sequence_or_set<F: Fn(ComponentTypeList<Resolved>) -> Type>(
     asn_span: proc_macro2::Span,
     mapper: F,
 ) -> Result<(Option<Definition<AsnModelType>>, Item), TokenStream> {
+    let is_unknown_fields = |field: &syn::Field| {
+        asn.protobuf_unknown_fields
+            && field
+                .ident
+                .as_ref()
+                .is_some_and(|ident| ident == UNKNOWN_FIELDS)
+    };
+
+    if asn.protobuf_unknown_fields && !strct.fields.iter().any(is_unknown_fields) {
+        compile_err_ts(
+            asn_span,
+            format!(
+                "protobuf_unknown_fields requires the field `{}: UnknownFields`",
+                UNKNOWN_FIELDS
+            ),
+        )?;
+    }
+
     let fields = strct
         .fields
         .iter_mut()
+        .filter(|field| !is_unknown_fields(field))
         .map(|field| {
             if field.ident.is_none() {
                 compile_err_ts(
@@ -162,11 +467,11 @@ fn parse_sequence_or_set<F: Fn(ComponentTypeList<Resolved>) -> Type>(
                 &mut field.attrs,
             )
             .map(|asn| Field {
-                name: field.ident.as_ref().unwrap().to_string(),
+                name: field.ident.as_ref().unwrap().unraw().to_string(),
                 role: asn,
             })
         })
-        .vec_result()?;
+        .collect::<Result<Vec<_>, _>>()?;
 
     Ok((
         Some(Definition(
@@ -326,8 +631,18 @@ fn parse_choice(
 fn find_extensible_index(
     asn: &AsnAttribute<DefinitionHeader>,
     asn_span: proc_macro2::Span,
-    variants: impl Iterator<Item = impl AsRef<str>>,
+    mut variants: impl Iterator<Item = impl AsRef<str>>,
 ) -> Result<Option<usize>, TokenStream> {
+    if asn.extensible {
+        return if asn.extensible_after.is_some() || variants.next().is_some() {
+            Err(compile_error_ts(
+                asn_span,
+                "The extensible attribute is only allowed without fields, consider extensible_after",
+            ))
+        } else {
+            Ok(Some(0))
+        };
+    }
     asn.extensible_after
         .as_ref()
         .map(|name| {
@@ -372,15 +687,26 @@ fn into_asn<C: Context<Primary = Type>>(ty: &syn::Type, mut asn: AsnAttribute<C>
     AsnModelType {
         tag: asn.tag,
         r#type: if let Type::TypeReference(_, empty_tag) = asn.primary {
+            let ty = unboxed(ty);
             Type::TypeReference(quote! { #ty }.to_string(), empty_tag.or(asn.tag))
         } else {
-            if let Type::Integer(int) = asn.primary.no_optional_mut() {
-                asn.consts
+            match asn.primary.no_optional_mut() {
+                Type::Integer(int) => asn
+                    .consts
                     .into_iter()
                     .map(|c| match c {
                         ConstLit::I64(name, value) => (name, value),
                     })
-                    .for_each(|v| int.constants.push(v));
+                    .for_each(|v| int.constants.push(v)),
+                // the named bits, which are never negative
+                Type::BitString(bits) => asn
+                    .consts
+                    .into_iter()
+                    .map(|c| match c {
+                        ConstLit::I64(name, value) => (name, value as u64),
+                    })
+                    .for_each(|v| bits.constants.push(v)),
+                _ => {}
             }
             asn.primary
         },
@@ -388,6 +714,20 @@ fn into_asn<C: Context<Primary = Type>>(ty: &syn::Type, mut asn: AsnAttribute<C>
     }
 }
 
+/// The type `T` of a `Box<T>`, which refers to `T` as well
+fn unboxed(ty: &syn::Type) -> &syn::Type {
+    if let syn::Type::Path(path) = ty {
+        if let Some(segment) = path.path.segments.last().filter(|s| s.ident == "Box") {
+            if let syn::PathArguments::AngleBracketed(arguments) = &segment.arguments {
+                if let Some(syn::GenericArgument::Type(inner)) = arguments.args.first() {
+                    return inner;
+                }
+            }
+        }
+    }
+    ty
+}
+
 fn compile_err_ts<T: std::fmt::Display>(
     span: proc_macro2::Span,
     msg: T,
@@ -8,7 +8,7 @@ use syn::Token;
 #[allow(clippy::upper_case_acronyms)]
 enum MMV {
     MinMax,
-    Value(i64),
+    Value(i128),
 }
 
 impl MMV {
@@ -16,7 +16,7 @@ impl MMV {
         if let Ok(Lit::Int(int)) = input.parse::<Lit>() {
             Ok(Some(MMV::Value(
                 int.base10_digits()
-                    .parse::<i64>()
+                    .parse::<i128>()
                     .map_err(|_| input.error("Expected int literal for from value of range"))?,
             )))
         } else if let Ok(ident) = input.parse::<Ident>() {
@@ -36,7 +36,7 @@ impl MMV {
 }
 
 #[derive(Debug)]
-pub struct IntegerRange(pub Option<(i64, i64)>, pub bool);
+pub struct IntegerRange(pub Option<(i128, i128)>, pub bool);
 
 impl Parse for IntegerRange {
     fn parse<'a>(input: ParseStream) -> syn::Result<Self> {
@@ -64,13 +64,23 @@ impl Parse for IntegerRange {
                     if max.is_positive() {
                         0
                     } else {
-                        i64::MAX.wrapping_add(1)
+                        i128::from(i64::MIN)
                     },
                     max,
                 )),
                 extensible,
             )),
-            (MMV::Value(min), MMV::MinMax) => Ok(IntegerRange(Some((min, i64::MAX)), extensible)),
+            (MMV::Value(min), MMV::MinMax) => Ok(IntegerRange(
+                Some((
+                    min,
+                    if min > i128::from(i64::MAX) {
+                        i128::MAX
+                    } else {
+                        i128::from(i64::MAX)
+                    },
+                )),
+                extensible,
+            )),
         }
     }
 }
@@ -0,0 +1,927 @@
+//! Imports proto3 files as ASN.1 model, so that existing protobuf schemas can be migrated to
+//! generated Rust types (with UPER, DER, ... support) through the same pipeline as ASN.1
+//! modules. The resulting model can be resolved and converted to a `Model<Rust>` and
+//! `Model<Protobuf>` or written as ASN.1 module:
+//!
+//! ```rust
+//! use asn1rs_model::generate::asn1::Asn1DefGenerator;
+//! use asn1rs_model::protobuf::import::import;
+//! use asn1rs_model::protobuf::ToProtobufModel;
+//!
+//! let model = import(
+//!     r#"syntax = "proto3";
+//!     package my.messages;
+//!
+//!     message Header {
+//!         uint32 timestamp = 1;
+//!         repeated string tags = 2;
+//!     }"#,
+//! )
+//! .unwrap()
+//! .try_resolve()
+//! .unwrap();
+//!
+//! assert_eq!("MyMessages", model.name);
+//! let _protobuf = model.to_rust().to_protobuf();
+//! let (_file_name, asn1) = Asn1DefGenerator::generate_file(&model).unwrap();
+//! assert!(asn1.contains("timestamp INTEGER (0..4294967295),"));
+//! ```
+//!
+//! The protobuf definitions are mapped as follows:
+//!
+//!  - a `message` becomes a `SEQUENCE` with the fields ordered by their field number. Nested
+//!    messages and enums become definitions of their own, named after their path
+//!    (`Outer.Inner` becomes `OuterInner`),
+//!  - an `enum` becomes an `ENUMERATED` with the numbers of the values. The prefix of the enum
+//!    name (`KIND_` for `KIND_SMALL` in `Kind`) is removed from the value names and aliases are
+//!    skipped,
+//!  - `int32`, `sint32` and `sfixed32` become `INTEGER (-2147483648..2147483647)`, `uint32` and
+//!    `fixed32` become `INTEGER (0..4294967295)` and likewise for the 64 bit types, with
+//!    `uint64` and `fixed64` being `INTEGER (0..MAX)`,
+//!  - `bool`, `string` and `bytes` become `BOOLEAN`, `UTF8String` and `OCTET STRING`,
+//!  - `repeated T` becomes `SEQUENCE OF T` and `map<K, V>` becomes
+//!    `SEQUENCE OF SEQUENCE { key K, value V }`,
+//!  - a `oneof` becomes an `OPTIONAL` `CHOICE` at the position of its lowest field number,
+//!  - `optional` fields and fields of message types become `OPTIONAL`,
+//!  - types of other packages (such as `other.pkg.Type`) are imported from the module named
+//!    after the package (`OtherPkg`). Because it is unknown whether they are messages or enums,
+//!    such fields become `OPTIONAL` as well.
+//!
+//! Options, `reserved` ranges, `import` statements and services are ignored. `float`, `double`,
+//! groups, extensions and proto2 files are not supported.
+//!
+//! The field numbers are not part of the model: the protobuf encoding of the generated types
+//! numbers the fields by their position and uses its own mapping of the scalar types. It is
+//! therefore not wire compatible to the imported file in general.
+
+use crate::asn::{Asn, Charset, Choice, ChoiceVariant, ComponentTypeList, Enumerated};
+use crate::asn::{EnumeratedVariant, Integer, Range, Size, Type};
+use crate::generate::asn1::Asn1DefGenerator;
+use crate::generate::protobuf::ProtobufDefGenerator;
+use crate::model::{Definition, Field, Import, Model};
+use crate::resolve::{LitOrRef, Unresolved};
+use crate::rust::rust_struct_or_enum_name;
+use std::convert::TryFrom;
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    UnexpectedToken {
+        line: usize,
+        found: String,
+        expected: &'static str,
+    },
+    UnexpectedEndOfFile,
+    /// The file does not declare `syntax = "proto3";`
+    UnsupportedSyntax(String),
+    UnsupportedType {
+        line: usize,
+        name: String,
+    },
+    UndefinedType {
+        line: usize,
+        name: String,
+    },
+    InvalidNumber {
+        line: usize,
+        value: String,
+    },
+}
+
+impl std::error::Error for Error {}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnexpectedToken {
+                line,
+                found,
+                expected,
+            } => write!(
+                f,
+                "Line {}: Unexpected token '{}', expected {}",
+                line, found, expected
+            ),
+            Error::UnexpectedEndOfFile => write!(f, "Unexpected end of file"),
+            Error::UnsupportedSyntax(syntax) => write!(
+                f,
+                "Unsupported syntax '{}', only proto3 is supported",
+                syntax
+            ),
+            Error::UnsupportedType { line, name } => {
+                write!(f, "Line {}: The type {} is not supported", line, name)
+            }
+            Error::UndefinedType { line, name } => {
+                write!(f, "Line {}: The type {} is not defined", line, name)
+            }
+            Error::InvalidNumber { line, value } => {
+                write!(f, "Line {}: Invalid number '{}'", line, value)
+            }
+        }
+    }
+}
+
+/// Parses the given proto3 file into an (unresolved) ASN.1 model. The model is named after the
+/// package (`my.messages` becomes `MyMessages`) and has an empty name without a package.
+pub fn import(source: &str) -> Result<Model<Asn<Unresolved>>, Error> {
+    let file = Parser {
+        tokens: tokenize(source),
+        position: 0,
+    }
+    .parse_file()?;
+    file.into_model()
+}
+
+/// The name of the module for the given protobuf package, see [`import`]
+pub fn module_name(package: &str) -> String {
+    package.split('.').map(rust_struct_or_enum_name).collect()
+}
+
+#[derive(Debug, Clone, PartialEq)]
+enum Token {
+    /// Identifiers, keywords and (fully) qualified names
+    Word(String),
+    /// Numbers, which are only validated when needed
+    Number(String),
+    /// The content of a string literal, without quotes
+    String(String),
+    Symbol(char),
+}
+
+impl Display for Token {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Token::Word(word) | Token::Number(word) => write!(f, "{}", word),
+            Token::String(string) => write!(f, "\"{}\"", string),
+            Token::Symbol(symbol) => write!(f, "{}", symbol),
+        }
+    }
+}
+
+fn tokenize(source: &str) -> Vec<(usize, Token)> {
+    let mut tokens = Vec::new();
+    let mut line = 1;
+    let mut chars = source.chars().peekable();
+    while let Some(c) = chars.next() {
+        match c {
+            '\n' => line += 1,
+            c if c.is_whitespace() => {}
+            '/' if chars.peek() == Some(&'/') => {
+                while chars.peek().is_some_and(|c| *c != '\n') {
+                    chars.next();
+                }
+            }
+            '/' if chars.peek() == Some(&'*') => {
+                chars.next();
+                let mut previous = ' ';
+                for c in chars.by_ref() {
+                    if c == '\n' {
+                        line += 1;
+                    } else if previous == '*' && c == '/' {
+                        break;
+                    }
+                    previous = c;
+                }
+            }
+            '"' | '\'' => {
+                let mut string = String::new();
+                while let Some(next) = chars.next() {
+                    match next {
+                        '\\' => string.extend(chars.next()),
+                        next if next == c => break,
+                        next => string.push(next),
+                    }
+                }
+                tokens.push((line, Token::String(string)));
+            }
+            c if c.is_ascii_alphabetic()
+                || c == '_'
+                || (c == '.' && chars.peek().is_some_and(|c| c.is_ascii_alphabetic())) =>
+            {
+                let mut word = String::from(c);
+                while let Some(next) =
+                    chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '_' || *c == '.')
+                {
+                    word.push(next);
+                }
+                tokens.push((line, Token::Word(word)));
+            }
+            c if c.is_ascii_digit() => {
+                let mut number = String::from(c);
+                while let Some(next) = chars.next_if(|c| c.is_ascii_alphanumeric() || *c == '.') {
+                    number.push(next);
+                }
+                tokens.push((line, Token::Number(number)));
+            }
+            c => tokens.push((line, Token::Symbol(c))),
+        }
+    }
+    tokens
+}
+
+#[derive(Debug, Default)]
+struct ProtoFile {
+    package: Option<String>,
+    messages: Vec<ProtoMessage>,
+    enums: Vec<ProtoEnum>,
+    /// The path of each message and enum in the order of their definition
+    order: Vec<String>,
+}
+
+#[derive(Debug)]
+struct ProtoMessage {
+    /// The path of the message, such as `Outer.Inner`
+    path: String,
+    fields: Vec<ProtoField>,
+}
+
+#[derive(Debug)]
+struct ProtoEnum {
+    path: String,
+    values: Vec<(String, i64)>,
+}
+
+#[derive(Debug)]
+struct ProtoField {
+    name: String,
+    /// The lowest field number for a `oneof`
+    number: i64,
+    kind: FieldKind,
+}
+
+#[derive(Debug)]
+enum FieldKind {
+    Single(Label, FieldType),
+    Map(FieldType, FieldType),
+    OneOf(Vec<(String, FieldType)>),
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+enum Label {
+    None,
+    Optional,
+    Repeated,
+}
+
+#[derive(Debug)]
+struct FieldType {
+    line: usize,
+    name: String,
+}
+
+struct Parser {
+    tokens: Vec<(usize, Token)>,
+    position: usize,
+}
+
+impl Parser {
+    fn parse_file(&mut self) -> Result<ProtoFile, Error> {
+        let mut file = ProtoFile::default();
+        let mut syntax = None;
+        while let Some(word) = self.next_word_opt()? {
+            match word.as_str() {
+                "syntax" => {
+                    self.expect_symbol('=')?;
+                    syntax = Some(self.next_string()?);
+                    self.expect_symbol(';')?;
+                }
+                "package" => {
+                    file.package = Some(self.next_word()?);
+                    self.expect_symbol(';')?;
+                }
+                "import" | "option" => self.skip_statement()?,
+                "message" => self.parse_message(&mut file, "")?,
+                "enum" => self.parse_enum(&mut file, "")?,
+                "service" => {
+                    self.next_word()?;
+                    self.skip_block()?;
+                }
+                _ => return Err(self.unexpected_previous("a top-level definition")),
+            }
+        }
+        match syntax {
+            Some(syntax) if syntax == "proto3" => Ok(file),
+            syntax => Err(Error::UnsupportedSyntax(
+                syntax.unwrap_or_else(|| "proto2".to_string()),
+            )),
+        }
+    }
+
+    fn parse_message(&mut self, file: &mut ProtoFile, scope: &str) -> Result<(), Error> {
+        let path = Self::path(scope, &self.next_word()?);
+        file.order.push(path.clone());
+        let index = file.messages.len();
+        file.messages.push(ProtoMessage {
+            path: path.clone(),
+            fields: Vec::new(),
+        });
+        self.expect_symbol('{')?;
+        loop {
+            if self.next_if_symbol('}') {
+                break;
+            }
+            let word = self.next_word()?;
+            let field = match word.as_str() {
+                "message" => {
+                    self.parse_message(file, &path)?;
+                    continue;
+                }
+                "enum" => {
+                    self.parse_enum(file, &path)?;
+                    continue;
+                }
+                "option" | "reserved" => {
+                    self.skip_statement()?;
+                    continue;
+                }
+                "oneof" => self.parse_oneof()?,
+                "map" => self.parse_map()?,
+                "optional" => {
+                    let r#type = self.next_field_type()?;
+                    self.parse_field(Label::Optional, r#type)?
+                }
+                "repeated" => {
+                    let r#type = self.next_field_type()?;
+                    self.parse_field(Label::Repeated, r#type)?
+                }
+                "required" | "group" | "extend" | "extensions" => {
+                    return Err(Error::UnsupportedType {
+                        line: self.previous_line(),
+                        name: word,
+                    })
+                }
+                _ => {
+                    let r#type = FieldType {
+                        line: self.previous_line(),
+                        name: word,
+                    };
+                    self.parse_field(Label::None, r#type)?
+                }
+            };
+            file.messages[index].fields.push(field);
+        }
+        file.messages[index]
+            .fields
+            .sort_by_key(|field| field.number);
+        Ok(())
+    }
+
+    fn parse_field(&mut self, label: Label, r#type: FieldType) -> Result<ProtoField, Error> {
+        let name = self.next_word()?;
+        let number = self.parse_number_assignment()?;
+        Ok(ProtoField {
+            name,
+            number,
+            kind: FieldKind::Single(label, r#type),
+        })
+    }
+
+    fn parse_map(&mut self) -> Result<ProtoField, Error> {
+        self.expect_symbol('<')?;
+        let key = self.next_field_type()?;
+        self.expect_symbol(',')?;
+        let value = self.next_field_type()?;
+        self.expect_symbol('>')?;
+        let name = self.next_word()?;
+        let number = self.parse_number_assignment()?;
+        Ok(ProtoField {
+            name,
+            number,
+            kind: FieldKind::Map(key, value),
+        })
+    }
+
+    fn parse_oneof(&mut self) -> Result<ProtoField, Error> {
+        let name = self.next_word()?;
+        let mut variants = Vec::new();
+        let mut number = i64::MAX;
+        self.expect_symbol('{')?;
+        while !self.next_if_symbol('}') {
+            let r#type = self.next_field_type()?;
+            if r#type.name == "option" {
+                self.skip_statement()?;
+                continue;
+            }
+            let variant = self.next_word()?;
+            number = number.min(self.parse_number_assignment()?);
+            variants.push((number, variant, r#type));
+        }
+        variants.sort_by_key(|(number, ..)| *number);
+        Ok(ProtoField {
+            name,
+            number,
+            kind: FieldKind::OneOf(
+                variants
+                    .into_iter()
+                    .map(|(_, name, r#type)| (name, r#type))
+                    .collect(),
+            ),
+        })
+    }
+
+    fn parse_enum(&mut self, file: &mut ProtoFile, scope: &str) -> Result<(), Error> {
+        let path = Self::path(scope, &self.next_word()?);
+        let mut values = Vec::<(String, i64)>::new();
+        self.expect_symbol('{')?;
+        while !self.next_if_symbol('}') {
+            let name = self.next_word()?;
+            if name == "option" || name == "reserved" {
+                self.skip_statement()?;
+                continue;
+            }
+            let number = self.parse_number_assignment()?;
+            if values.iter().all(|(_, other)| *other != number) {
+                values.push((name, number));
+            }
+        }
+        file.order.push(path.clone());
+        file.enums.push(ProtoEnum { path, values });
+        Ok(())
+    }
+
+    /// Parses `= <number> [options];`
+    fn parse_number_assignment(&mut self) -> Result<i64, Error> {
+        self.expect_symbol('=')?;
+        let negative = self.next_if_symbol('-');
+        let number = match self.next()? {
+            (line, Token::Number(number)) => {
+                let parsed = if let Some(hex) = number.strip_prefix("0x") {
+                    i64::from_str_radix(hex, 16)
+                } else if number.len() > 1 && number.starts_with('0') {
+                    i64::from_str_radix(&number[1..], 8)
+                } else {
+                    number.parse()
+                };
+                parsed.map_err(|_| Error::InvalidNumber {
+                    line,
+                    value: number,
+                })?
+            }
+            (line, token) => {
+                return Err(Error::UnexpectedToken {
+                    line,
+                    found: token.to_string(),
+                    expected: "a number",
+                })
+            }
+        };
+        if self.next_if_symbol('[') {
+            self.skip_until(']')?;
+        }
+        self.expect_symbol(';')?;
+        Ok(if negative { -number } else { number })
+    }
+
+    fn next_field_type(&mut self) -> Result<FieldType, Error> {
+        let name = self.next_word()?;
+        Ok(FieldType {
+            line: self.previous_line(),
+            name,
+        })
+    }
+
+    /// Skips everything up to and including the next `;` outside of brackets and braces
+    fn skip_statement(&mut self) -> Result<(), Error> {
+        self.skip_until(';')
+    }
+
+    fn skip_block(&mut self) -> Result<(), Error> {
+        self.expect_symbol('{')?;
+        self.skip_until('}')
+    }
+
+    fn skip_until(&mut self, end: char) -> Result<(), Error> {
+        let mut depth = 0_usize;
+        loop {
+            match self.next()?.1 {
+                Token::Symbol(symbol) if symbol == end && depth == 0 => return Ok(()),
+                Token::Symbol('{' | '[' | '(') => depth += 1,
+                Token::Symbol('}' | ']' | ')') => depth = depth.saturating_sub(1),
+                _ => {}
+            }
+        }
+    }
+
+    fn next(&mut self) -> Result<(usize, Token), Error> {
+        let token = self
+            .tokens
+            .get(self.position)
+            .cloned()
+            .ok_or(Error::UnexpectedEndOfFile)?;
+        self.position += 1;
+        Ok(token)
+    }
+
+    fn next_word_opt(&mut self) -> Result<Option<String>, Error> {
+        while self.next_if_symbol(';') {}
+        if self.position < self.tokens.len() {
+            self.next_word().map(Some)
+        } else {
+            Ok(None)
+        }
+    }
+
+    fn next_word(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            (_, Token::Word(word)) => Ok(word),
+            (line, token) => Err(Error::UnexpectedToken {
+                line,
+                found: token.to_string(),
+                expected: "an identifier",
+            }),
+        }
+    }
+
+    fn next_string(&mut self) -> Result<String, Error> {
+        match self.next()? {
+            (_, Token::String(string)) => Ok(string),
+            (line, token) => Err(Error::UnexpectedToken {
+                line,
+                found: token.to_string(),
+                expected: "a string",
+            }),
+        }
+    }
+
+    fn expect_symbol(&mut self, symbol: char) -> Result<(), Error> {
+        match self.next()? {
+            (_, Token::Symbol(s)) if s == symbol => Ok(()),
+            (line, token) => Err(Error::UnexpectedToken {
+                line,
+                found: token.to_string(),
+                expected: match symbol {
+                    '=' => "'='",
+                    ';' => "';'",
+                    '{' => "'{'",
+                    '<' => "'<'",
+                    '>' => "'>'",
+                    ',' => "','",
+                    _ => "a symbol",
+                },
+            }),
+        }
+    }
+
+    fn next_if_symbol(&mut self, symbol: char) -> bool {
+        let matches =
+            matches!(self.tokens.get(self.position), Some((_, Token::Symbol(s))) if *s == symbol);
+        if matches {
+            self.position += 1;
+        }
+        matches
+    }
+
+    fn previous_line(&self) -> usize {
+        self.tokens[self.position - 1].0
+    }
+
+    fn unexpected_previous(&self, expected: &'static str) -> Error {
+        let (line, token) = &self.tokens[self.position - 1];
+        Error::UnexpectedToken {
+            line: *line,
+            found: token.to_string(),
+            expected,
+        }
+    }
+
+    fn path(scope: &str, name: &str) -> String {
+        if scope.is_empty() {
+            name.to_string()
+        } else {
+            format!("{}.{}", scope, name)
+        }
+    }
+}
+
+/// How a field type is referred to in the model
+enum Reference {
+    Message(String),
+    Enum(String),
+    Imported(String, String),
+}
+
+impl ProtoFile {
+    fn into_model(self) -> Result<Model<Asn<Unresolved>>, Error> {
+        let mut model = Model {
+            name: self.package.as_deref().map(module_name).unwrap_or_default(),
+            ..Model::default()
+        };
+        for path in &self.order {
+            let definition = if let Some(message) = self.messages.iter().find(|m| m.path.eq(path)) {
+                Type::Sequence(ComponentTypeList {
+                    fields: message
+                        .fields
+                        .iter()
+                        .map(|field| self.field(&message.path, field, &mut model.imports))
+                        .collect::<Result<Vec<_>, _>>()?,
+                    extension_after: None,
+                })
+            } else {
+                let proto_enum = self.enums.iter().find(|e| e.path.eq(path)).unwrap();
+                Self::enumerated(proto_enum)?
+            };
+            model.definitions.push(Definition(
+                Self::definition_name(path),
+                Asn::untagged(definition),
+            ));
+        }
+        Ok(model)
+    }
+
+    fn field(
+        &self,
+        scope: &str,
+        field: &ProtoField,
+        imports: &mut Vec<Import>,
+    ) -> Result<Field<Asn<Unresolved>>, Error> {
+        let r#type = match &field.kind {
+            FieldKind::Single(label, r#type) => {
+                let (inner, has_presence) = self.field_type(scope, r#type, imports)?;
+                match label {
+                    Label::Repeated => Type::SequenceOf(Box::new(inner), Size::Any),
+                    Label::Optional => inner.optional(),
+                    Label::None if has_presence => inner.optional(),
+                    Label::None => inner,
+                }
+            }
+            FieldKind::Map(key, value) => Type::SequenceOf(
+                Box::new(Type::Sequence(ComponentTypeList {
+                    fields: vec![
+                        Field {
+                            name: "key".to_string(),
+                            role: Asn::untagged(self.field_type(scope, key, imports)?.0),
+                        },
+                        Field {
+                            name: "value".to_string(),
+                            role: Asn::untagged(self.field_type(scope, value, imports)?.0),
+                        },
+                    ],
+                    extension_after: None,
+                })),
+                Size::Any,
+            ),
+            FieldKind::OneOf(variants) => Type::Choice(Choice::from_variants(
+                variants
+                    .iter()
+                    .map(|(name, r#type)| {
+                        Ok(ChoiceVariant {
+                            name: Asn1DefGenerator::identifier(name),
+                            tag: None,
+                            r#type: self.field_type(scope, r#type, imports)?.0,
+                        })
+                    })
+                    .collect::<Result<Vec<_>, Error>>()?
+                    .into_iter(),
+            ))
+            .optional(),
+        };
+        Ok(Field {
+            name: Asn1DefGenerator::identifier(&field.name),
+            role: Asn::untagged(r#type),
+        })
+    }
+
+    /// The type of the field and whether the field has presence (is a message)
+    fn field_type(
+        &self,
+        scope: &str,
+        r#type: &FieldType,
+        imports: &mut Vec<Import>,
+    ) -> Result<(Type<Unresolved>, bool), Error> {
+        let integer = |min: i64, max: Option<i64>| {
+            Type::Integer(Integer::with_range(Range(
+                Some(LitOrRef::Lit(i128::from(min))),
+                max.map(|max| LitOrRef::Lit(i128::from(max))),
+                false,
+            )))
+        };
+        Ok((
+            match r#type.name.as_str() {
+                "bool" => Type::Boolean,
+                "int32" | "sint32" | "sfixed32" => {
+                    integer(i64::from(i32::MIN), Some(i64::from(i32::MAX)))
+                }
+                "uint32" | "fixed32" => integer(0, Some(i64::from(u32::MAX))),
+                "int64" | "sint64" | "sfixed64" => integer(i64::MIN, Some(i64::MAX)),
+                "uint64" | "fixed64" => integer(0, None),
+                "string" => Type::String(Size::Any, Charset::Utf8, None),
+                "bytes" => Type::OctetString(Size::Any),
+                "float" | "double" => {
+                    return Err(Error::UnsupportedType {
+                        line: r#type.line,
+                        name: r#type.name.clone(),
+                    })
+                }
+                _ => {
+                    return Ok(match self.resolve(scope, r#type)? {
+                        Reference::Message(name) => (Type::TypeReference(name, None), true),
+                        Reference::Enum(name) => (Type::TypeReference(name, None), false),
+                        Reference::Imported(module, name) => {
+                            match imports.iter_mut().find(|import| import.from == module) {
+                                Some(import) if import.what.contains(&name) => {}
+                                Some(import) => import.what.push(name.clone()),
+                                None => imports.push(Import {
+                                    what: vec![name.clone()],
+                                    from: module,
+                                    from_oid: None,
+                                }),
+                            }
+                            (Type::TypeReference(name, None), true)
+                        }
+                    })
+                }
+            },
+            false,
+        ))
+    }
+
+    /// Resolves the type name by the scoping rules of protobuf: relative to the enclosing
+    /// messages from the innermost to the outermost, then relative to the package
+    fn resolve(&self, scope: &str, r#type: &FieldType) -> Result<Reference, Error> {
+        let package_prefix = self.package.as_ref().map(|p| format!("{}.", p));
+        let name = r#type.name.strip_prefix('.').unwrap_or(&r#type.name);
+        let local = package_prefix
+            .as_deref()
+            .and_then(|prefix| name.strip_prefix(prefix))
+            .unwrap_or(name);
+
+        let mut candidates = Vec::new();
+        if !r#type.name.starts_with('.') {
+            let mut scope = scope;
+            while !scope.is_empty() {
+                candidates.push(format!("{}.{}", scope, local));
+                scope = scope.rfind('.').map_or("", |index| &scope[..index]);
+            }
+        }
+        candidates.push(local.to_string());
+
+        for candidate in candidates {
+            if self.messages.iter().any(|m| m.path == candidate) {
+                return Ok(Reference::Message(Self::definition_name(&candidate)));
+            } else if self.enums.iter().any(|e| e.path == candidate) {
+                return Ok(Reference::Enum(Self::definition_name(&candidate)));
+            }
+        }
+
+        match name.rfind('.') {
+            Some(index) if local == name => Ok(Reference::Imported(
+                module_name(&name[..index]),
+                name[index + 1..].to_string(),
+            )),
+            _ => Err(Error::UndefinedType {
+                line: r#type.line,
+                name: r#type.name.clone(),
+            }),
+        }
+    }
+
+    fn enumerated(proto_enum: &ProtoEnum) -> Result<Type<Unresolved>, Error> {
+        let name = proto_enum.path.rsplit('.').next().unwrap_or_default();
+        let prefix = format!("{}_", ProtobufDefGenerator::variant_name(name));
+        Ok(Type::Enumerated(Enumerated::from_variants(
+            proto_enum
+                .values
+                .iter()
+                .map(|(value, number)| {
+                    let number = usize::try_from(*number).map_err(|_| Error::InvalidNumber {
+                        line: 0,
+                        value: number.to_string(),
+                    })?;
+                    Ok(EnumeratedVariant::from_name_number(
+                        Asn1DefGenerator::identifier(value.strip_prefix(&prefix).unwrap_or(value)),
+                        number,
+                    ))
+                })
+                .collect::<Result<Vec<_>, Error>>()?,
+        )))
+    }
+
+    fn definition_name(path: &str) -> String {
+        path.split('.').collect()
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_import_to_asn1() {
+        let model = import(
+            r#"
+            syntax = "proto3";
+            package shop.v1;
+
+            import "google/protobuf/timestamp.proto";
+
+            // An order of a customer
+            message Order {
+                enum Status {
+                    STATUS_UNKNOWN = 0;
+                    STATUS_SHIPPED = 1;
+                    STATUS_SENT = 1;
+                    STATUS_DELIVERED = 2 [deprecated = true];
+                }
+                message Item {
+                    string sku = 1;
+                    uint32 count = 2;
+                }
+                reserved 4, 8 to 10;
+
+                int64 order_id = 1;
+                repeated Item items = 3;
+                Status status = 2;
+                optional string note = 5;
+                oneof payment {
+                    bytes token = 7;
+                    .shop.v1.Voucher voucher = 6;
+                }
+                map<string, int32> discounts = 11;
+                google.protobuf.Timestamp created = 12;
+            }
+
+            message Voucher { Order.Item item = 1; fixed64 code = 2; }
+
+            service Shop {
+                rpc Place(Order) returns (Order) { option idempotency_level = IDEMPOTENT; }
+            }
+            "#,
+        )
+        .unwrap()
+        .try_resolve()
+        .unwrap();
+
+        let (file_name, content) = Asn1DefGenerator::generate_file(&model).unwrap();
+        assert_eq!("ShopV1.asn1", file_name);
+        assert_eq!(
+            r#"ShopV1 DEFINITIONS AUTOMATIC TAGS ::=
+BEGIN
+
+IMPORTS
+    Timestamp FROM GoogleProtobuf
+    ;
+
+Order ::= SEQUENCE {
+    order-id INTEGER (-9223372036854775808..9223372036854775807),
+    status OrderStatus,
+    items SEQUENCE OF OrderItem,
+    note UTF8String OPTIONAL,
+    payment CHOICE {
+        voucher Voucher,
+        token OCTET STRING
+    } OPTIONAL,
+    discounts SEQUENCE OF SEQUENCE {
+        key UTF8String,
+        value INTEGER (-2147483648..2147483647)
+    },
+    created Timestamp OPTIONAL
+}
+
+OrderStatus ::= ENUMERATED {
+    unknown(0),
+    shipped(1),
+    delivered(2)
+}
+
+OrderItem ::= SEQUENCE {
+    sku UTF8String,
+    count INTEGER (0..4294967295)
+}
+
+Voucher ::= SEQUENCE {
+    item OrderItem OPTIONAL,
+    code INTEGER (0..MAX)
+}
+
+END
+"#,
+            content
+        );
+    }
+
+    #[test]
+    fn test_unsupported_input() {
+        assert_eq!(
+            Err(Error::UnsupportedSyntax("proto2".to_string())),
+            import("syntax = \"proto2\"; message A { optional int32 a = 1; }").map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::UnsupportedSyntax("proto2".to_string())),
+            import("message A { int32 a = 1; }").map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::UnsupportedType {
+                line: 2,
+                name: "double".to_string()
+            }),
+            import("syntax = \"proto3\";\nmessage A { double a = 1; }").map(|_| ())
+        );
+        assert_eq!(
+            Err(Error::UndefinedType {
+                line: 1,
+                name: "Missing".to_string()
+            }),
+            import("syntax = \"proto3\"; message A { Missing a = 1; }").map(|_| ())
+        );
+    }
+}
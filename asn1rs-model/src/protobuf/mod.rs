@@ -1,5 +1,9 @@
+pub mod import;
+pub mod numbers;
+pub mod services;
+
 use crate::asn::{Charset, Range, Size};
-use crate::model::{Definition, Model, Target};
+use crate::model::{Definition, Docs, Model, Target};
 use crate::rust::{
     rust_module_name, rust_struct_or_enum_name, rust_variant_name, EncodingOrdering, Rust, RustType,
 };
@@ -12,10 +16,10 @@ const DATAENUM_VARIABLE_NAME_REPLACEMENT: &str = "value";
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub enum ProtobufType {
     Bool,
-    #[allow(dead_code)]
     SFixed32,
-    #[allow(dead_code)]
     SFixed64,
+    Fixed32,
+    Fixed64,
     UInt32,
     UInt64,
     SInt32,
@@ -24,6 +28,9 @@ pub enum ProtobufType {
     Bytes,
     BitsReprByBytesAndBitsLen,
     Repeated(Box<ProtobufType>),
+    /// A `map<K, V>`, which is encoded like a repeated message with the key as field 1 and
+    /// the value as field 2
+    Map(Box<ProtobufType>, Box<ProtobufType>),
     OneOf(Vec<(String, ProtobufType)>),
     /// Indicates a complex, custom type that is
     /// not one of rusts known types
@@ -39,18 +46,21 @@ impl ProtobufType {
         #[allow(clippy::match_same_arms)] // to have the same order as the original enum
         match self {
             ProtobufType::Bool => RustType::Bool,
-            ProtobufType::SFixed32 => RustType::I32(Range::inclusive(0, i32::MAX)),
-            ProtobufType::SFixed64 => RustType::I64(Range::inclusive(0, i64::MAX)),
+            ProtobufType::SFixed32 => RustType::I32(Range::inclusive(i32::MIN, i32::MAX)),
+            ProtobufType::SFixed64 => RustType::I64(Range::inclusive(i64::MIN, i64::MAX)),
+            ProtobufType::Fixed32 => RustType::U32(Range::inclusive(0, u32::MAX)),
+            ProtobufType::Fixed64 => RustType::U64(Range::none()),
             ProtobufType::UInt32 => RustType::U32(Range::inclusive(0, u32::MAX)),
             ProtobufType::UInt64 => RustType::U64(Range::none()),
-            ProtobufType::SInt32 => RustType::I32(Range::inclusive(0, i32::MAX)),
-            ProtobufType::SInt64 => RustType::I64(Range::inclusive(0, i64::MAX)),
-            ProtobufType::String => RustType::String(Size::Any, Charset::Utf8),
+            ProtobufType::SInt32 => RustType::I32(Range::inclusive(i32::MIN, i32::MAX)),
+            ProtobufType::SInt64 => RustType::I64(Range::inclusive(i64::MIN, i64::MAX)),
+            ProtobufType::String => RustType::String(Size::Any, Charset::Utf8, None),
             ProtobufType::Bytes => RustType::VecU8(Size::Any),
             ProtobufType::BitsReprByBytesAndBitsLen => RustType::BitVec(Size::Any),
             ProtobufType::Repeated(inner) => {
                 RustType::Vec(Box::new(inner.to_rust()), Size::Any, EncodingOrdering::Keep)
             }
+            ProtobufType::Map(..) => panic!("ProtobufType::Map cannot be mapped to a RustType"),
             ProtobufType::OneOf(_) => panic!("ProtobufType::OneOf cannot be mapped to a RustType"),
             ProtobufType::Complex(name) => RustType::Complex(name.clone(), None),
         }
@@ -62,6 +72,8 @@ impl ProtobufType {
             ProtobufType::Bool => true,
             ProtobufType::SFixed32 => true,
             ProtobufType::SFixed64 => true,
+            ProtobufType::Fixed32 => true,
+            ProtobufType::Fixed64 => true,
             ProtobufType::UInt32 => true,
             ProtobufType::UInt64 => true,
             ProtobufType::SInt32 => true,
@@ -71,6 +83,36 @@ impl ProtobufType {
             ProtobufType::OneOf(_) => false,
             ProtobufType::Complex(_) => false,
             ProtobufType::Repeated(_) => false,
+            ProtobufType::Map(..) => false,
+        }
+    }
+
+    /// Whether the type is allowed as key of a `map<K, V>`: any integral or string type
+    pub fn is_map_key(&self) -> bool {
+        matches!(
+            self,
+            ProtobufType::Bool
+                | ProtobufType::SFixed32
+                | ProtobufType::SFixed64
+                | ProtobufType::Fixed32
+                | ProtobufType::Fixed64
+                | ProtobufType::UInt32
+                | ProtobufType::UInt64
+                | ProtobufType::SInt32
+                | ProtobufType::SInt64
+                | ProtobufType::String
+        )
+    }
+
+    /// The constant size wire type for varint encoded integers, also of repeated fields
+    pub fn to_fixed(&self) -> ProtobufType {
+        match self {
+            ProtobufType::UInt32 => ProtobufType::Fixed32,
+            ProtobufType::UInt64 => ProtobufType::Fixed64,
+            ProtobufType::SInt32 => ProtobufType::SFixed32,
+            ProtobufType::SInt64 => ProtobufType::SFixed64,
+            ProtobufType::Repeated(inner) => ProtobufType::Repeated(Box::new(inner.to_fixed())),
+            other => other.clone(),
         }
     }
 }
@@ -81,6 +123,8 @@ impl ToString for ProtobufType {
             ProtobufType::Bool => "bool",
             ProtobufType::SFixed32 => "sfixed32",
             ProtobufType::SFixed64 => "sfixed64",
+            ProtobufType::Fixed32 => "fixed32",
+            ProtobufType::Fixed64 => "fixed64",
             ProtobufType::UInt32 => "uint32",
             ProtobufType::UInt64 => "uint64",
             ProtobufType::SInt32 => "sint32",
@@ -91,6 +135,9 @@ impl ToString for ProtobufType {
             ProtobufType::OneOf(_) => "oneof",
             ProtobufType::Complex(name) => return name.clone(),
             ProtobufType::Repeated(name) => return format!("repeated {}", name.to_string()),
+            ProtobufType::Map(key, value) => {
+                return format!("map<{}, {}>", key.to_string(), value.to_string())
+            }
         }
         .into()
     }
@@ -125,6 +172,7 @@ impl Model<Protobuf> {
             imports: rust_model.imports.clone(),
             definitions: Vec::with_capacity(rust_model.definitions.len()),
             value_references: Vec::default(),
+            docs: Docs::default(),
         };
         for Definition(name, rust) in &rust_model.definitions {
             let proto = Self::definition_to_protobuf(rust);
@@ -142,26 +190,43 @@ impl Model<Protobuf> {
                 tag: _,
                 extension_after: _,
                 ordering: _,
+                protobuf_unknown_fields: _,
             } => {
                 let mut proto_fields = Vec::with_capacity(fields.len());
                 for field in fields.iter() {
+                    let r#type = Self::definition_type_to_protobuf_type(field.r#type());
                     proto_fields.push((
                         proto_field_name(field.name()),
-                        Self::definition_type_to_protobuf_type(field.r#type()),
+                        if field.protobuf_fixed() {
+                            r#type.to_fixed()
+                        } else {
+                            r#type
+                        },
                     ));
                 }
 
                 Protobuf::Message(proto_fields)
             }
             Rust::Enum(r_enum) => {
-                Protobuf::Enum(r_enum.variants().map(|v| proto_variant_name(v)).collect())
+                // the protobuf readers and writers use the enumeration index as number
+                Protobuf::Enum(
+                    r_enum
+                        .variants_by_enumeration_index()
+                        .map(|v| proto_variant_name(v))
+                        .collect(),
+                )
             }
             Rust::DataEnum(enumeration) => {
                 let mut proto_enum = Vec::with_capacity(enumeration.len());
                 for variant in enumeration.variants() {
+                    let r#type = Self::definition_type_to_protobuf_type(variant.r#type());
                     proto_enum.push((
                         proto_field_name(variant.name()),
-                        Self::definition_type_to_protobuf_type(variant.r#type()),
+                        if variant.protobuf_fixed() {
+                            r#type.to_fixed()
+                        } else {
+                            r#type
+                        },
                     ))
                 }
                 Protobuf::Message(vec![(
@@ -188,12 +253,16 @@ impl Model<Protobuf> {
             RustType::I32(_) => ProtobufType::SInt32,
             RustType::U64(_) => ProtobufType::UInt64,
             RustType::I64(_) => ProtobufType::SInt64,
+            // protobuf has no 128 bit integers, the 16 bytes of the big-endian two's complement
+            // are written instead
+            RustType::U128(_) | RustType::I128(_) => ProtobufType::Bytes,
             RustType::String(..) => ProtobufType::String,
-            RustType::VecU8(_) => ProtobufType::Bytes,
+            RustType::VecU8(_) | RustType::Uuid => ProtobufType::Bytes,
             RustType::BitVec(_) => ProtobufType::BitsReprByBytesAndBitsLen,
             RustType::Null => ProtobufType::Bytes,
 
             RustType::Complex(complex, _) => ProtobufType::Complex(complex.clone()),
+            RustType::Box(inner) => Self::definition_type_to_protobuf_type(inner),
 
             RustType::Option(inner) => {
                 // in protobuf everything is optional...
@@ -211,6 +280,93 @@ impl Model<Protobuf> {
     }
 }
 
+/// Encodes the integers of all fields and variants, whose range covers the whole range of a
+/// 32- or 64-bit integer, with the constant size wire types `fixed32`/`fixed64` (or their signed
+/// counterparts). For uniformly distributed values, such as hashes or random identifiers, these
+/// are smaller than the varint encoding.
+pub fn use_fixed_for_full_range_integers(model: &mut Model<Rust>) {
+    fn full_range(rust_type: &RustType) -> bool {
+        match rust_type {
+            RustType::U32(Range(0, u32::MAX, false))
+            | RustType::I32(Range(i32::MIN, i32::MAX, false))
+            | RustType::I64(Range(i64::MIN, i64::MAX, false)) => true,
+            RustType::Option(inner) | RustType::Default(inner, _) | RustType::Vec(inner, ..) => {
+                full_range(inner)
+            }
+            _ => false,
+        }
+    }
+
+    for Definition(_, rust) in &mut model.definitions {
+        match rust {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .filter(|f| full_range(f.r#type()))
+                .for_each(|f| f.set_protobuf_fixed(true)),
+            Rust::DataEnum(data) => data
+                .variants_mut()
+                .filter(|v| full_range(v.r#type()))
+                .for_each(|v| v.set_protobuf_fixed(true)),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+        }
+    }
+}
+
+/// Adds the `unknown_fields` field to all structs, which retains the fields of a protobuf message
+/// that are unknown to this revision of the definition. These are written again when the struct
+/// is serialized, so that a message of a newer revision survives being decoded, modified and
+/// encoded again.
+pub fn preserve_unknown_fields(model: &mut Model<Rust>) {
+    for Definition(_, rust) in &mut model.definitions {
+        rust.set_protobuf_unknown_fields(true);
+    }
+}
+
+/// Replaces the repeated fields of messages, that consist of exactly the fields `key` and
+/// `value`, such as `SEQUENCE OF SEQUENCE { key UTF8String, value INTEGER }`, with a
+/// `map<K, V>`. The encoding of both is the same, the messages of the entries are kept.
+pub fn use_maps(model: &mut Model<Protobuf>) {
+    let entries = model
+        .definitions
+        .iter()
+        .filter_map(|Definition(name, protobuf)| match protobuf {
+            Protobuf::Message(fields) => match &fields[..] {
+                [(key_name, key), (value_name, value)]
+                    if key_name == "key"
+                        && value_name == "value"
+                        && key.is_map_key()
+                        && !matches!(
+                            value,
+                            ProtobufType::Repeated(_)
+                                | ProtobufType::Map(..)
+                                | ProtobufType::OneOf(_)
+                        ) =>
+                {
+                    Some((name.clone(), (key.clone(), value.clone())))
+                }
+                _ => None,
+            },
+            Protobuf::Enum(_) => None,
+        })
+        .collect::<Vec<_>>();
+
+    for Definition(_, protobuf) in &mut model.definitions {
+        if let Protobuf::Message(fields) = protobuf {
+            for (_, r#type) in fields.iter_mut() {
+                if let ProtobufType::Repeated(inner) = r#type {
+                    if let ProtobufType::Complex(name) = inner.as_ref() {
+                        let name = proto_definition_name(name);
+                        if let Some((_, (key, value))) = entries.iter().find(|(n, _)| *n == name) {
+                            *r#type =
+                                ProtobufType::Map(Box::new(key.clone()), Box::new(value.clone()));
+                        }
+                    }
+                }
+            }
+        }
+    }
+}
+
 pub trait ToProtobufModel {
     fn to_protobuf(&self) -> Model<Protobuf>;
 }
@@ -271,6 +427,36 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_negative_ranges_to_zigzag_protobuf() {
+        test_model_definition_conversion(
+            &[Definition(
+                "Signed".into(),
+                Rust::struct_from_fields(vec![
+                    Field::from_name_type("small", RustType::I8(Range::inclusive(-5, 5))),
+                    Field::from_name_type("large", RustType::I64(Range(-5, i64::MAX, true))),
+                    Field::from_name_type("positive", RustType::U16(Range::inclusive(0, 300))),
+                ]),
+            )],
+            &[Definition(
+                "Signed".into(),
+                Protobuf::Message(vec![
+                    ("small".into(), ProtobufType::SInt32),
+                    ("large".into(), ProtobufType::SInt64),
+                    ("positive".into(), ProtobufType::UInt32),
+                ]),
+            )],
+        );
+        assert_eq!(
+            RustType::I32(Range::inclusive(i32::MIN, i32::MAX)),
+            ProtobufType::SInt32.to_rust()
+        );
+        assert_eq!(
+            RustType::I64(Range::inclusive(i64::MIN, i64::MAX)),
+            ProtobufType::SInt64.to_rust()
+        );
+    }
+
     #[test]
     fn test_simple_rust_tuple_to_protobuf() {
         test_model_definition_conversion(
@@ -309,7 +495,7 @@ mod tests {
                 "SuchStruct".into(),
                 Rust::struct_from_fields(vec![Field::from_name_type(
                     "very_optional",
-                    RustType::Option(Box::new(RustType::String(Size::Any, Charset::Utf8))),
+                    RustType::Option(Box::new(RustType::String(Size::Any, Charset::Utf8, None))),
                 )]),
             )],
             &[Definition(
@@ -327,7 +513,7 @@ mod tests {
                 Rust::DataEnum(
                     vec![DataVariant::from_name_type(
                         "MuchVariant",
-                        RustType::String(Size::Any, Charset::Utf8),
+                        RustType::String(Size::Any, Charset::Utf8, None),
                     )]
                     .into(),
                 ),
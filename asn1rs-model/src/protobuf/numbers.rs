@@ -0,0 +1,232 @@
+//! Explicit protobuf field numbers, so that the numbers of existing fields stay stable when
+//! fields are inserted, reordered or removed between revisions of a schema. Without them, the
+//! fields of a SEQUENCE and the alternatives of a CHOICE are numbered by their position.
+//!
+//! The mapping file contains one field number per line in the form `Type.field = number`, where
+//! `Type` and `field` are the ASN.1 names of the type and the identifier of the field or
+//! alternative. Empty lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # inserted `priority` in revision 2, removed `legacyId` in revision 3
+//! Message.id = 1
+//! Message.legacyId = 2
+//! Message.payload = 3
+//! Message.priority = 4
+//! ```
+//!
+//! Fields without a number get the lowest unused number greater than the number of the
+//! previous field. Numbers of fields that no longer exist are not reused, the
+//! [`crate::generate::protobuf::ProtobufDefGenerator`] declares them as `reserved`.
+
+use crate::generate::mapping::{self, PerDefinition};
+use crate::model::{Definition, Model};
+use crate::rust::{rust_field_name, Rust};
+use std::ops::RangeInclusive;
+
+/// The largest field number protobuf allows
+pub const MAX_FIELD_NUMBER: u32 = (1 << 29) - 1;
+/// The field numbers protobuf reserves for its own implementation
+pub const RESERVED_FIELD_NUMBERS: RangeInclusive<u32> = 19_000..=19_999;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line with the given (1-based) number is not of the form `Type.field = number`
+    InvalidLine(usize, String),
+    /// The number on the line with the given (1-based) number is not a valid field number
+    InvalidNumber(usize, u32),
+    /// The number on the line with the given (1-based) number is already used in the same type
+    DuplicateNumber(usize, u32),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidLine(number, line) => write!(
+                f,
+                "Line {number} is not of the form 'Type.field = number': {line}"
+            ),
+            Error::InvalidNumber(number, field_number) => write!(
+                f,
+                "Line {number} assigns {field_number}, which is not a valid protobuf field number"
+            ),
+            Error::DuplicateNumber(number, field_number) => write!(
+                f,
+                "Line {number} assigns {field_number}, which is already assigned in the same type"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct FieldNumbers(PerDefinition<(String, u32)>);
+
+impl FieldNumbers {
+    pub fn parse(mapping: &str) -> Result<Self, Error> {
+        let mut numbers = Self::default();
+        for line in mapping::lines(mapping) {
+            let (definition, field, number) = line
+                .split_member()
+                .and_then(|(definition, field, number)| {
+                    Some((definition, field, number.parse::<u32>().ok()?))
+                })
+                .ok_or_else(|| Error::InvalidLine(line.number, line.content.to_string()))?;
+            if !Self::is_valid(number) {
+                return Err(Error::InvalidNumber(line.number, number));
+            }
+            if numbers.of(definition).iter().any(|(_, n)| *n == number) {
+                return Err(Error::DuplicateNumber(line.number, number));
+            }
+            numbers.add(definition, field, number);
+        }
+        Ok(numbers)
+    }
+
+    /// Whether protobuf allows the given number as field number
+    pub fn is_valid(number: u32) -> bool {
+        (1..=MAX_FIELD_NUMBER).contains(&number) && !RESERVED_FIELD_NUMBERS.contains(&number)
+    }
+
+    pub fn add<D: Into<String>, F: Into<String>>(&mut self, definition: D, field: F, number: u32) {
+        self.0.push(&definition.into(), (field.into(), number));
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    /// The `(field, number)` pairs of the given type
+    pub fn of(&self, definition: &str) -> &[(String, u32)] {
+        self.0.of(definition)
+    }
+
+    /// The field numbers for the given fields or alternatives of a type, in order. The names
+    /// can be ASN.1, rust or protobuf identifiers.
+    pub fn assign<'a>(&self, definition: &str, fields: impl Iterator<Item = &'a str>) -> Vec<u32> {
+        let listed = self.of(definition);
+        let mut used = listed.iter().map(|(_, n)| *n).collect::<Vec<_>>();
+        let mut previous = 0;
+        fields
+            .map(|field| {
+                let field = rust_field_name(field);
+                let number = listed
+                    .iter()
+                    .find(|(name, _)| rust_field_name(name) == field)
+                    .map(|(_, number)| *number)
+                    .unwrap_or_else(|| {
+                        let number = (previous + 1..)
+                            .find(|n| Self::is_valid(*n) && !used.contains(n))
+                            .unwrap_or(previous + 1);
+                        used.push(number);
+                        number
+                    });
+                previous = number;
+                number
+            })
+            .collect()
+    }
+
+    /// The `(field, number)` pairs of the given type, whose field no longer exists
+    pub fn removed<'a>(
+        &self,
+        definition: &str,
+        fields: impl Iterator<Item = &'a str> + Clone,
+    ) -> Vec<(String, u32)> {
+        self.of(definition)
+            .iter()
+            .filter(|(name, _)| {
+                let name = rust_field_name(name);
+                !fields.clone().any(|field| rust_field_name(field) == name)
+            })
+            .cloned()
+            .collect()
+    }
+
+    /// Assigns the field numbers to the fields of the structs and the variants of the data
+    /// enums of the model, whose type has any field number in the mapping
+    pub fn apply(&self, model: &mut Model<Rust>) {
+        for Definition(name, rust) in &mut model.definitions {
+            if self.of(name).is_empty() {
+                continue;
+            }
+            let numbers = match rust {
+                Rust::Struct { fields, .. } => self.assign(name, fields.iter().map(|f| f.name())),
+                Rust::DataEnum(data) => self.assign(name, data.variants().map(|v| v.name())),
+                Rust::Enum(_) | Rust::TupleStruct { .. } => continue,
+            };
+            rust.set_protobuf_numbers(&numbers);
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse() {
+        let numbers = FieldNumbers::parse(
+            r"
+            # comment
+            Message.msgId = 3
+            Message.payload=7
+            Status.offline = 1
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            &[("msgId".to_string(), 3), ("payload".to_string(), 7)],
+            numbers.of("Message")
+        );
+        assert_eq!(&[("offline".to_string(), 1)], numbers.of("Status"));
+        assert!(numbers.of("Unknown").is_empty());
+    }
+
+    #[test]
+    pub fn test_parse_invalid_lines() {
+        assert_eq!(
+            Err(Error::InvalidLine(2, "Message.msgId = three".to_string())),
+            FieldNumbers::parse("\nMessage.msgId = three")
+        );
+        assert_eq!(
+            Err(Error::InvalidNumber(1, 0)),
+            FieldNumbers::parse("Message.msgId = 0")
+        );
+        assert_eq!(
+            Err(Error::InvalidNumber(1, 19_500)),
+            FieldNumbers::parse("Message.msgId = 19500")
+        );
+        assert_eq!(
+            Err(Error::DuplicateNumber(2, 3)),
+            FieldNumbers::parse("Message.msgId = 3\nMessage.payload = 3")
+        );
+    }
+
+    #[test]
+    pub fn test_assign_skips_listed_and_removed_numbers() {
+        let numbers = FieldNumbers::parse(
+            r"
+            Message.id = 1
+            Message.legacyId = 2
+            Message.payload = 5
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            vec![1, 3, 5, 6],
+            numbers.assign(
+                "Message",
+                ["id", "priority", "payload", "trailer"].iter().copied()
+            )
+        );
+        assert_eq!(
+            vec![1, 2, 3],
+            numbers.assign("Unknown", ["a", "b", "c"].iter().copied())
+        );
+        assert_eq!(
+            vec![("legacyId".to_string(), 2)],
+            numbers.removed("Message", ["id", "priority", "payload"].iter().copied())
+        );
+    }
+}
@@ -0,0 +1,208 @@
+//! gRPC services with request/response pairs of the generated types as unary methods. The
+//! [`crate::generate::protobuf::ProtobufDefGenerator`] declares them as `service` in the
+//! `.proto` files and the [`crate::generate::RustCodeGenerator`] generates a service trait, a
+//! client stub and a server dispatching the calls to the trait (see `asn1rs::grpc`).
+//!
+//! The mapping file contains one method per line in the form
+//! `Service.Method = Request -> Response`, where `Request` and `Response` are the ASN.1 names of
+//! the types. Empty lines and lines starting with `#` are ignored:
+//!
+//! ```text
+//! # the telemetry of the vehicles
+//! Telemetry.Report = ReportRequest -> ReportResponse
+//! Telemetry.Query = QueryRequest -> QueryResponse
+//! ```
+//!
+//! A service is declared in the module, that defines the request type of its first method. The
+//! other types must be defined in or imported by the same module. Because protobuf declares
+//! ENUMERATED types as `enum` instead of as `message`, they cannot be requests or responses.
+
+use crate::generate::mapping;
+use crate::model::{Definition, Model, Target};
+use crate::rust::rust_struct_or_enum_name;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// The line with the given (1-based) number is not of the form
+    /// `Service.Method = Request -> Response`
+    InvalidLine(usize, String),
+    /// The method on the line with the given (1-based) number is already declared
+    DuplicateMethod(usize, String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidLine(number, line) => write!(
+                f,
+                "Line {number} is not of the form 'Service.Method = Request -> Response': {line}"
+            ),
+            Error::DuplicateMethod(number, method) => write!(
+                f,
+                "Line {number} declares the method {method}, which is already declared"
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Method {
+    pub name: String,
+    /// The name of the generated request type
+    pub request: String,
+    /// The name of the generated response type
+    pub response: String,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Service {
+    pub name: String,
+    pub methods: Vec<Method>,
+}
+
+impl Service {
+    /// The path of the HTTP/2 request of the method, for example `/telemetry.Telemetry/Report`
+    pub fn path(&self, package: &str, method: &Method) -> String {
+        format!("/{}.{}/{}", package, self.name, method.name)
+    }
+}
+
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Services(Vec<Service>);
+
+impl Services {
+    pub fn parse(mapping: &str) -> Result<Self, Error> {
+        let mut services = Self::default();
+        for line in mapping::lines(mapping) {
+            let (service, method, request, response) = line
+                .split_member()
+                .and_then(|(service, method, types)| {
+                    let (request, response) = types.split_once("->")?;
+                    let (request, response) = (request.trim(), response.trim());
+                    (mapping::is_identifier(request) && mapping::is_identifier(response))
+                        .then_some((service, method, request, response))
+                })
+                .ok_or_else(|| Error::InvalidLine(line.number, line.content.to_string()))?;
+            if services
+                .of(service)
+                .is_some_and(|s| s.methods.iter().any(|m| m.name == method))
+            {
+                return Err(Error::DuplicateMethod(
+                    line.number,
+                    format!("{service}.{method}"),
+                ));
+            }
+            services.add(service, method, request, response);
+        }
+        Ok(services)
+    }
+
+    pub fn add<S: Into<String>, M: Into<String>>(
+        &mut self,
+        service: S,
+        method: M,
+        request: &str,
+        response: &str,
+    ) {
+        let service = service.into();
+        let method = Method {
+            name: method.into(),
+            request: rust_struct_or_enum_name(request),
+            response: rust_struct_or_enum_name(response),
+        };
+        match self.0.iter_mut().find(|s| s.name == service) {
+            Some(service) => service.methods.push(method),
+            None => self.0.push(Service {
+                name: service,
+                methods: vec![method],
+            }),
+        }
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &Service> {
+        self.0.iter()
+    }
+
+    pub fn of(&self, service: &str) -> Option<&Service> {
+        self.0.iter().find(|s| s.name == service)
+    }
+
+    /// The services that are declared in the given model, because it defines the request type
+    /// of their first method
+    pub fn declared_in<'a, T: Target>(
+        &'a self,
+        model: &'a Model<T>,
+    ) -> impl Iterator<Item = &'a Service> {
+        self.0.iter().filter(move |service| {
+            service.methods.first().is_some_and(|method| {
+                model
+                    .definitions
+                    .iter()
+                    .any(|Definition(name, _)| *name == method.request)
+            })
+        })
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse() {
+        let services = Services::parse(
+            r"
+            # comment
+            Telemetry.Report = ReportRequest -> ReportResponse
+            Telemetry.Query=query-request->QueryResponse
+            Admin.Reset = Reset -> Reset
+            ",
+        )
+        .unwrap();
+        assert_eq!(
+            vec!["Telemetry", "Admin"],
+            services.iter().map(|s| s.name.as_str()).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            &[
+                Method {
+                    name: "Report".to_string(),
+                    request: "ReportRequest".to_string(),
+                    response: "ReportResponse".to_string(),
+                },
+                Method {
+                    name: "Query".to_string(),
+                    request: "QueryRequest".to_string(),
+                    response: "QueryResponse".to_string(),
+                }
+            ],
+            &services.of("Telemetry").unwrap().methods[..]
+        );
+        assert!(services.of("Unknown").is_none());
+    }
+
+    #[test]
+    pub fn test_parse_invalid_lines() {
+        assert_eq!(
+            Err(Error::InvalidLine(2, "Telemetry.Report".to_string())),
+            Services::parse("\nTelemetry.Report")
+        );
+        assert_eq!(
+            Err(Error::InvalidLine(
+                1,
+                "Telemetry.Report = Request -> stream Response".to_string()
+            )),
+            Services::parse("Telemetry.Report = Request -> stream Response")
+        );
+        assert_eq!(
+            Err(Error::DuplicateMethod(2, "Telemetry.Report".to_string())),
+            Services::parse("Telemetry.Report = A -> B\nTelemetry.Report = C -> D")
+        );
+    }
+}
@@ -11,7 +11,7 @@ pub trait ResolveState: Clone {
 pub struct Resolved;
 impl ResolveState for Resolved {
     type SizeType = usize;
-    type RangeType = i64;
+    type RangeType = i128;
     type ConstType = LiteralValue;
 }
 
@@ -19,7 +19,7 @@ impl ResolveState for Resolved {
 pub struct Unresolved;
 impl ResolveState for Unresolved {
     type SizeType = LitOrRef<usize>;
-    type RangeType = LitOrRef<i64>;
+    type RangeType = LitOrRef<i128>;
     type ConstType = LitOrRef<LiteralValue>;
 }
 
@@ -55,6 +55,12 @@ pub enum Error {
     FailedToResolveType(String),
     FailedToResolveReference(String),
     FailedToParseLiteral(String),
+    /// The module (first) imported by the other module (second) is not known
+    UnknownModule(String, String),
+    /// The item (first) is neither defined in nor imported by the module (second) it is imported from
+    UnknownImport(String, String),
+    /// The type (first) is neither defined in nor imported by the module (second) referring to it
+    UnknownTypeReference(String, String),
 }
 
 impl std::error::Error for Error {}
@@ -70,6 +76,23 @@ impl std::fmt::Display for Error {
             Error::FailedToParseLiteral(literal) => {
                 write!(f, "Failed to parse literal: {}", literal)
             }
+            Error::UnknownModule(module, importer) => {
+                write!(
+                    f,
+                    "Module {} imported by {} is not loaded",
+                    module, importer
+                )
+            }
+            Error::UnknownImport(name, module) => {
+                write!(f, "Module {} does not define the imported {}", module, name)
+            }
+            Error::UnknownTypeReference(name, module) => {
+                write!(
+                    f,
+                    "Type {} is neither defined in nor imported by {}",
+                    name, module
+                )
+            }
         }
     }
 }
@@ -1,14 +1,15 @@
 use crate::asn::{
     Asn, ComponentTypeList, Range, Size, Tag, TagProperty, TagResolver, Type as AsnType, Type,
 };
-use crate::asn::{Charset, ChoiceVariant, Integer};
+use crate::asn::{Charset, ChoiceVariant, Integer, PermittedAlphabet};
 use crate::model::Import;
 use crate::model::Model;
 use crate::model::ValueReference;
-use crate::model::{Definition, LiteralValue, Target};
+use crate::model::{Definition, Docs, LiteralValue, Target};
 use crate::resolve::{ResolveState, Resolved};
 use crate::rust::Field as RustField;
 use std::borrow::Cow;
+use std::convert::TryFrom;
 
 const I8_MAX: i64 = i8::MAX as i64;
 const I16_MAX: i64 = i16::MAX as i64;
@@ -20,6 +21,9 @@ const U16_MAX: u64 = u16::MAX as u64;
 const U32_MAX: u64 = u32::MAX as u64;
 //const U64_MAX: u64 = u64::MAX as u64;
 
+/// The size of the OCTET STRINGs that are represented as [`RustType::Uuid`]
+pub const UUID_SIZE: Size = Size::Fix(16, false);
+
 pub type PlainVariant = String;
 pub type PlainEnum = Enumeration<PlainVariant>;
 pub type DataEnum = Enumeration<DataVariant>;
@@ -40,14 +44,24 @@ pub enum RustType {
     U32(Range<u32>),
     I64(Range<i64>),
     U64(Range<Option<u64>>),
-    String(Size, Charset),
+    /// An INTEGER with a bound exceeding the 64 bit types, `None` for an absent bound
+    I128(Range<Option<i128>>),
+    /// An INTEGER with a bound exceeding the 64 bit types, `None` for an absent bound
+    U128(Range<Option<u128>>),
+    String(Size, Charset, Option<PermittedAlphabet>),
     VecU8(Size),
+    /// An OCTET STRING (SIZE(16)) represented as `uuid::Uuid`, see
+    /// [`use_uuid_for_16_byte_octet_strings`]
+    Uuid,
     BitVec(Size),
     Vec(Box<RustType>, Size, EncodingOrdering),
     Null,
 
     Option(Box<RustType>),
     Default(Box<RustType>, LiteralValue),
+    /// A [`RustType::Complex`] on the heap, that breaks the cycle of a recursive type, see
+    /// [`box_recursive_types`]
+    Box(Box<RustType>),
 
     /// Indicates a complex, custom type that is
     /// not one of rusts known types. This can be
@@ -57,9 +71,39 @@ pub enum RustType {
 }
 
 impl RustType {
+    /// Replaces an OCTET STRING (SIZE(16)), also if optional or within a list, with
+    /// [`RustType::Uuid`]
+    fn replace_with_uuid(&mut self) {
+        match self {
+            RustType::VecU8(size) if *size == UUID_SIZE => *self = RustType::Uuid,
+            RustType::Option(inner) | RustType::Vec(inner, ..) => inner.replace_with_uuid(),
+            _ => {}
+        }
+    }
+
+    /// Replaces a [`RustType::Complex`], also if optional, with a [`RustType::Box`] of it
+    fn replace_with_box(&mut self) {
+        match self {
+            RustType::Complex(..) => *self = RustType::Box(Box::new(self.clone())),
+            RustType::Option(inner) => inner.replace_with_box(),
+            _ => {}
+        }
+    }
+
+    /// The name of the referred type, if it is stored inline and not already on the heap
+    fn inline_reference(&self) -> Option<&str> {
+        match self {
+            RustType::Complex(name, _) => Some(name),
+            RustType::Option(inner) => inner.inline_reference(),
+            _ => None,
+        }
+    }
+
     pub fn as_inner_type(&self) -> &RustType {
-        if let RustType::Vec(inner, ..) | RustType::Option(inner) | RustType::Default(inner, ..) =
-            self
+        if let RustType::Vec(inner, ..)
+        | RustType::Option(inner)
+        | RustType::Default(inner, ..)
+        | RustType::Box(inner) = self
         {
             inner.as_inner_type()
         } else {
@@ -68,8 +112,10 @@ impl RustType {
     }
 
     pub fn into_inner_type(self) -> RustType {
-        if let RustType::Vec(inner, ..) | RustType::Option(inner) | RustType::Default(inner, ..) =
-            self
+        if let RustType::Vec(inner, ..)
+        | RustType::Option(inner)
+        | RustType::Default(inner, ..)
+        | RustType::Box(inner) = self
         {
             inner.into_inner_type()
         } else {
@@ -122,7 +168,9 @@ impl RustType {
                 | RustType::U32(_)
                 | RustType::I32(_)
                 | RustType::U64(_)
-                | RustType::I64(_),
+                | RustType::I64(_)
+                | RustType::U128(_)
+                | RustType::I128(_),
         ) || matches!(self, RustType::Default(inner, ..) if inner.is_primitive())
     }
 
@@ -156,13 +204,25 @@ impl RustType {
             RustType::I64(Range(min, max, extensible)) => {
                 Some(Range(min.to_string(), max.to_string(), *extensible))
             }
+            RustType::I128(Range(min, max, extensible)) => Some(Range(
+                min.unwrap_or(i128::MIN).to_string(),
+                max.unwrap_or(i128::MAX).to_string(),
+                *extensible,
+            )),
+            RustType::U128(Range(min, max, extensible)) => Some(Range(
+                min.unwrap_or_default().to_string(),
+                max.unwrap_or(i128::MAX as u128).to_string(),
+                *extensible,
+            )),
             RustType::String(..) => None,
             RustType::VecU8(_) => None,
+            RustType::Uuid => None,
             RustType::BitVec(_) => None,
             RustType::Vec(inner, _size, _ordering) => inner.integer_range_str(),
             RustType::Null => None,
             RustType::Option(inner) => inner.integer_range_str(),
             RustType::Default(inner, ..) => inner.integer_range_str(),
+            RustType::Box(_) => None,
             RustType::Complex(_, _) => None,
         }
     }
@@ -171,45 +231,54 @@ impl RustType {
         match self {
             RustType::Bool => AsnType::Boolean,
             RustType::I8(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
-                Some(i64::from(min)),
-                Some(i64::from(max)),
+                Some(i128::from(min)),
+                Some(i128::from(max)),
                 extensible,
             )),
             RustType::U8(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
-                Some(i64::from(min)),
-                Some(i64::from(max)),
+                Some(i128::from(min)),
+                Some(i128::from(max)),
                 extensible,
             )),
             RustType::I16(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
-                Some(i64::from(min)),
-                Some(i64::from(max)),
+                Some(i128::from(min)),
+                Some(i128::from(max)),
                 extensible,
             )),
             RustType::U16(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
-                Some(i64::from(min)),
-                Some(i64::from(max)),
+                Some(i128::from(min)),
+                Some(i128::from(max)),
                 extensible,
             )),
             RustType::I32(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
-                Some(i64::from(min)),
-                Some(i64::from(max)),
+                Some(i128::from(min)),
+                Some(i128::from(max)),
                 extensible,
             )),
             RustType::U32(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
-                Some(i64::from(min)),
-                Some(i64::from(max)),
+                Some(i128::from(min)),
+                Some(i128::from(max)),
+                extensible,
+            )),
+            RustType::I64(Range(min, max, extensible)) => AsnType::integer_with_range(Range(
+                Some(i128::from(min)),
+                Some(i128::from(max)),
                 extensible,
             )),
-            RustType::I64(Range(min, max, extensible)) => {
-                AsnType::integer_with_range(Range(Some(min), Some(max), extensible))
-            }
             RustType::U64(range) => AsnType::integer_with_range(Range(
-                range.min().map(|v| v as i64),
-                range.max().map(|v| v as i64),
+                range.min().map(i128::from),
+                range.max().map(i128::from),
+                range.extensible(),
+            )),
+            RustType::I128(range) => AsnType::integer_with_range(range),
+            RustType::U128(range) => AsnType::integer_with_range(Range(
+                range.min().map(|v| v as i128),
+                range.max().map(|v| v as i128),
                 range.extensible(),
             )),
-            RustType::String(size, charset) => AsnType::String(size, charset),
+            RustType::String(size, charset, alphabet) => AsnType::String(size, charset, alphabet),
             RustType::VecU8(size) => AsnType::OctetString(size),
+            RustType::Uuid => AsnType::OctetString(UUID_SIZE),
             RustType::BitVec(size) => AsnType::bit_vec_with_size(size),
             RustType::Vec(inner, size, EncodingOrdering::Keep) => {
                 AsnType::SequenceOf(Box::new(inner.into_asn()), size)
@@ -222,6 +291,7 @@ impl RustType {
             RustType::Default(value, default) => {
                 AsnType::Default(Box::new(value.into_asn()), default)
             }
+            RustType::Box(inner) => inner.into_asn(),
             RustType::Complex(name, tag) => AsnType::TypeReference(name, tag),
         }
     }
@@ -237,8 +307,11 @@ impl RustType {
             RustType::I32(_) => matches!(other, RustType::I32(_)),
             RustType::U64(_) => matches!(other, RustType::U64(_)),
             RustType::I64(_) => matches!(other, RustType::I64(_)),
+            RustType::U128(_) => matches!(other, RustType::U128(_)),
+            RustType::I128(_) => matches!(other, RustType::I128(_)),
             RustType::String(..) => matches!(other, RustType::String(..)),
             RustType::VecU8(_) => matches!(other, RustType::VecU8(_)),
+            RustType::Uuid => RustType::Uuid == *other,
             RustType::BitVec(_) => matches!(other, RustType::BitVec(_)),
             RustType::Vec(inner_a, _size, _ordering) => {
                 if let RustType::Vec(inner_b, _other_size, _ordering) = other {
@@ -257,6 +330,9 @@ impl RustType {
                     || matches!(other, RustType::Default(o, ..) if o.similar(inner))
                     || matches!(other, RustType::Option(o, ..) if o.similar(inner))
             }
+            RustType::Box(inner) => {
+                inner.similar(other) || matches!(other, RustType::Box(o) if o.similar(inner))
+            }
             RustType::Complex(inner_a, _tag) => {
                 if let RustType::Complex(inner_b, _tag) = other {
                     inner_a.eq(inner_b)
@@ -278,15 +354,18 @@ impl RustType {
             | RustType::I32(_)
             | RustType::U32(_)
             | RustType::I64(_)
-            | RustType::U64(_) => Tag::DEFAULT_INTEGER,
+            | RustType::U64(_)
+            | RustType::I128(_)
+            | RustType::U128(_) => Tag::DEFAULT_INTEGER,
             RustType::BitVec(_) => Tag::DEFAULT_BIT_STRING,
-            RustType::VecU8(_) => Tag::DEFAULT_OCTET_STRING,
-            RustType::String(_, charset) => charset.default_tag(),
+            RustType::VecU8(_) | RustType::Uuid => Tag::DEFAULT_OCTET_STRING,
+            RustType::String(_, charset, _) => charset.default_tag(),
             RustType::Vec(_, _, EncodingOrdering::Keep) => Tag::DEFAULT_SEQUENCE_OF,
             RustType::Vec(_, _, EncodingOrdering::Sort) => Tag::DEFAULT_SET_OF,
             RustType::Null => Tag::DEFAULT_NULL,
             RustType::Option(inner) => return inner.tag(),
             RustType::Default(inner, ..) => return inner.tag(),
+            RustType::Box(inner) => return inner.tag(),
             // TODO this is wrong. This should resolve the tag from the referenced type instead, but atm the infrastructure is missing to do such a thing, see github#13
             RustType::Complex(_, tag) => return *tag,
         })
@@ -308,6 +387,9 @@ pub enum Rust {
         fields: Vec<Field>,
         tag: Option<Tag>,
         extension_after: Option<usize>,
+        /// Whether the struct has an `unknown_fields` field, that retains the fields of a
+        /// protobuf message, which are unknown to this revision of the definition
+        protobuf_unknown_fields: bool,
     },
     Enum(PlainEnum),
     DataEnum(DataEnum),
@@ -329,6 +411,7 @@ impl Rust {
             fields,
             tag: None,
             extension_after: None,
+            protobuf_unknown_fields: false,
         }
     }
 
@@ -339,6 +422,191 @@ impl Rust {
             constants: Vec::default(),
         }
     }
+
+    /// The protobuf field numbers of the fields of a struct or the variants of a data enum, or
+    /// `None` if all of them are numbered by their position, starting at 1
+    pub fn protobuf_numbers(&self) -> Option<Vec<u32>> {
+        let numbers = match self {
+            Rust::Struct { fields, .. } => fields.iter().map(Field::protobuf_number).collect(),
+            Rust::DataEnum(data) => data.variants().map(DataVariant::protobuf_number).collect(),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => Vec::new(),
+        }
+        .into_iter()
+        .enumerate()
+        .map(|(index, number)| number.unwrap_or(index as u32 + 1))
+        .collect::<Vec<_>>();
+        let positional = numbers
+            .iter()
+            .enumerate()
+            .all(|(index, number)| *number == index as u32 + 1);
+        Some(numbers).filter(|_| !positional)
+    }
+
+    /// Assigns the given protobuf field numbers to the fields of a struct or the variants of a
+    /// data enum, in order
+    pub fn set_protobuf_numbers(&mut self, numbers: &[u32]) {
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .zip(numbers)
+                .for_each(|(field, number)| field.set_protobuf_number(Some(*number))),
+            Rust::DataEnum(data) => data
+                .variants_mut()
+                .zip(numbers)
+                .for_each(|(variant, number)| variant.set_protobuf_number(Some(*number))),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+        }
+    }
+
+    /// The names of the fields of a struct or the variants of a data enum, whose integers are
+    /// encoded as protobuf `fixed32`/`fixed64` (or their signed counterparts)
+    pub fn protobuf_fixed(&self) -> Vec<&str> {
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter()
+                .filter(|f| f.protobuf_fixed())
+                .map(Field::name)
+                .collect(),
+            Rust::DataEnum(data) => data
+                .variants()
+                .filter(|v| v.protobuf_fixed())
+                .map(DataVariant::name)
+                .collect(),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => Vec::new(),
+        }
+    }
+
+    /// Encodes the integers of the fields of a struct or the variants of a data enum with the
+    /// given names as protobuf `fixed32`/`fixed64` (or their signed counterparts)
+    pub fn set_protobuf_fixed<T: AsRef<str>>(&mut self, names: &[T]) {
+        let fixed = |name: &str| names.iter().any(|n| n.as_ref() == name);
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .filter(|f| fixed(f.name()))
+                .for_each(|f| f.set_protobuf_fixed(true)),
+            Rust::DataEnum(data) => data
+                .variants_mut()
+                .filter(|v| fixed(v.name()))
+                .for_each(|v| v.set_protobuf_fixed(true)),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+        }
+    }
+
+    /// The names of the fields of a struct or the variants of a data enum, whose OCTET STRING
+    /// (SIZE(16)) is represented as `uuid::Uuid`
+    pub fn uuid(&self) -> Vec<&str> {
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter()
+                .filter(|f| *f.r#type().as_inner_type() == RustType::Uuid)
+                .map(Field::name)
+                .collect(),
+            Rust::DataEnum(data) => data
+                .variants()
+                .filter(|v| *v.r#type().as_inner_type() == RustType::Uuid)
+                .map(DataVariant::name)
+                .collect(),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => Vec::new(),
+        }
+    }
+
+    /// Represents the OCTET STRING (SIZE(16)) of the fields of a struct or the variants of a data
+    /// enum with the given names as `uuid::Uuid`, other types are not affected
+    pub fn set_uuid<T: AsRef<str>>(&mut self, names: &[T]) {
+        let uuid = |name: &str| names.iter().any(|n| n.as_ref() == name);
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .filter(|f| uuid(f.name()))
+                .for_each(|f| f.name_type.1.replace_with_uuid()),
+            Rust::DataEnum(data) => data
+                .variants_mut()
+                .filter(|v| uuid(v.name()))
+                .for_each(|v| v.name_type.1.replace_with_uuid()),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+        }
+    }
+
+    /// The names of the fields of a struct or the variants of a data enum, whose referred type is
+    /// boxed
+    pub fn boxed(&self) -> Vec<&str> {
+        let boxed = |r#type: &RustType| matches!(r#type.as_no_option(), RustType::Box(_));
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter()
+                .filter(|f| boxed(f.r#type()))
+                .map(Field::name)
+                .collect(),
+            Rust::DataEnum(data) => data
+                .variants()
+                .filter(|v| boxed(v.r#type()))
+                .map(DataVariant::name)
+                .collect(),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => Vec::new(),
+        }
+    }
+
+    /// Boxes the referred type, also if optional, of the fields of a struct or the variants of a
+    /// data enum with the given names, other types are not affected
+    pub fn set_boxed<T: AsRef<str>>(&mut self, names: &[T]) {
+        let boxed = |name: &str| names.iter().any(|n| n.as_ref() == name);
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .filter(|f| boxed(f.name()))
+                .for_each(|f| f.name_type.1.replace_with_box()),
+            Rust::DataEnum(data) => data
+                .variants_mut()
+                .filter(|v| boxed(v.name()))
+                .for_each(|v| v.name_type.1.replace_with_box()),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+        }
+    }
+
+    /// The types this type stores inline, with the names of the fields or variants referring to
+    /// them (`None` for the type of a tuple struct)
+    fn inline_references(&self) -> Vec<(Option<&str>, &str)> {
+        match self {
+            Rust::Struct { fields, .. } => fields
+                .iter()
+                .filter_map(|f| Some((Some(f.name()), f.r#type().inline_reference()?)))
+                .collect(),
+            Rust::DataEnum(data) => data
+                .variants()
+                .filter_map(|v| Some((Some(v.name()), v.r#type().inline_reference()?)))
+                .collect(),
+            Rust::TupleStruct { r#type, .. } => r#type
+                .inline_reference()
+                .map(|r| (None, r))
+                .into_iter()
+                .collect(),
+            Rust::Enum(_) => Vec::new(),
+        }
+    }
+
+    /// Whether this is a struct with an `unknown_fields` field, that retains the unknown fields
+    /// of a protobuf message
+    pub fn protobuf_unknown_fields(&self) -> bool {
+        matches!(
+            self,
+            Rust::Struct {
+                protobuf_unknown_fields: true,
+                ..
+            }
+        )
+    }
+
+    /// Adds (or removes) the `unknown_fields` field to a struct, other types are not affected
+    pub fn set_protobuf_unknown_fields(&mut self, unknown_fields: bool) {
+        if let Rust::Struct {
+            protobuf_unknown_fields,
+            ..
+        } = self
+        {
+            *protobuf_unknown_fields = unknown_fields;
+        }
+    }
 }
 
 impl Target for Rust {
@@ -390,8 +658,10 @@ impl RustType {
             RustType::I32(_) => "i32",
             RustType::U64(_) => "u64",
             RustType::I64(_) => "i64",
+            RustType::U128(_) => "u128",
+            RustType::I128(_) => "i128",
             RustType::String(..) => "&'static str",
-            RustType::VecU8(_) => "&'static [u8]",
+            RustType::VecU8(_) | RustType::Uuid => "&'static [u8]",
             RustType::BitVec(_) => "u64",
             RustType::Vec(inner, _size, _ordering) => {
                 return Cow::Owned(format!("&'static [{}]", inner.to_const_lit_string()))
@@ -401,6 +671,7 @@ impl RustType {
                 return Cow::Owned(format!("Option<{}>", inner.to_const_lit_string()))
             }
             RustType::Default(inner, ..) => return inner.to_const_lit_string(),
+            RustType::Box(inner) => return inner.to_const_lit_string(),
             RustType::Complex(name, _) => return Cow::Owned(name.clone()),
         })
     }
@@ -418,13 +689,17 @@ impl ToString for RustType {
             RustType::I32(_) => "i32",
             RustType::U64(_) => "u64",
             RustType::I64(_) => "i64",
+            RustType::U128(_) => "u128",
+            RustType::I128(_) => "i128",
             RustType::String(..) => "String",
             RustType::VecU8(_) => "Vec<u8>",
+            RustType::Uuid => "Uuid",
             RustType::BitVec(_) => "BitVec",
             RustType::Vec(inner, _size, _ordering) => return format!("Vec<{}>", inner.to_string()),
             RustType::Null => "Null",
             RustType::Option(inner) => return format!("Option<{}>", inner.to_string()),
             RustType::Default(inner, ..) => return inner.to_string(),
+            RustType::Box(inner) => return format!("Box<{}>", inner.to_string()),
             RustType::Complex(name, _) => return name.clone(),
         }
         .into()
@@ -436,6 +711,8 @@ pub struct Field {
     pub(crate) name_type: (String, RustType),
     pub(crate) tag: Option<Tag>,
     pub(crate) constants: Vec<(String, String)>,
+    pub(crate) protobuf_number: Option<u32>,
+    pub(crate) protobuf_fixed: bool,
 }
 
 impl Field {
@@ -444,6 +721,8 @@ impl Field {
             name_type: (name.to_string(), r#type),
             tag: None,
             constants: Vec::default(),
+            protobuf_number: None,
+            protobuf_fixed: false,
         }
     }
 
@@ -467,6 +746,24 @@ impl Field {
         self.constants = constants;
         self
     }
+
+    /// The protobuf field number, if not numbered by its position
+    pub fn protobuf_number(&self) -> Option<u32> {
+        self.protobuf_number
+    }
+
+    pub fn set_protobuf_number(&mut self, number: Option<u32>) {
+        self.protobuf_number = number;
+    }
+
+    /// Whether the integer is encoded as protobuf `fixed32`/`fixed64` instead of as varint
+    pub fn protobuf_fixed(&self) -> bool {
+        self.protobuf_fixed
+    }
+
+    pub fn set_protobuf_fixed(&mut self, fixed: bool) {
+        self.protobuf_fixed = fixed;
+    }
 }
 
 impl TagProperty for Field {
@@ -483,11 +780,42 @@ impl TagProperty for Field {
     }
 }
 
-#[derive(Debug, Clone, PartialOrd, PartialEq, Eq)]
+#[derive(Debug, Clone)]
 pub struct Enumeration<T> {
     variants: Vec<T>,
     tag: Option<Tag>,
     extended_after_index: Option<usize>,
+    numbers: Option<Vec<usize>>,
+    identifiers: Vec<String>,
+}
+
+/// The ASN.1 identifiers are not compared, they are lost when reparsing the generated code
+impl<T: PartialEq> PartialEq for Enumeration<T> {
+    fn eq(&self, other: &Self) -> bool {
+        self.variants == other.variants
+            && self.tag == other.tag
+            && self.extended_after_index == other.extended_after_index
+            && self.numbers == other.numbers
+    }
+}
+
+impl<T: Eq> Eq for Enumeration<T> {}
+
+impl<T: PartialOrd> PartialOrd for Enumeration<T> {
+    fn partial_cmp(&self, other: &Self) -> Option<std::cmp::Ordering> {
+        (
+            &self.variants,
+            &self.tag,
+            &self.extended_after_index,
+            &self.numbers,
+        )
+            .partial_cmp(&(
+                &other.variants,
+                &other.tag,
+                &other.extended_after_index,
+                &other.numbers,
+            ))
+    }
 }
 
 impl<T> From<Vec<T>> for Enumeration<T> {
@@ -496,6 +824,8 @@ impl<T> From<Vec<T>> for Enumeration<T> {
             variants,
             tag: None,
             extended_after_index: None,
+            numbers: None,
+            identifiers: Vec::new(),
         }
     }
 }
@@ -518,6 +848,10 @@ impl<T> Enumeration<T> {
         self.variants.iter()
     }
 
+    pub fn variants_mut(&mut self) -> impl Iterator<Item = &mut T> {
+        self.variants.iter_mut()
+    }
+
     pub fn extension_after_index(&self) -> Option<usize> {
         self.extended_after_index
     }
@@ -530,6 +864,17 @@ impl<T> Enumeration<T> {
     pub fn is_extensible(&self) -> bool {
         self.extended_after_index.is_some()
     }
+
+    pub fn with_identifiers(mut self, identifiers: Vec<String>) -> Self {
+        self.identifiers = identifiers;
+        self
+    }
+
+    /// The ASN.1 identifier of the variant at the given index, `None` if the enumeration was not
+    /// converted from an ASN.1 definition
+    pub fn identifier(&self, index: usize) -> Option<&str> {
+        self.identifiers.get(index).map(String::as_str)
+    }
 }
 
 impl<T> TagProperty for Enumeration<T> {
@@ -550,12 +895,60 @@ impl PlainEnum {
     pub fn from_names(names: impl Iterator<Item = impl ToString>) -> Self {
         Self::from(names.map(|n| n.to_string()).collect::<Vec<_>>())
     }
+
+    pub fn with_numbers(mut self, numbers: Option<Vec<usize>>) -> Self {
+        self.numbers = numbers;
+        self
+    }
+
+    /// The numbers of the variants, `None` if the ASN.1 definition declares none
+    pub fn numbers(&self) -> Option<&[usize]> {
+        self.numbers.as_deref()
+    }
+
+    /// The enumeration index of the variant at the given index, which is what the encodings
+    /// serialize. The variants of the root and of the extension additions are each ordered by
+    /// their number, see ITU-T X.691 | ISO/IEC 8825-2, 14.1 and 14.3
+    pub fn enumeration_index(&self, index: usize) -> usize {
+        match &self.numbers {
+            None => index,
+            Some(numbers) => {
+                let root_len = self
+                    .extended_after_index
+                    .map_or(numbers.len(), |index| index + 1);
+                let range = if index < root_len {
+                    0..root_len
+                } else {
+                    root_len..numbers.len()
+                };
+                range.start
+                    + numbers[range]
+                        .iter()
+                        .filter(|number| **number < numbers[index])
+                        .count()
+            }
+        }
+    }
+
+    /// The index of the variant with the given enumeration index
+    pub fn variant_index(&self, enumeration_index: usize) -> Option<usize> {
+        (0..self.len()).find(|index| self.enumeration_index(*index) == enumeration_index)
+    }
+
+    /// The variants ordered by their enumeration index
+    pub fn variants_by_enumeration_index(&self) -> impl Iterator<Item = &PlainVariant> {
+        (0..self.len())
+            .filter_map(move |index| self.variant_index(index))
+            .map(move |index| &self.variants[index])
+    }
 }
 
 #[derive(Debug, Clone, PartialOrd, PartialEq)]
 pub struct DataVariant {
     name_type: (String, RustType),
     tag: Option<Tag>,
+    protobuf_number: Option<u32>,
+    protobuf_fixed: bool,
 }
 
 impl DataVariant {
@@ -563,6 +956,8 @@ impl DataVariant {
         Self {
             name_type: (name.to_string(), r#type),
             tag: None,
+            protobuf_number: None,
+            protobuf_fixed: false,
         }
     }
 
@@ -577,6 +972,24 @@ impl DataVariant {
     pub fn r#type(&self) -> &RustType {
         &self.name_type.1
     }
+
+    /// The protobuf field number within the `oneof`, if not numbered by its position
+    pub fn protobuf_number(&self) -> Option<u32> {
+        self.protobuf_number
+    }
+
+    pub fn set_protobuf_number(&mut self, number: Option<u32>) {
+        self.protobuf_number = number;
+    }
+
+    /// Whether the integer is encoded as protobuf `fixed32`/`fixed64` instead of as varint
+    pub fn protobuf_fixed(&self) -> bool {
+        self.protobuf_fixed
+    }
+
+    pub fn set_protobuf_fixed(&mut self, fixed: bool) {
+        self.protobuf_fixed = fixed;
+    }
 }
 
 impl TagProperty for DataVariant {
@@ -593,17 +1006,113 @@ impl TagProperty for DataVariant {
     }
 }
 
+/// How the types of the inline `SEQUENCE`s, `SET`s, `CHOICE`s and `ENUMERATED`s of fields and
+/// variants are named, see [`Model::to_rust_with_naming`]. Collisions of the names are reported
+/// by [`Model::validate_with_naming`].
+#[derive(Debug, Default, Clone, Copy)]
+pub enum InlineNaming {
+    /// The name of the parent type followed by the name of the field or variant, such as
+    /// `PersonAddress` for the field `address` of `Person`
+    #[default]
+    ParentPrefixed,
+    /// Like [`InlineNaming::ParentPrefixed`], but with a number appended, if the name is already
+    /// taken by a definition, an imported type or another inline type, such as `PersonAddress2`
+    Numbered,
+    /// The name returned for the rust name of the parent type and the ASN.1 name of the field or
+    /// variant, converted to a rust type name
+    Custom(fn(&str, &str) -> String),
+}
+
+/// Assigns the names of the inline types in the order of their conversion
+pub(crate) struct InlineNames {
+    naming: InlineNaming,
+    make_names_nice: bool,
+    taken: Vec<String>,
+}
+
+impl InlineNames {
+    /// The given names of the definitions and imported types are taken from the beginning
+    pub(crate) fn new<'a>(
+        naming: InlineNaming,
+        make_names_nice: bool,
+        taken: impl Iterator<Item = &'a str>,
+    ) -> Self {
+        let mut names = Self {
+            naming,
+            make_names_nice,
+            taken: Vec::default(),
+        };
+        names.taken = taken.map(|name| names.struct_or_enum_name(name)).collect();
+        names
+    }
+
+    /// The name of the inline type of the given field or variant, before being claimed
+    pub(crate) fn member(&self, parent: &str, member: &str) -> String {
+        match self.naming {
+            InlineNaming::Custom(name) => name(parent, member),
+            InlineNaming::ParentPrefixed | InlineNaming::Numbered => {
+                format!("{}{}", parent, self.struct_or_enum_name(member))
+            }
+        }
+    }
+
+    /// Claims the given name for an inline type and returns the name to use
+    pub(crate) fn claim(&mut self, name: &str) -> String {
+        let mut name = self.struct_or_enum_name(name);
+        if matches!(self.naming, InlineNaming::Numbered) && self.taken.contains(&name) {
+            name = (2..)
+                .map(|number| format!("{}{}", name, number))
+                .find(|numbered| !self.taken.contains(numbered))
+                .unwrap_or(name);
+        }
+        self.taken.push(name.clone());
+        name
+    }
+
+    fn struct_or_enum_name(&self, name: &str) -> String {
+        if self.make_names_nice {
+            rust_struct_or_enum_name(name)
+        } else {
+            name.to_string()
+        }
+    }
+}
+
 impl Model<Rust> {
     pub fn convert_asn_to_rust(
         asn_model: &Model<Asn>,
         scope: &[&Model<Asn>],
         make_names_nice: bool,
+    ) -> Model<Rust> {
+        Self::convert_asn_to_rust_with_naming(
+            asn_model,
+            scope,
+            make_names_nice,
+            InlineNaming::default(),
+        )
+    }
+
+    pub fn convert_asn_to_rust_with_naming(
+        asn_model: &Model<Asn>,
+        scope: &[&Model<Asn>],
+        make_names_nice: bool,
+        inline_naming: InlineNaming,
     ) -> Model<Rust> {
         let mut definitions = Vec::with_capacity(asn_model.definitions.len());
         let mut ctxt = Context {
             resolver: TagResolver::new(asn_model, scope),
             target: &mut definitions,
             make_names_nice,
+            inline_names: InlineNames::new(
+                inline_naming,
+                make_names_nice,
+                asn_model.definitions.iter().map(Definition::name).chain(
+                    asn_model
+                        .imports
+                        .iter()
+                        .flat_map(|import| import.what.iter().map(String::as_str)),
+                ),
+            ),
         };
         let mut model = Model {
             name: ctxt.module_name(&asn_model.name),
@@ -619,10 +1128,14 @@ impl Model<Rust> {
                 .collect(),
             definitions: Vec::default(),
             value_references: Vec::with_capacity(asn_model.value_references.len()),
+            docs: Docs::default(),
         };
         for Definition(name, asn) in &asn_model.definitions {
             let rust_name = ctxt.struct_or_enum_name(name);
             Self::definition_to_rust(&rust_name, &asn.r#type, asn.tag, &mut ctxt);
+            for (member, doc) in Self::docs_to_rust(&asn_model.docs, name, &asn.r#type, &ctxt) {
+                model.docs.insert(rust_name.clone(), member, doc);
+            }
         }
         for vref in &asn_model.value_references {
             if let Some(rust_type) = Self::map_asn_type_to_rust_type_flat(&vref.role.r#type) {
@@ -637,9 +1150,43 @@ impl Model<Rust> {
             }
         }
         model.definitions = definitions;
+        box_recursive_types(&mut model);
         model
     }
 
+    /// The docs of the given definition and of its fields or variants, by their rust names
+    fn docs_to_rust(
+        docs: &Docs,
+        name: &str,
+        r#type: &Type,
+        ctxt: &Context<'_>,
+    ) -> Vec<(Option<String>, String)> {
+        let members: Vec<(&str, String)> = match r#type {
+            Type::Sequence(ComponentTypeList { fields, .. })
+            | Type::Set(ComponentTypeList { fields, .. }) => fields
+                .iter()
+                .map(|field| (field.name.as_str(), ctxt.field_name(&field.name)))
+                .collect(),
+            Type::Choice(choice) => choice
+                .variants()
+                .map(|variant| (variant.name(), ctxt.variant_name(variant.name())))
+                .collect(),
+            Type::Enumerated(enumerated) => enumerated
+                .variants()
+                .map(|variant| (variant.name(), ctxt.variant_name(variant.name())))
+                .collect(),
+            _ => Vec::new(),
+        };
+        docs.get(name, None)
+            .map(|doc| (None, doc.to_string()))
+            .into_iter()
+            .chain(members.into_iter().filter_map(|(member, rust_member)| {
+                docs.get(name, Some(member))
+                    .map(|doc| (Some(rust_member), doc.to_string()))
+            }))
+            .collect()
+    }
+
     fn map_asn_type_to_rust_type_flat(r#type: &Type) -> Option<RustType> {
         Some(match &r#type {
             Type::Boolean => RustType::Bool,
@@ -647,7 +1194,9 @@ impl Model<Rust> {
                 Self::asn_extensible_integer_to_rust(int)
             }
             Type::Integer(int) => Self::asn_fixed_integer_to_rust_type(int),
-            Type::String(size, charset) => RustType::String(size.clone(), *charset),
+            Type::String(size, charset, alphabet) => {
+                RustType::String(size.clone(), *charset, alphabet.clone())
+            }
             Type::OctetString(size) => RustType::VecU8(size.clone()),
             Type::BitString(bs) => RustType::BitVec(bs.size.clone()),
             Type::Null => RustType::Null,
@@ -677,11 +1226,7 @@ impl Model<Rust> {
     /// The name is expected in a valid and rusty way
     fn definition_to_rust(name: &str, asn: &AsnType, tag: Option<Tag>, ctxt: &mut Context<'_>) {
         match asn {
-            AsnType::Boolean
-            | AsnType::Null
-            | AsnType::String(..)
-            | AsnType::OctetString(_)
-            | AsnType::BitString(_) => {
+            AsnType::Boolean | AsnType::Null | AsnType::String(..) | AsnType::OctetString(_) => {
                 let rust_type = Self::definition_type_to_rust_type(name, asn, tag, ctxt);
                 ctxt.add_definition(Definition(
                     name.to_string(),
@@ -696,7 +1241,7 @@ impl Model<Rust> {
                 ));
             }
 
-            me @ AsnType::Integer(_) => {
+            me @ AsnType::Integer(_) | me @ AsnType::BitString(_) => {
                 let rust_type = Self::definition_type_to_rust_type(name, asn, tag, ctxt);
                 let constants = ctxt.to_rust_constants(me);
                 ctxt.add_definition(Definition(
@@ -742,6 +1287,7 @@ impl Model<Rust> {
                         fields,
                         tag,
                         extension_after: *extension_after,
+                        protobuf_unknown_fields: false,
                     },
                 ));
             }
@@ -758,6 +1304,7 @@ impl Model<Rust> {
                         fields,
                         tag,
                         extension_after: *extension_after,
+                        protobuf_unknown_fields: false,
                     },
                 ));
             }
@@ -788,6 +1335,8 @@ impl Model<Rust> {
                     variants: Vec::with_capacity(choice.len()),
                     tag,
                     extended_after_index: choice.extension_after_index(),
+                    numbers: None,
+                    identifiers: choice.variants().map(|v| v.name.clone()).collect(),
                 };
 
                 for ChoiceVariant {
@@ -796,7 +1345,7 @@ impl Model<Rust> {
                     tag,
                 } in choice.variants()
                 {
-                    let rust_name = format!("{}{}", name, ctxt.struct_or_enum_name(variant_name));
+                    let rust_name = ctxt.inline_names.member(name, variant_name);
                     let rust_role =
                         Self::definition_type_to_rust_type(&rust_name, r#type, *tag, ctxt);
                     let rust_field_name = ctxt.variant_name(variant_name);
@@ -813,6 +1362,14 @@ impl Model<Rust> {
                     variants: Vec::with_capacity(enumerated.len()),
                     tag,
                     extended_after_index: enumerated.extension_after_index(),
+                    numbers: enumerated
+                        .variants()
+                        .any(|variant| variant.number().is_some())
+                        .then(|| enumerated.numbers()),
+                    identifiers: enumerated
+                        .variants()
+                        .map(|v| v.name().to_string())
+                        .collect(),
                 };
 
                 for variant in enumerated.variants() {
@@ -833,7 +1390,7 @@ impl Model<Rust> {
         let mut rust_fields = Vec::with_capacity(fields.len());
 
         for (index, field) in fields.iter().enumerate() {
-            let rust_name = format!("{}{}", name, ctxt.struct_or_enum_name(&field.name));
+            let rust_name = ctxt.inline_names.member(name, &field.name);
             let tag = field.role.tag;
             let rust_role =
                 Self::definition_type_to_rust_type(&rust_name, &field.role.r#type, tag, ctxt);
@@ -872,7 +1429,9 @@ impl Model<Rust> {
             }
             AsnType::Integer(int) => Self::asn_fixed_integer_to_rust_type(int),
 
-            AsnType::String(size, charset) => RustType::String(size.clone(), *charset),
+            AsnType::String(size, charset, alphabet) => {
+                RustType::String(size.clone(), *charset, alphabet.clone())
+            }
             AsnType::OctetString(size) => RustType::VecU8(size.clone()),
             AsnType::BitString(bitstring) => RustType::BitVec(bitstring.size.clone()),
             Type::Optional(inner) => {
@@ -916,7 +1475,7 @@ impl Model<Rust> {
             | ty @ AsnType::Set(_)
             | ty @ AsnType::Enumerated(_)
             | ty @ AsnType::Choice(_) => {
-                let name = ctxt.struct_or_enum_name(name);
+                let name = ctxt.inline_names.claim(name);
                 Self::definition_to_rust(&name, asn, tag, ctxt);
                 RustType::Complex(name, tag.or_else(|| ctxt.resolver().resolve_type_tag(ty)))
             }
@@ -927,10 +1486,39 @@ impl Model<Rust> {
         }
     }
 
+    /// The range if both bounds fit into an `i64`
+    fn narrow_integer_range(range: &Range<Option<i128>>) -> Option<Range<Option<i64>>> {
+        let narrow = |bound: &Option<i128>| bound.map(i64::try_from).transpose().ok();
+        Some(Range(
+            narrow(range.min())?,
+            narrow(range.max())?,
+            range.extensible(),
+        ))
+    }
+
+    fn asn_wide_integer_to_rust_type(range: &Range<Option<i128>>) -> RustType {
+        let Range(min, max, extensible) = *range;
+        // the proc-macro attributes denote MIN and MAX through the limits of i128
+        let min = min.filter(|min| *min != i128::MIN);
+        let max = max.filter(|max| *max != i128::MAX);
+        if min.unwrap_or_default() >= 0 && max.unwrap_or_default() >= 0 {
+            RustType::U128(Range(
+                min.map(|v| v as u128),
+                max.map(|v| v as u128),
+                extensible,
+            ))
+        } else {
+            RustType::I128(Range(min, max, extensible))
+        }
+    }
+
     fn asn_extensible_integer_to_rust(
         int: &Integer<<Resolved as ResolveState>::RangeType>,
     ) -> RustType {
-        match (int.range.min(), int.range.max()) {
+        let Some(range) = Self::narrow_integer_range(&int.range) else {
+            return Self::asn_wide_integer_to_rust_type(&int.range);
+        };
+        match (range.min(), range.max()) {
             (None, None) | (Some(0), None) | (Some(0), Some(i64::MAX)) | (None, Some(i64::MAX)) => {
                 RustType::U64(Range(None, None, true))
             }
@@ -948,13 +1536,17 @@ impl Model<Rust> {
     fn asn_fixed_integer_to_rust_type(
         int: &Integer<<Resolved as ResolveState>::RangeType>,
     ) -> RustType {
-        match (int.range.min(), int.range.max()) {
+        let Some(range) = Self::narrow_integer_range(&int.range) else {
+            return Self::asn_wide_integer_to_rust_type(&int.range);
+        };
+        match (range.min(), range.max()) {
             (None, None) | (Some(0), None) | (Some(0), Some(i64::MAX)) | (None, Some(i64::MAX)) => {
                 RustType::U64(Range(None, None, false))
             }
             (min, max) => {
-                let min = min.unwrap_or_default();
                 let max = max.unwrap_or(i64::MAX);
+                // without a lower bound, a negative upper bound can only be met by a signed type
+                let min = min.unwrap_or(if max < 0 { i64::MIN } else { 0 });
                 if min >= 0 {
                     match max as u64 {
                         m if m <= U8_MAX => RustType::U8(Range::inclusive(min as u8, max as u8)),
@@ -985,6 +1577,7 @@ struct Context<'a> {
     resolver: TagResolver<'a>,
     target: &'a mut Vec<Definition<Rust>>,
     make_names_nice: bool,
+    inline_names: InlineNames,
 }
 
 impl Context<'_> {
@@ -1067,6 +1660,69 @@ impl Context<'_> {
 }
 
 #[allow(clippy::module_name_repetitions)]
+/// Represents the OCTET STRING (SIZE(16)) of all fields and variants as `uuid::Uuid` (which
+/// requires the `uuid` feature of `asn1rs`). The value is still encoded as its 16 bytes.
+pub fn use_uuid_for_16_byte_octet_strings(model: &mut Model<Rust>) {
+    for Definition(_, rust) in &mut model.definitions {
+        match rust {
+            Rust::Struct { fields, .. } => fields
+                .iter_mut()
+                .for_each(|f| f.name_type.1.replace_with_uuid()),
+            Rust::DataEnum(data) => data
+                .variants_mut()
+                .for_each(|v| v.name_type.1.replace_with_uuid()),
+            Rust::Enum(_) | Rust::TupleStruct { .. } => {}
+        }
+    }
+}
+
+/// Boxes the references of recursive types, which would otherwise be infinitely sized. Types
+/// within a `Vec` are on the heap already. A cycle is broken at the field or variant referring
+/// back to a type that is still being visited (in the order of the definitions), or at the one
+/// before if that reference is the type of a tuple struct.
+pub fn box_recursive_types(model: &mut Model<Rust>) {
+    while let Some((index, name)) = find_recursive_reference(&model.definitions) {
+        model.definitions[index].1.set_boxed(&[name]);
+    }
+}
+
+/// Finds the field or variant to box for the first cycle of inline references, as index of its
+/// definition and its name
+fn find_recursive_reference(definitions: &[Definition<Rust>]) -> Option<(usize, String)> {
+    fn visit(
+        definitions: &[Definition<Rust>],
+        index: usize,
+        path: &mut Vec<(usize, Option<String>)>,
+        done: &mut [bool],
+    ) -> Option<(usize, String)> {
+        if let Some(start) = path.iter().position(|(i, _)| *i == index) {
+            // a cycle of tuple structs is invalid and would be reported by the compiler anyway
+            return path[start..]
+                .iter()
+                .rev()
+                .find_map(|(i, name)| Some((*i, name.clone()?)));
+        }
+        if done[index] {
+            return None;
+        }
+        for (name, reference) in definitions[index].1.inline_references() {
+            if let Some(target) = definitions.iter().position(|d| d.0 == reference) {
+                path.push((index, name.map(str::to_string)));
+                let found = visit(definitions, target, path, done);
+                path.pop();
+                if found.is_some() {
+                    return found;
+                }
+            }
+        }
+        done[index] = true;
+        None
+    }
+
+    let mut done = vec![false; definitions.len()];
+    (0..definitions.len()).find_map(|index| visit(definitions, index, &mut Vec::new(), &mut done))
+}
+
 pub fn rust_field_name(name: &str) -> String {
     rust_module_name(name, false)
 }
@@ -1299,6 +1955,83 @@ mod tests {
         );
     }
 
+    #[test]
+    fn test_wide_integers_represented_as_128_bit_types() {
+        let model_rust = Model::try_from(Tokenizer::default().parse(
+            r"WideSchema DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Wide ::= SEQUENCE {
+                unsigned INTEGER (0..79228162514264337593543950335),
+                signed INTEGER (-9223372036854775809..0),
+                semi INTEGER (18446744073709551616..MAX, ...)
+            }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        assert_eq!(
+            Definition(
+                "Wide".into(),
+                Rust::struct_from_fields(vec![
+                    RustField::from_name_type(
+                        "unsigned",
+                        RustType::U128(Range(Some(0), Some((1 << 96) - 1), false)),
+                    ),
+                    RustField::from_name_type(
+                        "signed",
+                        RustType::I128(Range(Some(i128::from(i64::MIN) - 1), Some(0), false)),
+                    ),
+                    RustField::from_name_type(
+                        "semi",
+                        RustType::U128(Range(Some(1 << 64), None, true)),
+                    ),
+                ]),
+            ),
+            model_rust.definitions[0]
+        );
+    }
+
+    #[test]
+    fn test_recursive_references_are_boxed() {
+        let model_rust = Model::try_from(Tokenizer::default().parse(
+            r"RecursiveSchema DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Node ::= SEQUENCE { next Node OPTIONAL }
+            Expr ::= CHOICE { negated Expr, sum Sum }
+            Sum ::= SEQUENCE { left Expr, right Expr }
+            Alias ::= Tree
+            Tree ::= SEQUENCE { alias Alias OPTIONAL, children SEQUENCE OF Tree }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+
+        let boxed = model_rust
+            .definitions
+            .iter()
+            .map(|Definition(name, rust)| (name.as_str(), rust.boxed()))
+            .collect::<Vec<_>>();
+        assert_eq!(
+            vec![
+                ("Node", vec!["next"]),
+                ("Expr", vec!["Negated"]),
+                ("Sum", vec!["left", "right"]),
+                ("Alias", vec![]),
+                ("Tree", vec!["alias"]),
+            ],
+            boxed
+        );
+        match &model_rust.definitions[0].1 {
+            Rust::Struct { fields, .. } => {
+                assert_eq!("Option<Box<Node>>", fields[0].r#type().to_string())
+            }
+            other => panic!("Unexpected definition {:?}", other),
+        }
+    }
+
     #[test]
     fn test_inline_asn_enumerated_represented_correctly_as_rust_model() {
         let model_rust = Model::try_from(Tokenizer::default().parse(INLINE_ASN_WITH_ENUM))
@@ -1595,7 +2328,7 @@ mod tests {
                     vec![
                         DataVariant::from_name_type(
                             "BerndDasBrot",
-                            RustType::String(Size::Any, Charset::Utf8),
+                            RustType::String(Size::Any, Charset::Utf8, None),
                         ),
                         DataVariant::from_name_type("NochSoEinBrot", RustType::VecU8(Size::Any)),
                     ]
@@ -1641,7 +2374,7 @@ mod tests {
                         DataVariant::from_name_type(
                             "NormalList",
                             RustType::Vec(
-                                Box::new(RustType::String(Size::Any, Charset::Utf8)),
+                                Box::new(RustType::String(Size::Any, Charset::Utf8, None)),
                                 Size::Any,
                                 EncodingOrdering::Keep
                             ),
@@ -1683,7 +2416,7 @@ mod tests {
             Definition(
                 "TupleTest".into(),
                 Rust::tuple_struct_from_type(RustType::Vec(
-                    Box::new(RustType::String(Size::Any, Charset::Utf8)),
+                    Box::new(RustType::String(Size::Any, Charset::Utf8, None)),
                     Size::Any,
                     EncodingOrdering::Keep
                 )),
@@ -1716,7 +2449,7 @@ mod tests {
                 "NestedTupleTest".into(),
                 Rust::tuple_struct_from_type(RustType::Vec(
                     Box::new(RustType::Vec(
-                        Box::new(RustType::String(Size::Any, Charset::Utf8)),
+                        Box::new(RustType::String(Size::Any, Charset::Utf8, None)),
                         Size::Any,
                         EncodingOrdering::Keep
                     )),
@@ -1752,7 +2485,7 @@ mod tests {
                 Rust::struct_from_fields(vec![RustField::from_name_type(
                     "strings",
                     RustType::Option(Box::new(RustType::Vec(
-                        Box::new(RustType::String(Size::Any, Charset::Utf8)),
+                        Box::new(RustType::String(Size::Any, Charset::Utf8, None)),
                         Size::Any,
                         EncodingOrdering::Keep
                     ))),
@@ -1785,7 +2518,7 @@ mod tests {
                 Rust::struct_from_fields(vec![RustField::from_name_type(
                     "strings",
                     RustType::Vec(
-                        Box::new(RustType::String(Size::Any, Charset::Utf8)),
+                        Box::new(RustType::String(Size::Any, Charset::Utf8, None)),
                         Size::Any,
                         EncodingOrdering::Keep
                     ),
@@ -1825,7 +2558,7 @@ mod tests {
                     "strings",
                     RustType::Vec(
                         Box::new(RustType::Vec(
-                            Box::new(RustType::String(Size::Any, Charset::Utf8)),
+                            Box::new(RustType::String(Size::Any, Charset::Utf8, None)),
                             Size::Any,
                             EncodingOrdering::Keep
                         )),
@@ -1863,12 +2596,51 @@ mod tests {
                 Rust::Enum(
                     PlainEnum::from_names(["Abc", "Def", "Ghi"].iter())
                         .with_extension_after(Some(2))
+                        .with_numbers(Some(vec![0, 1, 42]))
                 ),
             )],
             &model_rust.definitions[..]
         );
     }
 
+    #[test]
+    pub fn test_enum_numbers_and_enumeration_indices() {
+        let mut model_asn = Model::default();
+        model_asn.name = "NumberedEnum".to_string();
+        model_asn.definitions.push(Definition(
+            "Numbered".to_string(),
+            AsnType::Enumerated(
+                Enumerated::from(vec![
+                    EnumeratedVariant::from_name_number("abc", 9),
+                    "def".into(),
+                    EnumeratedVariant::from_name_number("ghi", 1),
+                    "jkl".into(),
+                    "mno".into(),
+                    EnumeratedVariant::from_name_number("pqr", 12),
+                    "stu".into(),
+                ])
+                .with_extension_after(3),
+            )
+            .untagged(),
+        ));
+        let model_rust = model_asn.to_rust();
+        let Rust::Enum(plain) = model_rust.definitions[0].value() else {
+            panic!("Expected Rust::Enum for ASN.1 ENUMERATED")
+        };
+        assert_eq!(Some(&[9, 0, 1, 2, 3, 12, 13][..]), plain.numbers());
+        assert_eq!(
+            vec![3, 0, 1, 2, 4, 5, 6],
+            (0..plain.len())
+                .map(|index| plain.enumeration_index(index))
+                .collect::<Vec<_>>()
+        );
+        assert_eq!(
+            vec!["Def", "Ghi", "Jkl", "Abc", "Mno", "Pqr", "Stu"],
+            plain.variants_by_enumeration_index().collect::<Vec<_>>()
+        );
+        assert_eq!(None, plain.variant_index(7));
+    }
+
     #[test]
     pub fn test_extensible_choice() {
         let mut model_asn = Model::default();
@@ -1920,6 +2692,7 @@ mod tests {
             fields: Vec::default(),
             tag: None,
             extension_after: None,
+            protobuf_unknown_fields: false,
         });
     }
 
@@ -1935,7 +2708,7 @@ mod tests {
         test_property(Rust::DataEnum(DataEnum::from(vec![
             DataVariant::from_name_type(
                 "SomeName".to_string(),
-                RustType::String(Size::Any, Charset::Visible),
+                RustType::String(Size::Any, Charset::Visible, None),
             ),
         ])));
     }
@@ -1993,6 +2766,7 @@ mod tests {
                     value: LiteralValue::Boolean(true),
                 },
             ],
+            docs: Docs::default(),
         };
 
         assert_starts_with_lines(
@@ -2040,6 +2814,7 @@ mod tests {
                 ),
             ],
             value_references: vec![],
+            docs: Docs::default(),
         };
         assert_eq!(
             vec![
@@ -2069,11 +2844,57 @@ mod tests {
                             ),
                         ],
                         tag: None,
-                        extension_after: None
+                        extension_after: None,
+                        protobuf_unknown_fields: false,
                     }
                 ),
             ],
             asn.to_rust().definitions
         );
     }
+
+    #[test]
+    fn test_inline_naming() {
+        let asn = Model::try_from(Tokenizer::default().parse(
+            r"Inline DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+            Person ::= SEQUENCE {
+                address SEQUENCE { street UTF8String },
+                kind ENUMERATED { a, b }
+            }
+            PersonAddress ::= SEQUENCE { city UTF8String }
+            END",
+        ))
+        .unwrap()
+        .try_resolve()
+        .unwrap();
+        let names = |naming| {
+            asn.to_rust_with_naming(&[], naming)
+                .definitions
+                .into_iter()
+                .map(|Definition(name, _)| name)
+                .collect::<Vec<_>>()
+        };
+
+        assert_eq!(
+            vec!["PersonAddress", "PersonKind", "Person", "PersonAddress"],
+            names(InlineNaming::ParentPrefixed)
+        );
+        assert_eq!(
+            vec!["PersonAddress2", "PersonKind", "Person", "PersonAddress"],
+            names(InlineNaming::Numbered)
+        );
+        assert_eq!(
+            vec![
+                "AddressForPerson",
+                "KindForPerson",
+                "Person",
+                "PersonAddress"
+            ],
+            names(InlineNaming::Custom(|parent, member| format!(
+                "{}For{}",
+                member, parent
+            )))
+        );
+    }
 }
@@ -0,0 +1,444 @@
+//! Checks of a [`Model`] beyond its syntax, which would otherwise only surface as compile errors
+//! of the generated code or as ambiguous encodings. See [`Model::validate`].
+
+use crate::asn::{Asn, Tag, TagProperty, TagResolver, Type};
+use crate::model::{Definition, Model};
+use crate::resolve::ResolveState;
+use crate::rust::{rust_field_name, rust_struct_or_enum_name, rust_variant_name};
+use crate::rust::{InlineNames, InlineNaming};
+use std::fmt::{Display, Formatter};
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Warning,
+    Error,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum IssueKind {
+    /// The type is neither defined in nor imported by the module
+    UndefinedReference(String),
+    /// The definitions (first, second) have the same name (third) in the generated code. Inline
+    /// types are named by the path of their field or variant, such as `Message.payload`.
+    DuplicateDefinition(String, String, String),
+    /// The fields or variants (first, second) have the same name (third) in the generated code
+    DuplicateName(String, String, String),
+    /// The alternatives (first, second) of a `CHOICE` have the same tag (third), so that they
+    /// cannot be told apart in BER and DER. Automatic tagging is not applied, just as in the
+    /// generated code.
+    TagConflict(String, String, Tag),
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Issue {
+    /// The name of the module
+    pub module: String,
+    /// The definition and the (nested) fields or variants the issue was found in, separated by
+    /// dots, for example `Message.payload`
+    pub path: String,
+    pub kind: IssueKind,
+}
+
+impl Issue {
+    /// Tag conflicts only affect BER and DER and are therefore warnings, all other issues prevent
+    /// the generated code from compiling
+    pub fn severity(&self) -> Severity {
+        match self.kind {
+            IssueKind::TagConflict(..) => Severity::Warning,
+            IssueKind::UndefinedReference(_)
+            | IssueKind::DuplicateDefinition(..)
+            | IssueKind::DuplicateName(..) => Severity::Error,
+        }
+    }
+
+    /// A stable identifier of the kind of issue
+    pub fn code(&self) -> &'static str {
+        match self.kind {
+            IssueKind::UndefinedReference(_) => "V0001",
+            IssueKind::DuplicateDefinition(..) => "V0002",
+            IssueKind::DuplicateName(..) => "V0003",
+            IssueKind::TagConflict(..) => "V0004",
+        }
+    }
+}
+
+impl Display for Issue {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}::{}: ", self.module, self.path)?;
+        match &self.kind {
+            IssueKind::UndefinedReference(name) => write!(
+                f,
+                "Type {} is neither defined in nor imported by the module",
+                name
+            ),
+            IssueKind::DuplicateDefinition(first, second, rust) => write!(
+                f,
+                "Definitions {} and {} are both named {} in the generated code",
+                first, second, rust
+            ),
+            IssueKind::DuplicateName(first, second, rust) => write!(
+                f,
+                "{} and {} are both named {} in the generated code",
+                first, second, rust
+            ),
+            IssueKind::TagConflict(first, second, tag) => write!(
+                f,
+                "Alternatives {} and {} share the tag [{} {}]",
+                first,
+                second,
+                match tag {
+                    Tag::Universal(_) => "UNIVERSAL",
+                    Tag::Application(_) => "APPLICATION",
+                    Tag::ContextSpecific(_) => "CONTEXT",
+                    Tag::Private(_) => "PRIVATE",
+                },
+                tag.value()
+            ),
+        }
+    }
+}
+
+impl<RS: ResolveState> Model<Asn<RS>> {
+    /// Checks that all type references can be resolved, that the names of the definitions and
+    /// of the fields and variants within each type do not collide after being converted to rust
+    /// names and that the alternatives of each `CHOICE` have distinct tags
+    pub fn validate(&self) -> Vec<Issue> {
+        self.validate_with_scope(&[])
+    }
+
+    /// Like [`Model::validate`], but resolves the tags of imported types within the given scope
+    pub fn validate_with_scope(&self, scope: &[&Self]) -> Vec<Issue> {
+        self.validate_with_naming(scope, InlineNaming::default())
+    }
+
+    /// Like [`Model::validate_with_scope`], but checks the names of the inline types as given by
+    /// the naming strategy against each other and against the definitions and imported types
+    pub fn validate_with_naming(&self, scope: &[&Self], inline_naming: InlineNaming) -> Vec<Issue> {
+        let names = self.definitions.iter().map(Definition::name).chain(
+            self.imports
+                .iter()
+                .flat_map(|i| i.what.iter().map(String::as_str)),
+        );
+        let mut validator = Validator {
+            model: self,
+            resolver: TagResolver::new(self, scope),
+            inline_names: InlineNames::new(inline_naming, true, names.clone()),
+            named: names
+                .map(|name| (rust_struct_or_enum_name(name), name.to_string()))
+                .collect(),
+            issues: Vec::new(),
+        };
+        validator.check_duplicates(
+            self.name.as_str(),
+            self.definitions.iter().map(Definition::name),
+            rust_struct_or_enum_name,
+            IssueKind::DuplicateDefinition,
+        );
+        for Definition(name, asn) in &self.definitions {
+            let rust_name = rust_struct_or_enum_name(name);
+            let rust_name = match &asn.r#type {
+                Type::Sequence(_) | Type::Set(_) | Type::Choice(_) | Type::Enumerated(_) => {
+                    rust_name
+                }
+                other => validator.inline_type_name(name, rust_name, other),
+            };
+            validator.check_type(name, &rust_name, &asn.r#type);
+        }
+        validator.issues
+    }
+}
+
+struct Validator<'a, RS: ResolveState> {
+    model: &'a Model<Asn<RS>>,
+    resolver: TagResolver<'a, RS>,
+    inline_names: InlineNames,
+    /// The rust names of the definitions, imported types and inline types so far, with the
+    /// ASN.1 name or the path they were taken by
+    named: Vec<(String, String)>,
+    issues: Vec<Issue>,
+}
+
+impl<RS: ResolveState> Validator<'_, RS> {
+    /// The rust name of the inline type of the given path or the given name, if the type is not
+    /// defined inline
+    fn inline_type_name(&mut self, path: &str, name: String, r#type: &Type<RS>) -> String {
+        match r#type {
+            Type::Optional(inner)
+            | Type::Default(inner, _)
+            | Type::SequenceOf(inner, _)
+            | Type::SetOf(inner, _) => self.inline_type_name(path, name, inner),
+            Type::Sequence(_) | Type::Set(_) | Type::Choice(_) | Type::Enumerated(_) => {
+                let name = self.inline_names.claim(&name);
+                if let Some((_, other)) = self.named.iter().find(|(rust, _)| name.eq(rust)) {
+                    self.issues.push(Issue {
+                        module: self.model.name.clone(),
+                        path: self.model.name.clone(),
+                        kind: IssueKind::DuplicateDefinition(
+                            other.clone(),
+                            path.to_string(),
+                            name.clone(),
+                        ),
+                    });
+                }
+                self.named.push((name.clone(), path.to_string()));
+                name
+            }
+            Type::Boolean
+            | Type::Integer(_)
+            | Type::String(..)
+            | Type::OctetString(_)
+            | Type::BitString(_)
+            | Type::Null
+            | Type::TypeReference(..) => name,
+        }
+    }
+
+    fn check_type(&mut self, path: &str, rust_name: &str, r#type: &Type<RS>) {
+        match r#type {
+            Type::Optional(inner)
+            | Type::Default(inner, _)
+            | Type::SequenceOf(inner, _)
+            | Type::SetOf(inner, _) => self.check_type(path, rust_name, inner),
+            Type::Sequence(components) | Type::Set(components) => {
+                self.check_duplicates(
+                    path,
+                    components.fields.iter().map(|field| field.name.as_str()),
+                    rust_field_name,
+                    IssueKind::DuplicateName,
+                );
+                for field in &components.fields {
+                    let path = format!("{}.{}", path, field.name);
+                    let name = self.inline_names.member(rust_name, &field.name);
+                    let name = self.inline_type_name(&path, name, &field.role.r#type);
+                    self.check_type(&path, &name, &field.role.r#type);
+                }
+            }
+            Type::Choice(choice) => {
+                self.check_duplicates(
+                    path,
+                    choice.variants().map(|variant| variant.name()),
+                    rust_variant_name,
+                    IssueKind::DuplicateName,
+                );
+                let tags = choice
+                    .variants()
+                    .map(|variant| {
+                        variant
+                            .tag()
+                            .or_else(|| self.resolver.resolve_type_tag(variant.r#type()))
+                    })
+                    .collect::<Vec<_>>();
+                let variants = choice.variants().collect::<Vec<_>>();
+                for (index, tag) in tags.iter().enumerate() {
+                    if let Some(other) = tags[..index]
+                        .iter()
+                        .position(|other| tag.is_some() && other == tag)
+                    {
+                        self.issues.push(Issue {
+                            module: self.model.name.clone(),
+                            path: path.to_string(),
+                            kind: IssueKind::TagConflict(
+                                variants[other].name().to_string(),
+                                variants[index].name().to_string(),
+                                tag.unwrap(),
+                            ),
+                        });
+                    }
+                }
+                for variant in variants {
+                    let path = format!("{}.{}", path, variant.name());
+                    let name = self.inline_names.member(rust_name, variant.name());
+                    let name = self.inline_type_name(&path, name, variant.r#type());
+                    self.check_type(&path, &name, variant.r#type());
+                }
+            }
+            Type::Enumerated(enumerated) => self.check_duplicates(
+                path,
+                enumerated.variants().map(|variant| variant.name()),
+                rust_variant_name,
+                IssueKind::DuplicateName,
+            ),
+            Type::TypeReference(name, _) => {
+                let known = self.model.definitions.iter().any(|d| d.name().eq(name))
+                    || self.model.imports.iter().any(|i| i.what.contains(name));
+                if !known {
+                    self.issues.push(Issue {
+                        module: self.model.name.clone(),
+                        path: path.to_string(),
+                        kind: IssueKind::UndefinedReference(name.clone()),
+                    });
+                }
+            }
+            Type::Boolean
+            | Type::Integer(_)
+            | Type::String(..)
+            | Type::OctetString(_)
+            | Type::BitString(_)
+            | Type::Null => {}
+        }
+    }
+
+    fn check_duplicates<'n>(
+        &mut self,
+        path: &str,
+        names: impl Iterator<Item = &'n str>,
+        rust_name: fn(&str) -> String,
+        kind: fn(String, String, String) -> IssueKind,
+    ) {
+        let mut seen: Vec<(&str, String)> = Vec::new();
+        for name in names {
+            let rust = rust_name(name);
+            if let Some((other, _)) = seen.iter().find(|(_, other)| rust.eq(other)) {
+                self.issues.push(Issue {
+                    module: self.model.name.clone(),
+                    path: path.to_string(),
+                    kind: kind(other.to_string(), name.to_string(), rust.clone()),
+                });
+            }
+            seen.push((name, rust));
+        }
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::parse::Tokenizer;
+
+    fn validate(asn: &str) -> Vec<Issue> {
+        Model::try_from(Tokenizer.parse(asn)).unwrap().validate()
+    }
+
+    #[test]
+    pub fn test_valid_model_has_no_issues() {
+        assert_eq!(
+            Vec::<Issue>::new(),
+            validate(
+                r"Valid DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+                IMPORTS Imported FROM Other;
+                Kind ::= ENUMERATED { small, large }
+                Payload ::= CHOICE { number [0] INTEGER, text [1] UTF8String, kind Kind }
+                Message ::= SEQUENCE { kind Kind, payload Payload, other Imported OPTIONAL }
+                END"
+            )
+        );
+    }
+
+    #[test]
+    pub fn test_issues_are_found_in_nested_types() {
+        let issues = validate(
+            r"Invalid DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Message ::= SEQUENCE {
+                msg-id INTEGER,
+                msg_id INTEGER,
+                payload CHOICE { number INTEGER, count INTEGER (0..7), text Missing },
+                status ENUMERATED { is-ok, isOk }
+            }
+            message ::= INTEGER
+            END",
+        );
+        assert_eq!(
+            vec![
+                Issue {
+                    module: "Invalid".to_string(),
+                    path: "Invalid".to_string(),
+                    kind: IssueKind::DuplicateDefinition(
+                        "Message".to_string(),
+                        "message".to_string(),
+                        "Message".to_string()
+                    ),
+                },
+                Issue {
+                    module: "Invalid".to_string(),
+                    path: "Message".to_string(),
+                    kind: IssueKind::DuplicateName(
+                        "msg-id".to_string(),
+                        "msg_id".to_string(),
+                        "msg_id".to_string()
+                    ),
+                },
+                Issue {
+                    module: "Invalid".to_string(),
+                    path: "Message.payload".to_string(),
+                    kind: IssueKind::TagConflict(
+                        "number".to_string(),
+                        "count".to_string(),
+                        Tag::DEFAULT_INTEGER
+                    ),
+                },
+                Issue {
+                    module: "Invalid".to_string(),
+                    path: "Message.payload.text".to_string(),
+                    kind: IssueKind::UndefinedReference("Missing".to_string()),
+                },
+                Issue {
+                    module: "Invalid".to_string(),
+                    path: "Message.status".to_string(),
+                    kind: IssueKind::DuplicateName(
+                        "is-ok".to_string(),
+                        "isOk".to_string(),
+                        "IsOk".to_string()
+                    ),
+                },
+            ],
+            issues
+        );
+        assert_eq!(
+            vec![
+                Severity::Error,
+                Severity::Error,
+                Severity::Warning,
+                Severity::Error,
+                Severity::Error
+            ],
+            issues.iter().map(Issue::severity).collect::<Vec<_>>()
+        );
+        assert_eq!(
+            "Invalid::Message.payload: Alternatives number and count share the tag [UNIVERSAL 2]",
+            issues[2].to_string()
+        );
+    }
+
+    #[test]
+    pub fn test_inline_name_collisions() {
+        let model = Model::try_from(Tokenizer.parse(
+            r"Inline DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Person ::= SEQUENCE {
+                address SEQUENCE { street UTF8String },
+                kind ENUMERATED { a, b }
+            }
+            PersonAddress ::= SEQUENCE { city UTF8String }
+            Pet ::= SEQUENCE { owner-kind ENUMERATED { a, b } }
+            PetOwner ::= SEQUENCE { kind ENUMERATED { c, d } }
+            END",
+        ))
+        .unwrap();
+        assert_eq!(
+            vec![
+                Issue {
+                    module: "Inline".to_string(),
+                    path: "Inline".to_string(),
+                    kind: IssueKind::DuplicateDefinition(
+                        "PersonAddress".to_string(),
+                        "Person.address".to_string(),
+                        "PersonAddress".to_string()
+                    ),
+                },
+                Issue {
+                    module: "Inline".to_string(),
+                    path: "Inline".to_string(),
+                    kind: IssueKind::DuplicateDefinition(
+                        "Pet.owner-kind".to_string(),
+                        "PetOwner.kind".to_string(),
+                        "PetOwnerKind".to_string()
+                    ),
+                },
+            ],
+            model.validate()
+        );
+        assert_eq!(
+            Vec::<Issue>::new(),
+            model.validate_with_naming(&[], InlineNaming::Numbered)
+        );
+    }
+}
@@ -0,0 +1,6 @@
+#![no_main]
+
+use asn1rs_fuzz::Color;
+use libfuzzer_sys::fuzz_target;
+
+fuzz_target!(|data: &[u8]| asn1rs::fuzz::der_decode::<Color>(data));
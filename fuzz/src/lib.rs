@@ -0,0 +1,46 @@
+//! Definitions that are decoded by the fuzz targets. `Pdu` is meant to cover as many
+//! descriptors as possible in a single type. DER does not support constructed types yet, so the
+//! DER target decodes `Color` instead.
+
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"Fuzz DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Pdu ::= CHOICE {
+        record Record,
+        records SEQUENCE SIZE(0..8) OF Record,
+        flags Flags,
+        switches SET OF BOOLEAN,
+        color Color,
+        ...,
+        raw OCTET STRING
+    }
+
+    Record ::= SEQUENCE {
+        id INTEGER (0..65535),
+        delta INTEGER (-1000..1000, ...),
+        big INTEGER,
+        name UTF8String (SIZE(0..32)),
+        code NumericString (SIZE(4)),
+        label PrintableString OPTIONAL,
+        ascii IA5String,
+        visible VisibleString (SIZE(1..8, ...)),
+        payload OCTET STRING (SIZE(0..16)),
+        bits BIT STRING (SIZE(0..40)),
+        valid BOOLEAN DEFAULT TRUE,
+        nothing NULL,
+        ...,
+        color Color OPTIONAL
+    }
+
+    Color ::= ENUMERATED { red, green, blue, ... }
+
+    Flags ::= SET {
+        a BOOLEAN,
+        b INTEGER (0..7)
+    }
+
+    END"
+);
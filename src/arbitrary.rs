@@ -0,0 +1,159 @@
+//! Support for generating random but valid values of generated types with [`arbitrary`] and for
+//! checking that these values survive the round-trip through a codec.
+//!
+//! The `Arbitrary` implementations are generated by enabling
+//! `RustCodeGenerator::set_generate_arbitrary_impls` (or by passing the
+//! `ArbitraryImplGenerator` as supplement to the generator). The generated implementations
+//! call the functions of this module to respect the size and charset constraints of the
+//! ASN.1 definition.
+
+use crate::descriptor::bitstring::BitVec;
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::rw::UperWriter;
+use std::fmt::Debug;
+
+pub use ::arbitrary::{Arbitrary, Error, Result, Unstructured};
+pub use asn1rs_model::asn::Charset;
+
+/// Upper limit of additional elements that are generated for a size constraint without an
+/// upper bound (or a very large one), so that generating values terminates in reasonable time.
+pub const MAX_UNBOUNDED_ADDITIONAL_LEN: usize = 32;
+
+/// Picks a length within the given inclusive bounds. Without upper bound, at most
+/// [`MAX_UNBOUNDED_ADDITIONAL_LEN`] elements are added to `min`.
+pub fn size(u: &mut Unstructured<'_>, min: usize, max: Option<usize>) -> Result<usize> {
+    let max = max
+        .unwrap_or(usize::MAX)
+        .min(min.saturating_add(MAX_UNBOUNDED_ADDITIONAL_LEN));
+    u.int_in_range(min..=max)
+}
+
+/// Generates a string of the given length (in chars) that only consists of characters valid
+/// for the given [`Charset`].
+pub fn string(
+    u: &mut Unstructured<'_>,
+    min: usize,
+    max: Option<usize>,
+    charset: Charset,
+) -> Result<String> {
+    let len = size(u, min, max)?;
+    let characters = match charset {
+        Charset::Utf8 => return (0..len).map(|_| u.arbitrary::<char>()).collect(),
+        Charset::Numeric => Charset::NUMERIC_STRING_CHARACTERS,
+        Charset::Printable => Charset::PRINTABLE_STRING_CHARACTERS,
+        Charset::Ia5 => Charset::IA5_STRING_CHARACTERS,
+        Charset::Visible
+        | Charset::Teletex
+        | Charset::Videotex
+        | Charset::Graphic
+        | Charset::ObjectDescriptor => Charset::VISIBLE_STRING_CHARACTERS,
+    };
+    let characters = characters.as_bytes();
+    (0..len)
+        .map(|_| u.choose(characters).map(|c| char::from(*c)))
+        .collect()
+}
+
+/// Generates a string of the given length (in chars) that only consists of characters of the
+/// given permitted alphabet.
+pub fn string_from(
+    u: &mut Unstructured<'_>,
+    min: usize,
+    max: Option<usize>,
+    alphabet: &str,
+) -> Result<String> {
+    let len = size(u, min, max)?;
+    let characters = alphabet.chars().collect::<Vec<_>>();
+    (0..len).map(|_| u.choose(&characters).copied()).collect()
+}
+
+/// Generates an octet string with a length within the given bounds.
+pub fn octets(u: &mut Unstructured<'_>, min: usize, max: Option<usize>) -> Result<Vec<u8>> {
+    let len = size(u, min, max)?;
+    (0..len).map(|_| u.arbitrary::<u8>()).collect()
+}
+
+/// Generates a bit string with a bit-length within the given bounds.
+pub fn bit_vec(u: &mut Unstructured<'_>, min: usize, max: Option<usize>) -> Result<BitVec> {
+    let bit_len = size(u, min, max)?;
+    let bytes = (0..bit_len.div_ceil(BYTE_LEN))
+        .map(|_| u.arbitrary::<u8>())
+        .collect::<Result<Vec<u8>>>()?;
+    Ok(BitVec::from_bytes(bytes, bit_len as u64))
+}
+
+/// Generates a `Vec` with a length within the given bounds, the elements are provided by `f`.
+pub fn vec<'a, T, F: FnMut(&mut Unstructured<'a>) -> Result<T>>(
+    u: &mut Unstructured<'a>,
+    min: usize,
+    max: Option<usize>,
+    mut f: F,
+) -> Result<Vec<T>> {
+    let len = size(u, min, max)?;
+    (0..len).map(|_| f(u)).collect()
+}
+
+/// Serializes the value in UPER and deserializes it again.
+pub fn uper_round_trip<T: Readable + Writable>(
+    value: &T,
+) -> core::result::Result<T, crate::protocol::per::Error> {
+    let mut writer = UperWriter::default();
+    writer.write(value)?;
+    let mut reader = writer.as_reader();
+    let result = reader.read::<T>()?;
+    debug_assert_eq!(0, reader.bits_remaining());
+    Ok(result)
+}
+
+/// Asserts that the value is equal to itself after being serialized and deserialized in UPER.
+///
+/// # Panics
+/// If the round-trip fails or the deserialized value is not equal to the given value.
+pub fn assert_uper_round_trip<T: Readable + Writable + Debug + PartialEq>(value: &T) {
+    match uper_round_trip(value) {
+        Ok(result) => assert_eq!(value, &result, "UPER round-trip changed the value"),
+        Err(e) => panic!("UPER round-trip failed for {value:?}: {e}"),
+    }
+}
+
+/// Serializes the value in protobuf and deserializes it again.
+#[cfg(feature = "protobuf")]
+pub fn protobuf_round_trip<T: Readable + Writable>(
+    value: &T,
+) -> core::result::Result<T, crate::protocol::protobuf::Error> {
+    let mut writer = crate::rw::ProtobufWriter::default();
+    writer.write(value)?;
+    writer.as_reader().read::<T>()
+}
+
+/// Asserts that the value is equal to itself after being serialized and deserialized in
+/// protobuf.
+///
+/// # Panics
+/// If the round-trip fails or the deserialized value is not equal to the given value.
+#[cfg(feature = "protobuf")]
+pub fn assert_protobuf_round_trip<T: Readable + Writable + Debug + PartialEq>(value: &T) {
+    match protobuf_round_trip(value) {
+        Ok(result) => assert_eq!(value, &result, "protobuf round-trip changed the value"),
+        Err(e) => panic!("protobuf round-trip failed for {value:?}: {e:?}"),
+    }
+}
+
+/// Generates values from the given data until it is exhausted and asserts the UPER round-trip
+/// for each of them. Useful as body of a fuzz target or for quick property tests with
+/// (pseudo-)random input data.
+pub fn assert_uper_round_trips<'a, T: Arbitrary<'a> + Readable + Writable + Debug + PartialEq>(
+    data: &'a [u8],
+) -> Result<()> {
+    let mut u = Unstructured::new(data);
+    while !u.is_empty() {
+        let len = u.len();
+        assert_uper_round_trip(&T::arbitrary(&mut u)?);
+        if len == u.len() {
+            // the type does not consume any data
+            break;
+        }
+    }
+    Ok(())
+}
@@ -0,0 +1,220 @@
+//! Helpers for build scripts (`build.rs`), which generate the Rust code for ASN.1 files at
+//! build time.
+
+use crate::converter::{Converter, Error};
+use std::collections::hash_map::DefaultHasher;
+use std::hash::{Hash, Hasher};
+use std::path::{Path, PathBuf};
+
+/// Remembers the hash of the inputs and the generated files of the last run
+const CACHE_FILE: &str = ".asn1rs-cache";
+const MOD_FILE: &str = "mod.rs";
+const EXTENSIONS: [&str; 2] = ["asn1", "asn"];
+
+/// Generates the Rust code for all `.asn1` and `.asn` files in `source_dir` and its
+/// sub-directories into `out_dir`, together with a `mod.rs` declaring a module for each of the
+/// generated files. Because all files are converted together, they can `IMPORT` from each other.
+///
+/// The `cargo:rerun-if-changed` lines for the directory and the files are printed. If neither the
+/// files nor the version of asn1rs changed since the last run, the files are not generated again,
+/// so that their modification time does not trigger a recompilation.
+///
+/// Returns the paths of the generated files, including the `mod.rs`.
+///
+/// ```rust,no_run
+/// // in the main function of the build.rs
+/// let out_dir = std::env::var("OUT_DIR").unwrap();
+/// asn1rs::build::compile_dir("asn1/", out_dir).expect("Failed to compile the ASN.1 files");
+/// ```
+///
+/// The generated modules are then included with `include!(concat!(env!("OUT_DIR"), "/mod.rs"));`
+pub fn compile_dir<S: AsRef<Path>, O: AsRef<Path>>(
+    source_dir: S,
+    out_dir: O,
+) -> Result<Vec<PathBuf>, Error> {
+    let source_dir = source_dir.as_ref();
+    let sources = find_sources(source_dir)?;
+
+    println!("cargo:rerun-if-changed={}", source_dir.display());
+    for source in &sources {
+        println!("cargo:rerun-if-changed={}", source.display());
+    }
+
+    std::fs::create_dir_all(out_dir.as_ref())?;
+    let out_dir = out_dir.as_ref().canonicalize()?;
+    let hash = hash_sources(&sources)?;
+
+    if let Some(files) = cached_files(&out_dir, &hash) {
+        return Ok(files);
+    }
+
+    let mut converter = Converter::default();
+    for source in &sources {
+        converter.load_file(source)?;
+    }
+
+    let files = converter
+        .to_rust(&out_dir, |_| {})?
+        .into_iter()
+        .flat_map(|(_module, files)| files)
+        .collect::<Vec<_>>();
+
+    std::fs::write(out_dir.join(MOD_FILE), mod_file(&out_dir, &files))?;
+    std::fs::write(
+        out_dir.join(CACHE_FILE),
+        core::iter::once(hash.as_str())
+            .chain(files.iter().map(String::as_str))
+            .collect::<Vec<_>>()
+            .join("\n"),
+    )?;
+
+    Ok(with_mod_file(&out_dir, &files))
+}
+
+/// Finds all `.asn1` and `.asn` files in `source_dir` and its sub-directories, sorted by their
+/// path
+pub fn find_sources<S: AsRef<Path>>(source_dir: S) -> Result<Vec<PathBuf>, Error> {
+    let mut sources = Vec::new();
+    collect_sources(source_dir.as_ref(), &mut sources)?;
+    sources.sort();
+    Ok(sources)
+}
+
+fn collect_sources(dir: &Path, sources: &mut Vec<PathBuf>) -> Result<(), Error> {
+    for entry in std::fs::read_dir(dir)? {
+        let path = entry?.path();
+        if path.is_dir() {
+            collect_sources(&path, sources)?;
+        } else if path
+            .extension()
+            .and_then(|e| e.to_str())
+            .is_some_and(|e| EXTENSIONS.contains(&e))
+        {
+            sources.push(path);
+        }
+    }
+    Ok(())
+}
+
+fn hash_sources(sources: &[PathBuf]) -> Result<String, Error> {
+    let mut hasher = DefaultHasher::new();
+    env!("CARGO_PKG_VERSION").hash(&mut hasher);
+    for source in sources {
+        source.hash(&mut hasher);
+        std::fs::read(source)?.hash(&mut hasher);
+    }
+    Ok(format!("{:016x}", hasher.finish()))
+}
+
+/// The files of the last run, if they were generated from the same inputs and still exist
+fn cached_files(out_dir: &Path, hash: &str) -> Option<Vec<PathBuf>> {
+    let cache = std::fs::read_to_string(out_dir.join(CACHE_FILE)).ok()?;
+    let mut lines = cache.lines();
+    if lines.next() != Some(hash) {
+        return None;
+    }
+    let files = lines.map(str::to_string).collect::<Vec<_>>();
+    let files = with_mod_file(out_dir, &files);
+    files.iter().all(|file| file.is_file()).then_some(files)
+}
+
+fn with_mod_file(out_dir: &Path, files: &[String]) -> Vec<PathBuf> {
+    files
+        .iter()
+        .map(String::as_str)
+        .chain(core::iter::once(MOD_FILE))
+        .map(|file| out_dir.join(file))
+        .collect()
+}
+
+/// The modules point to the absolute path of the files, so that the `mod.rs` can be included
+/// from anywhere with `include!`
+fn mod_file(out_dir: &Path, files: &[String]) -> String {
+    let mut content = String::from("// Generated by asn1rs, do not edit\n");
+    for file in files {
+        let module = file.strip_suffix(".rs").unwrap_or(file);
+        content.push_str(&format!(
+            "\n#[path = {:?}]\npub mod {};\n",
+            out_dir.join(file).display().to_string(),
+            module
+        ));
+    }
+    content
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn temp_dir(name: &str) -> PathBuf {
+        let dir = std::env::temp_dir().join(format!("asn1rs-{}-{}", name, std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+        dir
+    }
+
+    #[test]
+    pub fn test_compile_dir_and_cache() {
+        let source_dir = temp_dir("compile-dir-source");
+        let out_dir = temp_dir("compile-dir-out");
+        std::fs::create_dir_all(source_dir.join("nested")).unwrap();
+        std::fs::write(
+            source_dir.join("basic.asn1"),
+            r"Basic DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            IMPORTS Inner FROM Nested;
+            Outer ::= SEQUENCE { inner Inner }
+            END",
+        )
+        .unwrap();
+        std::fs::write(
+            source_dir.join("nested").join("nested.asn"),
+            r"Nested DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Inner ::= INTEGER (0..255)
+            END",
+        )
+        .unwrap();
+        std::fs::write(source_dir.join("README.md"), "not ASN.1").unwrap();
+
+        let files = compile_dir(&source_dir, &out_dir).unwrap();
+        let out_dir = out_dir.canonicalize().unwrap();
+        assert_eq!(
+            vec![
+                out_dir.join("nested.rs"),
+                out_dir.join("basic.rs"),
+                out_dir.join("mod.rs")
+            ],
+            files
+        );
+        let mod_rs = std::fs::read_to_string(out_dir.join("mod.rs")).unwrap();
+        assert!(mod_rs.contains(&format!(
+            "#[path = {:?}]\npub mod basic;\n",
+            out_dir.join("basic.rs").display().to_string()
+        )));
+        assert!(mod_rs.contains("pub mod nested;\n"));
+
+        // unchanged inputs are not generated again
+        std::fs::write(out_dir.join("basic.rs"), "// untouched").unwrap();
+        assert_eq!(files, compile_dir(&source_dir, &out_dir).unwrap());
+        assert_eq!(
+            "// untouched",
+            std::fs::read_to_string(out_dir.join("basic.rs")).unwrap()
+        );
+
+        // but changed inputs are
+        std::fs::write(
+            source_dir.join("nested").join("nested.asn"),
+            r"Nested DEFINITIONS AUTOMATIC TAGS ::= BEGIN
+            Inner ::= INTEGER (0..65535)
+            END",
+        )
+        .unwrap();
+        assert_eq!(files, compile_dir(&source_dir, &out_dir).unwrap());
+        assert_ne!(
+            "// untouched",
+            std::fs::read_to_string(out_dir.join("basic.rs")).unwrap()
+        );
+
+        let _ = std::fs::remove_dir_all(source_dir);
+        let _ = std::fs::remove_dir_all(out_dir);
+    }
+}
@@ -0,0 +1,273 @@
+//! A type-erased container for encoded values, so that messages of different types can be
+//! passed through queues and channels without generics, see [`AnyPdu`] and [`PduRegistry`].
+
+use super::{Codec, Error};
+use crate::descriptor::{Readable, Writable};
+use std::collections::HashMap;
+
+/// The first byte of a serialized [`AnyPdu`]
+const ENVELOPE_MAGIC: u8 = 0xA5;
+/// The version of the envelope format, the second byte of a serialized [`AnyPdu`]
+const ENVELOPE_VERSION: u8 = 1;
+/// The length of the header of a serialized [`AnyPdu`]: magic, version, codec id and the
+/// fingerprint as 8 bytes in big endian
+const ENVELOPE_HEADER_LEN: usize = 11;
+
+/// Identifies a type by the FNV-1a hash of its path, such as `my_crate::messages::Ping`.
+///
+/// The path is provided by [`std::any::type_name`], which is not guaranteed to be identical
+/// between compiler versions. Where the producer and consumer of a [`AnyPdu`] are built
+/// separately, explicit fingerprints, for example [`fingerprint_of_name`] of the ASN.1 name,
+/// should be used with [`AnyPdu::encode_as`] and [`PduRegistry::register_as`] instead.
+pub fn fingerprint<T: ?Sized>() -> u64 {
+    fingerprint_of_name(std::any::type_name::<T>())
+}
+
+/// The FNV-1a hash of the given name
+pub const fn fingerprint_of_name(name: &str) -> u64 {
+    let bytes = name.as_bytes();
+    let mut hash = 0xcbf2_9ce4_8422_2325_u64;
+    let mut index = 0;
+    while index < bytes.len() {
+        hash ^= bytes[index] as u64;
+        hash = hash.wrapping_mul(0x0000_0100_0000_01b3);
+        index += 1;
+    }
+    hash
+}
+
+/// An encoded value with the [`Codec`] it is encoded with and the fingerprint of its type.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+/// use asn1rs::codec::{AnyPdu, Codec, PduRegistry};
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Ping {
+///     #[asn(integer(0..255))]
+///     pub id: u8,
+/// }
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Pong {
+///     #[asn(utf8string)]
+///     pub text: String,
+/// }
+///
+/// #[derive(Debug, PartialEq)]
+/// enum Event {
+///     Ping(Ping),
+///     Pong(Pong),
+/// }
+///
+/// let mut registry = PduRegistry::default();
+/// registry.register::<Ping>(Event::Ping);
+/// registry.register::<Pong>(Event::Pong);
+///
+/// let queue = vec![
+///     AnyPdu::encode(Codec::Uper, &Ping { id: 7 }).unwrap().to_envelope(),
+///     AnyPdu::encode(Codec::Jer, &Pong { text: "hi".into() }).unwrap().to_envelope(),
+/// ];
+///
+/// let events = queue
+///     .iter()
+///     .map(|envelope| registry.decode(&AnyPdu::from_envelope(envelope)?))
+///     .collect::<Result<Vec<_>, _>>()
+///     .unwrap();
+///
+/// assert_eq!(
+///     vec![Event::Ping(Ping { id: 7 }), Event::Pong(Pong { text: "hi".into() })],
+///     events
+/// );
+/// ```
+#[derive(Debug, Clone, PartialEq, Eq, Hash)]
+pub struct AnyPdu {
+    codec: Codec,
+    fingerprint: u64,
+    bytes: Vec<u8>,
+}
+
+impl AnyPdu {
+    pub fn new(codec: Codec, fingerprint: u64, bytes: Vec<u8>) -> Self {
+        Self {
+            codec,
+            fingerprint,
+            bytes,
+        }
+    }
+
+    /// Encodes the value with the given codec and the [`fingerprint`] of its type
+    pub fn encode<T: Writable>(codec: Codec, value: &T) -> Result<Self, Error> {
+        Self::encode_as(codec, fingerprint::<T>(), value)
+    }
+
+    /// Encodes the value with the given codec and an explicit fingerprint
+    pub fn encode_as<T: Writable>(
+        codec: Codec,
+        fingerprint: u64,
+        value: &T,
+    ) -> Result<Self, Error> {
+        Ok(Self::new(codec, fingerprint, codec.encode(value)?))
+    }
+
+    /// Decodes the value, which fails with [`Error::TypeMismatch`] if the value is not of the
+    /// type `T` according to its [`fingerprint`]
+    pub fn decode<T: Readable>(&self) -> Result<T, Error> {
+        if self.is::<T>() {
+            self.codec.decode(&self.bytes)
+        } else {
+            Err(Error::TypeMismatch {
+                expected: fingerprint::<T>(),
+                found: self.fingerprint,
+            })
+        }
+    }
+
+    /// Whether the value is of the type `T` according to its [`fingerprint`]
+    pub fn is<T: ?Sized>(&self) -> bool {
+        self.fingerprint == fingerprint::<T>()
+    }
+
+    pub fn codec(&self) -> Codec {
+        self.codec
+    }
+
+    pub fn fingerprint(&self) -> u64 {
+        self.fingerprint
+    }
+
+    /// The encoded value
+    pub fn bytes(&self) -> &[u8] {
+        &self.bytes
+    }
+
+    pub fn into_bytes(self) -> Vec<u8> {
+        self.bytes
+    }
+
+    /// Serializes the codec id, the fingerprint and the encoded value into a self-describing
+    /// envelope, from which [`AnyPdu::from_envelope`] detects the codec again
+    pub fn to_envelope(&self) -> Vec<u8> {
+        let mut envelope = Vec::with_capacity(ENVELOPE_HEADER_LEN + self.bytes.len());
+        envelope.push(ENVELOPE_MAGIC);
+        envelope.push(ENVELOPE_VERSION);
+        envelope.push(self.codec.id());
+        envelope.extend_from_slice(&self.fingerprint.to_be_bytes());
+        envelope.extend_from_slice(&self.bytes);
+        envelope
+    }
+
+    /// Parses an envelope written by [`AnyPdu::to_envelope`]
+    pub fn from_envelope(envelope: &[u8]) -> Result<Self, Error> {
+        if envelope.len() < ENVELOPE_HEADER_LEN
+            || envelope[0] != ENVELOPE_MAGIC
+            || envelope[1] != ENVELOPE_VERSION
+        {
+            return Err(Error::InvalidEnvelope);
+        }
+        let codec = Codec::from_id(envelope[2]).ok_or(Error::UnknownCodec(envelope[2]))?;
+        let mut fingerprint = [0_u8; 8];
+        fingerprint.copy_from_slice(&envelope[3..ENVELOPE_HEADER_LEN]);
+        Ok(Self::new(
+            codec,
+            u64::from_be_bytes(fingerprint),
+            envelope[ENVELOPE_HEADER_LEN..].to_vec(),
+        ))
+    }
+}
+
+type Decoder<R> = Box<dyn Fn(&AnyPdu) -> Result<R, Error> + Send + Sync>;
+
+/// Decodes [`AnyPdu`]s into the registered types by their fingerprint and maps the decoded
+/// values to a common type `R`, such as an enum of all expected messages or `Box<dyn Any>`
+pub struct PduRegistry<R> {
+    decoders: HashMap<u64, (&'static str, Decoder<R>)>,
+}
+
+impl<R> Default for PduRegistry<R> {
+    fn default() -> Self {
+        Self {
+            decoders: HashMap::default(),
+        }
+    }
+}
+
+impl<R> PduRegistry<R> {
+    /// Registers the type `T` by its [`fingerprint`], replacing any previous registration
+    pub fn register<T: Readable + 'static>(
+        &mut self,
+        map: impl Fn(T) -> R + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.register_as(fingerprint::<T>(), map)
+    }
+
+    /// Registers the type `T` by an explicit fingerprint, see [`AnyPdu::encode_as`]
+    pub fn register_as<T: Readable + 'static>(
+        &mut self,
+        fingerprint: u64,
+        map: impl Fn(T) -> R + Send + Sync + 'static,
+    ) -> &mut Self {
+        self.decoders.insert(
+            fingerprint,
+            (
+                std::any::type_name::<T>(),
+                Box::new(move |pdu| pdu.codec.decode::<T>(&pdu.bytes).map(&map)),
+            ),
+        );
+        self
+    }
+
+    /// Decodes the value as the type registered for its fingerprint, which fails with
+    /// [`Error::UnregisteredType`] if there is none
+    pub fn decode(&self, pdu: &AnyPdu) -> Result<R, Error> {
+        match self.decoders.get(&pdu.fingerprint) {
+            Some((_, decoder)) => decoder(pdu),
+            None => Err(Error::UnregisteredType(pdu.fingerprint)),
+        }
+    }
+
+    /// The name of the type registered for the given fingerprint
+    pub fn type_name(&self, fingerprint: u64) -> Option<&'static str> {
+        self.decoders.get(&fingerprint).map(|(name, _)| *name)
+    }
+
+    pub fn is_registered(&self, fingerprint: u64) -> bool {
+        self.decoders.contains_key(&fingerprint)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_fingerprint_of_name() {
+        assert_eq!(0xcbf2_9ce4_8422_2325, fingerprint_of_name(""));
+        assert_eq!(0xaf63_dc4c_8601_ec8c, fingerprint_of_name("a"));
+        assert_eq!(fingerprint_of_name("bool"), fingerprint::<bool>());
+        assert_ne!(fingerprint::<u8>(), fingerprint::<i8>());
+    }
+
+    #[test]
+    pub fn test_invalid_envelopes() {
+        let envelope = AnyPdu::new(Codec::Der, 0x0102_0304_0506_0708, vec![0xFF]).to_envelope();
+        assert_eq!(
+            vec![0xA5, 0x01, 0x01, 0x01, 0x02, 0x03, 0x04, 0x05, 0x06, 0x07, 0x08, 0xFF],
+            envelope
+        );
+        assert!(matches!(
+            AnyPdu::from_envelope(&envelope[..10]),
+            Err(Error::InvalidEnvelope)
+        ));
+        assert!(matches!(
+            AnyPdu::from_envelope(&[0xA5, 0x01, 0x7F, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(Error::UnknownCodec(0x7F))
+        ));
+        assert!(matches!(
+            AnyPdu::from_envelope(&[0xA5, 0x02, 0x00, 0, 0, 0, 0, 0, 0, 0, 0]),
+            Err(Error::InvalidEnvelope)
+        ));
+    }
+}
@@ -0,0 +1,242 @@
+//! Encodes and decodes generated types with encoding rules that are selected at runtime, and
+//! converts payloads between them with the generated type as pivot, see [`transcode`].
+//!
+//! The DER support of the generated types is limited to BOOLEAN, INTEGER and ENUMERATED values
+//! so far, see [`crate::rw::BasicWriter`].
+//!
+//! The prelude exports [`encode`] and [`decode`] as the entry points for callers that are generic
+//! over the codec.
+//!
+//! Encoded values of different types can be passed around type-erased as [`AnyPdu`] and decoded
+//! through a [`PduRegistry`].
+
+mod any;
+
+pub use any::fingerprint;
+pub use any::fingerprint_of_name;
+pub use any::AnyPdu;
+pub use any::PduRegistry;
+
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::protocol::basic::{Error as DerError, DER};
+use crate::protocol::jer::Error as JerError;
+use crate::protocol::per::Error as UperError;
+#[cfg(feature = "protobuf")]
+use crate::protocol::protobuf::Error as ProtobufError;
+use crate::rw::{JerReader, JerWriter, UperReader, UperWriter};
+#[cfg(feature = "protobuf")]
+use crate::rw::{ProtobufReader, ProtobufWriter};
+
+#[derive(Debug)]
+pub enum Error {
+    Uper(UperError),
+    Der(DerError),
+    #[cfg(feature = "protobuf")]
+    Protobuf(ProtobufError),
+    Jer(JerError),
+    /// The JER payload is not valid UTF-8
+    InvalidUtf8,
+    /// The bytes are no envelope written by [`AnyPdu::to_envelope`]
+    InvalidEnvelope,
+    /// The codec id of the envelope is unknown or its codec is not enabled, see [`Codec::id`]
+    UnknownCodec(u8),
+    /// The fingerprint of the [`AnyPdu`] is not the one of the expected type
+    TypeMismatch {
+        expected: u64,
+        found: u64,
+    },
+    /// No type is registered for the fingerprint in the [`PduRegistry`]
+    UnregisteredType(u64),
+}
+
+impl From<UperError> for Error {
+    fn from(e: UperError) -> Self {
+        Error::Uper(e)
+    }
+}
+
+impl From<DerError> for Error {
+    fn from(e: DerError) -> Self {
+        Error::Der(e)
+    }
+}
+
+#[cfg(feature = "protobuf")]
+impl From<ProtobufError> for Error {
+    fn from(e: ProtobufError) -> Self {
+        Error::Protobuf(e)
+    }
+}
+
+impl From<JerError> for Error {
+    fn from(e: JerError) -> Self {
+        Error::Jer(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Uper(e) => e.fmt(f),
+            Error::Der(e) => e.fmt(f),
+            #[cfg(feature = "protobuf")]
+            Error::Protobuf(e) => e.fmt(f),
+            Error::Jer(e) => e.fmt(f),
+            Error::InvalidUtf8 => write!(f, "The JER payload is not valid UTF-8"),
+            Error::InvalidEnvelope => write!(f, "The bytes are no valid envelope"),
+            Error::UnknownCodec(id) => write!(f, "The codec id {} is unknown", id),
+            Error::TypeMismatch { expected, found } => write!(
+                f,
+                "Expected a value with the fingerprint {:016x} but found {:016x}",
+                expected, found
+            ),
+            Error::UnregisteredType(fingerprint) => write!(
+                f,
+                "No type is registered for the fingerprint {:016x}",
+                fingerprint
+            ),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The encoding rules supported for the generated types
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Codec {
+    /// Unaligned Packed Encoding Rules, ITU-T X.691
+    Uper,
+    /// Distinguished Encoding Rules, ITU-T X.690
+    Der,
+    #[cfg(feature = "protobuf")]
+    Protobuf,
+    /// JSON Encoding Rules, ITU-T X.697, as UTF-8 bytes
+    Jer,
+}
+
+impl Codec {
+    /// The id of the codec in the envelope of an [`AnyPdu`], which does not depend on the
+    /// enabled features
+    pub const fn id(self) -> u8 {
+        match self {
+            Codec::Uper => 0,
+            Codec::Der => 1,
+            #[cfg(feature = "protobuf")]
+            Codec::Protobuf => 2,
+            Codec::Jer => 3,
+        }
+    }
+
+    /// The codec with the given id, if it is known and enabled, see [`Codec::id`]
+    pub const fn from_id(id: u8) -> Option<Self> {
+        match id {
+            0 => Some(Codec::Uper),
+            1 => Some(Codec::Der),
+            #[cfg(feature = "protobuf")]
+            2 => Some(Codec::Protobuf),
+            3 => Some(Codec::Jer),
+            _ => None,
+        }
+    }
+
+    pub fn encode<T: Writable>(self, value: &T) -> Result<Vec<u8>, Error> {
+        Ok(match self {
+            Codec::Uper => {
+                let mut writer = UperWriter::default();
+                writer.write(value)?;
+                writer.into_bytes_vec()
+            }
+            Codec::Der => {
+                let mut writer = DER::writer(Vec::new());
+                writer.write(value)?;
+                writer.into_inner()
+            }
+            #[cfg(feature = "protobuf")]
+            Codec::Protobuf => {
+                let mut writer = ProtobufWriter::default();
+                writer.write(value)?;
+                writer.into_bytes_vec()
+            }
+            Codec::Jer => {
+                let mut writer = JerWriter::default();
+                match writer.write(value) {
+                    Ok(()) => writer.into_string().into_bytes(),
+                    Err(infallible) => match infallible {},
+                }
+            }
+        })
+    }
+
+    pub fn decode<T: Readable>(self, bytes: &[u8]) -> Result<T, Error> {
+        Ok(match self {
+            Codec::Uper => UperReader::from((bytes, bytes.len() * 8)).read::<T>()?,
+            Codec::Der => DER::reader(bytes).read::<T>()?,
+            #[cfg(feature = "protobuf")]
+            Codec::Protobuf => ProtobufReader::from(bytes).read::<T>()?,
+            Codec::Jer => {
+                let json = core::str::from_utf8(bytes).map_err(|_| Error::InvalidUtf8)?;
+                JerReader::parse(json)?.read::<T>()?
+            }
+        })
+    }
+}
+
+/// Encodes the value with the encoding rules selected at runtime, the entry point for
+/// applications that are generic over the codec - such as plugins that are configured with one.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Point {
+///     #[asn(integer(0..255))]
+///     pub x: u8,
+///     #[asn(integer(0..255))]
+///     pub y: u8,
+/// }
+///
+/// let point = Point { x: 2, y: 128 };
+/// for codec in [Codec::Uper, Codec::Jer] {
+///     let bytes = encode(&point, codec).unwrap();
+///     assert_eq!(point, decode::<Point>(&bytes, codec).unwrap());
+/// }
+/// ```
+#[inline]
+pub fn encode<T: Writable>(value: &T, codec: Codec) -> Result<Vec<u8>, Error> {
+    codec.encode(value)
+}
+
+/// Decodes the value with the encoding rules selected at runtime, see [`encode`]
+#[inline]
+pub fn decode<T: Readable>(bytes: &[u8], codec: Codec) -> Result<T, Error> {
+    codec.decode(bytes)
+}
+
+/// Decodes the payload as `T` with the encoding rules `from` and encodes it again with the
+/// encoding rules `to`.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+/// use asn1rs::codec::{transcode, Codec};
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Point {
+///     #[asn(integer(0..255))]
+///     pub x: u8,
+///     #[asn(integer(0..255))]
+///     pub y: u8,
+/// }
+///
+/// let json = transcode::<Point>(Codec::Uper, Codec::Jer, &[0x02, 0x80]).unwrap();
+/// assert_eq!(br#"{"x":2,"y":128}"#, &json[..]);
+/// assert_eq!(vec![0x02, 0x80], transcode::<Point>(Codec::Jer, Codec::Uper, &json).unwrap());
+/// ```
+pub fn transcode<T: Readable + Writable>(
+    from: Codec,
+    to: Codec,
+    bytes: &[u8],
+) -> Result<Vec<u8>, Error> {
+    to.encode(&from.decode::<T>(bytes)?)
+}
@@ -1,19 +1,30 @@
-use asn1rs_model::asn::MultiModuleResolver;
+use asn1rs_model::asn::{Asn, MultiModuleResolver};
+use asn1rs_model::generate::c::CGenerator;
+use asn1rs_model::generate::fuzz_dict::FuzzDictionaryGenerator;
+use asn1rs_model::generate::json_schema::JsonSchemaGenerator;
 use asn1rs_model::generate::rust::RustCodeGenerator as RustGenerator;
-use asn1rs_model::generate::Generator;
+use asn1rs_model::generate::{Codec, Generator};
 use asn1rs_model::parse::Tokenizer;
+use asn1rs_model::resolve::Unresolved;
+use asn1rs_model::rust::InlineNaming;
+use asn1rs_model::validate::{Issue, Severity};
 use asn1rs_model::Model;
-use std::collections::HashMap;
-use std::path::Path;
+use std::path::{Path, PathBuf};
 
 #[derive(Debug)]
 pub enum Error {
     RustGenerator,
     #[cfg(feature = "protobuf")]
     ProtobufGenerator(asn1rs_model::generate::protobuf::Error),
+    #[cfg(feature = "protobuf")]
+    ProtoImport(asn1rs_model::protobuf::import::Error),
     Model(asn1rs_model::parse::Error),
     Io(std::io::Error),
     ResolveFailure(asn1rs_model::resolve::Error),
+    /// The loaded models have issues, which prevent the conversion, see [`Converter::validate`]
+    Validation(Vec<Issue>),
+    /// Formatting the generated rust files failed with the given output of `rustfmt`
+    Rustfmt(String),
 }
 
 #[cfg(feature = "protobuf")]
@@ -23,6 +34,13 @@ impl From<asn1rs_model::generate::protobuf::Error> for Error {
     }
 }
 
+#[cfg(feature = "protobuf")]
+impl From<asn1rs_model::protobuf::import::Error> for Error {
+    fn from(e: asn1rs_model::protobuf::import::Error) -> Self {
+        Error::ProtoImport(e)
+    }
+}
+
 impl From<asn1rs_model::parse::Error> for Error {
     fn from(m: asn1rs_model::parse::Error) -> Self {
         Error::Model(m)
@@ -41,17 +59,191 @@ impl From<asn1rs_model::resolve::Error> for Error {
     }
 }
 
+/// Converts a set of ASN.1 modules, which may `IMPORT` from each other. The generated files are
+/// returned per module in dependency order.
 #[derive(Default)]
 pub struct Converter {
     models: MultiModuleResolver,
+    /// The file each model was loaded from
+    sources: Vec<(PathBuf, String)>,
+    /// The modules to convert, all if `None`
+    only: Option<Vec<String>>,
+    warnings_as_errors: bool,
+    inline_naming: InlineNaming,
+    /// The codecs to generate the specific rust code for, all if `None`
+    codecs: Option<Vec<Codec>>,
+    rust_mod_file: bool,
+    rust_mod_oid_tree: bool,
+    rust_fmt: bool,
+    #[cfg(feature = "protobuf")]
+    protobuf_field_numbers: asn1rs_model::protobuf::numbers::FieldNumbers,
+    #[cfg(feature = "protobuf")]
+    protobuf_fixed_integers: bool,
+    #[cfg(feature = "protobuf")]
+    protobuf_maps: bool,
+    #[cfg(feature = "protobuf")]
+    protobuf_unknown_fields: bool,
+    #[cfg(feature = "protobuf")]
+    protobuf_services: asn1rs_model::protobuf::services::Services,
 }
 
 impl Converter {
+    /// Whether issues of [`Severity::Warning`] fail the validation as well
+    pub fn set_warnings_as_errors(&mut self, warnings_as_errors: bool) {
+        self.warnings_as_errors = warnings_as_errors;
+    }
+
+    /// How the types of inline definitions are named, see [`InlineNaming`]. Names colliding
+    /// with the chosen strategy fail the validation.
+    pub fn set_inline_naming(&mut self, inline_naming: InlineNaming) {
+        self.inline_naming = inline_naming;
+    }
+
+    /// Restricts the codec specific code in the generated rust files to the given codecs, `None`
+    /// generates it for all codecs, see [`RustGenerator::set_codecs`]
+    pub fn set_codecs(&mut self, codecs: Option<Vec<Codec>>) {
+        self.codecs = codecs;
+    }
+
+    /// Whether a `mod.rs` declaring all rust modules and re-exporting their types is written as
+    /// well, see [`RustGenerator::mod_file`]
+    pub fn set_rust_mod_file(&mut self, mod_file: bool) {
+        self.rust_mod_file = mod_file;
+    }
+
+    /// Whether the `mod.rs` re-exports the modules in nested modules following their object
+    /// identifiers as well, implies [`Converter::set_rust_mod_file`]
+    pub fn set_rust_mod_oid_tree(&mut self, oid_tree: bool) {
+        self.rust_mod_oid_tree = oid_tree;
+    }
+
+    /// Whether the generated rust files are formatted with `rustfmt` (or the binary in the
+    /// `RUSTFMT` environment variable), which must be installed
+    pub fn set_rust_fmt(&mut self, rust_fmt: bool) {
+        self.rust_fmt = rust_fmt;
+    }
+
+    /// The explicit protobuf field numbers of the generated rust types and protobuf messages,
+    /// see [`asn1rs_model::protobuf::numbers`]
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_field_numbers(
+        &mut self,
+        numbers: asn1rs_model::protobuf::numbers::FieldNumbers,
+    ) {
+        self.protobuf_field_numbers = numbers;
+    }
+
+    /// Whether integers with the full range of a 32- or 64-bit integer are encoded as protobuf
+    /// `fixed32`/`fixed64` (or their signed counterparts) instead of as varint, see
+    /// [`asn1rs_model::protobuf::use_fixed_for_full_range_integers`]
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_fixed_integers(&mut self, fixed_integers: bool) {
+        self.protobuf_fixed_integers = fixed_integers;
+    }
+
+    /// Whether `SEQUENCE OF SEQUENCE { key K, value V }` is declared as protobuf `map<K, V>`
+    /// instead of as repeated message, see [`asn1rs_model::protobuf::use_maps`]
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_maps(&mut self, maps: bool) {
+        self.protobuf_maps = maps;
+    }
+
+    /// Whether the generated rust structs retain the unknown fields of protobuf messages, see
+    /// [`asn1rs_model::protobuf::preserve_unknown_fields`]
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_unknown_fields(&mut self, unknown_fields: bool) {
+        self.protobuf_unknown_fields = unknown_fields;
+    }
+
+    /// The gRPC services of request/response pairs, that are declared in the protobuf files and
+    /// generated as service traits, clients and servers, see
+    /// [`asn1rs_model::protobuf::services`]
+    #[cfg(feature = "protobuf")]
+    pub fn set_protobuf_services(&mut self, services: asn1rs_model::protobuf::services::Services) {
+        self.protobuf_services = services;
+    }
+
+    /// Restricts the conversions to the given modules, `None` converts all modules again. All
+    /// loaded modules are still used to resolve the imports.
+    pub fn set_modules_to_convert(&mut self, modules: Option<Vec<String>>) {
+        self.only = modules;
+    }
+
+    /// The names of the modules loaded from the given file
+    pub fn modules_of<F: AsRef<Path>>(&self, file: F) -> Vec<String> {
+        self.sources
+            .iter()
+            .filter(|(source, _)| source.as_path() == file.as_ref())
+            .map(|(_, module)| module.clone())
+            .collect()
+    }
+
+    /// The given modules and all loaded modules importing from them, see
+    /// [`MultiModuleResolver::dependents`]
+    pub fn dependents(&self, modules: &[&str]) -> Vec<String> {
+        self.models.dependents(modules)
+    }
+
+    fn converts(&self, module: &str) -> bool {
+        self.only
+            .as_ref()
+            .is_none_or(|only| only.iter().any(|m| m == module))
+    }
+
+    fn push<F: AsRef<Path>>(&mut self, file: F, model: Model<Asn<Unresolved>>) {
+        self.sources
+            .push((file.as_ref().to_path_buf(), model.name.clone()));
+        self.models.push(model);
+    }
+
+    /// Validates the loaded models, see [`Model::validate_with_naming`]. This is done before each
+    /// conversion, which fails with [`Error::Validation`] if there is any error (or warning, if
+    /// treated as errors). Otherwise the remaining warnings are returned.
+    pub fn validate(&self) -> Result<Vec<Issue>, Error> {
+        let issues = self.models.validate_with_naming(self.inline_naming);
+        if issues
+            .iter()
+            .any(|issue| issue.severity() == Severity::Error || self.warnings_as_errors)
+        {
+            Err(Error::Validation(issues))
+        } else {
+            Ok(issues)
+        }
+    }
+
     pub fn load_file<F: AsRef<Path>>(&mut self, file: F) -> Result<(), Error> {
-        let input = ::std::fs::read_to_string(file)?;
-        let tokens = Tokenizer.parse(&input);
+        let input = ::std::fs::read_to_string(file.as_ref())?;
+        let tokens = Tokenizer.parse_with_comments(&input);
         let model = Model::try_from(tokens)?;
-        self.models.push(model);
+        self.push(file, model);
+        Ok(())
+    }
+
+    /// Like [`Converter::load_file`], but skips the definitions that cannot be parsed, see
+    /// [`Model::try_from_lenient`]. The reasons for skipping are returned as warnings.
+    pub fn load_file_lenient<F: AsRef<Path>>(
+        &mut self,
+        file: F,
+    ) -> Result<Vec<asn1rs_model::parse::Error>, Error> {
+        let input = ::std::fs::read_to_string(file.as_ref())?;
+        let tokens = Tokenizer.parse_with_comments(&input);
+        let (model, warnings) = Model::try_from_lenient(tokens)?;
+        self.push(file, model);
+        Ok(warnings)
+    }
+
+    /// Loads a proto3 file as ASN.1 module, see [`asn1rs_model::protobuf::import`]. Without a
+    /// `package`, the module is named after the file.
+    #[cfg(feature = "protobuf")]
+    pub fn load_proto_file<F: AsRef<Path>>(&mut self, file: F) -> Result<(), Error> {
+        let input = ::std::fs::read_to_string(file.as_ref())?;
+        let mut model = asn1rs_model::protobuf::import::import(&input)?;
+        if model.name.is_empty() {
+            if let Some(stem) = file.as_ref().file_stem() {
+                model.name = asn1rs_model::protobuf::import::module_name(&stem.to_string_lossy());
+            }
+        }
+        self.push(file, model);
         Ok(())
     }
 
@@ -59,18 +251,34 @@ impl Converter {
         &self,
         directory: D,
         custom_adjustments: A,
-    ) -> Result<HashMap<String, Vec<String>>, Error> {
+    ) -> Result<Vec<(String, Vec<String>)>, Error> {
+        self.validate()?;
         let models = self.models.try_resolve_all()?;
         let scope = models.iter().collect::<Vec<_>>();
-        let mut files = HashMap::with_capacity(models.len());
+        let mut files = Vec::with_capacity(models.len());
 
-        for model in &models {
+        for model in models.iter().filter(|m| self.converts(&m.name)) {
             let mut generator = RustGenerator::default();
-            generator.add_model(model.to_rust_with_scope(&scope[..]));
+            generator.add_model(model.to_rust_with_naming(&scope[..], self.inline_naming));
+            #[cfg(feature = "protobuf")]
+            generator.models_mut().iter_mut().for_each(|rust| {
+                self.protobuf_field_numbers.apply(rust);
+                if self.protobuf_fixed_integers {
+                    asn1rs_model::protobuf::use_fixed_for_full_range_integers(rust);
+                }
+                if self.protobuf_unknown_fields {
+                    asn1rs_model::protobuf::preserve_unknown_fields(rust);
+                }
+            });
+            #[cfg(feature = "protobuf")]
+            generator.set_protobuf_services(self.protobuf_services.clone());
+            if let Some(codecs) = &self.codecs {
+                generator.set_codecs(codecs);
+            }
 
             custom_adjustments(&mut generator);
 
-            files.insert(
+            files.push((
                 model.name.clone(),
                 generator
                     .to_string()
@@ -81,7 +289,130 @@ impl Converter {
                         Ok::<_, Error>(file)
                     })
                     .collect::<Result<Vec<_>, _>>()?,
-            );
+            ));
+        }
+
+        if self.rust_mod_file || self.rust_mod_oid_tree {
+            // all modules, also the ones not converted again
+            let rust = models
+                .iter()
+                .map(|model| model.to_rust_with_naming(&scope[..], self.inline_naming))
+                .collect::<Vec<_>>();
+            let content =
+                RustGenerator::mod_file(&rust.iter().collect::<Vec<_>>(), self.rust_mod_oid_tree);
+            ::std::fs::write(directory.as_ref().join("mod.rs"), content)?;
+            files.push(("mod".to_string(), vec!["mod.rs".to_string()]));
+        }
+
+        if self.rust_fmt && !files.is_empty() {
+            let output = std::process::Command::new(
+                std::env::var_os("RUSTFMT").unwrap_or_else(|| "rustfmt".into()),
+            )
+            .args(["--edition", "2021"])
+            .args(
+                files
+                    .iter()
+                    .flat_map(|(_, files)| files)
+                    .map(|file| directory.as_ref().join(file)),
+            )
+            .output()?;
+            if !output.status.success() {
+                return Err(Error::Rustfmt(
+                    String::from_utf8_lossy(&output.stderr).into_owned(),
+                ));
+            }
+        }
+
+        Ok(files)
+    }
+
+    /// Writes an AFL/libFuzzer dictionary for each definition (PDU) of the loaded models
+    pub fn to_fuzz_dictionaries<D: AsRef<Path>>(
+        &self,
+        directory: D,
+    ) -> Result<Vec<(String, Vec<String>)>, Error> {
+        self.validate()?;
+        let models = self.models.try_resolve_all()?;
+        let scope = models.iter().collect::<Vec<_>>();
+        let mut files = Vec::with_capacity(models.len());
+
+        for model in models.iter().filter(|m| self.converts(&m.name)) {
+            let mut generator = FuzzDictionaryGenerator::default();
+            generator.add_model(model.to_rust_with_naming(&scope[..], self.inline_naming));
+
+            files.push((
+                model.name.clone(),
+                generator
+                    .to_string()
+                    .unwrap_or_else(|e| match e {})
+                    .into_iter()
+                    .map(|(file, content)| {
+                        ::std::fs::write(directory.as_ref().join(&file), content)?;
+                        Ok::<_, Error>(file)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ));
+        }
+
+        Ok(files)
+    }
+
+    /// Writes a JSON Schema of the JER representation for each of the loaded models, see
+    /// [`JsonSchemaGenerator`]
+    pub fn to_json_schemas<D: AsRef<Path>>(
+        &self,
+        directory: D,
+    ) -> Result<Vec<(String, Vec<String>)>, Error> {
+        self.validate()?;
+        let models = self.models.try_resolve_all()?;
+        let scope = models.iter().collect::<Vec<_>>();
+        let mut files = Vec::with_capacity(models.len());
+
+        for model in models.iter().filter(|m| self.converts(&m.name)) {
+            let mut generator = JsonSchemaGenerator::default();
+            generator.add_model(model.to_rust_with_naming(&scope[..], self.inline_naming));
+
+            files.push((
+                model.name.clone(),
+                generator
+                    .to_string()
+                    .unwrap_or_else(|e| match e {})
+                    .into_iter()
+                    .map(|(file, content)| {
+                        ::std::fs::write(directory.as_ref().join(&file), content)?;
+                        Ok::<_, Error>(file)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ));
+        }
+
+        Ok(files)
+    }
+
+    /// Writes a C header and the Rust file with the matching `extern "C"` functions for each of
+    /// the loaded models, see [`CGenerator`]
+    pub fn to_c<D: AsRef<Path>>(&self, directory: D) -> Result<Vec<(String, Vec<String>)>, Error> {
+        self.validate()?;
+        let models = self.models.try_resolve_all()?;
+        let scope = models.iter().collect::<Vec<_>>();
+        let mut files = Vec::with_capacity(models.len());
+
+        for model in models.iter().filter(|m| self.converts(&m.name)) {
+            let mut generator = CGenerator::default();
+            generator.add_model(model.to_rust_with_naming(&scope[..], self.inline_naming));
+
+            files.push((
+                model.name.clone(),
+                generator
+                    .to_string()
+                    .unwrap_or_else(|e| match e {})
+                    .into_iter()
+                    .map(|(file, content)| {
+                        ::std::fs::write(directory.as_ref().join(&file), content)?;
+                        Ok::<_, Error>(file)
+                    })
+                    .collect::<Result<Vec<_>, _>>()?,
+            ));
         }
 
         Ok(files)
@@ -91,18 +422,29 @@ impl Converter {
     pub fn to_protobuf<D: AsRef<Path>>(
         &self,
         directory: D,
-    ) -> Result<HashMap<String, Vec<String>>, Error> {
+    ) -> Result<Vec<(String, Vec<String>)>, Error> {
         use asn1rs_model::protobuf::ToProtobufModel;
 
+        self.validate()?;
         let models = self.models.try_resolve_all()?;
         let scope = models.iter().collect::<Vec<_>>();
-        let mut files = HashMap::with_capacity(models.len());
+        let mut files = Vec::with_capacity(models.len());
 
-        for model in &models {
+        for model in models.iter().filter(|m| self.converts(&m.name)) {
             let mut generator = asn1rs_model::generate::protobuf::ProtobufDefGenerator::default();
-            generator.add_model(model.to_rust_with_scope(&scope[..]).to_protobuf());
+            generator.set_field_numbers(self.protobuf_field_numbers.clone());
+            generator.set_services(self.protobuf_services.clone());
+            let mut rust = model.to_rust_with_naming(&scope[..], self.inline_naming);
+            if self.protobuf_fixed_integers {
+                asn1rs_model::protobuf::use_fixed_for_full_range_integers(&mut rust);
+            }
+            let mut protobuf = rust.to_protobuf();
+            if self.protobuf_maps {
+                asn1rs_model::protobuf::use_maps(&mut protobuf);
+            }
+            generator.add_model(protobuf);
 
-            files.insert(
+            files.push((
                 model.name.clone(),
                 generator
                     .to_string()?
@@ -112,7 +454,7 @@ impl Converter {
                         Ok::<_, Error>(file)
                     })
                     .collect::<Result<Vec<_>, _>>()?,
-            );
+            ));
         }
 
         Ok(files)
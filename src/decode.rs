@@ -0,0 +1,209 @@
+//! Decodes captured payloads against a schema that is loaded at runtime, see the `decode`
+//! subcommand of the command line interface.
+
+use crate::output::json_value;
+use asn1rs::rw::Notation;
+use asn1rs::util::Error as HexError;
+use asn1rs::value::{BestEffort, Error as ValueError, Schema, Value};
+use std::path::Path;
+
+#[derive(Debug)]
+pub enum Error {
+    Io(std::io::Error),
+    /// The payload is not valid hex, see [`asn1rs::util::from_hex`]
+    Hex(HexError),
+    Value(ValueError),
+}
+
+impl From<std::io::Error> for Error {
+    fn from(e: std::io::Error) -> Self {
+        Error::Io(e)
+    }
+}
+
+impl From<HexError> for Error {
+    fn from(e: HexError) -> Self {
+        Error::Hex(e)
+    }
+}
+
+impl From<ValueError> for Error {
+    fn from(e: ValueError) -> Self {
+        Error::Value(e)
+    }
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum Encoding {
+    Uper,
+    Der,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum PrintFormat {
+    /// ASN.1 value notation
+    Value,
+    /// Generic String Encoding Rules (RFC 3641)
+    Gser,
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum TranscodeTarget {
+    Uper,
+    Der,
+    /// JSON Encoding Rules, see [`json_value`]
+    #[value(alias = "jer")]
+    Json,
+}
+
+/// The result of [`transcode`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Transcoded {
+    Binary(Vec<u8>),
+    Text(String),
+}
+
+impl Transcoded {
+    pub fn to_bytes(&self) -> &[u8] {
+        match self {
+            Transcoded::Binary(bytes) => bytes,
+            Transcoded::Text(text) => text.as_bytes(),
+        }
+    }
+}
+
+/// Binary payloads are displayed as hex digits
+impl std::fmt::Display for Transcoded {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Transcoded::Binary(bytes) => bytes.iter().try_for_each(|b| write!(f, "{:02x}", b)),
+            Transcoded::Text(text) => f.write_str(text),
+        }
+    }
+}
+
+/// Loads the schema from the given ASN.1 files
+pub fn load_schema<P: AsRef<Path>>(files: &[P]) -> Result<Schema, Error> {
+    let sources = files
+        .iter()
+        .map(std::fs::read_to_string)
+        .collect::<Result<Vec<_>, _>>()?;
+    Ok(Schema::parse_all(sources.iter().map(String::as_str))?)
+}
+
+/// Reads the payload from the given file or from stdin for `-`. Unless `binary` is set, the
+/// payload is expected as hex digits, see [`asn1rs::util::from_hex`] for the accepted separators.
+pub fn read_payload(file: &str, binary: bool) -> Result<Vec<u8>, Error> {
+    let content = if file == "-" {
+        let mut content = Vec::new();
+        std::io::Read::read_to_end(&mut std::io::stdin(), &mut content)?;
+        content
+    } else {
+        std::fs::read(file)?
+    };
+    if binary {
+        Ok(content)
+    } else {
+        Ok(asn1rs::util::from_hex(&String::from_utf8_lossy(&content))?)
+    }
+}
+
+pub fn decode(
+    schema: &Schema,
+    pdu: &str,
+    encoding: Encoding,
+    payload: &[u8],
+) -> Result<Value, Error> {
+    Ok(match encoding {
+        Encoding::Uper => schema.decode_uper(pdu, payload)?,
+        Encoding::Der => schema.decode_der(pdu, payload)?,
+    })
+}
+
+/// Decodes as much of the payload as possible, see [`asn1rs::value::best_effort`]
+pub fn decode_best_effort(
+    schema: &Schema,
+    pdu: &str,
+    encoding: Encoding,
+    payload: &[u8],
+) -> BestEffort {
+    match encoding {
+        Encoding::Uper => schema.decode_uper_best_effort(pdu, payload),
+        Encoding::Der => schema.decode_der_best_effort(pdu, payload),
+    }
+}
+
+pub fn transcode(
+    schema: &Schema,
+    pdu: &str,
+    value: &Value,
+    target: TranscodeTarget,
+) -> Result<Transcoded, Error> {
+    Ok(match target {
+        TranscodeTarget::Uper => Transcoded::Binary(schema.encode_uper(pdu, value)?),
+        TranscodeTarget::Der => Transcoded::Binary(schema.encode_der(pdu, value)?),
+        TranscodeTarget::Json => Transcoded::Text(json_value(value)),
+    })
+}
+
+pub fn print(value: &Value, format: PrintFormat) -> String {
+    match format {
+        PrintFormat::Value => value.to_notation(Notation::Asn1),
+        PrintFormat::Gser => value.to_notation(Notation::Gser),
+        PrintFormat::Json => json_value(value),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_decode_and_print() {
+        let schema = Schema::parse(
+            r#"Example DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+                Point ::= SEQUENCE {
+                    x INTEGER (0..255),
+                    y INTEGER (0..255),
+                    label UTF8String OPTIONAL
+                }
+            END"#,
+        )
+        .unwrap();
+        let value = decode(&schema, "Point", Encoding::Uper, &[0x01, 0x01, 0x00]).unwrap();
+        assert_eq!("{\n  x 2,\n  y 2\n}", print(&value, PrintFormat::Value));
+        assert_eq!("{ x 2, y 2 }", print(&value, PrintFormat::Gser));
+        assert_eq!(r#"{"x":2,"y":2}"#, print(&value, PrintFormat::Json));
+    }
+
+    #[test]
+    pub fn test_transcode() {
+        let schema = Schema::parse(
+            r#"Example DEFINITIONS AUTOMATIC TAGS ::=
+            BEGIN
+                Point ::= SEQUENCE {
+                    x INTEGER (0..255),
+                    y INTEGER (0..255)
+                }
+            END"#,
+        )
+        .unwrap();
+        let value = decode(&schema, "Point", Encoding::Uper, &[0x02, 0x80]).unwrap();
+        let der = transcode(&schema, "Point", &value, TranscodeTarget::Der).unwrap();
+        assert_eq!("300780010281020080", der.to_string());
+        let Transcoded::Binary(der) = der else {
+            panic!("DER is binary");
+        };
+        let value = decode(&schema, "Point", Encoding::Der, &der).unwrap();
+        assert_eq!(
+            Transcoded::Binary(vec![0x02, 0x80]),
+            transcode(&schema, "Point", &value, TranscodeTarget::Uper).unwrap()
+        );
+        assert_eq!(
+            Transcoded::Text(r#"{"x":2,"y":128}"#.to_string()),
+            transcode(&schema, "Point", &value, TranscodeTarget::Json).unwrap()
+        );
+    }
+}
@@ -0,0 +1,45 @@
+use crate::descriptor::octetstring::{Constraint, NoConstraint};
+use crate::descriptor::{Readable, ReadableType, Reader, Writable, WritableType, Writer};
+use core::marker::PhantomData;
+
+/// A field of the type `ANY` or of an open type, whose value is kept as [`DerAny`]. The DER
+/// reader captures the complete TLV and the DER writer writes it verbatim. Other codecs encode
+/// the bytes as `OCTET STRING` with the given constraint.
+pub struct Any<C: Constraint = NoConstraint>(PhantomData<C>);
+
+/// The complete TLV - identifier, length and content octets - of a value of the type `ANY` or
+/// of an open type in DER, so that values covered by a signature survive byte-exact
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash)]
+pub struct DerAny(pub Vec<u8>);
+
+impl<C: Constraint> WritableType for Any<C> {
+    type Type = DerAny;
+
+    #[inline]
+    fn write_value<W: Writer>(writer: &mut W, value: &Self::Type) -> Result<(), W::Error> {
+        writer.write_any::<C>(&value.0)
+    }
+}
+
+impl<C: Constraint> ReadableType for Any<C> {
+    type Type = DerAny;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_any::<C>().map(DerAny)
+    }
+}
+
+impl Writable for DerAny {
+    #[inline]
+    fn write<W: Writer>(&self, writer: &mut W) -> Result<(), W::Error> {
+        Any::<NoConstraint>::write_value(writer, self)
+    }
+}
+
+impl Readable for DerAny {
+    #[inline]
+    fn read<R: Reader>(reader: &mut R) -> Result<Self, R::Error> {
+        Any::<NoConstraint>::read_value(reader)
+    }
+}
@@ -10,6 +10,23 @@ pub trait Constraint: super::common::Constraint {
     const MIN: Option<u64> = None;
     const MAX: Option<u64> = None;
     const EXTENSIBLE: bool = false;
+    /// Whether the BIT STRING has a named bit list, see [`canonical_bit_len`]
+    const NAMED_BITS: bool = false;
+}
+
+/// The length of a BIT STRING with a named bit list in the canonical encodings: trailing zero
+/// bits are removed, or added to reach the lower bound of the size constraint,
+/// ITU-T X.690 | ISO/IEC 8825-1, 11.2.2 and ITU-T X.691 | ISO/IEC 8825-2, 16.3
+pub fn canonical_bit_len(value: &[u8], bit_len: u64, min: Option<u64>) -> u64 {
+    let significant = (0..bit_len)
+        .rev()
+        .find(|bit| {
+            value
+                .get((bit / 8) as usize)
+                .is_some_and(|byte| byte & (0x80_u8 >> (bit % 8)) != 0)
+        })
+        .map_or(0, |bit| bit + 1);
+    significant.max(min.unwrap_or(0))
 }
 
 #[derive(Default)]
@@ -149,6 +166,15 @@ impl BitVec {
 pub mod tests {
     use super::*;
 
+    #[test]
+    fn canonical_bit_len_without_trailing_zeros() {
+        assert_eq!(0, canonical_bit_len(&[0x00, 0x00], 16, None));
+        assert_eq!(6, canonical_bit_len(&[0b1000_0100, 0x00], 16, None));
+        assert_eq!(9, canonical_bit_len(&[0x00, 0x80], 16, None));
+        assert_eq!(8, canonical_bit_len(&[0b1000_0100, 0x00], 16, Some(8)));
+        assert_eq!(8, canonical_bit_len(&[0x80], 1, Some(8)));
+    }
+
     #[test]
     fn trailing_bit_len_repr() {
         for bit_len in 0..(BYTE_LEN * 10) {
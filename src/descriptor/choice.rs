@@ -8,6 +8,11 @@ pub trait Constraint: super::common::Constraint + Sized {
     const VARIANT_COUNT: u64;
     const STD_VARIANT_COUNT: u64;
     const EXTENSIBLE: bool = false;
+    /// The ASN.1 identifiers of the variants, indexed by the choice index
+    const VARIANT_NAMES: &'static [&'static str] = &[];
+    /// The protobuf field numbers of the variants, indexed by the choice index. If empty, the
+    /// variants are numbered by their choice index, starting at 1.
+    const PROTOBUF_FIELD_NUMBERS: &'static [u32] = &[];
 
     fn to_choice_index(&self) -> u64;
 
@@ -1,5 +1,10 @@
 use asn1rs_model::asn::Tag;
 
+/// The constraint every descriptor constraint builds upon. Implementing it (together with the
+/// constraint trait of a descriptor) is the extension point for custom primitive types, see
+/// the [module documentation](super) of the descriptors.
 pub trait Constraint {
+    /// The tag used by tag-aware codecs such as DER, usually the default tag of the ASN.1 type
+    /// (e.g. [`Tag::DEFAULT_INTEGER`]) unless the type is tagged explicitly
     const TAG: Tag;
 }
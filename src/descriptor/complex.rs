@@ -1,6 +1,8 @@
 use crate::descriptor::{Readable, ReadableType, Reader, Writable, WritableType, Writer};
 use core::marker::PhantomData;
 
+/// Describes a type with its own [`Readable`] and [`Writable`] implementation, such as a
+/// generated type or a custom primitive type (see the [module documentation](super))
 pub struct Complex<V, T: Constraint>(PhantomData<T>, PhantomData<V>);
 
 pub trait Constraint: super::common::Constraint {}
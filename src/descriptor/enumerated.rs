@@ -8,10 +8,24 @@ pub trait Constraint: super::common::Constraint + Sized {
     const VARIANT_COUNT: u64;
     const STD_VARIANT_COUNT: u64;
     const EXTENSIBLE: bool = false;
+    /// The ASN.1 identifiers of the variants, indexed by the choice index
+    const VARIANT_NAMES: &'static [&'static str] = &[];
 
     fn to_choice_index(&self) -> u64;
 
     fn from_choice_index(index: u64) -> Option<Self>;
+
+    /// The number of the variant as declared in the ASN.1 definition, which is what BER and DER
+    /// serialize, while the packed encodings serialize the choice index
+    #[inline]
+    fn to_number(&self) -> u64 {
+        self.to_choice_index()
+    }
+
+    #[inline]
+    fn from_number(number: u64) -> Option<Self> {
+        Self::from_choice_index(number)
+    }
 }
 
 impl<C: Constraint> WritableType for Enumerated<C> {
@@ -0,0 +1,156 @@
+//! Describes the generated types at runtime - their fields, tags and constraints - so that
+//! applications can build forms or validators without parsing the ASN.1 definition again.
+//!
+//! The `#[asn(..)]` attribute implements [`Metadata`] for every type:
+//!
+//! ```rust
+//! use asn1rs::descriptor::metadata::{Presence, TypeKind, ValueInfo};
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Reading {
+//!     #[asn(integer(-40..125))]
+//!     pub celsius: i8,
+//!     #[asn(optional(utf8string(size(1..16))))]
+//!     pub label: Option<String>,
+//! }
+//!
+//! let TypeKind::Sequence { fields, .. } = Reading::METADATA.kind else {
+//!     unreachable!()
+//! };
+//! assert_eq!("celsius", fields[0].name);
+//! assert!(matches!(
+//!     fields[0].r#type,
+//!     ValueInfo::Integer { min: Some(-40), max: Some(125), .. }
+//! ));
+//! assert_eq!(Presence::Optional, fields[1].presence);
+//! ```
+
+use asn1rs_model::asn::{Charset, Tag};
+use core::marker::PhantomData;
+
+/// A type with a description of its ASN.1 definition, implemented by the `#[asn(..)]` attribute
+pub trait Metadata {
+    const METADATA: TypeInfo;
+}
+
+/// Looks up the [`Metadata`] of a type referenced by [`ValueInfo::Complex`], which custom types
+/// (see the [module documentation](super) of the descriptors) do not need to implement. If the
+/// type implements [`Metadata`], the inherent `metadata` method takes precedence over the one
+/// of [`ProbeFallback`].
+#[doc(hidden)]
+pub struct Probe<T>(pub PhantomData<T>);
+
+impl<T: Metadata> Probe<T> {
+    #[inline]
+    pub fn metadata(&self) -> Option<TypeInfo> {
+        Some(T::METADATA)
+    }
+}
+
+#[doc(hidden)]
+pub trait ProbeFallback {
+    #[inline]
+    fn metadata(&self) -> Option<TypeInfo> {
+        None
+    }
+}
+
+impl<T> ProbeFallback for Probe<T> {}
+
+/// The description of a generated type
+#[derive(Debug, Copy, Clone)]
+pub struct TypeInfo {
+    /// The name of the Rust type
+    pub name: &'static str,
+    pub tag: Tag,
+    pub kind: TypeKind,
+}
+
+#[derive(Debug, Copy, Clone)]
+pub enum TypeKind {
+    Sequence {
+        fields: &'static [FieldInfo],
+        /// The index of the field after which the extension marker is placed, if extensible
+        extended_after_field: Option<usize>,
+    },
+    Set {
+        fields: &'static [FieldInfo],
+        /// The index of the field after which the extension marker is placed, if extensible
+        extended_after_field: Option<usize>,
+    },
+    Enumerated {
+        /// The ASN.1 identifiers of the variants in the order of their enumeration index
+        variants: &'static [&'static str],
+        extensible: bool,
+    },
+    Choice {
+        /// The alternatives, whose [`FieldInfo::presence`] is always [`Presence::Required`]
+        variants: &'static [FieldInfo],
+        extensible: bool,
+    },
+    /// A type assignment of a single value, such as `Celsius ::= INTEGER (-40..125)`, which is
+    /// generated as a tuple struct
+    Wrapped(ValueInfo),
+}
+
+/// The description of a field or of a `CHOICE` alternative
+#[derive(Debug, Copy, Clone)]
+pub struct FieldInfo {
+    /// The ASN.1 identifier, as used by the JSON Encoding Rules
+    pub name: &'static str,
+    pub tag: Tag,
+    pub presence: Presence,
+    pub r#type: ValueInfo,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Presence {
+    Required,
+    Optional,
+    /// Optional, with the given ASN.1 value notation as default value
+    Default(&'static str),
+}
+
+/// The type and constraints of a value
+#[derive(Debug, Copy, Clone)]
+pub enum ValueInfo {
+    Boolean,
+    /// The bounds are absent if not constrained or exceeding the range of an `i128`
+    Integer {
+        min: Option<i128>,
+        max: Option<i128>,
+        extensible: bool,
+    },
+    String {
+        charset: Charset,
+        size: SizeInfo,
+        permitted_alphabet: Option<&'static str>,
+    },
+    OctetString(SizeInfo),
+    BitString(SizeInfo),
+    Null,
+    SequenceOf {
+        item: &'static ValueInfo,
+        size: SizeInfo,
+    },
+    SetOf {
+        item: &'static ValueInfo,
+        size: SizeInfo,
+    },
+    /// Another type, whose description is returned by `metadata` unless it is a custom type
+    /// without [`Metadata`]
+    Complex {
+        name: &'static str,
+        metadata: fn() -> Option<TypeInfo>,
+    },
+}
+
+/// A `SIZE` constraint
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub struct SizeInfo {
+    pub min: Option<u64>,
+    pub max: Option<u64>,
+    pub extensible: bool,
+}
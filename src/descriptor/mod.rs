@@ -1,3 +1,66 @@
+//! Descriptors connect Rust types with the ASN.1 type they represent. Generated code and the
+//! `#[asn(..)]` attribute describe each field with a descriptor type (e.g.
+//! `Integer<u8, MyConstraint>`), whose [`ReadableType`] and [`WritableType`] implementations
+//! forward the value and its constraint to a [`Reader`] or [`Writer`] - the codec.
+//!
+//! # Custom primitive types
+//!
+//! A downstream crate can add its own leaf types by implementing [`Readable`] and [`Writable`]
+//! in terms of an existing descriptor. The constraint of that descriptor is an ordinary type
+//! implementing [`common::Constraint`] and the constraint trait of the descriptor (with their
+//! `const`s defaulting to unconstrained). Because all codecs only see the underlying
+//! descriptor, the custom type is encoded by all of them the same way.
+//!
+//! The following fixed-point temperature is encoded as `INTEGER (-27315..100000)` in steps of
+//! 0.01 degree Celsius:
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//! use asn1rs::descriptor::{common, numbers, Integer};
+//! use asn1rs::model::asn::Tag;
+//!
+//! #[derive(Debug, Copy, Clone, PartialEq)]
+//! pub struct Celsius(pub f32);
+//!
+//! pub struct CentiCelsius;
+//! impl common::Constraint for CentiCelsius {
+//!     const TAG: Tag = Tag::DEFAULT_INTEGER;
+//! }
+//! impl numbers::Constraint<i32> for CentiCelsius {
+//!     const MIN: Option<i64> = Some(-27315);
+//!     const MAX: Option<i64> = Some(100000);
+//!     const MIN_T: Option<i32> = Some(-27315);
+//!     const MAX_T: Option<i32> = Some(100000);
+//! }
+//!
+//! impl Writable for Celsius {
+//!     fn write<W: Writer>(&self, writer: &mut W) -> Result<(), W::Error> {
+//!         let centi = (self.0 * 100.0).round() as i32;
+//!         Integer::<i32, CentiCelsius>::write_value(writer, &centi)
+//!     }
+//! }
+//!
+//! impl Readable for Celsius {
+//!     fn read<R: Reader>(reader: &mut R) -> Result<Self, R::Error> {
+//!         let centi = Integer::<i32, CentiCelsius>::read_value(reader)?;
+//!         Ok(Celsius(centi as f32 / 100.0))
+//!     }
+//! }
+//!
+//! let mut writer = UperWriter::default();
+//! writer.write(&Celsius(21.5)).unwrap();
+//! assert_eq!(17, writer.bit_len());
+//!
+//! let mut reader = writer.as_reader();
+//! assert_eq!(Celsius(21.5), reader.read::<Celsius>().unwrap());
+//! ```
+//!
+//! Such a type can then be used in generated and annotated types like any other type, for
+//! example as field `#[asn(complex(Celsius, tag(UNIVERSAL(2))))] temperature: Celsius` of an
+//! `#[asn(sequence)]`. The tag passed to `complex` should match the [`common::Constraint::TAG`]
+//! of the descriptor.
+
+pub mod any;
 pub mod bitstring;
 pub mod boolean;
 pub mod choice;
@@ -5,21 +68,29 @@ pub mod common;
 pub mod complex;
 pub mod default;
 pub mod enumerated;
+pub mod graphicstring;
 pub mod ia5string;
+pub mod metadata;
 pub mod null;
 pub mod numbers;
 pub mod numericstring;
+pub mod objectdescriptor;
 pub mod octetstring;
 pub mod optional;
 pub mod printablestring;
+pub mod schema;
 pub mod sequence;
 pub mod sequenceof;
 pub mod set;
 pub mod setof;
+pub mod teletexstring;
 pub mod utf8string;
+pub mod videotexstring;
 pub mod visiblestring;
 
 pub use crate::descriptor::null::Null;
+pub use any::Any;
+pub use any::DerAny;
 pub use bitstring::BitString;
 pub use bitstring::BitVec;
 pub use boolean::Boolean;
@@ -27,25 +98,37 @@ pub use choice::Choice;
 pub use complex::Complex;
 pub use default::DefaultValue;
 pub use enumerated::Enumerated;
+pub use graphicstring::GraphicString;
 pub use ia5string::Ia5String;
+pub use metadata::Metadata;
 pub use null::NullT;
 pub use numbers::Integer;
 pub use numericstring::NumericString;
+pub use objectdescriptor::ObjectDescriptor;
 pub use octetstring::OctetString;
+#[cfg(feature = "uuid")]
+pub use octetstring::UuidOctetString;
 pub use printablestring::PrintableString;
+pub use schema::asn1_module;
+pub use schema::Asn1Schema;
 pub use sequence::Sequence;
 pub use sequenceof::SequenceOf;
 pub use set::Set;
 pub use setof::SetOf;
+pub use teletexstring::TeletexString;
 pub use utf8string::Utf8String;
+pub use videotexstring::VideotexString;
 pub use visiblestring::VisibleString;
 
 pub mod prelude {
     pub use super::bitstring::BitVec;
+    pub use super::Asn1Schema;
+    pub use super::Metadata;
     pub use super::Null;
     pub use super::Readable;
     pub use super::ReadableType;
     pub use super::Reader;
+    pub use super::Rewind;
     pub use super::Writable;
     pub use super::WritableType;
     pub use super::Writer;
@@ -112,17 +195,91 @@ pub trait Reader {
 
     fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error>;
 
+    /// Reads an OCTET STRING of exactly 16 bytes as UUID, see [`octetstring::UuidOctetString`]
+    #[cfg(feature = "uuid")]
+    fn read_uuid<C: octetstring::Constraint>(&mut self) -> Result<uuid::Uuid, Self::Error>;
+
     fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error>;
 
     fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error>;
 
     fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error>;
+
+    /// Reads a value of the type `ANY` or of an open type, see [`any::Any`]. Unless overridden,
+    /// the value is read as `OCTET STRING`.
+    #[inline]
+    fn read_any<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
+        self.read_octet_string::<C>()
+    }
+
+    /// The fields of the protobuf message currently being read, that were not read as one of
+    /// its known fields. Other representations have no unknown fields.
+    #[cfg(feature = "protobuf")]
+    #[inline]
+    fn read_protobuf_unknown_fields(
+        &mut self,
+    ) -> Result<crate::protocol::protobuf::UnknownFields, Self::Error> {
+        Ok(Default::default())
+    }
 }
 
+/// A [`Reader`] that can return to a previous read position, so that the input can be decoded
+/// as one of several types without cloning it:
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Small {
+///     #[asn(integer(0..7))]
+///     pub value: u8,
+/// }
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Large {
+///     #[asn(integer(0..65535))]
+///     pub value: u16,
+/// }
+///
+/// let mut writer = UperWriter::default();
+/// writer.write(&Small { value: 5 }).unwrap();
+///
+/// let mut reader = writer.as_reader();
+/// assert!(reader.try_read::<Large>().is_err());
+/// assert_eq!(Small { value: 5 }, reader.try_read::<Small>().unwrap());
+/// ```
+pub trait Rewind: Reader {
+    type Checkpoint;
+
+    /// The current read position, to return to with [`Rewind::restore`]
+    fn checkpoint(&self) -> Self::Checkpoint;
+
+    fn restore(&mut self, checkpoint: Self::Checkpoint);
+
+    /// Reads a `T` or - if that fails - restores the read position, so that another type can be
+    /// tried
+    fn try_read<T: Readable>(&mut self) -> Result<T, Self::Error>
+    where
+        Self: Sized,
+    {
+        let checkpoint = self.checkpoint();
+        let result = T::read(self);
+        if result.is_err() {
+            self.restore(checkpoint);
+        }
+        result
+    }
+}
+
+/// A type that can be read from any [`Reader`], usually by delegating to the descriptor
+/// describing it. See the [module documentation](self) on how to implement custom types.
 pub trait Readable: Sized {
     fn read<R: Reader>(reader: &mut R) -> Result<Self, R::Error>;
 }
 
+/// A descriptor, which reads values of [`ReadableType::Type`] with the help of its constraint
 pub trait ReadableType {
     type Type: Sized;
 
@@ -138,6 +295,14 @@ impl<T: Readable> ReadableType for T {
     }
 }
 
+/// A boxed value is read like the value, for types that refer to themselves
+impl<T: Readable> Readable for Box<T> {
+    #[inline]
+    fn read<R: Reader>(reader: &mut R) -> Result<Self, R::Error> {
+        T::read(reader).map(Box::new)
+    }
+}
+
 pub trait Writer {
     type Error;
 
@@ -154,10 +319,26 @@ pub trait Writer {
         f: F,
     ) -> Result<(), Self::Error>;
 
+    #[inline]
     fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
         &mut self,
         slice: &[T::Type],
-    ) -> Result<(), Self::Error>;
+    ) -> Result<(), Self::Error> {
+        self.write_sequence_of_iter::<C, T, _>(slice.len(), slice)
+    }
+
+    /// Writes the `len` values of the given iterator as `SEQUENCE OF` without requiring them in
+    /// a `Vec`, so that producers can stream the values from an iterator or channel into the
+    /// codec. Codecs that encode the length ahead of the values (such as UPER) fail if the
+    /// iterator does not yield exactly `len` values.
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
+        &mut self,
+        len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>;
 
     fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
         &mut self,
@@ -225,18 +406,56 @@ pub trait Writer {
     fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error>;
 
     fn write_null<C: null::Constraint>(&mut self, value: &Null) -> Result<(), Self::Error>;
+
+    /// Writes a value of the type `ANY` or of an open type, see [`any::Any`]. Unless overridden,
+    /// the value is written as `OCTET STRING`.
+    #[inline]
+    fn write_any<C: octetstring::Constraint>(&mut self, value: &[u8]) -> Result<(), Self::Error> {
+        self.write_octet_string::<C>(value)
+    }
+
+    /// Writes the unknown fields of a protobuf message after its known fields. Other
+    /// representations ignore them.
+    #[cfg(feature = "protobuf")]
+    #[inline]
+    fn write_protobuf_unknown_fields(
+        &mut self,
+        _fields: &crate::protocol::protobuf::UnknownFields,
+    ) -> Result<(), Self::Error> {
+        Ok(())
+    }
 }
 
+/// A type that can be written to any [`Writer`], usually by delegating to the descriptor
+/// describing it. See the [module documentation](self) on how to implement custom types.
 pub trait Writable {
     fn write<W: Writer>(&self, writer: &mut W) -> Result<(), W::Error>;
 }
 
+/// A boxed value is written like the value, for types that refer to themselves
+impl<T: Writable> Writable for Box<T> {
+    #[inline]
+    fn write<W: Writer>(&self, writer: &mut W) -> Result<(), W::Error> {
+        T::write(self, writer)
+    }
+}
+
+/// A descriptor, which writes values of [`WritableType::Type`] with the help of its constraint
 pub trait WritableType {
     type Type;
 
     fn write_value<W: Writer>(writer: &mut W, value: &Self::Type) -> Result<(), W::Error>;
 }
 
+impl<T: Writable> WritableType for T {
+    type Type = T;
+
+    #[inline]
+    fn write_value<W: Writer>(writer: &mut W, value: &T) -> Result<(), W::Error> {
+        value.write(writer)
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
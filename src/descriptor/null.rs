@@ -34,7 +34,7 @@ impl<C: Constraint> ReadableType for NullT<C> {
     }
 }
 
-#[derive(Default, Debug, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
+#[derive(Default, Debug, Copy, Clone, Ord, PartialOrd, Eq, PartialEq, Hash)]
 pub struct Null;
 
 impl From<()> for Null {
@@ -8,9 +8,26 @@ pub struct Integer<T: Number = u64, C: Constraint<T> = NoConstraint>(
 );
 
 pub trait Number: Copy {
+    /// Whether the type can represent negative values
+    const SIGNED: bool;
+
+    /// Whether the type exceeds 64 bits, the codecs then use [`Number::to_i128`] and
+    /// [`Number::from_i128`] instead of their 64 bit counterparts
+    const WIDE: bool = false;
+
     fn to_i64(self) -> i64;
 
     fn from_i64(value: i64) -> Self;
+
+    #[inline]
+    fn to_i128(self) -> i128 {
+        i128::from(self.to_i64())
+    }
+
+    #[inline]
+    fn from_i128(value: i128) -> Self {
+        Self::from_i64(value as i64)
+    }
 }
 
 pub trait Constraint<T: Number>: super::common::Constraint {
@@ -20,6 +37,9 @@ pub trait Constraint<T: Number>: super::common::Constraint {
     const MIN_T: Option<T> = None;
     const MAX_T: Option<T> = None;
     const EXTENSIBLE: bool = false;
+    /// Whether protobuf encodes the value with the constant size wire types `fixed32`/`fixed64`
+    /// (`sfixed32`/`sfixed64` if the range includes negative values) instead of as varint
+    const PROTOBUF_FIXED: bool = false;
 }
 
 #[derive(Default)]
@@ -51,8 +71,11 @@ impl<T: Number, C: Constraint<T>> ReadableType for Integer<T, C> {
 }
 
 macro_rules! impl_number {
-    ( $($T:ident),+ ) => {$(
+    ( $signed:literal, $wide:literal: $($T:ident),+ ) => {$(
         impl Number for $T {
+            const SIGNED: bool = $signed;
+            const WIDE: bool = $wide;
+
             #[inline]
             fn to_i64(self) -> i64 {
                 self as i64
@@ -62,12 +85,24 @@ macro_rules! impl_number {
             fn from_i64(value: i64) -> Self {
                 value as $T
             }
+
+            #[inline]
+            fn to_i128(self) -> i128 {
+                self as i128
+            }
+
+            #[inline]
+            fn from_i128(value: i128) -> Self {
+                value as $T
+            }
         }
     )*}
 }
 
-impl_number!(u8, u16, u32, u64);
-impl_number!(i8, i16, i32, i64);
+impl_number!(false, false: u8, u16, u32, u64);
+impl_number!(true, false: i8, i16, i32, i64);
+impl_number!(false, true: u128);
+impl_number!(true, true: i128);
 
 /*
 macro_rules! read_write {
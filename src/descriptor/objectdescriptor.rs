@@ -0,0 +1,13 @@
+use asn1rs_model::asn::Tag;
+
+/// ITU-T X.680 | ISO/IEC 8824-1, 44.3: `ObjectDescriptor ::= [UNIVERSAL 7] IMPLICIT GraphicString`
+pub type ObjectDescriptor<C = NoConstraint> = super::GraphicString<C>;
+
+pub use super::graphicstring::Constraint;
+
+#[derive(Default)]
+pub struct NoConstraint;
+impl super::common::Constraint for NoConstraint {
+    const TAG: Tag = Tag::DEFAULT_OBJECT_DESCRIPTOR;
+}
+impl Constraint for NoConstraint {}
@@ -34,3 +34,29 @@ impl<C: Constraint> ReadableType for OctetString<C> {
         reader.read_octet_string::<C>()
     }
 }
+
+/// An OCTET STRING (SIZE(16)), that is represented as [`uuid::Uuid`] instead of as `Vec<u8>`.
+/// The 16 bytes of the UUID are encoded as they are, so the encoding equals the one of an
+/// [`OctetString`] with the same constraint.
+#[cfg(feature = "uuid")]
+pub struct UuidOctetString<C: Constraint = NoConstraint>(PhantomData<C>);
+
+#[cfg(feature = "uuid")]
+impl<C: Constraint> WritableType for UuidOctetString<C> {
+    type Type = uuid::Uuid;
+
+    #[inline]
+    fn write_value<W: Writer>(writer: &mut W, value: &Self::Type) -> Result<(), W::Error> {
+        writer.write_octet_string::<C>(value.as_bytes())
+    }
+}
+
+#[cfg(feature = "uuid")]
+impl<C: Constraint> ReadableType for UuidOctetString<C> {
+    type Type = uuid::Uuid;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_uuid::<C>()
+    }
+}
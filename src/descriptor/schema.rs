@@ -0,0 +1,69 @@
+//! The ASN.1 definitions of Rust types, so that a specification can be published for types that
+//! were written in Rust first.
+//!
+//! Adding `Asn1Schema` to the derives of a type annotated with `#[asn(..)]` implements
+//! [`Asn1Schema`] with the type assignment equivalent to the annotations. The `#[asn(..)]`
+//! attribute has to be placed above the `#[derive(..)]` attribute. Field and variant names are
+//! written as ASN.1 identifiers (`msg_id` becomes `msg-id`), which are converted back to the
+//! same Rust names when generating code from the module.
+//!
+//! ```rust
+//! use asn1rs::descriptor::{asn1_module, Asn1Schema};
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(enumerated)]
+//! #[derive(Debug, Default, Copy, Clone, PartialEq, Asn1Schema)]
+//! pub enum Unit {
+//!     #[default]
+//!     Meter,
+//!     Foot,
+//! }
+//!
+//! #[asn(sequence, extensible_after(unit))]
+//! #[derive(Debug, Default, PartialEq, Asn1Schema)]
+//! pub struct Length {
+//!     #[asn(integer(0..65535))]
+//!     pub value: u16,
+//!     #[asn(complex(Unit, tag(UNIVERSAL(10))))]
+//!     pub unit: Unit,
+//! }
+//!
+//! assert_eq!(
+//!     asn1_module("Lengths", &[Unit::ASN1_DEFINITION, Length::ASN1_DEFINITION]),
+//!     r"Lengths DEFINITIONS AUTOMATIC TAGS ::=
+//! BEGIN
+//!
+//! Unit ::= ENUMERATED {
+//!     meter,
+//!     foot
+//! }
+//!
+//! Length ::= SEQUENCE {
+//!     value INTEGER (0..65535),
+//!     unit Unit,
+//!     ...
+//! }
+//!
+//! END
+//! "
+//! );
+//! ```
+
+/// A type with a known ASN.1 definition, usually implemented through `#[derive(Asn1Schema)]`
+pub trait Asn1Schema {
+    /// The type assignment of this type, such as `Length ::= SEQUENCE { .. }`
+    const ASN1_DEFINITION: &'static str;
+}
+
+/// Encloses the given type assignments in an ASN.1 module with the given name. The module can
+/// be parsed into a model with `asn1rs::model::parse::Tokenizer` and `Model::try_from` (feature
+/// `model`).
+pub fn asn1_module(name: &str, definitions: &[&str]) -> String {
+    let mut module = format!("{} DEFINITIONS AUTOMATIC TAGS ::=\nBEGIN\n\n", name);
+    for definition in definitions {
+        module.push_str(definition.trim_end());
+        module.push_str("\n\n");
+    }
+    module.push_str("END\n");
+    module
+}
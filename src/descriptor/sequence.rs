@@ -8,6 +8,11 @@ pub trait Constraint: super::common::Constraint {
     const STD_OPTIONAL_FIELDS: u64;
     const FIELD_COUNT: u64;
     const EXTENDED_AFTER_FIELD: Option<u64>;
+    /// The ASN.1 identifiers of the fields, in the order they are written and read
+    const FIELD_NAMES: &'static [&'static str] = &[];
+    /// The protobuf field numbers of the fields, in the order they are written and read. If
+    /// empty, the fields are numbered by their position, starting at 1.
+    const PROTOBUF_FIELD_NUMBERS: &'static [u32] = &[];
 
     fn read_seq<R: Reader>(reader: &mut R) -> Result<Self, R::Error>
     where
@@ -0,0 +1,52 @@
+use crate::descriptor::{utf8string, ReadableType, Reader, WritableType, Writer};
+use asn1rs_model::asn::Tag;
+use core::marker::PhantomData;
+
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.6: TeletexString is not a known-multiplier character
+/// string type. Just like [`super::Utf8String`], it is encoded as an unconstrained number of
+/// octets - the size constraint is not PER-visible.
+pub struct TeletexString<C: Constraint = NoConstraint>(PhantomData<C>);
+
+pub trait Constraint: super::common::Constraint {
+    const MIN: Option<u64> = None;
+    const MAX: Option<u64> = None;
+    const EXTENSIBLE: bool = false;
+}
+
+#[derive(Default)]
+pub struct NoConstraint;
+impl super::common::Constraint for NoConstraint {
+    const TAG: Tag = Tag::DEFAULT_TELETEXT_STRING;
+}
+impl Constraint for NoConstraint {}
+
+/// Forwards the constraint of the TeletexString to the reader and writer of [`super::Utf8String`]
+struct AsUtf8String<C: Constraint>(PhantomData<C>);
+
+impl<C: Constraint> super::common::Constraint for AsUtf8String<C> {
+    const TAG: Tag = C::TAG;
+}
+
+impl<C: Constraint> utf8string::Constraint for AsUtf8String<C> {
+    const MIN: Option<u64> = C::MIN;
+    const MAX: Option<u64> = C::MAX;
+    const EXTENSIBLE: bool = C::EXTENSIBLE;
+}
+
+impl<C: Constraint> WritableType for TeletexString<C> {
+    type Type = String;
+
+    #[inline]
+    fn write_value<W: Writer>(writer: &mut W, value: &Self::Type) -> Result<(), W::Error> {
+        writer.write_utf8string::<AsUtf8String<C>>(value.as_str())
+    }
+}
+
+impl<C: Constraint> ReadableType for TeletexString<C> {
+    type Type = String;
+
+    #[inline]
+    fn read_value<R: Reader>(reader: &mut R) -> Result<Self::Type, <R as Reader>::Error> {
+        reader.read_utf8string::<AsUtf8String<C>>()
+    }
+}
@@ -0,0 +1,440 @@
+//! Runtime support for the C bindings written by the `CGenerator` of `asn1rs-model`. For each
+//! type of a module, the generator writes a C header with `#[repr(C)]` compatible declarations
+//! and a Rust file with the matching mirror types, [`FfiType`] implementations and
+//! `extern "C"` functions, which delegate to [`decode`], [`encode`] and [`free`].
+//!
+//! Values returned by a `*_decode` function are owned by the Rust allocator and must be released
+//! through the `*_free` function of their type. Values passed to a `*_encode` function are only
+//! read, so they can be assembled by the C code itself.
+
+use crate::codec::Codec;
+use crate::descriptor::bitstring::BitVec;
+use crate::descriptor::null::Null;
+use crate::descriptor::{Readable, Writable};
+use std::os::raw::c_char;
+use std::panic::{catch_unwind, AssertUnwindSafe};
+use std::ptr;
+
+/// The operation succeeded
+pub const ASN1RS_OK: i32 = 0;
+/// A required pointer argument or the data pointer of a non-empty list is null
+pub const ASN1RS_NULL_POINTER: i32 = -1;
+/// The codec id is unknown or its codec is not enabled, see [`Codec::id`]
+pub const ASN1RS_UNKNOWN_CODEC: i32 = -2;
+/// The bytes could not be decoded
+pub const ASN1RS_DECODE_FAILED: i32 = -3;
+/// The value could not be encoded
+pub const ASN1RS_ENCODE_FAILED: i32 = -4;
+/// The value is not valid for its type, such as a string that is not UTF-8 or an unknown
+/// variant of an ENUMERATED or CHOICE
+pub const ASN1RS_INVALID_VALUE: i32 = -5;
+/// The output buffer is too small, the required size is written nevertheless
+pub const ASN1RS_BUFFER_TOO_SMALL: i32 = -6;
+
+/// A NUL-terminated UTF-8 string, `len` excludes the terminating NUL
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsnString {
+    pub data: *mut c_char,
+    pub len: usize,
+}
+
+/// A list of `len` consecutive values, such as a SEQUENCE OF or the bytes of an OCTET STRING
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsnList<T> {
+    pub data: *mut T,
+    pub len: usize,
+}
+
+/// The bytes of an OCTET STRING
+pub type AsnBytes = AsnList<u8>;
+
+/// The `bit_len` bits of a BIT STRING, stored in `len` bytes starting with the most significant
+/// bit of the first byte
+#[repr(C)]
+#[derive(Debug)]
+pub struct AsnBits {
+    pub data: *mut u8,
+    pub len: usize,
+    pub bit_len: u64,
+}
+
+/// Converts a value into its C representation and back
+pub trait FfiType: Sized {
+    /// The `#[repr(C)]` compatible representation
+    type C;
+
+    /// Moves the value into its C representation, whose allocations must be released by
+    /// [`FfiType::free_c`]
+    fn into_c(self) -> Self::C;
+
+    /// Creates the value from its C representation, which is not modified
+    ///
+    /// # Safety
+    ///
+    /// All pointers of the C representation must either be null or valid for their length
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32>;
+
+    /// Releases the allocations of a C representation created by [`FfiType::into_c`] and
+    /// resets its pointers to null
+    ///
+    /// # Safety
+    ///
+    /// The C representation must have been created by [`FfiType::into_c`]
+    unsafe fn free_c(c: &mut Self::C);
+}
+
+macro_rules! ffi_identity {
+    ($($ty:ty),*) => {
+        $(
+            impl FfiType for $ty {
+                type C = $ty;
+
+                fn into_c(self) -> Self::C {
+                    self
+                }
+
+                unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+                    Ok(*c)
+                }
+
+                unsafe fn free_c(_c: &mut Self::C) {}
+            }
+        )*
+    };
+}
+
+ffi_identity!(bool, u8, i8, u16, i16, u32, i32, u64, i64);
+
+impl FfiType for Null {
+    type C = u8;
+
+    fn into_c(self) -> Self::C {
+        0
+    }
+
+    unsafe fn from_c(_c: &Self::C) -> Result<Self, i32> {
+        Ok(Null)
+    }
+
+    unsafe fn free_c(_c: &mut Self::C) {}
+}
+
+impl FfiType for String {
+    type C = AsnString;
+
+    fn into_c(self) -> Self::C {
+        let len = self.len();
+        let mut bytes = self.into_bytes();
+        bytes.push(0);
+        AsnString {
+            data: Box::into_raw(bytes.into_boxed_slice()).cast(),
+            len,
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        String::from_utf8(slice(c.data.cast::<u8>(), c.len)?.to_vec())
+            .map_err(|_| ASN1RS_INVALID_VALUE)
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        if !c.data.is_null() {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(
+                c.data.cast::<u8>(),
+                c.len + 1,
+            )));
+        }
+        c.data = ptr::null_mut();
+        c.len = 0;
+    }
+}
+
+impl<T: FfiType> FfiType for Vec<T> {
+    type C = AsnList<T::C>;
+
+    fn into_c(self) -> Self::C {
+        let values = self
+            .into_iter()
+            .map(T::into_c)
+            .collect::<Vec<_>>()
+            .into_boxed_slice();
+        AsnList {
+            len: values.len(),
+            data: Box::into_raw(values).cast(),
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        slice(c.data, c.len)?
+            .iter()
+            .map(|value| T::from_c(value))
+            .collect()
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        if !c.data.is_null() {
+            let mut values = Box::from_raw(ptr::slice_from_raw_parts_mut(c.data, c.len));
+            values.iter_mut().for_each(|value| T::free_c(value));
+        }
+        c.data = ptr::null_mut();
+        c.len = 0;
+    }
+}
+
+impl FfiType for BitVec {
+    type C = AsnBits;
+
+    fn into_c(self) -> Self::C {
+        let (bytes, bit_len) = self.split();
+        let bytes = bytes.into_boxed_slice();
+        AsnBits {
+            len: bytes.len(),
+            data: Box::into_raw(bytes).cast(),
+            bit_len,
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        if c.bit_len > c.len as u64 * 8 {
+            return Err(ASN1RS_INVALID_VALUE);
+        }
+        Ok(BitVec::from_bytes(
+            slice(c.data, c.len)?.to_vec(),
+            c.bit_len,
+        ))
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        if !c.data.is_null() {
+            drop(Box::from_raw(ptr::slice_from_raw_parts_mut(c.data, c.len)));
+        }
+        c.data = ptr::null_mut();
+        c.len = 0;
+        c.bit_len = 0;
+    }
+}
+
+/// A UUID is passed as its 16 bytes
+#[cfg(feature = "uuid")]
+impl FfiType for uuid::Uuid {
+    type C = AsnBytes;
+
+    fn into_c(self) -> Self::C {
+        self.as_bytes().to_vec().into_c()
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        uuid::Uuid::from_slice(slice(c.data, c.len)?).map_err(|_| ASN1RS_INVALID_VALUE)
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        Vec::<u8>::free_c(c)
+    }
+}
+
+/// An absent value is a null pointer
+impl<T: FfiType> FfiType for Option<T> {
+    type C = *mut T::C;
+
+    fn into_c(self) -> Self::C {
+        match self {
+            Some(value) => Box::into_raw(Box::new(value.into_c())),
+            None => ptr::null_mut(),
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        match c.as_ref() {
+            Some(value) => T::from_c(value).map(Some),
+            None => Ok(None),
+        }
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        if !c.is_null() {
+            let mut value = Box::from_raw(*c);
+            T::free_c(&mut value);
+        }
+        *c = ptr::null_mut();
+    }
+}
+
+/// A boxed value is a pointer, which must not be null
+impl<T: FfiType> FfiType for Box<T> {
+    type C = *mut T::C;
+
+    fn into_c(self) -> Self::C {
+        Box::into_raw(Box::new((*self).into_c()))
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        match c.as_ref() {
+            Some(value) => T::from_c(value).map(Box::new),
+            None => Err(ASN1RS_NULL_POINTER),
+        }
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        Option::<T>::free_c(c)
+    }
+}
+
+/// The `len` values at `data`, which may only be null if there are none
+unsafe fn slice<'a, T>(data: *const T, len: usize) -> Result<&'a [T], i32> {
+    if len == 0 {
+        Ok(&[])
+    } else if data.is_null() {
+        Err(ASN1RS_NULL_POINTER)
+    } else {
+        Ok(std::slice::from_raw_parts(data, len))
+    }
+}
+
+/// Decodes the `len` bytes at `bytes` with the codec of the given id, see [`Codec::id`], and
+/// writes the C representation of the value to `out`, which must be released by [`free`].
+///
+/// # Safety
+///
+/// `bytes` must be valid for `len` bytes and `out` must be valid for writes. The previous
+/// content of `out` is overwritten without being released.
+pub unsafe fn decode<T: Readable + FfiType>(
+    codec: u8,
+    bytes: *const u8,
+    len: usize,
+    out: *mut T::C,
+) -> i32 {
+    if out.is_null() {
+        return ASN1RS_NULL_POINTER;
+    }
+    let Some(codec) = Codec::from_id(codec) else {
+        return ASN1RS_UNKNOWN_CODEC;
+    };
+    let bytes = match slice(bytes, len) {
+        Ok(bytes) => bytes,
+        Err(status) => return status,
+    };
+    match catch_unwind(|| codec.decode::<T>(bytes)) {
+        Ok(Ok(value)) => {
+            out.write(value.into_c());
+            ASN1RS_OK
+        }
+        Ok(Err(_)) | Err(_) => ASN1RS_DECODE_FAILED,
+    }
+}
+
+/// Encodes the C representation of a value at `value` with the codec of the given id, see
+/// [`Codec::id`], into the buffer `out` of `capacity` bytes. The length of the encoded value is
+/// written to `written`, also if the buffer is too small, so that the call can be repeated with
+/// a sufficiently large buffer.
+///
+/// # Safety
+///
+/// `value` must point to a valid C representation, `out` must be valid for `capacity` bytes
+/// and `written` must be valid for writes.
+pub unsafe fn encode<T: Writable + FfiType>(
+    codec: u8,
+    value: *const T::C,
+    out: *mut u8,
+    capacity: usize,
+    written: *mut usize,
+) -> i32 {
+    if written.is_null() || (out.is_null() && capacity > 0) {
+        return ASN1RS_NULL_POINTER;
+    }
+    let Some(value) = value.as_ref() else {
+        return ASN1RS_NULL_POINTER;
+    };
+    let Some(codec) = Codec::from_id(codec) else {
+        return ASN1RS_UNKNOWN_CODEC;
+    };
+    let value = match T::from_c(value) {
+        Ok(value) => value,
+        Err(status) => return status,
+    };
+    let bytes = match catch_unwind(AssertUnwindSafe(|| codec.encode(&value))) {
+        Ok(Ok(bytes)) => bytes,
+        Ok(Err(_)) | Err(_) => return ASN1RS_ENCODE_FAILED,
+    };
+    written.write(bytes.len());
+    if bytes.len() > capacity {
+        ASN1RS_BUFFER_TOO_SMALL
+    } else {
+        ptr::copy_nonoverlapping(bytes.as_ptr(), out, bytes.len());
+        ASN1RS_OK
+    }
+}
+
+/// Releases the allocations of a value written by [`decode`], the value itself is not released
+/// and can be reused
+///
+/// # Safety
+///
+/// `value` must be null or point to a C representation written by [`decode`], which has not
+/// been modified since
+pub unsafe fn free<T: FfiType>(value: *mut T::C) {
+    if let Some(value) = value.as_mut() {
+        T::free_c(value);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_round_trip_through_c_representation() {
+        let value = vec![Some("first".to_string()), None, Some(String::new())];
+        let mut c = value.clone().into_c();
+        assert_eq!(3, c.len);
+        unsafe {
+            let first = &**c.data;
+            assert_eq!(5, first.len);
+            assert_eq!(0, *first.data.add(5));
+            assert!((*c.data.add(1)).is_null());
+            assert_eq!(value, Vec::<Option<String>>::from_c(&c).unwrap());
+            Vec::<Option<String>>::free_c(&mut c);
+        }
+        assert!(c.data.is_null());
+        assert_eq!(0, c.len);
+
+        let mut bits = BitVec::from_bytes(vec![0b1010_0000], 3).into_c();
+        unsafe {
+            assert_eq!(
+                BitVec::from_bytes(vec![0b1010_0000], 3),
+                BitVec::from_c(&bits).unwrap()
+            );
+            bits.bit_len = 9;
+            assert_eq!(Err(ASN1RS_INVALID_VALUE), BitVec::from_c(&bits).map(drop));
+            BitVec::free_c(&mut bits);
+        }
+    }
+
+    #[test]
+    pub fn test_invalid_c_representations() {
+        unsafe {
+            assert_eq!(
+                Ok(Vec::new()),
+                Vec::<u8>::from_c(&AsnList {
+                    data: ptr::null_mut(),
+                    len: 0,
+                })
+            );
+            assert_eq!(
+                Err(ASN1RS_NULL_POINTER),
+                Vec::<u8>::from_c(&AsnList {
+                    data: ptr::null_mut(),
+                    len: 1,
+                })
+            );
+            assert_eq!(
+                Err(ASN1RS_INVALID_VALUE),
+                String::from_c(&AsnString {
+                    data: [0xFF_u8].as_ptr() as *mut c_char,
+                    len: 1,
+                })
+            );
+        }
+    }
+}
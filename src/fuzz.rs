@@ -0,0 +1,71 @@
+//! Entrypoints for fuzzing the codecs and the parser, e.g. with `cargo fuzz` (see the `fuzz`
+//! directory of the repository for ready to use targets).
+//!
+//! The decode entrypoints are generic over the (generated) type to decode. They must never panic
+//! for any input: malformed input has to be rejected with an error and every value that was
+//! decoded successfully has to survive the round-trip through the same codec.
+
+use crate::arbitrary::{assert_uper_round_trip, Unstructured};
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::model::generate::{Generator, RustCodeGenerator};
+use crate::model::parse::{Token, Tokenizer};
+use crate::model::Model;
+use crate::protocol::basic::DER;
+use crate::rw::UperReader;
+use std::fmt::Debug;
+
+/// Decodes `T` from the given UPER encoded bytes and checks the round-trip of the result.
+pub fn uper_decode<T: Readable + Writable + Debug + PartialEq>(data: &[u8]) {
+    let mut reader = UperReader::from((data, data.len() * 8));
+    if let Ok(value) = reader.read::<T>() {
+        assert_uper_round_trip(&value);
+    }
+}
+
+/// Decodes `T` from the given DER encoded bytes and checks the round-trip of the result.
+pub fn der_decode<T: Readable + Writable + Debug + PartialEq>(data: &[u8]) {
+    if let Ok(value) = DER::reader(data).read::<T>() {
+        let mut writer = DER::writer(Vec::new());
+        if let Err(e) = writer.write(&value) {
+            panic!("Failed to DER encode decoded value {value:?}: {e:?}");
+        }
+        let bytes = writer.into_inner();
+        match DER::reader(&bytes[..]).read::<T>() {
+            Ok(result) => assert_eq!(value, result, "DER round-trip changed the value"),
+            Err(e) => panic!("DER round-trip failed for {value:?}: {e:?}"),
+        }
+    }
+}
+
+/// Decodes `T` from the given protobuf encoded bytes and checks the round-trip of the result.
+#[cfg(feature = "protobuf")]
+pub fn protobuf_decode<T: Readable + Writable + Debug + PartialEq>(data: &[u8]) {
+    if let Ok(value) = crate::rw::ProtobufReader::from(data).read::<T>() {
+        crate::arbitrary::assert_protobuf_round_trip(&value);
+    }
+}
+
+/// Feeds the given bytes as ASN.1 definition into the parser, the resolver and - if these
+/// succeed - the rust code generator.
+pub fn parse(data: &[u8]) {
+    if let Ok(asn) = std::str::from_utf8(data) {
+        compile(Tokenizer.parse(asn));
+    }
+}
+
+/// Like [`parse`], but skips the tokenizer and feeds a stream of [`Token`]s that is derived
+/// from the given bytes into the parser. Because the tokens are built from ASN.1 keywords and
+/// separators, this reaches deeper into the parser than random text.
+pub fn parse_tokens(data: &[u8]) {
+    if let Ok(tokens) = Unstructured::new(data).arbitrary::<Vec<Token>>() {
+        compile(tokens);
+    }
+}
+
+fn compile(tokens: Vec<Token>) {
+    if let Ok(model) = Model::try_from(tokens) {
+        if let Ok(model) = model.try_resolve() {
+            let _ = RustCodeGenerator::from(model.to_rust()).to_string();
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Transport independent glue for the gRPC services, that are generated from the
+//! `asn1rs_model::protobuf::services` mapping: a `Service` trait with one method per request/
+//! response pair, a `ServiceClient` calling the methods through a [`Channel`] and a
+//! `ServiceServer` dispatching encoded calls to an implementation of the trait.
+//!
+//! The requests and responses are encoded with the [`ProtobufWriter`] as the messages of the
+//! `service` in the generated `.proto` files, so the generated types interoperate with other
+//! gRPC implementations. To use them with `tonic`, the [`Channel`] is implemented once with a
+//! `tonic::client::Grpc` and a codec passing the bytes through, and the server forwards the
+//! path and the body of the requests to the `handle` function of the `ServiceServer`.
+
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::protocol::protobuf::Error as ProtobufError;
+use crate::rw::{ProtobufReader, ProtobufWriter};
+use core::future::Future;
+
+#[derive(Debug)]
+pub enum Error<E> {
+    /// The request or the response could not be encoded or decoded
+    Protobuf(ProtobufError),
+    /// The [`Channel`] or the implementation of the service failed
+    Status(E),
+}
+
+impl<E: std::fmt::Display> std::fmt::Display for Error<E> {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::Protobuf(e) => e.fmt(f),
+            Error::Status(e) => e.fmt(f),
+        }
+    }
+}
+
+impl<E: std::fmt::Debug + std::fmt::Display> std::error::Error for Error<E> {}
+
+/// The transport of unary gRPC calls for the generated clients
+pub trait Channel {
+    type Error;
+
+    /// Calls the method with the given path (such as `/telemetry.Telemetry/Report`) with the
+    /// encoded request and returns the encoded response
+    fn unary(
+        &mut self,
+        path: &'static str,
+        request: Vec<u8>,
+    ) -> impl Future<Output = Result<Vec<u8>, Self::Error>> + Send;
+}
+
+/// Encodes the request, calls the method with the given path through the [`Channel`] and
+/// decodes the response
+pub async fn call<C: Channel, Req: Writable, Res: Readable>(
+    channel: &mut C,
+    path: &'static str,
+    request: &Req,
+) -> Result<Res, Error<C::Error>> {
+    let mut writer = ProtobufWriter::default();
+    writer.write(request).map_err(Error::Protobuf)?;
+    let response = channel
+        .unary(path, writer.into_bytes_vec())
+        .await
+        .map_err(Error::Status)?;
+    ProtobufReader::from(&response[..])
+        .read::<Res>()
+        .map_err(Error::Protobuf)
+}
+
+/// Decodes the request, passes it to the method of the service and encodes its response
+pub async fn handle<Req, Res, E, F, Fut>(request: &[u8], method: F) -> Result<Vec<u8>, Error<E>>
+where
+    Req: Readable,
+    Res: Writable,
+    F: FnOnce(Req) -> Fut,
+    Fut: Future<Output = Result<Res, E>>,
+{
+    let request = ProtobufReader::from(request)
+        .read::<Req>()
+        .map_err(Error::Protobuf)?;
+    let response = method(request).await.map_err(Error::Status)?;
+    let mut writer = ProtobufWriter::default();
+    writer.write(&response).map_err(Error::Protobuf)?;
+    Ok(writer.into_bytes_vec())
+}
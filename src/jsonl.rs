@@ -0,0 +1,162 @@
+//! Logging of decoded (or to be encoded) values as JSON Lines, one record per value:
+//!
+//! ```json
+//! {"timestamp":"2024-05-17T08:30:00.250Z","type":"Message","payload":{"id":513,"ack":true}}
+//! ```
+//!
+//! The `timestamp` is the UTC time of logging in RFC 3339 with milliseconds, the `type` is the
+//! name of the rust type and the `payload` is the value in the JSON Encoding Rules, see
+//! [`JerWriter`].
+//!
+//! ```rust
+//! use asn1rs::jsonl::JsonlLogger;
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, Default, PartialEq)]
+//! pub struct Ping {
+//!     #[asn(integer(0..255))]
+//!     pub id: u8,
+//! }
+//!
+//! let mut logger = JsonlLogger::new(Vec::new());
+//! logger.log(&Ping { id: 7 }).unwrap();
+//! let log = String::from_utf8(logger.into_inner()).unwrap();
+//! assert!(log.ends_with(r#","type":"Ping","payload":{"id":7}}
+//! "#));
+//! ```
+
+use crate::descriptor::{Writable, Writer};
+use crate::protocol::jer::write_string;
+use crate::rw::JerWriter;
+use std::fs::{File, OpenOptions};
+use std::io::{BufWriter, Write};
+use std::path::Path;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+/// Appends a JSON Lines record for each logged value to the underlying writer
+pub struct JsonlLogger<W: Write> {
+    writer: W,
+}
+
+impl JsonlLogger<BufWriter<File>> {
+    /// Appends to the given file, which is created if it does not exist
+    pub fn append<P: AsRef<Path>>(path: P) -> std::io::Result<Self> {
+        OpenOptions::new()
+            .create(true)
+            .append(true)
+            .open(path)
+            .map(|file| Self::new(BufWriter::new(file)))
+    }
+}
+
+impl<W: Write> JsonlLogger<W> {
+    pub fn new(writer: W) -> Self {
+        Self { writer }
+    }
+
+    /// Logs the value with the current time and the name of its type
+    pub fn log<T: Writable>(&mut self, value: &T) -> std::io::Result<()> {
+        self.log_at(SystemTime::now(), type_name::<T>(), value)
+    }
+
+    /// Logs the value with the given time and type name
+    pub fn log_at<T: Writable>(
+        &mut self,
+        timestamp: SystemTime,
+        type_name: &str,
+        value: &T,
+    ) -> std::io::Result<()> {
+        let mut payload = JerWriter::default();
+        payload.write(value).unwrap_or_else(|e| match e {});
+
+        let mut record = String::with_capacity(payload.as_str().len() + 64);
+        record.push_str("{\"timestamp\":\"");
+        record.push_str(&rfc3339(timestamp));
+        record.push_str("\",\"type\":");
+        write_string(&mut record, type_name);
+        record.push_str(",\"payload\":");
+        record.push_str(payload.as_str());
+        record.push_str("}\n");
+        self.writer.write_all(record.as_bytes())
+    }
+
+    pub fn flush(&mut self) -> std::io::Result<()> {
+        self.writer.flush()
+    }
+
+    pub fn into_inner(self) -> W {
+        self.writer
+    }
+}
+
+/// The name of the type without its module path
+fn type_name<T>() -> &'static str {
+    let name = std::any::type_name::<T>();
+    let name = name.split('<').next().unwrap_or(name);
+    name.rsplit("::").next().unwrap_or(name)
+}
+
+/// Formats the time as UTC in RFC 3339 with milliseconds, times before the unix epoch are
+/// formatted as the epoch
+fn rfc3339(time: SystemTime) -> String {
+    let since_epoch = time.duration_since(UNIX_EPOCH).unwrap_or_default();
+    let seconds = since_epoch.as_secs();
+    let (year, month, day) = civil_from_days(seconds / 86_400);
+    let seconds_of_day = seconds % 86_400;
+    format!(
+        "{:04}-{:02}-{:02}T{:02}:{:02}:{:02}.{:03}Z",
+        year,
+        month,
+        day,
+        seconds_of_day / 3_600,
+        seconds_of_day % 3_600 / 60,
+        seconds_of_day % 60,
+        since_epoch.subsec_millis()
+    )
+}
+
+/// The proleptic Gregorian date of the given number of days since 1970-01-01, see
+/// <http://howardhinnant.github.io/date_algorithms.html#civil_from_days>
+fn civil_from_days(days: u64) -> (u64, u64, u64) {
+    let days = days + 719_468;
+    let era = days / 146_097;
+    let day_of_era = days % 146_097;
+    let year_of_era =
+        (day_of_era - day_of_era / 1_460 + day_of_era / 36_524 - day_of_era / 146_096) / 365;
+    let day_of_year = day_of_era - (365 * year_of_era + year_of_era / 4 - year_of_era / 100);
+    let month_index = (5 * day_of_year + 2) / 153;
+    let day = day_of_year - (153 * month_index + 2) / 5 + 1;
+    let month = if month_index < 10 {
+        month_index + 3
+    } else {
+        month_index - 9
+    };
+    let year = year_of_era + era * 400 + u64::from(month <= 2);
+    (year, month, day)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use std::time::Duration;
+
+    #[test]
+    pub fn test_rfc3339() {
+        assert_eq!("1970-01-01T00:00:00.000Z", rfc3339(UNIX_EPOCH));
+        assert_eq!(
+            "2000-02-29T23:59:59.999Z",
+            rfc3339(UNIX_EPOCH + Duration::from_millis(951_868_799_999))
+        );
+        assert_eq!(
+            "2024-05-17T08:30:00.250Z",
+            rfc3339(UNIX_EPOCH + Duration::from_millis(1_715_934_600_250))
+        );
+    }
+
+    #[test]
+    pub fn test_type_name() {
+        assert_eq!("JerWriter", type_name::<JerWriter>());
+        assert_eq!("Vec", type_name::<Vec<JerWriter>>());
+    }
+}
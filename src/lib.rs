@@ -11,12 +11,33 @@ pub mod macros {}
 #[macro_use]
 pub mod internal_macros;
 
+#[cfg(feature = "arbitrary")]
+pub mod arbitrary;
+pub mod codec;
 pub mod descriptor;
+#[cfg(feature = "ffi")]
+pub mod ffi;
+#[cfg(feature = "fuzz")]
+pub mod fuzz;
+#[cfg(feature = "protobuf")]
+pub mod grpc;
+#[cfg(feature = "jsonl")]
+pub mod jsonl;
+#[cfg(feature = "pem")]
+pub mod pem;
+pub mod oid;
 pub mod prelude;
 pub mod protocol;
 pub mod rw;
+pub mod util;
+#[cfg(feature = "wasm")]
+pub mod wasm;
 
+#[cfg(feature = "model")]
+pub mod build;
 #[cfg(feature = "model")]
 pub mod converter;
 #[cfg(feature = "model")]
 pub use asn1rs_model as model;
+#[cfg(feature = "model")]
+pub mod value;
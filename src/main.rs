@@ -2,44 +2,432 @@
 #![warn(unused_extern_crates)]
 
 mod converter;
+mod decode;
+mod output;
+mod watch;
+use asn1rs::model::generate::attributes::Attributes;
+use asn1rs::model::generate::rename::Renames;
+use asn1rs::model::generate::{Codec, Generator};
+#[cfg(feature = "protobuf")]
+use asn1rs::model::protobuf::numbers::FieldNumbers;
+#[cfg(feature = "protobuf")]
+use asn1rs::model::protobuf::services::Services;
+use asn1rs::model::rust::{use_uuid_for_16_byte_octet_strings, InlineNaming};
 use converter::Converter;
+use output::{OutputFormat, Report, Severity};
 
 pub fn main() {
-    let params = <Parameters as clap::Parser>::parse();
+    let mut params = <Parameters as clap::Parser>::parse();
+    if params.json_diagnostics {
+        params.output = OutputFormat::Json;
+    }
+
+    match params.command.take() {
+        Some(Command::Decode(decode)) => run_decode(decode, params.output),
+        Some(Command::Transcode(transcode)) => run_transcode(transcode, params.output),
+        Some(Command::Watch(watch)) => run_watch(&params, watch),
+        None => {}
+    }
+
+    let destination_dir = params.destination_dir.clone().unwrap_or_default();
+
+    if let Some(dir) = &params.watch {
+        watch_and_convert(
+            &params,
+            "compile",
+            &[(params.conversion_target, destination_dir)],
+            std::slice::from_ref(dir),
+        )
+    }
+
+    finish(
+        &convert(&params, &destination_dir, &params.source_files),
+        params.output,
+    )
+}
+
+/// Loads and converts the given files, stopping at the first file that fails to load
+fn convert(params: &Parameters, destination_dir: &str, sources: &[String]) -> Report {
+    let mut report = Report::new("compile");
+    if let Some(converter) = load(params, sources, &mut report) {
+        generate(
+            params,
+            &converter,
+            params.conversion_target,
+            destination_dir,
+            &mut report,
+        );
+    }
+    report
+}
+
+/// Loads and validates the given files, stopping at the first file that fails to load. Returns
+/// `None` if there is any error, which is added to the report.
+fn load(params: &Parameters, sources: &[String], report: &mut Report) -> Option<Converter> {
     let mut converter = Converter::default();
 
-    for source in &params.source_files {
-        if let Err(e) = converter.load_file(source) {
-            println!("Failed to load file {}: {:?}", source, e);
-            return;
+    for source in sources {
+        #[cfg(feature = "protobuf")]
+        if source.ends_with(".proto") {
+            if let Err(e) = converter.load_proto_file(source) {
+                report.add_error(
+                    Some(source.clone()),
+                    match e {
+                        converter::Error::ProtoImport(e) => {
+                            format!("Failed to import file {}: {}", source, e)
+                        }
+                        e => format!("Failed to load file {}: {:?}", source, e),
+                    },
+                );
+                return None;
+            }
+            continue;
+        }
+        let loaded = if params.lenient {
+            converter.load_file_lenient(source)
+        } else {
+            converter.load_file(source).map(|()| Vec::new())
+        };
+        match loaded {
+            Ok(warnings) => {
+                if !warnings.is_empty() {
+                    let input = std::fs::read_to_string(source).unwrap_or_default();
+                    for warning in &warnings {
+                        report.add_parse_diagnostic(
+                            Severity::Warning,
+                            source.clone(),
+                            &input,
+                            warning,
+                        );
+                    }
+                }
+            }
+            Err(converter::Error::Model(e)) => {
+                let input = std::fs::read_to_string(source).unwrap_or_default();
+                report.add_parse_diagnostic(Severity::Error, source.clone(), &input, &e);
+                return None;
+            }
+            Err(e) => {
+                report.add_error(
+                    Some(source.clone()),
+                    format!("Failed to load file {}: {:?}", source, e),
+                );
+                return None;
+            }
         }
     }
 
-    let result = match params.conversion_target {
-        ConversionTarget::Rust => converter.to_rust(&params.destination_dir, |rust| {
-            rust.set_fields_pub(!params.rust_fields_not_public);
-            rust.set_fields_have_getter_and_setter(params.rust_getter_and_setter);
-        }),
+    converter.set_warnings_as_errors(params.warnings_as_errors);
+    converter.set_inline_naming(params.inline_naming.into());
+    converter.set_rust_mod_file(params.rust_mod_file);
+    converter.set_rust_mod_oid_tree(params.rust_mod_oid_tree);
+    converter.set_rust_fmt(params.rust_fmt);
+    if let Some(Codecs(codecs)) = &params.codecs {
+        converter.set_codecs(Some(codecs.clone()));
+    }
+    #[cfg(feature = "protobuf")]
+    converter.set_protobuf_fixed_integers(params.protobuf_fixed_integers);
+    #[cfg(feature = "protobuf")]
+    converter.set_protobuf_maps(params.protobuf_maps);
+    #[cfg(feature = "protobuf")]
+    converter.set_protobuf_unknown_fields(params.protobuf_unknown_fields);
+    #[cfg(feature = "protobuf")]
+    if let Some(file) = &params.protobuf_field_numbers {
+        match std::fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|mapping| FieldNumbers::parse(&mapping).map_err(|e| e.to_string()))
+        {
+            Ok(numbers) => converter.set_protobuf_field_numbers(numbers),
+            Err(e) => {
+                report.add_error(
+                    Some(file.clone()),
+                    format!("Failed to load protobuf field numbers {}: {}", file, e),
+                );
+                return None;
+            }
+        }
+    }
+    #[cfg(feature = "protobuf")]
+    if let Some(file) = &params.protobuf_services {
+        match std::fs::read_to_string(file)
+            .map_err(|e| e.to_string())
+            .and_then(|mapping| Services::parse(&mapping).map_err(|e| e.to_string()))
+        {
+            Ok(services) => converter.set_protobuf_services(services),
+            Err(e) => {
+                report.add_error(
+                    Some(file.clone()),
+                    format!("Failed to load protobuf services {}: {}", file, e),
+                );
+                return None;
+            }
+        }
+    }
+    let issues = match converter.validate() {
+        Ok(issues) | Err(converter::Error::Validation(issues)) => issues,
+        Err(e) => {
+            report.add_error(None, format!("Failed to validate: {:?}", e));
+            return None;
+        }
+    };
+    for issue in &issues {
+        report.add_validation_issue(issue, params.warnings_as_errors);
+    }
+    Some(converter).filter(|_| report.is_success())
+}
+
+/// Converts the loaded modules to the given target and adds the written files to the report
+fn generate(
+    params: &Parameters,
+    converter: &Converter,
+    target: ConversionTarget,
+    destination_dir: &str,
+    report: &mut Report,
+) {
+    let result = match target {
+        ConversionTarget::Rust => {
+            let renames = match &params.rust_renames {
+                None => Renames::default(),
+                Some(file) => match std::fs::read_to_string(file)
+                    .map_err(|e| e.to_string())
+                    .and_then(|mapping| Renames::parse(&mapping).map_err(|e| e.to_string()))
+                {
+                    Ok(renames) => renames,
+                    Err(e) => {
+                        report.add_error(
+                            Some(file.clone()),
+                            format!("Failed to load renames {}: {}", file, e),
+                        );
+                        return;
+                    }
+                },
+            };
+            let attributes = match &params.rust_attributes {
+                None => Attributes::default(),
+                Some(file) => match std::fs::read_to_string(file)
+                    .map_err(|e| e.to_string())
+                    .and_then(|mapping| Attributes::parse(&mapping).map_err(|e| e.to_string()))
+                {
+                    Ok(attributes) => attributes,
+                    Err(e) => {
+                        report.add_error(
+                            Some(file.clone()),
+                            format!("Failed to load attributes {}: {}", file, e),
+                        );
+                        return;
+                    }
+                },
+            };
+            converter.to_rust(destination_dir, |rust| {
+                rust.set_fields_pub(!params.rust_fields_not_public);
+                rust.set_fields_have_getter_and_setter(params.rust_getter_and_setter);
+                rust.set_generate_arbitrary_impls(params.rust_arbitrary);
+                rust.set_arbitrary_feature(params.rust_arbitrary_feature.clone());
+                rust.set_generate_pyo3_classes(params.rust_pyo3);
+                rust.set_pyo3_feature(params.rust_pyo3_feature.clone());
+                #[cfg(feature = "protobuf")]
+                rust.set_protobuf_feature(params.rust_protobuf_feature.clone());
+                rust.set_enums_have_repr(params.rust_repr_enums);
+                rust.set_extensible_enums_non_exhaustive(params.rust_non_exhaustive_enums);
+                rust.set_optimize_code_size(params.rust_optimize_code_size);
+                rust.set_integers_validated(params.rust_validated_integers);
+                rust.set_builders(params.rust_builders);
+                rust.set_views(params.rust_views);
+                rust.set_enums_have_display_and_from_str(params.rust_display_and_from_str);
+                rust.set_auto_derives(params.rust_auto_derives);
+                rust.set_renames(renames.clone());
+                rust.set_attributes(attributes.clone());
+                if params.rust_uuid {
+                    rust.models_mut()
+                        .iter_mut()
+                        .for_each(use_uuid_for_16_byte_octet_strings);
+                }
+            })
+        }
         #[cfg(feature = "protobuf")]
-        ConversionTarget::Proto => converter.to_protobuf(&params.destination_dir),
+        ConversionTarget::Proto => converter.to_protobuf(destination_dir),
+        ConversionTarget::FuzzDict => converter.to_fuzz_dictionaries(destination_dir),
+        ConversionTarget::JsonSchema => converter.to_json_schemas(destination_dir),
+        ConversionTarget::C => converter.to_c(destination_dir),
     };
 
     match result {
-        Err(e) => println!("Failed to convert: {:?}", e),
+        Err(e) => report.add_error(None, format!("Failed to convert: {:?}", e)),
         Ok(files) => {
-            for (source, mut files) in files {
-                println!("Successfully converted {} => {}", source, files.remove(0));
-                files
-                    .iter()
-                    .for_each(|f| println!("                          => {}", f));
+            for (source, files) in files {
+                report.add_result(source, files);
             }
         }
     }
 }
 
+fn run_watch(params: &Parameters, watch: WatchParameters) -> ! {
+    let mut targets = Vec::new();
+    targets.extend(watch.rust.map(|dir| (ConversionTarget::Rust, dir)));
+    #[cfg(feature = "protobuf")]
+    targets.extend(watch.proto.map(|dir| (ConversionTarget::Proto, dir)));
+    targets.extend(watch.fuzz_dict.map(|dir| (ConversionTarget::FuzzDict, dir)));
+    targets.extend(
+        watch
+            .json_schema
+            .map(|dir| (ConversionTarget::JsonSchema, dir)),
+    );
+    targets.extend(watch.c.map(|dir| (ConversionTarget::C, dir)));
+
+    if targets.is_empty() {
+        let mut report = Report::new("watch");
+        report.add_error(None, "No output directory given, see 'asn1rs watch --help'");
+        finish(&report, params.output)
+    }
+
+    watch_and_convert(params, "watch", &targets, &watch.dirs)
+}
+
+/// Converts the ASN.1 files of the directories (and the source files of the parameters) to each
+/// target whenever they change. After a successful conversion, only the modules of the changed
+/// files and the modules importing from them are converted again.
+fn watch_and_convert(
+    params: &Parameters,
+    command: &'static str,
+    targets: &[(ConversionTarget, String)],
+    dirs: &[String],
+) -> ! {
+    let mut converted = false;
+    watch::watch(dirs, |changes| {
+        let mut report = Report::new(command);
+        match changes {
+            Ok(changes) => {
+                let mut sources = changes.files;
+                sources.extend(params.source_files.iter().cloned());
+                if let Some(mut converter) = load(params, &sources, &mut report) {
+                    if converted && !changes.initial {
+                        let changed = changes
+                            .changed
+                            .iter()
+                            .flat_map(|file| converter.modules_of(file))
+                            .collect::<Vec<_>>();
+                        let changed = changed.iter().map(String::as_str).collect::<Vec<_>>();
+                        converter.set_modules_to_convert(Some(converter.dependents(&changed)));
+                    }
+                    for (target, destination_dir) in targets {
+                        generate(params, &converter, *target, destination_dir, &mut report);
+                    }
+                }
+                converted = report.is_success();
+            }
+            Err(e) => {
+                converted = false;
+                report.add_error(
+                    Some(dirs.join(", ")),
+                    format!("Failed to watch {}: {:?}", dirs.join(", "), e),
+                );
+            }
+        }
+        report.print(params.output);
+    })
+}
+
+fn run_decode(params: DecodeParameters, format: OutputFormat) -> ! {
+    if params.best_effort {
+        run_decode_best_effort(params, format)
+    }
+    let result = decode::load_schema(&params.input.schema).and_then(|schema| {
+        let payload = decode::read_payload(&params.input.payload, params.input.binary)?;
+        decode::decode(
+            &schema,
+            &params.input.pdu,
+            params.encoding_rules(),
+            &payload,
+        )
+    });
+
+    match result {
+        Ok(value) => println!("{}", decode::print(&value, params.print)),
+        Err(e) => fail("decode", params.input, e, format),
+    }
+    std::process::exit(0)
+}
+
+/// Prints the partially decoded value and the errors encountered while decoding it, fails only
+/// if not even the outermost value could be decoded
+fn run_decode_best_effort(params: DecodeParameters, format: OutputFormat) -> ! {
+    let result = decode::load_schema(&params.input.schema).and_then(|schema| {
+        let payload = decode::read_payload(&params.input.payload, params.input.binary)?;
+        Ok(decode::decode_best_effort(
+            &schema,
+            &params.input.pdu,
+            params.encoding_rules(),
+            &payload,
+        ))
+    });
+
+    match result {
+        Ok(result) => {
+            for error in &result.errors {
+                let path = if error.path.is_empty() {
+                    &params.input.pdu
+                } else {
+                    &error.path
+                };
+                eprintln!("warning: Failed to decode {}: {}", path, error.error);
+            }
+            match result.value {
+                Some(value) => println!("{}", decode::print(&value, params.print)),
+                None => std::process::exit(1),
+            }
+        }
+        Err(e) => fail("decode", params.input, e, format),
+    }
+    std::process::exit(0)
+}
+
+fn run_transcode(params: TranscodeParameters, format: OutputFormat) -> ! {
+    let result = decode::load_schema(&params.input.schema).and_then(|schema| {
+        let payload = decode::read_payload(&params.input.payload, params.input.binary)?;
+        let value = decode::decode(&schema, &params.input.pdu, params.from, &payload)?;
+        decode::transcode(&schema, &params.input.pdu, &value, params.to)
+    });
+
+    match result {
+        Ok(transcoded) => match &params.out {
+            Some(file) => {
+                if let Err(e) = std::fs::write(file, transcoded.to_bytes()) {
+                    fail("transcode", params.input, decode::Error::Io(e), format)
+                }
+            }
+            None => println!("{}", transcoded),
+        },
+        Err(e) => fail("transcode", params.input, e, format),
+    }
+    std::process::exit(0)
+}
+
+fn fail(
+    command: &'static str,
+    params: PayloadParameters,
+    error: decode::Error,
+    format: OutputFormat,
+) -> ! {
+    let mut report = Report::new(command);
+    report.add_error(
+        Some(params.payload),
+        format!("Failed to {} {}: {:?}", command, params.pdu, error),
+    );
+    finish(&report, format)
+}
+
+/// Prints the report and exits with a non-zero exit code if it contains any error
+fn finish(report: &Report, format: OutputFormat) -> ! {
+    report.print(format);
+    std::process::exit(if report.is_success() { 0 } else { 1 })
+}
+
 #[derive(clap::Parser, Debug)]
 #[command(author, version, about, long_about = None)] // Read from `Cargo.toml`
+#[command(subcommand_negates_reqs = true)]
 pub struct Parameters {
+    #[command(subcommand)]
+    pub command: Option<Command>,
     #[arg(
         short = 'n',
         long = "rust-fields-not-public",
@@ -51,9 +439,188 @@ pub struct Parameters {
         short = 'g',
         long = "rust-getter-and-setter",
         env = "RUST_GETTER_AND_SETTER",
-        help = "Whether to generate getter and setter for the fields of the generated rust structs"
+        help = "Whether to generate getter and setter for the fields of the generated rust structs and the value of tuple structs, as well as is_/as_/into_ accessors for the variants of enums with data"
     )]
     pub rust_getter_and_setter: bool,
+    #[arg(
+        short = 'a',
+        long = "rust-arbitrary",
+        env = "RUST_ARBITRARY",
+        help = "Whether to implement 'Arbitrary' for the generated rust types (requires the 'arbitrary' feature of asn1rs)"
+    )]
+    pub rust_arbitrary: bool,
+    #[arg(
+        long = "rust-arbitrary-feature",
+        env = "RUST_ARBITRARY_FEATURE",
+        help = "The feature of the crate embedding the generated rust code, behind which the 'Arbitrary' impls are gated with '#[cfg(feature = ..)]'"
+    )]
+    pub rust_arbitrary_feature: Option<String>,
+    #[arg(
+        long = "rust-pyo3",
+        env = "RUST_PYO3",
+        help = "Whether to wrap the generated rust types in PyO3 classes and add a '#[pymodule]' function per module (requires a dependency on pyo3)"
+    )]
+    pub rust_pyo3: bool,
+    #[arg(
+        long = "rust-pyo3-feature",
+        env = "RUST_PYO3_FEATURE",
+        help = "The feature of the crate embedding the generated rust code, behind which the PyO3 classes and functions are gated with '#[cfg(feature = ..)]'"
+    )]
+    pub rust_pyo3_feature: Option<String>,
+    #[arg(
+        long = "rust-repr-enums",
+        env = "RUST_REPR_ENUMS",
+        help = "Whether the generated rust enums of ENUMERATEDs have a '#[repr(u8)]' (or u16, u32) with their numbers as discriminants and convert from and to u64"
+    )]
+    pub rust_repr_enums: bool,
+    #[arg(
+        long = "rust-non-exhaustive-enums",
+        env = "RUST_NON_EXHAUSTIVE_ENUMS",
+        help = "Whether the generated rust enums of extensible ENUMERATEDs and CHOICEs are '#[non_exhaustive]'"
+    )]
+    pub rust_non_exhaustive_enums: bool,
+    #[arg(
+        long = "rust-optimize-code-size",
+        env = "RUST_OPTIMIZE_CODE_SIZE",
+        help = "Whether the generated 'Readable' and 'Writable' impls are '#[inline(never)]', which reduces the code size of deeply nested schemas at the cost of function calls"
+    )]
+    pub rust_optimize_code_size: bool,
+    #[arg(
+        long = "rust-validated-integers",
+        env = "RUST_VALIDATED_INTEGERS",
+        help = "Whether the generated rust types of constrained INTEGERs keep their value private and check the range in 'try_new()' and 'TryFrom'"
+    )]
+    pub rust_validated_integers: bool,
+    #[arg(
+        long = "rust-builders",
+        env = "RUST_BUILDERS",
+        help = "Whether to generate a 'FooBuilder' with a setter per field and a 'build()' checking the mandatory fields for each rust struct"
+    )]
+    pub rust_builders: bool,
+    #[arg(
+        long = "rust-views",
+        env = "RUST_VIEWS",
+        help = "Whether to generate a 'FooView' and a 'Foo::read_view(reader, fields)' decoding only the selected fields for each SEQUENCE"
+    )]
+    pub rust_views: bool,
+    #[arg(
+        long = "rust-display-and-from-str",
+        env = "RUST_DISPLAY_AND_FROM_STR",
+        help = "Whether to implement 'Display' and 'FromStr' with the ASN.1 identifiers of the variants for the rust enums"
+    )]
+    pub rust_display_and_from_str: bool,
+    #[arg(
+        long = "rust-auto-derives",
+        env = "RUST_AUTO_DERIVES",
+        help = "Whether to derive 'Copy', 'Eq', 'PartialOrd' and 'Ord' for the rust types whose fields or variants all implement them"
+    )]
+    pub rust_auto_derives: bool,
+    #[arg(
+        long = "rust-renames",
+        env = "RUST_RENAMES",
+        help = "A file mapping renamed fields and variants ('Type.oldName = newName' per line), for which deprecated aliases are generated"
+    )]
+    pub rust_renames: Option<String>,
+    #[arg(
+        long = "rust-attributes",
+        env = "RUST_ATTRIBUTES",
+        help = "A file with additional attributes ('Type = attribute' or 'Type.member = attribute' per line), which are emitted verbatim on the generated rust types, fields and variants"
+    )]
+    pub rust_attributes: Option<String>,
+    #[arg(
+        long = "rust-uuid",
+        env = "RUST_UUID",
+        help = "Represents OCTET STRING (SIZE(16)) fields and variants as uuid::Uuid in the generated rust code (requires the 'uuid' feature of asn1rs)"
+    )]
+    pub rust_uuid: bool,
+    #[arg(
+        long = "rust-mod-file",
+        env = "RUST_MOD_FILE",
+        help = "Writes a 'mod.rs' declaring all generated rust modules and re-exporting the types with unique names"
+    )]
+    pub rust_mod_file: bool,
+    #[arg(
+        long = "rust-mod-oid-tree",
+        env = "RUST_MOD_OID_TREE",
+        help = "Re-exports the generated rust modules in nested modules following their object identifiers in the 'mod.rs' as well, implies '--rust-mod-file'"
+    )]
+    pub rust_mod_oid_tree: bool,
+    #[arg(
+        long = "rust-fmt",
+        env = "RUST_FMT",
+        help = "Formats the generated rust files with 'rustfmt' (or the binary in the RUSTFMT environment variable)"
+    )]
+    pub rust_fmt: bool,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "protobuf-field-numbers",
+        env = "PROTOBUF_FIELD_NUMBERS",
+        help = "A file assigning stable protobuf field numbers ('Type.field = number' per line) to the generated rust types and protobuf messages, the numbers of removed fields are reserved"
+    )]
+    pub protobuf_field_numbers: Option<String>,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "protobuf-fixed-integers",
+        env = "PROTOBUF_FIXED_INTEGERS",
+        help = "Encodes integers with the full range of a 32- or 64-bit integer as protobuf fixed32/fixed64 (sfixed32/sfixed64 if signed) instead of as varint"
+    )]
+    pub protobuf_fixed_integers: bool,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "protobuf-maps",
+        env = "PROTOBUF_MAPS",
+        help = "Declares SEQUENCE OF SEQUENCE { key K, value V } as protobuf map<K, V> instead of as repeated message, both have the same encoding"
+    )]
+    pub protobuf_maps: bool,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "protobuf-unknown-fields",
+        env = "PROTOBUF_UNKNOWN_FIELDS",
+        help = "Adds an unknown_fields field to the generated rust structs, which retains the fields of protobuf messages that are unknown to the definition and writes them again when encoded"
+    )]
+    pub protobuf_unknown_fields: bool,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "protobuf-services",
+        env = "PROTOBUF_SERVICES",
+        help = "A file declaring gRPC services ('Service.Method = Request -> Response' per line), which are written to the protobuf files and generated as rust service traits, clients and servers"
+    )]
+    pub protobuf_services: Option<String>,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "rust-protobuf-feature",
+        env = "RUST_PROTOBUF_FEATURE",
+        help = "The feature of the crate embedding the generated rust code, behind which the gRPC service traits, clients and servers as well as the protobuf field numbers and fixed integers are gated with '#[cfg(feature = ..)]'"
+    )]
+    pub rust_protobuf_feature: Option<String>,
+    #[arg(
+        long = "codecs",
+        env = "CODECS",
+        value_parser = parse_codecs,
+        help = "The comma separated codecs to generate the specific rust code for, all by default or 'none'. Only protobuf has specific code (the field numbers, fixed integers, unknown fields and gRPC services), the code of UPER, DER and JER is shared by all codecs"
+    )]
+    pub codecs: Option<Codecs>,
+    #[arg(
+        short = 'l',
+        long = "lenient",
+        env = "LENIENT",
+        help = "Skips definitions that cannot be parsed, for example information object classes or parameterized types, with a warning instead of failing"
+    )]
+    pub lenient: bool,
+    #[arg(
+        long = "warnings-as-errors",
+        env = "WARNINGS_AS_ERRORS",
+        help = "Fails the conversion on warnings of the validation, such as CHOICE alternatives sharing a tag"
+    )]
+    pub warnings_as_errors: bool,
+    #[arg(
+        value_enum,
+        long = "inline-naming",
+        env = "INLINE_NAMING",
+        help = "How the types of inline SEQUENCEs, SETs, CHOICEs and ENUMERATEDs are named: after their parent type and field or variant, or like that with a number appended if the name is already taken",
+        default_value = "parent-prefixed"
+    )]
+    pub inline_naming: InlineNamingStrategy,
     #[arg(
         value_enum,
         short = 't',
@@ -63,8 +630,32 @@ pub struct Parameters {
         default_value = "rust"
     )]
     pub conversion_target: ConversionTarget,
-    #[arg(env = "DESTINATION_DIR")]
-    pub destination_dir: String,
+    #[arg(
+        short = 'w',
+        long = "watch",
+        env = "WATCH",
+        help = "Converts the ASN.1 files of the given directory whenever they change, until interrupted"
+    )]
+    pub watch: Option<String>,
+    #[arg(
+        value_enum,
+        short = 'o',
+        long = "output",
+        env = "OUTPUT",
+        help = "The format of the printed results and diagnostics",
+        default_value = "text",
+        global = true
+    )]
+    pub output: OutputFormat,
+    #[arg(
+        long = "json-diagnostics",
+        env = "JSON_DIAGNOSTICS",
+        help = "Prints the results and diagnostics as JSON, with the code, line, column and expected tokens of parse errors (same as '--output json')",
+        global = true
+    )]
+    pub json_diagnostics: bool,
+    #[arg(env = "DESTINATION_DIR", required = true)]
+    pub destination_dir: Option<String>,
     #[arg(env = "SOURCE_FILES")]
     pub source_files: Vec<String>,
 }
@@ -74,4 +665,185 @@ pub enum ConversionTarget {
     Rust,
     #[cfg(feature = "protobuf")]
     Proto,
+    FuzzDict,
+    JsonSchema,
+    C,
+}
+
+/// The codecs selected with `--codecs`, see [`Codec`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Codecs(pub Vec<Codec>);
+
+/// Rejects the codecs without specific generated code instead of silently ignoring them
+fn parse_codecs(codecs: &str) -> Result<Codecs, String> {
+    if codecs == "none" {
+        return Ok(Codecs(Vec::new()));
+    }
+    codecs
+        .split(',')
+        .map(|codec| match codec.trim() {
+            "protobuf" => Ok(Codec::Protobuf),
+            codec @ ("uper" | "der" | "jer") => Err(format!(
+                "{codec} has no specific generated code to select, the Readable and Writable impls are shared by all codecs"
+            )),
+            codec => Err(format!(
+                "unknown codec '{codec}', expected 'protobuf' or 'none'"
+            )),
+        })
+        .collect::<Result<_, _>>()
+        .map(Codecs)
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum InlineNamingStrategy {
+    ParentPrefixed,
+    Numbered,
+}
+
+impl From<InlineNamingStrategy> for InlineNaming {
+    fn from(strategy: InlineNamingStrategy) -> Self {
+        match strategy {
+            InlineNamingStrategy::ParentPrefixed => InlineNaming::ParentPrefixed,
+            InlineNamingStrategy::Numbered => InlineNaming::Numbered,
+        }
+    }
+}
+
+#[derive(clap::Subcommand, Debug)]
+pub enum Command {
+    /// Decodes a payload against a schema and prints the decoded value
+    Decode(DecodeParameters),
+    /// Decodes a payload against a schema and encodes it again with other encoding rules
+    Transcode(TranscodeParameters),
+    /// Watches directories of ASN.1 files and converts the changed modules (and the modules
+    /// importing from them) again whenever files change, until interrupted
+    Watch(WatchParameters),
+}
+
+#[derive(clap::Args, Debug)]
+pub struct WatchParameters {
+    #[arg(
+        long = "rust",
+        help = "The directory to write the generated rust files to"
+    )]
+    pub rust: Option<String>,
+    #[cfg(feature = "protobuf")]
+    #[arg(
+        long = "proto",
+        help = "The directory to write the generated protobuf files to"
+    )]
+    pub proto: Option<String>,
+    #[arg(
+        long = "fuzz-dict",
+        help = "The directory to write the fuzzing dictionaries to"
+    )]
+    pub fuzz_dict: Option<String>,
+    #[arg(
+        long = "json-schema",
+        help = "The directory to write the JSON Schemas of the JER representation to"
+    )]
+    pub json_schema: Option<String>,
+    #[arg(
+        long = "c",
+        help = "The directory to write the C headers and their Rust FFI functions to"
+    )]
+    pub c: Option<String>,
+    #[arg(required = true, help = "The directories containing the ASN.1 files")]
+    pub dirs: Vec<String>,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct PayloadParameters {
+    #[arg(
+        short = 's',
+        long = "schema",
+        required = true,
+        help = "The ASN.1 file(s) defining the PDU and the types it refers to"
+    )]
+    pub schema: Vec<String>,
+    #[arg(short = 'p', long = "pdu", help = "The name of the type to decode")]
+    pub pdu: String,
+    #[arg(
+        short = 'b',
+        long = "binary",
+        help = "Whether the payload file is binary instead of hex digits"
+    )]
+    pub binary: bool,
+    #[arg(help = "The file containing the payload, or '-' to read it from stdin")]
+    pub payload: String,
+}
+
+#[derive(clap::Args, Debug)]
+pub struct DecodeParameters {
+    #[command(flatten)]
+    pub input: PayloadParameters,
+    #[arg(
+        value_enum,
+        short = 'e',
+        long = "encoding",
+        help = "The encoding rules the payload is encoded with",
+        default_value = "uper"
+    )]
+    pub encoding: decode::Encoding,
+    #[arg(
+        long = "uper",
+        conflicts_with_all = ["encoding", "der"],
+        help = "Shorthand for '--encoding uper'"
+    )]
+    pub uper: bool,
+    #[arg(
+        long = "der",
+        conflicts_with_all = ["encoding", "uper"],
+        help = "Shorthand for '--encoding der'"
+    )]
+    pub der: bool,
+    #[arg(
+        value_enum,
+        long = "print",
+        help = "The notation to print the decoded value in",
+        default_value = "value"
+    )]
+    pub print: decode::PrintFormat,
+    #[arg(
+        long = "best-effort",
+        help = "Continues after values that fail to decode where the encoding allows it and prints the partially decoded value, with the errors as warnings"
+    )]
+    pub best_effort: bool,
+}
+
+impl DecodeParameters {
+    /// The encoding rules selected by `--encoding` or one of its shorthand flags
+    pub fn encoding_rules(&self) -> decode::Encoding {
+        if self.der {
+            decode::Encoding::Der
+        } else if self.uper {
+            decode::Encoding::Uper
+        } else {
+            self.encoding
+        }
+    }
+}
+
+#[derive(clap::Args, Debug)]
+pub struct TranscodeParameters {
+    #[command(flatten)]
+    pub input: PayloadParameters,
+    #[arg(
+        value_enum,
+        long = "from",
+        help = "The encoding rules the payload is encoded with",
+        default_value = "uper"
+    )]
+    pub from: decode::Encoding,
+    #[arg(
+        value_enum,
+        long = "to",
+        help = "The encoding rules to encode the payload with"
+    )]
+    pub to: decode::TranscodeTarget,
+    #[arg(
+        long = "out",
+        help = "The file to write the transcoded payload to, instead of printing it as hex digits"
+    )]
+    pub out: Option<String>,
 }
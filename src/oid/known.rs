@@ -0,0 +1,92 @@
+//! Commonly used OBJECT IDENTIFIERs of PKCS #1 (RFC 8017), PKCS #9 (RFC 2985), ANSI X9.62 and
+//! SEC 2 (RFC 5480), RFC 8410, NIST, X.520 and PKIX (RFC 5280).
+
+use super::Oid;
+
+pub const RSA_ENCRYPTION: Oid = Oid::from_static(&[1, 2, 840, 113549, 1, 1, 1]);
+pub const RSASSA_PSS: Oid = Oid::from_static(&[1, 2, 840, 113549, 1, 1, 10]);
+pub const SHA256_WITH_RSA_ENCRYPTION: Oid = Oid::from_static(&[1, 2, 840, 113549, 1, 1, 11]);
+pub const SHA384_WITH_RSA_ENCRYPTION: Oid = Oid::from_static(&[1, 2, 840, 113549, 1, 1, 12]);
+pub const SHA512_WITH_RSA_ENCRYPTION: Oid = Oid::from_static(&[1, 2, 840, 113549, 1, 1, 13]);
+pub const EMAIL_ADDRESS: Oid = Oid::from_static(&[1, 2, 840, 113549, 1, 9, 1]);
+
+pub const EC_PUBLIC_KEY: Oid = Oid::from_static(&[1, 2, 840, 10045, 2, 1]);
+pub const PRIME256V1: Oid = Oid::from_static(&[1, 2, 840, 10045, 3, 1, 7]);
+pub const ECDSA_WITH_SHA256: Oid = Oid::from_static(&[1, 2, 840, 10045, 4, 3, 2]);
+pub const ECDSA_WITH_SHA384: Oid = Oid::from_static(&[1, 2, 840, 10045, 4, 3, 3]);
+pub const ECDSA_WITH_SHA512: Oid = Oid::from_static(&[1, 2, 840, 10045, 4, 3, 4]);
+pub const SECP384R1: Oid = Oid::from_static(&[1, 3, 132, 0, 34]);
+pub const SECP521R1: Oid = Oid::from_static(&[1, 3, 132, 0, 35]);
+pub const ED25519: Oid = Oid::from_static(&[1, 3, 101, 112]);
+pub const ED448: Oid = Oid::from_static(&[1, 3, 101, 113]);
+
+pub const SHA256: Oid = Oid::from_static(&[2, 16, 840, 1, 101, 3, 4, 2, 1]);
+pub const SHA384: Oid = Oid::from_static(&[2, 16, 840, 1, 101, 3, 4, 2, 2]);
+pub const SHA512: Oid = Oid::from_static(&[2, 16, 840, 1, 101, 3, 4, 2, 3]);
+
+pub const COMMON_NAME: Oid = Oid::from_static(&[2, 5, 4, 3]);
+pub const SERIAL_NUMBER: Oid = Oid::from_static(&[2, 5, 4, 5]);
+pub const COUNTRY_NAME: Oid = Oid::from_static(&[2, 5, 4, 6]);
+pub const LOCALITY_NAME: Oid = Oid::from_static(&[2, 5, 4, 7]);
+pub const STATE_OR_PROVINCE_NAME: Oid = Oid::from_static(&[2, 5, 4, 8]);
+pub const ORGANIZATION_NAME: Oid = Oid::from_static(&[2, 5, 4, 10]);
+pub const ORGANIZATIONAL_UNIT_NAME: Oid = Oid::from_static(&[2, 5, 4, 11]);
+
+pub const SUBJECT_KEY_IDENTIFIER: Oid = Oid::from_static(&[2, 5, 29, 14]);
+pub const KEY_USAGE: Oid = Oid::from_static(&[2, 5, 29, 15]);
+pub const SUBJECT_ALT_NAME: Oid = Oid::from_static(&[2, 5, 29, 17]);
+pub const BASIC_CONSTRAINTS: Oid = Oid::from_static(&[2, 5, 29, 19]);
+pub const CRL_DISTRIBUTION_POINTS: Oid = Oid::from_static(&[2, 5, 29, 31]);
+pub const CERTIFICATE_POLICIES: Oid = Oid::from_static(&[2, 5, 29, 32]);
+pub const AUTHORITY_KEY_IDENTIFIER: Oid = Oid::from_static(&[2, 5, 29, 35]);
+pub const EXT_KEY_USAGE: Oid = Oid::from_static(&[2, 5, 29, 37]);
+
+pub const AUTHORITY_INFO_ACCESS: Oid = Oid::from_static(&[1, 3, 6, 1, 5, 5, 7, 1, 1]);
+pub const SERVER_AUTH: Oid = Oid::from_static(&[1, 3, 6, 1, 5, 5, 7, 3, 1]);
+pub const CLIENT_AUTH: Oid = Oid::from_static(&[1, 3, 6, 1, 5, 5, 7, 3, 2]);
+pub const CODE_SIGNING: Oid = Oid::from_static(&[1, 3, 6, 1, 5, 5, 7, 3, 3]);
+pub const OCSP: Oid = Oid::from_static(&[1, 3, 6, 1, 5, 5, 7, 48, 1]);
+pub const CA_ISSUERS: Oid = Oid::from_static(&[1, 3, 6, 1, 5, 5, 7, 48, 2]);
+
+/// The constants of this module with their ASN.1 identifiers, see [`Oid::name`]
+pub const NAMES: &[(Oid, &str)] = &[
+    (RSA_ENCRYPTION, "rsaEncryption"),
+    (RSASSA_PSS, "id-RSASSA-PSS"),
+    (SHA256_WITH_RSA_ENCRYPTION, "sha256WithRSAEncryption"),
+    (SHA384_WITH_RSA_ENCRYPTION, "sha384WithRSAEncryption"),
+    (SHA512_WITH_RSA_ENCRYPTION, "sha512WithRSAEncryption"),
+    (EMAIL_ADDRESS, "emailAddress"),
+    (EC_PUBLIC_KEY, "ecPublicKey"),
+    (PRIME256V1, "prime256v1"),
+    (ECDSA_WITH_SHA256, "ecdsa-with-SHA256"),
+    (ECDSA_WITH_SHA384, "ecdsa-with-SHA384"),
+    (ECDSA_WITH_SHA512, "ecdsa-with-SHA512"),
+    (SECP384R1, "secp384r1"),
+    (SECP521R1, "secp521r1"),
+    (ED25519, "id-Ed25519"),
+    (ED448, "id-Ed448"),
+    (SHA256, "id-sha256"),
+    (SHA384, "id-sha384"),
+    (SHA512, "id-sha512"),
+    (COMMON_NAME, "commonName"),
+    (SERIAL_NUMBER, "serialNumber"),
+    (COUNTRY_NAME, "countryName"),
+    (LOCALITY_NAME, "localityName"),
+    (STATE_OR_PROVINCE_NAME, "stateOrProvinceName"),
+    (ORGANIZATION_NAME, "organizationName"),
+    (ORGANIZATIONAL_UNIT_NAME, "organizationalUnitName"),
+    (SUBJECT_KEY_IDENTIFIER, "subjectKeyIdentifier"),
+    (KEY_USAGE, "keyUsage"),
+    (SUBJECT_ALT_NAME, "subjectAltName"),
+    (BASIC_CONSTRAINTS, "basicConstraints"),
+    (CRL_DISTRIBUTION_POINTS, "cRLDistributionPoints"),
+    (CERTIFICATE_POLICIES, "certificatePolicies"),
+    (AUTHORITY_KEY_IDENTIFIER, "authorityKeyIdentifier"),
+    (EXT_KEY_USAGE, "extKeyUsage"),
+    (AUTHORITY_INFO_ACCESS, "id-pe-authorityInfoAccess"),
+    (SERVER_AUTH, "id-kp-serverAuth"),
+    (CLIENT_AUTH, "id-kp-clientAuth"),
+    (CODE_SIGNING, "id-kp-codeSigning"),
+    (OCSP, "id-ad-ocsp"),
+    (CA_ISSUERS, "id-ad-caIssuers"),
+];
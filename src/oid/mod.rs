@@ -0,0 +1,243 @@
+//! The runtime representation of OBJECT IDENTIFIER values (ITU-T X.680 | ISO/IEC 8824-1,
+//! chapter 32), with the dotted-decimal notation and the content octets of the basic encoding
+//! rules. The constants of commonly used arcs are in [`known`].
+//!
+//! ```rust
+//! use asn1rs::oid::{known, Oid};
+//!
+//! let oid = "1.2.840.10045.2.1".parse::<Oid>().unwrap();
+//! assert_eq!(known::EC_PUBLIC_KEY, oid);
+//! assert_eq!(Some("ecPublicKey"), oid.name());
+//! assert_eq!("2.5.4.3", known::COMMON_NAME.to_string());
+//! ```
+
+pub mod known;
+
+use std::borrow::Cow;
+use std::fmt::{Display, Formatter};
+use std::str::FromStr;
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Error {
+    /// An OBJECT IDENTIFIER consists of at least two arcs
+    TooFewArcs(usize),
+    /// The first arc is not 0, 1 or 2, or the second arc exceeds 39 below the arcs 0 and 1
+    InvalidRootArcs(u64, u64),
+    /// The arc at the given index is not a decimal number without sign
+    InvalidArc(String, usize),
+    /// The content octets are empty, not minimal or end within a subidentifier
+    InvalidEncoding,
+    /// A subidentifier exceeds the range of an `u64`
+    ArcOverflow,
+}
+
+impl Display for Error {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::TooFewArcs(len) => write!(f, "Expected at least two arcs but got {len}"),
+            Error::InvalidRootArcs(first, second) => {
+                write!(f, "The arcs {first}.{second} are not valid root arcs")
+            }
+            Error::InvalidArc(arc, index) => write!(f, "Invalid arc {arc:?} at index {index}"),
+            Error::InvalidEncoding => write!(f, "Invalid content octets of an OBJECT IDENTIFIER"),
+            Error::ArcOverflow => write!(f, "An arc exceeds the range of an u64"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// An OBJECT IDENTIFIER value as its sequence of arcs
+#[derive(Debug, Clone, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct Oid(Cow<'static, [u64]>);
+
+impl Oid {
+    /// For constants, the arcs are not validated
+    #[inline]
+    pub const fn from_static(arcs: &'static [u64]) -> Self {
+        Self(Cow::Borrowed(arcs))
+    }
+
+    /// Validates the root arcs according to ITU-T X.660 | ISO/IEC 9834-1, chapter A.2
+    pub fn new(arcs: Vec<u64>) -> Result<Self, Error> {
+        match arcs[..] {
+            [] | [_] => Err(Error::TooFewArcs(arcs.len())),
+            [first, second, ..] if first > 2 || (first < 2 && second > 39) => {
+                Err(Error::InvalidRootArcs(first, second))
+            }
+            _ => Ok(Self(Cow::Owned(arcs))),
+        }
+    }
+
+    #[inline]
+    pub fn arcs(&self) -> &[u64] {
+        &self.0
+    }
+
+    /// The name of the OBJECT IDENTIFIER in [`known`], if listed there
+    pub fn name(&self) -> Option<&'static str> {
+        known::NAMES
+            .iter()
+            .find(|(oid, _)| oid == self)
+            .map(|(_, name)| *name)
+    }
+
+    /// Whether the given OBJECT IDENTIFIER is this one or one of its arcs
+    #[inline]
+    pub fn starts_with(&self, prefix: &Oid) -> bool {
+        self.0.starts_with(&prefix.0)
+    }
+
+    /// The content octets in the basic encoding rules, ITU-T X.690, chapter 8.19
+    pub fn to_der_content(&self) -> Vec<u8> {
+        let mut content = Vec::new();
+        let mut arcs = self.0.iter().copied();
+        let first = arcs.next().unwrap_or_default();
+        let second = arcs.next().unwrap_or_default();
+        // 8.19.4: the first two arcs share the first subidentifier
+        let subidentifiers = core::iter::once(u128::from(first) * 40 + u128::from(second))
+            .chain(arcs.map(u128::from));
+        for subidentifier in subidentifiers {
+            // 8.19.2: base 128 with the most significant group first, each but the last group
+            // with the high bit set
+            let groups = (u128::BITS - subidentifier.leading_zeros())
+                .div_ceil(7)
+                .max(1);
+            for group in (0..groups).rev() {
+                let bits = ((subidentifier >> (group * 7)) & 0x7F) as u8;
+                content.push(if group > 0 { bits | 0x80 } else { bits });
+            }
+        }
+        content
+    }
+
+    /// Parses the content octets of the basic encoding rules, ITU-T X.690, chapter 8.19
+    pub fn from_der_content(content: &[u8]) -> Result<Self, Error> {
+        let mut arcs = Vec::new();
+        let mut subidentifier = 0_u128;
+        let mut complete = true;
+        for byte in content {
+            // 8.19.2: the leading group is not 0x80
+            if complete && *byte == 0x80 {
+                return Err(Error::InvalidEncoding);
+            }
+            subidentifier =
+                subidentifier.checked_mul(128).ok_or(Error::ArcOverflow)? | u128::from(byte & 0x7F);
+            complete = byte & 0x80 == 0;
+            if complete {
+                if arcs.is_empty() {
+                    let first = subidentifier.min(80) / 40;
+                    arcs.push(first as u64);
+                    subidentifier -= first * 40;
+                }
+                arcs.push(u64::try_from(subidentifier).map_err(|_| Error::ArcOverflow)?);
+                subidentifier = 0;
+            }
+        }
+        if arcs.is_empty() || !complete {
+            Err(Error::InvalidEncoding)
+        } else {
+            Ok(Self(Cow::Owned(arcs)))
+        }
+    }
+}
+
+impl Display for Oid {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        for (index, arc) in self.0.iter().enumerate() {
+            if index > 0 {
+                write!(f, ".")?;
+            }
+            write!(f, "{arc}")?;
+        }
+        Ok(())
+    }
+}
+
+impl FromStr for Oid {
+    type Err = Error;
+
+    /// Parses the dotted-decimal notation, such as `2.5.4.3`
+    fn from_str(s: &str) -> Result<Self, Self::Err> {
+        s.split('.')
+            .enumerate()
+            .map(|(index, arc)| {
+                if arc.bytes().all(|b| b.is_ascii_digit()) {
+                    arc.parse::<u64>().ok()
+                } else {
+                    None
+                }
+                .ok_or_else(|| Error::InvalidArc(arc.to_string(), index))
+            })
+            .collect::<Result<Vec<_>, _>>()
+            .and_then(Self::new)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_dotted_decimal() {
+        let oid = "1.2.840.113549.1.1.11".parse::<Oid>().unwrap();
+        assert_eq!(&[1, 2, 840, 113549, 1, 1, 11], oid.arcs());
+        assert_eq!("1.2.840.113549.1.1.11", oid.to_string());
+        assert_eq!(Some("sha256WithRSAEncryption"), oid.name());
+        assert_eq!(None, "1.2.3".parse::<Oid>().unwrap().name());
+    }
+
+    #[test]
+    fn test_invalid_dotted_decimal() {
+        assert_eq!(Err(Error::TooFewArcs(1)), "2".parse::<Oid>());
+        assert_eq!(Err(Error::InvalidRootArcs(3, 1)), "3.1".parse::<Oid>());
+        assert_eq!(Err(Error::InvalidRootArcs(1, 40)), "1.40".parse::<Oid>());
+        assert_eq!(
+            Err(Error::InvalidArc("+4".to_string(), 2)),
+            "2.5.+4".parse::<Oid>()
+        );
+        assert_eq!(
+            Err(Error::InvalidArc(String::new(), 2)),
+            "2.5..3".parse::<Oid>()
+        );
+        assert!("2.999".parse::<Oid>().is_ok());
+    }
+
+    #[test]
+    fn test_der_content() {
+        // ITU-T X.690, chapter 8.19.5
+        let oid = Oid::new(vec![2, 999, 3]).unwrap();
+        assert_eq!(vec![0x88, 0x37, 0x03], oid.to_der_content());
+        assert_eq!(oid, Oid::from_der_content(&[0x88, 0x37, 0x03]).unwrap());
+
+        let content = [0x2A, 0x86, 0x48, 0x86, 0xF7, 0x0D, 0x01, 0x01, 0x01];
+        assert_eq!(known::RSA_ENCRYPTION.to_der_content(), content);
+        assert_eq!(
+            known::RSA_ENCRYPTION,
+            Oid::from_der_content(&content).unwrap()
+        );
+        assert_eq!(
+            Oid::new(vec![1, 39]).unwrap(),
+            Oid::from_der_content(&[0x4F]).unwrap()
+        );
+    }
+
+    #[test]
+    fn test_invalid_der_content() {
+        assert_eq!(Err(Error::InvalidEncoding), Oid::from_der_content(&[]));
+        assert_eq!(
+            Err(Error::InvalidEncoding),
+            Oid::from_der_content(&[0x2A, 0x86])
+        );
+        assert_eq!(
+            Err(Error::InvalidEncoding),
+            Oid::from_der_content(&[0x2A, 0x80, 0x01])
+        );
+        assert_eq!(
+            Err(Error::ArcOverflow),
+            Oid::from_der_content(&[
+                0x2A, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0x7F
+            ])
+        );
+    }
+}
@@ -0,0 +1,412 @@
+//! Reporting of the results and diagnostics of the command line interface, either as human
+//! readable text or as JSON document for automation.
+//!
+//! The JSON document has the following, stable schema:
+//!
+//! ```json
+//! {
+//!   "version": 1,
+//!   "command": "compile",
+//!   "success": true,
+//!   "results": [
+//!     { "module": "MyModule", "files": ["my_module.rs"] }
+//!   ],
+//!   "diagnostics": [
+//!     { "severity": "error", "source": "my_module.asn1", "message": "..." }
+//!   ]
+//! }
+//! ```
+//!
+//! Diagnostics of parse errors additionally contain the stable `code` of the error, the 1-based
+//! `line` and `column` of the offending token (if any) and a description of the `expected`
+//! tokens:
+//!
+//! ```json
+//! { "severity": "error", "source": "my_module.asn1", "message": "...", "code": "P0002",
+//!   "line": 3, "column": 9, "expected": ["\"::=\""] }
+//! ```
+//!
+//! Additional fields might be added to the objects in future versions, but existing fields are
+//! neither removed nor changed in their meaning without increasing `version`.
+
+use asn1rs::model::parse::Error as ParseError;
+use asn1rs::model::validate::{Issue, Severity as IssueSeverity};
+use asn1rs::protocol::jer::write_string;
+use asn1rs::util::to_hex;
+use asn1rs::value::Value;
+use std::fmt::Write;
+
+pub const JSON_SCHEMA_VERSION: u32 = 1;
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq, PartialOrd, Ord, clap::ValueEnum)]
+pub enum OutputFormat {
+    Text,
+    Json,
+}
+
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
+impl Severity {
+    pub const fn as_str(self) -> &'static str {
+        match self {
+            Severity::Error => "error",
+            Severity::Warning => "warning",
+        }
+    }
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Diagnostic {
+    pub severity: Severity,
+    pub source: Option<String>,
+    pub message: String,
+    pub code: Option<&'static str>,
+    /// The 1-based line and column within the source
+    pub location: Option<(usize, usize)>,
+    pub expected: Vec<String>,
+    /// The offending source line with the location being marked
+    pub snippet: Option<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ModuleResult {
+    pub module: String,
+    pub files: Vec<String>,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Report {
+    command: &'static str,
+    results: Vec<ModuleResult>,
+    diagnostics: Vec<Diagnostic>,
+}
+
+impl Report {
+    pub fn new(command: &'static str) -> Self {
+        Self {
+            command,
+            results: Vec::new(),
+            diagnostics: Vec::new(),
+        }
+    }
+
+    pub fn add_result(&mut self, module: impl Into<String>, files: Vec<String>) {
+        self.results.push(ModuleResult {
+            module: module.into(),
+            files,
+        });
+    }
+
+    pub fn add_error(&mut self, source: Option<String>, message: impl Into<String>) {
+        self.diagnostics.push(Diagnostic {
+            severity: Severity::Error,
+            source,
+            message: message.into(),
+            code: None,
+            location: None,
+            expected: Vec::new(),
+            snippet: None,
+        });
+    }
+
+    /// Adds the structured diagnostic of the parse error, `input` is the content of `source`
+    pub fn add_parse_diagnostic(
+        &mut self,
+        severity: Severity,
+        source: String,
+        input: &str,
+        error: &ParseError,
+    ) {
+        let diagnostic = error.diagnostic();
+        self.diagnostics.push(Diagnostic {
+            severity,
+            snippet: diagnostic.snippet(input),
+            source: Some(source),
+            message: diagnostic.message,
+            code: Some(diagnostic.code),
+            location: diagnostic.location.map(|l| (l.line(), l.column())),
+            expected: diagnostic.expected,
+        });
+    }
+
+    /// Adds the issue found by validating a model, warnings are reported as errors if requested
+    pub fn add_validation_issue(&mut self, issue: &Issue, warnings_as_errors: bool) {
+        self.diagnostics.push(Diagnostic {
+            severity: match issue.severity() {
+                IssueSeverity::Warning if !warnings_as_errors => Severity::Warning,
+                _ => Severity::Error,
+            },
+            source: None,
+            message: issue.to_string(),
+            code: Some(issue.code()),
+            location: None,
+            expected: Vec::new(),
+            snippet: None,
+        });
+    }
+
+    pub fn is_success(&self) -> bool {
+        !self
+            .diagnostics
+            .iter()
+            .any(|d| d.severity == Severity::Error)
+    }
+
+    pub fn print(&self, format: OutputFormat) {
+        match format {
+            OutputFormat::Text => print!("{}", self.to_text()),
+            OutputFormat::Json => println!("{}", self.to_json()),
+        }
+    }
+
+    pub fn to_text(&self) -> String {
+        let mut text = String::new();
+        for diagnostic in &self.diagnostics {
+            match diagnostic.code {
+                None => {
+                    let _ = writeln!(text, "{}", diagnostic.message);
+                }
+                Some(code) => {
+                    let _ = writeln!(
+                        text,
+                        "{}[{}]: {}",
+                        diagnostic.severity.as_str(),
+                        code,
+                        diagnostic.message
+                    );
+                    let _ = match (diagnostic.source.as_deref(), diagnostic.location) {
+                        (source, Some((line, column))) => writeln!(
+                            text,
+                            " --> {}:{line}:{column}",
+                            source.unwrap_or("<unknown>")
+                        ),
+                        (Some(source), None) => writeln!(text, " --> {source}"),
+                        (None, None) => Ok(()),
+                    };
+                    if let Some(snippet) = &diagnostic.snippet {
+                        let _ = writeln!(text, "{}", snippet);
+                    }
+                }
+            }
+        }
+        for result in &self.results {
+            let mut files = result.files.iter();
+            if let Some(first) = files.next() {
+                let _ = writeln!(
+                    text,
+                    "Successfully converted {} => {}",
+                    result.module, first
+                );
+            }
+            for file in files {
+                let _ = writeln!(text, "                          => {}", file);
+            }
+        }
+        text
+    }
+
+    pub fn to_json(&self) -> String {
+        let mut json = String::new();
+        let _ = write!(
+            json,
+            "{{\"version\":{},\"command\":{},\"success\":{},\"results\":[",
+            JSON_SCHEMA_VERSION,
+            json_string(self.command),
+            self.is_success()
+        );
+        for (index, result) in self.results.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"module\":{},\"files\":[{}]}}",
+                json_string(&result.module),
+                result
+                    .files
+                    .iter()
+                    .map(|f| json_string(f))
+                    .collect::<Vec<_>>()
+                    .join(",")
+            );
+        }
+        json.push_str("],\"diagnostics\":[");
+        for (index, diagnostic) in self.diagnostics.iter().enumerate() {
+            if index > 0 {
+                json.push(',');
+            }
+            let _ = write!(
+                json,
+                "{{\"severity\":{},\"source\":{},\"message\":{}",
+                json_string(diagnostic.severity.as_str()),
+                diagnostic
+                    .source
+                    .as_deref()
+                    .map(json_string)
+                    .unwrap_or_else(|| "null".to_string()),
+                json_string(&diagnostic.message)
+            );
+            if let Some(code) = diagnostic.code {
+                let _ = write!(json, ",\"code\":{}", json_string(code));
+                if let Some((line, column)) = diagnostic.location {
+                    let _ = write!(json, ",\"line\":{},\"column\":{}", line, column);
+                }
+                let _ = write!(
+                    json,
+                    ",\"expected\":[{}]",
+                    diagnostic
+                        .expected
+                        .iter()
+                        .map(|e| json_string(e))
+                        .collect::<Vec<_>>()
+                        .join(",")
+                );
+            }
+            json.push('}');
+        }
+        json.push_str("]}");
+        json
+    }
+}
+
+/// Quotes and escapes the given string according to RFC 8259, see [`write_string`]
+pub fn json_string(value: &str) -> String {
+    let mut string = String::with_capacity(value.len() + 2);
+    write_string(&mut string, value);
+    string
+}
+
+/// Renders the [`Value`] as JSON, similar to the JSON Encoding Rules (ITU-T X.697): `OCTET
+/// STRING`s are hex strings, `BIT STRING`s objects with the hex `value` and the bit `length`,
+/// `ENUMERATED`s their identifier and `CHOICE`s objects with the alternative as only member.
+pub fn json_value(value: &Value) -> String {
+    let members = |fields: &[(String, Value)]| {
+        fields
+            .iter()
+            .map(|(name, value)| format!("{}:{}", json_string(name), json_value(value)))
+            .collect::<Vec<_>>()
+            .join(",")
+    };
+    match value {
+        Value::Boolean(value) => value.to_string(),
+        Value::Integer(value) => value.to_string(),
+        Value::Null => "null".to_string(),
+        Value::Enumerated(name) => json_string(name),
+        Value::OctetString(bytes) => json_string(&to_hex(bytes)),
+        Value::BitString(bits) => format!(
+            "{{\"value\":{},\"length\":{}}}",
+            json_string(&to_hex(bits.as_byte_slice())),
+            bits.bit_len()
+        ),
+        Value::Sequence(fields) | Value::Set(fields) => format!("{{{}}}", members(fields)),
+        Value::SequenceOf(values) | Value::SetOf(values) => format!(
+            "[{}]",
+            values.iter().map(json_value).collect::<Vec<_>>().join(",")
+        ),
+        Value::Choice(name, value) => format!("{{{}:{}}}", json_string(name), json_value(value)),
+        string => json_string(string.as_str().unwrap_or_default()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_json_string_escaping() {
+        assert_eq!(r#""abc""#, json_string("abc"));
+        assert_eq!(r#""a\"b\\c\nd\u0001""#, json_string("a\"b\\c\nd\u{01}"));
+    }
+
+    #[test]
+    pub fn test_json_report() {
+        let mut report = Report::new("compile");
+        report.add_result("MyModule", vec!["my_module.rs".to_string()]);
+        assert_eq!(
+            r#"{"version":1,"command":"compile","success":true,"results":[{"module":"MyModule","files":["my_module.rs"]}],"diagnostics":[]}"#,
+            report.to_json()
+        );
+
+        report.add_error(Some("broken.asn1".to_string()), "Failed");
+        assert!(!report.is_success());
+        assert!(report.to_json().ends_with(
+            r#""diagnostics":[{"severity":"error","source":"broken.asn1","message":"Failed"}]}"#
+        ));
+    }
+
+    #[test]
+    pub fn test_parse_error_report() {
+        let input =
+            "Broken DEFINITIONS AUTOMATIC TAGS ::= BEGIN\n  Id ::= ENUMERATED { a(x) }\nEND";
+        let error = asn1rs::model::Model::try_from(asn1rs::model::parse::Tokenizer.parse(input))
+            .unwrap_err();
+        let mut report = Report::new("compile");
+        report.add_parse_diagnostic(Severity::Error, "broken.asn1".to_string(), input, &error);
+        assert_eq!(
+            r#"{"version":1,"command":"compile","success":false,"results":[],"diagnostics":[{"severity":"error","source":"broken.asn1","message":"an invalid value for an enum variant was encountered: \"x\"","code":"P0009","line":2,"column":25,"expected":["number"]}]}"#,
+            report.to_json()
+        );
+        assert_eq!(
+            "error[P0009]: an invalid value for an enum variant was encountered: \"x\"\n --> broken.asn1:2:25\n  |\n2 |   Id ::= ENUMERATED { a(x) }\n  |                         ^\n",
+            report.to_text()
+        );
+    }
+
+    #[test]
+    pub fn test_validation_issue_report() {
+        let input = "Ambiguous DEFINITIONS AUTOMATIC TAGS ::= BEGIN\n  Id ::= CHOICE { a INTEGER, b INTEGER }\nEND";
+        let issues = asn1rs::model::Model::try_from(asn1rs::model::parse::Tokenizer.parse(input))
+            .unwrap()
+            .validate();
+        let mut report = Report::new("compile");
+        report.add_validation_issue(&issues[0], false);
+        assert!(report.is_success());
+        assert_eq!(
+            "warning[V0004]: Ambiguous::Id: Alternatives a and b share the tag [UNIVERSAL 2]\n",
+            report.to_text()
+        );
+        report.add_validation_issue(&issues[0], true);
+        assert!(!report.is_success());
+    }
+
+    #[test]
+    pub fn test_text_report() {
+        let mut report = Report::new("compile");
+        report.add_result("MyModule", vec!["a.rs".to_string(), "b.rs".to_string()]);
+        assert_eq!(
+            "Successfully converted MyModule => a.rs\n                          => b.rs\n",
+            report.to_text()
+        );
+    }
+
+    #[test]
+    pub fn test_json_value() {
+        let value = Value::Sequence(vec![
+            ("id".to_string(), Value::Integer(-3)),
+            (
+                "shape".to_string(),
+                Value::Choice(
+                    "label".to_string(),
+                    Box::new(Value::Ia5String("a\"b".to_string())),
+                ),
+            ),
+            (
+                "flags".to_string(),
+                Value::BitString(asn1rs::descriptor::BitVec::from_bytes(vec![0xA0], 3)),
+            ),
+            ("blob".to_string(), Value::OctetString(vec![0x0A, 0xFF])),
+            (
+                "items".to_string(),
+                Value::SequenceOf(vec![Value::Null, Value::Boolean(true)]),
+            ),
+        ]);
+        assert_eq!(
+            r#"{"id":-3,"shape":{"label":"a\"b"},"flags":{"value":"a0","length":3},"blob":"0aff","items":[null,true]}"#,
+            json_value(&value)
+        );
+    }
+}
@@ -0,0 +1,147 @@
+//! PEM armoring (RFC 7468) of DER encoded values, which wraps the base64 representation of the
+//! DER encoding in `-----BEGIN {label}-----` and `-----END {label}-----` lines:
+//!
+//! ```rust
+//! use asn1rs::descriptor::DerAny;
+//! use asn1rs::pem;
+//!
+//! // INTEGER 42
+//! let value = DerAny(vec![0x02, 0x01, 0x2A]);
+//! let pem = pem::encode("SERIAL", &value).unwrap();
+//! assert_eq!("-----BEGIN SERIAL-----\nAgEq\n-----END SERIAL-----\n", pem);
+//! assert_eq!(value, pem::decode::<DerAny>("SERIAL", &pem).unwrap());
+//! ```
+
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::protocol::basic::{Error as DerError, DER};
+use crate::util::{from_base64, to_base64, Error as Base64Error};
+
+/// The number of base64 characters per line, RFC 7468, chapter 2
+const LINE_LEN: usize = 64;
+
+#[derive(Debug)]
+pub enum Error {
+    /// There is no block with the given label
+    MissingBlock(String),
+    Base64(Base64Error),
+    Der(DerError),
+    /// The given number of bytes follow the DER encoded value within the block
+    TrailingBytes(usize),
+}
+
+impl From<Base64Error> for Error {
+    fn from(e: Base64Error) -> Self {
+        Error::Base64(e)
+    }
+}
+
+impl From<DerError> for Error {
+    fn from(e: DerError) -> Self {
+        Error::Der(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::MissingBlock(label) => write!(f, "There is no PEM block labeled {label:?}"),
+            Error::Base64(e) => e.fmt(f),
+            Error::Der(e) => e.fmt(f),
+            Error::TrailingBytes(len) => {
+                write!(f, "The DER encoded value is followed by {len} bytes")
+            }
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Encodes the value in DER and armors it with the given label
+pub fn encode<T: Writable>(label: &str, value: &T) -> Result<String, Error> {
+    let mut buffer = Vec::new();
+    DER::writer(&mut buffer).write(value)?;
+    Ok(to_pem(label, &buffer))
+}
+
+/// Decodes the value from the DER encoding within the first block with the given label
+pub fn decode<T: Readable>(label: &str, pem: &str) -> Result<T, Error> {
+    let bytes = from_pem(label, pem)?;
+    let mut reader = DER::reader(&bytes[..]);
+    let value = reader.read::<T>()?;
+    match reader.into_inner().len() {
+        0 => Ok(value),
+        trailing => Err(Error::TrailingBytes(trailing)),
+    }
+}
+
+/// Armors the given bytes with the given label, in lines of 64 base64 characters
+pub fn to_pem(label: &str, bytes: &[u8]) -> String {
+    let base64 = to_base64(bytes);
+    let mut pem = format!("-----BEGIN {label}-----\n");
+    for line in base64.as_bytes().chunks(LINE_LEN) {
+        // base64 is ASCII only
+        pem.push_str(core::str::from_utf8(line).unwrap_or_default());
+        pem.push('\n');
+    }
+    pem.push_str(&format!("-----END {label}-----\n"));
+    pem
+}
+
+/// Returns the bytes of the first block with the given label. Text around the blocks - such as
+/// the explanatory text of RFC 7468, chapter 5.2 - and other blocks are ignored.
+pub fn from_pem(label: &str, pem: &str) -> Result<Vec<u8>, Error> {
+    let begin = format!("-----BEGIN {label}-----");
+    let end = format!("-----END {label}-----");
+    let content = pem
+        .find(&begin)
+        .map(|index| &pem[index + begin.len()..])
+        .and_then(|content| content.find(&end).map(|index| &content[..index]))
+        .ok_or_else(|| Error::MissingBlock(label.to_string()))?;
+    Ok(from_base64(content)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::descriptor::DerAny;
+
+    #[test]
+    fn test_lines_of_64_characters() {
+        let bytes = (0..100).collect::<Vec<u8>>();
+        let pem = to_pem("TEST", &bytes);
+        let lines = pem.lines().collect::<Vec<_>>();
+        assert_eq!(5, lines.len());
+        assert_eq!("-----BEGIN TEST-----", lines[0]);
+        assert_eq!(64, lines[1].len());
+        assert_eq!(64, lines[2].len());
+        assert_eq!(8, lines[3].len());
+        assert_eq!("-----END TEST-----", lines[4]);
+        assert_eq!(bytes, from_pem("TEST", &pem).unwrap());
+    }
+
+    #[test]
+    fn test_explanatory_text_and_other_blocks_are_ignored() {
+        let pem = format!(
+            "Subject: CN=Other\n{}\nSubject: CN=Test\r\n{}",
+            to_pem("OTHER", &[0x05, 0x00]),
+            to_pem("TEST", &[0x02, 0x01, 0x2A]).replace('\n', "\r\n"),
+        );
+        assert_eq!(
+            DerAny(vec![0x02, 0x01, 0x2A]),
+            decode::<DerAny>("TEST", &pem).unwrap()
+        );
+        assert!(matches!(
+            decode::<DerAny>("CERTIFICATE", &pem),
+            Err(Error::MissingBlock(label)) if label == "CERTIFICATE"
+        ));
+    }
+
+    #[test]
+    fn test_trailing_bytes() {
+        let pem = to_pem("TEST", &[0x05, 0x00, 0xFF]);
+        assert!(matches!(
+            decode::<DerAny>("TEST", &pem),
+            Err(Error::TrailingBytes(1))
+        ));
+    }
+}
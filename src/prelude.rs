@@ -1,7 +1,10 @@
+pub use crate::codec::{decode, encode, Codec};
 pub use crate::descriptor::prelude::*;
 #[cfg(feature = "macros")]
 pub use crate::macros::*;
 #[cfg(feature = "protobuf")]
-pub use crate::protocol::protobuf::ProtobufEq;
+pub use crate::protocol::protobuf::{ProtobufEq, UnknownFields};
 pub use crate::protocol::*;
 pub use crate::rw::*;
+#[cfg(feature = "uuid")]
+pub use uuid::Uuid;
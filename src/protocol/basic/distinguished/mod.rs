@@ -1,5 +1,11 @@
 #![allow(clippy::unusual_byte_groupings)]
 
+mod stream;
+mod tlv;
+
+pub use stream::*;
+pub use tlv::*;
+
 use crate::protocol::basic::err::Error;
 use crate::protocol::basic::{BasicRead, BasicWrite};
 use crate::rw::{BasicReader, BasicWriter};
@@ -99,6 +105,76 @@ impl<T: Read> BasicRead for T {
         self.read_exact(&mut bytes[offset..])?;
         Ok(u64::from_be_bytes(bytes))
     }
+
+    fn read_integer_i128(&mut self, byte_len: u32) -> Result<i128, Error> {
+        let mut bytes = 0i128.to_be_bytes();
+
+        if byte_len as usize > bytes.len() {
+            return Err(Error::unsupported_byte_len(
+                bytes.len() as u8,
+                byte_len as u8,
+            ));
+        }
+
+        let offset = bytes.len() - byte_len as usize;
+        self.read_exact(&mut bytes[offset..])?;
+        if bytes.get(offset).is_some_and(|byte| byte & 0x80 != 0) {
+            bytes[..offset].fill(0xFF);
+        }
+        Ok(i128::from_be_bytes(bytes))
+    }
+
+    fn read_bit_string(&mut self, byte_len: u64) -> Result<(Vec<u8>, u64), Error> {
+        if byte_len == 0 {
+            return Err(Error::unexpected_length(1..u64::MAX, byte_len));
+        }
+        let mut unused = [0u8; 1];
+        self.read_exact(&mut unused[..])?;
+        let unused = unused[0];
+        // 8.6.2.2 and 8.6.2.3
+        if unused > 7 || (byte_len == 1 && unused != 0) {
+            return Err(Error::invalid_unused_bits(unused));
+        }
+        let mut bytes = Vec::new();
+        self.by_ref().take(byte_len - 1).read_to_end(&mut bytes)?;
+        if bytes.len() as u64 != byte_len - 1 {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        let bit_len = bytes.len() as u64 * u64::from(u8::BITS) - u64::from(unused);
+        Ok((bytes, bit_len))
+    }
+
+    fn read_tlv(&mut self) -> Result<Vec<u8>, Error> {
+        /// Keeps the identifier and length octets while they are parsed
+        struct Recording<'a, R: Read> {
+            read: &'a mut R,
+            bytes: Vec<u8>,
+        }
+
+        impl<R: Read> Read for Recording<'_, R> {
+            fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+                let len = self.read.read(buf)?;
+                self.bytes.extend_from_slice(&buf[..len]);
+                Ok(len)
+            }
+        }
+
+        let mut recording = Recording {
+            read: self,
+            bytes: Vec::new(),
+        };
+        let mut identifier = [0u8; 1];
+        recording.read_exact(&mut identifier[..])?;
+        let header = stream::read_header(&mut recording, identifier[0], &mut 0)?;
+
+        let mut bytes = recording.bytes;
+        let header_len = bytes.len();
+        self.by_ref().take(header.len).read_to_end(&mut bytes)?;
+        if (bytes.len() - header_len) as u64 != header.len {
+            return Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into());
+        }
+        Ok(bytes)
+    }
 }
 
 impl<T: Write> BasicWrite for T {
@@ -154,6 +230,43 @@ impl<T: Write> BasicWrite for T {
         self.write_all(&bytes[offset as usize..])?;
         Ok(())
     }
+
+    #[inline]
+    fn write_integer_i128(&mut self, value: i128) -> Result<(), Error> {
+        let bytes = value.to_be_bytes();
+        self.write_all(&bytes[bytes.len() - integer_i128_len(value)..])?;
+        Ok(())
+    }
+
+    fn write_bit_string(&mut self, value: &[u8], bit_len: u64) -> Result<(), Error> {
+        let byte_len = bit_len.div_ceil(u64::from(u8::BITS));
+        let unused = (byte_len * u64::from(u8::BITS) - bit_len) as u8;
+        let mut content = (0..byte_len as usize)
+            .map(|index| value.get(index).copied().unwrap_or_default())
+            .collect::<Vec<u8>>();
+        if let Some(last) = content.last_mut() {
+            *last &= 0xFF << unused;
+        }
+        self.write_all(&[unused])?;
+        self.write_all(&content)?;
+        Ok(())
+    }
+
+    #[inline]
+    fn write_tlv(&mut self, tlv: &[u8]) -> Result<(), Error> {
+        Ok(self.write_all(tlv)?)
+    }
+}
+
+/// The number of bytes of the minimal two's complement of the value, ITU-T X.690, chapter 8.3.2
+pub fn integer_i128_len(value: i128) -> usize {
+    let redundant_bits = if value.is_negative() {
+        value.leading_ones()
+    } else {
+        value.leading_zeros()
+    };
+    // the most significant bit of the first byte must remain the sign bit
+    core::mem::size_of::<i128>() - (redundant_bits.saturating_sub(1) / u8::BITS) as usize
 }
 
 #[cfg(test)]
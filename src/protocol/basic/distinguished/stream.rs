@@ -0,0 +1,214 @@
+use super::{
+    CLASS_BITS_APPLICATION, CLASS_BITS_CONTEXT_SPECIFIC, CLASS_BITS_MASK, CLASS_BITS_UNIVERSAL,
+    LENGTH_BIT_MASK, LENGTH_BIT_SHORT_FORM,
+};
+use crate::protocol::basic::err::Error;
+use asn1rs_model::asn::Tag;
+use std::io::Read;
+
+const CONSTRUCTED_BIT: u8 = 0b0010_0000;
+const NUMBER_BITS_MASK: u8 = 0b0001_1111;
+
+/// The maximum content length of a primitive TLV that [`DerStreamReader`] buffers by default
+pub const DEFAULT_MAX_CONTENT_LEN: u64 = 16 * 1024 * 1024;
+
+/// The identifier and length octets of a TLV, ITU-T X.690, chapter 8.1
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Header {
+    pub tag: Tag,
+    pub constructed: bool,
+    /// The length of the content octets
+    pub len: u64,
+}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum DerToken {
+    /// A constructed TLV, whose nested TLVs follow until the matching [`DerToken::End`]
+    Start(Header),
+    /// A primitive TLV with its content octets
+    Primitive { header: Header, content: Vec<u8> },
+    /// The end of the content of the constructed TLV with the given tag
+    End(Tag),
+}
+
+/// Parses the TLVs of DER encoded data from any [`Read`] one at a time. Only the content octets of
+/// the current primitive TLV are kept in memory, so that large structures - such as
+/// multi-megabyte certificate bundles - are decoded without loading them as a whole.
+///
+/// ```rust
+/// use asn1rs::model::asn::Tag;
+/// use asn1rs::protocol::basic::{DerStreamReader, DerToken};
+///
+/// // SEQUENCE { INTEGER 5, BOOLEAN TRUE }
+/// let bytes: &[u8] = &[0x30, 0x06, 0x02, 0x01, 0x05, 0x01, 0x01, 0xFF];
+/// let mut reader = DerStreamReader::new(bytes);
+/// let mut integers = Vec::new();
+/// while let Some(token) = reader.next_token().unwrap() {
+///     if let DerToken::Primitive { header, content } = token {
+///         if header.tag == Tag::Universal(2) {
+///             integers.push(content);
+///         }
+///     }
+/// }
+/// assert_eq!(vec![vec![0x05]], integers);
+/// ```
+pub struct DerStreamReader<R: Read> {
+    read: R,
+    /// The tags and the number of remaining content octets of the enclosing constructed TLVs
+    enclosing: Vec<(Tag, u64)>,
+    max_content_len: u64,
+}
+
+impl<R: Read> DerStreamReader<R> {
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            enclosing: Vec::default(),
+            max_content_len: DEFAULT_MAX_CONTENT_LEN,
+        }
+    }
+
+    /// Rejects primitive TLVs with more content octets instead of buffering them
+    #[inline]
+    pub fn with_max_content_len(mut self, max_content_len: u64) -> Self {
+        self.max_content_len = max_content_len;
+        self
+    }
+
+    /// The number of constructed TLVs that have been started but not yet ended
+    #[inline]
+    pub fn depth(&self) -> usize {
+        self.enclosing.len()
+    }
+
+    #[inline]
+    pub fn into_inner(self) -> R {
+        self.read
+    }
+
+    /// Reads the next token or returns `None` if the input ends after a complete top-level TLV
+    pub fn next_token(&mut self) -> Result<Option<DerToken>, Error> {
+        if let Some((tag, 0)) = self.enclosing.last().copied() {
+            self.enclosing.pop();
+            return Ok(Some(DerToken::End(tag)));
+        }
+
+        let mut header_len = 0_u64;
+        let Some(first) = read_byte_or_eof(&mut self.read, &mut header_len)? else {
+            return if self.enclosing.is_empty() {
+                Ok(None)
+            } else {
+                Err(unexpected_eof())
+            };
+        };
+        let header = read_header(&mut self.read, first, &mut header_len)?;
+
+        if let Some((_, remaining)) = self.enclosing.last_mut() {
+            let available = remaining
+                .checked_sub(header_len)
+                .ok_or_else(|| Error::unexpected_length(0..*remaining + 1, header_len))?;
+            if header.len > available {
+                return Err(Error::unexpected_length(0..available + 1, header.len));
+            }
+            *remaining = available - header.len;
+        }
+
+        if header.constructed {
+            self.enclosing.push((header.tag, header.len));
+            Ok(Some(DerToken::Start(header)))
+        } else if header.len > self.max_content_len {
+            Err(Error::unexpected_length(
+                0..self.max_content_len + 1,
+                header.len,
+            ))
+        } else {
+            let mut content = Vec::new();
+            self.read
+                .by_ref()
+                .take(header.len)
+                .read_to_end(&mut content)?;
+            if content.len() as u64 != header.len {
+                return Err(unexpected_eof());
+            }
+            Ok(Some(DerToken::Primitive { header, content }))
+        }
+    }
+}
+
+fn read_byte_or_eof<R: Read>(read: &mut R, counter: &mut u64) -> Result<Option<u8>, Error> {
+    let mut byte = [0u8; 1];
+    loop {
+        return match read.read(&mut byte[..]) {
+            Ok(0) => Ok(None),
+            Ok(_) => {
+                *counter += 1;
+                Ok(Some(byte[0]))
+            }
+            Err(e) if e.kind() == std::io::ErrorKind::Interrupted => continue,
+            Err(e) => Err(e.into()),
+        };
+    }
+}
+
+fn read_byte<R: Read>(read: &mut R, counter: &mut u64) -> Result<u8, Error> {
+    read_byte_or_eof(read, counter)?.ok_or_else(unexpected_eof)
+}
+
+/// Reads the remaining identifier and length octets after the given first identifier octet and
+/// adds their number to the counter, ITU-T X.690, chapter 8.1.2 and 8.1.3
+pub(crate) fn read_header<R: Read>(
+    read: &mut R,
+    identifier: u8,
+    counter: &mut u64,
+) -> Result<Header, Error> {
+    let mut number = usize::from(identifier & NUMBER_BITS_MASK);
+    if number == usize::from(NUMBER_BITS_MASK) {
+        // 8.1.2.4: the number follows in base 128 with the most significant group first
+        number = 0;
+        loop {
+            let byte = read_byte(read, counter)?;
+            number = number
+                .checked_mul(128)
+                .ok_or_else(|| Error::unsupported_byte_len(8, 9))?
+                | usize::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+    let tag = match identifier & CLASS_BITS_MASK {
+        CLASS_BITS_UNIVERSAL => Tag::Universal(number),
+        CLASS_BITS_APPLICATION => Tag::Application(number),
+        CLASS_BITS_CONTEXT_SPECIFIC => Tag::ContextSpecific(number),
+        _ => Tag::Private(number),
+    };
+
+    let length = read_byte(read, counter)?;
+    let len = if length & LENGTH_BIT_MASK == LENGTH_BIT_SHORT_FORM {
+        // 8.1.3.4
+        u64::from(length)
+    } else {
+        // 8.1.3.5, the indefinite form of 8.1.3.6 is not permitted by 10.1
+        let byte_len = length & !LENGTH_BIT_MASK;
+        if byte_len == 0 {
+            return Err(Error::indefinite_length());
+        } else if usize::from(byte_len) > core::mem::size_of::<u64>() {
+            return Err(Error::unsupported_byte_len(8, byte_len));
+        }
+        let mut len = 0_u64;
+        for _ in 0..byte_len {
+            len = (len << 8) | u64::from(read_byte(read, counter)?);
+        }
+        len
+    };
+
+    Ok(Header {
+        tag,
+        constructed: identifier & CONSTRUCTED_BIT != 0,
+        len,
+    })
+}
+
+fn unexpected_eof() -> Error {
+    std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into()
+}
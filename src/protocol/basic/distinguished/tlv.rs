@@ -0,0 +1,107 @@
+use super::stream::{read_header, Header};
+use crate::protocol::basic::err::Error;
+use asn1rs_model::asn::Tag;
+
+/// A TLV within DER encoded bytes, borrowing its content
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Tlv<'a> {
+    pub header: Header,
+    /// The content octets
+    pub content: &'a [u8],
+    /// The identifier, length and content octets, to pass the TLV through unchanged
+    pub raw: &'a [u8],
+}
+
+impl<'a> Tlv<'a> {
+    #[inline]
+    pub fn tag(&self) -> Tag {
+        self.header.tag
+    }
+
+    #[inline]
+    pub fn is_constructed(&self) -> bool {
+        self.header.constructed
+    }
+
+    /// The TLVs within the content of a constructed TLV, none for a primitive TLV
+    #[inline]
+    pub fn children(&self) -> TlvIter<'a> {
+        if self.header.constructed {
+            TlvIter::new(self.content)
+        } else {
+            TlvIter::new(&[])
+        }
+    }
+}
+
+/// Walks the consecutive TLVs of DER encoded bytes without generated types. Nested TLVs are
+/// visited through [`Tlv::children`]. The iteration ends after the first error.
+///
+/// ```rust
+/// use asn1rs::model::asn::Tag;
+/// use asn1rs::protocol::basic::TlvIter;
+///
+/// // SEQUENCE { INTEGER 5, BOOLEAN TRUE }
+/// let bytes = [0x30, 0x06, 0x02, 0x01, 0x05, 0x01, 0x01, 0xFF];
+/// let sequence = TlvIter::new(&bytes).next().unwrap().unwrap();
+/// assert_eq!(Tag::Universal(16), sequence.tag());
+///
+/// let fields = sequence.children().collect::<Result<Vec<_>, _>>().unwrap();
+/// assert_eq!(Tag::Universal(2), fields[0].tag());
+/// assert_eq!(&[0x05], fields[0].content);
+/// assert_eq!(&[0x01, 0x01, 0xFF], fields[1].raw);
+/// ```
+#[derive(Debug, Clone)]
+pub struct TlvIter<'a> {
+    remaining: &'a [u8],
+}
+
+impl<'a> TlvIter<'a> {
+    #[inline]
+    pub fn new(bytes: &'a [u8]) -> Self {
+        Self { remaining: bytes }
+    }
+
+    /// The bytes after the TLVs that have been iterated
+    #[inline]
+    pub fn remaining(&self) -> &'a [u8] {
+        self.remaining
+    }
+
+    fn read_tlv(&mut self) -> Result<Tlv<'a>, Error> {
+        let bytes = self.remaining;
+        let mut input = bytes;
+        let mut header_len = 0_u64;
+        let mut identifier = [0u8; 1];
+        std::io::Read::read_exact(&mut input, &mut identifier[..])?;
+        let header = read_header(&mut input, identifier[0], &mut header_len)?;
+        if header.len > input.len() as u64 {
+            return Err(Error::unexpected_length(
+                0..input.len() as u64 + 1,
+                header.len,
+            ));
+        }
+        let (content, rest) = input.split_at(header.len as usize);
+        self.remaining = rest;
+        Ok(Tlv {
+            header,
+            content,
+            raw: &bytes[..bytes.len() - rest.len()],
+        })
+    }
+}
+
+impl<'a> Iterator for TlvIter<'a> {
+    type Item = Result<Tlv<'a>, Error>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.remaining.is_empty() {
+            return None;
+        }
+        let result = self.read_tlv();
+        if result.is_err() {
+            self.remaining = &[];
+        }
+        Some(result)
+    }
+}
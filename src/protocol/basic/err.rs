@@ -34,6 +34,24 @@ impl Error {
     pub fn unsupported_byte_len(max: u8, got: u8) -> Self {
         Self::from(ErrorKind::UnsupportedByteLen { max, got })
     }
+
+    #[cold]
+    #[inline(never)]
+    pub fn invalid_unused_bits(got: u8) -> Self {
+        Self::from(ErrorKind::InvalidUnusedBits { got })
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn indefinite_length() -> Self {
+        Self::from(ErrorKind::IndefiniteLength)
+    }
+
+    #[cold]
+    #[inline(never)]
+    pub fn padding_bits_not_zero() -> Self {
+        Self::from(ErrorKind::PaddingBitsNotZero)
+    }
 }
 
 impl From<ErrorKind> for Error {
@@ -90,10 +108,33 @@ impl From<ErrorKind> for Inner {
 
 #[derive(Debug)]
 pub enum ErrorKind {
-    UnexpectedTypeTag { expected: Tag, got: Tag },
-    UnexpectedTypeLength { expected: Range<u64>, got: u64 },
-    UnexpectedChoiceIndex { expected: Range<u64>, got: u64 },
-    UnsupportedByteLen { max: u8, got: u8 },
+    UnexpectedTypeTag {
+        expected: Tag,
+        got: Tag,
+    },
+    UnexpectedTypeLength {
+        expected: Range<u64>,
+        got: u64,
+    },
+    UnexpectedChoiceIndex {
+        expected: Range<u64>,
+        got: u64,
+    },
+    UnsupportedByteLen {
+        max: u8,
+        got: u8,
+    },
+    /// The initial octet of a BIT STRING exceeds 7 unused bits or the BIT STRING is empty but
+    /// claims unused bits, ITU-T X.690, chapter 8.6.2
+    InvalidUnusedBits {
+        got: u8,
+    },
+    /// The unused bits in the final octet of a BIT STRING are not zero, as DER requires in
+    /// ITU-T X.690, chapter 11.2.1
+    PaddingBitsNotZero,
+    /// The indefinite form of the length octets, which DER does not permit,
+    /// ITU-T X.690, chapter 10.1
+    IndefiniteLength,
     IoError(std::io::Error),
 }
 
@@ -115,6 +156,15 @@ impl Display for ErrorKind {
                     "Unsupported byte length received, max={max:?} but got {got:?}"
                 )
             }
+            ErrorKind::InvalidUnusedBits { got } => {
+                write!(f, "Invalid number of unused bits in a BIT STRING: {got}")
+            }
+            ErrorKind::PaddingBitsNotZero => {
+                write!(f, "The unused bits of the BIT STRING are not zero")
+            }
+            ErrorKind::IndefiniteLength => {
+                write!(f, "The indefinite form of the length is not permitted")
+            }
             ErrorKind::IoError(e) => {
                 write!(f, "Experienced underlying IO error: {e:?}")
             }
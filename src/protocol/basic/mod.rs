@@ -6,6 +6,7 @@ mod err;
 
 pub use distinguished::*;
 pub use err::Error;
+pub use err::ErrorKind;
 
 use asn1rs_model::asn::Tag;
 
@@ -30,6 +31,19 @@ pub trait BasicRead {
 
     /// According to ITU-T X.690, chapter 8.3, the integer type is represented in a series of bytes.
     fn read_integer_u64(&mut self, byte_len: u32) -> Result<u64, Error>;
+
+    /// According to ITU-T X.690, chapter 8.3, the integer type is represented in a series of bytes
+    /// of the two's complement, which is sign extended to 128 bits.
+    fn read_integer_i128(&mut self, byte_len: u32) -> Result<i128, Error>;
+
+    /// According to ITU-T X.690, chapter 8.6.2, the bit string type is represented by an initial
+    /// octet with the number of unused bits in the final octet, followed by the bits. Returns the
+    /// octets - with the unused bits as read - and the number of bits.
+    fn read_bit_string(&mut self, byte_len: u64) -> Result<(Vec<u8>, u64), Error>;
+
+    /// According to ITU-T X.690, chapter 8.1, reads the identifier, length and content octets of
+    /// the next TLV as they are.
+    fn read_tlv(&mut self) -> Result<Vec<u8>, Error>;
 }
 
 /// According to ITU-T X.690
@@ -53,4 +67,17 @@ pub trait BasicWrite {
 
     /// According to ITU-T X.690, chapter 8.3, the integer type is represented in a series of bytes.
     fn write_integer_u64(&mut self, value: u64) -> Result<(), Error>;
+
+    /// According to ITU-T X.690, chapter 8.3, the integer type is represented in the minimal
+    /// number of bytes of the two's complement.
+    fn write_integer_i128(&mut self, value: i128) -> Result<(), Error>;
+
+    /// According to ITU-T X.690, chapter 8.6.2, the bit string type is represented by an initial
+    /// octet with the number of unused bits in the final octet, followed by the bits. The unused
+    /// bits are zero (11.2.1) and missing octets of the value are written as zero.
+    fn write_bit_string(&mut self, value: &[u8], bit_len: u64) -> Result<(), Error>;
+
+    /// According to ITU-T X.690, chapter 8.1, writes the identifier, length and content octets
+    /// of a complete TLV as they are.
+    fn write_tlv(&mut self, tlv: &[u8]) -> Result<(), Error>;
 }
@@ -0,0 +1,293 @@
+//! This module contains the JSON primitives of the JSON Encoding Rules (JER, ITU-T X.697): a
+//! minimal JSON document model, its parser and the string escaping.
+
+use std::fmt::Write;
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The JSON document is malformed at the given char index
+    InvalidSyntax(usize),
+    /// The JSON document ends unexpectedly
+    UnexpectedEnd,
+    /// The number at the given char index is not an integer or exceeds 128 bits
+    UnsupportedNumber(usize),
+    /// A value of the given kind was expected, but a different one was found
+    UnexpectedValue(&'static str),
+    /// The member for the given field is missing in the JSON object
+    MissingMember(&'static str),
+    /// The identifier is not an alternative of the CHOICE or a variant of the ENUMERATED
+    UnknownIdentifier(String),
+    /// The value does not consist of hex digits
+    InvalidHexString(String),
+    /// The value is not encoded in (standard or URL-safe) base64
+    InvalidBase64String(String),
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidSyntax(index) => write!(f, "Invalid JSON syntax at index {index}"),
+            Error::UnexpectedEnd => write!(f, "Unexpected end of the JSON document"),
+            Error::UnsupportedNumber(index) => {
+                write!(f, "The number at index {index} is not a 128 bit integer")
+            }
+            Error::UnexpectedValue(expected) => write!(f, "Expected a JSON {expected}"),
+            Error::MissingMember(name) => write!(f, "The member '{name}' is missing"),
+            Error::UnknownIdentifier(name) => write!(f, "The identifier '{name}' is unknown"),
+            Error::InvalidHexString(value) => write!(f, "'{value}' is not a hex string"),
+            Error::InvalidBase64String(value) => write!(f, "'{value}' is not a base64 string"),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// A parsed JSON document. The members of objects keep their order, numbers are restricted to
+/// integers, because JER encodes REAL values (which are not supported) only as numbers.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Json {
+    Null,
+    Boolean(bool),
+    Number(i128),
+    String(String),
+    Array(Vec<Json>),
+    Object(Vec<(String, Json)>),
+}
+
+impl Json {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        let mut parser = Parser {
+            chars: json.chars().enumerate().peekable(),
+        };
+        let value = parser.parse_value()?;
+        match parser.next_significant() {
+            None => Ok(value),
+            Some((index, _)) => Err(Error::InvalidSyntax(index)),
+        }
+    }
+}
+
+struct Parser<'a> {
+    chars: core::iter::Peekable<core::iter::Enumerate<std::str::Chars<'a>>>,
+}
+
+impl Parser<'_> {
+    fn next_significant(&mut self) -> Option<(usize, char)> {
+        self.chars.find(|(_, char)| !char.is_whitespace())
+    }
+
+    fn peek_significant(&mut self) -> Option<char> {
+        while let Some((_, char)) = self.chars.peek() {
+            if char.is_whitespace() {
+                self.chars.next();
+            } else {
+                return Some(*char);
+            }
+        }
+        None
+    }
+
+    fn expect(&mut self, expected: char) -> Result<(), Error> {
+        match self.next_significant() {
+            Some((_, char)) if char == expected => Ok(()),
+            Some((index, _)) => Err(Error::InvalidSyntax(index)),
+            None => Err(Error::UnexpectedEnd),
+        }
+    }
+
+    fn parse_value(&mut self) -> Result<Json, Error> {
+        let (index, char) = self.next_significant().ok_or(Error::UnexpectedEnd)?;
+        match char {
+            '{' => self.parse_object(),
+            '[' => self.parse_array(),
+            '"' => self.parse_string().map(Json::String),
+            '-' | '0'..='9' => self.parse_number(index, char),
+            _ => self.parse_literal(index, char),
+        }
+    }
+
+    fn parse_object(&mut self) -> Result<Json, Error> {
+        let mut members = Vec::new();
+        if self.peek_significant() == Some('}') {
+            self.chars.next();
+            return Ok(Json::Object(members));
+        }
+        loop {
+            self.expect('"')?;
+            let name = self.parse_string()?;
+            self.expect(':')?;
+            members.push((name, self.parse_value()?));
+            match self.next_significant() {
+                Some((_, ',')) => continue,
+                Some((_, '}')) => return Ok(Json::Object(members)),
+                Some((index, _)) => return Err(Error::InvalidSyntax(index)),
+                None => return Err(Error::UnexpectedEnd),
+            }
+        }
+    }
+
+    fn parse_array(&mut self) -> Result<Json, Error> {
+        let mut values = Vec::new();
+        if self.peek_significant() == Some(']') {
+            self.chars.next();
+            return Ok(Json::Array(values));
+        }
+        loop {
+            values.push(self.parse_value()?);
+            match self.next_significant() {
+                Some((_, ',')) => continue,
+                Some((_, ']')) => return Ok(Json::Array(values)),
+                Some((index, _)) => return Err(Error::InvalidSyntax(index)),
+                None => return Err(Error::UnexpectedEnd),
+            }
+        }
+    }
+
+    /// Parses the remainder of a string, after the opening quote
+    fn parse_string(&mut self) -> Result<String, Error> {
+        let mut string = String::new();
+        loop {
+            match self.chars.next().ok_or(Error::UnexpectedEnd)? {
+                (_, '"') => return Ok(string),
+                (_, '\\') => match self.chars.next().ok_or(Error::UnexpectedEnd)? {
+                    (_, '"') => string.push('"'),
+                    (_, '\\') => string.push('\\'),
+                    (_, '/') => string.push('/'),
+                    (_, 'b') => string.push('\u{08}'),
+                    (_, 'f') => string.push('\u{0C}'),
+                    (_, 'n') => string.push('\n'),
+                    (_, 'r') => string.push('\r'),
+                    (_, 't') => string.push('\t'),
+                    (index, 'u') => string.push(self.parse_unicode_escape(index)?),
+                    (index, _) => return Err(Error::InvalidSyntax(index)),
+                },
+                (index, char) if u32::from(char) < 0x20 => return Err(Error::InvalidSyntax(index)),
+                (_, char) => string.push(char),
+            }
+        }
+    }
+
+    fn parse_unicode_escape(&mut self, index: usize) -> Result<char, Error> {
+        let high = self.parse_hex4()?;
+        let code = if (0xD800..0xDC00).contains(&high) {
+            self.expect_raw('\\')?;
+            self.expect_raw('u')?;
+            let low = self.parse_hex4()?;
+            if !(0xDC00..0xE000).contains(&low) {
+                return Err(Error::InvalidSyntax(index));
+            }
+            0x10000 + ((high - 0xD800) << 10) + (low - 0xDC00)
+        } else {
+            high
+        };
+        char::from_u32(code).ok_or(Error::InvalidSyntax(index))
+    }
+
+    fn expect_raw(&mut self, expected: char) -> Result<(), Error> {
+        match self.chars.next() {
+            Some((_, char)) if char == expected => Ok(()),
+            Some((index, _)) => Err(Error::InvalidSyntax(index)),
+            None => Err(Error::UnexpectedEnd),
+        }
+    }
+
+    fn parse_hex4(&mut self) -> Result<u32, Error> {
+        let mut code = 0;
+        for _ in 0..4 {
+            let (index, char) = self.chars.next().ok_or(Error::UnexpectedEnd)?;
+            code = (code << 4) | char.to_digit(16).ok_or(Error::InvalidSyntax(index))?;
+        }
+        Ok(code)
+    }
+
+    fn parse_number(&mut self, index: usize, first: char) -> Result<Json, Error> {
+        let mut number = String::from(first);
+        while let Some((_, char)) = self.chars.peek() {
+            if char.is_ascii_digit() || matches!(char, '.' | 'e' | 'E' | '+' | '-') {
+                number.push(*char);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        number
+            .parse::<i128>()
+            .map(Json::Number)
+            .map_err(|_| Error::UnsupportedNumber(index))
+    }
+
+    fn parse_literal(&mut self, index: usize, first: char) -> Result<Json, Error> {
+        let mut literal = String::from(first);
+        while let Some((_, char)) = self.chars.peek() {
+            if char.is_ascii_alphabetic() {
+                literal.push(*char);
+                self.chars.next();
+            } else {
+                break;
+            }
+        }
+        match literal.as_str() {
+            "null" => Ok(Json::Null),
+            "true" => Ok(Json::Boolean(true)),
+            "false" => Ok(Json::Boolean(false)),
+            _ => Err(Error::InvalidSyntax(index)),
+        }
+    }
+}
+
+/// Appends the value as quoted and escaped JSON string
+pub fn write_string(output: &mut String, value: &str) {
+    output.push('"');
+    for char in value.chars() {
+        match char {
+            '"' => output.push_str("\\\""),
+            '\\' => output.push_str("\\\\"),
+            '\n' => output.push_str("\\n"),
+            '\r' => output.push_str("\\r"),
+            '\t' => output.push_str("\\t"),
+            c if u32::from(c) < 0x20 => {
+                let _ = write!(output, "\\u{:04x}", u32::from(c));
+            }
+            c => output.push(c),
+        }
+    }
+    output.push('"');
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_parse() {
+        assert_eq!(
+            Json::Object(vec![
+                ("a".to_string(), Json::Number(-12)),
+                (
+                    "b".to_string(),
+                    Json::Array(vec![Json::Null, Json::Boolean(true), Json::Boolean(false)])
+                ),
+                ("c".to_string(), Json::String("x\"\n\u{e9}\u{1F600}".to_string())),
+                ("d".to_string(), Json::Object(Vec::new())),
+            ]),
+            Json::parse(r#" { "a": -12, "b": [null, true, false], "c": "x\"\n\u00e9\ud83d\ude00", "d": {} } "#)
+                .unwrap()
+        );
+    }
+
+    #[test]
+    pub fn test_parse_errors() {
+        assert_eq!(Err(Error::UnexpectedEnd), Json::parse(r#"{"a": "#));
+        assert_eq!(Err(Error::InvalidSyntax(4)), Json::parse("[1, }"));
+        assert_eq!(Err(Error::InvalidSyntax(3)), Json::parse("{} x"));
+        assert_eq!(Err(Error::UnsupportedNumber(0)), Json::parse("1.5"));
+        assert_eq!(Err(Error::InvalidSyntax(0)), Json::parse("nil"));
+    }
+
+    #[test]
+    pub fn test_write_string() {
+        let mut output = String::new();
+        write_string(&mut output, "a\"b\\c\nd\u{01}");
+        assert_eq!(r#""a\"b\\c\nd\u0001""#, output);
+    }
+}
@@ -7,6 +7,7 @@
 //! ```
 
 pub mod basic;
+pub mod jer;
 pub mod per;
 #[cfg(feature = "protobuf")]
 pub mod protobuf;
@@ -84,6 +84,7 @@ pub(crate) struct Inner {
 pub enum ErrorKind {
     FromUtf8Error(FromUtf8Error),
     InvalidString(Charset, char, usize),
+    CharacterNotPermitted(char, usize),
     UnsupportedOperation(String),
     InsufficientSpaceInDestinationBuffer(Backtrace),
     InsufficientDataInSourceBuffer(Backtrace),
@@ -94,13 +95,17 @@ pub enum ErrorKind {
     },
     InvalidChoiceIndex(u64, u64),
     ExtensionFieldsInconsistent(String),
-    ValueNotInRange(i64, i64, i64),
+    ValueNotInRange(i128, i128, i128),
     ValueExceedsMaxInt,
     ValueIsNegativeButExpectedUnsigned(i64),
     SizeNotInRange(u64, u64, u64),
+    /// The announced number of values and the number of values that were given (so far)
+    ValueCountMismatch(usize, usize),
     BitLenNotInRange(u64, u64, u64),
     OptFlagsExhausted,
     EndOfStream,
+    /// The number of bits required and the number of bits the provided slice can hold
+    BufferTooSmall(usize, usize),
 }
 
 impl Error {
@@ -151,6 +156,11 @@ impl std::fmt::Display for ErrorKind {
                     charset, index, char
                 )
             }
+            Self::CharacterNotPermitted(char, index) => write!(
+                f,
+                "The character at index {} is not within the permitted alphabet: {}",
+                index, char
+            ),
             Self::UnsupportedOperation(o) => write!(f, "The operation is not supported: {}", o),
             Self::InsufficientSpaceInDestinationBuffer(backtrace) => write!(
                 f,
@@ -216,6 +226,11 @@ impl std::fmt::Display for ErrorKind {
                 "The size {} is not within the inclusive range of {} and {}",
                 size, min, max
             ),
+            Self::ValueCountMismatch(announced, given) => write!(
+                f,
+                "The length {} was announced but {} values were given",
+                announced, given
+            ),
             Self::BitLenNotInRange(size, min, max) => write!(
                 f,
                 "The length {} is not within the inclusive range of {} and {} for a bit field",
@@ -226,6 +241,11 @@ impl std::fmt::Display for ErrorKind {
                 f,
                 "Can no longer read or write any bytes from the underlying dataset"
             ),
+            Self::BufferTooSmall(required, capacity) => write!(
+                f,
+                "The buffer can hold {} bits but {} bits are required",
+                capacity, required
+            ),
         }
     }
 }
@@ -237,6 +257,9 @@ impl PartialEq for ErrorKind {
             Self::InvalidString(a, b, c) => {
                 matches!(other, Self::InvalidString(oa, ob, oc) if (a, b, c) == (oa, ob, oc))
             }
+            Self::CharacterNotPermitted(a, b) => {
+                matches!(other, Self::CharacterNotPermitted(oa, ob) if (a, b) == (oa, ob))
+            }
             Self::UnsupportedOperation(a) => {
                 matches!(other, Self::UnsupportedOperation(oa) if a == oa)
             }
@@ -265,11 +288,17 @@ impl PartialEq for ErrorKind {
             Self::SizeNotInRange(a, b, c) => {
                 matches!(other, Self::SizeNotInRange(oa, ob, oc) if (a,b ,c) == (oa, ob,oc))
             }
+            Self::ValueCountMismatch(a, b) => {
+                matches!(other, Self::ValueCountMismatch(oa, ob) if (a, b) == (oa, ob))
+            }
             Self::BitLenNotInRange(a, b, c) => {
                 matches!(other, Self::BitLenNotInRange(oa, ob, oc) if (a,b ,c) == (oa, ob,oc))
             }
             Self::OptFlagsExhausted => matches!(other, Self::OptFlagsExhausted),
             Self::EndOfStream => matches!(other, Self::EndOfStream),
+            Self::BufferTooSmall(a, b) => {
+                matches!(other, Self::BufferTooSmall(oa, ob) if (a, b) == (oa, ob))
+            }
         }
     }
 }
@@ -2,6 +2,9 @@
 //! which the encoding/decoding depends on the UNALIGNED flag.
 //! The idea is to provide all building blocks to composite the more complex types on top of the
 //! traits without caring about the representation being ALIGNED or UNALIGNED.
+//! The procedures are implemented once on top of [`unaligned::BitRead`] and
+//! [`unaligned::BitWrite`], which only pad to the octet boundaries of the ALIGNED variant through
+//! their `align` hooks.
 
 pub mod err;
 pub mod unaligned;
@@ -55,9 +58,36 @@ pub trait PackedRead {
     fn read_semi_constrained_whole_number(&mut self, lower_bound: i64) -> Result<i64, Error>;
 
     /// According to ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 3.7.27, a semi constrained whole
-    /// number is a whole number with a lower-bound constrained but no upper-bound constrained
+    /// number is a whole number with a lower-bound constrained but no upper-bound constrained.
+    /// As X.691 puts no bound on the length, values encoded in more than eight octets are read
+    /// if the excess octets only extend the sign, otherwise [`ErrorKind::ValueExceedsMaxInt`] is
+    /// returned.
     fn read_unconstrained_whole_number(&mut self) -> Result<i64, Error>;
 
+    /// Like [`PackedRead::read_non_negative_binary_integer`] but for values exceeding 64 bits
+    fn read_non_negative_binary_integer_u128(
+        &mut self,
+        lower_bound: Option<u128>,
+        upper_bound: Option<u128>,
+    ) -> Result<u128, Error>;
+
+    /// Like [`PackedRead::read_2s_compliment_binary_integer`] but for values exceeding 64 bits
+    fn read_2s_compliment_binary_integer_i128(&mut self, bit_len: u64) -> Result<i128, Error>;
+
+    /// Like [`PackedRead::read_constrained_whole_number`] but for bounds exceeding 64 bits
+    fn read_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+        upper_bound: i128,
+    ) -> Result<i128, Error>;
+
+    /// Like [`PackedRead::read_semi_constrained_whole_number`] but for values exceeding 64 bits
+    fn read_semi_constrained_whole_number_i128(&mut self, lower_bound: i128)
+        -> Result<i128, Error>;
+
+    /// Like [`PackedRead::read_unconstrained_whole_number`] but for values exceeding 64 bits
+    fn read_unconstrained_whole_number_i128(&mut self) -> Result<i128, Error>;
+
     fn read_bitstring(
         &mut self,
         lower_bound_size: Option<u64>,
@@ -72,6 +102,15 @@ pub trait PackedRead {
         extensible: bool,
     ) -> Result<Vec<u8>, Error>;
 
+    /// According to ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.4, reads `len` characters
+    /// of a known-multiplier character string, see
+    /// [`PackedWrite::write_known_multiplier_characters`]
+    fn read_known_multiplier_characters(
+        &mut self,
+        alphabet: &str,
+        len: u64,
+    ) -> Result<String, Error>;
+
     fn read_choice_index(&mut self, std_variants: u64, extensible: bool) -> Result<u64, Error>;
 
     fn read_enumeration_index(&mut self, std_variants: u64, extensible: bool)
@@ -137,6 +176,39 @@ pub trait PackedWrite {
     /// number is a whole number with a lower-bound constrained but no upper-bound constrained
     fn write_unconstrained_whole_number(&mut self, value: i64) -> Result<(), Error>;
 
+    /// Like [`PackedWrite::write_non_negative_binary_integer`] but for values exceeding 64 bits
+    fn write_non_negative_binary_integer_u128(
+        &mut self,
+        lower_bound: Option<u128>,
+        upper_bound: Option<u128>,
+        value: u128,
+    ) -> Result<(), Error>;
+
+    /// Like [`PackedWrite::write_2s_compliment_binary_integer`] but for values exceeding 64 bits
+    fn write_2s_compliment_binary_integer_i128(
+        &mut self,
+        bit_len: u64,
+        value: i128,
+    ) -> Result<(), Error>;
+
+    /// Like [`PackedWrite::write_constrained_whole_number`] but for bounds exceeding 64 bits
+    fn write_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+        upper_bound: i128,
+        value: i128,
+    ) -> Result<(), Error>;
+
+    /// Like [`PackedWrite::write_semi_constrained_whole_number`] but for values exceeding 64 bits
+    fn write_semi_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+        value: i128,
+    ) -> Result<(), Error>;
+
+    /// Like [`PackedWrite::write_unconstrained_whole_number`] but for values exceeding 64 bits
+    fn write_unconstrained_whole_number_i128(&mut self, value: i128) -> Result<(), Error>;
+
     fn write_bitstring(
         &mut self,
         lower_bound_size: Option<u64>,
@@ -155,6 +227,17 @@ pub trait PackedWrite {
         src: &[u8],
     ) -> Result<(), Error>;
 
+    /// According to ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.4, each character of a
+    /// known-multiplier character string is encoded with the minimal number of bits for the
+    /// (sorted, ASCII) permitted alphabet: as its value if the greatest value of the alphabet
+    /// fits into these bits, otherwise as its index within the alphabet. The length determinant
+    /// is not written.
+    fn write_known_multiplier_characters(
+        &mut self,
+        alphabet: &str,
+        value: &str,
+    ) -> Result<(), Error>;
+
     fn write_choice_index(
         &mut self,
         std_variants: u64,
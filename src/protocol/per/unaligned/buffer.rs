@@ -2,6 +2,10 @@ use super::*;
 use crate::protocol::per::Error;
 use crate::protocol::per::ErrorKind;
 
+/// A growing buffer of bits. Writes extend the content by the bytes they need, the allocation
+/// itself grows like a `Vec<u8>` - at least doubling its capacity - so that the number of
+/// reallocations is logarithmic in the encoded size. [`BitBuffer::reserve`] avoids them up front
+/// and [`BitBuffer::clear`] keeps the allocation for the next message.
 #[derive(Debug, Default)]
 pub struct BitBuffer {
     pub(crate) buffer: Vec<u8>,
@@ -45,6 +49,8 @@ impl BitBuffer {
         }
     }
 
+    /// Discards the content but keeps the allocated capacity, so that the buffer can be reused
+    /// for the next message without reallocating
     pub fn clear(&mut self) {
         self.buffer.clear();
         self.write_position = 0;
@@ -67,6 +73,24 @@ impl BitBuffer {
         self.buffer.len()
     }
 
+    /// The number of bytes that can be held without reallocating
+    pub fn capacity(&self) -> usize {
+        self.buffer.capacity()
+    }
+
+    /// Reserves the capacity for at least the given number of bits behind the write-position
+    pub fn reserve(&mut self, additional_bits: usize) {
+        let required_len = (self.write_position + additional_bits).div_ceil(BYTE_LEN);
+        self.buffer
+            .reserve(required_len.saturating_sub(self.buffer.len()));
+    }
+
+    /// The content for [`std::io::Write::write_vectored`], for example to send it behind a
+    /// separately encoded header without copying both into one buffer first
+    pub fn as_io_slices(&self) -> [std::io::IoSlice<'_>; 1] {
+        [std::io::IoSlice::new(&self.buffer)]
+    }
+
     /// Changes the write-position to the given position for the closure call.
     /// Restores the original write-position after the call.
     ///
@@ -230,6 +254,28 @@ impl BitWrite for BitBuffer {
     }
 }
 
+impl ScopedBitWrite for BitBuffer {
+    #[inline]
+    fn content(&self) -> &[u8] {
+        BitBuffer::content(self)
+    }
+
+    #[inline]
+    fn bit_len(&self) -> usize {
+        self.write_position
+    }
+
+    #[inline]
+    fn set_bit_len(&mut self, bit_len: usize) {
+        self.write_position = bit_len;
+    }
+
+    #[inline]
+    fn with_write_position_at<T, F: Fn(&mut Self) -> T>(&mut self, position: usize, f: F) -> T {
+        BitBuffer::with_write_position_at(self, position, f)
+    }
+}
+
 pub struct Bits<'a> {
     slice: &'a [u8],
     pos: usize,
@@ -348,6 +394,63 @@ pub mod tests {
     use crate::protocol::per::unaligned::PackedRead;
     use crate::protocol::per::unaligned::PackedWrite;
 
+    #[test]
+    pub fn bit_buffer_reserve_and_reuse() -> Result<(), Error> {
+        let mut buffer = BitBuffer::default();
+        buffer.write_bits_with_len(&[0xFF], 4)?;
+        buffer.reserve(8 * 1024);
+        let capacity = buffer.capacity();
+        assert!(capacity >= 1024 + 1);
+
+        buffer.write_bits(&[0xAB; 1024])?;
+        assert_eq!(4 + 8 * 1024, buffer.bit_len());
+        assert_eq!(capacity, buffer.capacity());
+
+        buffer.clear();
+        assert_eq!(0, buffer.bit_len());
+        assert_eq!(0, buffer.byte_len());
+        assert_eq!(capacity, buffer.capacity());
+
+        // previous content must not leak into the padding of the reused buffer
+        buffer.write_bit(true)?;
+        assert_eq!(&[0x80], buffer.content());
+        Ok(())
+    }
+
+    #[test]
+    pub fn bit_buffer_growth_is_amortized() -> Result<(), Error> {
+        let mut buffer = BitBuffer::default();
+        let mut reallocations = 0;
+        let mut capacity = buffer.capacity();
+        for _ in 0..64 * 1024 {
+            buffer.write_bits_with_len(&[0xFF], 3)?;
+            if buffer.capacity() != capacity {
+                capacity = buffer.capacity();
+                reallocations += 1;
+            }
+        }
+        assert_eq!(3 * 64 * 1024 / 8, buffer.byte_len());
+        assert!(reallocations <= 20, "{reallocations} reallocations");
+        Ok(())
+    }
+
+    #[test]
+    pub fn bit_buffer_as_io_slices() -> Result<(), Error> {
+        use std::io::Write;
+        let mut buffer = BitBuffer::default();
+        buffer.write_bits(&[0x01, 0x02])?;
+        buffer.write_bit(true)?;
+
+        let mut out = vec![0xAA];
+        let [content] = buffer.as_io_slices();
+        let written = out
+            .write_vectored(&[std::io::IoSlice::new(&[0xBB]), content])
+            .unwrap();
+        assert_eq!(4, written);
+        assert_eq!(vec![0xAA, 0xBB, 0x01, 0x02, 0x80], out);
+        Ok(())
+    }
+
     #[test]
     pub fn bit_buffer_write_bit_keeps_correct_order() -> Result<(), Error> {
         let mut buffer = BitBuffer::default();
@@ -783,6 +886,54 @@ pub mod tests {
         Ok(())
     }
 
+    #[test]
+    fn bit_buffer_unconstrained_whole_number_exceeding_8_octets() -> Result<(), Error> {
+        // redundant sign extension octets, as emitted by other encoders
+        let mut buffer = BitBuffer::from_bytes(vec![9, 0xFF, 0xFF, 0xFF, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(-(1 << 48), buffer.read_unconstrained_whole_number()?);
+        let mut buffer = BitBuffer::from_bytes(vec![
+            9, 0x00, 0x7F, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+        ]);
+        assert_eq!(i64::MAX, buffer.read_unconstrained_whole_number()?);
+        let mut buffer = BitBuffer::from_bytes(vec![
+            17, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF, 0xFF,
+            0xFF, 0xFF, 0xFF,
+        ]);
+        assert_eq!(-1, buffer.read_unconstrained_whole_number_i128()?);
+
+        // i64::MAX + 1
+        let mut buffer = BitBuffer::from_bytes(vec![9, 0x00, 0x80, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            Err(ErrorKind::ValueExceedsMaxInt.into()),
+            buffer.read_unconstrained_whole_number()
+        );
+        // 1 << 64
+        let mut buffer = BitBuffer::from_bytes(vec![9, 0x01, 0, 0, 0, 0, 0, 0, 0, 0]);
+        assert_eq!(
+            Err(ErrorKind::ValueExceedsMaxInt.into()),
+            buffer.read_unconstrained_whole_number()
+        );
+        Ok(())
+    }
+
+    #[test]
+    fn bit_buffer_semi_constrained_whole_number_exceeding_8_octets() -> Result<(), Error> {
+        let mut buffer = BitBuffer::from_bytes(vec![10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 42]);
+        assert_eq!(42 - 5, buffer.read_semi_constrained_whole_number(-5)?);
+        let mut buffer = BitBuffer::from_bytes(vec![10, 0, 0, 0, 0, 0, 0, 0, 0, 0, 42]);
+        assert_eq!(
+            42,
+            buffer.read_non_negative_binary_integer_u128(None, None)?
+        );
+        // 0xFF is not the extension of an unsigned number
+        let mut buffer = BitBuffer::from_bytes(vec![9, 0xFF, 0, 0, 0, 0, 0, 0, 0, 42]);
+        assert_eq!(
+            Err(ErrorKind::ValueExceedsMaxInt.into()),
+            buffer.read_non_negative_binary_integer(None, None)
+        );
+        Ok(())
+    }
+
     #[test]
     fn bit_buffer_write_constrained_whole_number_detects_not_in_range_positive_only() {
         let mut buffer = BitBuffer::default();
@@ -997,4 +1148,132 @@ pub mod tests {
         assert_eq!(3, read_once(&[0x81], 8, 2)?);
         Ok(())
     }
+
+    /// Pads to octet boundaries like the ALIGNED variant, which the procedures shared with the
+    /// UNALIGNED variant need to request at the right places
+    struct Aligning(BitBuffer);
+
+    impl BitRead for Aligning {
+        fn read_bit(&mut self) -> Result<bool, Error> {
+            self.0.read_bit()
+        }
+
+        fn read_bits(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+            self.0.read_bits(dst)
+        }
+
+        fn read_bits_with_offset(
+            &mut self,
+            dst: &mut [u8],
+            dst_bit_offset: usize,
+        ) -> Result<(), Error> {
+            self.0.read_bits_with_offset(dst, dst_bit_offset)
+        }
+
+        fn read_bits_with_len(&mut self, dst: &mut [u8], dst_bit_len: usize) -> Result<(), Error> {
+            self.0.read_bits_with_len(dst, dst_bit_len)
+        }
+
+        fn read_bits_with_offset_len(
+            &mut self,
+            dst: &mut [u8],
+            dst_bit_offset: usize,
+            dst_bit_len: usize,
+        ) -> Result<(), Error> {
+            self.0
+                .read_bits_with_offset_len(dst, dst_bit_offset, dst_bit_len)
+        }
+
+        fn align(&mut self) -> Result<(), Error> {
+            while !self.0.read_position.is_multiple_of(BYTE_LEN) {
+                self.0.read_bit()?;
+            }
+            Ok(())
+        }
+    }
+
+    impl BitWrite for Aligning {
+        fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+            self.0.write_bit(bit)
+        }
+
+        fn write_bits(&mut self, src: &[u8]) -> Result<(), Error> {
+            self.0.write_bits(src)
+        }
+
+        fn write_bits_with_offset(
+            &mut self,
+            src: &[u8],
+            src_bit_offset: usize,
+        ) -> Result<(), Error> {
+            self.0.write_bits_with_offset(src, src_bit_offset)
+        }
+
+        fn write_bits_with_len(&mut self, src: &[u8], bit_len: usize) -> Result<(), Error> {
+            self.0.write_bits_with_len(src, bit_len)
+        }
+
+        fn write_bits_with_offset_len(
+            &mut self,
+            src: &[u8],
+            src_bit_offset: usize,
+            src_bit_len: usize,
+        ) -> Result<(), Error> {
+            self.0
+                .write_bits_with_offset_len(src, src_bit_offset, src_bit_len)
+        }
+
+        fn align(&mut self) -> Result<(), Error> {
+            while !self.0.write_position.is_multiple_of(BYTE_LEN) {
+                self.0.write_bit(false)?;
+            }
+            Ok(())
+        }
+    }
+
+    #[test]
+    fn aligned_octet_string_and_length_determinant() -> Result<(), Error> {
+        let mut buffer = Aligning(BitBuffer::default());
+        buffer.write_bit(true)?;
+        // 17.6: up to two octets of fixed size are not aligned
+        buffer.write_octetstring(Some(1), Some(1), false, &[0xAB])?;
+        // 17.7: but more than two octets of fixed size are
+        buffer.write_octetstring(Some(3), Some(3), false, &[0x01, 0x02, 0x03])?;
+        buffer.write_bit(true)?;
+        // 17.8 -> 11.9.3.5: the length determinant is aligned
+        buffer.write_octetstring(None, None, false, &[0xCD])?;
+        assert_eq!(
+            &[0xD5, 0x80, 0x01, 0x02, 0x03, 0x80, 0x01, 0xCD],
+            buffer.0.content()
+        );
+
+        assert!(buffer.read_bit()?);
+        assert_eq!(
+            vec![0xAB],
+            buffer.read_octetstring(Some(1), Some(1), false)?
+        );
+        assert_eq!(
+            vec![0x01, 0x02, 0x03],
+            buffer.read_octetstring(Some(3), Some(3), false)?
+        );
+        assert!(buffer.read_bit()?);
+        assert_eq!(vec![0xCD], buffer.read_octetstring(None, None, false)?);
+        Ok(())
+    }
+
+    #[test]
+    fn aligned_bit_string() -> Result<(), Error> {
+        let mut buffer = Aligning(BitBuffer::default());
+        buffer.write_bit(true)?;
+        // 16.11: the content after a length determinant of constrained size is aligned
+        buffer.write_bitstring(Some(0), Some(7), false, &[0xF0], 0, 4)?;
+        assert_eq!(&[0xC0, 0xF0], buffer.0.content());
+
+        assert!(buffer.read_bit()?);
+        assert_eq!(
+            (vec![0xF0], 4),
+            buffer.read_bitstring(Some(0), Some(7), false)?
+        );
+        Ok(())
+    }
 }
@@ -0,0 +1,253 @@
+//! [`BitWrite`] into [`BytesMut`] and [`BitRead`] from [`Bytes`], so that the encoded content can
+//! be passed to network code - such as a `tokio` codec - without copying it out of a `Vec<u8>`.
+
+use super::*;
+use crate::protocol::per::Error;
+use crate::protocol::per::ErrorKind;
+use ::bytes::{Bytes, BytesMut};
+
+/// Writes bits into a [`BytesMut`], behind the content it already holds
+#[derive(Debug, Default)]
+pub struct BytesMutBits {
+    buffer: BytesMut,
+    write_position: usize,
+}
+
+impl BytesMutBits {
+    pub fn with_capacity(capacity: usize) -> Self {
+        Self::from(BytesMut::with_capacity(capacity))
+    }
+
+    pub fn content(&self) -> &[u8] {
+        &self.buffer
+    }
+
+    pub const fn bit_len(&self) -> usize {
+        self.write_position
+    }
+
+    pub fn byte_len(&self) -> usize {
+        self.buffer.len()
+    }
+
+    pub fn into_inner(self) -> BytesMut {
+        self.buffer
+    }
+
+    /// Converts the written content into an immutable and cheaply cloneable [`Bytes`]
+    pub fn freeze(self) -> Bytes {
+        self.buffer.freeze()
+    }
+
+    pub fn ensure_can_write_additional_bits(&mut self, bit_len: usize) {
+        let required_len = (self.write_position + bit_len).div_ceil(BYTE_LEN);
+        if required_len > self.buffer.len() {
+            self.buffer.resize(required_len, 0);
+        }
+    }
+}
+
+impl From<BytesMut> for BytesMutBits {
+    /// Existing content is kept, the bits are written starting at the next byte
+    fn from(buffer: BytesMut) -> Self {
+        Self {
+            write_position: buffer.len() * BYTE_LEN,
+            buffer,
+        }
+    }
+}
+
+impl From<BytesMutBits> for BytesMut {
+    fn from(bits: BytesMutBits) -> Self {
+        bits.buffer
+    }
+}
+
+impl BitWrite for BytesMutBits {
+    #[inline]
+    fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(1);
+        BitWrite::write_bit(&mut (&mut self.buffer[..], &mut self.write_position), bit)
+    }
+
+    #[inline]
+    fn write_bits(&mut self, src: &[u8]) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(src.len() * BYTE_LEN);
+        BitWrite::write_bits(&mut (&mut self.buffer[..], &mut self.write_position), src)
+    }
+
+    #[inline]
+    fn write_bits_with_offset(&mut self, src: &[u8], src_bit_offset: usize) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(src.len() * BYTE_LEN - src_bit_offset);
+        BitWrite::write_bits_with_offset(
+            &mut (&mut self.buffer[..], &mut self.write_position),
+            src,
+            src_bit_offset,
+        )
+    }
+
+    #[inline]
+    fn write_bits_with_len(&mut self, src: &[u8], bit_len: usize) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(bit_len);
+        BitWrite::write_bits_with_len(
+            &mut (&mut self.buffer[..], &mut self.write_position),
+            src,
+            bit_len,
+        )
+    }
+
+    #[inline]
+    fn write_bits_with_offset_len(
+        &mut self,
+        src: &[u8],
+        src_bit_offset: usize,
+        src_bit_len: usize,
+    ) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(src_bit_len);
+        BitWrite::write_bits_with_offset_len(
+            &mut (&mut self.buffer[..], &mut self.write_position),
+            src,
+            src_bit_offset,
+            src_bit_len,
+        )
+    }
+}
+
+/// Reads bits from a [`Bytes`], like [`Bits`](super::buffer::Bits) but without borrowing
+#[derive(Debug, Clone, Default)]
+pub struct BytesBits {
+    bytes: Bytes,
+    pos: usize,
+    len: usize,
+}
+
+impl BytesBits {
+    pub fn into_inner(self) -> Bytes {
+        self.bytes
+    }
+}
+
+impl From<Bytes> for BytesBits {
+    fn from(bytes: Bytes) -> Self {
+        Self {
+            len: bytes.len() * BYTE_LEN,
+            bytes,
+            pos: 0,
+        }
+    }
+}
+
+impl From<(Bytes, usize)> for BytesBits {
+    fn from((bytes, len): (Bytes, usize)) -> Self {
+        debug_assert!(len <= bytes.len() * BYTE_LEN);
+        Self { bytes, pos: 0, len }
+    }
+}
+
+impl From<BytesMutBits> for BytesBits {
+    fn from(bits: BytesMutBits) -> Self {
+        let len = bits.bit_len();
+        Self::from((bits.freeze(), len))
+    }
+}
+
+impl BitRead for BytesBits {
+    #[inline]
+    fn read_bit(&mut self) -> Result<bool, Error> {
+        if self.pos < self.len {
+            BitRead::read_bit(&mut (&self.bytes[..], &mut self.pos))
+        } else {
+            Err(ErrorKind::EndOfStream.into())
+        }
+    }
+
+    #[inline]
+    fn read_bits(&mut self, dst: &mut [u8]) -> Result<(), Error> {
+        BitRead::read_bits(&mut (&self.bytes[..], &mut self.pos), dst)
+    }
+
+    #[inline]
+    fn read_bits_with_offset(
+        &mut self,
+        dst: &mut [u8],
+        dst_bit_offset: usize,
+    ) -> Result<(), Error> {
+        BitRead::read_bits_with_offset(&mut (&self.bytes[..], &mut self.pos), dst, dst_bit_offset)
+    }
+
+    #[inline]
+    fn read_bits_with_len(&mut self, dst: &mut [u8], dst_bit_len: usize) -> Result<(), Error> {
+        BitRead::read_bits_with_len(&mut (&self.bytes[..], &mut self.pos), dst, dst_bit_len)
+    }
+
+    #[inline]
+    fn read_bits_with_offset_len(
+        &mut self,
+        dst: &mut [u8],
+        dst_bit_offset: usize,
+        dst_bit_len: usize,
+    ) -> Result<(), Error> {
+        BitRead::read_bits_with_offset_len(
+            &mut (&self.bytes[..], &mut self.pos),
+            dst,
+            dst_bit_offset,
+            dst_bit_len,
+        )
+    }
+}
+
+impl ScopedBitRead for BytesBits {
+    #[inline]
+    fn pos(&self) -> usize {
+        self.pos
+    }
+
+    #[inline]
+    fn set_pos(&mut self, position: usize) -> usize {
+        let pos = position.min(self.len);
+        self.pos = pos;
+        pos
+    }
+
+    #[inline]
+    fn len(&self) -> usize {
+        self.len
+    }
+
+    #[inline]
+    fn set_len(&mut self, len: usize) -> usize {
+        let len = len.min(self.bytes.len() * BYTE_LEN);
+        self.len = len;
+        len
+    }
+
+    #[inline]
+    fn remaining(&self) -> usize {
+        self.len - self.pos
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_write_behind_existing_content() {
+        let mut bits = BytesMutBits::from(BytesMut::from(&[0xAB][..]));
+        bits.write_bit(true).unwrap();
+        bits.write_bits_with_len(&[0xFF], 3).unwrap();
+        bits.write_constrained_whole_number(0, 255, 0x5A).unwrap();
+        assert_eq!(8 + 12, bits.bit_len());
+        assert_eq!(&[0xAB, 0xF5, 0xA0], bits.content());
+
+        let mut bits = BytesBits::from(bits);
+        assert_eq!(8 + 12, ScopedBitRead::len(&bits));
+        assert_eq!(0xAB, bits.read_constrained_whole_number(0, 255).unwrap());
+        assert!(bits.read_bit().unwrap());
+        let mut dst = [0u8];
+        bits.read_bits_with_len(&mut dst, 3).unwrap();
+        assert_eq!(0xE0, dst[0]);
+        assert_eq!(0x5A, bits.read_constrained_whole_number(0, 255).unwrap());
+        assert!(bits.read_bit().is_err());
+    }
+}
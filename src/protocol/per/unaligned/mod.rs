@@ -2,6 +2,8 @@ use crate::protocol::per::{Error, ErrorKind};
 use crate::protocol::per::{PackedRead, PackedWrite};
 
 pub mod buffer;
+#[cfg(feature = "bytes")]
+pub mod bytes;
 pub mod slice;
 
 pub const BYTE_LEN: usize = 8;
@@ -9,7 +11,6 @@ pub const BYTE_LEN: usize = 8;
 const FRAGMENT_SIZE: u64 = 16 * 1024;
 const MAX_FRAGMENTS: u8 = 4  /* 11.9.3.8, NOTE */ ;
 const MIN_FRAGMENT_SIZE: u64 = FRAGMENT_SIZE;
-const MAX_FRAGMENTS_SIZE: u64 = FRAGMENT_SIZE * MAX_FRAGMENTS as u64;
 
 const LENGTH_127: u64 = 127;
 const LENGTH_16K: u64 = 16 * 1024;
@@ -17,6 +18,18 @@ const LENGTH_64K: u64 = 64 * 1024;
 
 const SMALL_NON_NEGATIVE_NUMBER: u64 = 64;
 
+/// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.4: The number of bits per character of the
+/// given (sorted, ASCII) alphabet and whether characters are encoded as their index within the
+/// alphabet because the greatest character value does not fit into these bits
+#[inline]
+fn known_multiplier_bits(alphabet: &[u8]) -> (usize, bool) {
+    let bits = (usize::BITS - alphabet.len().saturating_sub(1).leading_zeros()) as usize;
+    let indexed = alphabet
+        .last()
+        .is_some_and(|greatest| usize::from(*greatest) >= 1 << bits);
+    (bits, indexed)
+}
+
 pub trait BitRead {
     fn read_bit(&mut self) -> Result<bool, Error>;
 
@@ -33,6 +46,14 @@ pub trait BitRead {
         dst_bit_offset: usize,
         dst_bit_len: usize,
     ) -> Result<(), Error>;
+
+    /// Skips the padding before an octet-aligned bit-field (X.691 3.7.18), which only the ALIGNED
+    /// variant has. The procedures of [`PackedRead`] call this wherever the ALIGNED variant
+    /// requires it, so that an ALIGNED reader does not need to duplicate them.
+    #[inline]
+    fn align(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
 }
 
 pub trait ScopedBitRead: BitRead {
@@ -68,6 +89,46 @@ pub trait ScopedBitRead: BitRead {
     }
 }
 
+/// X.691 puts no bound on the length of unconstrained and semi-constrained whole numbers (11.7,
+/// 11.8), so other encoders might emit more octets than the Rust integer has - for example a
+/// redundant leading zero octet. Reads the octets exceeding `max_octets`, which must all be the
+/// `0x00` or - if `signed` - the `0xFF` extension of the value that follows, and returns the
+/// length of that value together with the extension, if any.
+#[inline]
+fn read_excess_whole_number_octets<R: BitRead>(
+    reader: &mut R,
+    octet_len: u64,
+    max_octets: usize,
+    signed: bool,
+) -> Result<(u64, Option<u8>), Error> {
+    let excess = octet_len.saturating_sub(max_octets as u64);
+    let mut extension = None;
+    for _ in 0..excess {
+        let mut octet = [0u8; 1];
+        reader.read_bits(&mut octet)?;
+        let valid = match extension {
+            None => octet[0] == 0x00 || (signed && octet[0] == 0xFF),
+            Some(extension) => octet[0] == extension,
+        };
+        if !valid {
+            return Err(ErrorKind::ValueExceedsMaxInt.into());
+        }
+        extension = Some(octet[0]);
+    }
+    Ok((octet_len - excess, extension))
+}
+
+/// Whether the sign of the value matches the sign extension read by
+/// [`read_excess_whole_number_octets`]
+#[inline]
+fn ensure_sign_extension(negative: bool, extension: Option<u8>) -> Result<(), Error> {
+    match extension {
+        Some(0x00) if negative => Err(ErrorKind::ValueExceedsMaxInt.into()),
+        Some(0xFF) if !negative => Err(ErrorKind::ValueExceedsMaxInt.into()),
+        _ => Ok(()),
+    }
+}
+
 impl<T: BitRead> PackedRead for T {
     /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 12
     #[inline]
@@ -99,7 +160,9 @@ impl<T: BitRead> PackedRead for T {
             Ok(lower + u64::from_be_bytes(bytes))
         } else {
             let mut bytes = [0u8; std::mem::size_of::<u64>()];
-            let length = self.read_length_determinant(None, None)? as usize;
+            let length = self.read_length_determinant(None, None)?;
+            let (length, _) = read_excess_whole_number_octets(self, length, bytes.len(), false)?;
+            let length = length as usize;
 
             if let Some(offset) = bytes.len().checked_sub(length) {
                 self.read_bits(&mut bytes[offset..])?;
@@ -182,7 +245,118 @@ impl<T: BitRead> PackedRead for T {
     #[inline]
     fn read_unconstrained_whole_number(&mut self) -> Result<i64, Error> {
         let octet_len = self.read_length_determinant(None, None)?;
-        self.read_2s_compliment_binary_integer(octet_len * BYTE_LEN as u64)
+        let (octet_len, extension) =
+            read_excess_whole_number_octets(self, octet_len, std::mem::size_of::<i64>(), true)?;
+        let value = self.read_2s_compliment_binary_integer(octet_len * BYTE_LEN as u64)?;
+        ensure_sign_extension(value < 0, extension)?;
+        Ok(value)
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.3
+    #[inline]
+    #[allow(clippy::redundant_pattern_matching)] // allow for const_*!
+    fn read_non_negative_binary_integer_u128(
+        &mut self,
+        lower_bound: Option<u128>,
+        upper_bound: Option<u128>,
+    ) -> Result<u128, Error> {
+        let range = match (lower_bound, upper_bound) {
+            (None, None) => None,
+            (lb, ub) => Some((
+                const_unwrap_or!(lb, 0),
+                const_unwrap_or!(ub, i128::MAX as u128),
+            )),
+        };
+
+        if let Some((lower, upper)) = range {
+            let range = upper.saturating_sub(lower);
+            let offset_bits = range.leading_zeros() as usize;
+            let mut bytes = [0u8; std::mem::size_of::<u128>()];
+            self.read_bits_with_offset(&mut bytes, offset_bits)?;
+            Ok(lower.wrapping_add(u128::from_be_bytes(bytes)))
+        } else {
+            let mut bytes = [0u8; std::mem::size_of::<u128>()];
+            let length = self.read_length_determinant(None, None)?;
+            let (length, _) = read_excess_whole_number_octets(self, length, bytes.len(), false)?;
+            let length = length as usize;
+
+            if let Some(offset) = bytes.len().checked_sub(length) {
+                self.read_bits(&mut bytes[offset..])?;
+                Ok(u128::from_be_bytes(bytes))
+            } else {
+                Err(Error::length_determinant_exceeds_limit(length, bytes.len()))
+            }
+        }
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.4
+    #[inline]
+    fn read_2s_compliment_binary_integer_i128(&mut self, bit_len: u64) -> Result<i128, Error> {
+        let mut bytes = [0u8; std::mem::size_of::<i128>()];
+
+        if bit_len == 0 || bit_len as usize > bytes.len() * BYTE_LEN {
+            return Err(ErrorKind::BitLenNotInRange(
+                bit_len,
+                1_u64,
+                (bytes.len() * BYTE_LEN) as u64,
+            )
+            .into());
+        }
+
+        let bits_offset = (bytes.len() * BYTE_LEN) - bit_len as usize;
+        self.read_bits_with_offset(&mut bytes, bits_offset)?;
+        let byte_offset = bits_offset / BYTE_LEN;
+        let bit_offset = bits_offset % BYTE_LEN;
+        // check if the most significant bit is set (2er compliment -> negative number)
+        if bytes[byte_offset] & (0x80 >> bit_offset) != 0 {
+            // negative number, needs to be expanded before converting
+            for byte in bytes.iter_mut().take(byte_offset) {
+                *byte = 0xFF;
+            }
+            for i in 0..bit_offset {
+                bytes[byte_offset] |= 0x80 >> i;
+            }
+        }
+        Ok(i128::from_be_bytes(bytes))
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.5
+    #[inline]
+    fn read_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+        upper_bound: i128,
+    ) -> Result<i128, Error> {
+        if upper_bound > lower_bound {
+            // the range might only fit into an unsigned 128 bit integer
+            let range = upper_bound.wrapping_sub(lower_bound) as u128;
+            Ok(lower_bound.wrapping_add(
+                self.read_non_negative_binary_integer_u128(None, Some(range))? as i128,
+            ))
+        } else {
+            Ok(lower_bound)
+        }
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.7
+    #[inline]
+    fn read_semi_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+    ) -> Result<i128, Error> {
+        let n = self.read_non_negative_binary_integer_u128(None, None)?;
+        Ok(lower_bound.wrapping_add(n as i128))
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.8
+    #[inline]
+    fn read_unconstrained_whole_number_i128(&mut self) -> Result<i128, Error> {
+        let octet_len = self.read_length_determinant(None, None)?;
+        let (octet_len, extension) =
+            read_excess_whole_number_octets(self, octet_len, std::mem::size_of::<i128>(), true)?;
+        let value = self.read_2s_compliment_binary_integer_i128(octet_len * BYTE_LEN as u64)?;
+        ensure_sign_extension(value < 0, extension)?;
+        Ok(value)
     }
 
     /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.9.3
@@ -216,7 +390,8 @@ impl<T: BitRead> PackedRead for T {
             // 11.9.4.1 -> 11.9.3.4 -> 11.6.1
             self.read_non_negative_binary_integer(lower_bound, upper_bound)
         } else {
-            // 11.9.4.1 -> 11.9.3.5
+            // 11.9.4.1 -> 11.9.3.5, octet-aligned in the ALIGNED variant
+            self.align()?;
             if !self.read_bit()? {
                 // 11.9.3.6: less than or equal to 127
                 self.read_non_negative_binary_integer(None, Some(LENGTH_127))
@@ -245,43 +420,58 @@ impl<T: BitRead> PackedRead for T {
         // let lower_bound = const_unwrap_or!(lower_bound_size, 0);
         let upper_bound = const_unwrap_or!(upper_bound_size, i64::MAX as u64);
 
-        let (mut bit_len, fragmentation_possible) = if extensible && self.read_bit()? {
-            // 16.6
-            // self.read_semi_constrained_whole_number(0)
-            // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
-            (self.read_length_determinant(None, None)?, true)
+        let (mut bit_len, fragmentation_possible, octet_aligned) =
+            if extensible && self.read_bit()? {
+                // 16.6
+                // self.read_semi_constrained_whole_number(0)
+                // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
+                (self.read_length_determinant(None, None)?, true, true)
+            } else if upper_bound == 0 {
+                // 16.8
+                return Ok((Vec::default(), 0));
+            }
+            /*else if const_is_some!(lower_bound_size)
+                && lower_bound_size == upper_bound_size
+                && upper_bound <= 16
+            {
+                // 16.9
+                (upper_bound, false)
+            }*/
+            else if const_is_some!(lower_bound_size)
+                && lower_bound_size == upper_bound_size
+                && upper_bound < LENGTH_64K
+            {
+                // 16.10, octet-aligned in the ALIGNED variant if longer than 16 bits
+                (upper_bound, false, upper_bound > 16)
+            } else {
+                // 16.11, only the length determinant without bounds is fragmented
+                (
+                    self.read_length_determinant(lower_bound_size, upper_bound_size)?,
+                    const_is_none!(lower_bound_size) && const_is_none!(upper_bound_size),
+                    true,
+                )
+            };
+
+        if bit_len == 0 {
+            // neither content nor fragments follow an empty BIT STRING
+            return Ok((Vec::default(), 0));
         }
-        /*else if const_is_some!(lower_bound_size)
-            && lower_bound_size == upper_bound_size
-            && upper_bound <= 16
-        {
-            // 16.9
-            (upper_bound, false)
-        }*/
-        else if const_is_some!(lower_bound_size)
-            && lower_bound_size == upper_bound_size
-            && upper_bound < LENGTH_64K
-        {
-            // 16.10
-            (upper_bound, false)
-        } else {
-            // 16.11
-            (
-                self.read_length_determinant(lower_bound_size, upper_bound_size)?,
-                true,
-            )
-        };
 
-        let mut byte_len = (bit_len + 7) / 8;
-        let mut buffer = vec![0u8; byte_len as usize];
+        if octet_aligned {
+            self.align()?;
+        }
+
+        let mut buffer = vec![0u8; bit_len.div_ceil(BYTE_LEN as u64) as usize];
         self.read_bits_with_len(&mut buffer[..], bit_len as usize)?;
 
-        // fragmentation?
+        // fragmentation? 11.9.3.8: fragments of 16K multiples are followed by another length
         if fragmentation_possible && bit_len >= LENGTH_16K {
             loop {
                 let ext_bit_len = self.read_length_determinant(None, None)?;
-                let ext_byte_len = byte_len - ((bit_len + ext_bit_len) + 7) / 8;
-                buffer.extend(core::iter::repeat(0x00).take(ext_byte_len as usize));
+                buffer.resize(
+                    (bit_len + ext_bit_len).div_ceil(BYTE_LEN as u64) as usize,
+                    0x00,
+                );
                 self.read_bits_with_offset_len(
                     &mut buffer[..],
                     bit_len as usize,
@@ -289,7 +479,6 @@ impl<T: BitRead> PackedRead for T {
                 )?;
 
                 bit_len += ext_bit_len;
-                byte_len += ext_bit_len;
 
                 if ext_bit_len < LENGTH_16K {
                     break;
@@ -313,35 +502,46 @@ impl<T: BitRead> PackedRead for T {
         // let lower_bound = const_unwrap_or!(lower_bound_size, 0);
         let upper_bound = const_unwrap_or!(upper_bound_size, i64::MAX as u64);
 
-        let (mut byte_len, fragmentation_possible) = if extensible && self.read_bit()? {
-            // 17.3
-            // self.read_semi_constrained_whole_number(0)
-            // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
-            (self.read_length_determinant(None, None)?, true)
-        } else if upper_bound == 0 {
-            // 17.5
+        let (mut byte_len, fragmentation_possible, octet_aligned) =
+            if extensible && self.read_bit()? {
+                // 17.3
+                // self.read_semi_constrained_whole_number(0)
+                // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
+                (self.read_length_determinant(None, None)?, true, true)
+            } else if upper_bound == 0 {
+                // 17.5
+                return Ok(Vec::default());
+            }
+            /* else if const_is_some!(lower_bound_size)
+                && lower_bound_size == upper_bound_size
+                && upper_bound <= 2
+            {
+                // 17.6
+                (upper_bound, false)
+            }*/
+            else if const_is_some!(lower_bound_size)
+                && lower_bound_size == upper_bound_size
+                && upper_bound < LENGTH_64K
+            {
+                // 17.7, octet-aligned in the ALIGNED variant if longer than two octets
+                (upper_bound, false, upper_bound > 2)
+            } else {
+                // 17.8, only the length determinant without bounds is fragmented
+                (
+                    self.read_length_determinant(lower_bound_size, upper_bound_size)?,
+                    const_is_none!(lower_bound_size) && const_is_none!(upper_bound_size),
+                    true,
+                )
+            };
+
+        if byte_len == 0 {
+            // neither content nor fragments follow an empty OCTET STRING
             return Ok(Vec::default());
         }
-        /* else if const_is_some!(lower_bound_size)
-            && lower_bound_size == upper_bound_size
-            && upper_bound <= 2
-        {
-            // 17.6
-            (upper_bound, false)
-        }*/
-        else if const_is_some!(lower_bound_size)
-            && lower_bound_size == upper_bound_size
-            && upper_bound < LENGTH_64K
-        {
-            // 17.7
-            (upper_bound, false)
-        } else {
-            // 17.8
-            (
-                self.read_length_determinant(lower_bound_size, upper_bound_size)?,
-                true,
-            )
-        };
+
+        if octet_aligned {
+            self.align()?;
+        }
 
         let mut buffer = vec![0u8; byte_len as usize];
         self.read_bits(&mut buffer[..])?;
@@ -363,6 +563,36 @@ impl<T: BitRead> PackedRead for T {
         Ok(buffer)
     }
 
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.4
+    #[inline]
+    fn read_known_multiplier_characters(
+        &mut self,
+        alphabet: &str,
+        len: u64,
+    ) -> Result<String, Error> {
+        let alphabet = alphabet.as_bytes();
+        let (bits, indexed) = known_multiplier_bits(alphabet);
+        (0..len as usize)
+            .map(|index| {
+                let mut value = 0_u8;
+                if bits > 0 {
+                    self.read_bits_with_offset(core::slice::from_mut(&mut value), BYTE_LEN - bits)?;
+                }
+                if indexed {
+                    alphabet.get(usize::from(value)).copied().ok_or_else(|| {
+                        ErrorKind::ValueNotInRange(i128::from(value), 0, alphabet.len() as i128 - 1)
+                            .into()
+                    })
+                } else if alphabet.binary_search(&value).is_ok() {
+                    Ok(value)
+                } else {
+                    Err(ErrorKind::CharacterNotPermitted(char::from(value), index).into())
+                }
+                .map(char::from)
+            })
+            .collect()
+    }
+
     #[inline]
     fn read_choice_index(&mut self, std_variants: u64, extensible: bool) -> Result<u64, Error> {
         self.read_enumeration_index(std_variants, extensible)
@@ -397,6 +627,35 @@ pub trait BitWrite {
         src_bit_offset: usize,
         src_bit_len: usize,
     ) -> Result<(), Error>;
+
+    /// Writes the padding before an octet-aligned bit-field (X.691 3.7.18), which only the
+    /// ALIGNED variant has, see [`BitRead::align`]
+    #[inline]
+    fn align(&mut self) -> Result<(), Error> {
+        Ok(())
+    }
+}
+
+pub trait ScopedBitWrite: BitWrite {
+    /// The written bytes, the bits of the last byte behind [`ScopedBitWrite::bit_len()`] are zero
+    fn content(&self) -> &[u8];
+
+    /// The number of written bits, which is also the write-position
+    fn bit_len(&self) -> usize;
+
+    /// Moves the write-position, for example to undo previous writes
+    fn set_bit_len(&mut self, bit_len: usize);
+
+    /// Changes the write-position to the given position for the closure call.
+    /// Restores the original write-position after the call.
+    #[inline]
+    fn with_write_position_at<T, F: Fn(&mut Self) -> T>(&mut self, position: usize, f: F) -> T {
+        let before = self.bit_len();
+        self.set_bit_len(position);
+        let result = f(self);
+        self.set_bit_len(before);
+        result
+    }
 }
 
 impl<T: BitWrite> PackedWrite for T {
@@ -460,7 +719,12 @@ impl<T: BitWrite> PackedWrite for T {
         let range = upper_bound - lower_bound;
         if range > 0 {
             if value < lower_bound || value > upper_bound {
-                Err(ErrorKind::ValueNotInRange(value, lower_bound, upper_bound).into())
+                Err(ErrorKind::ValueNotInRange(
+                    i128::from(value),
+                    i128::from(lower_bound),
+                    i128::from(upper_bound),
+                )
+                .into())
             } else {
                 self.write_non_negative_binary_integer(
                     None,
@@ -496,7 +760,12 @@ impl<T: BitWrite> PackedWrite for T {
         value: i64,
     ) -> Result<(), Error> {
         if value < lower_bound {
-            Err(ErrorKind::ValueNotInRange(value, lower_bound, i64::MAX).into())
+            Err(ErrorKind::ValueNotInRange(
+                i128::from(value),
+                i128::from(lower_bound),
+                i128::from(i64::MAX),
+            )
+            .into())
         } else {
             self.write_non_negative_binary_integer(None, None, (value - lower_bound) as u64)
         }
@@ -516,6 +785,107 @@ impl<T: BitWrite> PackedWrite for T {
         self.write_2s_compliment_binary_integer(octet_len * BYTE_LEN as u64, value)
     }
 
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.3
+    #[inline]
+    #[allow(clippy::redundant_pattern_matching)] // allow for const_*!
+    fn write_non_negative_binary_integer_u128(
+        &mut self,
+        lower_bound: Option<u128>,
+        upper_bound: Option<u128>,
+        value: u128,
+    ) -> Result<(), Error> {
+        let range = match (lower_bound, upper_bound) {
+            (None, None) => None,
+            (lb, ub) => Some((
+                const_unwrap_or!(lb, 0),
+                const_unwrap_or!(ub, i128::MAX as u128),
+            )),
+        };
+
+        if let Some((lower, upper)) = range {
+            let range = upper - lower;
+            let offset_bits = range.leading_zeros() as usize;
+            let bytes = (value - lower).to_be_bytes();
+            self.write_bits_with_offset(&bytes[..], offset_bits)?;
+            Ok(())
+        } else {
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.3.6, zero is encoded in one octet
+            let offset = (value.leading_zeros() as u64 / 8).min(15);
+            let len = std::mem::size_of::<u128>() as u64 - offset;
+            let bytes = value.to_be_bytes();
+            self.write_length_determinant(None, None, len)?;
+            self.write_bits(&bytes[offset as usize..])
+        }
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.4
+    #[inline]
+    fn write_2s_compliment_binary_integer_i128(
+        &mut self,
+        bit_len: u64,
+        value: i128,
+    ) -> Result<(), Error> {
+        let bytes = value.to_be_bytes();
+        let bits_offset = (bytes.len() * BYTE_LEN) - bit_len as usize;
+        self.write_bits_with_offset(&bytes[..], bits_offset)
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.5
+    #[inline]
+    fn write_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+        upper_bound: i128,
+        value: i128,
+    ) -> Result<(), Error> {
+        if upper_bound > lower_bound {
+            if value < lower_bound || value > upper_bound {
+                Err(ErrorKind::ValueNotInRange(value, lower_bound, upper_bound).into())
+            } else {
+                // the range might only fit into an unsigned 128 bit integer
+                self.write_non_negative_binary_integer_u128(
+                    None,
+                    Some(upper_bound.wrapping_sub(lower_bound) as u128),
+                    value.wrapping_sub(lower_bound) as u128,
+                )
+            }
+        } else {
+            Ok(())
+        }
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.7
+    #[inline]
+    fn write_semi_constrained_whole_number_i128(
+        &mut self,
+        lower_bound: i128,
+        value: i128,
+    ) -> Result<(), Error> {
+        if value < lower_bound {
+            Err(ErrorKind::ValueNotInRange(value, lower_bound, i128::MAX).into())
+        } else {
+            self.write_non_negative_binary_integer_u128(
+                None,
+                None,
+                value.wrapping_sub(lower_bound) as u128,
+            )
+        }
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.8
+    #[inline]
+    fn write_unconstrained_whole_number_i128(&mut self, value: i128) -> Result<(), Error> {
+        let prefix_len = if value.is_negative() {
+            value.leading_ones().saturating_sub(1)
+        } else {
+            value.leading_zeros().saturating_sub(1)
+        } as u64
+            / 8;
+        let octet_len = core::mem::size_of::<i128>() as u64 - prefix_len;
+        self.write_length_determinant(None, None, octet_len)?;
+        self.write_2s_compliment_binary_integer_i128(octet_len * BYTE_LEN as u64, value)
+    }
+
     /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.9.3
     #[inline]
     fn write_normally_small_length(&mut self, value: u64) -> Result<(), Error> {
@@ -542,9 +912,9 @@ impl<T: BitWrite> PackedWrite for T {
                 Ok(None)
             } else if value < lower_bound_unwrapped {
                 Err(ErrorKind::ValueNotInRange(
-                    value as i64,
-                    lower_bound_unwrapped as i64,
-                    upper_bound_unwrapped as i64,
+                    i128::from(value),
+                    i128::from(lower_bound_unwrapped),
+                    i128::from(upper_bound_unwrapped),
                 )
                 .into())
             } else {
@@ -560,7 +930,8 @@ impl<T: BitWrite> PackedWrite for T {
             self.write_non_negative_binary_integer(lower_bound, upper_bound, value)?;
             Ok(None)
         } else {
-            // 11.9.4.1 -> 11.9.3.5
+            // 11.9.4.1 -> 11.9.3.5, octet-aligned in the ALIGNED variant
+            self.align()?;
             if value <= LENGTH_127 {
                 // 11.9.3.6: less than or equal to 127
                 self.write_bit(false)?;
@@ -599,22 +970,24 @@ impl<T: BitWrite> PackedWrite for T {
         let lower_bound = const_unwrap_or!(lower_bound_size, 0);
         let upper_bound = const_unwrap_or!(upper_bound_size, i64::MAX as u64);
         let length = len;
-        let fragmented = length > MAX_FRAGMENTS_SIZE;
         let out_of_range = length < lower_bound || length > upper_bound;
 
         if extensible {
             self.write_bit(out_of_range)?;
         }
 
-        if out_of_range {
+        let (fragment_size, octet_aligned) = if out_of_range {
             if extensible {
                 // 16.6
                 // self.read_semi_constrained_whole_number(0)
                 // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
-                self.write_length_determinant(None, None, length)?;
+                (self.write_length_determinant(None, None, length)?, true)
             } else {
                 return Err(ErrorKind::SizeNotInRange(length, lower_bound, upper_bound).into());
             }
+        } else if upper_bound == 0 {
+            // 16.8
+            return Ok(());
         }
         /*else if const_is_some!(lower_bound_size)
             && lower_bound_size == upper_bound_size
@@ -626,34 +999,48 @@ impl<T: BitWrite> PackedWrite for T {
             && lower_bound_size == upper_bound_size
             && upper_bound < LENGTH_64K
         {
-            // 16.10
+            // 16.10, octet-aligned in the ALIGNED variant if longer than 16 bits
+            (None, upper_bound > 16)
         } else {
             // 16.11
-            self.write_length_determinant(lower_bound_size, upper_bound_size, length)?;
+            (
+                self.write_length_determinant(lower_bound_size, upper_bound_size, length)?,
+                true,
+            )
+        };
+
+        if length == 0 {
+            return Ok(());
+        }
+
+        if octet_aligned {
+            self.align()?;
         }
 
         self.write_bits_with_offset_len(
             src,
             offset as usize,
-            MAX_FRAGMENTS_SIZE.min(length) as usize,
+            fragment_size.unwrap_or(length) as usize,
         )?;
 
-        if fragmented {
-            let mut written_bits = MAX_FRAGMENTS_SIZE;
+        if let Some(mut written_bits) = fragment_size {
             loop {
-                let fragment_size = (length - written_bits).min(MAX_FRAGMENTS_SIZE);
-                let fragment_size = fragment_size - (fragment_size % MIN_FRAGMENT_SIZE);
-                self.write_length_determinant(None, None, fragment_size)?;
+                let remaining = length - written_bits;
+                let fragment_size = self
+                    .write_length_determinant(None, None, remaining)?
+                    .unwrap_or(remaining);
+
                 self.write_bits_with_offset_len(
                     src,
                     (offset + written_bits) as usize,
                     fragment_size as usize,
                 )?;
-                written_bits += fragment_size;
 
                 if fragment_size < MIN_FRAGMENT_SIZE {
                     break;
                 }
+
+                written_bits += fragment_size;
             }
         }
 
@@ -680,12 +1067,12 @@ impl<T: BitWrite> PackedWrite for T {
             self.write_bit(out_of_range)?;
         }
 
-        let fragment_size = if out_of_range {
+        let (fragment_size, octet_aligned) = if out_of_range {
             if extensible {
                 // 17.3
                 // self.read_semi_constrained_whole_number(0)
                 // self.read_non_negative_binary_integer(0, MAX) + lb  | lb=0=>MIN for unsigned
-                self.write_length_determinant(None, None, length)?
+                (self.write_length_determinant(None, None, length)?, true)
             } else {
                 return Err(ErrorKind::SizeNotInRange(length, lower_bound, upper_bound).into());
             }
@@ -703,13 +1090,24 @@ impl<T: BitWrite> PackedWrite for T {
             && lower_bound_size == upper_bound_size
             && upper_bound < LENGTH_64K
         {
-            // 17.7
-            None
+            // 17.7, octet-aligned in the ALIGNED variant if longer than two octets
+            (None, upper_bound > 2)
         } else {
             // 17.8
-            self.write_length_determinant(lower_bound_size, upper_bound_size, length)?
+            (
+                self.write_length_determinant(lower_bound_size, upper_bound_size, length)?,
+                true,
+            )
         };
 
+        if length == 0 {
+            return Ok(());
+        }
+
+        if octet_aligned {
+            self.align()?;
+        }
+
         self.write_bits(&src[..fragment_size.unwrap_or(length) as usize])?;
 
         if let Some(mut written_bytes) = fragment_size {
@@ -734,6 +1132,32 @@ impl<T: BitWrite> PackedWrite for T {
         Ok(())
     }
 
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.4
+    #[inline]
+    fn write_known_multiplier_characters(
+        &mut self,
+        alphabet: &str,
+        value: &str,
+    ) -> Result<(), Error> {
+        let alphabet = alphabet.as_bytes();
+        let (bits, indexed) = known_multiplier_bits(alphabet);
+        for (index, char) in value.chars().enumerate() {
+            let position = u8::try_from(char)
+                .ok()
+                .and_then(|char| alphabet.binary_search(&char).ok())
+                .ok_or(ErrorKind::CharacterNotPermitted(char, index))?;
+            if bits > 0 {
+                let value = if indexed {
+                    position as u8
+                } else {
+                    alphabet[position]
+                };
+                self.write_bits_with_offset(&[value], BYTE_LEN - bits)?;
+            }
+        }
+        Ok(())
+    }
+
     #[inline]
     fn write_choice_index(
         &mut self,
@@ -1,6 +1,9 @@
 use super::BitRead;
+use super::MIN_FRAGMENT_SIZE;
 use crate::protocol::per::unaligned::BitWrite;
+use crate::protocol::per::unaligned::ScopedBitWrite;
 use crate::protocol::per::unaligned::BYTE_LEN;
+use crate::protocol::per::PackedWrite;
 use crate::protocol::per::{Error, ErrorKind};
 
 impl BitRead for (&[u8], &mut usize) {
@@ -89,6 +92,194 @@ impl<'a> BitWrite for (&'a mut [u8], &mut usize) {
     }
 }
 
+/// Writes bits into a slice of fixed size, that the caller provides and that is never
+/// reallocated. Writes beyond the end of the slice fail with [`ErrorKind::BufferTooSmall`].
+#[derive(Debug)]
+pub struct SliceBits<'a> {
+    buffer: &'a mut [u8],
+    write_position: usize,
+}
+
+impl<'a> SliceBits<'a> {
+    pub fn content(&self) -> &[u8] {
+        &self.buffer[..self.write_position.div_ceil(BYTE_LEN)]
+    }
+
+    pub const fn bit_len(&self) -> usize {
+        self.write_position
+    }
+
+    /// Zeroes the written bytes, so that the slice can be reused for the next message
+    pub fn clear(&mut self) {
+        self.buffer[..self.write_position.div_ceil(BYTE_LEN)].fill(0);
+        self.write_position = 0;
+    }
+
+    #[inline]
+    fn ensure_can_write_additional_bits(&self, bit_len: usize) -> Result<(), Error> {
+        let required = self.write_position + bit_len;
+        let capacity = self.buffer.len() * BYTE_LEN;
+        if required > capacity {
+            Err(ErrorKind::BufferTooSmall(required, capacity).into())
+        } else {
+            Ok(())
+        }
+    }
+
+    /// Hands the slice over to a scratch writer, which writes behind the content of this writer -
+    /// starting at the next byte - for example to encode an open type before its length is known
+    pub(crate) fn split_off_scratch(&mut self) -> Self {
+        Self {
+            write_position: self.write_position.div_ceil(BYTE_LEN) * BYTE_LEN,
+            buffer: core::mem::take(&mut self.buffer),
+        }
+    }
+
+    /// Takes the slice back from the scratch writer and zeroes what it has written
+    pub(crate) fn discard_scratch(&mut self, scratch: Self) {
+        let start = self.write_position.div_ceil(BYTE_LEN);
+        let end = scratch.write_position.div_ceil(BYTE_LEN).max(start);
+        self.buffer = scratch.buffer;
+        self.buffer[start..end].fill(0);
+    }
+
+    /// Takes the slice back from the scratch writer and writes what it has written as unconstrained
+    /// octet string (ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 17.8) at the write-position
+    pub(crate) fn write_scratch_as_octetstring(&mut self, scratch: Self) -> Result<(), Error> {
+        let start = self.write_position.div_ceil(BYTE_LEN);
+        let octets = scratch.write_position.div_ceil(BYTE_LEN).max(start) - start;
+        self.buffer = scratch.buffer;
+
+        // The octets are moved to the end of the slice, so that the length determinants written in
+        // front of them cannot overwrite octets that are not yet copied - unless the slice is too
+        // small for the octet string anyway
+        let tail = self.buffer.len() - octets;
+        self.buffer.copy_within(start..start + octets, tail);
+
+        let position = self.write_position;
+        let result = self.write_octetstring_from(tail, octets);
+        let end = if result.is_ok() {
+            self.write_position
+        } else {
+            self.write_position = position;
+            position
+        };
+
+        // restore the zeroes behind the content
+        if end % BYTE_LEN != 0 {
+            self.buffer[end / BYTE_LEN] &= !(0xFF >> (end % BYTE_LEN));
+        }
+        let zero_from = if result.is_ok() {
+            end.div_ceil(BYTE_LEN).max(tail)
+        } else {
+            end.div_ceil(BYTE_LEN)
+        };
+        self.buffer[zero_from..].fill(0);
+        result
+    }
+
+    fn write_octetstring_from(&mut self, offset: usize, octets: usize) -> Result<(), Error> {
+        let mut chunk = [0_u8; 64];
+        let mut written = 0;
+        loop {
+            let remaining = octets - written;
+            let fragment_size = self
+                .write_length_determinant(None, None, remaining as u64)?
+                .map_or(remaining, |size| size as usize);
+
+            for index in (written..written + fragment_size).step_by(chunk.len()) {
+                let len = chunk.len().min(written + fragment_size - index);
+                chunk[..len].copy_from_slice(&self.buffer[offset + index..][..len]);
+                self.write_bits(&chunk[..len])?;
+            }
+
+            if (fragment_size as u64) < MIN_FRAGMENT_SIZE {
+                return Ok(());
+            }
+
+            written += fragment_size;
+        }
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for SliceBits<'a> {
+    /// The slice is zeroed, so that the padding bits of the last byte are zero
+    fn from(buffer: &'a mut [u8]) -> Self {
+        buffer.fill(0);
+        Self {
+            buffer,
+            write_position: 0,
+        }
+    }
+}
+
+impl BitWrite for SliceBits<'_> {
+    #[inline]
+    fn write_bit(&mut self, bit: bool) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(1)?;
+        BitWrite::write_bit(&mut (&mut self.buffer[..], &mut self.write_position), bit)
+    }
+
+    #[inline]
+    fn write_bits(&mut self, src: &[u8]) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(src.len() * BYTE_LEN)?;
+        BitWrite::write_bits(&mut (&mut self.buffer[..], &mut self.write_position), src)
+    }
+
+    #[inline]
+    fn write_bits_with_offset(&mut self, src: &[u8], src_bit_offset: usize) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(src.len() * BYTE_LEN - src_bit_offset)?;
+        BitWrite::write_bits_with_offset(
+            &mut (&mut self.buffer[..], &mut self.write_position),
+            src,
+            src_bit_offset,
+        )
+    }
+
+    #[inline]
+    fn write_bits_with_len(&mut self, src: &[u8], bit_len: usize) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(bit_len)?;
+        BitWrite::write_bits_with_len(
+            &mut (&mut self.buffer[..], &mut self.write_position),
+            src,
+            bit_len,
+        )
+    }
+
+    #[inline]
+    fn write_bits_with_offset_len(
+        &mut self,
+        src: &[u8],
+        src_bit_offset: usize,
+        src_bit_len: usize,
+    ) -> Result<(), Error> {
+        self.ensure_can_write_additional_bits(src_bit_len)?;
+        BitWrite::write_bits_with_offset_len(
+            &mut (&mut self.buffer[..], &mut self.write_position),
+            src,
+            src_bit_offset,
+            src_bit_len,
+        )
+    }
+}
+
+impl ScopedBitWrite for SliceBits<'_> {
+    #[inline]
+    fn content(&self) -> &[u8] {
+        SliceBits::content(self)
+    }
+
+    #[inline]
+    fn bit_len(&self) -> usize {
+        self.write_position
+    }
+
+    #[inline]
+    fn set_bit_len(&mut self, bit_len: usize) {
+        self.write_position = bit_len;
+    }
+}
+
 #[inline]
 fn bit_string_copy(
     src: &[u8],
@@ -1,4 +1,5 @@
 use crate::descriptor::bitstring::BitVec;
+use crate::descriptor::numbers::{Constraint, Number};
 use backtrace::Backtrace;
 use byteorder::LittleEndian as E;
 use byteorder::ReadBytesExt;
@@ -23,6 +24,7 @@ pub enum Error {
     InvalidVariant(Backtrace, u64),
     UnexpectedFormat(Backtrace, Format),
     UnexpectedTag(Backtrace, (u32, Format)),
+    InvalidLength(Backtrace, u64),
 }
 
 impl Error {
@@ -50,6 +52,11 @@ impl Error {
     pub fn unexpected_tag(tag: (u32, Format)) -> Self {
         Error::UnexpectedTag(Backtrace::new(), tag)
     }
+
+    #[allow(unused)]
+    pub fn invalid_length(length: u64) -> Self {
+        Error::InvalidLength(Backtrace::new(), length)
+    }
 }
 
 impl std::fmt::Display for Error {
@@ -69,13 +76,14 @@ impl std::fmt::Display for Error {
             Error::UnexpectedTag(b, (tag, format)) => {
                 write!(f, "Tag({}/{:?}) is unexpected\n{:?}", tag, format, b)
             }
+            Error::InvalidLength(b, length) => write!(f, "Length({}) is invalid\n{:?}", length, b),
         }
     }
 }
 
 impl std::error::Error for Error {}
 
-#[derive(Debug, PartialOrd, PartialEq, Eq, Clone, Copy)]
+#[derive(Debug, PartialOrd, Ord, PartialEq, Eq, Hash, Clone, Copy)]
 #[repr(u32)]
 pub enum Format {
     #[allow(unused)]
@@ -84,6 +92,9 @@ pub enum Format {
     Fixed64 = 1,
     #[allow(unused)]
     LengthDelimited = 2,
+    /// Deprecated, the fields of a group follow until the matching [`Format::EndGroup`]
+    StartGroup = 3,
+    EndGroup = 4,
     #[allow(unused)]
     Fixed32 = 5,
 }
@@ -95,12 +106,128 @@ impl Format {
             0 => Ok(Format::VarInt),
             1 => Ok(Format::Fixed64),
             2 => Ok(Format::LengthDelimited),
+            3 => Ok(Format::StartGroup),
+            4 => Ok(Format::EndGroup),
             5 => Ok(Format::Fixed32),
             f => Err(Error::InvalidFormat(Backtrace::new(), f)),
         }
     }
 }
 
+/// A field of a protobuf message, that is unknown to the definition it was read for
+#[derive(Debug, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnknownField {
+    pub number: u32,
+    pub format: Format,
+    /// The encoded value without its tag: the varint or fixed size bytes, the content of a
+    /// length delimited field without its length or the fields of a group without the end tag
+    pub value: Vec<u8>,
+}
+
+/// The unknown fields of a protobuf message in the order they were read. They are written
+/// after the known fields, so that a message of a newer revision of the definition survives
+/// being decoded, modified and encoded again.
+#[derive(Debug, Default, Clone, PartialEq, Eq, Hash, PartialOrd, Ord)]
+pub struct UnknownFields(Vec<UnknownField>);
+
+impl UnknownFields {
+    pub fn push(&mut self, field: UnknownField) {
+        self.0.push(field);
+    }
+
+    pub fn iter(&self) -> impl Iterator<Item = &UnknownField> {
+        self.0.iter()
+    }
+
+    pub fn len(&self) -> usize {
+        self.0.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.0.is_empty()
+    }
+
+    pub fn clear(&mut self) {
+        self.0.clear();
+    }
+}
+
+impl From<Vec<UnknownField>> for UnknownFields {
+    fn from(fields: Vec<UnknownField>) -> Self {
+        Self(fields)
+    }
+}
+
+/// The protobuf scalar type an `INTEGER` is encoded as. Ranges that include negative values are
+/// zigzag encoded (`sint32`/`sint64`), because a negative `int64` always takes ten bytes.
+#[derive(Debug, PartialEq, Eq, Clone, Copy)]
+pub enum IntegerType {
+    UInt32,
+    UInt64,
+    SInt32,
+    SInt64,
+    Fixed32,
+    Fixed64,
+    SFixed32,
+    SFixed64,
+}
+
+impl IntegerType {
+    /// Without a lower bound, the range includes negative values if the rust type does. The
+    /// values of an extensible range are not limited by the bounds and therefore always need
+    /// 64 bits, which is compatible on the wire with the 32-bit variant.
+    pub fn of<T: Number, C: Constraint<T>>() -> Self {
+        let min = const_unwrap_or!(C::MIN, if T::SIGNED { i64::MIN } else { 0 });
+        let max = const_unwrap_or!(C::MAX, i64::MAX);
+        let varint = match (min >= 0, C::EXTENSIBLE) {
+            (true, false) if max <= i64::from(u32::MAX) => IntegerType::UInt32,
+            (true, _) => IntegerType::UInt64,
+            (false, false) if min >= i64::from(i32::MIN) && max <= i64::from(i32::MAX) => {
+                IntegerType::SInt32
+            }
+            (false, _) => IntegerType::SInt64,
+        };
+        if C::PROTOBUF_FIXED {
+            varint.to_fixed()
+        } else {
+            varint
+        }
+    }
+
+    /// The constant size wire type with the same signedness and bitness
+    pub fn to_fixed(self) -> Self {
+        match self {
+            IntegerType::UInt32 | IntegerType::Fixed32 => IntegerType::Fixed32,
+            IntegerType::UInt64 | IntegerType::Fixed64 => IntegerType::Fixed64,
+            IntegerType::SInt32 | IntegerType::SFixed32 => IntegerType::SFixed32,
+            IntegerType::SInt64 | IntegerType::SFixed64 => IntegerType::SFixed64,
+        }
+    }
+
+    pub fn format(self) -> Format {
+        match self {
+            IntegerType::UInt32
+            | IntegerType::UInt64
+            | IntegerType::SInt32
+            | IntegerType::SInt64 => Format::VarInt,
+            IntegerType::Fixed32 | IntegerType::SFixed32 => Format::Fixed32,
+            IntegerType::Fixed64 | IntegerType::SFixed64 => Format::Fixed64,
+        }
+    }
+}
+
+/// Protobuf has no 128 bit integers, these are encoded as `bytes` of the big-endian two's
+/// complement instead. Shorter values are sign extended, `None` if there are more than 16 bytes.
+pub fn wide_integer_from_bytes(bytes: &[u8]) -> Option<i128> {
+    let mut value = [0u8; core::mem::size_of::<i128>()];
+    let offset = value.len().checked_sub(bytes.len())?;
+    if bytes.first().is_some_and(|byte| byte & 0x80 != 0) {
+        value[..offset].fill(0xFF);
+    }
+    value[offset..].copy_from_slice(bytes);
+    Some(i128::from_be_bytes(value))
+}
+
 impl From<IoError> for Error {
     fn from(e: IoError) -> Self {
         Error::Io(Backtrace::new(), e)
@@ -126,6 +253,16 @@ pub trait ProtoWrite {
 
     fn write_sfixed32(&mut self, value: i32) -> Result<(), Error>;
 
+    fn write_sfixed64(&mut self, value: i64) -> Result<(), Error>;
+
+    fn write_fixed32(&mut self, value: u32) -> Result<(), Error> {
+        self.write_sfixed32(value as i32)
+    }
+
+    fn write_fixed64(&mut self, value: u64) -> Result<(), Error> {
+        self.write_sfixed64(value as i64)
+    }
+
     fn write_uint32(&mut self, value: u32) -> Result<(), Error> {
         self.write_varint(u64::from(value))
     }
@@ -168,6 +305,21 @@ pub trait ProtoWrite {
         self.write_sfixed32(value)
     }
 
+    fn write_tagged_sfixed64(&mut self, field: u32, value: i64) -> Result<(), Error> {
+        self.write_tag(field, Format::Fixed64)?;
+        self.write_sfixed64(value)
+    }
+
+    fn write_tagged_fixed32(&mut self, field: u32, value: u32) -> Result<(), Error> {
+        self.write_tag(field, Format::Fixed32)?;
+        self.write_fixed32(value)
+    }
+
+    fn write_tagged_fixed64(&mut self, field: u32, value: u64) -> Result<(), Error> {
+        self.write_tag(field, Format::Fixed64)?;
+        self.write_fixed64(value)
+    }
+
     fn write_tagged_uint32(&mut self, field: u32, value: u32) -> Result<(), Error> {
         self.write_tag(field, Format::VarInt)?;
         self.write_uint32(value)
@@ -224,6 +376,11 @@ impl<W: Write> ProtoWrite for W {
         Ok(())
     }
 
+    fn write_sfixed64(&mut self, value: i64) -> Result<(), Error> {
+        self.write_i64::<E>(value)?;
+        Ok(())
+    }
+
     fn write_string(&mut self, value: &str) -> Result<(), Error> {
         self.write_bytes(value.as_bytes())?;
         Ok(())
@@ -258,6 +415,16 @@ pub trait ProtoRead {
 
     fn read_sfixed32(&mut self) -> Result<i32, Error>;
 
+    fn read_sfixed64(&mut self) -> Result<i64, Error>;
+
+    fn read_fixed32(&mut self) -> Result<u32, Error> {
+        Ok(self.read_sfixed32()? as u32)
+    }
+
+    fn read_fixed64(&mut self) -> Result<u64, Error> {
+        Ok(self.read_sfixed64()? as u64)
+    }
+
     fn read_uint32(&mut self) -> Result<u32, Error> {
         Ok(self.read_varint()? as u32)
     }
@@ -308,6 +475,10 @@ impl<R: Read> ProtoRead for R {
         Ok(self.read_i32::<E>()?)
     }
 
+    fn read_sfixed64(&mut self) -> Result<i64, Error> {
+        Ok(self.read_i64::<E>()?)
+    }
+
     fn read_string(&mut self) -> Result<String, Error> {
         let bytes = self.read_bytes()?;
         if let Ok(string) = String::from_utf8(bytes) {
@@ -45,6 +45,12 @@ impl<T: ProtobufEq + Default + PartialEq> ProtobufEq<Option<T>> for Option<T> {
     }
 }
 
+impl<T: ProtobufEq> ProtobufEq<Box<T>> for Box<T> {
+    fn protobuf_eq(&self, other: &Box<T>) -> bool {
+        T::protobuf_eq(self, other)
+    }
+}
+
 impl<T: ProtobufEq> ProtobufEq<Vec<T>> for Vec<T> {
     fn protobuf_eq(&self, other: &Vec<T>) -> bool {
         if self.len() == other.len() {
@@ -1,8 +1,9 @@
+use crate::descriptor::bitstring::canonical_bit_len;
 use crate::descriptor::numbers::Number;
 use crate::descriptor::sequence::Constraint;
-use crate::descriptor::{numbers, Null, ReadableType, Reader, WritableType, Writer};
+use crate::descriptor::{numbers, Null, ReadableType, Reader, Rewind, WritableType, Writer};
 use crate::protocol::basic::Error;
-use crate::protocol::basic::{BasicRead, BasicWrite};
+use crate::protocol::basic::{integer_i128_len, BasicRead, BasicWrite, TlvIter};
 use asn1rs_model::asn::Tag;
 use std::marker::PhantomData;
 
@@ -34,10 +35,15 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         todo!()
     }
 
-    fn write_sequence_of<C: crate::descriptor::sequenceof::Constraint, T: WritableType>(
+    fn write_sequence_of_iter<C: crate::descriptor::sequenceof::Constraint, T: WritableType, I>(
         &mut self,
-        _slice: &[T::Type],
-    ) -> Result<(), Self::Error> {
+        _len: usize,
+        _iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
         todo!()
     }
 
@@ -70,10 +76,7 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
             for IntegerConstraint<IC>
         {
         }
-        numbers::Integer::<u64, IntegerConstraint<C>>::write_value(
-            self,
-            &enumerated.to_choice_index(),
-        )
+        numbers::Integer::<u64, IntegerConstraint<C>>::write_value(self, &enumerated.to_number())
     }
 
     fn write_choice<C: crate::descriptor::choice::Constraint>(
@@ -102,6 +105,12 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
         value: T,
     ) -> Result<(), Self::Error> {
         self.write.write_identifier(C::TAG)?;
+        if T::WIDE {
+            let value = value.to_i128();
+            self.write.write_length(integer_i128_len(value) as u64)?;
+            self.write.write_integer_i128(value)?;
+            return Ok(());
+        }
         let value = value.to_i64();
         let offset = value.leading_zeros() / u8::BITS;
         let len = value.to_be_bytes().len() as u64 - offset as u64;
@@ -154,10 +163,19 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
 
     fn write_bit_string<C: crate::descriptor::bitstring::Constraint>(
         &mut self,
-        _value: &[u8],
-        _bit_len: u64,
+        value: &[u8],
+        bit_len: u64,
     ) -> Result<(), Self::Error> {
-        todo!()
+        // 11.2.2: trailing zero bits of a named bit list are removed
+        let bit_len = if C::NAMED_BITS {
+            canonical_bit_len(value, bit_len, C::MIN)
+        } else {
+            bit_len
+        };
+        self.write.write_identifier(C::TAG)?;
+        self.write
+            .write_length(1 + bit_len.div_ceil(u64::from(u8::BITS)))?;
+        self.write.write_bit_string(value, bit_len)
     }
 
     fn write_boolean<C: crate::descriptor::boolean::Constraint>(
@@ -176,26 +194,71 @@ impl<W: BasicWrite> Writer for BasicWriter<W> {
     ) -> Result<(), Self::Error> {
         todo!()
     }
+
+    /// Writes the TLV verbatim, after ensuring that the value is exactly one TLV
+    fn write_any<C: crate::descriptor::octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        match TlvIter::new(value).next().transpose()? {
+            Some(tlv) if tlv.raw.len() == value.len() => self.write.write_tlv(value),
+            Some(tlv) => Err(Error::unexpected_length(
+                tlv.raw.len() as u64..tlv.raw.len() as u64 + 1,
+                value.len() as u64,
+            )),
+            None => Err(std::io::Error::from(std::io::ErrorKind::UnexpectedEof).into()),
+        }
+    }
 }
 
 pub struct BasicReader<R: BasicRead> {
     read: R,
+    strict: bool,
 }
 
 impl<W: BasicRead> From<W> for BasicReader<W> {
     #[inline]
     fn from(read: W) -> Self {
-        Self { read }
+        Self {
+            read,
+            strict: false,
+        }
     }
 }
 
 impl<W: BasicRead> BasicReader<W> {
+    /// Rejects values that are not encoded as DER requires, such as BIT STRINGs whose unused bits
+    /// are not zero. Otherwise, these unused bits are cleared.
+    #[inline]
+    pub fn strict(mut self) -> Self {
+        self.strict = true;
+        self
+    }
+
+    #[inline]
+    pub fn is_strict(&self) -> bool {
+        self.strict
+    }
+
     #[inline]
     pub fn into_inner(self) -> W {
         self.read
     }
 }
 
+/// The checkpoint is the remaining input, which is why only readers of slices can be rewound
+impl<'a> Rewind for BasicReader<&'a [u8]> {
+    type Checkpoint = &'a [u8];
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        self.read
+    }
+
+    fn restore(&mut self, checkpoint: Self::Checkpoint) {
+        self.read = checkpoint;
+    }
+}
+
 impl<R: BasicRead> Reader for BasicReader<R> {
     type Error = Error;
 
@@ -240,8 +303,7 @@ impl<R: BasicRead> Reader for BasicReader<R> {
         {
         }
         numbers::Integer::<u64, IntegerConstraint<C>>::read_value(self).and_then(|v| {
-            C::from_choice_index(v)
-                .ok_or_else(|| Error::unexpected_choice_index(0..C::VARIANT_COUNT, v))
+            C::from_number(v).ok_or_else(|| Error::unexpected_choice_index(0..C::VARIANT_COUNT, v))
         })
     }
 
@@ -267,7 +329,11 @@ impl<R: BasicRead> Reader for BasicReader<R> {
             return Err(Error::unexpected_tag(C::TAG, identifier));
         }
         let len = self.read.read_length()?;
-        self.read.read_integer_i64(len as u32).map(T::from_i64)
+        if T::WIDE {
+            self.read.read_integer_i128(len as u32).map(T::from_i128)
+        } else {
+            self.read.read_integer_i64(len as u32).map(T::from_i64)
+        }
     }
 
     fn read_utf8string<C: crate::descriptor::utf8string::Constraint>(
@@ -306,10 +372,33 @@ impl<R: BasicRead> Reader for BasicReader<R> {
         todo!()
     }
 
+    #[cfg(feature = "uuid")]
+    fn read_uuid<C: crate::descriptor::octetstring::Constraint>(
+        &mut self,
+    ) -> Result<uuid::Uuid, Self::Error> {
+        let bytes = self.read_octet_string::<C>()?;
+        uuid::Uuid::from_slice(&bytes)
+            .map_err(|_| Error::unexpected_length(16..17, bytes.len() as u64))
+    }
+
     fn read_bit_string<C: crate::descriptor::bitstring::Constraint>(
         &mut self,
     ) -> Result<(Vec<u8>, u64), Self::Error> {
-        todo!()
+        let identifier = self.read.read_identifier()?;
+        if identifier.value() != C::TAG.value() {
+            return Err(Error::unexpected_tag(C::TAG, identifier));
+        }
+        let len = self.read.read_length()?;
+        let (mut bytes, bit_len) = self.read.read_bit_string(len)?;
+        let unused = bytes.len() as u64 * u64::from(u8::BITS) - bit_len;
+        if let Some(last) = bytes.last_mut() {
+            let used = 0xFF_u8 << unused;
+            if self.strict && *last & !used != 0 {
+                return Err(Error::padding_bits_not_zero());
+            }
+            *last &= used;
+        }
+        Ok((bytes, bit_len))
     }
 
     fn read_boolean<C: crate::descriptor::boolean::Constraint>(
@@ -330,4 +419,11 @@ impl<R: BasicRead> Reader for BasicReader<R> {
     fn read_null<C: crate::descriptor::null::Constraint>(&mut self) -> Result<Null, Self::Error> {
         todo!()
     }
+
+    /// Reads the complete TLV, whatever its tag
+    fn read_any<C: crate::descriptor::octetstring::Constraint>(
+        &mut self,
+    ) -> Result<Vec<u8>, Self::Error> {
+        self.read.read_tlv()
+    }
 }
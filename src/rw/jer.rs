@@ -0,0 +1,552 @@
+use crate::descriptor::*;
+use crate::protocol::jer::{write_string, Error, Json};
+use crate::util::{from_hex, to_hex};
+use core::borrow::Borrow;
+
+enum WriteScope {
+    Fields {
+        names: &'static [&'static str],
+        index: usize,
+        written: usize,
+    },
+    Items {
+        written: usize,
+    },
+    Alternative,
+}
+
+/// Writes [`Writable`] values in the JSON Encoding Rules (JER, ITU-T X.697). The member names
+/// are taken from the `FIELD_NAMES` and `VARIANT_NAMES` of the descriptor constraints.
+///
+/// SEQUENCE and SET values are written as objects without the absent OPTIONAL fields, SEQUENCE
+/// OF and SET OF values as arrays and CHOICE values as objects with the identifier of the
+/// alternative as single member. ENUMERATED values are written as their identifier, OCTET
+/// STRING values as hex string and BIT STRING values as object with the hex `value` and the
+/// `length` in bits.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// let mut writer = JerWriter::default();
+/// writer.write_utf8string::<asn1rs::descriptor::utf8string::NoConstraint>("say \"hi\"").unwrap();
+/// assert_eq!(r#""say \"hi\"""#, writer.as_str());
+/// ```
+#[derive(Default)]
+pub struct JerWriter {
+    output: String,
+    scopes: Vec<WriteScope>,
+}
+
+impl JerWriter {
+    pub fn as_str(&self) -> &str {
+        &self.output
+    }
+
+    pub fn into_string(self) -> String {
+        self.output
+    }
+
+    /// Writes the separator and the member name of the field, if the value is part of a
+    /// constructed value
+    fn begin_value(&mut self) {
+        let (written, name) = match self.scopes.last_mut() {
+            Some(WriteScope::Fields {
+                names,
+                index,
+                written,
+            }) => {
+                *index += 1;
+                *written += 1;
+                (*written, names.get(*index - 1).copied())
+            }
+            Some(WriteScope::Items { written }) => {
+                *written += 1;
+                (*written, None)
+            }
+            Some(WriteScope::Alternative) | None => return,
+        };
+        if written > 1 {
+            self.output.push(',');
+        }
+        if let Some(name) = name {
+            write_string(&mut self.output, name);
+            self.output.push(':');
+        }
+    }
+
+    /// Skips the field of an absent OPTIONAL value
+    fn skip_value(&mut self) {
+        if let Some(WriteScope::Fields { index, .. }) = self.scopes.last_mut() {
+            *index += 1;
+        }
+    }
+
+    fn write_constructed<F: FnOnce(&mut Self) -> Result<(), core::convert::Infallible>>(
+        &mut self,
+        scope: WriteScope,
+        f: F,
+    ) -> Result<(), core::convert::Infallible> {
+        self.begin_value();
+        let (open, close) = match scope {
+            WriteScope::Items { .. } => ('[', ']'),
+            _ => ('{', '}'),
+        };
+        self.output.push(open);
+        self.scopes.push(scope);
+        f(self)?;
+        self.scopes.pop();
+        self.output.push(close);
+        Ok(())
+    }
+
+    fn write_identifier(&mut self, names: &'static [&'static str], index: u64) {
+        match names.get(index as usize) {
+            Some(name) => write_string(&mut self.output, name),
+            None => write_string(&mut self.output, &index.to_string()),
+        }
+    }
+
+    fn write_text(&mut self, value: &str) -> Result<(), core::convert::Infallible> {
+        self.begin_value();
+        write_string(&mut self.output, value);
+        Ok(())
+    }
+}
+
+impl Writer for JerWriter {
+    type Error = core::convert::Infallible;
+
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = WriteScope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+            written: 0,
+        };
+        self.write_constructed(scope, f)
+    }
+
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
+        &mut self,
+        _len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
+        self.write_constructed(WriteScope::Items { written: 0 }, |w| {
+            iter.into_iter()
+                .try_for_each(|value| T::write_value(w, value.borrow()))
+        })
+    }
+
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_sequence::<C, F>(f)
+    }
+
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[<T as WritableType>::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_constructed(WriteScope::Items { written: 0 }, |w| {
+            slice.iter().try_for_each(|value| T::write_value(w, value))
+        })
+    }
+
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.write_identifier(C::VARIANT_NAMES, enumerated.to_choice_index());
+        Ok(())
+    }
+
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push('{');
+        self.write_identifier(C::VARIANT_NAMES, choice.to_choice_index());
+        self.output.push(':');
+        self.scopes.push(WriteScope::Alternative);
+        let result = choice.write_content(self);
+        self.scopes.pop();
+        self.output.push('}');
+        result
+    }
+
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.skip_value();
+                Ok(())
+            }
+        }
+    }
+
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        T::write_value(self, value)
+    }
+
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str(&value.to_i128().to_string());
+        Ok(())
+    }
+
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.write_text(&to_hex(value))
+    }
+
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        let bytes = value.len().min(bit_len.div_ceil(8) as usize);
+        self.output.push_str("{\"value\":");
+        write_string(&mut self.output, &to_hex(&value[..bytes]));
+        self.output.push_str(&format!(",\"length\":{bit_len}}}"));
+        Ok(())
+    }
+
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str(if value { "true" } else { "false" });
+        Ok(())
+    }
+
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str("null");
+        Ok(())
+    }
+}
+
+enum ReadScope {
+    Fields {
+        members: Vec<(String, Json)>,
+        names: &'static [&'static str],
+        index: usize,
+    },
+    Items(std::vec::IntoIter<Json>),
+    Value(Option<Json>),
+}
+
+/// Reads [`Readable`] values from JSON in the format written by the [`JerWriter`]. Absent
+/// OPTIONAL fields are read as `None`, absent DEFAULT fields as their default value and unknown
+/// members are ignored.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// let mut reader = JerReader::parse(r#""say \"hi\"""#).unwrap();
+/// let value = reader.read_utf8string::<asn1rs::descriptor::utf8string::NoConstraint>();
+/// assert_eq!(Ok("say \"hi\"".to_string()), value);
+/// ```
+pub struct JerReader {
+    scopes: Vec<ReadScope>,
+}
+
+impl From<Json> for JerReader {
+    fn from(json: Json) -> Self {
+        Self {
+            scopes: vec![ReadScope::Value(Some(json))],
+        }
+    }
+}
+
+impl JerReader {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        Json::parse(json).map(Self::from)
+    }
+
+    /// Takes the next value of the current scope, or `None` if the member of the field is absent
+    fn take_opt(&mut self) -> Result<Option<Json>, Error> {
+        Ok(match self.scopes.last_mut() {
+            Some(ReadScope::Fields {
+                members,
+                names,
+                index,
+            }) => {
+                *index += 1;
+                let name = names
+                    .get(*index - 1)
+                    .ok_or(Error::MissingMember("<unnamed>"))?;
+                members
+                    .iter()
+                    .position(|(member, _)| member == name)
+                    .map(|position| members.remove(position).1)
+            }
+            Some(ReadScope::Items(items)) => items.next(),
+            Some(ReadScope::Value(value)) => value.take(),
+            None => None,
+        })
+    }
+
+    fn take(&mut self) -> Result<Json, Error> {
+        let name = match self.scopes.last() {
+            Some(ReadScope::Fields { names, index, .. }) => {
+                names.get(*index).copied().unwrap_or("<unnamed>")
+            }
+            _ => "<value>",
+        };
+        self.take_opt()?.ok_or(Error::MissingMember(name))
+    }
+
+    fn with_scope<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        scope: ReadScope,
+        f: F,
+    ) -> Result<T, Error> {
+        self.scopes.push(scope);
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn take_string(&mut self) -> Result<String, Error> {
+        match self.take()? {
+            Json::String(string) => Ok(string),
+            _ => Err(Error::UnexpectedValue("string")),
+        }
+    }
+
+    fn hex_bytes(hex: &str) -> Result<Vec<u8>, Error> {
+        from_hex(hex).map_err(|_| Error::InvalidHexString(hex.to_string()))
+    }
+
+    fn variant_index(names: &'static [&'static str], name: String) -> Result<u64, Error> {
+        names
+            .iter()
+            .position(|variant| *variant == name)
+            .map(|index| index as u64)
+            .ok_or(Error::UnknownIdentifier(name))
+    }
+}
+
+impl Reader for JerReader {
+    type Error = Error;
+
+    fn read_sequence<
+        C: sequence::Constraint,
+        S: Sized,
+        F: Fn(&mut Self) -> Result<S, Self::Error>,
+    >(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        match self.take()? {
+            Json::Object(members) => self.with_scope(
+                ReadScope::Fields {
+                    members,
+                    names: C::FIELD_NAMES,
+                    index: 0,
+                },
+                f,
+            ),
+            _ => Err(Error::UnexpectedValue("object")),
+        }
+    }
+
+    fn read_sequence_of<C: sequenceof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        match self.take()? {
+            Json::Array(items) => {
+                let len = items.len();
+                self.with_scope(ReadScope::Items(items.into_iter()), |r| {
+                    (0..len).map(|_| T::read_value(r)).collect()
+                })
+            }
+            _ => Err(Error::UnexpectedValue("array")),
+        }
+    }
+
+    fn read_set<C: set::Constraint, S: Sized, F: Fn(&mut Self) -> Result<S, Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.read_sequence::<C, S, F>(f)
+    }
+
+    fn read_set_of<C: setof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.read_sequence_of::<C, T>()
+    }
+
+    fn read_enumerated<C: enumerated::Constraint>(&mut self) -> Result<C, Self::Error> {
+        let name = self.take_string()?;
+        let index = Self::variant_index(C::VARIANT_NAMES, name)?;
+        C::from_choice_index(index).ok_or_else(|| Error::UnknownIdentifier(index.to_string()))
+    }
+
+    fn read_choice<C: choice::Constraint>(&mut self) -> Result<C, Self::Error> {
+        match self.take()? {
+            Json::Object(mut members) if members.len() == 1 => {
+                let (name, value) = members.remove(0);
+                let index = Self::variant_index(C::VARIANT_NAMES, name)?;
+                self.with_scope(ReadScope::Value(Some(value)), |r| C::read_content(index, r))?
+                    .ok_or_else(|| Error::UnknownIdentifier(index.to_string()))
+            }
+            _ => Err(Error::UnexpectedValue("object with a single member")),
+        }
+    }
+
+    fn read_opt<T: ReadableType>(&mut self) -> Result<Option<T::Type>, Self::Error> {
+        match self.take_opt()? {
+            None => Ok(None),
+            Some(value) => self
+                .with_scope(ReadScope::Value(Some(value)), T::read_value)
+                .map(Some),
+        }
+    }
+
+    fn read_default<C: default::Constraint<Owned = T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        match self.take_opt()? {
+            None => Ok(C::DEFAULT_VALUE.to_owned()),
+            Some(value) => self.with_scope(ReadScope::Value(Some(value)), T::read_value),
+        }
+    }
+
+    fn read_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+    ) -> Result<T, Self::Error> {
+        match self.take()? {
+            Json::Number(number) => Ok(T::from_i128(number)),
+            _ => Err(Error::UnexpectedValue("number")),
+        }
+    }
+
+    fn read_utf8string<C: utf8string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_ia5string<C: ia5string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_numeric_string<C: numericstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_printable_string<C: printablestring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
+        let hex = self.take_string()?;
+        Self::hex_bytes(&hex)
+    }
+
+    #[cfg(feature = "uuid")]
+    fn read_uuid<C: octetstring::Constraint>(&mut self) -> Result<uuid::Uuid, Self::Error> {
+        let bytes = self.read_octet_string::<C>()?;
+        uuid::Uuid::from_slice(&bytes).map_err(|_| Error::UnexpectedValue("UUID of 16 bytes"))
+    }
+
+    fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
+        match self.take()? {
+            // fixed size BIT STRINGs may be written as plain hex string
+            Json::String(hex) => {
+                let bytes = Self::hex_bytes(&hex)?;
+                let bit_len = bytes.len() as u64 * 8;
+                Ok((bytes, bit_len))
+            }
+            Json::Object(members) => self.with_scope(
+                ReadScope::Fields {
+                    members,
+                    names: &["value", "length"],
+                    index: 0,
+                },
+                |r| {
+                    let hex = r.take_string()?;
+                    let bytes = Self::hex_bytes(&hex)?;
+                    match r.take()? {
+                        Json::Number(bit_len)
+                            if bit_len >= 0 && bytes.len() as u64 * 8 >= bit_len as u64 =>
+                        {
+                            Ok((bytes, bit_len as u64))
+                        }
+                        _ => Err(Error::UnexpectedValue("bit length")),
+                    }
+                },
+            ),
+            _ => Err(Error::UnexpectedValue("object")),
+        }
+    }
+
+    fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error> {
+        match self.take()? {
+            Json::Boolean(value) => Ok(value),
+            _ => Err(Error::UnexpectedValue("boolean")),
+        }
+    }
+
+    fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
+        match self.take()? {
+            Json::Null => Ok(Null),
+            _ => Err(Error::UnexpectedValue("null")),
+        }
+    }
+}
@@ -1,15 +1,31 @@
 mod der;
+mod jer;
 mod println;
 #[cfg(feature = "protobuf")]
+mod proto_json;
+#[cfg(feature = "protobuf")]
 mod proto_read;
 #[cfg(feature = "protobuf")]
 mod proto_write;
+mod scratch;
 mod uper;
+mod uper_stream;
+mod validate;
+mod value_notation;
+mod visitor;
 
 pub use der::*;
+pub use jer::*;
 pub use println::*;
 #[cfg(feature = "protobuf")]
+pub use proto_json::*;
+#[cfg(feature = "protobuf")]
 pub use proto_read::*;
 #[cfg(feature = "protobuf")]
 pub use proto_write::*;
+pub use scratch::*;
 pub use uper::*;
+pub use uper_stream::*;
+pub use validate::*;
+pub use value_notation::*;
+pub use visitor::*;
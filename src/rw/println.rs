@@ -1,4 +1,5 @@
 use crate::descriptor::*;
+use core::borrow::Borrow;
 
 #[derive(Default)]
 pub struct PrintlnWriter(usize);
@@ -8,7 +9,7 @@ impl PrintlnWriter {
         println!("{}{}", " ".repeat(self.0), text);
     }
 
-    fn with_increased_indentation<R, F: Fn(&mut Self) -> R>(&mut self, f: F) -> R {
+    fn with_increased_indentation<R, F: FnOnce(&mut Self) -> R>(&mut self, f: F) -> R {
         self.0 += 1;
         let r = f(self);
         self.0 -= 1;
@@ -27,23 +28,29 @@ impl Writer for PrintlnWriter {
         self.with_increased_indentation(|w| f(w))
     }
 
-    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
         &mut self,
-        slice: &[T::Type],
-    ) -> Result<(), Self::Error> {
+        len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
         self.indented_println(format!(
-            "Writing sequence-of ({}..{}), tag={:?}",
+            "Writing sequence-of ({}..{}) of {} values, tag={:?}",
             C::MIN
                 .map(|v| format!("{}", v))
                 .unwrap_or_else(|| String::from("MIN")),
             C::MAX
                 .map(|v| format!("{}", v))
                 .unwrap_or_else(|| String::from("MAX")),
+            len,
             C::TAG,
         ));
         self.with_increased_indentation(|w| {
-            for value in slice {
-                T::write_value(w, value)?;
+            for value in iter {
+                T::write_value(w, value.borrow())?;
             }
             Ok(())
         })
@@ -165,7 +172,7 @@ impl Writer for PrintlnWriter {
             if C::EXTENSIBLE { ",..." } else { "" },
             C::TAG,
         ));
-        self.with_increased_indentation(|w| w.indented_println(value.to_i64().to_string()));
+        self.with_increased_indentation(|w| w.indented_println(value.to_i128().to_string()));
         Ok(())
     }
 
@@ -0,0 +1,672 @@
+use crate::descriptor::*;
+use crate::protocol::jer::{write_string, Error, Json};
+use crate::protocol::protobuf::{wide_integer_from_bytes, IntegerType};
+use crate::util::{from_base64, to_base64};
+use core::borrow::Borrow;
+
+/// The name of the single field of the message a transparent type is declared as
+const TUPLE_FIELD_NAME: &str = "value";
+
+/// The JSON name of a field or `oneof` alternative, which is the lowerCamelCase ASN.1
+/// identifier of the `FIELD_NAMES` and `VARIANT_NAMES` of the descriptor constraints
+fn json_name(name: &str) -> &str {
+    match name {
+        "0" => TUPLE_FIELD_NAME,
+        name => name,
+    }
+}
+
+/// The name of a field or `oneof` alternative in the generated `.proto` file (snake_case), which
+/// readers accept as well
+fn proto_name(name: &str) -> String {
+    let mut proto = String::with_capacity(name.len() + 4);
+    for char in json_name(name).chars() {
+        if char.is_ascii_uppercase() {
+            proto.push('_');
+            proto.push(char.to_ascii_lowercase());
+        } else {
+            proto.push(char);
+        }
+    }
+    proto
+}
+
+/// The name of an enum value in the generated `.proto` file, which is prefixed with the name of
+/// the enum, for example `STATUS_IDLE` (see the `ProtobufDefGenerator` of `asn1rs-model`)
+fn proto_enum_value_name(name: &str, variant: &str) -> String {
+    let mut proto = String::with_capacity(name.len() + variant.len() + 4);
+    for (index, part) in [name, variant].iter().enumerate() {
+        if index > 0 {
+            proto.push('_');
+        }
+        let mut prev_upper = true;
+        for char in part.chars() {
+            if char == '-' {
+                proto.push('_');
+                continue;
+            }
+            if !prev_upper && char.is_uppercase() {
+                proto.push('_');
+            }
+            proto.extend(char.to_uppercase());
+            prev_upper = char.is_uppercase();
+        }
+    }
+    proto
+}
+
+/// Whether the `INTEGER` is encoded as one of the 64-bit protobuf types, which the JSON mapping
+/// writes as decimal string, because JSON numbers cannot represent them precisely
+fn is_64_bit<T: numbers::Number, C: numbers::Constraint<T>>() -> bool {
+    matches!(
+        IntegerType::of::<T, C>(),
+        IntegerType::UInt64 | IntegerType::SInt64 | IntegerType::Fixed64 | IntegerType::SFixed64
+    )
+}
+
+enum WriteScope {
+    Fields {
+        names: &'static [&'static str],
+        index: usize,
+        written: usize,
+    },
+    Items {
+        written: usize,
+    },
+    Alternative,
+}
+
+/// Writes [`Writable`] values in the canonical JSON mapping of proto3, for the messages the
+/// `ProtobufDefGenerator` of `asn1rs-model` declares for them. This allows to exchange them with
+/// gRPC gateways and other tools, that speak protobuf-JSON instead of JER.
+///
+/// The fields are named by their lowerCamelCase JSON name, absent OPTIONAL fields are omitted
+/// and all other fields are written, even if they have their default value. 64-bit integers are
+/// written as decimal strings, enum values by their name in the `.proto` file (such as
+/// `STATUS_IDLE`), OCTET STRING values in base64 and BIT STRING values in base64 of the
+/// `bytes` they are encoded as in protobuf. CHOICE values are written as object with the
+/// alternative of the `oneof` as single member. Because the encoding of a `map<K, V>` and its
+/// repeated entry messages only equals in the binary format, SEQUENCE OF key/value entries are
+/// written as arrays of entry objects.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// let mut writer = ProtoJsonWriter::default();
+/// writer.write_octet_string::<asn1rs::descriptor::octetstring::NoConstraint>(b"hi").unwrap();
+/// assert_eq!(r#""aGk=""#, writer.as_str());
+/// ```
+#[derive(Default)]
+pub struct ProtoJsonWriter {
+    output: String,
+    scopes: Vec<WriteScope>,
+}
+
+impl ProtoJsonWriter {
+    pub fn as_str(&self) -> &str {
+        &self.output
+    }
+
+    pub fn into_string(self) -> String {
+        self.output
+    }
+
+    /// Writes the separator and the member name of the field, if the value is part of a
+    /// constructed value
+    fn begin_value(&mut self) {
+        let (written, name) = match self.scopes.last_mut() {
+            Some(WriteScope::Fields {
+                names,
+                index,
+                written,
+            }) => {
+                *index += 1;
+                *written += 1;
+                (*written, names.get(*index - 1).copied())
+            }
+            Some(WriteScope::Items { written }) => {
+                *written += 1;
+                (*written, None)
+            }
+            Some(WriteScope::Alternative) | None => return,
+        };
+        if written > 1 {
+            self.output.push(',');
+        }
+        if let Some(name) = name {
+            write_string(&mut self.output, json_name(name));
+            self.output.push(':');
+        }
+    }
+
+    /// Skips the field of an absent OPTIONAL value
+    fn skip_value(&mut self) {
+        if let Some(WriteScope::Fields { index, .. }) = self.scopes.last_mut() {
+            *index += 1;
+        }
+    }
+
+    fn write_constructed<F: FnOnce(&mut Self) -> Result<(), core::convert::Infallible>>(
+        &mut self,
+        scope: WriteScope,
+        f: F,
+    ) -> Result<(), core::convert::Infallible> {
+        self.begin_value();
+        let (open, close) = match scope {
+            WriteScope::Items { .. } => ('[', ']'),
+            _ => ('{', '}'),
+        };
+        self.output.push(open);
+        self.scopes.push(scope);
+        f(self)?;
+        self.scopes.pop();
+        self.output.push(close);
+        Ok(())
+    }
+
+    fn write_text(&mut self, value: &str) -> Result<(), core::convert::Infallible> {
+        self.begin_value();
+        write_string(&mut self.output, value);
+        Ok(())
+    }
+}
+
+impl Writer for ProtoJsonWriter {
+    type Error = core::convert::Infallible;
+
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = WriteScope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+            written: 0,
+        };
+        self.write_constructed(scope, f)
+    }
+
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
+        &mut self,
+        _len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
+        self.write_constructed(WriteScope::Items { written: 0 }, |w| {
+            iter.into_iter()
+                .try_for_each(|value| T::write_value(w, value.borrow()))
+        })
+    }
+
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = WriteScope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+            written: 0,
+        };
+        self.write_constructed(scope, f)
+    }
+
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[<T as WritableType>::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_constructed(WriteScope::Items { written: 0 }, |w| {
+            slice.iter().try_for_each(|value| T::write_value(w, value))
+        })
+    }
+
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        let index = enumerated.to_choice_index();
+        match C::VARIANT_NAMES.get(index as usize) {
+            Some(variant) => {
+                write_string(&mut self.output, &proto_enum_value_name(C::NAME, variant))
+            }
+            None => self.output.push_str(&index.to_string()),
+        }
+        Ok(())
+    }
+
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push('{');
+        let index = choice.to_choice_index();
+        match C::VARIANT_NAMES.get(index as usize) {
+            Some(name) => write_string(&mut self.output, json_name(name)),
+            None => write_string(&mut self.output, &index.to_string()),
+        }
+        self.output.push(':');
+        self.scopes.push(WriteScope::Alternative);
+        let result = choice.write_content(self);
+        self.scopes.pop();
+        self.output.push('}');
+        result
+    }
+
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.skip_value();
+                Ok(())
+            }
+        }
+    }
+
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        T::write_value(self, value)
+    }
+
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        if T::WIDE {
+            // the bytes of protocol::protobuf::wide_integer_from_bytes
+            return self.write_text(&to_base64(&value.to_i128().to_be_bytes()));
+        }
+        let value = value.to_i64();
+        let number = if T::SIGNED {
+            value.to_string()
+        } else {
+            (value as u64).to_string()
+        };
+        if is_64_bit::<T, C>() {
+            self.write_text(&number)
+        } else {
+            self.begin_value();
+            self.output.push_str(&number);
+            Ok(())
+        }
+    }
+
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_text(value)
+    }
+
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.write_text(&to_base64(value))
+    }
+
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        let bits = BitVec::from_bytes(value.to_vec(), bit_len);
+        self.write_text(&to_base64(&bits.to_vec_with_trailing_bit_len()))
+    }
+
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str(if value { "true" } else { "false" });
+        Ok(())
+    }
+
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        // NULL is declared as (empty) bytes
+        self.write_text("")
+    }
+}
+
+enum ReadScope {
+    Fields {
+        members: Vec<(String, Json)>,
+        names: &'static [&'static str],
+        index: usize,
+    },
+    Items(std::vec::IntoIter<Json>),
+    Value(Option<Json>),
+}
+
+/// Reads [`Readable`] values from the canonical JSON mapping of proto3, see
+/// [`ProtoJsonWriter`]. As in the binary format, absent fields (or fields with the value `null`)
+/// are read as their default value, absent OPTIONAL fields as `None` and unknown members are
+/// ignored. The members may be named by their JSON name or their name in the `.proto` file,
+/// 64-bit integers may be numbers or strings, enum values names or numbers and bytes may be
+/// encoded in standard or URL-safe base64.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// let mut reader = ProtoJsonReader::parse(r#""18446744073709551615""#).unwrap();
+/// let value = reader.read_number::<u64, asn1rs::descriptor::numbers::NoConstraint>();
+/// assert_eq!(Ok(u64::MAX), value);
+/// ```
+pub struct ProtoJsonReader {
+    scopes: Vec<ReadScope>,
+}
+
+impl From<Json> for ProtoJsonReader {
+    fn from(json: Json) -> Self {
+        Self {
+            scopes: vec![ReadScope::Value(Some(json))],
+        }
+    }
+}
+
+impl ProtoJsonReader {
+    pub fn parse(json: &str) -> Result<Self, Error> {
+        Json::parse(json).map(Self::from)
+    }
+
+    /// Takes the next value of the current scope, or `None` if the member of the field is absent
+    /// or `null`
+    fn take_opt(&mut self) -> Option<Json> {
+        let value = match self.scopes.last_mut() {
+            Some(ReadScope::Fields {
+                members,
+                names,
+                index,
+            }) => {
+                *index += 1;
+                let name = names.get(*index - 1)?;
+                let proto = proto_name(name);
+                members
+                    .iter()
+                    .position(|(member, _)| member == json_name(name) || *member == proto)
+                    .map(|position| members.remove(position).1)
+            }
+            Some(ReadScope::Items(items)) => items.next(),
+            Some(ReadScope::Value(value)) => value.take(),
+            None => None,
+        };
+        value.filter(|value| *value != Json::Null)
+    }
+
+    fn with_scope<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        scope: ReadScope,
+        f: F,
+    ) -> Result<T, Error> {
+        self.scopes.push(scope);
+        let result = f(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn read_fields<S, F: Fn(&mut Self) -> Result<S, Error>>(
+        &mut self,
+        names: &'static [&'static str],
+        f: F,
+    ) -> Result<S, Error> {
+        let members = match self.take_opt() {
+            None => Vec::new(),
+            Some(Json::Object(members)) => members,
+            Some(_) => return Err(Error::UnexpectedValue("object")),
+        };
+        self.with_scope(
+            ReadScope::Fields {
+                members,
+                names,
+                index: 0,
+            },
+            f,
+        )
+    }
+
+    fn read_items<T: ReadableType>(&mut self) -> Result<Vec<T::Type>, Error> {
+        let items = match self.take_opt() {
+            None => Vec::new(),
+            Some(Json::Array(items)) => items,
+            Some(_) => return Err(Error::UnexpectedValue("array")),
+        };
+        let len = items.len();
+        self.with_scope(ReadScope::Items(items.into_iter()), |r| {
+            (0..len).map(|_| T::read_value(r)).collect()
+        })
+    }
+
+    fn take_string(&mut self) -> Result<String, Error> {
+        match self.take_opt() {
+            None => Ok(String::new()),
+            Some(Json::String(string)) => Ok(string),
+            Some(_) => Err(Error::UnexpectedValue("string")),
+        }
+    }
+
+    fn take_bytes(&mut self) -> Result<Vec<u8>, Error> {
+        Self::bytes_from_base64(self.take_string()?)
+    }
+
+    fn bytes_from_base64(base64: String) -> Result<Vec<u8>, Error> {
+        // the URL-safe alphabet only differs in two symbols
+        from_base64(&base64.replace('-', "+").replace('_', "/"))
+            .map_err(|_| Error::InvalidBase64String(base64))
+    }
+
+    fn alternative_index(names: &'static [&'static str], name: &str) -> Result<u64, Error> {
+        names
+            .iter()
+            .position(|alternative| {
+                json_name(alternative) == name || proto_name(alternative) == name
+            })
+            .map(|index| index as u64)
+            .ok_or_else(|| Error::UnknownIdentifier(name.to_string()))
+    }
+}
+
+impl Reader for ProtoJsonReader {
+    type Error = Error;
+
+    fn read_sequence<
+        C: sequence::Constraint,
+        S: Sized,
+        F: Fn(&mut Self) -> Result<S, Self::Error>,
+    >(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.read_fields(C::FIELD_NAMES, f)
+    }
+
+    fn read_sequence_of<C: sequenceof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.read_items::<T>()
+    }
+
+    fn read_set<C: set::Constraint, S: Sized, F: Fn(&mut Self) -> Result<S, Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<S, Self::Error> {
+        self.read_fields(C::FIELD_NAMES, f)
+    }
+
+    fn read_set_of<C: setof::Constraint, T: ReadableType>(
+        &mut self,
+    ) -> Result<Vec<T::Type>, Self::Error> {
+        self.read_items::<T>()
+    }
+
+    fn read_enumerated<C: enumerated::Constraint>(&mut self) -> Result<C, Self::Error> {
+        let index = match self.take_opt() {
+            None => 0,
+            Some(Json::Number(number)) => number as u64,
+            Some(Json::String(name)) => C::VARIANT_NAMES
+                .iter()
+                .position(|variant| proto_enum_value_name(C::NAME, variant) == name)
+                .map(|index| index as u64)
+                .ok_or(Error::UnknownIdentifier(name))?,
+            Some(_) => return Err(Error::UnexpectedValue("enum value")),
+        };
+        C::from_choice_index(index).ok_or_else(|| Error::UnknownIdentifier(index.to_string()))
+    }
+
+    fn read_choice<C: choice::Constraint>(&mut self) -> Result<C, Self::Error> {
+        let mut members = match self.take_opt() {
+            None => return Err(Error::MissingMember(C::NAME)),
+            Some(Json::Object(members)) => members,
+            Some(_) => return Err(Error::UnexpectedValue("object")),
+        };
+        members.retain(|(_, value)| *value != Json::Null);
+        match members.pop() {
+            Some((name, value)) if members.is_empty() => {
+                let index = Self::alternative_index(C::VARIANT_NAMES, &name)?;
+                self.with_scope(ReadScope::Value(Some(value)), |r| C::read_content(index, r))?
+                    .ok_or_else(|| Error::UnknownIdentifier(index.to_string()))
+            }
+            _ => Err(Error::UnexpectedValue("object with a single member")),
+        }
+    }
+
+    fn read_opt<T: ReadableType>(&mut self) -> Result<Option<T::Type>, Self::Error> {
+        match self.take_opt() {
+            None => Ok(None),
+            Some(value) => self
+                .with_scope(ReadScope::Value(Some(value)), T::read_value)
+                .map(Some),
+        }
+    }
+
+    fn read_default<C: default::Constraint<Owned = T::Type>, T: ReadableType>(
+        &mut self,
+    ) -> Result<T::Type, Self::Error> {
+        // like the ProtobufReader, an absent value is the default value of the protobuf type
+        T::read_value(self)
+    }
+
+    fn read_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+    ) -> Result<T, Self::Error> {
+        if T::WIDE {
+            return match self.take_opt() {
+                None => Ok(T::from_i128(0)),
+                Some(Json::String(base64)) => {
+                    wide_integer_from_bytes(&Self::bytes_from_base64(base64)?)
+                        .map(T::from_i128)
+                        .ok_or(Error::UnexpectedValue("integer of at most 16 bytes"))
+                }
+                Some(_) => Err(Error::UnexpectedValue("integer")),
+            };
+        }
+        match self.take_opt() {
+            None => Ok(T::from_i64(0)),
+            Some(Json::Number(number)) => Ok(T::from_i128(number)),
+            Some(Json::String(number)) => number
+                .parse::<i64>()
+                .or_else(|_| number.parse::<u64>().map(|number| number as i64))
+                .map(T::from_i64)
+                .map_err(|_| Error::UnexpectedValue("integer")),
+            Some(_) => Err(Error::UnexpectedValue("integer")),
+        }
+    }
+
+    fn read_utf8string<C: utf8string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_ia5string<C: ia5string::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_numeric_string<C: numericstring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_visible_string<C: visiblestring::Constraint>(&mut self) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_printable_string<C: printablestring::Constraint>(
+        &mut self,
+    ) -> Result<String, Self::Error> {
+        self.take_string()
+    }
+
+    fn read_octet_string<C: octetstring::Constraint>(&mut self) -> Result<Vec<u8>, Self::Error> {
+        self.take_bytes()
+    }
+
+    #[cfg(feature = "uuid")]
+    fn read_uuid<C: octetstring::Constraint>(&mut self) -> Result<uuid::Uuid, Self::Error> {
+        match self.take_bytes()? {
+            bytes if bytes.is_empty() => Ok(uuid::Uuid::nil()),
+            bytes => uuid::Uuid::from_slice(&bytes)
+                .map_err(|_| Error::UnexpectedValue("UUID of 16 bytes")),
+        }
+    }
+
+    fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
+        let bytes = self.take_bytes()?;
+        match bytes.len() {
+            0 => Ok((Vec::new(), 0)),
+            len if len < core::mem::size_of::<u64>() => Err(Error::UnexpectedValue("bit string")),
+            _ => Ok(BitVec::from_vec_with_trailing_bit_len(bytes).split()),
+        }
+    }
+
+    fn read_boolean<C: boolean::Constraint>(&mut self) -> Result<bool, Self::Error> {
+        match self.take_opt() {
+            None => Ok(false),
+            Some(Json::Boolean(value)) => Ok(value),
+            Some(_) => Err(Error::UnexpectedValue("boolean")),
+        }
+    }
+
+    fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
+        let _ = self.take_opt();
+        Ok(Null)
+    }
+}
+
+/// Serializes the value in the canonical JSON mapping of proto3, see [`ProtoJsonWriter`]
+pub fn to_proto_json<T: Writable>(value: &T) -> String {
+    let mut writer = ProtoJsonWriter::default();
+    match writer.write(value) {
+        Ok(()) => writer.into_string(),
+        Err(infallible) => match infallible {},
+    }
+}
+
+/// Deserializes the value from the canonical JSON mapping of proto3, see [`ProtoJsonReader`]
+pub fn from_proto_json<T: Readable>(json: &str) -> Result<T, Error> {
+    ProtoJsonReader::parse(json)?.read::<T>()
+}
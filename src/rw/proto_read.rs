@@ -1,6 +1,8 @@
 use crate::descriptor::*;
 use crate::protocol::protobuf::ProtoRead as _;
-use crate::protocol::protobuf::{Error, Format};
+use crate::protocol::protobuf::{
+    wide_integer_from_bytes, Error, Format, IntegerType, UnknownField, UnknownFields,
+};
 use std::borrow::Cow;
 use std::collections::VecDeque;
 use std::ops::Range;
@@ -12,10 +14,21 @@ enum State {
     },
     Enclosed {
         tag_counter: u32,
+        numbers: &'static [u32],
         tags: VecDeque<(u32, Format, Range<usize>)>,
     },
 }
 
+/// The field number of the field at the given (1-based) position, see
+/// [`sequence::Constraint::PROTOBUF_FIELD_NUMBERS`]
+#[inline]
+fn field_number(numbers: &[u32], position: u32) -> u32 {
+    numbers
+        .get(position as usize - 1)
+        .copied()
+        .unwrap_or(position)
+}
+
 pub struct ProtobufReader<'a> {
     source: Cow<'a, [u8]>,
     state: State,
@@ -44,7 +57,11 @@ impl From<Vec<u8>> for ProtobufReader<'static> {
 }
 
 impl<'a> ProtobufReader<'a> {
-    fn index_enclosed(&self, range: Range<usize>) -> Result<State, <Self as Reader>::Error> {
+    fn index_enclosed(
+        &self,
+        range: Range<usize>,
+        numbers: &'static [u32],
+    ) -> Result<State, <Self as Reader>::Error> {
         let mut position = range.start;
         let mut tags = VecDeque::new();
 
@@ -56,22 +73,24 @@ impl<'a> ProtobufReader<'a> {
             let pos_after = reader.len();
             let content_position = position + (pos_before - pos_after);
             let (content_offset, content_length) =
-                Self::read_content_offset_and_length(reader, format)?;
+                Self::read_content_offset_and_length(reader, tag, format)?;
             let content_position = content_position + content_offset;
             let content_end = content_position + content_length;
 
             tags.push_back((tag, format, content_position..content_end));
-            position = content_end;
+            position = content_end + Self::end_group_tag_length(tag, format);
         }
 
         Ok(State::Enclosed {
             tag_counter: 1,
+            numbers,
             tags,
         })
     }
 
     fn read_content_offset_and_length(
         slice: &mut &[u8],
+        tag: u32,
         format: Format,
     ) -> Result<(usize, usize), <Self as Reader>::Error> {
         match format {
@@ -89,6 +108,38 @@ impl<'a> ProtobufReader<'a> {
                 Ok((len_before - len_after, content_length as usize))
             }
             Format::Fixed32 => Ok((0, 4)),
+            Format::StartGroup => {
+                // the content of a group are all fields (and nested groups) until the end tag
+                let mut fields = *slice;
+                loop {
+                    let content_length = slice.len() - fields.len();
+                    let (field, field_format) = fields.read_tag()?;
+                    match field_format {
+                        Format::EndGroup if field == tag => return Ok((0, content_length)),
+                        Format::EndGroup => {
+                            return Err(Error::unexpected_tag((field, field_format)))
+                        }
+                        _ => {}
+                    }
+                    let (offset, length) =
+                        Self::read_content_offset_and_length(&mut &*fields, field, field_format)?;
+                    let end = offset + length + Self::end_group_tag_length(field, field_format);
+                    fields = fields.get(end..).ok_or_else(|| {
+                        Error::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof))
+                    })?;
+                }
+            }
+            Format::EndGroup => Err(Error::unexpected_tag((tag, format))),
+        }
+    }
+
+    /// The length of the end tag, that follows the content of a group
+    fn end_group_tag_length(tag: u32, format: Format) -> usize {
+        if format == Format::StartGroup {
+            let end_tag = u64::from(tag << 3 | Format::EndGroup as u32);
+            (64 - end_tag.leading_zeros() as usize).max(1).div_ceil(7)
+        } else {
+            0
         }
     }
 
@@ -96,9 +147,11 @@ impl<'a> ProtobufReader<'a> {
         match &self.state {
             State::Root { .. } => true,
             State::Enclosed {
-                tag_counter, tags, ..
+                tag_counter,
+                numbers,
+                tags,
             } => {
-                let next_tag = *tag_counter;
+                let next_tag = field_number(numbers, *tag_counter);
                 tags.iter().any(|(tag, _format, _range)| *tag == next_tag)
             }
         }
@@ -131,8 +184,12 @@ impl<'a> ProtobufReader<'a> {
     ) -> Option<Range<usize>> {
         match &mut self.state {
             State::Root { range } => Some(range.clone()),
-            State::Enclosed { tag_counter, tags } => {
-                let next_tag = *tag_counter;
+            State::Enclosed {
+                tag_counter,
+                numbers,
+                tags,
+            } => {
+                let next_tag = field_number(numbers, *tag_counter);
 
                 if INCREMENT {
                     *tag_counter += 1;
@@ -170,13 +227,14 @@ impl<'a> ProtobufReader<'a> {
     #[inline]
     fn read_set_or_sequence<S: Sized, F: Fn(&mut Self) -> Result<S, <Self as Reader>::Error>>(
         &mut self,
+        numbers: &'static [u32],
         f: F,
     ) -> Result<S, <Self as Reader>::Error> {
         let range = self
             .next_tag_range_filter_format::<true>(Format::LengthDelimited)
             .unwrap_or(0..0);
 
-        let mut state = self.index_enclosed(range)?;
+        let mut state = self.index_enclosed(range, numbers)?;
 
         core::mem::swap(&mut self.state, &mut state);
         let result = f(self);
@@ -215,7 +273,7 @@ impl<'a> Reader for ProtobufReader<'a> {
         &mut self,
         f: F,
     ) -> Result<S, Self::Error> {
-        self.read_set_or_sequence(f)
+        self.read_set_or_sequence(C::PROTOBUF_FIELD_NUMBERS, f)
     }
 
     #[inline]
@@ -230,7 +288,7 @@ impl<'a> Reader for ProtobufReader<'a> {
         &mut self,
         f: F,
     ) -> Result<S, Self::Error> {
-        self.read_set_or_sequence(f)
+        self.read_set_or_sequence(C::PROTOBUF_FIELD_NUMBERS, f)
     }
 
     #[inline]
@@ -269,8 +327,17 @@ impl<'a> Reader for ProtobufReader<'a> {
                     (format, range.start + read..range.end, tag)
                 };
 
+                let index = match C::PROTOBUF_FIELD_NUMBERS.iter().position(|n| *n == tag) {
+                    Some(index) => index as u64,
+                    None if C::PROTOBUF_FIELD_NUMBERS.is_empty() => {
+                        u64::from(tag.saturating_sub(1))
+                    }
+                    None => return Err(Error::unexpected_tag((tag, format))),
+                };
+
                 let mut state = State::Enclosed {
                     tag_counter: 1,
+                    numbers: &[],
                     tags: {
                         let mut v = VecDeque::with_capacity(1);
                         v.push_back((1u32, format, range));
@@ -278,7 +345,7 @@ impl<'a> Reader for ProtobufReader<'a> {
                     },
                 };
                 core::mem::swap(&mut self.state, &mut state);
-                let result = C::read_content(u64::from(tag.saturating_sub(1)), self);
+                let result = C::read_content(index, self);
                 self.state = state;
 
                 match result {
@@ -314,28 +381,31 @@ impl<'a> Reader for ProtobufReader<'a> {
     fn read_number<T: numbers::Number, C: numbers::Constraint<T>>(
         &mut self,
     ) -> Result<T, Self::Error> {
-        let mut reader = self.next_range_format_reader(Format::VarInt);
+        if T::WIDE {
+            let mut reader = self.next_range_format_reader(Format::LengthDelimited);
+            let bytes = reader.read_bytes()?;
+            return wide_integer_from_bytes(&bytes)
+                .map(T::from_i128)
+                .ok_or_else(|| Error::invalid_length(bytes.len() as u64));
+        }
+
+        let integer_type = IntegerType::of::<T, C>();
+        let mut reader = self.next_range_format_reader(integer_type.format());
 
         // protobuf does not serialize null or 0-ish values
         if reader.is_empty() {
             return Ok(T::from_i64(0));
         }
 
-        // This way is clearer, that the first branch is for unsigned and the second branch for
-        // signed types, while the inner branches determine 32- or 64-bitness
-        #[allow(clippy::collapsible_if)]
-        if const_unwrap_or!(C::MIN, 0) >= 0 {
-            if const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(u32::MAX) {
-                reader.read_uint32().map(|v| T::from_i64(v as i64))
-            } else {
-                reader.read_uint64().map(|v| T::from_i64(v as i64))
-            }
-        } else if const_unwrap_or!(C::MIN, i64::MIN) >= i64::from(i32::MIN)
-            && const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(i32::MAX)
-        {
-            reader.read_sint32().map(|v| T::from_i64(v as i64))
-        } else {
-            reader.read_sint64().map(|v| T::from_i64(v))
+        match integer_type {
+            IntegerType::UInt32 => reader.read_uint32().map(|v| T::from_i64(v as i64)),
+            IntegerType::UInt64 => reader.read_uint64().map(|v| T::from_i64(v as i64)),
+            IntegerType::SInt32 => reader.read_sint32().map(|v| T::from_i64(v as i64)),
+            IntegerType::SInt64 => reader.read_sint64().map(|v| T::from_i64(v)),
+            IntegerType::Fixed32 => reader.read_fixed32().map(|v| T::from_i64(v as i64)),
+            IntegerType::Fixed64 => reader.read_fixed64().map(|v| T::from_i64(v as i64)),
+            IntegerType::SFixed32 => reader.read_sfixed32().map(|v| T::from_i64(v as i64)),
+            IntegerType::SFixed64 => reader.read_sfixed64().map(|v| T::from_i64(v)),
         }
     }
 
@@ -377,6 +447,17 @@ impl<'a> Reader for ProtobufReader<'a> {
         reader.read_bytes()
     }
 
+    #[cfg(feature = "uuid")]
+    #[inline]
+    fn read_uuid<C: octetstring::Constraint>(&mut self) -> Result<uuid::Uuid, Self::Error> {
+        match self.read_octet_string::<C>()? {
+            // the default value of an absent bytes field
+            bytes if bytes.is_empty() => Ok(uuid::Uuid::nil()),
+            bytes => uuid::Uuid::from_slice(&bytes)
+                .map_err(|_| Error::invalid_length(bytes.len() as u64)),
+        }
+    }
+
     #[inline]
     fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
         let mut reader = self.next_range_format_reader(Format::LengthDelimited); // TODO Format::VarInt ??
@@ -401,4 +482,32 @@ impl<'a> Reader for ProtobufReader<'a> {
     fn read_null<C: null::Constraint>(&mut self) -> Result<Null, Self::Error> {
         Ok(Null)
     }
+
+    #[inline]
+    fn read_protobuf_unknown_fields(&mut self) -> Result<UnknownFields, Self::Error> {
+        let mut unknown = UnknownFields::default();
+        if let State::Enclosed {
+            tag_counter,
+            numbers,
+            tags,
+        } = &mut self.state
+        {
+            // fields with a known number, that were not read (again), are no unknown fields
+            let known = (1..*tag_counter)
+                .map(|position| field_number(numbers, position))
+                .collect::<Vec<_>>();
+            tags.retain(|(number, format, range)| {
+                if known.contains(number) {
+                    return true;
+                }
+                unknown.push(UnknownField {
+                    number: *number,
+                    format: *format,
+                    value: self.source[range.clone()].to_vec(),
+                });
+                false
+            });
+        }
+        Ok(unknown)
+    }
 }
@@ -1,15 +1,28 @@
 use crate::descriptor::*;
 use crate::prelude::ProtobufReader;
 use crate::protocol::protobuf::ProtoWrite as _;
-use crate::protocol::protobuf::{Error, Format};
+use crate::protocol::protobuf::{Error, Format, IntegerType, UnknownFields};
+use core::borrow::Borrow;
 use std::io::Write;
 
 #[derive(Debug, Default, Copy, Clone)]
 struct State {
     tag_counter: u32,
+    numbers: &'static [u32],
     format: Option<Format>,
 }
 
+impl State {
+    /// The field number of the next field, see [`sequence::Constraint::PROTOBUF_FIELD_NUMBERS`]
+    #[inline]
+    fn next_tag(&self) -> u32 {
+        self.numbers
+            .get(self.tag_counter as usize)
+            .copied()
+            .unwrap_or(self.tag_counter + 1)
+    }
+}
+
 enum SliceOrVec<'a> {
     Vec(Vec<u8>),
     Slice(usize, &'a mut [u8]),
@@ -123,13 +136,15 @@ impl<'a> ProtobufWriter<'a> {
     #[inline]
     fn write_set_or_sequence<F: Fn(&mut Self) -> Result<(), <Self as Writer>::Error>>(
         &mut self,
+        numbers: &'static [u32],
         f: F,
     ) -> Result<(), <Self as Writer>::Error> {
         let root = core::mem::take(&mut self.is_root);
         let mut state = core::mem::take(&mut self.state);
+        self.state.numbers = numbers;
 
         let result = if !root {
-            let tag = state.tag_counter + 1;
+            let tag = state.next_tag();
             let mut content = core::mem::take(&mut self.buffer);
 
             let result = f(self);
@@ -140,7 +155,7 @@ impl<'a> ProtobufWriter<'a> {
                 self.buffer.write_tag(tag, Format::LengthDelimited)?;
                 self.buffer.write_varint(content.len() as u64)?;
                 self.buffer.write_all(&content[..])?;
-                state.tag_counter = tag;
+                state.tag_counter += 1;
             }
 
             result
@@ -156,14 +171,18 @@ impl<'a> ProtobufWriter<'a> {
     }
 
     #[inline]
-    fn write_set_or_sequence_of<T: WritableType>(
+    fn write_set_or_sequence_of<T: WritableType, I>(
         &mut self,
-        slice: &[<T as WritableType>::Type],
-    ) -> Result<(), <Self as Writer>::Error> {
+        iter: I,
+    ) -> Result<(), <Self as Writer>::Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T::Type>,
+    {
         let state = self.state;
 
-        for value in slice {
-            let result = T::write_value(self, value);
+        for value in iter {
+            let result = T::write_value(self, value.borrow());
             self.state = state;
             result?;
         }
@@ -182,15 +201,20 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         f: F,
     ) -> Result<(), Self::Error> {
-        self.write_set_or_sequence(f)
+        self.write_set_or_sequence(C::PROTOBUF_FIELD_NUMBERS, f)
     }
 
     #[inline]
-    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
         &mut self,
-        slice: &[<T as WritableType>::Type],
-    ) -> Result<(), Self::Error> {
-        self.write_set_or_sequence_of::<T>(slice)
+        _len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
+        self.write_set_or_sequence_of::<T, _>(iter)
     }
 
     #[inline]
@@ -198,7 +222,7 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         f: F,
     ) -> Result<(), Self::Error> {
-        self.write_set_or_sequence(f)
+        self.write_set_or_sequence(C::PROTOBUF_FIELD_NUMBERS, f)
     }
 
     #[inline]
@@ -206,7 +230,7 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         slice: &[<T as WritableType>::Type],
     ) -> Result<(), Self::Error> {
-        self.write_set_or_sequence_of::<T>(slice)
+        self.write_set_or_sequence_of::<T, _>(slice)
     }
 
     #[inline]
@@ -218,10 +242,10 @@ impl Writer for ProtobufWriter<'_> {
             self.buffer
                 .write_enum_variant(enumerated.to_choice_index() as u32)?;
         } else {
-            let tag = self.state.tag_counter + 1;
+            let tag = self.state.next_tag();
             self.buffer
                 .write_tagged_enum_variant(tag, enumerated.to_choice_index() as u32)?;
-            self.state.tag_counter = tag;
+            self.state.tag_counter += 1;
         }
         self.state.format = Some(Format::VarInt);
         Ok(())
@@ -237,6 +261,7 @@ impl Writer for ProtobufWriter<'_> {
 
             // writing to the new buffer
             self.state.tag_counter = choice.to_choice_index() as u32;
+            self.state.numbers = C::PROTOBUF_FIELD_NUMBERS;
             let result = choice.write_content(self);
 
             // restore the original self attributes
@@ -246,16 +271,17 @@ impl Writer for ProtobufWriter<'_> {
             if result.is_ok() {
                 let buffer = buffer.into_inner_vec().unwrap(); // fine because take creates a vec
                 let format = Format::LengthDelimited;
-                let tag = self.state.tag_counter + 1;
+                let tag = self.state.next_tag();
                 self.buffer.write_tag(tag, format)?;
                 self.buffer.write_bytes(&buffer[..])?;
-                self.state.tag_counter = tag;
+                self.state.tag_counter += 1;
                 self.state.format = Some(format);
             }
 
             result
         } else {
             self.state.tag_counter = choice.to_choice_index() as u32;
+            self.state.numbers = C::PROTOBUF_FIELD_NUMBERS;
             choice.write_content(self)
         };
 
@@ -291,30 +317,32 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: T,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
-
-        // This way is clearer, that the first branch is for unsigned and the second branch for
-        // signed types, while the inner branches determine 32- or 64-bitness
-        #[allow(clippy::collapsible_if)]
-        if const_unwrap_or!(C::MIN, 0) >= 0 {
-            if const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(u32::MAX) {
-                let value = value.to_i64() as u32; // safe cast because of check above
-                self.buffer.write_tagged_uint32(tag, value)?;
-            } else {
-                let value = value.to_i64() as u64; // safe cast because of first check
-                self.buffer.write_tagged_uint64(tag, value)?;
-            }
-        } else if const_unwrap_or!(C::MIN, i64::MIN) >= i64::from(i32::MIN)
-            && const_unwrap_or!(C::MAX, i64::MAX) <= i64::from(i32::MAX)
-        {
-            let value = value.to_i64() as i32; // safe cast because of check above
-            self.buffer.write_tagged_sint32(tag, value)?;
-        } else {
-            let value = value.to_i64();
-            self.buffer.write_tagged_sint64(tag, value)?;
+        let tag = self.state.next_tag();
+
+        if T::WIDE {
+            // see protocol::protobuf::wide_integer_from_bytes
+            self.buffer
+                .write_tagged_bytes(tag, &value.to_i128().to_be_bytes())?;
+            self.state.format = Some(Format::LengthDelimited);
+            self.state.tag_counter += 1;
+            return Ok(());
         }
-        self.state.format = Some(Format::VarInt);
-        self.state.tag_counter = tag;
+
+        let value = value.to_i64();
+        let integer_type = IntegerType::of::<T, C>();
+        match integer_type {
+            // the casts are safe because of the range checks in IntegerType::of
+            IntegerType::UInt32 => self.buffer.write_tagged_uint32(tag, value as u32)?,
+            IntegerType::UInt64 => self.buffer.write_tagged_uint64(tag, value as u64)?,
+            IntegerType::SInt32 => self.buffer.write_tagged_sint32(tag, value as i32)?,
+            IntegerType::SInt64 => self.buffer.write_tagged_sint64(tag, value)?,
+            IntegerType::Fixed32 => self.buffer.write_tagged_fixed32(tag, value as u32)?,
+            IntegerType::Fixed64 => self.buffer.write_tagged_fixed64(tag, value as u64)?,
+            IntegerType::SFixed32 => self.buffer.write_tagged_sfixed32(tag, value as i32)?,
+            IntegerType::SFixed64 => self.buffer.write_tagged_sfixed64(tag, value)?,
+        }
+        self.state.format = Some(integer_type.format());
+        self.state.tag_counter += 1;
         Ok(())
     }
 
@@ -323,9 +351,9 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: &str,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_string(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
@@ -335,9 +363,9 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: &str,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_string(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
@@ -347,9 +375,9 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: &str,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_string(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
@@ -359,9 +387,9 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: &str,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_string(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
@@ -371,9 +399,9 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: &str,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_string(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
@@ -383,9 +411,9 @@ impl Writer for ProtobufWriter<'_> {
         &mut self,
         value: &[u8],
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_bytes(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
@@ -396,21 +424,21 @@ impl Writer for ProtobufWriter<'_> {
         value: &[u8],
         bit_len: u64,
     ) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         let mut value = value[..(bit_len as usize + 7) / 8].to_vec();
         bit_len.to_be_bytes().iter().for_each(|b| value.push(*b));
 
         self.buffer.write_tagged_bytes(tag, &value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::LengthDelimited);
         Ok(())
     }
 
     #[inline]
     fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
-        let tag = self.state.tag_counter + 1;
+        let tag = self.state.next_tag();
         self.buffer.write_tagged_bool(tag, value)?;
-        self.state.tag_counter = tag;
+        self.state.tag_counter += 1;
         self.state.format = Some(Format::VarInt);
         Ok(())
     }
@@ -419,4 +447,19 @@ impl Writer for ProtobufWriter<'_> {
     fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
         Ok(())
     }
+
+    #[inline]
+    fn write_protobuf_unknown_fields(&mut self, fields: &UnknownFields) -> Result<(), Self::Error> {
+        for field in fields.iter() {
+            self.buffer.write_tag(field.number, field.format)?;
+            if field.format == Format::LengthDelimited {
+                self.buffer.write_varint(field.value.len() as u64)?;
+            }
+            self.buffer.write_all(&field.value)?;
+            if field.format == Format::StartGroup {
+                self.buffer.write_tag(field.number, Format::EndGroup)?;
+            }
+        }
+        Ok(())
+    }
 }
@@ -0,0 +1,82 @@
+//! Thread-local scratch buffers for the temporary encodings of the writers, such as the content
+//! of open types in UPER or the elements of a `SET OF` in DER, which must be encoded before the
+//! surrounding length or order is known. The buffers are returned to a pool after use, so that
+//! encoders do not allocate for these intermediate steps once they are warmed up.
+
+use std::cell::RefCell;
+
+/// The number of buffers kept per thread, which limits the nesting depth of temporary encodings
+/// that are served without allocating
+pub const SCRATCH_BUFFERS_PER_THREAD: usize = 8;
+
+/// Buffers that grew beyond this capacity are dropped instead of being returned to the pool, so
+/// that a single large message does not pin its memory for the lifetime of the thread
+pub const SCRATCH_BUFFER_MAX_CAPACITY: usize = 64 * 1024;
+
+thread_local! {
+    static SCRATCH_BUFFERS: RefCell<Vec<Vec<u8>>> = const { RefCell::new(Vec::new()) };
+}
+
+/// Calls `f` with an empty buffer from the thread-local pool and returns the buffer to the pool
+/// afterwards. Nested calls receive distinct buffers.
+pub fn with_scratch_buffer<T, F: FnOnce(&mut Vec<u8>) -> T>(f: F) -> T {
+    let mut buffer = SCRATCH_BUFFERS
+        .try_with(|pool| pool.borrow_mut().pop())
+        .ok()
+        .flatten()
+        .unwrap_or_default();
+    let result = f(&mut buffer);
+    if buffer.capacity() <= SCRATCH_BUFFER_MAX_CAPACITY {
+        buffer.clear();
+        let _ = SCRATCH_BUFFERS.try_with(|pool| {
+            let mut pool = pool.borrow_mut();
+            if pool.len() < SCRATCH_BUFFERS_PER_THREAD {
+                pool.push(buffer);
+            }
+        });
+    }
+    result
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_scratch_buffer_is_reused() {
+        let (pointer, capacity) = with_scratch_buffer(|buffer| {
+            assert!(buffer.is_empty());
+            buffer.extend_from_slice(&[1, 2, 3]);
+            (buffer.as_ptr(), buffer.capacity())
+        });
+        with_scratch_buffer(|buffer| {
+            assert!(buffer.is_empty());
+            assert_eq!(pointer, buffer.as_ptr());
+            assert_eq!(capacity, buffer.capacity());
+        });
+    }
+
+    #[test]
+    pub fn test_nested_scratch_buffers_are_distinct() {
+        with_scratch_buffer(|outer| {
+            outer.push(1);
+            with_scratch_buffer(|inner| {
+                assert!(inner.is_empty());
+                inner.push(2);
+            });
+            assert_eq!(&[1], &outer[..]);
+        });
+    }
+
+    #[test]
+    pub fn test_large_scratch_buffers_are_dropped() {
+        let pointer = with_scratch_buffer(|buffer| {
+            buffer.resize(SCRATCH_BUFFER_MAX_CAPACITY + 1, 0);
+            buffer.as_ptr()
+        });
+        with_scratch_buffer(|buffer| {
+            assert_ne!(pointer, buffer.as_ptr());
+            assert!(buffer.capacity() <= SCRATCH_BUFFER_MAX_CAPACITY);
+        });
+    }
+}
@@ -2,16 +2,24 @@ use crate::descriptor::*;
 use crate::protocol::per::err::Error;
 use crate::protocol::per::err::ErrorKind;
 use crate::protocol::per::unaligned::buffer::BitBuffer;
+use crate::protocol::per::unaligned::BitRead;
 use crate::protocol::per::unaligned::BitWrite;
 use crate::protocol::per::unaligned::BYTE_LEN;
 use crate::protocol::per::PackedRead;
 use crate::protocol::per::PackedWrite;
+use crate::rw::with_scratch_buffer;
 use asn1rs_model::asn::Charset;
+use core::borrow::Borrow;
+use std::borrow::Cow;
 use std::fmt::Debug;
-use std::ops::Range;
+use std::ops::{Deref, DerefMut, Range};
 
 pub use crate::protocol::per::unaligned::buffer::Bits;
+#[cfg(feature = "bytes")]
+pub use crate::protocol::per::unaligned::bytes::BytesBits;
+pub use crate::protocol::per::unaligned::slice::SliceBits;
 pub use crate::protocol::per::unaligned::ScopedBitRead;
+pub use crate::protocol::per::unaligned::ScopedBitWrite;
 
 #[derive(Debug, Clone)]
 pub enum Scope {
@@ -77,7 +85,7 @@ impl Scope {
     #[inline]
     pub fn write_into_field(
         &mut self,
-        buffer: &mut BitBuffer,
+        buffer: &mut impl ScopedBitWrite,
         is_opt: bool,
         is_present: bool,
     ) -> Result<(), Error> {
@@ -112,17 +120,17 @@ impl Scope {
                         buffer.write_normally_small_non_negative_whole_number(
                             *number_of_ext_fields as u64 - 1,
                         )?;
-                        let pos = buffer.write_position;
+                        let pos = buffer.bit_len();
                         for _ in 0..*number_of_ext_fields {
                             if let Err(e) = buffer.write_bit(true) {
-                                buffer.write_position = pos;
+                                buffer.set_bit_len(pos);
                                 return Err(e);
                             }
                         }
 
                         // pos + 1 because the bit for the current call is already set
                         // by the initializer loop above
-                        let range = pos + 1..buffer.write_position;
+                        let range = pos + 1..buffer.bit_len();
                         *self = Scope::AllBitField(range);
                     } else {
                         *self = Scope::ExtensibleSequenceEmpty(name);
@@ -243,12 +251,97 @@ impl Scope {
     }
 }
 
-#[derive(Default)]
-pub struct UperWriter {
-    bits: BitBuffer,
+/// The number of fields in the extension root of the sequence, which is zero for an extensible
+/// sequence without any fields
+#[inline]
+const fn std_fields<C: sequence::Constraint>(extension_after: u64) -> usize {
+    let std_fields = extension_after + 1;
+    if std_fields < C::FIELD_COUNT {
+        std_fields as usize
+    } else {
+        C::FIELD_COUNT as usize
+    }
+}
+
+/// The bits a [`UperWriter`] writes into, see [`BitBuffer`] and [`SliceBits`]
+pub trait UperBitWrite: ScopedBitWrite + Sized {
+    /// See [`UperWriter::write_open_type`]
+    fn write_open_type<T, F: FnOnce(&mut UperWriter<Self>) -> Result<T, Error>>(
+        writer: &mut UperWriter<Self>,
+        f: F,
+    ) -> Result<T, Error>;
+}
+
+impl UperBitWrite for BitBuffer {
+    /// Encodes into a thread-local scratch buffer
+    fn write_open_type<T, F: FnOnce(&mut UperWriter<Self>) -> Result<T, Error>>(
+        writer: &mut UperWriter<Self>,
+        f: F,
+    ) -> Result<T, Error> {
+        with_scratch_buffer(|buffer| {
+            let mut scratch = UperWriter {
+                bits: BitBuffer::from_bits(core::mem::take(buffer), 0),
+                scope: None,
+            };
+            let result = f(&mut scratch);
+            *buffer = scratch.bits.buffer;
+            let result = result?;
+            writer.bits.write_octetstring(None, None, false, buffer)?;
+            Ok(result)
+        })
+    }
+}
+
+impl UperBitWrite for SliceBits<'_> {
+    /// Encodes behind the content into the same slice and moves the encoding into place
+    /// afterwards, so that not even open types allocate
+    fn write_open_type<T, F: FnOnce(&mut UperWriter<Self>) -> Result<T, Error>>(
+        writer: &mut UperWriter<Self>,
+        f: F,
+    ) -> Result<T, Error> {
+        let mut scratch = UperWriter {
+            bits: writer.bits.split_off_scratch(),
+            scope: None,
+        };
+        match f(&mut scratch) {
+            Ok(result) => {
+                writer.bits.write_scratch_as_octetstring(scratch.bits)?;
+                Ok(result)
+            }
+            Err(e) => {
+                writer.bits.discard_scratch(scratch.bits);
+                Err(e)
+            }
+        }
+    }
+}
+
+pub struct UperWriter<B: UperBitWrite = BitBuffer> {
+    bits: B,
     scope: Option<Scope>,
 }
 
+impl Default for UperWriter {
+    fn default() -> Self {
+        Self {
+            bits: BitBuffer::default(),
+            scope: None,
+        }
+    }
+}
+
+impl<'a> From<&'a mut [u8]> for UperWriter<SliceBits<'a>> {
+    /// Writes into the given slice without allocating, the slice is zeroed first so that the
+    /// padding bits of the last byte are zero. A write that exceeds the slice fails with
+    /// [`ErrorKind::BufferTooSmall`].
+    fn from(slice: &'a mut [u8]) -> Self {
+        UperWriter {
+            bits: SliceBits::from(slice),
+            scope: None,
+        }
+    }
+}
+
 impl UperWriter {
     pub fn with_capacity(capacity_bytes: usize) -> Self {
         Self {
@@ -257,8 +350,16 @@ impl UperWriter {
         }
     }
 
-    pub fn byte_content(&self) -> &[u8] {
-        self.bits.content()
+    /// See [`BitBuffer::reserve`]
+    pub fn reserve(&mut self, additional_bits: usize) {
+        self.bits.reserve(additional_bits);
+    }
+
+    /// Discards the written content but keeps the allocated buffer, so that a long-running
+    /// encoder can reuse one writer for all messages
+    pub fn clear(&mut self) {
+        self.bits.clear();
+        self.scope = None;
     }
 
     pub const fn bit_len(&self) -> usize {
@@ -273,8 +374,38 @@ impl UperWriter {
         self.bits.into()
     }
 
+    /// Hands the encoded content over as [`bytes::Bytes`] without copying it
+    #[cfg(feature = "bytes")]
+    pub fn into_bytes(self) -> bytes::Bytes {
+        bytes::Bytes::from(self.into_bytes_vec())
+    }
+}
+
+impl UperWriter<SliceBits<'_>> {
+    /// Zeroes the written bytes of the slice, so that the writer can be reused for the next
+    /// message
+    pub fn clear(&mut self) {
+        self.bits.clear();
+        self.scope = None;
+    }
+
+    pub const fn bit_len(&self) -> usize {
+        self.bits.bit_len()
+    }
+}
+
+impl<B: UperBitWrite> UperWriter<B> {
+    pub fn byte_content(&self) -> &[u8] {
+        self.bits.content()
+    }
+
+    /// See [`BitBuffer::as_io_slices`]
+    pub fn as_io_slices(&self) -> [std::io::IoSlice<'_>; 1] {
+        [std::io::IoSlice::new(self.bits.content())]
+    }
+
     pub fn as_reader(&self) -> UperReader<Bits> {
-        UperReader::from(Bits::from((self.byte_content(), self.bit_len())))
+        UperReader::from(Bits::from((self.byte_content(), self.bits.bit_len())))
     }
 
     #[inline]
@@ -325,16 +456,21 @@ impl UperWriter {
         f: F,
     ) -> Result<T, Error> {
         if const_map_or!(self.scope, Scope::encode_as_open_type_field, false) {
-            let mut writer = UperWriter::with_capacity(512);
-            let result = f(&mut writer)?;
-            self.bits
-                .write_octetstring(None, None, false, writer.bits.content())?;
-            Ok(result)
+            self.write_open_type(f)
         } else {
             f(self)
         }
     }
 
+    /// Encodes with `f` into a scratch writer and writes the encoding as octet string into this
+    /// writer, as required for open types and extension additions
+    pub fn write_open_type<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<T, Error> {
+        B::write_open_type(self, f)
+    }
+
     #[inline]
     pub fn write_extensible_bit_and_length_or_err(
         &mut self,
@@ -366,7 +502,7 @@ impl UperWriter {
     }
 }
 
-impl Writer for UperWriter {
+impl<B: UperBitWrite> Writer for UperWriter<B> {
     type Error = Error;
 
     #[inline]
@@ -377,7 +513,7 @@ impl Writer for UperWriter {
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
             let extension = if let Some(extension_after) = C::EXTENDED_AFTER_FIELD {
-                let bit_pos = w.bits.write_position;
+                let bit_pos = w.bits.bit_len();
                 // if no extension field is present, none will call into overwriting this
                 w.bits.write_bit(false)?;
                 Some((extension_after, bit_pos))
@@ -388,13 +524,13 @@ impl Writer for UperWriter {
             // In UPER the values for all OPTIONAL flags are written before any field
             // value is written. This remembers their position, so a later call of `write_opt`
             // can write them to the buffer
-            let write_pos = w.bits.write_position;
+            let write_pos = w.bits.bit_len();
             let range = write_pos..write_pos + C::STD_OPTIONAL_FIELDS as usize;
             for _ in 0..C::STD_OPTIONAL_FIELDS {
                 // insert in reverse order so that a simple pop() in `write_opt` retrieves
                 // the relevant position
                 if let Err(e) = w.bits.write_bit(false) {
-                    w.bits.write_position = write_pos; // undo write_bits
+                    w.bits.set_bit_len(write_pos); // undo write_bits
                     return Err(e);
                 }
             }
@@ -405,8 +541,9 @@ impl Writer for UperWriter {
                         name: C::NAME,
                         bit_pos,
                         opt_bit_field: Some(range),
-                        calls_until_ext_bitfield: (extension_after + 1) as usize,
-                        number_of_ext_fields: (C::FIELD_COUNT - (extension_after + 1)) as usize,
+                        calls_until_ext_bitfield: std_fields::<C>(extension_after),
+                        number_of_ext_fields: C::FIELD_COUNT as usize
+                            - std_fields::<C>(extension_after),
                     },
                     f,
                 )
@@ -418,10 +555,15 @@ impl Writer for UperWriter {
 
     #[inline]
     #[allow(clippy::redundant_pattern_matching)] // allow for const_*!
-    fn write_sequence_of<C: sequenceof::Constraint, T: WritableType>(
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
         &mut self,
-        slice: &[T::Type],
-    ) -> Result<(), Self::Error> {
+        len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: Borrow<T::Type>,
+    {
         self.write_bit_field_entry(false, true)?;
         self.scope_stashed(|w| {
             w.write_extensible_bit_and_length_or_err(
@@ -429,14 +571,23 @@ impl Writer for UperWriter {
                 C::MIN,
                 C::MAX,
                 i64::MAX as u64,
-                slice.len() as u64,
+                len as u64,
             )?;
 
             w.scope_stashed(|w| {
-                for value in slice {
-                    T::write_value(w, value)?;
+                let mut written = 0;
+                for value in iter {
+                    if written == len {
+                        return Err(ErrorKind::ValueCountMismatch(len, written + 1).into());
+                    }
+                    T::write_value(w, value.borrow())?;
+                    written += 1;
+                }
+                if written == len {
+                    Ok(())
+                } else {
+                    Err(ErrorKind::ValueCountMismatch(len, written).into())
                 }
-                Ok(())
             })
         })
     }
@@ -483,11 +634,7 @@ impl Writer for UperWriter {
                 .write_choice_index(C::STD_VARIANT_COUNT, C::EXTENSIBLE, index)?;
 
             if index >= C::STD_VARIANT_COUNT {
-                // TODO performance
-                let mut writer = UperWriter::with_capacity(512);
-                choice.write_content(&mut writer)?;
-                w.bits
-                    .write_octetstring(None, None, false, writer.byte_content())
+                w.write_open_type(|w| choice.write_content(w))
             } else {
                 choice.write_content(w)
             }
@@ -529,6 +676,17 @@ impl Writer for UperWriter {
         value: T,
     ) -> Result<(), Self::Error> {
         self.write_bit_field_entry(false, true)?;
+        if T::WIDE {
+            return self.with_buffer(|w| {
+                write_wide_whole_number(
+                    &mut w.bits,
+                    C::MIN_T.map(T::to_i128),
+                    C::MAX_T.map(T::to_i128),
+                    C::EXTENSIBLE,
+                    value.to_i128(),
+                )
+            });
+        }
         let value = value.to_i64();
 
         let max_fn = if C::EXTENSIBLE {
@@ -601,12 +759,10 @@ impl Writer for UperWriter {
                 value.chars().count() as u64,
             )?;
 
-            for char in value.chars().map(|c| c as u8) {
-                // 7 bits
-                w.bits.write_bits_with_offset(&[char], 1)?;
-            }
-
-            Ok(())
+            w.bits.write_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::IA5_STRING_CHARACTERS),
+                value,
+            )
         })
     }
 
@@ -627,15 +783,10 @@ impl Writer for UperWriter {
                 value.chars().count() as u64,
             )?;
 
-            for char in value.chars().map(|c| c as u8) {
-                let char = match char - 32 {
-                    0 => 0,
-                    c => c - 15,
-                };
-                w.bits.write_bits_with_offset(&[char], 4)?;
-            }
-
-            Ok(())
+            w.bits.write_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::NUMERIC_STRING_CHARACTERS),
+                value,
+            )
         })
     }
 
@@ -656,11 +807,10 @@ impl Writer for UperWriter {
                 value.chars().count() as u64,
             )?;
 
-            for char in value.chars() {
-                w.bits.write_bits_with_offset(&[char as u8], 1)?;
-            }
-
-            Ok(())
+            w.bits.write_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::PRINTABLE_STRING_CHARACTERS),
+                value,
+            )
         })
     }
 
@@ -681,11 +831,10 @@ impl Writer for UperWriter {
                 value.chars().count() as u64,
             )?;
 
-            for char in value.chars() {
-                w.bits.write_bits_with_offset(&[char as u8], 1)?;
-            }
-
-            Ok(())
+            w.bits.write_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::VISIBLE_STRING_CHARACTERS),
+                value,
+            )
         })
     }
 
@@ -707,10 +856,23 @@ impl Writer for UperWriter {
         value: &[u8],
         bit_len: u64,
     ) -> Result<(), Self::Error> {
+        let (value, bit_len) = if C::NAMED_BITS {
+            let bit_len = bitstring::canonical_bit_len(value, bit_len, C::MIN);
+            let mut value = Cow::Borrowed(value);
+            if bit_len as usize > value.len() * BYTE_LEN {
+                // the added trailing zero bits
+                value
+                    .to_mut()
+                    .resize((bit_len as usize).div_ceil(BYTE_LEN), 0x00);
+            }
+            (value, bit_len)
+        } else {
+            (Cow::Borrowed(value), bit_len)
+        };
         self.write_bit_field_entry(false, true)?;
         self.with_buffer(|w| {
             w.bits
-                .write_bitstring(C::MIN, C::MAX, C::EXTENSIBLE, value, 0, bit_len)
+                .write_bitstring(C::MIN, C::MAX, C::EXTENSIBLE, &value, 0, bit_len)
         })
     }
 
@@ -751,6 +913,13 @@ impl<'a> From<(&'a [u8], usize)> for UperReader<Bits<'a>> {
     }
 }
 
+#[cfg(feature = "bytes")]
+impl From<bytes::Bytes> for UperReader<BytesBits> {
+    fn from(bytes: bytes::Bytes) -> Self {
+        UperReader::from(BytesBits::from(bytes))
+    }
+}
+
 impl<B: ScopedBitRead> UperReader<B> {
     #[inline]
     pub fn into_bits(self) -> B {
@@ -812,6 +981,17 @@ impl<B: ScopedBitRead> UperReader<B> {
         self.bits.remaining()
     }
 
+    /// Reads the index of the `CHOICE` `C` at the current position - such as at the start of a
+    /// message - without advancing it, so that the message can be routed before it is decoded.
+    /// Indices from [`choice::Constraint::STD_VARIANT_COUNT`] on belong to the extension
+    /// additions and might be unknown to `C`.
+    pub fn peek_choice_index<C: choice::Constraint>(&mut self) -> Result<u64, Error> {
+        let position = self.bits.pos();
+        self.bits.with_read_position_at(position, |bits| {
+            bits.read_choice_index(C::STD_VARIANT_COUNT, C::EXTENSIBLE)
+        })
+    }
+
     #[inline]
     pub fn scope_pushed<T, F: FnOnce(&mut Self) -> Result<T, Error>>(
         &mut self,
@@ -873,6 +1053,26 @@ impl<B: ScopedBitRead> UperReader<B> {
         result
     }
 
+    /// Limits the reader to the next `bit_len` bits - such as the content of an open type or of a
+    /// `CONTAINING` field - so that it can be decoded by independent code without overrunning into
+    /// the subsequent fields. Once the returned reader is dropped, reading continues after the
+    /// window, no matter how much of it was read.
+    pub fn scoped(&mut self, bit_len: usize) -> Result<ScopedUperReader<'_, B>, Error> {
+        if bit_len > self.bits.remaining() {
+            return Err(Error::insufficient_data_in_source_buffer());
+        }
+        let end = self.bits.pos() + bit_len;
+        let len = self.bits.len();
+        self.bits.set_len(end);
+        let scope = self.scope.take();
+        Ok(ScopedUperReader {
+            reader: self,
+            end,
+            len,
+            scope,
+        })
+    }
+
     #[inline]
     pub fn read_bit_field_entry(&mut self, is_opt: bool) -> Result<Option<bool>, Error> {
         #[allow(clippy::let_and_return)]
@@ -915,6 +1115,118 @@ impl<B: ScopedBitRead> UperReader<B> {
     }
 }
 
+/// Encodes integers exceeding 64 bits, of which the bounds are only given through
+/// [`numbers::Constraint::MIN_T`] and [`numbers::Constraint::MAX_T`]. Unlike for the 64 bit
+/// integers, a range without an upper bound is encoded as semi-constrained whole number.
+pub(crate) fn write_wide_whole_number(
+    bits: &mut impl BitWrite,
+    min: Option<i128>,
+    max: Option<i128>,
+    extensible: bool,
+    value: i128,
+) -> Result<(), Error> {
+    let in_range = min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max);
+
+    if extensible {
+        bits.write_bit(!in_range)?;
+    } else if !in_range {
+        return Err(ErrorKind::ValueNotInRange(
+            value,
+            min.unwrap_or(i128::MIN),
+            max.unwrap_or(i128::MAX),
+        )
+        .into());
+    }
+
+    match (min, max) {
+        (Some(min), Some(max)) if in_range => {
+            bits.write_constrained_whole_number_i128(min, max, value)
+        }
+        (Some(min), None) if in_range => bits.write_semi_constrained_whole_number_i128(min, value),
+        _ => bits.write_unconstrained_whole_number_i128(value),
+    }
+}
+
+/// Decodes integers exceeding 64 bits, see [`write_wide_whole_number`]
+pub(crate) fn read_wide_whole_number(
+    bits: &mut impl BitRead,
+    min: Option<i128>,
+    max: Option<i128>,
+    extensible: bool,
+) -> Result<i128, Error> {
+    let extended = extensible && bits.read_bit()?;
+    match (min, max) {
+        (Some(min), Some(max)) if !extended => bits.read_constrained_whole_number_i128(min, max),
+        (Some(min), None) if !extended => bits.read_semi_constrained_whole_number_i128(min),
+        _ => bits.read_unconstrained_whole_number_i128(),
+    }
+}
+
+/// An [`UperReader`] limited to a window of bits, see [`UperReader::scoped`]
+pub struct ScopedUperReader<'a, B: ScopedBitRead> {
+    reader: &'a mut UperReader<B>,
+    /// The position after the window
+    end: usize,
+    /// The length of the underlying reader
+    len: usize,
+    /// The scope of the underlying reader, which does not apply to the window
+    scope: Option<Scope>,
+}
+
+impl<B: ScopedBitRead> Deref for ScopedUperReader<'_, B> {
+    type Target = UperReader<B>;
+
+    #[inline]
+    fn deref(&self) -> &Self::Target {
+        self.reader
+    }
+}
+
+impl<B: ScopedBitRead> DerefMut for ScopedUperReader<'_, B> {
+    #[inline]
+    fn deref_mut(&mut self) -> &mut Self::Target {
+        self.reader
+    }
+}
+
+impl<B: ScopedBitRead> Drop for ScopedUperReader<'_, B> {
+    fn drop(&mut self) {
+        self.reader.bits.set_len(self.len);
+        self.reader.bits.set_pos(self.end);
+        self.reader.scope = self.scope.take();
+    }
+}
+
+/// A read position of an [`UperReader`], see [`Rewind`]
+#[derive(Debug, Clone)]
+pub struct UperCheckpoint {
+    position: usize,
+    scope: Option<Scope>,
+    #[cfg(feature = "descriptive-deserialize-errors")]
+    scope_description_len: usize,
+}
+
+impl<B: ScopedBitRead> Rewind for UperReader<B> {
+    type Checkpoint = UperCheckpoint;
+
+    fn checkpoint(&self) -> Self::Checkpoint {
+        UperCheckpoint {
+            position: self.bits.pos(),
+            scope: self.scope.clone(),
+            #[cfg(feature = "descriptive-deserialize-errors")]
+            scope_description_len: self.scope_description.len(),
+        }
+    }
+
+    fn restore(&mut self, checkpoint: Self::Checkpoint) {
+        self.bits.set_pos(checkpoint.position);
+        self.scope = checkpoint.scope;
+        #[cfg(feature = "descriptive-deserialize-errors")]
+        self.scope_description
+            .truncate(checkpoint.scope_description_len);
+    }
+}
+
 impl<B: ScopedBitRead> Reader for UperReader<B> {
     type Error = Error;
 
@@ -976,8 +1288,9 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
                         name: C::NAME,
                         bit_pos,
                         opt_bit_field: Some(range),
-                        calls_until_ext_bitfield: (extension_after + 1) as usize,
-                        number_of_ext_fields: (C::FIELD_COUNT - (extension_after + 1)) as usize,
+                        calls_until_ext_bitfield: std_fields::<C>(extension_after),
+                        number_of_ext_fields: C::FIELD_COUNT as usize
+                            - std_fields::<C>(extension_after),
                     },
                     f,
                 )
@@ -1154,6 +1467,25 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
 
         let _ = self.read_bit_field_entry(false)?;
         self.with_buffer(|r| {
+            if T::WIDE {
+                let result = read_wide_whole_number(
+                    &mut r.bits,
+                    C::MIN_T.map(T::to_i128),
+                    C::MAX_T.map(T::to_i128),
+                    C::EXTENSIBLE,
+                );
+
+                #[cfg(feature = "descriptive-deserialize-errors")]
+                r.scope_description.push(ScopeDescription::Result(
+                    result
+                        .as_ref()
+                        .map(ToString::to_string)
+                        .map_err(|e| e.clone()),
+                ));
+
+                return result.map(T::from_i128);
+            }
+
             let unconstrained = if C::EXTENSIBLE {
                 r.bits.read_bit()?
             } else {
@@ -1218,12 +1550,10 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
                 r.read_length_determinant(C::MIN, C::MAX)?
             };
 
-            let mut buffer = vec![0u8; len as usize];
-            for i in 0..len as usize {
-                r.bits.read_bits_with_offset(&mut buffer[i..i + 1], 1)?;
-            }
-
-            String::from_utf8(buffer).map_err(|e| ErrorKind::FromUtf8Error(e).into())
+            r.bits.read_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::IA5_STRING_CHARACTERS),
+                len,
+            )
         });
 
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1248,16 +1578,10 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
                 r.read_length_determinant(C::MIN, C::MAX)?
             };
 
-            let mut buffer = vec![0u8; len as usize];
-            for i in 0..len as usize {
-                r.bits.read_bits_with_offset(&mut buffer[i..i + 1], 4)?;
-                match buffer[i] {
-                    0_u8 => buffer[i] = 32_u8,
-                    c => buffer[i] = 32_u8 + 15 + c,
-                }
-            }
-
-            String::from_utf8(buffer).map_err(|e| ErrorKind::FromUtf8Error(e).into())
+            r.bits.read_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::NUMERIC_STRING_CHARACTERS),
+                len,
+            )
         });
 
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1284,12 +1608,10 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
                 r.read_length_determinant(C::MIN, C::MAX)?
             };
 
-            let mut buffer = vec![0u8; len as usize];
-            buffer
-                .chunks_exact_mut(1)
-                .try_for_each(|chunk| r.bits.read_bits_with_offset(chunk, 1))?;
-
-            String::from_utf8(buffer).map_err(|e| ErrorKind::FromUtf8Error(e).into())
+            r.bits.read_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::PRINTABLE_STRING_CHARACTERS),
+                len,
+            )
         });
 
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1314,12 +1636,10 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
                 r.read_length_determinant(C::MIN, C::MAX)?
             };
 
-            let mut buffer = vec![0u8; len as usize];
-            buffer
-                .chunks_exact_mut(1)
-                .try_for_each(|chunk| r.bits.read_bits_with_offset(chunk, 1))?;
-
-            String::from_utf8(buffer).map_err(|e| ErrorKind::FromUtf8Error(e).into())
+            r.bits.read_known_multiplier_characters(
+                const_unwrap_or!(C::PERMITTED_ALPHABET, Charset::VISIBLE_STRING_CHARACTERS),
+                len,
+            )
         });
 
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -1355,6 +1675,14 @@ impl<B: ScopedBitRead> Reader for UperReader<B> {
         result
     }
 
+    #[cfg(feature = "uuid")]
+    #[inline]
+    fn read_uuid<C: octetstring::Constraint>(&mut self) -> Result<uuid::Uuid, Self::Error> {
+        let bytes = self.read_octet_string::<C>()?;
+        uuid::Uuid::from_slice(&bytes)
+            .map_err(|_| ErrorKind::SizeNotInRange(bytes.len() as u64, 16, 16).into())
+    }
+
     #[inline]
     fn read_bit_string<C: bitstring::Constraint>(&mut self) -> Result<(Vec<u8>, u64), Self::Error> {
         #[cfg(feature = "descriptive-deserialize-errors")]
@@ -0,0 +1,230 @@
+use crate::descriptor::sequenceof::{self, NoConstraint};
+use crate::descriptor::{Readable, Reader};
+use crate::protocol::per::err::{Error, ErrorKind};
+use crate::protocol::per::unaligned::{BitRead, BYTE_LEN};
+use crate::protocol::per::PackedRead;
+use crate::rw::{Bits, ScopedBitRead, UperReader};
+use std::io::Read;
+use std::marker::PhantomData;
+
+/// The number of bytes [`UperSequenceOfReader`] reads from its source at once by default
+pub const DEFAULT_CHUNK_LEN: usize = 8 * 1024;
+
+#[derive(Debug)]
+pub enum UperStreamError {
+    Io(std::io::Error),
+    Uper(Error),
+}
+
+impl From<std::io::Error> for UperStreamError {
+    fn from(e: std::io::Error) -> Self {
+        UperStreamError::Io(e)
+    }
+}
+
+impl From<Error> for UperStreamError {
+    fn from(e: Error) -> Self {
+        UperStreamError::Uper(e)
+    }
+}
+
+impl std::fmt::Display for UperStreamError {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            UperStreamError::Io(e) => e.fmt(f),
+            UperStreamError::Uper(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for UperStreamError {}
+
+/// Decodes the elements of a top-level `SEQUENCE OF T` in UPER one at a time from any [`Read`],
+/// so that recordings of any size are processed with constant memory: only the bytes of the
+/// element being decoded are buffered. The size constraint of the `SEQUENCE OF` is given by `C`.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+/// use asn1rs::rw::UperSequenceOfReader;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Frame {
+///     #[asn(integer(0..65535))]
+///     pub id: u16,
+///     #[asn(octet_string)]
+///     pub payload: Vec<u8>,
+/// }
+///
+/// #[asn(transparent)]
+/// #[derive(Debug, PartialEq)]
+/// pub struct Recording(#[asn(sequence_of(complex(Frame, tag(UNIVERSAL(16)))))] Vec<Frame>);
+///
+/// let frames = (0..100).map(|id| Frame { id, payload: vec![0xAB; 3] }).collect();
+/// let mut writer = UperWriter::default();
+/// writer.write(&Recording(frames)).unwrap();
+/// let recording = writer.into_bytes_vec();
+///
+/// let mut count = 0;
+/// for frame in UperSequenceOfReader::<_, Frame>::new(&recording[..]) {
+///     assert_eq!(count, frame.unwrap().id);
+///     count += 1;
+/// }
+/// assert_eq!(100, count);
+/// ```
+pub struct UperSequenceOfReader<R: Read, T: Readable, C: sequenceof::Constraint = NoConstraint> {
+    read: R,
+    buffer: Vec<u8>,
+    /// The bit position of the next element within the buffer
+    pos: usize,
+    /// The number of elements not yet decoded, unknown until the length determinant is read
+    remaining: Option<u64>,
+    end_of_input: bool,
+    failed: bool,
+    chunk_len: usize,
+    _marker: PhantomData<fn() -> (T, C)>,
+}
+
+impl<R: Read, T: Readable, C: sequenceof::Constraint> UperSequenceOfReader<R, T, C> {
+    pub fn new(read: R) -> Self {
+        Self {
+            read,
+            buffer: Vec::new(),
+            pos: 0,
+            remaining: None,
+            end_of_input: false,
+            failed: false,
+            chunk_len: DEFAULT_CHUNK_LEN,
+            _marker: PhantomData,
+        }
+    }
+
+    /// Reads the given number of bytes - at least one - from the source at once
+    #[inline]
+    pub fn with_chunk_len(mut self, chunk_len: usize) -> Self {
+        self.chunk_len = chunk_len.max(1);
+        self
+    }
+
+    /// The number of elements that have not been decoded yet, once the length determinant has
+    /// been read by the first call of [`Iterator::next`]
+    #[inline]
+    pub fn remaining(&self) -> Option<u64> {
+        self.remaining
+    }
+
+    fn next_element(&mut self) -> Result<Option<T>, UperStreamError> {
+        let remaining = match self.remaining {
+            Some(remaining) => remaining,
+            None => {
+                let len = self.read_len()?;
+                *self.remaining.insert(len)
+            }
+        };
+        if remaining == 0 {
+            return Ok(None);
+        }
+        let value = self.read_element()?;
+        self.remaining = Some(remaining - 1);
+        Ok(Some(value))
+    }
+
+    fn read_len(&mut self) -> Result<u64, UperStreamError> {
+        self.decode(|mut bits| {
+            let len = if C::EXTENSIBLE && bits.read_bit()? {
+                bits.read_length_determinant(None, None)?
+            } else {
+                bits.read_length_determinant(C::MIN, C::MAX)?
+            };
+            Ok((len, bits.pos()))
+        })
+    }
+
+    fn read_element(&mut self) -> Result<T, UperStreamError> {
+        self.decode(|bits| {
+            let mut reader = UperReader::from(bits);
+            let value = reader.read::<T>()?;
+            Ok((value, reader.into_bits().pos()))
+        })
+    }
+
+    /// Decodes from the current position, reading more of the source and starting over while
+    /// the buffered bytes end within the value
+    fn decode<V>(
+        &mut self,
+        f: impl Fn(Bits<'_>) -> Result<(V, usize), Error>,
+    ) -> Result<V, UperStreamError> {
+        loop {
+            let mut bits = Bits::from((&self.buffer[..], self.buffer.len() * BYTE_LEN));
+            bits.set_pos(self.pos);
+            match f(bits) {
+                Ok((value, pos)) => {
+                    self.pos = pos;
+                    self.discard_consumed_bytes();
+                    return Ok(value);
+                }
+                Err(e) if !self.end_of_input && is_end_of_data(&e) => self.fill()?,
+                Err(e) => return Err(e.into()),
+            }
+        }
+    }
+
+    /// Appends at least as many bytes as are buffered for the current value, so that a value
+    /// spanning many chunks is not decoded again for each of them
+    fn fill(&mut self) -> Result<(), UperStreamError> {
+        let len = self.buffer.len();
+        let additional = self.chunk_len.max(len - self.pos / BYTE_LEN);
+        self.buffer.resize(len + additional, 0);
+        let mut filled = len;
+        while filled < self.buffer.len() {
+            match self.read.read(&mut self.buffer[filled..]) {
+                Ok(0) => {
+                    self.end_of_input = true;
+                    break;
+                }
+                Ok(read) => filled += read,
+                Err(e) if e.kind() == std::io::ErrorKind::Interrupted => {}
+                Err(e) => {
+                    self.buffer.truncate(filled);
+                    return Err(e.into());
+                }
+            }
+        }
+        self.buffer.truncate(filled);
+        Ok(())
+    }
+
+    fn discard_consumed_bytes(&mut self) {
+        let consumed = self.pos / BYTE_LEN;
+        // moving the remaining bytes for every small element would be wasteful
+        if consumed >= self.chunk_len || consumed == self.buffer.len() {
+            self.buffer.drain(..consumed);
+            self.pos -= consumed * BYTE_LEN;
+        }
+    }
+}
+
+impl<R: Read, T: Readable, C: sequenceof::Constraint> Iterator for UperSequenceOfReader<R, T, C> {
+    type Item = Result<T, UperStreamError>;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        if self.failed {
+            return None;
+        }
+        let result = self.next_element().transpose();
+        self.failed = matches!(result, Some(Err(_)));
+        result
+    }
+}
+
+impl<R: Read, T: Readable, C: sequenceof::Constraint> std::iter::FusedIterator
+    for UperSequenceOfReader<R, T, C>
+{
+}
+
+fn is_end_of_data(error: &Error) -> bool {
+    matches!(
+        error.kind(),
+        ErrorKind::EndOfStream | ErrorKind::InsufficientDataInSourceBuffer(_)
+    )
+}
@@ -0,0 +1,382 @@
+use crate::descriptor::*;
+use crate::rw::visitor::{path_string, PathTracker, Scope};
+use crate::rw::Segment;
+use asn1rs_model::asn::Charset;
+use core::borrow::Borrow;
+use core::fmt::{Display, Formatter};
+
+/// A value that violates the constraints of its definition, as found by [`Validate::validate`]
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct ConstraintViolation {
+    /// The path to the offending value, empty for the root value
+    pub path: Vec<Segment>,
+    pub kind: Violation,
+}
+
+impl ConstraintViolation {
+    /// The path joined by dots, such as `person.addresses.0.street`
+    pub fn path_string(&self) -> String {
+        path_string(&self.path)
+    }
+}
+
+impl Display for ConstraintViolation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        if self.path.is_empty() {
+            write!(f, "{}", self.kind)
+        } else {
+            write!(f, "{}: {}", self.path_string(), self.kind)
+        }
+    }
+}
+
+impl std::error::Error for ConstraintViolation {}
+
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub enum Violation {
+    /// The integer is outside of its (not extensible) range
+    ValueNotInRange {
+        value: i128,
+        min: Option<i128>,
+        max: Option<i128>,
+    },
+    /// The number of characters, bytes, bits or items is outside of the (not extensible) `SIZE`
+    /// constraint
+    SizeNotInRange {
+        size: u64,
+        min: Option<u64>,
+        max: Option<u64>,
+    },
+    /// The character at the given index is not part of the character set of the string type or
+    /// its permitted alphabet
+    CharacterNotPermitted { character: char, index: usize },
+}
+
+impl Display for Violation {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        fn bound<T: Display>(bound: &Option<T>) -> String {
+            bound.as_ref().map(ToString::to_string).unwrap_or_default()
+        }
+        match self {
+            Violation::ValueNotInRange { value, min, max } => write!(
+                f,
+                "The value {} is not within {}..{}",
+                value,
+                bound(min),
+                bound(max)
+            ),
+            Violation::SizeNotInRange { size, min, max } => write!(
+                f,
+                "The size {} is not within {}..{}",
+                size,
+                bound(min),
+                bound(max)
+            ),
+            Violation::CharacterNotPermitted { character, index } => write!(
+                f,
+                "The character {:?} at index {} is not permitted",
+                character, index
+            ),
+        }
+    }
+}
+
+/// Checks the values of any [`Writable`] - and therefore every generated type - against the
+/// ranges, `SIZE` constraints and permitted alphabets of their definition, without encoding
+/// them. Values outside of an extensible constraint are valid.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[asn(sequence)]
+/// #[derive(Debug, Default, PartialEq)]
+/// pub struct Reading {
+///     #[asn(integer(-40..125))]
+///     pub celsius: i8,
+/// }
+///
+/// assert!(Reading { celsius: 21 }.validate().is_ok());
+/// let violation = Reading { celsius: -50 }.validate().unwrap_err();
+/// assert_eq!("celsius: The value -50 is not within -40..125", violation.to_string());
+/// ```
+pub trait Validate {
+    fn validate(&self) -> Result<(), ConstraintViolation>;
+}
+
+impl<T: Writable> Validate for T {
+    fn validate(&self) -> Result<(), ConstraintViolation> {
+        self.write(&mut ValidatingWriter::default())
+    }
+}
+
+/// The [`Writer`] behind [`Validate::validate`], which fails on the first constraint violation
+#[derive(Default)]
+pub struct ValidatingWriter {
+    tracker: PathTracker,
+}
+
+impl ValidatingWriter {
+    /// Checks a primitive value, whose violation - if any - is reported with its path
+    fn check(&mut self, violation: Option<Violation>) -> Result<(), ConstraintViolation> {
+        let entered = self.tracker.enter();
+        let result = match violation {
+            None => Ok(()),
+            Some(kind) => Err(ConstraintViolation {
+                path: self.tracker.path().to_vec(),
+                kind,
+            }),
+        };
+        self.tracker.leave(entered);
+        result
+    }
+
+    fn check_constructed<F: FnOnce(&mut Self) -> Result<(), ConstraintViolation>>(
+        &mut self,
+        violation: Option<Violation>,
+        scope: Scope,
+        f: F,
+    ) -> Result<(), ConstraintViolation> {
+        let entered = self.tracker.enter();
+        if let Some(kind) = violation {
+            return Err(ConstraintViolation {
+                path: self.tracker.path().to_vec(),
+                kind,
+            });
+        }
+        self.tracker.push_scope(scope);
+        f(self)?;
+        self.tracker.pop_scope();
+        self.tracker.leave(entered);
+        Ok(())
+    }
+
+    fn size_violation(
+        extensible: bool,
+        min: Option<u64>,
+        max: Option<u64>,
+        size: u64,
+    ) -> Option<Violation> {
+        let in_range = min.is_none_or(|min| size >= min) && max.is_none_or(|max| size <= max);
+        if extensible || in_range {
+            None
+        } else {
+            Some(Violation::SizeNotInRange { size, min, max })
+        }
+    }
+
+    fn string_violation(
+        extensible: bool,
+        min: Option<u64>,
+        max: Option<u64>,
+        charset: Option<Charset>,
+        alphabet: Option<&'static str>,
+        value: &str,
+    ) -> Option<Violation> {
+        value
+            .chars()
+            .enumerate()
+            .find(|(_, character)| {
+                !charset.is_none_or(|charset| charset.is_valid(*character))
+                    || !alphabet.is_none_or(|alphabet| alphabet.contains(*character))
+            })
+            .map(|(index, character)| Violation::CharacterNotPermitted { character, index })
+            .or_else(|| Self::size_violation(extensible, min, max, value.chars().count() as u64))
+    }
+}
+
+impl Writer for ValidatingWriter {
+    type Error = ConstraintViolation;
+
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = Scope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+        };
+        self.check_constructed(None, scope, f)
+    }
+
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
+        &mut self,
+        len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
+        let violation = Self::size_violation(C::EXTENSIBLE, C::MIN, C::MAX, len as u64);
+        self.check_constructed(violation, Scope::Items { index: 0 }, |w| {
+            iter.into_iter()
+                .try_for_each(|value| T::write_value(w, value.borrow()))
+        })
+    }
+
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_sequence::<C, F>(f)
+    }
+
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        let violation = Self::size_violation(C::EXTENSIBLE, C::MIN, C::MAX, slice.len() as u64);
+        self.check_constructed(violation, Scope::Items { index: 0 }, |w| {
+            slice.iter().try_for_each(|value| T::write_value(w, value))
+        })
+    }
+
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        _enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        self.check(None)
+    }
+
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        let index = choice.to_choice_index();
+        let segment = C::VARIANT_NAMES
+            .get(index as usize)
+            .map(|name| Segment::Identifier(name))
+            .unwrap_or(Segment::Index(index as usize));
+        self.check_constructed(None, Scope::Alternative(segment), |w| {
+            choice.write_content(w)
+        })
+    }
+
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.tracker.skip_value();
+                Ok(())
+            }
+        }
+    }
+
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        T::write_value(self, value)
+    }
+
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let value = value.to_i128();
+        let min = C::MIN_T.map(T::to_i128).or(C::MIN.map(i128::from));
+        let max = C::MAX_T.map(T::to_i128).or(C::MAX.map(i128::from));
+        let in_range = min.is_none_or(|min| value >= min) && max.is_none_or(|max| value <= max);
+        self.check(if C::EXTENSIBLE || in_range {
+            None
+        } else {
+            Some(Violation::ValueNotInRange { value, min, max })
+        })
+    }
+
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.check(Self::string_violation(
+            C::EXTENSIBLE,
+            C::MIN,
+            C::MAX,
+            None,
+            None,
+            value,
+        ))
+    }
+
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.check(Self::string_violation(
+            C::EXTENSIBLE,
+            C::MIN,
+            C::MAX,
+            Some(Charset::Ia5),
+            C::PERMITTED_ALPHABET,
+            value,
+        ))
+    }
+
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.check(Self::string_violation(
+            C::EXTENSIBLE,
+            C::MIN,
+            C::MAX,
+            Some(Charset::Numeric),
+            C::PERMITTED_ALPHABET,
+            value,
+        ))
+    }
+
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.check(Self::string_violation(
+            C::EXTENSIBLE,
+            C::MIN,
+            C::MAX,
+            Some(Charset::Visible),
+            C::PERMITTED_ALPHABET,
+            value,
+        ))
+    }
+
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.check(Self::string_violation(
+            C::EXTENSIBLE,
+            C::MIN,
+            C::MAX,
+            Some(Charset::Printable),
+            C::PERMITTED_ALPHABET,
+            value,
+        ))
+    }
+
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        let size = value.len() as u64;
+        self.check(Self::size_violation(C::EXTENSIBLE, C::MIN, C::MAX, size))
+    }
+
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        let bit_len = if C::NAMED_BITS {
+            bitstring::canonical_bit_len(value, bit_len, C::MIN)
+        } else {
+            bit_len
+        };
+        self.check(Self::size_violation(C::EXTENSIBLE, C::MIN, C::MAX, bit_len))
+    }
+
+    fn write_boolean<C: boolean::Constraint>(&mut self, _value: bool) -> Result<(), Self::Error> {
+        self.check(None)
+    }
+
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.check(None)
+    }
+}
@@ -0,0 +1,349 @@
+use crate::descriptor::*;
+use core::borrow::Borrow;
+use core::fmt::{Display, Formatter};
+
+/// The textual representation produced by the [`ValueNotationWriter`]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Eq)]
+pub enum Notation {
+    /// ASN.1 value notation, ITU-T X.680 | ISO/IEC 8824-1, printed on multiple indented lines
+    #[default]
+    Asn1,
+    /// Generic String Encoding Rules, RFC 3641, printed on a single line
+    Gser,
+}
+
+enum Scope {
+    Fields {
+        names: &'static [&'static str],
+        index: usize,
+        written: usize,
+    },
+    Items {
+        written: usize,
+    },
+    Alternative,
+}
+
+/// Renders [`Writable`] values as ASN.1 value notation or GSER. The identifiers are taken from
+/// the `FIELD_NAMES` and `VARIANT_NAMES` of the descriptor constraints.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// let mut writer = ValueNotationWriter::new(Notation::Gser);
+/// writer.write_utf8string::<asn1rs::descriptor::utf8string::NoConstraint>("say \"hi\"").unwrap();
+/// assert_eq!(r#""say ""hi""""#, writer.as_str());
+/// ```
+#[derive(Default)]
+pub struct ValueNotationWriter {
+    notation: Notation,
+    output: String,
+    scopes: Vec<Scope>,
+}
+
+impl ValueNotationWriter {
+    pub fn new(notation: Notation) -> Self {
+        Self {
+            notation,
+            ..Default::default()
+        }
+    }
+
+    pub fn as_str(&self) -> &str {
+        &self.output
+    }
+
+    pub fn into_string(self) -> String {
+        self.output
+    }
+
+    fn new_line(&mut self) {
+        match self.notation {
+            Notation::Asn1 => {
+                self.output.push('\n');
+                for scope in &self.scopes {
+                    if !matches!(scope, Scope::Alternative) {
+                        self.output.push_str("  ");
+                    }
+                }
+            }
+            Notation::Gser => self.output.push(' '),
+        }
+    }
+
+    /// Writes the separator and the identifier of the field, if the value is part of a
+    /// constructed value
+    fn begin_value(&mut self) {
+        let (written, name) = match self.scopes.last_mut() {
+            Some(Scope::Fields {
+                names,
+                index,
+                written,
+            }) => {
+                *index += 1;
+                *written += 1;
+                (*written, names.get(*index - 1).copied())
+            }
+            Some(Scope::Items { written }) => {
+                *written += 1;
+                (*written, None)
+            }
+            Some(Scope::Alternative) | None => return,
+        };
+        if written > 1 {
+            self.output.push(',');
+        }
+        self.new_line();
+        if let Some(name) = name {
+            self.output.push_str(name);
+            self.output.push(' ');
+        }
+    }
+
+    /// Skips the field of an absent OPTIONAL value
+    fn skip_value(&mut self) {
+        if let Some(Scope::Fields { index, .. }) = self.scopes.last_mut() {
+            *index += 1;
+        }
+    }
+
+    fn write_constructed<F: FnOnce(&mut Self) -> Result<(), core::convert::Infallible>>(
+        &mut self,
+        scope: Scope,
+        f: F,
+    ) -> Result<(), core::convert::Infallible> {
+        self.begin_value();
+        self.output.push('{');
+        self.scopes.push(scope);
+        f(self)?;
+        let written = match self.scopes.pop() {
+            Some(Scope::Fields { written, .. }) | Some(Scope::Items { written }) => written,
+            _ => 0,
+        };
+        if written > 0 {
+            self.new_line();
+        }
+        self.output.push('}');
+        Ok(())
+    }
+
+    fn write_identifier(&mut self, names: &'static [&'static str], index: u64) {
+        match names.get(index as usize) {
+            Some(name) => self.output.push_str(name),
+            None => self.output.push_str(&index.to_string()),
+        }
+    }
+
+    fn write_quoted(&mut self, value: &str) -> Result<(), core::convert::Infallible> {
+        self.begin_value();
+        self.output.push('"');
+        self.output.push_str(&value.replace('"', "\"\""));
+        self.output.push('"');
+        Ok(())
+    }
+}
+
+impl Writer for ValueNotationWriter {
+    type Error = core::convert::Infallible;
+
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = Scope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+            written: 0,
+        };
+        self.write_constructed(scope, f)
+    }
+
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
+        &mut self,
+        _len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
+        self.write_constructed(Scope::Items { written: 0 }, |w| {
+            iter.into_iter()
+                .try_for_each(|value| T::write_value(w, value.borrow()))
+        })
+    }
+
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        self.write_sequence::<C, F>(f)
+    }
+
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[<T as WritableType>::Type],
+    ) -> Result<(), Self::Error> {
+        self.write_constructed(Scope::Items { written: 0 }, |w| {
+            slice.iter().try_for_each(|value| T::write_value(w, value))
+        })
+    }
+
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.write_identifier(C::VARIANT_NAMES, enumerated.to_choice_index());
+        Ok(())
+    }
+
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.write_identifier(C::VARIANT_NAMES, choice.to_choice_index());
+        self.output.push_str(match self.notation {
+            Notation::Asn1 => " : ",
+            Notation::Gser => ":",
+        });
+        self.scopes.push(Scope::Alternative);
+        let result = choice.write_content(self);
+        self.scopes.pop();
+        result
+    }
+
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.skip_value();
+                Ok(())
+            }
+        }
+    }
+
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        T::write_value(self, value)
+    }
+
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str(&value.to_i128().to_string());
+        Ok(())
+    }
+
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_quoted(value)
+    }
+
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_quoted(value)
+    }
+
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_quoted(value)
+    }
+
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_quoted(value)
+    }
+
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        self.write_quoted(value)
+    }
+
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push('\'');
+        for byte in value {
+            self.output.push_str(&format!("{:02X}", byte));
+        }
+        self.output.push_str("'H");
+        Ok(())
+    }
+
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push('\'');
+        for bit in 0..bit_len as usize {
+            let set = value
+                .get(bit / 8)
+                .is_some_and(|byte| byte & (0x80 >> (bit % 8)) != 0);
+            self.output.push(if set { '1' } else { '0' });
+        }
+        self.output.push_str("'B");
+        Ok(())
+    }
+
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str(if value { "TRUE" } else { "FALSE" });
+        Ok(())
+    }
+
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.begin_value();
+        self.output.push_str("NULL");
+        Ok(())
+    }
+}
+
+/// Formats the [`Writable`] value with a [`ValueNotationWriter`], for example for log output:
+/// `println!("{}", ValueNotation::gser(&message))`
+pub struct ValueNotation<'a, T: Writable> {
+    value: &'a T,
+    notation: Notation,
+}
+
+impl<'a, T: Writable> ValueNotation<'a, T> {
+    pub const fn new(value: &'a T) -> Self {
+        Self {
+            value,
+            notation: Notation::Asn1,
+        }
+    }
+
+    pub const fn gser(value: &'a T) -> Self {
+        Self {
+            value,
+            notation: Notation::Gser,
+        }
+    }
+}
+
+impl<T: Writable> Display for ValueNotation<'_, T> {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        let mut writer = ValueNotationWriter::new(self.notation);
+        match self.value.write(&mut writer) {
+            Ok(()) => f.write_str(writer.as_str()),
+            Err(infallible) => match infallible {},
+        }
+    }
+}
@@ -0,0 +1,465 @@
+use crate::descriptor::*;
+use asn1rs_model::asn::Tag;
+use core::borrow::Borrow;
+use core::fmt::{Display, Formatter};
+
+/// The ASN.1 type of a value reported to an [`AsnVisitor`]
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum ValueKind {
+    Sequence,
+    SequenceOf,
+    Set,
+    SetOf,
+    Enumerated,
+    Choice,
+    Integer,
+    Utf8String,
+    Ia5String,
+    NumericString,
+    VisibleString,
+    PrintableString,
+    OctetString,
+    BitString,
+    Boolean,
+    Null,
+}
+
+impl ValueKind {
+    /// Whether the value contains further values, which are visited after it
+    pub const fn is_constructed(self) -> bool {
+        matches!(
+            self,
+            Self::Sequence | Self::SequenceOf | Self::Set | Self::SetOf | Self::Choice
+        )
+    }
+}
+
+/// The content of a value reported to an [`AsnVisitor`]. The members of constructed values
+/// are visited separately.
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub enum VisitedValue<'a> {
+    /// A SEQUENCE or SET with the number of fields of its definition, or a SEQUENCE OF or SET
+    /// OF with the number of its items
+    Constructed(usize),
+    /// The variant of an ENUMERATED or the alternative of a CHOICE, with its identifier if the
+    /// constraint provides it
+    Identifier {
+        index: u64,
+        name: Option<&'static str>,
+    },
+    Integer(i128),
+    String(&'a str),
+    Bytes(&'a [u8]),
+    Bits {
+        bytes: &'a [u8],
+        bit_len: u64,
+    },
+    Boolean(bool),
+    Null,
+}
+
+/// An element of the path from the visited root value to the current value
+#[derive(Debug, Copy, Clone, PartialEq, Eq, Hash)]
+pub enum Segment {
+    /// The identifier of a field or of a CHOICE alternative
+    Identifier(&'static str),
+    /// The position of an item of a SEQUENCE OF or SET OF, or of a field without a known
+    /// identifier
+    Index(usize),
+}
+
+impl Display for Segment {
+    fn fmt(&self, f: &mut Formatter<'_>) -> core::fmt::Result {
+        match self {
+            Segment::Identifier(name) => f.write_str(name),
+            Segment::Index(index) => write!(f, "{}", index),
+        }
+    }
+}
+
+/// Describes the location and type of a visited value
+#[derive(Debug, Copy, Clone, PartialEq, Eq)]
+pub struct Field<'a> {
+    /// The path to the value, empty for the root value
+    pub path: &'a [Segment],
+    pub tag: Tag,
+    pub kind: ValueKind,
+}
+
+impl Field<'_> {
+    /// The identifier of the field or alternative, if the value is not an item or the root
+    pub fn name(&self) -> Option<&'static str> {
+        match self.path.last() {
+            Some(Segment::Identifier(name)) => Some(name),
+            _ => None,
+        }
+    }
+
+    /// The path joined by dots, such as `person.addresses.0.street`
+    pub fn path_string(&self) -> String {
+        path_string(self.path)
+    }
+}
+
+pub(crate) fn path_string(path: &[Segment]) -> String {
+    path.iter()
+        .map(ToString::to_string)
+        .collect::<Vec<_>>()
+        .join(".")
+}
+
+/// Receives every present value of a [`Visitable`] in the order of its definition, so that
+/// generic tooling - extracting metrics, redacting values, mapping to a database - can be
+/// written once instead of for every message type.
+///
+/// ```rust
+/// use asn1rs::prelude::*;
+///
+/// #[derive(Default)]
+/// struct Strings(Vec<String>);
+///
+/// impl AsnVisitor for Strings {
+///     fn visit(&mut self, field: &Field<'_>, value: VisitedValue<'_>) {
+///         if let VisitedValue::String(value) = value {
+///             self.0.push(format!("{}={}", field.path_string(), value));
+///         }
+///     }
+/// }
+///
+/// let mut strings = Strings::default();
+/// VisitingWriter::new(&mut strings)
+///     .write_utf8string::<asn1rs::descriptor::utf8string::NoConstraint>("hi")
+///     .unwrap();
+/// assert_eq!(vec!["=hi".to_string()], strings.0);
+/// ```
+pub trait AsnVisitor {
+    /// Called for each value, for constructed values before their members
+    fn visit(&mut self, field: &Field<'_>, value: VisitedValue<'_>);
+
+    /// Called after all members of a constructed value were visited
+    #[inline]
+    fn leave(&mut self, _field: &Field<'_>) {}
+}
+
+/// The acceptor for [`AsnVisitor`]s, available on every [`Writable`] and therefore on every
+/// generated type
+pub trait Visitable {
+    fn accept<V: AsnVisitor>(&self, visitor: &mut V);
+}
+
+impl<T: Writable> Visitable for T {
+    fn accept<V: AsnVisitor>(&self, visitor: &mut V) {
+        match self.write(&mut VisitingWriter::new(visitor)) {
+            Ok(()) => {}
+            Err(infallible) => match infallible {},
+        }
+    }
+}
+
+pub(crate) enum Scope {
+    Fields {
+        names: &'static [&'static str],
+        index: usize,
+    },
+    Items {
+        index: usize,
+    },
+    Alternative(Segment),
+}
+
+/// Tracks the [`Segment`]s of the values written to a [`Writer`], for the writers reporting
+/// where a value is located
+#[derive(Default)]
+pub(crate) struct PathTracker {
+    path: Vec<Segment>,
+    scopes: Vec<Scope>,
+}
+
+impl PathTracker {
+    pub(crate) fn path(&self) -> &[Segment] {
+        &self.path
+    }
+
+    /// Appends the segment of the next value within the current constructed value, if any, and
+    /// returns whether it did so
+    pub(crate) fn enter(&mut self) -> bool {
+        let segment = match self.scopes.last_mut() {
+            Some(Scope::Fields { names, index }) => {
+                *index += 1;
+                names
+                    .get(*index - 1)
+                    .map(|name| Segment::Identifier(name))
+                    .unwrap_or(Segment::Index(*index - 1))
+            }
+            Some(Scope::Items { index }) => {
+                *index += 1;
+                Segment::Index(*index - 1)
+            }
+            Some(Scope::Alternative(segment)) => *segment,
+            None => return false,
+        };
+        self.path.push(segment);
+        true
+    }
+
+    /// Removes the segment appended by [`PathTracker::enter`]
+    pub(crate) fn leave(&mut self, entered: bool) {
+        if entered {
+            self.path.pop();
+        }
+    }
+
+    pub(crate) fn push_scope(&mut self, scope: Scope) {
+        self.scopes.push(scope);
+    }
+
+    pub(crate) fn pop_scope(&mut self) {
+        self.scopes.pop();
+    }
+
+    /// Skips the field of an absent OPTIONAL value
+    pub(crate) fn skip_value(&mut self) {
+        if let Some(Scope::Fields { index, .. }) = self.scopes.last_mut() {
+            *index += 1;
+        }
+    }
+}
+
+/// Adapts an [`AsnVisitor`] to the [`Writer`] interface, see [`Visitable::accept`]
+pub struct VisitingWriter<'v, V: AsnVisitor> {
+    visitor: &'v mut V,
+    tracker: PathTracker,
+}
+
+impl<'v, V: AsnVisitor> VisitingWriter<'v, V> {
+    pub fn new(visitor: &'v mut V) -> Self {
+        Self {
+            visitor,
+            tracker: PathTracker::default(),
+        }
+    }
+
+    fn visit_primitive(&mut self, tag: Tag, kind: ValueKind, value: VisitedValue<'_>) {
+        let entered = self.tracker.enter();
+        let field = Field {
+            path: self.tracker.path(),
+            tag,
+            kind,
+        };
+        self.visitor.visit(&field, value);
+        self.tracker.leave(entered);
+    }
+
+    fn visit_constructed<F: FnOnce(&mut Self) -> Result<(), core::convert::Infallible>>(
+        &mut self,
+        tag: Tag,
+        kind: ValueKind,
+        value: VisitedValue<'_>,
+        scope: Scope,
+        f: F,
+    ) -> Result<(), core::convert::Infallible> {
+        let entered = self.tracker.enter();
+        self.visitor.visit(
+            &Field {
+                path: self.tracker.path(),
+                tag,
+                kind,
+            },
+            value,
+        );
+        self.tracker.push_scope(scope);
+        let result = f(self);
+        self.tracker.pop_scope();
+        self.visitor.leave(&Field {
+            path: self.tracker.path(),
+            tag,
+            kind,
+        });
+        self.tracker.leave(entered);
+        result
+    }
+}
+
+impl<V: AsnVisitor> Writer for VisitingWriter<'_, V> {
+    type Error = core::convert::Infallible;
+
+    fn write_sequence<C: sequence::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = Scope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+        };
+        let fields = VisitedValue::Constructed(C::FIELD_COUNT as usize);
+        self.visit_constructed(C::TAG, ValueKind::Sequence, fields, scope, f)
+    }
+
+    fn write_sequence_of_iter<C: sequenceof::Constraint, T: WritableType, I>(
+        &mut self,
+        len: usize,
+        iter: I,
+    ) -> Result<(), Self::Error>
+    where
+        I: IntoIterator,
+        I::Item: core::borrow::Borrow<T::Type>,
+    {
+        let items = VisitedValue::Constructed(len);
+        let scope = Scope::Items { index: 0 };
+        self.visit_constructed(C::TAG, ValueKind::SequenceOf, items, scope, |w| {
+            iter.into_iter()
+                .try_for_each(|value| T::write_value(w, value.borrow()))
+        })
+    }
+
+    fn write_set<C: set::Constraint, F: Fn(&mut Self) -> Result<(), Self::Error>>(
+        &mut self,
+        f: F,
+    ) -> Result<(), Self::Error> {
+        let scope = Scope::Fields {
+            names: C::FIELD_NAMES,
+            index: 0,
+        };
+        let fields = VisitedValue::Constructed(C::FIELD_COUNT as usize);
+        self.visit_constructed(C::TAG, ValueKind::Set, fields, scope, f)
+    }
+
+    fn write_set_of<C: setof::Constraint, T: WritableType>(
+        &mut self,
+        slice: &[T::Type],
+    ) -> Result<(), Self::Error> {
+        let items = VisitedValue::Constructed(slice.len());
+        let scope = Scope::Items { index: 0 };
+        self.visit_constructed(C::TAG, ValueKind::SetOf, items, scope, |w| {
+            slice.iter().try_for_each(|value| T::write_value(w, value))
+        })
+    }
+
+    fn write_enumerated<C: enumerated::Constraint>(
+        &mut self,
+        enumerated: &C,
+    ) -> Result<(), Self::Error> {
+        let index = enumerated.to_choice_index();
+        let name = C::VARIANT_NAMES.get(index as usize).copied();
+        let value = VisitedValue::Identifier { index, name };
+        self.visit_primitive(C::TAG, ValueKind::Enumerated, value);
+        Ok(())
+    }
+
+    fn write_choice<C: choice::Constraint>(&mut self, choice: &C) -> Result<(), Self::Error> {
+        let index = choice.to_choice_index();
+        let name = C::VARIANT_NAMES.get(index as usize).copied();
+        let value = VisitedValue::Identifier { index, name };
+        let scope = Scope::Alternative(
+            name.map(Segment::Identifier)
+                .unwrap_or(Segment::Index(index as usize)),
+        );
+        self.visit_constructed(C::TAG, ValueKind::Choice, value, scope, |w| {
+            choice.write_content(w)
+        })
+    }
+
+    fn write_opt<T: WritableType>(&mut self, value: Option<&T::Type>) -> Result<(), Self::Error> {
+        match value {
+            Some(value) => T::write_value(self, value),
+            None => {
+                self.tracker.skip_value();
+                Ok(())
+            }
+        }
+    }
+
+    fn write_default<C: default::Constraint<Owned = T::Type>, T: WritableType>(
+        &mut self,
+        value: &T::Type,
+    ) -> Result<(), Self::Error> {
+        T::write_value(self, value)
+    }
+
+    fn write_number<T: numbers::Number, C: numbers::Constraint<T>>(
+        &mut self,
+        value: T,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::Integer(value.to_i128());
+        self.visit_primitive(C::TAG, ValueKind::Integer, value);
+        Ok(())
+    }
+
+    fn write_utf8string<C: utf8string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::String(value);
+        self.visit_primitive(C::TAG, ValueKind::Utf8String, value);
+        Ok(())
+    }
+
+    fn write_ia5string<C: ia5string::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::String(value);
+        self.visit_primitive(C::TAG, ValueKind::Ia5String, value);
+        Ok(())
+    }
+
+    fn write_numeric_string<C: numericstring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::String(value);
+        self.visit_primitive(C::TAG, ValueKind::NumericString, value);
+        Ok(())
+    }
+
+    fn write_visible_string<C: visiblestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::String(value);
+        self.visit_primitive(C::TAG, ValueKind::VisibleString, value);
+        Ok(())
+    }
+
+    fn write_printable_string<C: printablestring::Constraint>(
+        &mut self,
+        value: &str,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::String(value);
+        self.visit_primitive(C::TAG, ValueKind::PrintableString, value);
+        Ok(())
+    }
+
+    fn write_octet_string<C: octetstring::Constraint>(
+        &mut self,
+        value: &[u8],
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::Bytes(value);
+        self.visit_primitive(C::TAG, ValueKind::OctetString, value);
+        Ok(())
+    }
+
+    fn write_bit_string<C: bitstring::Constraint>(
+        &mut self,
+        value: &[u8],
+        bit_len: u64,
+    ) -> Result<(), Self::Error> {
+        let value = VisitedValue::Bits {
+            bytes: value,
+            bit_len,
+        };
+        self.visit_primitive(C::TAG, ValueKind::BitString, value);
+        Ok(())
+    }
+
+    fn write_boolean<C: boolean::Constraint>(&mut self, value: bool) -> Result<(), Self::Error> {
+        let value = VisitedValue::Boolean(value);
+        self.visit_primitive(C::TAG, ValueKind::Boolean, value);
+        Ok(())
+    }
+
+    fn write_null<C: null::Constraint>(&mut self, _value: &Null) -> Result<(), Self::Error> {
+        self.visit_primitive(C::TAG, ValueKind::Null, VisitedValue::Null);
+        Ok(())
+    }
+}
@@ -0,0 +1,298 @@
+//! Conversion helpers between encoded byte buffers and their textual hex or base64 representation.
+//!
+//! The parsers are tolerant towards the formatting usually found in logs, test vectors and
+//! hexdumps: whitespace, `,`, `:` and `-` separators are ignored, as well as `0x` / `0X`
+//! prefixes in front of each group of hex digits.
+//!
+//! The `to_*` and `from_*` functions of the encoding rules encode and decode a value in one call,
+//! without setting up a writer or reader and without passing bit lengths.
+
+use crate::descriptor::{Readable, Reader, Writable, Writer};
+use crate::protocol::basic::{Error as DerError, DER};
+use crate::protocol::jer::Error as JerError;
+use crate::protocol::per::Error as UperError;
+#[cfg(feature = "protobuf")]
+use crate::protocol::protobuf::Error as ProtobufError;
+use crate::rw::{JerReader, JerWriter, UperReader, UperWriter};
+#[cfg(feature = "protobuf")]
+use crate::rw::{ProtobufReader, ProtobufWriter};
+
+const HEX_DIGITS: &[u8; 16] = b"0123456789abcdef";
+const BASE64_ALPHABET: &[u8; 64] =
+    b"ABCDEFGHIJKLMNOPQRSTUVWXYZabcdefghijklmnopqrstuvwxyz0123456789+/";
+const BASE64_PADDING: u8 = b'=';
+
+#[derive(Debug, Clone, PartialEq)]
+pub enum Error {
+    /// The character at the given char index is not valid for the input encoding
+    InvalidCharacter(char, usize),
+    /// The number of hex digits or base64 symbols does not form complete bytes
+    InvalidLength(usize),
+    Uper(UperError),
+}
+
+impl From<UperError> for Error {
+    fn from(e: UperError) -> Self {
+        Error::Uper(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::InvalidCharacter(char, index) => {
+                write!(f, "Invalid character {char:?} at index {index}")
+            }
+            Error::InvalidLength(len) => write!(
+                f,
+                "The input length of {len} digits does not describe complete bytes"
+            ),
+            Error::Uper(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// Formats the given bytes as a continuous string of lowercase hex digits.
+pub fn to_hex(bytes: &[u8]) -> String {
+    let mut string = String::with_capacity(bytes.len() * 2);
+    for byte in bytes {
+        string.push(char::from(HEX_DIGITS[usize::from(byte >> 4)]));
+        string.push(char::from(HEX_DIGITS[usize::from(byte & 0x0F)]));
+    }
+    string
+}
+
+/// Parses hex digits into bytes. Whitespace, the separators `,`, `:` and `-` as well as `0x`
+/// prefixes are skipped, so `"0x01 0x02"`, `"01:02"` and `"0102"` all result in `[1, 2]`.
+pub fn from_hex(hex: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(hex.len() / 2);
+    let mut high = None;
+    let mut chars = hex.chars().enumerate().peekable();
+
+    while let Some((index, char)) = chars.next() {
+        if char.is_whitespace() || matches!(char, ',' | ':' | '-') {
+            continue;
+        }
+
+        if char == '0' && high.is_none() && matches!(chars.peek(), Some((_, 'x' | 'X'))) {
+            let _ = chars.next();
+            continue;
+        }
+
+        let nibble = char
+            .to_digit(16)
+            .ok_or(Error::InvalidCharacter(char, index))? as u8;
+
+        match high.take() {
+            None => high = Some(nibble),
+            Some(high) => bytes.push((high << 4) | nibble),
+        }
+    }
+
+    if high.is_some() {
+        Err(Error::InvalidLength(bytes.len() * 2 + 1))
+    } else {
+        Ok(bytes)
+    }
+}
+
+/// Encodes the given bytes with the standard base64 alphabet including padding (RFC 4648).
+pub fn to_base64(bytes: &[u8]) -> String {
+    let mut string = String::with_capacity(bytes.len().div_ceil(3) * 4);
+    for chunk in bytes.chunks(3) {
+        let b0 = chunk[0];
+        let b1 = chunk.get(1).copied().unwrap_or_default();
+        let b2 = chunk.get(2).copied().unwrap_or_default();
+
+        let symbols = [
+            b0 >> 2,
+            (b0 << 4 | b1 >> 4) & 0x3F,
+            (b1 << 2 | b2 >> 6) & 0x3F,
+            b2 & 0x3F,
+        ];
+
+        for (index, symbol) in symbols.iter().enumerate() {
+            if index <= chunk.len() {
+                string.push(char::from(BASE64_ALPHABET[usize::from(*symbol)]));
+            } else {
+                string.push(char::from(BASE64_PADDING));
+            }
+        }
+    }
+    string
+}
+
+/// Decodes standard base64 (RFC 4648) into bytes. Whitespace is ignored and the trailing
+/// padding is optional.
+pub fn from_base64(base64: &str) -> Result<Vec<u8>, Error> {
+    let mut bytes = Vec::with_capacity(base64.len() / 4 * 3);
+    let mut accumulator = 0u32;
+    let mut symbols = 0usize;
+    let mut pending = 0usize;
+    let mut padding = false;
+
+    for (index, char) in base64.chars().enumerate() {
+        if char.is_whitespace() {
+            continue;
+        } else if char == char::from(BASE64_PADDING) {
+            padding = true;
+            continue;
+        } else if padding {
+            return Err(Error::InvalidCharacter(char, index));
+        }
+
+        let value = BASE64_ALPHABET
+            .iter()
+            .position(|c| char::from(*c) == char)
+            .ok_or(Error::InvalidCharacter(char, index))? as u32;
+
+        accumulator = (accumulator << 6) | value;
+        symbols += 1;
+        pending += 1;
+
+        if pending == 4 {
+            bytes.extend_from_slice(&accumulator.to_be_bytes()[1..]);
+            accumulator = 0;
+            pending = 0;
+        }
+    }
+
+    match pending {
+        0 => {}
+        2 => bytes.push((accumulator >> 4) as u8),
+        3 => bytes.extend_from_slice(&((accumulator >> 2) as u16).to_be_bytes()),
+        _ => return Err(Error::InvalidLength(symbols)),
+    }
+
+    Ok(bytes)
+}
+
+/// Serializes the value with the [`UperWriter`] into its (zero-padded) byte representation.
+pub fn to_uper_bytes<T: Writable>(value: &T) -> Result<Vec<u8>, UperError> {
+    let mut writer = UperWriter::default();
+    writer.write(value)?;
+    Ok(writer.into_bytes_vec())
+}
+
+/// Deserializes the value with the [`UperReader`] from the given bytes.
+pub fn from_uper_bytes<T: Readable>(bytes: &[u8]) -> Result<T, UperError> {
+    let mut reader = UperReader::from((bytes, bytes.len() * 8));
+    reader.read::<T>()
+}
+
+/// Serializes the value with the DER [`BasicWriter`](crate::rw::BasicWriter).
+pub fn to_der_bytes<T: Writable>(value: &T) -> Result<Vec<u8>, DerError> {
+    let mut writer = DER::writer(Vec::new());
+    writer.write(value)?;
+    Ok(writer.into_inner())
+}
+
+/// Deserializes the value with the DER [`BasicReader`](crate::rw::BasicReader) from the given
+/// bytes.
+pub fn from_der_bytes<T: Readable>(bytes: &[u8]) -> Result<T, DerError> {
+    DER::reader(bytes).read::<T>()
+}
+
+/// Serializes the value with the [`JerWriter`] into its JSON representation.
+pub fn to_jer_string<T: Writable>(value: &T) -> String {
+    let mut writer = JerWriter::default();
+    match writer.write(value) {
+        Ok(()) => writer.into_string(),
+        Err(infallible) => match infallible {},
+    }
+}
+
+/// Deserializes the value with the [`JerReader`] from the given JSON.
+pub fn from_jer_str<T: Readable>(json: &str) -> Result<T, JerError> {
+    JerReader::parse(json)?.read::<T>()
+}
+
+/// Serializes the value with the [`ProtobufWriter`].
+#[cfg(feature = "protobuf")]
+pub fn to_protobuf_bytes<T: Writable>(value: &T) -> Result<Vec<u8>, ProtobufError> {
+    let mut writer = ProtobufWriter::default();
+    writer.write(value)?;
+    Ok(writer.into_bytes_vec())
+}
+
+/// Deserializes the value with the [`ProtobufReader`] from the given bytes.
+#[cfg(feature = "protobuf")]
+pub fn from_protobuf_bytes<T: Readable>(bytes: &[u8]) -> Result<T, ProtobufError> {
+    ProtobufReader::from(bytes).read::<T>()
+}
+
+/// Serializes the value in UPER and formats the result as hex string, see [`to_hex`].
+pub fn to_uper_hex<T: Writable>(value: &T) -> Result<String, Error> {
+    Ok(to_hex(&to_uper_bytes(value)?))
+}
+
+/// Parses the hex string with [`from_hex`] and deserializes the value from it in UPER.
+pub fn from_uper_hex<T: Readable>(hex: &str) -> Result<T, Error> {
+    Ok(from_uper_bytes(&from_hex(hex)?)?)
+}
+
+/// Serializes the value in UPER and encodes the result in base64, see [`to_base64`].
+pub fn to_uper_base64<T: Writable>(value: &T) -> Result<String, Error> {
+    Ok(to_base64(&to_uper_bytes(value)?))
+}
+
+/// Decodes the base64 string with [`from_base64`] and deserializes the value from it in UPER.
+pub fn from_uper_base64<T: Readable>(base64: &str) -> Result<T, Error> {
+    Ok(from_uper_bytes(&from_base64(base64)?)?)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_hex_round_trip() {
+        let bytes = (0..=u8::MAX).collect::<Vec<_>>();
+        assert_eq!(bytes, from_hex(&to_hex(&bytes)).unwrap());
+        assert_eq!("00ff10", to_hex(&[0x00, 0xFF, 0x10]));
+    }
+
+    #[test]
+    pub fn test_hex_tolerant_parsing() {
+        let expected = vec![0xDE, 0xAD, 0xBE, 0xEF];
+        assert_eq!(expected, from_hex("deadbeef").unwrap());
+        assert_eq!(expected, from_hex("DE AD BE EF").unwrap());
+        assert_eq!(expected, from_hex("0xde 0xad 0xbe 0xef").unwrap());
+        assert_eq!(expected, from_hex("0xDEADBEEF").unwrap());
+        assert_eq!(expected, from_hex("de:ad:be:ef").unwrap());
+        assert_eq!(expected, from_hex("0xde, 0xad,\n\t0xbe, 0xef").unwrap());
+        assert_eq!(vec![0x00, 0x01], from_hex("00 01").unwrap());
+    }
+
+    #[test]
+    pub fn test_hex_invalid_input() {
+        assert_eq!(Err(Error::InvalidCharacter('g', 2)), from_hex("abg0"));
+        assert_eq!(Err(Error::InvalidLength(3)), from_hex("abc"));
+    }
+
+    #[test]
+    pub fn test_base64_rfc4648_vectors() {
+        for (plain, encoded) in [
+            ("", ""),
+            ("f", "Zg=="),
+            ("fo", "Zm8="),
+            ("foo", "Zm9v"),
+            ("foob", "Zm9vYg=="),
+            ("fooba", "Zm9vYmE="),
+            ("foobar", "Zm9vYmFy"),
+        ] {
+            assert_eq!(encoded, to_base64(plain.as_bytes()));
+            assert_eq!(plain.as_bytes(), &from_base64(encoded).unwrap()[..]);
+        }
+    }
+
+    #[test]
+    pub fn test_base64_tolerant_parsing() {
+        assert_eq!(b"foob".to_vec(), from_base64("Zm9v\nYg").unwrap());
+        assert_eq!(Err(Error::InvalidCharacter('!', 1)), from_base64("Z!=="));
+        assert_eq!(Err(Error::InvalidCharacter('A', 3)), from_base64("Zg=A"));
+        assert_eq!(Err(Error::InvalidLength(5)), from_base64("Zm9vY"));
+    }
+}
@@ -0,0 +1,174 @@
+//! Best-effort decoding of [`Value`]s for the forensic analysis of corrupted payloads.
+//!
+//! Instead of failing on the first error, the decoder records the error with the path of the
+//! affected value and continues where the structure of the encoding allows it:
+//!
+//!  - in DER, each field, alternative and element is enclosed in its own TLV, so that a
+//!    failing one is skipped and missing mandatory fields are only recorded,
+//!  - in UPER, only the extension additions are enclosed in open types. Any other failure
+//!    loses the position within the bit stream, so that the values decoded so far are returned
+//!    and the decoder continues after the enclosing open type (if any).
+
+use super::*;
+use crate::protocol::per::unaligned::buffer::Bits;
+
+/// An error that was recorded by a best-effort decode
+#[derive(Debug)]
+pub struct FieldError {
+    /// The path of the value that failed to decode, for example `shapes[2].label`, which is
+    /// empty for the decoded value itself
+    pub path: String,
+    pub error: Error,
+}
+
+/// The result of a best-effort decode
+#[derive(Debug)]
+pub struct BestEffort {
+    /// The partially populated value, `None` if not even the outermost value could be decoded
+    pub value: Option<Value>,
+    pub errors: Vec<FieldError>,
+}
+
+impl BestEffort {
+    /// Whether the value was decoded without any error
+    pub fn is_complete(&self) -> bool {
+        self.value.is_some() && self.errors.is_empty()
+    }
+}
+
+impl Schema {
+    /// Decodes a value of the given type from DER encoded bytes like [`Schema::decode_der`],
+    /// but skips the fields and elements that fail to decode, see [`crate::value::best_effort`]
+    pub fn decode_der_best_effort(&self, type_name: &str, mut bytes: &[u8]) -> BestEffort {
+        let mut recovery = Recovery::default();
+        let value = recovery.component(Segment::Root, |recovery| {
+            self.read_der_definition(&mut bytes, type_name, None, Some(recovery))
+        });
+        recovery.finish(value)
+    }
+
+    /// Decodes a value of the given type from UPER encoded bytes like [`Schema::decode_uper`],
+    /// but returns the values decoded until the first error and skips the extension additions
+    /// that fail to decode, see [`crate::value::best_effort`]
+    pub fn decode_uper_best_effort(&self, type_name: &str, bytes: &[u8]) -> BestEffort {
+        let mut recovery = Recovery::default();
+        let value = recovery.component(Segment::Root, |recovery| {
+            self.read_uper_definition(&mut Bits::from(bytes), type_name, Some(recovery))
+        });
+        recovery.finish(value)
+    }
+}
+
+#[derive(Debug, Copy, Clone)]
+pub(crate) enum Segment<'a> {
+    Root,
+    Field(&'a str),
+    Element(usize),
+}
+
+/// The state of a best-effort decode, which is passed through the decoding functions as
+/// `Option<&mut Recovery>`, being `None` for a regular decode
+#[derive(Debug, Default)]
+pub(crate) struct Recovery {
+    path: String,
+    errors: Vec<FieldError>,
+    /// Whether the position within the current UPER bit stream was lost
+    desynchronized: bool,
+}
+
+impl Recovery {
+    /// Decodes the value at the given path segment, records its error (if any) and returns
+    /// `None` in that case
+    pub(crate) fn component<T>(
+        &mut self,
+        segment: Segment,
+        f: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Option<T> {
+        match self.scoped(segment, f) {
+            Ok(value) => Some(value),
+            Err(error) => {
+                self.record(segment, error);
+                None
+            }
+        }
+    }
+
+    /// Decodes the value at the given path segment, so that the errors recorded while doing so
+    /// refer to it
+    pub(crate) fn scoped<T>(
+        &mut self,
+        segment: Segment,
+        f: impl FnOnce(&mut Self) -> Result<T, Error>,
+    ) -> Result<T, Error> {
+        let len = self.path.len();
+        self.push(segment);
+        let result = f(self);
+        self.path.truncate(len);
+        result
+    }
+
+    pub(crate) fn record(&mut self, segment: Segment, error: Error) {
+        let len = self.path.len();
+        self.push(segment);
+        self.errors.push(FieldError {
+            path: self.path.clone(),
+            error,
+        });
+        self.path.truncate(len);
+    }
+
+    pub(crate) fn is_desynchronized(recovery: &Option<&mut Self>) -> bool {
+        recovery.as_ref().is_some_and(|r| r.desynchronized)
+    }
+
+    pub(crate) fn set_desynchronized(&mut self, desynchronized: bool) {
+        self.desynchronized = desynchronized;
+    }
+
+    fn push(&mut self, segment: Segment) {
+        match segment {
+            Segment::Root => {}
+            Segment::Field(name) if self.path.is_empty() => self.path.push_str(name),
+            Segment::Field(name) => {
+                self.path.push('.');
+                self.path.push_str(name);
+            }
+            Segment::Element(index) => {
+                let _ = std::fmt::Write::write_fmt(&mut self.path, format_args!("[{}]", index));
+            }
+        }
+    }
+
+    fn finish(self, value: Option<Value>) -> BestEffort {
+        BestEffort {
+            value,
+            errors: self.errors,
+        }
+    }
+}
+
+/// Decodes a component through `f`. In a best-effort decode, an error is recorded and `None`
+/// returned instead
+pub(crate) fn recover<T>(
+    recovery: Option<&mut Recovery>,
+    segment: Segment,
+    f: impl FnOnce(Option<&mut Recovery>) -> Result<T, Error>,
+) -> Result<Option<T>, Error> {
+    match recovery {
+        None => f(None).map(Some),
+        Some(recovery) => Ok(recovery.component(segment, |recovery| f(Some(recovery)))),
+    }
+}
+
+/// Decodes a value through `f`, so that errors recorded in a best-effort decode refer to the
+/// given path segment
+pub(crate) fn scoped<T>(
+    recovery: Option<&mut Recovery>,
+    segment: Segment,
+    f: impl FnOnce(Option<&mut Recovery>) -> Result<T, Error>,
+) -> Result<T, Error> {
+    match recovery {
+        None => f(None),
+        Some(recovery) => recovery.scoped(segment, |recovery| f(Some(recovery))),
+    }
+}
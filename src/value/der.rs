@@ -0,0 +1,601 @@
+//! Distinguished Encoding Rules for [`Value`]s.
+//!
+//! All tags are applied implicitly, except for the tag of a `CHOICE`, which is always explicit
+//! (ITU-T X.680 | ISO/IEC 8824-1, 31.2.7).
+
+use super::*;
+use crate::protocol::basic::{BasicRead, BasicWrite};
+use crate::rw::with_scratch_buffer;
+
+const CLASS_BITS_MASK: u8 = 0b1100_0000;
+const CLASS_BITS_UNIVERSAL: u8 = 0b0000_0000;
+const CLASS_BITS_APPLICATION: u8 = 0b0100_0000;
+const CLASS_BITS_CONTEXT_SPECIFIC: u8 = 0b1000_0000;
+const CLASS_BITS_PRIVATE: u8 = 0b1100_0000;
+const CONSTRUCTED_BIT: u8 = 0b0010_0000;
+const NUMBER_BITS_MASK: u8 = 0b0001_1111;
+
+impl Schema {
+    /// Decodes a value of the given type from DER encoded bytes
+    pub fn decode_der(&self, type_name: &str, mut bytes: &[u8]) -> Result<Value, Error> {
+        self.read_der_definition(&mut bytes, type_name, None, None)
+    }
+
+    /// Encodes the value as the given type in DER
+    pub fn encode_der(&self, type_name: &str, value: &Value) -> Result<Vec<u8>, Error> {
+        let mut bytes = Vec::new();
+        self.write_der_definition(&mut bytes, type_name, None, value)?;
+        Ok(bytes)
+    }
+
+    fn write_der_definition(
+        &self,
+        out: &mut Vec<u8>,
+        name: &str,
+        tag: Option<Tag>,
+        value: &Value,
+    ) -> Result<(), Error> {
+        match self.resolve(name)? {
+            Rust::Struct {
+                ordering,
+                fields,
+                tag: own_tag,
+                extension_after,
+                ..
+            } => {
+                let components = struct_components(fields, *ordering, *extension_after);
+                let values = field_values(&components, fields_of(value, *ordering)?)?;
+                let std_len = std_len(components.len(), *extension_after);
+                let tag = tag.or(*own_tag).unwrap_or(default_struct_tag(*ordering));
+                with_scratch_buffer(|content| {
+                    for (index, (component, value)) in components.iter().zip(values).enumerate() {
+                        match value {
+                            Some(value) if is_present(component.r#type, Some(value)) => self
+                                .write_der_type(content, component.r#type, component.tag, value)?,
+                            None if index < std_len && !is_optional(component.r#type) => {
+                                return Err(Error::MissingField(component.name.to_string()))
+                            }
+                            _ => {}
+                        }
+                    }
+                    write_tlv(out, tag, true, content)
+                })
+            }
+            Rust::TupleStruct {
+                r#type,
+                tag: own_tag,
+                ..
+            } => self.write_der_type(out, r#type, tag.or(*own_tag), value),
+            Rust::Enum(enumeration) => {
+                let Value::Enumerated(variant) = value else {
+                    return mismatch("ENUMERATED", value);
+                };
+                let index = variant_index(enumeration.variants().map(String::as_str), variant)?;
+                let number = enumeration
+                    .numbers()
+                    .map_or(index, |numbers| numbers[index]);
+                let tag = tag.or(enumeration.tag()).unwrap_or(Tag::DEFAULT_ENUMERATED);
+                write_tlv(out, tag, false, &integer_content(number as i64))
+            }
+            Rust::DataEnum(enumeration) => {
+                let Value::Choice(alternative, value) = value else {
+                    return mismatch("CHOICE", value);
+                };
+                let alternatives = choice_components(enumeration);
+                let index = variant_index(alternatives.iter().map(|a| a.name), alternative)?;
+                let alternative = &alternatives[index];
+                match tag.or(enumeration.tag()) {
+                    Some(tag) => with_scratch_buffer(|content| {
+                        self.write_der_type(content, alternative.r#type, alternative.tag, value)?;
+                        write_tlv(out, tag, true, content)
+                    }),
+                    None => self.write_der_type(out, alternative.r#type, alternative.tag, value),
+                }
+            }
+        }
+    }
+
+    fn write_der_type(
+        &self,
+        out: &mut Vec<u8>,
+        r#type: &RustType,
+        tag: Option<Tag>,
+        value: &Value,
+    ) -> Result<(), Error> {
+        let content = match r#type {
+            RustType::Bool => match value {
+                Value::Boolean(value) => vec![if *value { 0xFF } else { 0x00 }],
+                value => return mismatch("BOOLEAN", value),
+            },
+            RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_)
+            | RustType::I128(_)
+            | RustType::U128(_) => match value.as_integer() {
+                Some(value) => integer_content(value),
+                None => return mismatch("INTEGER", value),
+            },
+            RustType::String(_, charset, _) => match value.as_str() {
+                Some(string) => {
+                    UperError::ensure_string_valid(*charset, string)?;
+                    string.as_bytes().to_vec()
+                }
+                None => return mismatch("string", value),
+            },
+            RustType::VecU8(_) | RustType::Uuid => match value {
+                Value::OctetString(octets) => octets.clone(),
+                value => return mismatch("OCTET STRING", value),
+            },
+            RustType::BitVec(_) => match value {
+                Value::BitString(bits) => bit_string_content(bits),
+                value => return mismatch("BIT STRING", value),
+            },
+            RustType::Vec(inner, _, ordering) => {
+                let elements = elements_of(value, *ordering)?;
+                let tag = tag.unwrap_or(default_struct_tag(*ordering));
+                return with_scratch_buffer(|content| {
+                    if *ordering == EncodingOrdering::Sort {
+                        // ITU-T X.690 | ISO/IEC 8825-1, 11.6
+                        with_scratch_buffer(|encodings| {
+                            let mut ranges = Vec::with_capacity(elements.len());
+                            for element in elements {
+                                let start = encodings.len();
+                                self.write_der_type(encodings, inner, None, element)?;
+                                ranges.push(start..encodings.len());
+                            }
+                            ranges.sort_by(|a, b| encodings[a.clone()].cmp(&encodings[b.clone()]));
+                            for range in ranges {
+                                content.extend_from_slice(&encodings[range]);
+                            }
+                            Ok::<_, Error>(())
+                        })?;
+                    } else {
+                        for element in elements {
+                            self.write_der_type(content, inner, None, element)?;
+                        }
+                    }
+                    write_tlv(out, tag, true, content)
+                });
+            }
+            RustType::Null => match value {
+                Value::Null => Vec::new(),
+                value => return mismatch("NULL", value),
+            },
+            RustType::Option(inner) | RustType::Default(inner, _) | RustType::Box(inner) => {
+                return self.write_der_type(out, inner, tag, value)
+            }
+            RustType::Complex(name, _) => return self.write_der_definition(out, name, tag, value),
+        };
+        // unwrap: all remaining types have a universal tag
+        write_tlv(out, tag.or_else(|| r#type.tag()).unwrap(), false, &content)
+    }
+
+    pub(crate) fn read_der_definition(
+        &self,
+        input: &mut &[u8],
+        name: &str,
+        tag: Option<Tag>,
+        mut recovery: Option<&mut Recovery>,
+    ) -> Result<Value, Error> {
+        match self.resolve(name)? {
+            Rust::Struct {
+                ordering,
+                fields,
+                tag: own_tag,
+                extension_after,
+                ..
+            } => {
+                let tag = tag.or(*own_tag).unwrap_or(default_struct_tag(*ordering));
+                let mut content = read_tlv(input, tag)?;
+                let components = struct_components(fields, *ordering, *extension_after);
+                let std_len = std_len(components.len(), *extension_after);
+                let mut values = Vec::with_capacity(components.len());
+
+                if *ordering == EncodingOrdering::Sort {
+                    while let Some(actual) = peek_tag(content)? {
+                        match components
+                            .iter()
+                            .find(|c| self.matches(c.r#type, c.tag, actual))
+                        {
+                            Some(c) => {
+                                if let Some(value) = self.read_der_component(
+                                    &mut content,
+                                    Segment::Field(c.name),
+                                    c.r#type,
+                                    c.tag,
+                                    recovery.as_deref_mut(),
+                                )? {
+                                    values.push((c.name.to_string(), value));
+                                }
+                            }
+                            None if extension_after.is_some() => skip_tlv(&mut content)?,
+                            None => unexpected_component(
+                                &mut content,
+                                DerError::unexpected_tag(tag, actual).into(),
+                                recovery.as_deref_mut(),
+                            )?,
+                        }
+                    }
+                } else {
+                    for c in &components {
+                        if peek_tag(content)?.is_some_and(|t| self.matches(c.r#type, c.tag, t)) {
+                            if let Some(value) = self.read_der_component(
+                                &mut content,
+                                Segment::Field(c.name),
+                                c.r#type,
+                                c.tag,
+                                recovery.as_deref_mut(),
+                            )? {
+                                values.push((c.name.to_string(), value));
+                            }
+                        }
+                    }
+                    if extension_after.is_some() {
+                        // unknown extensions
+                        while !content.is_empty() {
+                            skip_tlv(&mut content)?;
+                        }
+                    } else {
+                        while let Some(actual) = peek_tag(content)? {
+                            unexpected_component(
+                                &mut content,
+                                DerError::unexpected_tag(tag, actual).into(),
+                                recovery.as_deref_mut(),
+                            )?;
+                        }
+                    }
+                }
+
+                for (index, c) in components.iter().enumerate() {
+                    if values.iter().any(|(name, _)| name == c.name) {
+                        continue;
+                    }
+                    match c.r#type {
+                        RustType::Default(inner, literal) => {
+                            values.push((c.name.to_string(), default_value(inner, literal)))
+                        }
+                        RustType::Option(_) => {}
+                        _ if index >= std_len => {}
+                        _ => match recovery.as_deref_mut() {
+                            Some(recovery) => recovery.record(
+                                Segment::Field(c.name),
+                                Error::MissingField(c.name.to_string()),
+                            ),
+                            None => return Err(Error::MissingField(c.name.to_string())),
+                        },
+                    }
+                }
+
+                in_definition_order(fields, &mut values);
+                Ok(with_fields(*ordering, values))
+            }
+            Rust::TupleStruct {
+                r#type,
+                tag: own_tag,
+                ..
+            } => self.read_der_type(input, r#type, tag.or(*own_tag), recovery),
+            Rust::Enum(enumeration) => {
+                let tag = tag.or(enumeration.tag()).unwrap_or(Tag::DEFAULT_ENUMERATED);
+                let index = integer_from_content(read_tlv(input, tag)?)?;
+                usize::try_from(index)
+                    .ok()
+                    .and_then(|number| match enumeration.numbers() {
+                        Some(numbers) => numbers.iter().position(|n| *n == number),
+                        None => Some(number),
+                    })
+                    .and_then(|index| enumeration.variants().nth(index))
+                    .map(|variant| Value::Enumerated(variant.clone()))
+                    .ok_or_else(|| {
+                        DerError::unexpected_choice_index(0..enumeration.len() as u64, index as u64)
+                            .into()
+                    })
+            }
+            Rust::DataEnum(enumeration) => {
+                let mut content = match tag.or(enumeration.tag()) {
+                    Some(tag) => read_tlv(input, tag)?,
+                    None => *input,
+                };
+                let alternatives = choice_components(enumeration);
+                let actual = peek_tag(content)?.ok_or_else(end_of_stream)?;
+                let alternative = alternatives
+                    .iter()
+                    .find(|a| self.matches(a.r#type, a.tag, actual))
+                    .ok_or_else(|| {
+                        DerError::unexpected_tag(
+                            alternatives[0].tag.unwrap_or(Tag::Universal(0)),
+                            actual,
+                        )
+                    })?;
+                let value = scoped(recovery, Segment::Field(alternative.name), |recovery| {
+                    self.read_der_type(&mut content, alternative.r#type, alternative.tag, recovery)
+                })?;
+                if tag.or(enumeration.tag()).is_none() {
+                    *input = content;
+                }
+                Ok(Value::Choice(alternative.name.to_string(), Box::new(value)))
+            }
+        }
+    }
+
+    /// Reads the next TLV as the given type. In a best-effort decode, a failure is recorded and
+    /// the TLV skipped
+    fn read_der_component(
+        &self,
+        input: &mut &[u8],
+        segment: Segment,
+        r#type: &RustType,
+        tag: Option<Tag>,
+        recovery: Option<&mut Recovery>,
+    ) -> Result<Option<Value>, Error> {
+        match recovery {
+            None => self.read_der_type(input, r#type, tag, None).map(Some),
+            Some(recovery) => {
+                let mut rest = *input;
+                skip_tlv(&mut rest)?;
+                let mut tlv = &input[..input.len() - rest.len()];
+                *input = rest;
+                Ok(recovery.component(segment, |recovery| {
+                    self.read_der_type(&mut tlv, r#type, tag, Some(recovery))
+                }))
+            }
+        }
+    }
+
+    fn read_der_type(
+        &self,
+        input: &mut &[u8],
+        r#type: &RustType,
+        tag: Option<Tag>,
+        mut recovery: Option<&mut Recovery>,
+    ) -> Result<Value, Error> {
+        let expected = match r#type {
+            RustType::Option(inner) | RustType::Default(inner, _) | RustType::Box(inner) => {
+                return self.read_der_type(input, inner, tag, recovery)
+            }
+            RustType::Complex(name, _) => {
+                return self.read_der_definition(input, name, tag, recovery)
+            }
+            RustType::Vec(_, _, ordering) => tag.unwrap_or(default_struct_tag(*ordering)),
+            // unwrap: all remaining types have a universal tag
+            other => tag.or_else(|| other.tag()).unwrap(),
+        };
+        let content = read_tlv(input, expected)?;
+        Ok(match r#type {
+            RustType::Bool => match content {
+                [byte] => Value::Boolean(*byte != 0x00),
+                _ => return Err(DerError::unexpected_length(1..2, content.len() as u64).into()),
+            },
+            RustType::String(_, charset, _) => {
+                let string = String::from_utf8(content.to_vec()).map_err(|e| {
+                    Error::Uper(crate::protocol::per::ErrorKind::FromUtf8Error(e).into())
+                })?;
+                UperError::ensure_string_valid(*charset, &string)?;
+                Value::string(*charset, string)
+            }
+            RustType::VecU8(_) | RustType::Uuid => Value::OctetString(content.to_vec()),
+            RustType::BitVec(_) => Value::BitString(bit_string_from_content(content)?),
+            RustType::Vec(inner, _, ordering) => {
+                let mut content = content;
+                let mut elements = Vec::new();
+                while !content.is_empty() {
+                    if let Some(element) = self.read_der_component(
+                        &mut content,
+                        Segment::Element(elements.len()),
+                        inner,
+                        None,
+                        recovery.as_deref_mut(),
+                    )? {
+                        elements.push(element);
+                    }
+                }
+                with_elements(*ordering, elements)
+            }
+            RustType::Null => match content {
+                [] => Value::Null,
+                _ => return Err(DerError::unexpected_length(0..1, content.len() as u64).into()),
+            },
+            _ => Value::Integer(integer_from_content(content)?),
+        })
+    }
+
+    /// Whether a value of the given type and tag can start with the given tag
+    fn matches(&self, r#type: &RustType, tag: Option<Tag>, actual: Tag) -> bool {
+        if let Some(tag) = tag {
+            return tag == actual;
+        }
+        match r#type {
+            RustType::Option(inner) | RustType::Default(inner, _) | RustType::Box(inner) => {
+                self.matches(inner, None, actual)
+            }
+            RustType::Vec(_, _, ordering) => default_struct_tag(*ordering) == actual,
+            RustType::Complex(name, _) => match self.definition(name) {
+                None => false,
+                Some(Rust::Struct { ordering, tag, .. }) => {
+                    tag.unwrap_or(default_struct_tag(*ordering)) == actual
+                }
+                Some(Rust::TupleStruct { r#type, tag, .. }) => self.matches(r#type, *tag, actual),
+                Some(Rust::Enum(enumeration)) => {
+                    enumeration.tag().unwrap_or(Tag::DEFAULT_ENUMERATED) == actual
+                }
+                Some(Rust::DataEnum(enumeration)) => match enumeration.tag() {
+                    Some(tag) => tag == actual,
+                    None => choice_components(enumeration)
+                        .iter()
+                        .any(|a| self.matches(a.r#type, a.tag, actual)),
+                },
+            },
+            other => other.tag() == Some(actual),
+        }
+    }
+}
+
+fn choice_components(enumeration: &asn1rs_model::rust::DataEnum) -> Vec<Component<'_>> {
+    implicitly_tagged(
+        enumeration
+            .variants()
+            .map(|variant| (variant.name(), variant.r#type(), variant.tag())),
+    )
+}
+
+const fn default_struct_tag(ordering: EncodingOrdering) -> Tag {
+    match ordering {
+        EncodingOrdering::Keep => Tag::DEFAULT_SEQUENCE,
+        EncodingOrdering::Sort => Tag::DEFAULT_SET,
+    }
+}
+
+fn end_of_stream() -> Error {
+    DerError::from(std::io::Error::from(std::io::ErrorKind::UnexpectedEof)).into()
+}
+
+/// ITU-T X.690 | ISO/IEC 8825-1, 8.1.2
+fn write_identifier(out: &mut Vec<u8>, tag: Tag, constructed: bool) {
+    let mut identifier = match tag {
+        Tag::Universal(_) => CLASS_BITS_UNIVERSAL,
+        Tag::Application(_) => CLASS_BITS_APPLICATION,
+        Tag::ContextSpecific(_) => CLASS_BITS_CONTEXT_SPECIFIC,
+        Tag::Private(_) => CLASS_BITS_PRIVATE,
+    };
+    if constructed {
+        identifier |= CONSTRUCTED_BIT;
+    }
+    let number = tag.value();
+    if number < usize::from(NUMBER_BITS_MASK) {
+        out.push(identifier | number as u8);
+    } else {
+        // 8.1.2.4: the number follows in base 128 with the most significant group first
+        out.push(identifier | NUMBER_BITS_MASK);
+        let groups = (usize::BITS - number.leading_zeros()).div_ceil(7).max(1);
+        for group in (0..groups).rev() {
+            let bits = ((number >> (group * 7)) & 0x7F) as u8;
+            out.push(if group > 0 { bits | 0x80 } else { bits });
+        }
+    }
+}
+
+fn read_identifier(input: &mut &[u8]) -> Result<Tag, Error> {
+    let (&identifier, rest) = input.split_first().ok_or_else(end_of_stream)?;
+    *input = rest;
+    let mut number = usize::from(identifier & NUMBER_BITS_MASK);
+    if number == usize::from(NUMBER_BITS_MASK) {
+        number = 0;
+        loop {
+            let (&byte, rest) = input.split_first().ok_or_else(end_of_stream)?;
+            *input = rest;
+            number = number
+                .checked_mul(128)
+                .ok_or_else(|| DerError::unsupported_byte_len(8, 9))?
+                | usize::from(byte & 0x7F);
+            if byte & 0x80 == 0 {
+                break;
+            }
+        }
+    }
+    Ok(match identifier & CLASS_BITS_MASK {
+        CLASS_BITS_UNIVERSAL => Tag::Universal(number),
+        CLASS_BITS_APPLICATION => Tag::Application(number),
+        CLASS_BITS_CONTEXT_SPECIFIC => Tag::ContextSpecific(number),
+        _ => Tag::Private(number),
+    })
+}
+
+fn peek_tag(mut input: &[u8]) -> Result<Option<Tag>, Error> {
+    if input.is_empty() {
+        Ok(None)
+    } else {
+        read_identifier(&mut input).map(Some)
+    }
+}
+
+fn write_tlv(out: &mut Vec<u8>, tag: Tag, constructed: bool, content: &[u8]) -> Result<(), Error> {
+    write_identifier(out, tag, constructed);
+    out.write_length(content.len() as u64)?;
+    out.extend_from_slice(content);
+    Ok(())
+}
+
+/// Reads the identifier and length octets and returns the content, which must be tagged with
+/// the expected tag
+fn read_tlv<'a>(input: &mut &'a [u8], expected: Tag) -> Result<&'a [u8], Error> {
+    let tag = read_identifier(input)?;
+    if tag != expected {
+        return Err(DerError::unexpected_tag(expected, tag).into());
+    }
+    let len = input.read_length()?;
+    if len > input.len() as u64 {
+        return Err(DerError::unexpected_length(0..input.len() as u64 + 1, len).into());
+    }
+    let (content, rest) = input.split_at(len as usize);
+    *input = rest;
+    Ok(content)
+}
+
+/// Fails with the given error or records it and skips the unexpected TLV in a best-effort decode
+fn unexpected_component(
+    input: &mut &[u8],
+    error: Error,
+    recovery: Option<&mut Recovery>,
+) -> Result<(), Error> {
+    match recovery {
+        None => Err(error),
+        Some(recovery) => {
+            recovery.record(Segment::Root, error);
+            skip_tlv(input)
+        }
+    }
+}
+
+fn skip_tlv(input: &mut &[u8]) -> Result<(), Error> {
+    let tag = peek_tag(input)?.ok_or_else(end_of_stream)?;
+    read_tlv(input, tag).map(drop)
+}
+
+/// ITU-T X.690 | ISO/IEC 8825-1, 8.3: the minimal two's complement representation
+fn integer_content(value: i64) -> Vec<u8> {
+    let bytes = value.to_be_bytes();
+    let redundant = bytes
+        .windows(2)
+        .take_while(|pair| {
+            (pair[0] == 0x00 && pair[1] & 0x80 == 0) || (pair[0] == 0xFF && pair[1] & 0x80 != 0)
+        })
+        .count();
+    bytes[redundant..].to_vec()
+}
+
+fn integer_from_content(content: &[u8]) -> Result<i64, Error> {
+    if content.is_empty() || content.len() > 8 {
+        return Err(DerError::unexpected_length(1..9, content.len() as u64).into());
+    }
+    let fill = if content[0] & 0x80 != 0 { 0xFF } else { 0x00 };
+    let mut bytes = [fill; 8];
+    bytes[8 - content.len()..].copy_from_slice(content);
+    Ok(i64::from_be_bytes(bytes))
+}
+
+/// ITU-T X.690 | ISO/IEC 8825-1, 8.6: the number of unused bits in the last octet, followed by
+/// the bits. DER requires the unused bits to be zero (11.2.1).
+fn bit_string_content(bits: &BitVec) -> Vec<u8> {
+    let byte_len = bits.bit_len().div_ceil(8) as usize;
+    let unused = (byte_len as u64 * 8 - bits.bit_len()) as u8;
+    let mut content = Vec::with_capacity(byte_len + 1);
+    content.push(unused);
+    content.extend_from_slice(&bits.as_byte_slice()[..byte_len]);
+    if let Some(last) = content.last_mut().filter(|_| byte_len > 0) {
+        *last &= 0xFF << unused;
+    }
+    content
+}
+
+fn bit_string_from_content(content: &[u8]) -> Result<BitVec, Error> {
+    match content {
+        [unused, bytes @ ..] if *unused < 8 && (!bytes.is_empty() || *unused == 0) => Ok(
+            BitVec::from_bytes(bytes.to_vec(), bytes.len() as u64 * 8 - u64::from(*unused)),
+        ),
+        _ => Err(DerError::unexpected_length(1..2, content.len() as u64).into()),
+    }
+}
@@ -0,0 +1,478 @@
+//! Schema driven ("dynamic") encoding and decoding without generated code.
+//!
+//! A [`Schema`] is built at runtime from parsed ASN.1 definitions. It encodes and decodes
+//! [`Value`]s - a generic representation of any ASN.1 value - for the given type name. This is
+//! useful for tools like protocol analyzers that need to handle schemas which are not known at
+//! compile time.
+//!
+//! The schema is derived from the same rust model the code generator works on, so the encoding
+//! of a [`Value`] is the same as the encoding of the equivalent generated type.
+//!
+//! ```rust
+//! use asn1rs::value::{Schema, Value};
+//!
+//! let schema = Schema::parse(
+//!     r#"Example DEFINITIONS AUTOMATIC TAGS ::=
+//!     BEGIN
+//!         Point ::= SEQUENCE {
+//!             x INTEGER (0..255),
+//!             y INTEGER (0..255),
+//!             label UTF8String OPTIONAL
+//!         }
+//!     END"#,
+//! )
+//! .unwrap();
+//!
+//! let value = schema.decode_uper("Point", &[0x01, 0x01, 0x00]).unwrap();
+//! assert_eq!(Some(&Value::Integer(2)), value.get("x"));
+//! assert_eq!(None, value.get("label"));
+//! assert_eq!(vec![0x01, 0x01, 0x00], schema.encode_uper("Point", &value).unwrap());
+//! ```
+
+pub mod best_effort;
+mod der;
+mod notation;
+mod uper;
+
+use best_effort::{recover, scoped, Recovery, Segment};
+pub use best_effort::{BestEffort, FieldError};
+
+use crate::descriptor::bitstring::BitVec;
+use crate::protocol::basic::Error as DerError;
+use crate::protocol::per::Error as UperError;
+use asn1rs_model::asn::{Asn, Charset, MultiModuleResolver, Tag, TagProperty};
+use asn1rs_model::parse::Tokenizer;
+use asn1rs_model::resolve::Resolved;
+use asn1rs_model::rust::{EncodingOrdering, Rust, RustType};
+use asn1rs_model::{LiteralValue, Model};
+use std::collections::HashMap;
+
+/// A generic representation of any ASN.1 value
+#[derive(Debug, Clone, PartialEq)]
+pub enum Value {
+    Boolean(bool),
+    Integer(i64),
+    Null,
+    /// The name of the variant of an `ENUMERATED`
+    Enumerated(String),
+    Utf8String(String),
+    Ia5String(String),
+    NumericString(String),
+    PrintableString(String),
+    VisibleString(String),
+    TeletexString(String),
+    VideotexString(String),
+    GraphicString(String),
+    ObjectDescriptor(String),
+    OctetString(Vec<u8>),
+    BitString(BitVec),
+    /// The present fields in the order of their definition. Absent `OPTIONAL` fields are
+    /// omitted, absent `DEFAULT` fields are decoded as their default value.
+    Sequence(Vec<(String, Value)>),
+    /// See [`Value::Sequence`]
+    Set(Vec<(String, Value)>),
+    SequenceOf(Vec<Value>),
+    SetOf(Vec<Value>),
+    /// The name of the chosen alternative and its value
+    Choice(String, Box<Value>),
+}
+
+impl Value {
+    /// Creates the string variant for the given [`Charset`]
+    pub fn string(charset: Charset, string: impl Into<String>) -> Self {
+        let string = string.into();
+        match charset {
+            Charset::Utf8 => Value::Utf8String(string),
+            Charset::Numeric => Value::NumericString(string),
+            Charset::Printable => Value::PrintableString(string),
+            Charset::Ia5 => Value::Ia5String(string),
+            Charset::Visible => Value::VisibleString(string),
+            Charset::Teletex => Value::TeletexString(string),
+            Charset::Videotex => Value::VideotexString(string),
+            Charset::Graphic => Value::GraphicString(string),
+            Charset::ObjectDescriptor => Value::ObjectDescriptor(string),
+        }
+    }
+
+    /// The value of the field with the given name of a `SEQUENCE` or `SET`, or the value of the
+    /// alternative of a `CHOICE` if it has the given name.
+    pub fn get(&self, name: &str) -> Option<&Value> {
+        match self {
+            Value::Sequence(fields) | Value::Set(fields) => fields
+                .iter()
+                .find(|(field, _)| field == name)
+                .map(|(_, value)| value),
+            Value::Choice(alternative, value) if alternative == name => Some(value),
+            _ => None,
+        }
+    }
+
+    pub fn as_integer(&self) -> Option<i64> {
+        match self {
+            Value::Integer(value) => Some(*value),
+            _ => None,
+        }
+    }
+
+    /// The content of any of the string variants
+    pub fn as_str(&self) -> Option<&str> {
+        match self {
+            Value::Utf8String(string)
+            | Value::Ia5String(string)
+            | Value::NumericString(string)
+            | Value::PrintableString(string)
+            | Value::VisibleString(string)
+            | Value::TeletexString(string)
+            | Value::VideotexString(string)
+            | Value::GraphicString(string)
+            | Value::ObjectDescriptor(string) => Some(string),
+            _ => None,
+        }
+    }
+
+    /// The name of the ASN.1 type this value represents
+    pub const fn type_name(&self) -> &'static str {
+        match self {
+            Value::Boolean(_) => "BOOLEAN",
+            Value::Integer(_) => "INTEGER",
+            Value::Null => "NULL",
+            Value::Enumerated(_) => "ENUMERATED",
+            Value::Utf8String(_) => "UTF8String",
+            Value::Ia5String(_) => "IA5String",
+            Value::NumericString(_) => "NumericString",
+            Value::PrintableString(_) => "PrintableString",
+            Value::VisibleString(_) => "VisibleString",
+            Value::TeletexString(_) => "TeletexString",
+            Value::VideotexString(_) => "VideotexString",
+            Value::GraphicString(_) => "GraphicString",
+            Value::ObjectDescriptor(_) => "ObjectDescriptor",
+            Value::OctetString(_) => "OCTET STRING",
+            Value::BitString(_) => "BIT STRING",
+            Value::Sequence(_) => "SEQUENCE",
+            Value::Set(_) => "SET",
+            Value::SequenceOf(_) => "SEQUENCE OF",
+            Value::SetOf(_) => "SET OF",
+            Value::Choice(..) => "CHOICE",
+        }
+    }
+}
+
+#[derive(Debug)]
+pub enum Error {
+    /// The schema has no definition with the given name
+    UnknownType(String),
+    /// The value is not compatible with the type (first) it is supposed to be encoded as
+    TypeMismatch(&'static str, &'static str),
+    /// The mandatory field with the given name is not present in the value
+    MissingField(String),
+    /// The value has a field with the given name which is not part of the definition
+    UnknownField(String),
+    /// The type has no variant or alternative with the given name
+    UnknownVariant(String),
+    Parse(asn1rs_model::parse::Error),
+    Resolve(asn1rs_model::resolve::Error),
+    Uper(UperError),
+    Der(DerError),
+}
+
+impl From<UperError> for Error {
+    fn from(e: UperError) -> Self {
+        Error::Uper(e)
+    }
+}
+
+impl From<DerError> for Error {
+    fn from(e: DerError) -> Self {
+        Error::Der(e)
+    }
+}
+
+impl From<asn1rs_model::parse::Error> for Error {
+    fn from(e: asn1rs_model::parse::Error) -> Self {
+        Error::Parse(e)
+    }
+}
+
+impl From<asn1rs_model::resolve::Error> for Error {
+    fn from(e: asn1rs_model::resolve::Error) -> Self {
+        Error::Resolve(e)
+    }
+}
+
+impl std::fmt::Display for Error {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            Error::UnknownType(name) => write!(f, "The schema has no type named {name:?}"),
+            Error::TypeMismatch(expected, got) => {
+                write!(f, "Expected a value of type {expected} but got {got}")
+            }
+            Error::MissingField(name) => write!(f, "The mandatory field {name:?} is missing"),
+            Error::UnknownField(name) => write!(f, "The field {name:?} is not defined"),
+            Error::UnknownVariant(name) => write!(f, "The variant {name:?} is not defined"),
+            Error::Parse(e) => write!(f, "{e:?}"),
+            Error::Resolve(e) => write!(f, "{e:?}"),
+            Error::Uper(e) => e.fmt(f),
+            Error::Der(e) => e.fmt(f),
+        }
+    }
+}
+
+impl std::error::Error for Error {}
+
+/// The type definitions of one or more ASN.1 modules, used to encode and decode [`Value`]s
+/// at runtime.
+#[derive(Debug, Clone, Default)]
+pub struct Schema {
+    definitions: HashMap<String, Rust>,
+}
+
+impl Schema {
+    /// Parses and resolves the given ASN.1 module. Use [`Schema::from_models`] for modules
+    /// that import definitions from other modules.
+    pub fn parse(asn: &str) -> Result<Self, Error> {
+        let model = Model::try_from(Tokenizer.parse(asn))?.try_resolve()?;
+        Ok(Self::from_models(&[model]))
+    }
+
+    /// Parses and resolves the given ASN.1 modules, which may import definitions from each
+    /// other.
+    pub fn parse_all<'a>(asn: impl IntoIterator<Item = &'a str>) -> Result<Self, Error> {
+        let mut resolver = MultiModuleResolver::default();
+        for asn in asn {
+            resolver.push(Model::try_from(Tokenizer.parse(asn))?);
+        }
+        Ok(Self::from_models(&resolver.try_resolve_all()?))
+    }
+
+    /// Creates a schema from already resolved models, which may import definitions from each
+    /// other.
+    pub fn from_models(models: &[Model<Asn<Resolved>>]) -> Self {
+        let scope = models.iter().collect::<Vec<_>>();
+        let mut schema = Self::default();
+        for model in models {
+            schema.add_model(model.to_rust_keep_names_with_scope(&scope[..]));
+        }
+        schema
+    }
+
+    pub fn add_model(&mut self, model: Model<Rust>) {
+        self.definitions.extend(
+            model
+                .definitions
+                .into_iter()
+                .map(|definition| (definition.0, definition.1)),
+        );
+    }
+
+    pub fn definition(&self, name: &str) -> Option<&Rust> {
+        self.definitions.get(name)
+    }
+
+    pub fn type_names(&self) -> impl Iterator<Item = &str> {
+        self.definitions.keys().map(String::as_str)
+    }
+
+    fn resolve(&self, name: &str) -> Result<&Rust, Error> {
+        self.definition(name)
+            .ok_or_else(|| Error::UnknownType(name.to_string()))
+    }
+}
+
+/// A field of a `SEQUENCE` or `SET`, or an alternative of a `CHOICE`, with the tag it is
+/// encoded with
+struct Component<'a> {
+    name: &'a str,
+    r#type: &'a RustType,
+    tag: Option<Tag>,
+}
+
+/// The number of components in the extension root, which is zero for an extensible `SEQUENCE`,
+/// `SET` or `CHOICE` without any components
+fn std_len(len: usize, extension_after: Option<usize>) -> usize {
+    extension_after.map_or(len, |after| (after + 1).min(len))
+}
+
+/// The fields of a `SEQUENCE` or `SET` in the order of their encoding. Mirrors the tag
+/// assignment and ordering of the constraints the code generator writes.
+fn struct_components<'a>(
+    fields: &'a [asn1rs_model::rust::Field],
+    ordering: EncodingOrdering,
+    extension_after: Option<usize>,
+) -> Vec<Component<'a>> {
+    let mut components = implicitly_tagged(
+        fields
+            .iter()
+            .map(|field| (field.name(), field.r#type(), field.tag())),
+    );
+    if ordering == EncodingOrdering::Sort {
+        let mut indexed = components.into_iter().enumerate().collect::<Vec<_>>();
+        indexed.sort_by_key(|(index, component)| {
+            (
+                extension_after.is_some_and(|after| *index > after),
+                component.tag.or_else(|| component.r#type.tag()),
+            )
+        });
+        components = indexed
+            .into_iter()
+            .map(|(_, component)| component)
+            .collect();
+    }
+    components
+}
+
+/// ITU-T X.680 | ISO/IEC 8824-1, G.2.12.3: if no component has a tag, the components are
+/// tagged automatically with increasing context specific tags
+fn implicitly_tagged<'a>(
+    components: impl Iterator<Item = (&'a str, &'a RustType, Option<Tag>)>,
+) -> Vec<Component<'a>> {
+    let mut components = components
+        .map(|(name, r#type, tag)| Component { name, r#type, tag })
+        .collect::<Vec<_>>();
+    if components.iter().all(|c| c.tag.is_none()) {
+        for (index, component) in components.iter_mut().enumerate() {
+            component.tag = Some(Tag::ContextSpecific(index));
+        }
+    }
+    components
+}
+
+/// The [`Value`] of a `DEFAULT` literal for the given type
+fn default_value(r#type: &RustType, literal: &LiteralValue) -> Value {
+    match literal {
+        LiteralValue::Boolean(value) => Value::Boolean(*value),
+        LiteralValue::Integer(value) => Value::Integer(*value),
+        LiteralValue::OctetString(value) => Value::OctetString(value.clone()),
+        LiteralValue::EnumeratedVariant(_, variant) => Value::Enumerated(variant.clone()),
+        LiteralValue::String(value) => match r#type.as_inner_type() {
+            RustType::String(_, charset, _) => Value::string(*charset, value.clone()),
+            _ => Value::Utf8String(value.clone()),
+        },
+    }
+}
+
+/// The lower bound, upper bound and the extensible flag of an integer type exceeding 64 bits
+fn wide_integer_range(r#type: &RustType) -> Option<(Option<i128>, Option<i128>, bool)> {
+    match r#type {
+        RustType::I128(range) => Some((*range.min(), *range.max(), range.extensible())),
+        RustType::U128(range) => Some((
+            range.min().map(|min| min as i128),
+            range.max().map(|max| max as i128),
+            range.extensible(),
+        )),
+        _ => None,
+    }
+}
+
+/// The lower bound, upper bound and the extensible flag of an integer type
+fn integer_range(r#type: &RustType) -> Option<(Option<i64>, Option<i64>, bool)> {
+    macro_rules! range {
+        ($range:expr) => {
+            Some((
+                Some(i64::from(*$range.min())),
+                Some(i64::from(*$range.max())),
+                $range.extensible(),
+            ))
+        };
+    }
+    match r#type {
+        RustType::I8(range) => range!(range),
+        RustType::U8(range) => range!(range),
+        RustType::I16(range) => range!(range),
+        RustType::U16(range) => range!(range),
+        RustType::I32(range) => range!(range),
+        RustType::U32(range) => range!(range),
+        RustType::I64(range) => range!(range),
+        RustType::U64(range) => Some((
+            range.min().map(|min| min as i64),
+            range.max().map(|max| max as i64),
+            range.extensible(),
+        )),
+        _ => None,
+    }
+}
+
+fn fields_of(value: &Value, ordering: EncodingOrdering) -> Result<&[(String, Value)], Error> {
+    match (value, ordering) {
+        (Value::Sequence(fields), EncodingOrdering::Keep) => Ok(fields),
+        (Value::Set(fields), EncodingOrdering::Sort) => Ok(fields),
+        (value, EncodingOrdering::Keep) => Err(Error::TypeMismatch("SEQUENCE", value.type_name())),
+        (value, EncodingOrdering::Sort) => Err(Error::TypeMismatch("SET", value.type_name())),
+    }
+}
+
+/// Looks up the value of each component and ensures that the value has no additional fields
+fn field_values<'v>(
+    components: &[Component<'_>],
+    values: &'v [(String, Value)],
+) -> Result<Vec<Option<&'v Value>>, Error> {
+    if let Some((name, _)) = values
+        .iter()
+        .find(|(name, _)| !components.iter().any(|c| c.name == name))
+    {
+        return Err(Error::UnknownField(name.clone()));
+    }
+    Ok(components
+        .iter()
+        .map(|c| {
+            values
+                .iter()
+                .find(|(name, _)| name == c.name)
+                .map(|(_, value)| value)
+        })
+        .collect())
+}
+
+/// Whether the field is encoded at all: absent fields are not, neither are `DEFAULT` fields
+/// with their default value
+fn is_present(r#type: &RustType, value: Option<&Value>) -> bool {
+    match (r#type, value) {
+        (_, None) => false,
+        (RustType::Default(inner, literal), Some(value)) => default_value(inner, literal).ne(value),
+        (_, Some(_)) => true,
+    }
+}
+
+const fn is_optional(r#type: &RustType) -> bool {
+    matches!(r#type, RustType::Option(_) | RustType::Default(..))
+}
+
+fn with_fields(ordering: EncodingOrdering, fields: Vec<(String, Value)>) -> Value {
+    match ordering {
+        EncodingOrdering::Keep => Value::Sequence(fields),
+        EncodingOrdering::Sort => Value::Set(fields),
+    }
+}
+
+fn with_elements(ordering: EncodingOrdering, elements: Vec<Value>) -> Value {
+    match ordering {
+        EncodingOrdering::Keep => Value::SequenceOf(elements),
+        EncodingOrdering::Sort => Value::SetOf(elements),
+    }
+}
+
+fn elements_of(value: &Value, ordering: EncodingOrdering) -> Result<&[Value], Error> {
+    match (value, ordering) {
+        (Value::SequenceOf(elements), EncodingOrdering::Keep) => Ok(elements),
+        (Value::SetOf(elements), EncodingOrdering::Sort) => Ok(elements),
+        (value, EncodingOrdering::Keep) => {
+            Err(Error::TypeMismatch("SEQUENCE OF", value.type_name()))
+        }
+        (value, EncodingOrdering::Sort) => Err(Error::TypeMismatch("SET OF", value.type_name())),
+    }
+}
+
+/// Restores the order of definition for the fields of a `SET`, which are decoded in the order
+/// of their encoding
+fn in_definition_order(fields: &[asn1rs_model::rust::Field], values: &mut [(String, Value)]) {
+    values.sort_by_key(|(name, _)| fields.iter().position(|f| f.name() == name));
+}
+
+fn mismatch<T>(expected: &'static str, value: &Value) -> Result<T, Error> {
+    Err(Error::TypeMismatch(expected, value.type_name()))
+}
+
+fn variant_index<'a>(mut names: impl Iterator<Item = &'a str>, name: &str) -> Result<usize, Error> {
+    names
+        .position(|variant| variant == name)
+        .ok_or_else(|| Error::UnknownVariant(name.to_string()))
+}
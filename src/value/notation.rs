@@ -0,0 +1,102 @@
+use super::Value;
+use crate::rw::Notation;
+use std::fmt::{Display, Formatter};
+
+impl Value {
+    /// Renders the value in the same notation as the [`crate::rw::ValueNotationWriter`] does
+    /// for generated types
+    pub fn to_notation(&self, notation: Notation) -> String {
+        let mut output = String::new();
+        self.write_notation(notation, 0, &mut output);
+        output
+    }
+
+    fn write_notation(&self, notation: Notation, depth: usize, output: &mut String) {
+        match self {
+            Value::Boolean(value) => output.push_str(if *value { "TRUE" } else { "FALSE" }),
+            Value::Integer(value) => output.push_str(&value.to_string()),
+            Value::Null => output.push_str("NULL"),
+            Value::Enumerated(name) => output.push_str(name),
+            Value::OctetString(bytes) => {
+                output.push('\'');
+                for byte in bytes {
+                    output.push_str(&format!("{:02X}", byte));
+                }
+                output.push_str("'H");
+            }
+            Value::BitString(bits) => {
+                output.push('\'');
+                for bit in 0..bits.bit_len() {
+                    output.push(if bits.is_bit_set(bit) { '1' } else { '0' });
+                }
+                output.push_str("'B");
+            }
+            Value::Sequence(fields) | Value::Set(fields) => write_constructed(
+                notation,
+                depth,
+                output,
+                fields
+                    .iter()
+                    .map(|(name, value)| (Some(name.as_str()), value)),
+            ),
+            Value::SequenceOf(values) | Value::SetOf(values) => write_constructed(
+                notation,
+                depth,
+                output,
+                values.iter().map(|value| (None, value)),
+            ),
+            Value::Choice(name, value) => {
+                output.push_str(name);
+                output.push_str(match notation {
+                    Notation::Asn1 => " : ",
+                    Notation::Gser => ":",
+                });
+                value.write_notation(notation, depth, output);
+            }
+            string => {
+                output.push('"');
+                output.push_str(&string.as_str().unwrap_or_default().replace('"', "\"\""));
+                output.push('"');
+            }
+        }
+    }
+}
+
+fn write_constructed<'a>(
+    notation: Notation,
+    depth: usize,
+    output: &mut String,
+    components: impl Iterator<Item = (Option<&'a str>, &'a Value)>,
+) {
+    let new_line = |output: &mut String, depth: usize| match notation {
+        Notation::Asn1 => {
+            output.push('\n');
+            output.push_str(&"  ".repeat(depth));
+        }
+        Notation::Gser => output.push(' '),
+    };
+    output.push('{');
+    let mut written = 0;
+    for (name, value) in components {
+        if written > 0 {
+            output.push(',');
+        }
+        new_line(output, depth + 1);
+        if let Some(name) = name {
+            output.push_str(name);
+            output.push(' ');
+        }
+        value.write_notation(notation, depth + 1, output);
+        written += 1;
+    }
+    if written > 0 {
+        new_line(output, depth);
+    }
+    output.push('}');
+}
+
+impl Display for Value {
+    fn fmt(&self, f: &mut Formatter<'_>) -> std::fmt::Result {
+        f.write_str(&self.to_notation(Notation::Asn1))
+    }
+}
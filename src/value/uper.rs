@@ -0,0 +1,630 @@
+//! Unaligned PER for [`Value`]s, mirroring the behaviour of [`crate::rw::UperWriter`] and
+//! [`crate::rw::UperReader`] for generated types
+
+use super::*;
+use crate::protocol::per::unaligned::buffer::{BitBuffer, Bits};
+use crate::protocol::per::unaligned::{BitRead, BitWrite, ScopedBitRead};
+use crate::protocol::per::{ErrorKind, PackedRead, PackedWrite};
+use crate::rw::{read_wide_whole_number, with_scratch_buffer, write_wide_whole_number};
+use asn1rs_model::asn::{PermittedAlphabet, Size};
+use asn1rs_model::rust::UUID_SIZE;
+use std::borrow::Cow;
+
+impl Schema {
+    /// Decodes a value of the given type from UPER encoded bytes
+    pub fn decode_uper(&self, type_name: &str, bytes: &[u8]) -> Result<Value, Error> {
+        self.decode_uper_bits(type_name, bytes, bytes.len() * 8)
+    }
+
+    /// Decodes a value of the given type from the first `bit_len` bits of the given bytes
+    pub fn decode_uper_bits(
+        &self,
+        type_name: &str,
+        bytes: &[u8],
+        bit_len: usize,
+    ) -> Result<Value, Error> {
+        self.read_uper_definition(&mut Bits::from((bytes, bit_len)), type_name, None)
+    }
+
+    /// Encodes the value as the given type in UPER
+    pub fn encode_uper(&self, type_name: &str, value: &Value) -> Result<Vec<u8>, Error> {
+        let mut buffer = BitBuffer::default();
+        self.write_uper_definition(&mut buffer, type_name, value)?;
+        Ok(buffer.into())
+    }
+
+    fn write_uper_definition(
+        &self,
+        buffer: &mut BitBuffer,
+        name: &str,
+        value: &Value,
+    ) -> Result<(), Error> {
+        match self.resolve(name)? {
+            Rust::Struct {
+                ordering,
+                fields,
+                extension_after,
+                ..
+            } => {
+                let components = struct_components(fields, *ordering, *extension_after);
+                let values = field_values(&components, fields_of(value, *ordering)?)?;
+                self.write_uper_fields(buffer, &components, &values, *extension_after)
+            }
+            Rust::TupleStruct { r#type, .. } => self.write_uper_type(buffer, r#type, value),
+            Rust::Enum(enumeration) => {
+                let Value::Enumerated(variant) = value else {
+                    return mismatch("ENUMERATED", value);
+                };
+                let index = variant_index(enumeration.variants().map(String::as_str), variant)?;
+                buffer.write_enumeration_index(
+                    std_variants(enumeration.len(), enumeration.extension_after_index()),
+                    enumeration.is_extensible(),
+                    enumeration.enumeration_index(index) as u64,
+                )?;
+                Ok(())
+            }
+            Rust::DataEnum(enumeration) => {
+                let Value::Choice(alternative, value) = value else {
+                    return mismatch("CHOICE", value);
+                };
+                let index = variant_index(enumeration.variants().map(|v| v.name()), alternative)?;
+                let std_variants =
+                    std_variants(enumeration.len(), enumeration.extension_after_index());
+                buffer.write_choice_index(
+                    std_variants,
+                    enumeration.is_extensible(),
+                    index as u64,
+                )?;
+                // unwrap: the index was just looked up
+                let r#type = enumeration.variants().nth(index).unwrap().r#type();
+                if index as u64 >= std_variants {
+                    self.write_uper_open_type(buffer, r#type, value)
+                } else {
+                    self.write_uper_type(buffer, r#type, value)
+                }
+            }
+        }
+    }
+
+    fn write_uper_fields(
+        &self,
+        buffer: &mut BitBuffer,
+        components: &[Component<'_>],
+        values: &[Option<&Value>],
+        extension_after: Option<usize>,
+    ) -> Result<(), Error> {
+        let std_len = std_len(components.len(), extension_after);
+        let presence = components
+            .iter()
+            .zip(values)
+            .map(|(c, value)| is_present(c.r#type, *value))
+            .collect::<Vec<_>>();
+
+        for (component, value) in components.iter().zip(values).take(std_len) {
+            if value.is_none() && !is_optional(component.r#type) {
+                return Err(Error::MissingField(component.name.to_string()));
+            }
+        }
+
+        let extensions_present = presence[std_len..].iter().any(|present| *present);
+        if extension_after.is_some() {
+            buffer.write_bit(extensions_present)?;
+        }
+
+        for (component, present) in components.iter().zip(&presence).take(std_len) {
+            if is_optional(component.r#type) {
+                buffer.write_bit(*present)?;
+            }
+        }
+
+        for ((component, value), present) in
+            components.iter().zip(values).zip(&presence).take(std_len)
+        {
+            if let (true, Some(value)) = (*present, value) {
+                self.write_uper_type(buffer, component.r#type, value)?;
+            }
+        }
+
+        if extensions_present {
+            let extensions = &presence[std_len..];
+            buffer.write_normally_small_length(extensions.len() as u64 - 1)?;
+            for present in extensions {
+                buffer.write_bit(*present)?;
+            }
+            for ((component, value), present) in
+                components.iter().zip(values).zip(&presence).skip(std_len)
+            {
+                if let (true, Some(value)) = (*present, value) {
+                    self.write_uper_open_type(buffer, component.r#type, value)?;
+                }
+            }
+        }
+
+        Ok(())
+    }
+
+    /// ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 11.2: the encoding of the value is wrapped
+    /// into an octet string
+    fn write_uper_open_type(
+        &self,
+        buffer: &mut BitBuffer,
+        r#type: &RustType,
+        value: &Value,
+    ) -> Result<(), Error> {
+        with_scratch_buffer(|scratch| {
+            let mut content = BitBuffer::from_bits(core::mem::take(scratch), 0);
+            let result = self.write_uper_type(&mut content, r#type, value);
+            *scratch = content.buffer;
+            result?;
+            if scratch.is_empty() {
+                // an open type consists of at least one octet
+                buffer.write_octetstring(None, None, false, &[0x00])?;
+            } else {
+                buffer.write_octetstring(None, None, false, scratch)?;
+            }
+            Ok(())
+        })
+    }
+
+    fn write_uper_type(
+        &self,
+        buffer: &mut BitBuffer,
+        r#type: &RustType,
+        value: &Value,
+    ) -> Result<(), Error> {
+        match r#type {
+            RustType::Bool => match value {
+                Value::Boolean(value) => Ok(buffer.write_boolean(*value)?),
+                value => mismatch("BOOLEAN", value),
+            },
+            RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_) => {
+                let Some(value) = value.as_integer() else {
+                    return mismatch("INTEGER", value);
+                };
+                // unwrap: matched integer types above
+                let (min, max, extensible) = integer_range(r#type).unwrap();
+                let unconstrained = if extensible {
+                    let out_of_range = value < min.unwrap_or(0) || value > max.unwrap_or(i64::MAX);
+                    buffer.write_bit(out_of_range)?;
+                    out_of_range
+                } else {
+                    min.is_none() && max.is_none()
+                };
+                if unconstrained {
+                    buffer.write_unconstrained_whole_number(value)?;
+                } else {
+                    buffer.write_constrained_whole_number(
+                        min.unwrap_or(0),
+                        max.unwrap_or(i64::MAX),
+                        value,
+                    )?;
+                }
+                Ok(())
+            }
+            RustType::I128(_) | RustType::U128(_) => {
+                let Some(value) = value.as_integer() else {
+                    return mismatch("INTEGER", value);
+                };
+                // unwrap: matched wide integer types above
+                let (min, max, extensible) = wide_integer_range(r#type).unwrap();
+                Ok(write_wide_whole_number(
+                    buffer,
+                    min,
+                    max,
+                    extensible,
+                    i128::from(value),
+                )?)
+            }
+            RustType::String(size, charset, alphabet) => {
+                let Some(string) = value.as_str() else {
+                    return mismatch("string", value);
+                };
+                write_string(buffer, size, *charset, alphabet.as_ref(), string)
+            }
+            RustType::VecU8(size) => match value {
+                Value::OctetString(octets) => {
+                    let (min, max) = size_bounds(size);
+                    Ok(buffer.write_octetstring(min, max, size.extensible(), octets)?)
+                }
+                value => mismatch("OCTET STRING", value),
+            },
+            RustType::Uuid => self.write_uper_type(buffer, &RustType::VecU8(UUID_SIZE), value),
+            RustType::BitVec(size) => match value {
+                Value::BitString(bits) => {
+                    let (min, max) = size_bounds(size);
+                    Ok(buffer.write_bitstring(
+                        min,
+                        max,
+                        size.extensible(),
+                        bits.as_byte_slice(),
+                        0,
+                        bits.bit_len(),
+                    )?)
+                }
+                value => mismatch("BIT STRING", value),
+            },
+            RustType::Vec(inner, size, ordering) => {
+                let elements = elements_of(value, *ordering)?;
+                let (min, max) = size_bounds(size);
+                write_length(buffer, size.extensible(), min, max, elements.len() as u64)?;
+                elements
+                    .iter()
+                    .try_for_each(|element| self.write_uper_type(buffer, inner, element))
+            }
+            RustType::Null => match value {
+                Value::Null => Ok(()),
+                value => mismatch("NULL", value),
+            },
+            RustType::Option(inner) | RustType::Default(inner, _) | RustType::Box(inner) => {
+                self.write_uper_type(buffer, inner, value)
+            }
+            RustType::Complex(name, _) => self.write_uper_definition(buffer, name, value),
+        }
+    }
+
+    pub(crate) fn read_uper_definition(
+        &self,
+        bits: &mut Bits<'_>,
+        name: &str,
+        recovery: Option<&mut Recovery>,
+    ) -> Result<Value, Error> {
+        match self.resolve(name)? {
+            Rust::Struct {
+                ordering,
+                fields,
+                extension_after,
+                ..
+            } => {
+                let components = struct_components(fields, *ordering, *extension_after);
+                let mut values =
+                    self.read_uper_fields(bits, &components, *extension_after, recovery)?;
+                if *ordering == EncodingOrdering::Sort {
+                    in_definition_order(fields, &mut values);
+                }
+                Ok(with_fields(*ordering, values))
+            }
+            Rust::TupleStruct { r#type, .. } => self.read_uper_type(bits, r#type, recovery),
+            Rust::Enum(enumeration) => {
+                let index = bits.read_enumeration_index(
+                    std_variants(enumeration.len(), enumeration.extension_after_index()),
+                    enumeration.is_extensible(),
+                )?;
+                enumeration
+                    .variant_index(index as usize)
+                    .and_then(|index| enumeration.variants().nth(index))
+                    .map(|variant| Value::Enumerated(variant.clone()))
+                    .ok_or_else(|| invalid_index(index, enumeration.len()))
+            }
+            Rust::DataEnum(enumeration) => {
+                let std_variants =
+                    std_variants(enumeration.len(), enumeration.extension_after_index());
+                let index = bits.read_choice_index(std_variants, enumeration.is_extensible())?;
+                let variant = enumeration
+                    .variants()
+                    .nth(index as usize)
+                    .ok_or_else(|| invalid_index(index, enumeration.len()))?;
+                let value = scoped(recovery, Segment::Field(variant.name()), |recovery| {
+                    if index >= std_variants {
+                        self.read_uper_open_type(bits, variant.r#type(), recovery)
+                    } else {
+                        self.read_uper_type(bits, variant.r#type(), recovery)
+                    }
+                })?;
+                Ok(Value::Choice(variant.name().to_string(), Box::new(value)))
+            }
+        }
+    }
+
+    fn read_uper_fields(
+        &self,
+        bits: &mut Bits<'_>,
+        components: &[Component<'_>],
+        extension_after: Option<usize>,
+        mut recovery: Option<&mut Recovery>,
+    ) -> Result<Vec<(String, Value)>, Error> {
+        let std_len = std_len(components.len(), extension_after);
+        let extensions_present = extension_after.is_some() && bits.read_bit()?;
+
+        let mut presence = Vec::with_capacity(components.len());
+        for component in &components[..std_len] {
+            presence.push(!is_optional(component.r#type) || bits.read_bit()?);
+        }
+
+        let mut values = Vec::with_capacity(components.len());
+        for (component, present) in components.iter().zip(presence) {
+            if present {
+                let value = recover(
+                    recovery.as_deref_mut(),
+                    Segment::Field(component.name),
+                    |recovery| self.read_uper_type(bits, component.r#type, recovery),
+                )?;
+                match value {
+                    Some(value) => values.push((component.name.to_string(), value)),
+                    // the position of the following fields is unknown
+                    None => return Ok(desynchronized(values, recovery)),
+                }
+                if Recovery::is_desynchronized(&recovery) {
+                    return Ok(values);
+                }
+            } else if let RustType::Default(inner, literal) = component.r#type {
+                values.push((component.name.to_string(), default_value(inner, literal)));
+            }
+        }
+
+        let mut extensions = Vec::new();
+        if extensions_present {
+            let len = bits.read_normally_small_length()? + 1;
+            for _ in 0..len {
+                extensions.push(bits.read_bit()?);
+            }
+        }
+
+        for (index, component) in components.iter().enumerate().skip(std_len) {
+            if extensions.get(index - std_len).copied().unwrap_or(false) {
+                let value = recover(
+                    recovery.as_deref_mut(),
+                    Segment::Field(component.name),
+                    |recovery| self.read_uper_open_type(bits, component.r#type, recovery),
+                )?;
+                if let Some(value) = value {
+                    values.push((component.name.to_string(), value));
+                }
+            } else if let RustType::Default(inner, literal) = component.r#type {
+                values.push((component.name.to_string(), default_value(inner, literal)));
+            }
+        }
+
+        // skip the content of extensions which are unknown to this schema
+        for _ in extensions
+            .iter()
+            .skip(components.len() - std_len)
+            .filter(|p| **p)
+        {
+            let _ = bits.read_octetstring(None, None, false)?;
+        }
+
+        Ok(values)
+    }
+
+    fn read_uper_open_type(
+        &self,
+        bits: &mut Bits<'_>,
+        r#type: &RustType,
+        mut recovery: Option<&mut Recovery>,
+    ) -> Result<Value, Error> {
+        let content = bits.read_octetstring(None, None, false)?;
+        let value = self.read_uper_type(
+            &mut Bits::from(&content[..]),
+            r#type,
+            recovery.as_deref_mut(),
+        );
+        // the open type is skipped as a whole, regardless of where its content failed
+        if let Some(recovery) = recovery {
+            recovery.set_desynchronized(false);
+        }
+        value
+    }
+
+    fn read_uper_type(
+        &self,
+        bits: &mut Bits<'_>,
+        r#type: &RustType,
+        mut recovery: Option<&mut Recovery>,
+    ) -> Result<Value, Error> {
+        Ok(match r#type {
+            RustType::Bool => Value::Boolean(bits.read_boolean()?),
+            RustType::I8(_)
+            | RustType::U8(_)
+            | RustType::I16(_)
+            | RustType::U16(_)
+            | RustType::I32(_)
+            | RustType::U32(_)
+            | RustType::I64(_)
+            | RustType::U64(_) => {
+                // unwrap: matched integer types above
+                let (min, max, extensible) = integer_range(r#type).unwrap();
+                let unconstrained = if extensible {
+                    bits.read_bit()?
+                } else {
+                    min.is_none() && max.is_none()
+                };
+                Value::Integer(if unconstrained {
+                    bits.read_unconstrained_whole_number()?
+                } else {
+                    bits.read_constrained_whole_number(min.unwrap_or(0), max.unwrap_or(i64::MAX))?
+                })
+            }
+            RustType::I128(_) | RustType::U128(_) => {
+                // unwrap: matched wide integer types above
+                let (min, max, extensible) = wide_integer_range(r#type).unwrap();
+                let value = read_wide_whole_number(bits, min, max, extensible)?;
+                // values are limited to 64 bits
+                Value::Integer(i64::try_from(value).map_err(|_| {
+                    UperError::from(ErrorKind::ValueNotInRange(
+                        value,
+                        i128::from(i64::MIN),
+                        i128::from(i64::MAX),
+                    ))
+                })?)
+            }
+            RustType::String(size, charset, alphabet) => {
+                read_string(bits, size, *charset, alphabet.as_ref())?
+            }
+            RustType::VecU8(size) => {
+                let (min, max) = size_bounds(size);
+                Value::OctetString(bits.read_octetstring(min, max, size.extensible())?)
+            }
+            RustType::Uuid => self.read_uper_type(bits, &RustType::VecU8(UUID_SIZE), recovery)?,
+            RustType::BitVec(size) => {
+                let (min, max) = size_bounds(size);
+                let (bytes, bit_len) = bits.read_bitstring(min, max, size.extensible())?;
+                Value::BitString(BitVec::from_bytes(bytes, bit_len))
+            }
+            RustType::Vec(inner, size, ordering) => {
+                let (min, max) = size_bounds(size);
+                let len = read_length(bits, size.extensible(), min, max)?;
+                let mut elements = Vec::with_capacity((len as usize).min(bits.remaining()));
+                for index in 0..len as usize {
+                    let element = recover(
+                        recovery.as_deref_mut(),
+                        Segment::Element(index),
+                        |recovery| self.read_uper_type(bits, inner, recovery),
+                    )?;
+                    match element {
+                        Some(element) => elements.push(element),
+                        None => {
+                            return Ok(desynchronized(with_elements(*ordering, elements), recovery))
+                        }
+                    }
+                    if Recovery::is_desynchronized(&recovery) {
+                        break;
+                    }
+                }
+                with_elements(*ordering, elements)
+            }
+            RustType::Null => Value::Null,
+            RustType::Option(inner) | RustType::Default(inner, _) | RustType::Box(inner) => {
+                self.read_uper_type(bits, inner, recovery)?
+            }
+            RustType::Complex(name, _) => self.read_uper_definition(bits, name, recovery)?,
+        })
+    }
+}
+
+/// Marks the best-effort decode as having lost its position in the bit stream, so that the
+/// enclosing values stop decoding as well and the given partial value is returned
+fn desynchronized<T>(value: T, recovery: Option<&mut Recovery>) -> T {
+    if let Some(recovery) = recovery {
+        recovery.set_desynchronized(true);
+    }
+    value
+}
+
+fn std_variants(len: usize, extension_after: Option<usize>) -> u64 {
+    std_len(len, extension_after) as u64
+}
+
+fn size_bounds(size: &Size) -> (Option<u64>, Option<u64>) {
+    (
+        size.min().map(|min| *min as u64),
+        size.max().map(|max| *max as u64),
+    )
+}
+
+fn invalid_index(index: u64, len: usize) -> Error {
+    Error::Uper(ErrorKind::InvalidChoiceIndex(index, len as u64).into())
+}
+
+/// See [`crate::rw::UperWriter::write_extensible_bit_and_length_or_err`]
+fn write_length(
+    buffer: &mut BitBuffer,
+    extensible: bool,
+    min: Option<u64>,
+    max: Option<u64>,
+    len: u64,
+) -> Result<(), Error> {
+    let unwrapped_min = min.unwrap_or(0);
+    let unwrapped_max = max.unwrap_or(u64::MAX);
+    let out_of_range = len < unwrapped_min || len > unwrapped_max;
+
+    if extensible {
+        buffer.write_bit(out_of_range)?;
+    }
+
+    if out_of_range {
+        if !extensible {
+            return Err(UperError::from(ErrorKind::SizeNotInRange(
+                len,
+                unwrapped_min,
+                unwrapped_max,
+            ))
+            .into());
+        }
+        buffer.write_length_determinant(None, None, len)?;
+    } else {
+        buffer.write_length_determinant(min, max, len)?;
+    }
+    Ok(())
+}
+
+fn read_length(
+    bits: &mut Bits<'_>,
+    extensible: bool,
+    min: Option<u64>,
+    max: Option<u64>,
+) -> Result<u64, Error> {
+    Ok(if extensible && bits.read_bit()? {
+        bits.read_length_determinant(None, None)?
+    } else {
+        bits.read_length_determinant(min, max)?
+    })
+}
+
+fn write_string(
+    buffer: &mut BitBuffer,
+    size: &Size,
+    charset: Charset,
+    alphabet: Option<&PermittedAlphabet>,
+    string: &str,
+) -> Result<(), Error> {
+    let (min, max) = size_bounds(size);
+    let len = string.chars().count() as u64;
+    UperError::ensure_string_valid(charset, string)?;
+
+    match known_multiplier_alphabet(charset, alphabet) {
+        Some(alphabet) => {
+            write_length(buffer, size.extensible(), min, max, len)?;
+            buffer.write_known_multiplier_characters(&alphabet, string)?;
+        }
+        None => {
+            if !size.extensible() {
+                let min = min.unwrap_or(0);
+                let max = max.unwrap_or(u64::MAX);
+                if len < min || len > max {
+                    return Err(UperError::from(ErrorKind::SizeNotInRange(len, min, max)).into());
+                }
+            }
+            // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.3
+            // For 'known-multiplier character string types' there is no min/max in the encoding
+            buffer.write_octetstring(None, None, false, string.as_bytes())?;
+        }
+    }
+    Ok(())
+}
+
+fn read_string(
+    bits: &mut Bits<'_>,
+    size: &Size,
+    charset: Charset,
+    alphabet: Option<&PermittedAlphabet>,
+) -> Result<Value, Error> {
+    let string = match known_multiplier_alphabet(charset, alphabet) {
+        Some(alphabet) => {
+            let (min, max) = size_bounds(size);
+            let len = read_length(bits, size.extensible(), min, max)?;
+            bits.read_known_multiplier_characters(&alphabet, len)?
+        }
+        None => String::from_utf8(bits.read_octetstring(None, None, false)?)
+            .map_err(|e| Error::Uper(ErrorKind::FromUtf8Error(e).into()))?,
+    };
+    Ok(Value::string(charset, string))
+}
+
+/// The effective permitted alphabet of known-multiplier character string types
+fn known_multiplier_alphabet(
+    charset: Charset,
+    alphabet: Option<&PermittedAlphabet>,
+) -> Option<Cow<'static, str>> {
+    let characters = charset.known_multiplier_characters()?;
+    Some(match alphabet {
+        Some(alphabet) => Cow::Owned(alphabet.as_string()),
+        None => Cow::Borrowed(characters),
+    })
+}
@@ -0,0 +1,74 @@
+//! Bindings of the generated types for JavaScript through `wasm-bindgen`, so that the codecs of
+//! a backend can be reused as they are in the browser. Values cross the boundary as their JER
+//! representation (ITU-T X.697), which `JSON.parse` turns into plain objects, and the encoded
+//! bytes as `Uint8Array`. The codec is selected by its id, see [`Codec::id`].
+//!
+//! The runtime builds for `wasm32-unknown-unknown` with `--no-default-features --features
+//! macros,wasm`. [`wasm_bindings!`](crate::wasm_bindings) exports the functions of the given
+//! types:
+//!
+//! ```rust
+//! use asn1rs::prelude::*;
+//!
+//! #[asn(sequence)]
+//! #[derive(Debug, PartialEq)]
+//! pub struct Reading {
+//!     #[asn(integer(0..255))]
+//!     pub level: u8,
+//! }
+//!
+//! // `encodeReading(codec, json)` and `decodeReading(codec, bytes)` in JavaScript
+//! asn1rs::wasm_bindings!(Reading => encodeReading, decodeReading);
+//!
+//! let uper = asn1rs::wasm::encode::<Reading>(0, r#"{"level":200}"#).unwrap();
+//! assert_eq!(vec![200], uper);
+//! assert_eq!(r#"{"level":200}"#, asn1rs::wasm::decode::<Reading>(0, &uper).unwrap());
+//! ```
+
+use crate::codec::{Codec, Error};
+use crate::descriptor::{Readable, Writable};
+use crate::util::{from_jer_str, to_jer_string};
+
+pub use wasm_bindgen;
+
+/// Reads the value from its JER representation and encodes it with the codec of the given id
+pub fn encode<T: Readable + Writable>(codec: u8, json: &str) -> Result<Vec<u8>, Error> {
+    let codec = Codec::from_id(codec).ok_or(Error::UnknownCodec(codec))?;
+    codec.encode(&from_jer_str::<T>(json)?)
+}
+
+/// Decodes the value with the codec of the given id and writes its JER representation
+pub fn decode<T: Readable + Writable>(codec: u8, bytes: &[u8]) -> Result<String, Error> {
+    let codec = Codec::from_id(codec).ok_or(Error::UnknownCodec(codec))?;
+    Ok(to_jer_string(&codec.decode::<T>(bytes)?))
+}
+
+/// Exports an encode and a decode function for each of the given types to JavaScript, which
+/// delegate to [`encode`] and [`decode`] and throw an `Error` with the message of a failure.
+/// The crate using the macro does not need a direct dependency on `wasm-bindgen`.
+#[macro_export]
+macro_rules! wasm_bindings {
+    ($($ty:ty => $encode:ident, $decode:ident);+ $(;)?) => {
+        $(
+            #[$crate::wasm::wasm_bindgen::prelude::wasm_bindgen(
+                wasm_bindgen = $crate::wasm::wasm_bindgen
+            )]
+            pub fn $encode(
+                codec: u8,
+                json: &str,
+            ) -> ::core::result::Result<::std::vec::Vec<u8>, $crate::wasm::wasm_bindgen::JsError> {
+                Ok($crate::wasm::encode::<$ty>(codec, json)?)
+            }
+
+            #[$crate::wasm::wasm_bindgen::prelude::wasm_bindgen(
+                wasm_bindgen = $crate::wasm::wasm_bindgen
+            )]
+            pub fn $decode(
+                codec: u8,
+                bytes: &[u8],
+            ) -> ::core::result::Result<::std::string::String, $crate::wasm::wasm_bindgen::JsError> {
+                Ok($crate::wasm::decode::<$ty>(codec, bytes)?)
+            }
+        )+
+    };
+}
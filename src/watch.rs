@@ -0,0 +1,148 @@
+//! Converts the ASN.1 files of directories again whenever they change, see the `watch` command
+//! and the `--watch` option of the command line interface. The files are polled, so that no
+//! platform specific file system notifications are required.
+
+use asn1rs::build::find_sources;
+use asn1rs::converter::Error;
+use std::path::{Path, PathBuf};
+use std::time::{Duration, SystemTime};
+
+pub const POLL_INTERVAL: Duration = Duration::from_millis(500);
+
+/// The path, modification time and length of each ASN.1 file in the watched directories
+#[derive(Debug, Default, Clone, PartialEq, Eq)]
+pub struct Snapshot(Vec<(PathBuf, Option<SystemTime>, u64)>);
+
+impl Snapshot {
+    pub fn take<P: AsRef<Path>>(dir: P) -> Result<Self, Error> {
+        Self::take_all(&[dir])
+    }
+
+    pub fn take_all<P: AsRef<Path>>(dirs: &[P]) -> Result<Self, Error> {
+        let mut sources = Vec::new();
+        for dir in dirs {
+            sources.extend(find_sources(dir)?);
+        }
+        sources
+            .into_iter()
+            .map(|path| {
+                let metadata = std::fs::metadata(&path)?;
+                Ok((path, metadata.modified().ok(), metadata.len()))
+            })
+            .collect::<Result<Vec<_>, Error>>()
+            .map(Self)
+    }
+
+    pub fn files(&self) -> Vec<String> {
+        self.0
+            .iter()
+            .map(|(path, _, _)| path.display().to_string())
+            .collect()
+    }
+
+    /// The files that were added, removed or modified since the previous snapshot
+    pub fn changed_since(&self, previous: &Self) -> Vec<String> {
+        let mut changed = self
+            .0
+            .iter()
+            .filter(|file| !previous.0.contains(file))
+            .chain(
+                previous
+                    .0
+                    .iter()
+                    .filter(|(path, ..)| !self.0.iter().any(|(p, ..)| p == path)),
+            )
+            .map(|(path, ..)| path.display().to_string())
+            .collect::<Vec<_>>();
+        changed.sort();
+        changed
+    }
+}
+
+/// The ASN.1 files of the watched directories and the ones that changed
+#[derive(Debug, Clone, PartialEq, Eq)]
+pub struct Changes {
+    pub files: Vec<String>,
+    /// The files that were added, removed or modified, all files if `initial`
+    pub changed: Vec<String>,
+    /// Whether the files are reported for the first time, or for the first time after the
+    /// directories could not be read
+    pub initial: bool,
+}
+
+/// Calls `on_change` with the ASN.1 files of the directories initially and whenever a file is
+/// added, removed or modified. A failure to read the directories is passed on once, until they
+/// can be read again.
+pub fn watch<P: AsRef<Path>, F: FnMut(Result<Changes, Error>)>(dirs: &[P], mut on_change: F) -> ! {
+    let mut last: Option<Option<Snapshot>> = None;
+    loop {
+        let snapshot = Snapshot::take_all(dirs);
+        let current = snapshot.as_ref().ok().cloned();
+        if last.as_ref() != Some(&current) {
+            on_change(snapshot.map(|snapshot| {
+                let files = snapshot.files();
+                match last.as_ref().and_then(Option::as_ref) {
+                    Some(previous) => Changes {
+                        changed: snapshot.changed_since(previous),
+                        files,
+                        initial: false,
+                    },
+                    None => Changes {
+                        changed: files.clone(),
+                        files,
+                        initial: true,
+                    },
+                }
+            }));
+            last = Some(current);
+        }
+        std::thread::sleep(POLL_INTERVAL);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    pub fn test_snapshot_detects_changes() {
+        let dir = std::env::temp_dir().join(format!("asn1rs-watch-{}", std::process::id()));
+        let _ = std::fs::remove_dir_all(&dir);
+        std::fs::create_dir_all(&dir).unwrap();
+
+        std::fs::write(dir.join("a.asn1"), "A DEFINITIONS ::= BEGIN END").unwrap();
+        std::fs::write(dir.join("notes.txt"), "ignored").unwrap();
+        let first = Snapshot::take(&dir).unwrap();
+        assert_eq!(
+            vec![dir.join("a.asn1").display().to_string()],
+            first.files()
+        );
+        assert_eq!(first, Snapshot::take(&dir).unwrap());
+
+        std::fs::write(dir.join("notes.txt"), "still ignored").unwrap();
+        assert_eq!(first, Snapshot::take(&dir).unwrap());
+
+        std::fs::write(dir.join("a.asn1"), "A DEFINITIONS ::= BEGIN\n\nEND").unwrap();
+        let second = Snapshot::take(&dir).unwrap();
+        assert_ne!(first, second);
+        assert_eq!(
+            vec![dir.join("a.asn1").display().to_string()],
+            second.changed_since(&first)
+        );
+
+        std::fs::write(dir.join("b.asn"), "B DEFINITIONS ::= BEGIN END").unwrap();
+        let third = Snapshot::take(&dir).unwrap();
+        assert_eq!(2, third.files().len());
+        assert_eq!(
+            vec![dir.join("b.asn").display().to_string()],
+            third.changed_since(&second)
+        );
+        assert_eq!(
+            vec![dir.join("b.asn").display().to_string()],
+            second.changed_since(&third)
+        );
+
+        std::fs::remove_dir_all(&dir).unwrap();
+        assert!(Snapshot::take(&dir).is_err());
+    }
+}
@@ -0,0 +1,136 @@
+#![cfg(feature = "arbitrary")]
+
+use asn1rs::arbitrary::{assert_uper_round_trip, assert_uper_round_trips, Unstructured};
+use asn1rs::prelude::*;
+
+asn_to_rust!(
+    r"ArbitraryRoundTrip DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Potato ::= SEQUENCE {
+        size INTEGER (-5..300),
+        name PrintableString (SIZE(1..8)),
+        tags SEQUENCE SIZE(0..4) OF IA5String,
+        raw OCTET STRING,
+        bits BIT STRING (SIZE(3..20)),
+        color Color OPTIONAL,
+        ...,
+        origin UTF8String OPTIONAL,
+        weight INTEGER OPTIONAL
+    }
+
+    Color ::= ENUMERATED { red, green, blue }
+
+    Vegetable ::= CHOICE {
+        potato Potato,
+        count INTEGER (0..65535),
+        nothing NULL
+    }
+
+    END"
+);
+
+// The following impls are the output of the `ArbitraryImplGenerator` for the definitions above
+
+impl<'a> ::asn1rs::arbitrary::Arbitrary<'a> for Potato {
+    fn arbitrary(
+        u: &mut ::asn1rs::arbitrary::Unstructured<'a>,
+    ) -> ::asn1rs::arbitrary::Result<Self> {
+        let extensions = u.arbitrary::<bool>()?;
+        Ok(Self {
+            size: u.int_in_range(-5..=300)?,
+            name: ::asn1rs::arbitrary::string(
+                u,
+                1,
+                Some(8),
+                ::asn1rs::arbitrary::Charset::Printable,
+            )?,
+            tags: ::asn1rs::arbitrary::vec(u, 0, Some(4), |u| {
+                ::asn1rs::arbitrary::string(u, 0, None, ::asn1rs::arbitrary::Charset::Ia5)
+            })?,
+            raw: ::asn1rs::arbitrary::octets(u, 0, None)?,
+            bits: ::asn1rs::arbitrary::bit_vec(u, 3, Some(20))?,
+            color: if u.arbitrary::<bool>()? {
+                Some(u.arbitrary::<Color>()?)
+            } else {
+                None
+            },
+            origin: if extensions {
+                Some(::asn1rs::arbitrary::string(
+                    u,
+                    0,
+                    None,
+                    ::asn1rs::arbitrary::Charset::Utf8,
+                )?)
+            } else {
+                None
+            },
+            weight: if extensions {
+                Some(u.int_in_range(0..=9223372036854775807)?)
+            } else {
+                None
+            },
+        })
+    }
+}
+
+impl<'a> ::asn1rs::arbitrary::Arbitrary<'a> for Color {
+    fn arbitrary(
+        u: &mut ::asn1rs::arbitrary::Unstructured<'a>,
+    ) -> ::asn1rs::arbitrary::Result<Self> {
+        Ok(*u.choose(&Self::variants())?)
+    }
+}
+
+impl<'a> ::asn1rs::arbitrary::Arbitrary<'a> for Vegetable {
+    fn arbitrary(
+        u: &mut ::asn1rs::arbitrary::Unstructured<'a>,
+    ) -> ::asn1rs::arbitrary::Result<Self> {
+        Ok(match u.int_in_range(0..=2)? {
+            0 => Self::Potato(u.arbitrary::<Potato>()?),
+            1 => Self::Count(u.int_in_range(0..=65535)?),
+            2 => Self::Nothing(Null),
+            _ => unreachable!(),
+        })
+    }
+}
+
+/// Deterministic pseudo random input data (xorshift), so failures are reproducible
+fn pseudo_random_bytes(seed: u64, len: usize) -> Vec<u8> {
+    let mut state = seed;
+    (0..len)
+        .map(|_| {
+            state ^= state << 13;
+            state ^= state >> 7;
+            state ^= state << 17;
+            state as u8
+        })
+        .collect()
+}
+
+#[test]
+fn test_generated_values_respect_constraints() {
+    let data = pseudo_random_bytes(0x5EED, 4096);
+    let mut u = Unstructured::new(&data);
+    while !u.is_empty() {
+        let potato = u.arbitrary::<Potato>().unwrap();
+        assert!((-5..=300).contains(&potato.size));
+        assert!((1..=8).contains(&potato.name.chars().count()));
+        assert!(potato.tags.len() <= 4);
+        assert!((3..=20).contains(&potato.bits.bit_len()));
+        assert_eq!(potato.origin.is_some(), potato.weight.is_some());
+    }
+}
+
+#[test]
+fn test_uper_round_trip() {
+    for seed in 1..32 {
+        assert_uper_round_trips::<Vegetable>(&pseudo_random_bytes(seed, 2048)).unwrap();
+    }
+}
+
+#[test]
+fn test_uper_round_trip_of_exhausted_input() {
+    let mut u = Unstructured::new(&[]);
+    assert_uper_round_trip(&u.arbitrary::<Vegetable>().unwrap());
+}
@@ -0,0 +1,121 @@
+use asn1rs::descriptor::asn1_module;
+use asn1rs::prelude::*;
+use asn1rs::value::{Schema, Value};
+
+#[asn(enumerated)]
+#[derive(Debug, Default, Copy, Clone, PartialEq, Asn1Schema)]
+pub enum Topping {
+    #[default]
+    NotPineapple,
+    EvenLessPineapple,
+}
+
+#[asn(choice)]
+#[derive(Debug, Clone, PartialEq, Asn1Schema)]
+pub enum Filling {
+    #[asn(integer(0..255))]
+    Weight(u8),
+    #[asn(utf8string, tag(APPLICATION(2)))]
+    Name(String),
+}
+
+#[asn(transparent)]
+#[derive(Debug, Default, Clone, PartialEq, Asn1Schema)]
+pub struct Label(#[asn(ia5string(size(1..16)))] pub String);
+
+#[asn(sequence)]
+#[derive(Debug, Clone, PartialEq, Asn1Schema)]
+pub struct Pizza {
+    #[asn(integer(1..4))]
+    pub size_in_steps: u8,
+    #[asn(complex(Topping, tag(UNIVERSAL(10))))]
+    pub topping: Topping,
+    #[asn(sequence_of(complex(Filling, tag(APPLICATION(2)))))]
+    pub fillings: Vec<Filling>,
+    #[asn(optional(complex(Label, tag(UNIVERSAL(22)))))]
+    pub label: Option<Label>,
+    #[asn(default(boolean, true))]
+    pub hot: bool,
+}
+
+fn module() -> String {
+    asn1_module(
+        "Pizzeria",
+        &[
+            Topping::ASN1_DEFINITION,
+            Filling::ASN1_DEFINITION,
+            Label::ASN1_DEFINITION,
+            Pizza::ASN1_DEFINITION,
+        ],
+    )
+}
+
+#[test]
+fn test_definitions() {
+    assert_eq!(
+        "Topping ::= ENUMERATED {\n    notPineapple,\n    evenLessPineapple\n}\n",
+        Topping::ASN1_DEFINITION
+    );
+    assert_eq!(
+        "Filling ::= CHOICE {\n    weight INTEGER (0..255),\n    name [APPLICATION 2] UTF8String\n}\n",
+        Filling::ASN1_DEFINITION
+    );
+    assert_eq!(
+        "Label ::= IA5String (SIZE(1..16))\n",
+        Label::ASN1_DEFINITION
+    );
+    assert_eq!(
+        r"Pizza ::= SEQUENCE {
+    size-in-steps INTEGER (1..4),
+    topping Topping,
+    fillings SEQUENCE OF Filling,
+    label Label OPTIONAL,
+    hot BOOLEAN DEFAULT TRUE
+}
+",
+        Pizza::ASN1_DEFINITION
+    );
+}
+
+#[test]
+fn test_module_encodes_like_the_rust_types() {
+    let pizza = Pizza {
+        size_in_steps: 3,
+        topping: Topping::EvenLessPineapple,
+        fillings: vec![Filling::Weight(200), Filling::Name("ham".to_string())],
+        label: Some(Label("Hawaii".to_string())),
+        hot: false,
+    };
+
+    let mut writer = UperWriter::default();
+    writer.write(&pizza).unwrap();
+
+    let schema = Schema::parse(&module()).unwrap();
+    let value = schema.decode_uper("Pizza", writer.byte_content()).unwrap();
+    assert_eq!(
+        Value::Sequence(vec![
+            ("size-in-steps".to_string(), Value::Integer(3)),
+            (
+                "topping".to_string(),
+                Value::Enumerated("evenLessPineapple".to_string())
+            ),
+            (
+                "fillings".to_string(),
+                Value::SequenceOf(vec![
+                    Value::Choice("weight".to_string(), Box::new(Value::Integer(200))),
+                    Value::Choice(
+                        "name".to_string(),
+                        Box::new(Value::Utf8String("ham".to_string()))
+                    ),
+                ])
+            ),
+            ("label".to_string(), Value::Ia5String("Hawaii".to_string())),
+            ("hot".to_string(), Value::Boolean(false)),
+        ]),
+        value
+    );
+    assert_eq!(
+        writer.byte_content(),
+        &schema.encode_uper("Pizza", &value).unwrap()[..]
+    );
+}
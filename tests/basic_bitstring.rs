@@ -32,9 +32,49 @@ asn_to_rust!(
         } (SIZE(2))
     }
     
+    Permissions ::= BIT STRING { read(0), write(1), execute(5) }
+    
+    Marker ::= BIT STRING { first(0), fourth(3) } (SIZE(8))
+    
     END"
 );
 
+#[test]
+fn test_named_bit_accessors() {
+    let mut permissions = Permissions::default();
+    assert!(!permissions.is_write());
+    permissions.set_write(true);
+    permissions.set_execute(true);
+    assert!(permissions.is_write());
+    assert!(permissions.is_execute());
+    assert!(!permissions.is_read());
+    permissions.set_execute(false);
+    assert!(!permissions.is_execute());
+    assert_eq!(5, Permissions::EXECUTE);
+}
+
+#[test]
+fn test_named_bits_trailing_zeros_are_not_encoded() {
+    let mut permissions = Permissions(BitVec::with_len(16));
+    permissions.set_write(true);
+    let (bits, bytes) = serialize_uper(&permissions);
+    assert_eq!((10, &[0x02, 0x40][..]), (bits, &bytes[..]));
+    let decoded = deserialize_uper::<Permissions>(&bytes, bits);
+    assert_eq!(2, decoded.bit_len());
+    assert!(decoded.is_write());
+
+    serialize_and_deserialize_uper(8, &[0x00], &Permissions::default());
+}
+
+#[test]
+fn test_named_bits_are_padded_to_the_lower_bound() {
+    let mut marker = Marker::default();
+    marker.set_first(true);
+    let (bits, bytes) = serialize_uper(&marker);
+    assert_eq!((8, &[0x80][..]), (bits, &bytes[..]));
+    assert!(deserialize_uper::<Marker>(&bytes, bits).is_first());
+}
+
 #[test]
 fn test_some_container_flag_set() {
     let mut c = SomeContainer {
@@ -141,3 +181,48 @@ fn test_extensible_extended_7() {
         },
     );
 }
+
+#[test]
+fn test_unconstrained_fragmented_40000_bits() {
+    // 32K bits in the first fragment, the remaining 7232 bits with a two byte length
+    unconstrained_fragments(40000, &[(0, &[0xC2]), (1 + 4096, &[0x9C, 0x40])]);
+}
+
+#[test]
+fn test_unconstrained_fragmented_100000_bits() {
+    // 64K bits, 32K bits and the remaining 1696 bits
+    unconstrained_fragments(
+        100_000,
+        &[
+            (0, &[0xC4]),
+            (1 + 8192, &[0xC2]),
+            (2 + 8192 + 4096, &[0x86, 0xA0]),
+        ],
+    );
+}
+
+#[test]
+fn test_unconstrained_fragmented_64k_bits() {
+    unconstrained_fragments(65536, &[(0, &[0xC4]), (1 + 8192, &[0x00])]);
+}
+
+/// Checks the length determinants at the given byte positions of the serialized value
+fn unconstrained_fragments(bit_len: u64, length_determinants: &[(usize, &[u8])]) {
+    let bytes = (0..bit_len.div_ceil(8)).map(|i| (i % 251) as u8).collect();
+    let value = Unconstrained {
+        abc: BitVec::from_bytes(bytes, bit_len),
+    };
+    let (bits, bytes) = serialize_uper(&value);
+    let overhead = length_determinants
+        .iter()
+        .map(|(_, determinant)| determinant.len())
+        .sum::<usize>();
+    assert_eq!(bit_len as usize + overhead * 8, bits);
+    for (position, determinant) in length_determinants {
+        assert_eq!(
+            *determinant,
+            &bytes[*position..*position + determinant.len()]
+        );
+    }
+    assert_eq!(value, deserialize_uper::<Unconstrained>(&bytes, bits));
+}
@@ -510,3 +510,44 @@ fn test_basic_variants_parsed() {
         Basic::Abc(_) | Basic::Def(_) | Basic::Ghi(_) => {}
     }
 }
+
+#[test]
+fn test_extensible_variants_metadata() {
+    use asn1rs::model::asn::Tag;
+
+    assert_eq!(
+        ["Abc", "Def", "Ghi", "Jkl", "Mno"],
+        Extensible::VARIANT_NAMES
+    );
+    assert_eq!(
+        ["String", "u64", "u64", "Basic", "String"],
+        Extensible::VARIANT_TYPES
+    );
+    assert_eq!(Some(Tag::DEFAULT_UTF8_STRING), Extensible::VARIANT_TAGS[0]);
+    assert_eq!(
+        "Jkl",
+        Extensible::VARIANT_NAMES[Extensible::Jkl(Basic::Ghi(1)).value_index()]
+    );
+}
+
+#[test]
+fn test_peek_variant_uper() {
+    let (bits, bytes) = serialize_uper(&Extensible::Jkl(Basic::Def("routed".to_string())));
+    let mut reader = UperReader::from((&bytes[..], bits));
+    assert_eq!(
+        ExtensibleVariantKind::Jkl,
+        Extensible::peek_variant(&mut reader).unwrap()
+    );
+    // peeking does not consume the message
+    assert_eq!(
+        Extensible::Jkl(Basic::Def("routed".to_string())),
+        reader.read::<Extensible>().unwrap()
+    );
+
+    let (bits, bytes) = serialize_uper(&MoreThan63Extensions::E64(64));
+    let mut reader = UperReader::from((&bytes[..], bits));
+    let kind = MoreThan63Extensions::peek_variant(&mut reader).unwrap();
+    assert_eq!(MoreThan63ExtensionsVariantKind::E64, kind);
+    assert_eq!(kind, MoreThan63Extensions::E64(64).variant_kind());
+    assert_eq!(bits, reader.bits_remaining());
+}
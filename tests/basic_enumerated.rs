@@ -28,6 +28,20 @@ asn_to_rust!(
         qrs(15)
     }
 
+    Gaps ::= ENUMERATED {
+        a(1),
+        b(4),
+        c(9)
+    }
+
+    Unordered ::= ENUMERATED {
+        high(9),
+        low(1),
+        ...,
+        later(20),
+        latest
+    }
+
     
     END"
 );
@@ -90,3 +104,92 @@ fn test_der_basic() {
     serialize_and_deserialize_der(&[0x0A, 0x01, 0x01], &Basic::Def);
     serialize_and_deserialize_der(&[0x0A, 0x01, 0x02], &Basic::Ghi);
 }
+
+#[test]
+fn test_declared_numbers() {
+    assert_eq!(9, Gaps::C.value());
+    assert_eq!(Some(Gaps::B), Gaps::from_value(4));
+    assert_eq!(None, Gaps::from_value(2));
+    assert_eq!(2, Gaps::C.value_index());
+    assert_eq!(21, Unordered::Latest.value());
+    assert_eq!(Some(Unordered::Later), Unordered::from_value(20));
+}
+
+#[test]
+fn test_uper_encodes_the_index_of_the_ordered_numbers() {
+    serialize_and_deserialize_uper(2, &[0x00], &Gaps::A);
+    serialize_and_deserialize_uper(2, &[0x40], &Gaps::B);
+    serialize_and_deserialize_uper(2, &[0x80], &Gaps::C);
+
+    serialize_and_deserialize_uper(2, &[0x40], &Unordered::High);
+    serialize_and_deserialize_uper(2, &[0x00], &Unordered::Low);
+    serialize_and_deserialize_uper(8, &[0x80], &Unordered::Later);
+    serialize_and_deserialize_uper(8, &[0x81], &Unordered::Latest);
+}
+
+#[test]
+fn test_der_encodes_the_numbers() {
+    serialize_and_deserialize_der(&[0x0A, 0x01, 0x05], &PredefinedNumbers::Def);
+    serialize_and_deserialize_der(&[0x0A, 0x01, 0x08], &PredefinedNumbers::Ghi);
+    serialize_and_deserialize_der(&[0x0A, 0x01, 0x09], &Gaps::C);
+    serialize_and_deserialize_der(&[0x0A, 0x01, 0x09], &Unordered::High);
+    serialize_and_deserialize_der(&[0x0A, 0x01, 0x15], &Unordered::Latest);
+}
+
+#[test]
+fn test_jer_uses_the_identifiers() {
+    for value in [Unordered::High, Unordered::Low, Unordered::Later] {
+        let json = asn1rs::codec::Codec::Jer.encode(&value).unwrap();
+        assert_eq!(
+            value,
+            asn1rs::codec::Codec::Jer
+                .decode::<Unordered>(&json)
+                .unwrap()
+        );
+    }
+    assert_eq!(
+        b"\"high\"".to_vec(),
+        asn1rs::codec::Codec::Jer.encode(&Unordered::High).unwrap()
+    );
+}
+
+// generated for `Level ::= ENUMERATED { low(10), high(300) }` with `set_enums_have_repr(true)`
+#[asn(enumerated)]
+#[derive(Debug, Clone, PartialEq, Hash, Copy, PartialOrd, Eq, Default)]
+#[repr(u16)]
+pub enum Level {
+    #[default]
+    #[asn(10)]
+    Low = 10,
+    #[asn(300)]
+    High = 300,
+}
+
+impl ::core::convert::TryFrom<u64> for Level {
+    type Error = u64;
+
+    fn try_from(value: u64) -> Result<Self, u64> {
+        match value {
+            10 => Ok(Level::Low),
+            300 => Ok(Level::High),
+            _ => Err(value),
+        }
+    }
+}
+
+impl ::core::convert::From<Level> for u64 {
+    fn from(value: Level) -> Self {
+        value as u64
+    }
+}
+
+#[test]
+fn test_repr_discriminants_are_the_numbers() {
+    assert_eq!(300, Level::High as u16);
+    assert_eq!(10, u64::from(Level::Low));
+    assert_eq!(Ok(Level::High), Level::try_from(300));
+    assert_eq!(Err(11), Level::try_from(11));
+
+    serialize_and_deserialize_uper(1, &[0x80], &Level::High);
+    serialize_and_deserialize_der(&[0x0A, 0x02, 0x01, 0x2C], &Level::High);
+}
@@ -8,9 +8,25 @@ asn_to_rust!(
     
     NotRanged ::= Integer
     
+    State ::= INTEGER { idle(0), busy(1), occupied(1) } (0..255)
+    
     END"
 );
 
+#[test]
+fn test_named_numbers() {
+    assert_eq!(State(1), State::BUSY);
+    assert_eq!(State::BUSY, State::OCCUPIED);
+    assert_eq!(Some("IDLE"), State::IDLE.name());
+    assert_eq!(Some("BUSY"), State::OCCUPIED.name());
+    assert_eq!(None, State(7).name());
+
+    // the encoding of the integer is kept
+    let mut writer = UperWriter::default();
+    writer.write(&State::BUSY).unwrap();
+    assert_eq!(&[0x01], writer.byte_content());
+}
+
 #[test]
 fn test_whether_it_compiles_at_all() {}
 
@@ -59,3 +75,53 @@ fn test_uper_big() {
 
     assert_eq!(&[0x03, bytes[5], bytes[6], bytes[7]], writer.byte_content());
 }
+
+// generated for `Level ::= INTEGER (1..10)` with `set_integers_validated(true)`
+#[asn(transparent)]
+#[derive(Debug, Clone, PartialEq, Hash)]
+pub struct Level(#[asn(integer(1..10))] u8);
+
+impl Level {
+    pub const fn try_new(value: u8) -> Result<Self, u8> {
+        if matches!(value, 1..=10) {
+            Ok(Self(value))
+        } else {
+            Err(value)
+        }
+    }
+}
+
+impl ::core::convert::TryFrom<u8> for Level {
+    type Error = u8;
+
+    fn try_from(value: u8) -> Result<Self, Self::Error> {
+        Self::try_new(value)
+    }
+}
+
+impl ::core::convert::From<Level> for u8 {
+    fn from(value: Level) -> Self {
+        value.0
+    }
+}
+
+impl Default for Level {
+    fn default() -> Self {
+        Self(1)
+    }
+}
+
+#[test]
+fn test_validated_construction() {
+    assert_eq!(Err(0), Level::try_new(0));
+    assert_eq!(Err(11), Level::try_from(11));
+    assert_eq!(10, u8::from(Level::try_new(10).unwrap()));
+    assert_eq!(Ok(Level::default()), Level::try_new(1));
+
+    let mut writer = UperWriter::default();
+    writer.write(&Level::try_new(3).unwrap()).unwrap();
+    assert_eq!(&[0x20], writer.byte_content());
+
+    let mut reader = writer.as_reader();
+    assert_eq!(Level::try_new(3), Ok(reader.read::<Level>().unwrap()));
+}
@@ -0,0 +1,123 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::descriptor::{graphicstring, objectdescriptor, teletexstring, videotexstring};
+use asn1rs::value::Value;
+use test_utils::*;
+
+asn_to_rust!(
+    r"BasicLegacyStrings DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Directory ::= SEQUENCE {
+        graphic GraphicString,
+        videotex VideotexString (SIZE(1..4)),
+        teletex TeletexString,
+        t61 T61String OPTIONAL,
+        descriptor ObjectDescriptor,
+        iso646 ISO646String (SIZE(2))
+    }
+
+    Utf8Directory ::= SEQUENCE {
+        graphic UTF8String,
+        videotex UTF8String (SIZE(1..4)),
+        teletex UTF8String,
+        t61 UTF8String OPTIONAL,
+        descriptor UTF8String,
+        iso646 VisibleString (SIZE(2))
+    }
+
+    Control ::= SEQUENCE {
+        abc IA5String
+    }
+
+    END"
+);
+
+#[test]
+fn test_encoded_like_utf8string() {
+    let directory = Directory {
+        graphic: "Müller".to_string(),
+        videotex: "abcd".to_string(),
+        teletex: "c=DE".to_string(),
+        t61: Some("o=ACME".to_string()),
+        descriptor: "X.500 directory".to_string(),
+        iso646: "ok".to_string(),
+    };
+    let utf8 = Utf8Directory {
+        graphic: directory.graphic.clone(),
+        videotex: directory.videotex.clone(),
+        teletex: directory.teletex.clone(),
+        t61: directory.t61.clone(),
+        descriptor: directory.descriptor.clone(),
+        iso646: directory.iso646.clone(),
+    };
+    let (bits, bytes) = serialize_uper(&utf8);
+    serialize_and_deserialize_uper(bits, &bytes, &directory);
+}
+
+#[test]
+#[should_panic(expected = "SizeNotInRange(5, 1, 4)")]
+fn test_size_constraint_is_checked() {
+    serialize_uper(&Directory {
+        graphic: String::default(),
+        videotex: "abcde".to_string(),
+        teletex: String::default(),
+        t61: None,
+        descriptor: String::default(),
+        iso646: "ok".to_string(),
+    });
+}
+
+#[test]
+fn test_universal_tags() {
+    use asn1rs::descriptor::common::Constraint;
+    use asn1rs::model::asn::Tag;
+
+    assert_eq!(Tag::Universal(20), teletexstring::NoConstraint::TAG);
+    assert_eq!(Tag::Universal(21), videotexstring::NoConstraint::TAG);
+    assert_eq!(Tag::Universal(25), graphicstring::NoConstraint::TAG);
+    assert_eq!(Tag::Universal(7), objectdescriptor::NoConstraint::TAG);
+
+    let schema = asn1rs::value::Schema::parse(
+        r"BasicLegacyStringsTags DEFINITIONS ::=
+        BEGIN
+        Universal ::= SEQUENCE {
+            graphic GraphicString,
+            descriptor ObjectDescriptor,
+            -- tagged to prevent automatic tagging
+            visible [UNIVERSAL 26] VisibleString
+        }
+        END",
+    )
+    .unwrap();
+    let value = Value::Sequence(vec![
+        (
+            "graphic".to_string(),
+            Value::GraphicString("ab".to_string()),
+        ),
+        (
+            "descriptor".to_string(),
+            Value::ObjectDescriptor("cd".to_string()),
+        ),
+        ("visible".to_string(), Value::VisibleString("e".to_string())),
+    ]);
+    let bytes = schema.encode_der("Universal", &value).unwrap();
+    assert_eq!(
+        &[0x30, 0x0B, 0x19, 0x02, b'a', b'b', 0x07, 0x02, b'c', b'd', 0x1A, 0x01, b'e'][..],
+        &bytes[..]
+    );
+    assert_eq!(value, schema.decode_der("Universal", &bytes).unwrap());
+}
+
+#[test]
+fn test_ia5string_control_characters() {
+    serialize_and_deserialize_uper(
+        7 * 3 + 8,
+        &[0x03, 0x01, 0xFF, 0xF8],
+        &Control {
+            abc: "\u{0}\u{7F}\u{7F}".to_string(),
+        },
+    );
+}
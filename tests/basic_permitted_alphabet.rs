@@ -0,0 +1,125 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r#"BasicPermittedAlphabet DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Upper ::= SEQUENCE {
+        abc IA5String (FROM ("A".."Z"))
+    }
+
+    Octal ::= SEQUENCE {
+        abc IA5String (SIZE(1..4) ^ FROM ("0".."7"))
+    }
+
+    Binary ::= SEQUENCE {
+        abc IA5String (SIZE(2)) (FROM ("AB"))
+    }
+
+    Printable ::= SEQUENCE {
+        abc IA5String (FROM (" ".."~"))
+    }
+
+    Digits ::= SEQUENCE {
+        abc NumericString (FROM ("0".."9"))
+    }
+
+    END"#
+);
+
+#[test]
+fn test_indexed_characters() {
+    // H=7, E=4, L=11, L=11, O=14 with 5 bits each
+    serialize_and_deserialize_uper(
+        8 + 5 * 5,
+        &[0x05, 0x39, 0x16, 0xB7, 0x00],
+        &Upper {
+            abc: "HELLO".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_indexed_characters_with_size() {
+    serialize_and_deserialize_uper(
+        2 + 4 * 3,
+        &[0xC1, 0xBC],
+        &Octal {
+            abc: "0157".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_serial_constraints() {
+    serialize_and_deserialize_uper(
+        2,
+        &[0x80],
+        &Binary {
+            abc: "BA".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_characters_not_indexed_when_the_value_fits() {
+    // ITU-T X.691 | ISO/IEC 8825-2:2015, chapter 30.5.5: the greatest value (0x7E) fits
+    // into 7 bits, so the characters are encoded as they are
+    serialize_and_deserialize_uper(
+        8 + 2 * 7,
+        &[0x02, 0x91, 0xA4],
+        &Printable {
+            abc: "Hi".to_string(),
+        },
+    );
+}
+
+#[test]
+fn test_numeric_string_without_space() {
+    // '0' is at index 0 instead of 1, because the space is not permitted
+    serialize_and_deserialize_uper(
+        8 + 2 * 4,
+        &[0x02, 0x42],
+        &Digits {
+            abc: "42".to_string(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "CharacterNotPermitted('b', 1)")]
+fn test_character_not_permitted() {
+    serialize_uper(&Upper {
+        abc: "Ab".to_string(),
+    });
+}
+
+#[test]
+fn test_value_matches_generated_code() {
+    use asn1rs::value::{Schema, Value};
+    let schema = Schema::parse(
+        r#"BasicPermittedAlphabet DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+        Octal ::= SEQUENCE {
+            abc IA5String (SIZE(1..4) ^ FROM ("0".."7"))
+        }
+        END"#,
+    )
+    .unwrap();
+    let value = Value::Sequence(vec![(
+        "abc".to_string(),
+        Value::Ia5String("0157".to_string()),
+    )]);
+    assert_eq!(
+        &[0xC1, 0xBC][..],
+        &schema.encode_uper("Octal", &value).unwrap()[..]
+    );
+    assert_eq!(
+        value,
+        schema.decode_uper_bits("Octal", &[0xC1, 0xBC], 14).unwrap()
+    );
+}
@@ -723,3 +723,80 @@ pub struct NestedExtensibleStruct {
     #[asn(optional(complex(ExtensibleStruct, tag(UNIVERSAL(16)))))]
     inner: Option<ExtensibleStruct>,
 }
+
+/// Like [`InlinedPotato`], but with `#[inline(never)]` `Readable` and `Writable` impls
+#[asn(sequence, no_inline)]
+#[derive(Debug, Default, PartialOrd, PartialEq)]
+pub struct OutlinedPotato {
+    #[asn(integer)]
+    size: u64,
+    #[asn(complex(Potato, tag(UNIVERSAL(16))))]
+    inner: Potato,
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, PartialOrd, PartialEq)]
+pub struct InlinedPotato {
+    #[asn(integer)]
+    size: u64,
+    #[asn(complex(Potato, tag(UNIVERSAL(16))))]
+    inner: Potato,
+}
+
+#[asn(choice, no_inline)]
+#[derive(Debug, PartialOrd, PartialEq)]
+pub enum OutlinedChoice {
+    #[asn(complex(OutlinedPotato, tag(UNIVERSAL(16))))]
+    Potato(OutlinedPotato),
+    #[asn(integer(0..255))]
+    Other(u8),
+}
+
+#[test]
+fn test_no_inline_has_the_same_encoding() {
+    let potato = || Potato {
+        size: 123,
+        size2: 1234,
+        size3: 128,
+        string: String::from("where is the content"),
+    };
+    let outlined = OutlinedPotato {
+        size: 42,
+        inner: potato(),
+    };
+    let inlined = InlinedPotato {
+        size: 42,
+        inner: potato(),
+    };
+
+    let mut expected = UperWriter::default();
+    expected.write(&inlined).unwrap();
+    let mut uper = UperWriter::default();
+    uper.write(&outlined).unwrap();
+    assert_eq!(expected.byte_content(), uper.byte_content());
+    assert_eq!(expected.bit_len(), uper.bit_len());
+
+    let mut uper = uper.as_reader();
+    assert_eq!(outlined, uper.read::<OutlinedPotato>().unwrap());
+    assert_eq!(0, uper.bits_remaining());
+}
+
+#[test]
+fn test_no_inline_choice_uper() {
+    for choice in [
+        OutlinedChoice::Potato(OutlinedPotato {
+            size: 7,
+            inner: Potato {
+                size3: 12,
+                ..Default::default()
+            },
+        }),
+        OutlinedChoice::Other(200),
+    ] {
+        let mut uper = UperWriter::default();
+        uper.write(&choice).unwrap();
+        let mut uper = uper.as_reader();
+        assert_eq!(choice, uper.read::<OutlinedChoice>().unwrap());
+        assert_eq!(0, uper.bits_remaining());
+    }
+}
@@ -63,3 +63,139 @@ fn test_extensible() {
         },
     );
 }
+
+// generated for `Connection ::= SEQUENCE { host UTF8String, port INTEGER (0..65535) DEFAULT 443,
+// comment UTF8String OPTIONAL }` with `set_builders(true)` and `set_views(true)`
+#[asn(sequence)]
+#[derive(Default, Debug, Clone, PartialEq, Hash)]
+pub struct Connection {
+    #[asn(utf8string)]
+    pub host: String,
+    #[asn(default(integer(0..65535), 443))]
+    pub port: u16,
+    #[asn(optional(utf8string))]
+    pub comment: Option<String>,
+}
+
+impl Connection {
+    pub fn builder() -> ConnectionBuilder {
+        ConnectionBuilder::default()
+    }
+}
+
+#[derive(Default, Debug, Clone)]
+pub struct ConnectionBuilder {
+    host: Option<String>,
+    port: Option<u16>,
+    comment: Option<String>,
+}
+
+impl ConnectionBuilder {
+    pub fn host(self, value: String) -> Self {
+        Self {
+            host: Some(value),
+            ..self
+        }
+    }
+
+    pub fn port(self, value: u16) -> Self {
+        Self {
+            port: Some(value),
+            ..self
+        }
+    }
+
+    pub fn comment(self, value: String) -> Self {
+        Self {
+            comment: Some(value),
+            ..self
+        }
+    }
+
+    pub fn build(self) -> Result<Connection, &'static str> {
+        Ok(Connection {
+            host: self.host.ok_or("host")?,
+            port: self.port.unwrap_or(443),
+            comment: self.comment,
+        })
+    }
+}
+
+#[derive(Default, Debug, Clone, PartialEq)]
+pub struct ConnectionView {
+    pub host: Option<String>,
+    pub port: Option<u16>,
+    pub comment: Option<String>,
+}
+
+impl Connection {
+    pub fn read_view<R: ::asn1rs::descriptor::Reader>(
+        reader: &mut R,
+        fields: &[&str],
+    ) -> Result<ConnectionView, R::Error> {
+        reader.read_sequence::<Self, ConnectionView, _>(|reader| {
+            let mut view = ConnectionView::default();
+            let value = AsnDefConnectionFieldHost::read_value(reader)?;
+            if fields.contains(&"host") {
+                view.host = Some(value);
+            }
+            let value = AsnDefConnectionFieldPort::read_value(reader)?;
+            if fields.contains(&"port") {
+                view.port = Some(value);
+            }
+            let value = AsnDefConnectionFieldComment::read_value(reader)?;
+            if fields.contains(&"comment") {
+                view.comment = value;
+            }
+            Ok(view)
+        })
+    }
+}
+
+#[test]
+fn test_builder() {
+    assert_eq!(Err("host"), Connection::builder().port(80).build());
+
+    let connection = Connection::builder()
+        .host("db".to_string())
+        .build()
+        .unwrap();
+    assert_eq!(
+        Connection {
+            host: "db".to_string(),
+            port: 443,
+            comment: None,
+        },
+        connection
+    );
+    // neither the default port nor the comment is present
+    serialize_and_deserialize_uper(2 + 8 + 16, &[0x00, 0x99, 0x18, 0x80], &connection);
+}
+
+#[test]
+fn test_view() {
+    let connection = Connection {
+        host: "db".to_string(),
+        port: 80,
+        comment: Some("primary".to_string()),
+    };
+    let (bits, bytes) = serialize_uper(&connection);
+
+    let mut reader = UperReader::from((&bytes[..], bits));
+    let view = Connection::read_view(&mut reader, &["port"]).unwrap();
+    assert_eq!(
+        ConnectionView {
+            port: Some(80),
+            ..Default::default()
+        },
+        view
+    );
+    // the fields that are not selected are read nonetheless
+    assert_eq!(0, reader.bits_remaining());
+
+    let mut reader = UperReader::from((&bytes[..], bits));
+    let view = Connection::read_view(&mut reader, &["host", "comment"]).unwrap();
+    assert_eq!(Some("db"), view.host.as_deref());
+    assert_eq!(Some("primary"), view.comment.as_deref());
+    assert_eq!(None, view.port);
+}
@@ -0,0 +1,228 @@
+#![cfg(feature = "ffi")]
+
+use asn1rs::codec::Codec;
+use asn1rs::ffi::{ASN1RS_BUFFER_TOO_SMALL, ASN1RS_ENCODE_FAILED, ASN1RS_INVALID_VALUE, ASN1RS_OK};
+use asn1rs::model::generate::c::CGenerator;
+use asn1rs::model::parse::Tokenizer;
+use asn1rs::model::Model;
+use asn1rs::prelude::BitVec;
+use std::mem::MaybeUninit;
+
+const GATEWAY: &str = r"Gateway DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Priority ::= ENUMERATED { low, normal, high }
+
+    Label ::= UTF8String (SIZE(1..32))
+
+    Item ::= SEQUENCE {
+        id       INTEGER (0..65535),
+        label    Label,
+        payload  OCTET STRING,
+        flags    BIT STRING (SIZE(8)),
+        default  BOOLEAN DEFAULT TRUE
+    }
+
+    Command ::= CHOICE {
+        ping   NULL,
+        items  SEQUENCE OF Item,
+        note   UTF8String
+    }
+
+    Request ::= SEQUENCE {
+        priority  Priority,
+        command   Command,
+        deadline  INTEGER (0..4294967295) OPTIONAL
+    }
+
+    END";
+
+pub mod gateway {
+    asn1rs::macros::asn_to_rust!(
+        r"Gateway DEFINITIONS AUTOMATIC TAGS ::=
+        BEGIN
+
+        Priority ::= ENUMERATED { low, normal, high }
+
+        Label ::= UTF8String (SIZE(1..32))
+
+        Item ::= SEQUENCE {
+            id       INTEGER (0..65535),
+            label    Label,
+            payload  OCTET STRING,
+            flags    BIT STRING (SIZE(8)),
+            default  BOOLEAN DEFAULT TRUE
+        }
+
+        Command ::= CHOICE {
+            ping   NULL,
+            items  SEQUENCE OF Item,
+            note   UTF8String
+        }
+
+        Request ::= SEQUENCE {
+            priority  Priority,
+            command   Command,
+            deadline  INTEGER (0..4294967295) OPTIONAL
+        }
+
+        END"
+    );
+}
+
+pub mod gateway_ffi {
+    include!("c_ffi/gateway_ffi.rs");
+}
+
+use gateway::*;
+
+fn request() -> Request {
+    Request {
+        priority: Priority::High,
+        command: Command::Items(vec![Item {
+            id: 513,
+            label: Label("first".to_string()),
+            payload: vec![0xDE, 0xAD],
+            flags: BitVec::from_bytes(vec![0b1000_0001], 8),
+            default: false,
+        }]),
+        deadline: Some(60),
+    }
+}
+
+#[test]
+fn test_generated_ffi_is_up_to_date() {
+    let model = Model::try_from(Tokenizer.parse(GATEWAY))
+        .unwrap()
+        .try_resolve()
+        .unwrap()
+        .to_rust();
+    let (file, content) = CGenerator::generate_ffi(&model);
+    assert_eq!("gateway_ffi.rs", file);
+    assert_eq!(include_str!("c_ffi/gateway_ffi.rs"), content);
+}
+
+#[test]
+fn test_decode_into_c_representation_and_encode_again() {
+    let uper = Codec::Uper.encode(&request()).unwrap();
+    let mut decoded = MaybeUninit::<gateway_ffi::Request>::uninit();
+    unsafe {
+        assert_eq!(
+            ASN1RS_OK,
+            gateway_ffi::gateway_request_decode(
+                Codec::Uper.id(),
+                uper.as_ptr(),
+                uper.len(),
+                decoded.as_mut_ptr()
+            )
+        );
+        let decoded = decoded.assume_init_mut();
+        assert_eq!(2, decoded.priority);
+        assert_eq!(60, *decoded.deadline);
+        assert_eq!(1, decoded.command.tag);
+        let items = &decoded.command.value.items;
+        assert_eq!(1, items.len);
+        let item = &*items.data;
+        assert_eq!(513, item.id);
+        assert_eq!(
+            b"first\0",
+            std::slice::from_raw_parts(item.label.data.cast::<u8>(), item.label.len + 1)
+        );
+        assert_eq!(8, item.flags.bit_len);
+        assert!(!item.default_);
+
+        let mut written = 0;
+        assert_eq!(
+            ASN1RS_BUFFER_TOO_SMALL,
+            gateway_ffi::gateway_request_encode(
+                Codec::Jer.id(),
+                decoded,
+                std::ptr::null_mut(),
+                0,
+                &mut written
+            )
+        );
+        let mut jer = vec![0_u8; written];
+        assert_eq!(
+            ASN1RS_OK,
+            gateway_ffi::gateway_request_encode(
+                Codec::Jer.id(),
+                decoded,
+                jer.as_mut_ptr(),
+                jer.len(),
+                &mut written
+            )
+        );
+        assert_eq!(Codec::Jer.encode(&request()).unwrap(), jer);
+
+        // the DER writer does not support SEQUENCEs yet and panics, which must not unwind into C
+        assert_eq!(
+            ASN1RS_ENCODE_FAILED,
+            gateway_ffi::gateway_request_encode(
+                Codec::Der.id(),
+                decoded,
+                jer.as_mut_ptr(),
+                jer.len(),
+                &mut written
+            )
+        );
+
+        gateway_ffi::gateway_request_free(decoded);
+        assert!(decoded.deadline.is_null());
+        assert!(decoded.command.value.items.data.is_null());
+    }
+}
+
+#[test]
+fn test_encode_value_assembled_in_c() {
+    let mut note = b"hello\0".to_vec();
+    let value = gateway_ffi::Request {
+        priority: 7,
+        command: gateway_ffi::Command {
+            tag: 2,
+            value: gateway_ffi::CommandValue {
+                note: std::mem::ManuallyDrop::new(asn1rs::ffi::AsnString {
+                    data: note.as_mut_ptr().cast(),
+                    len: 5,
+                }),
+            },
+        },
+        deadline: std::ptr::null_mut(),
+    };
+    let mut buffer = [0_u8; 64];
+    let mut written = 0;
+    unsafe {
+        assert_eq!(
+            ASN1RS_INVALID_VALUE,
+            gateway_ffi::gateway_request_encode(
+                Codec::Uper.id(),
+                &value,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut written
+            )
+        );
+        let value = gateway_ffi::Request {
+            priority: 0,
+            ..value
+        };
+        assert_eq!(
+            ASN1RS_OK,
+            gateway_ffi::gateway_request_encode(
+                Codec::Uper.id(),
+                &value,
+                buffer.as_mut_ptr(),
+                buffer.len(),
+                &mut written
+            )
+        );
+    }
+    assert_eq!(
+        Request {
+            priority: Priority::Low,
+            command: Command::Note("hello".to_string()),
+            deadline: None,
+        },
+        Codec::Uper.decode::<Request>(&buffer[..written]).unwrap()
+    );
+}
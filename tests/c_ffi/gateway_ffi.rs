@@ -0,0 +1,298 @@
+// Generated by asn1rs from the ASN.1 module gateway, declared in gateway.h
+
+use asn1rs::ffi::FfiType;
+
+pub type Priority = u32;
+
+impl FfiType for super::gateway::Priority {
+    type C = Priority;
+
+    fn into_c(self) -> Self::C {
+        match self {
+            Self::Low => 0,
+            Self::Normal => 1,
+            Self::High => 2,
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        match *c {
+            0 => Ok(Self::Low),
+            1 => Ok(Self::Normal),
+            2 => Ok(Self::High),
+            _ => Err(asn1rs::ffi::ASN1RS_INVALID_VALUE),
+        }
+    }
+
+    unsafe fn free_c(_c: &mut Self::C) {}
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::decode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_priority_decode(codec: u8, bytes: *const u8, len: usize, out: *mut Priority) -> i32 {
+    asn1rs::ffi::decode::<super::gateway::Priority>(codec, bytes, len, out)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::encode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_priority_encode(codec: u8, value: *const Priority, out: *mut u8, capacity: usize, written: *mut usize) -> i32 {
+    asn1rs::ffi::encode::<super::gateway::Priority>(codec, value, out, capacity, written)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::free`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_priority_free(value: *mut Priority) {
+    asn1rs::ffi::free::<super::gateway::Priority>(value)
+}
+
+pub type Label = <String as FfiType>::C;
+
+impl FfiType for super::gateway::Label {
+    type C = Label;
+
+    fn into_c(self) -> Self::C {
+        FfiType::into_c(self.0)
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        <String as FfiType>::from_c(c).map(Self)
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        <String as FfiType>::free_c(c)
+    }
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::decode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_label_decode(codec: u8, bytes: *const u8, len: usize, out: *mut Label) -> i32 {
+    asn1rs::ffi::decode::<super::gateway::Label>(codec, bytes, len, out)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::encode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_label_encode(codec: u8, value: *const Label, out: *mut u8, capacity: usize, written: *mut usize) -> i32 {
+    asn1rs::ffi::encode::<super::gateway::Label>(codec, value, out, capacity, written)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::free`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_label_free(value: *mut Label) {
+    asn1rs::ffi::free::<super::gateway::Label>(value)
+}
+
+#[repr(C)]
+pub struct Item {
+    pub id: <u16 as FfiType>::C,
+    pub label: <super::gateway::Label as FfiType>::C,
+    pub payload: <Vec<u8> as FfiType>::C,
+    pub flags: <asn1rs::prelude::BitVec as FfiType>::C,
+    pub default_: <bool as FfiType>::C,
+}
+
+impl FfiType for super::gateway::Item {
+    type C = Item;
+
+    fn into_c(self) -> Self::C {
+        Item {
+            id: FfiType::into_c(self.id),
+            label: FfiType::into_c(self.label),
+            payload: FfiType::into_c(self.payload),
+            flags: FfiType::into_c(self.flags),
+            default_: FfiType::into_c(self.default),
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        Ok(Self {
+            id: <u16 as FfiType>::from_c(&c.id)?,
+            label: <super::gateway::Label as FfiType>::from_c(&c.label)?,
+            payload: <Vec<u8> as FfiType>::from_c(&c.payload)?,
+            flags: <asn1rs::prelude::BitVec as FfiType>::from_c(&c.flags)?,
+            default: <bool as FfiType>::from_c(&c.default_)?,
+        })
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        <u16 as FfiType>::free_c(&mut c.id);
+        <super::gateway::Label as FfiType>::free_c(&mut c.label);
+        <Vec<u8> as FfiType>::free_c(&mut c.payload);
+        <asn1rs::prelude::BitVec as FfiType>::free_c(&mut c.flags);
+        <bool as FfiType>::free_c(&mut c.default_);
+    }
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::decode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_item_decode(codec: u8, bytes: *const u8, len: usize, out: *mut Item) -> i32 {
+    asn1rs::ffi::decode::<super::gateway::Item>(codec, bytes, len, out)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::encode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_item_encode(codec: u8, value: *const Item, out: *mut u8, capacity: usize, written: *mut usize) -> i32 {
+    asn1rs::ffi::encode::<super::gateway::Item>(codec, value, out, capacity, written)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::free`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_item_free(value: *mut Item) {
+    asn1rs::ffi::free::<super::gateway::Item>(value)
+}
+
+#[repr(C)]
+pub struct Command {
+    pub tag: u32,
+    pub value: CommandValue,
+}
+
+#[repr(C)]
+pub union CommandValue {
+    pub ping: std::mem::ManuallyDrop<<asn1rs::prelude::Null as FfiType>::C>,
+    pub items: std::mem::ManuallyDrop<<Vec<super::gateway::Item> as FfiType>::C>,
+    pub note: std::mem::ManuallyDrop<<String as FfiType>::C>,
+}
+
+impl FfiType for super::gateway::Command {
+    type C = Command;
+
+    fn into_c(self) -> Self::C {
+        match self {
+            Self::Ping(value) => Command {
+                tag: 0,
+                value: CommandValue {
+                    ping: std::mem::ManuallyDrop::new(FfiType::into_c(value)),
+                },
+            },
+            Self::Items(value) => Command {
+                tag: 1,
+                value: CommandValue {
+                    items: std::mem::ManuallyDrop::new(FfiType::into_c(value)),
+                },
+            },
+            Self::Note(value) => Command {
+                tag: 2,
+                value: CommandValue {
+                    note: std::mem::ManuallyDrop::new(FfiType::into_c(value)),
+                },
+            },
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        match c.tag {
+            0 => Ok(Self::Ping(<asn1rs::prelude::Null as FfiType>::from_c(&c.value.ping)?)),
+            1 => Ok(Self::Items(<Vec<super::gateway::Item> as FfiType>::from_c(&c.value.items)?)),
+            2 => Ok(Self::Note(<String as FfiType>::from_c(&c.value.note)?)),
+            _ => Err(asn1rs::ffi::ASN1RS_INVALID_VALUE),
+        }
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        match c.tag {
+            0 => <asn1rs::prelude::Null as FfiType>::free_c(&mut c.value.ping),
+            1 => <Vec<super::gateway::Item> as FfiType>::free_c(&mut c.value.items),
+            2 => <String as FfiType>::free_c(&mut c.value.note),
+            _ => {}
+        }
+    }
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::decode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_command_decode(codec: u8, bytes: *const u8, len: usize, out: *mut Command) -> i32 {
+    asn1rs::ffi::decode::<super::gateway::Command>(codec, bytes, len, out)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::encode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_command_encode(codec: u8, value: *const Command, out: *mut u8, capacity: usize, written: *mut usize) -> i32 {
+    asn1rs::ffi::encode::<super::gateway::Command>(codec, value, out, capacity, written)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::free`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_command_free(value: *mut Command) {
+    asn1rs::ffi::free::<super::gateway::Command>(value)
+}
+
+#[repr(C)]
+pub struct Request {
+    pub priority: <super::gateway::Priority as FfiType>::C,
+    pub command: <super::gateway::Command as FfiType>::C,
+    pub deadline: <Option<u32> as FfiType>::C,
+}
+
+impl FfiType for super::gateway::Request {
+    type C = Request;
+
+    fn into_c(self) -> Self::C {
+        Request {
+            priority: FfiType::into_c(self.priority),
+            command: FfiType::into_c(self.command),
+            deadline: FfiType::into_c(self.deadline),
+        }
+    }
+
+    unsafe fn from_c(c: &Self::C) -> Result<Self, i32> {
+        Ok(Self {
+            priority: <super::gateway::Priority as FfiType>::from_c(&c.priority)?,
+            command: <super::gateway::Command as FfiType>::from_c(&c.command)?,
+            deadline: <Option<u32> as FfiType>::from_c(&c.deadline)?,
+        })
+    }
+
+    unsafe fn free_c(c: &mut Self::C) {
+        <super::gateway::Priority as FfiType>::free_c(&mut c.priority);
+        <super::gateway::Command as FfiType>::free_c(&mut c.command);
+        <Option<u32> as FfiType>::free_c(&mut c.deadline);
+    }
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::decode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_request_decode(codec: u8, bytes: *const u8, len: usize, out: *mut Request) -> i32 {
+    asn1rs::ffi::decode::<super::gateway::Request>(codec, bytes, len, out)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::encode`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_request_encode(codec: u8, value: *const Request, out: *mut u8, capacity: usize, written: *mut usize) -> i32 {
+    asn1rs::ffi::encode::<super::gateway::Request>(codec, value, out, capacity, written)
+}
+
+/// # Safety
+///
+/// See `asn1rs::ffi::free`
+#[no_mangle]
+pub unsafe extern "C" fn gateway_request_free(value: *mut Request) {
+    asn1rs::ffi::free::<super::gateway::Request>(value)
+}
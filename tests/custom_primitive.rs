@@ -0,0 +1,110 @@
+mod test_utils;
+
+use asn1rs::descriptor::{common, numbers, Integer};
+use asn1rs::model::asn::Tag;
+use test_utils::*;
+
+/// Fixed-point temperature, encoded as `INTEGER (-27315..100000)` in steps of 0.01 degree
+#[derive(Debug, Copy, Clone, PartialEq)]
+pub struct Celsius(pub f32);
+
+pub struct CentiCelsius;
+impl common::Constraint for CentiCelsius {
+    const TAG: Tag = Tag::DEFAULT_INTEGER;
+}
+impl numbers::Constraint<i32> for CentiCelsius {
+    const MIN: Option<i64> = Some(-27315);
+    const MAX: Option<i64> = Some(100000);
+    const MIN_T: Option<i32> = Some(-27315);
+    const MAX_T: Option<i32> = Some(100000);
+}
+
+impl Writable for Celsius {
+    fn write<W: Writer>(&self, writer: &mut W) -> Result<(), W::Error> {
+        let centi = (self.0 * 100.0).round() as i32;
+        Integer::<i32, CentiCelsius>::write_value(writer, &centi)
+    }
+}
+
+impl Readable for Celsius {
+    fn read<R: Reader>(reader: &mut R) -> Result<Self, R::Error> {
+        let centi = Integer::<i32, CentiCelsius>::read_value(reader)?;
+        Ok(Celsius(centi as f32 / 100.0))
+    }
+}
+
+#[asn(sequence)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Reading {
+    #[asn(integer(0..255))]
+    pub sensor: u8,
+    #[asn(complex(Celsius, tag(UNIVERSAL(2))))]
+    pub temperature: Celsius,
+    #[asn(sequence_of(complex(Celsius, tag(UNIVERSAL(2)))))]
+    pub history: Vec<Celsius>,
+}
+
+#[asn(choice)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Measurement {
+    #[asn(complex(Celsius, tag(UNIVERSAL(2))))]
+    Temperature(Celsius),
+    #[asn(integer(0..100))]
+    Humidity(u8),
+}
+
+fn reading() -> Reading {
+    Reading {
+        sensor: 7,
+        temperature: Celsius(-0.5),
+        history: vec![Celsius(21.25)],
+    }
+}
+
+#[test]
+fn test_uper() {
+    // sensor (8 bits), temperature (17 bits), length of history (8 bits), history (17 bits)
+    let (bits, bytes) = serialize_uper(&reading());
+    assert_eq!(8 + 17 + 8 + 17, bits);
+    assert_eq!(reading(), deserialize_uper::<Reading>(&bytes, bits));
+
+    serialize_and_deserialize_uper(
+        1 + 17,
+        &[0x1A, 0xAC, 0xC0],
+        &Measurement::Temperature(Celsius(0.0)),
+    );
+}
+
+#[test]
+#[should_panic(expected = "ValueNotInRange(-30000, -27315, 100000)")]
+fn test_uper_checks_the_constraint_of_the_underlying_descriptor() {
+    serialize_uper(&Measurement::Temperature(Celsius(-300.0)));
+}
+
+#[test]
+fn test_value_notation() {
+    assert_eq!(
+        "{ sensor 7, temperature -50, history { 2125 } }",
+        ValueNotation::gser(&reading()).to_string()
+    );
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_protobuf() {
+    let bytes = serialize_protobuf(&reading());
+    assert_eq!(reading(), deserialize_protobuf::<Reading>(&bytes));
+}
+
+#[test]
+fn test_metadata_of_custom_types_is_not_required() {
+    use asn1rs::descriptor::metadata::{TypeKind, ValueInfo};
+    let TypeKind::Sequence { fields, .. } = Reading::METADATA.kind else {
+        panic!("Unexpected kind {:?}", Reading::METADATA.kind)
+    };
+    let ValueInfo::Complex { name, metadata } = fields[1].r#type else {
+        panic!("Unexpected type {:?}", fields[1].r#type)
+    };
+    assert_eq!("Celsius", name);
+    assert!(metadata().is_none());
+}
@@ -0,0 +1,50 @@
+use asn1rs::descriptor::boolean::NoConstraint;
+use asn1rs::descriptor::{Boolean, DerAny};
+use asn1rs::prelude::basic::DER;
+use asn1rs::prelude::*;
+
+/// `[0] { OCTET STRING 'ABCD'H }` with a non-minimal long form length of the OCTET STRING, that a
+/// re-encoding would change
+const SIGNED: &[u8] = &[0xA0, 0x05, 0x04, 0x81, 0x02, 0xAB, 0xCD];
+
+#[test]
+fn test_der_any_is_read_and_written_verbatim() {
+    let mut bytes = SIGNED.to_vec();
+    bytes.extend_from_slice(&[0x01, 0x01, 0xFF]);
+
+    let mut reader = DER::reader(&bytes[..]);
+    let any = reader.read::<DerAny>().unwrap();
+    assert_eq!(SIGNED, &any.0[..]);
+    assert!(Boolean::<NoConstraint>::read_value(&mut reader).unwrap());
+
+    let mut buffer = Vec::new();
+    let mut writer = DER::writer(&mut buffer);
+    writer.write(&any).unwrap();
+    assert_eq!(SIGNED, &buffer[..]);
+}
+
+#[test]
+fn test_der_any_must_be_a_single_tlv() {
+    for value in [&[][..], &SIGNED[..4], &[SIGNED, &[0x05, 0x00]].concat()[..]] {
+        let mut buffer = Vec::new();
+        let mut writer = DER::writer(&mut buffer);
+        assert!(writer.write(&DerAny(value.to_vec())).is_err());
+        assert!(buffer.is_empty());
+    }
+}
+
+#[test]
+fn test_der_any_truncated() {
+    let mut reader = DER::reader(&SIGNED[..6]);
+    assert!(reader.read::<DerAny>().is_err());
+}
+
+#[test]
+fn test_der_any_as_uper_octet_string() {
+    let mut writer = UperWriter::default();
+    writer.write(&DerAny(SIGNED.to_vec())).unwrap();
+    assert_eq!(8 * (1 + SIGNED.len()), writer.bit_len());
+
+    let mut reader = writer.as_reader();
+    assert_eq!(DerAny(SIGNED.to_vec()), reader.read::<DerAny>().unwrap());
+}
@@ -0,0 +1,84 @@
+use asn1rs::descriptor::bitstring::{BitVec, Constraint, NoConstraint};
+use asn1rs::descriptor::common;
+use asn1rs::descriptor::{BitString, ReadableType, WritableType};
+use asn1rs::model::asn::Tag;
+use asn1rs::prelude::basic::DER;
+use asn1rs::protocol::basic::ErrorKind;
+
+/// `BIT STRING { read(0), write(1), execute(2) } (SIZE(0..8))`
+struct Flags;
+impl common::Constraint for Flags {
+    const TAG: Tag = Tag::DEFAULT_BIT_STRING;
+}
+impl Constraint for Flags {
+    const MAX: Option<u64> = Some(8);
+    const NAMED_BITS: bool = true;
+}
+
+fn write<C: Constraint>(value: &BitVec) -> Vec<u8> {
+    let mut buffer = Vec::new();
+    let mut writer = DER::writer(&mut buffer);
+    BitString::<C>::write_value(&mut writer, value).unwrap();
+    buffer
+}
+
+#[test]
+pub fn test_der_basic_bitstring() {
+    let value = BitVec::from_bytes(vec![0xAB, 0xC0], 12);
+    let buffer = write::<NoConstraint>(&value);
+    assert_eq!(&[0x03, 0x03, 0x04, 0xAB, 0xC0], &buffer[..]);
+
+    let mut reader = DER::reader(&buffer[..]).strict();
+    assert_eq!(
+        value,
+        BitString::<NoConstraint>::read_value(&mut reader).unwrap()
+    );
+}
+
+#[test]
+pub fn test_der_basic_bitstring_empty() {
+    let buffer = write::<NoConstraint>(&BitVec::default());
+    assert_eq!(&[0x03, 0x01, 0x00], &buffer[..]);
+
+    let mut reader = DER::reader(&buffer[..]).strict();
+    assert_eq!(
+        BitVec::default(),
+        BitString::<NoConstraint>::read_value(&mut reader).unwrap()
+    );
+}
+
+#[test]
+pub fn test_der_basic_bitstring_named_bits_without_trailing_zeros() {
+    let mut flags = BitVec::with_len(8);
+    flags.set_bit(0);
+    flags.set_bit(2);
+    assert_eq!(&[0x03, 0x02, 0x05, 0xA0], &write::<Flags>(&flags)[..]);
+    assert_eq!(
+        &[0x03, 0x01, 0x00],
+        &write::<Flags>(&BitVec::with_len(8))[..]
+    );
+}
+
+#[test]
+pub fn test_der_basic_bitstring_padding_bits() {
+    const BYTES: &[u8] = &[0x03, 0x02, 0x04, 0xAF];
+
+    let mut reader = DER::reader(BYTES);
+    assert_eq!(
+        BitVec::from_bytes(vec![0xA0], 4),
+        BitString::<NoConstraint>::read_value(&mut reader).unwrap()
+    );
+
+    let mut reader = DER::reader(BYTES).strict();
+    let error = BitString::<NoConstraint>::read_value(&mut reader).unwrap_err();
+    assert!(matches!(error.kind(), ErrorKind::PaddingBitsNotZero));
+}
+
+#[test]
+pub fn test_der_basic_bitstring_invalid_unused_bits() {
+    for bytes in [&[0x03, 0x02, 0x08, 0x00][..], &[0x03, 0x01, 0x01][..]] {
+        let mut reader = DER::reader(bytes);
+        let error = BitString::<NoConstraint>::read_value(&mut reader).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::InvalidUnusedBits { .. }));
+    }
+}
@@ -48,3 +48,36 @@ pub fn test_letsencrypt_point_numbers() {
         Integer::<i64, NoConstraint>::read_value(&mut reader).unwrap()
     );
 }
+
+fn write_read_wide_integer<T: Number + Debug + PartialEq>(expected: &[u8], value: T) {
+    let mut buffer = Vec::new();
+    let mut writer = DER::writer(&mut buffer);
+
+    Integer::<T, NoConstraint>::write_value(&mut writer, &value).unwrap();
+    assert_eq!(expected, &buffer[..]);
+
+    let mut reader = DER::reader(&buffer[..]);
+    let read = Integer::<T, NoConstraint>::read_value(&mut reader).unwrap();
+
+    assert_eq!(value, read);
+}
+
+#[test]
+pub fn test_wide_numbers() {
+    let mut expected = vec![0x02, 0x0D, 0x00];
+    expected.extend_from_slice(&[0xFF; 12]);
+    write_read_wide_integer(&expected, (1_u128 << 96) - 1);
+
+    let mut expected = vec![0x02, 0x0D, 0xFF];
+    expected.extend_from_slice(&[0x00; 11]);
+    expected.push(0x01);
+    write_read_wide_integer(&expected, 1_i128 - (1 << 96));
+
+    let mut expected = vec![0x02, 0x10, 0x80];
+    expected.extend_from_slice(&[0x00; 15]);
+    write_read_wide_integer(&expected, i128::MIN);
+
+    write_read_wide_integer(&[0x02, 0x01, 0x00], 0_u128);
+    write_read_wide_integer(&[0x02, 0x01, 0xFF], -1_i128);
+    write_read_wide_integer(&[0x02, 0x02, 0x00, 0x80], 0x80_u128);
+}
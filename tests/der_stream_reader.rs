@@ -0,0 +1,104 @@
+use asn1rs::model::asn::Tag;
+use asn1rs::protocol::basic::{DerStreamReader, DerToken, ErrorKind, Header};
+use std::io::Read;
+
+/// Hands out a single byte per call, like a slow socket or pipe
+struct Trickle<'a>(&'a [u8]);
+
+impl Read for Trickle<'_> {
+    fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+        (&mut self.0).take(1).read(buf)
+    }
+}
+
+/// `SEQUENCE { INTEGER 5, [0] { OCTET STRING 'ABCD'H }, [APPLICATION 31] NULL }` followed by
+/// another `BOOLEAN TRUE`
+const BYTES: &[u8] = &[
+    0x30, 0x0C, 0x02, 0x01, 0x05, 0xA0, 0x04, 0x04, 0x02, 0xAB, 0xCD, 0x5F, 0x1F, 0x00, 0x01, 0x01,
+    0xFF,
+];
+
+fn header(tag: Tag, constructed: bool, len: u64) -> Header {
+    Header {
+        tag,
+        constructed,
+        len,
+    }
+}
+
+fn primitive(tag: Tag, content: &[u8]) -> DerToken {
+    DerToken::Primitive {
+        header: header(tag, false, content.len() as u64),
+        content: content.to_vec(),
+    }
+}
+
+#[test]
+fn test_tokens() {
+    let mut reader = DerStreamReader::new(Trickle(BYTES));
+    let mut tokens = Vec::new();
+    let mut max_depth = 0;
+    while let Some(token) = reader.next_token().unwrap() {
+        max_depth = max_depth.max(reader.depth());
+        tokens.push(token);
+    }
+
+    assert_eq!(
+        vec![
+            DerToken::Start(header(Tag::Universal(16), true, 12)),
+            primitive(Tag::Universal(2), &[0x05]),
+            DerToken::Start(header(Tag::ContextSpecific(0), true, 4)),
+            primitive(Tag::Universal(4), &[0xAB, 0xCD]),
+            DerToken::End(Tag::ContextSpecific(0)),
+            primitive(Tag::Application(31), &[]),
+            DerToken::End(Tag::Universal(16)),
+            primitive(Tag::Universal(1), &[0xFF]),
+        ],
+        tokens
+    );
+    assert_eq!(2, max_depth);
+    assert_eq!(0, reader.depth());
+}
+
+#[test]
+fn test_primitive_exceeding_the_max_content_len() {
+    let mut reader = DerStreamReader::new(BYTES).with_max_content_len(1);
+    assert!(matches!(reader.next_token(), Ok(Some(DerToken::Start(_)))));
+    assert!(matches!(
+        reader.next_token(),
+        Ok(Some(DerToken::Primitive { .. }))
+    ));
+    assert!(matches!(reader.next_token(), Ok(Some(DerToken::Start(_)))));
+    let error = reader.next_token().unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::UnexpectedTypeLength { got: 2, .. }
+    ));
+}
+
+#[test]
+fn test_nested_tlv_exceeding_the_enclosing_tlv() {
+    // SEQUENCE of 3 octets containing an INTEGER of 2 octets
+    let mut reader = DerStreamReader::new(&[0x30, 0x03, 0x02, 0x02, 0x01, 0x02][..]);
+    assert!(matches!(reader.next_token(), Ok(Some(DerToken::Start(_)))));
+    assert!(reader.next_token().is_err());
+}
+
+#[test]
+fn test_truncated_input() {
+    let mut reader = DerStreamReader::new(&BYTES[..4]);
+    assert!(matches!(reader.next_token(), Ok(Some(DerToken::Start(_)))));
+    assert!(matches!(
+        reader.next_token().unwrap_err().kind(),
+        ErrorKind::IoError(_)
+    ));
+}
+
+#[test]
+fn test_indefinite_length() {
+    let mut reader = DerStreamReader::new(&[0x30, 0x80, 0x00, 0x00][..]);
+    assert!(matches!(
+        reader.next_token().unwrap_err().kind(),
+        ErrorKind::IndefiniteLength
+    ));
+}
@@ -0,0 +1,77 @@
+use asn1rs::model::asn::Tag;
+use asn1rs::protocol::basic::{ErrorKind, Tlv, TlvIter};
+
+/// `SEQUENCE { INTEGER 5, [0] { OCTET STRING 'ABCD'H }, [APPLICATION 31] NULL }` followed by
+/// another `BOOLEAN TRUE`
+const BYTES: &[u8] = &[
+    0x30, 0x0C, 0x02, 0x01, 0x05, 0xA0, 0x04, 0x04, 0x02, 0xAB, 0xCD, 0x5F, 0x1F, 0x00, 0x01, 0x01,
+    0xFF,
+];
+
+/// The tags of all TLVs, depth first, indented by their depth
+fn walk(tlvs: TlvIter, depth: usize, out: &mut Vec<String>) {
+    for tlv in tlvs {
+        let tlv = tlv.unwrap();
+        out.push(format!("{}{:?}", " ".repeat(depth), tlv.tag()));
+        walk(tlv.children(), depth + 1, out);
+    }
+}
+
+#[test]
+fn test_walk() {
+    let mut tags = Vec::new();
+    walk(TlvIter::new(BYTES), 0, &mut tags);
+    assert_eq!(
+        vec![
+            "Universal(16)",
+            " Universal(2)",
+            " ContextSpecific(0)",
+            "  Universal(4)",
+            " Application(31)",
+            "Universal(1)",
+        ],
+        tags
+    );
+}
+
+#[test]
+fn test_content_and_raw() {
+    let tlvs = TlvIter::new(BYTES)
+        .collect::<Result<Vec<Tlv>, _>>()
+        .unwrap();
+    assert_eq!(2, tlvs.len());
+
+    let sequence = tlvs[0];
+    assert!(sequence.is_constructed());
+    assert_eq!(12, sequence.header.len);
+    assert_eq!(&BYTES[2..14], sequence.content);
+    assert_eq!(&BYTES[..14], sequence.raw);
+
+    // pass the explicitly tagged field through unchanged
+    let tagged = sequence.children().nth(1).unwrap().unwrap();
+    assert_eq!(Tag::ContextSpecific(0), tagged.tag());
+    assert_eq!(&[0xA0, 0x04, 0x04, 0x02, 0xAB, 0xCD], tagged.raw);
+
+    let boolean = tlvs[1];
+    assert!(!boolean.is_constructed());
+    assert_eq!(&[0xFF], boolean.content);
+    assert_eq!(0, boolean.children().count());
+}
+
+#[test]
+fn test_ends_after_error() {
+    // the content of the INTEGER is missing
+    let mut iter = TlvIter::new(&[0x01, 0x01, 0xFF, 0x02, 0x02, 0x01]);
+    assert!(iter.next().unwrap().is_ok());
+    assert!(matches!(
+        iter.next().unwrap().unwrap_err().kind(),
+        ErrorKind::UnexpectedTypeLength { got: 2, .. }
+    ));
+    assert!(iter.next().is_none());
+
+    let mut iter = TlvIter::new(&[0x30]);
+    assert!(matches!(
+        iter.next().unwrap().unwrap_err().kind(),
+        ErrorKind::IoError(_)
+    ));
+}
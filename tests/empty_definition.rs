@@ -1,18 +1,109 @@
 #![recursion_limit = "512"]
 
-use asn1rs::prelude::asn_to_rust;
+mod test_utils;
+
+use asn1rs::value::{Schema, Value};
+use test_utils::*;
+
+macro_rules! asn_to_rust_and_schema {
+    ($asn:tt) => {
+        asn_to_rust!($asn);
+
+        fn schema() -> Schema {
+            Schema::parse($asn).unwrap()
+        }
+    };
+}
 
 // this should compile without noise
-asn_to_rust!(
+asn_to_rust_and_schema!(
     r"SomeEmptyTypeDefinitions DEFINITIONS AUTOMATIC TAGS ::=
     BEGIN
-    
+
     EmptySeq ::= SEQUENCE { }
-    
+
     ChoiceWithEmptyness ::= CHOICE {
 	    c1 INTEGER,
 	    c2 SEQUENCE {}
     }
-    
+
+    EmptyExt ::= SEQUENCE { ... }
+
+    EmptySet ::= SET {}
+
+    EmptyChoice ::= CHOICE {}
+
+    Holder ::= SEQUENCE {
+        seq     EmptySeq,
+        ext     EmptyExt,
+        set     EmptySet,
+        nothing EmptyChoice OPTIONAL,
+        number  INTEGER (0..255)
+    }
+
     END"
 );
+
+fn holder() -> Holder {
+    Holder {
+        seq: EmptySeq,
+        ext: EmptyExt,
+        set: EmptySet,
+        nothing: None,
+        number: 42,
+    }
+}
+
+#[test]
+fn test_empty_sequence_uper() {
+    serialize_and_deserialize_uper(0, &[], &EmptySeq);
+    serialize_and_deserialize_uper(0, &[], &EmptySet);
+    // only the extension bit
+    serialize_and_deserialize_uper(1, &[0x00], &EmptyExt);
+    serialize_and_deserialize_uper(1, &[0x80], &ChoiceWithEmptyness::C2(ChoiceWithEmptynessC2));
+}
+
+#[test]
+fn test_holder_uper() {
+    // extension bit of ext, presence bit of nothing, 8 bits of number
+    serialize_and_deserialize_uper(10, &[0x0A, 0x80], &holder());
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_holder_protobuf() {
+    serialize_and_deserialize_protobuf(
+        &[0x0A, 0x00, 0x12, 0x00, 0x1A, 0x00, 0x28, 0x2A],
+        &holder(),
+    );
+}
+
+#[test]
+fn test_holder_jer() {
+    use asn1rs::codec::Codec;
+    let json = br#"{"seq":{},"ext":{},"set":{},"number":42}"#;
+    assert_eq!(&json[..], &Codec::Jer.encode(&holder()).unwrap()[..]);
+    assert_eq!(holder(), Codec::Jer.decode::<Holder>(json).unwrap());
+}
+
+#[test]
+fn test_holder_dynamic() {
+    let schema = schema();
+    let value = Value::Sequence(vec![
+        ("seq".to_string(), Value::Sequence(Vec::new())),
+        ("ext".to_string(), Value::Sequence(Vec::new())),
+        ("set".to_string(), Value::Set(Vec::new())),
+        ("number".to_string(), Value::Integer(42)),
+    ]);
+
+    let uper = schema.encode_uper("Holder", &value).unwrap();
+    assert_eq!(serialize_uper(&holder()).1, uper);
+    assert_eq!(value, schema.decode_uper("Holder", &uper).unwrap());
+
+    let der = schema.encode_der("Holder", &value).unwrap();
+    assert_eq!(
+        vec![0x30, 0x09, 0xA0, 0x00, 0xA1, 0x00, 0xA2, 0x00, 0x84, 0x01, 0x2A],
+        der
+    );
+    assert_eq!(value, schema.decode_der("Holder", &der).unwrap());
+}
@@ -0,0 +1,68 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"ExtensibleAdditions DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Root ::= SEQUENCE {
+        id INTEGER (0..255),
+        note UTF8String OPTIONAL,
+        ...
+    }
+
+    Extended ::= SEQUENCE {
+        id INTEGER (0..255),
+        note UTF8String OPTIONAL,
+        ...,
+        priority INTEGER (0..7),
+        urgent BOOLEAN DEFAULT FALSE
+    }
+
+    END"
+);
+
+fn extended() -> Extended {
+    Extended {
+        id: 5,
+        note: None,
+        priority: None,
+        urgent: false,
+    }
+}
+
+#[test]
+fn test_root_only_peer_has_no_additions() {
+    let (bits, bytes) = serialize_uper(&Root { id: 5, note: None });
+    let extended: Extended = deserialize_uper(&bytes, bits);
+    assert_eq!(self::extended(), extended);
+    assert!(!extended.has_extension_additions());
+    assert!(extended.present_extension_additions().is_empty());
+}
+
+#[test]
+fn test_optional_root_field_is_no_addition() {
+    let mut extended = extended();
+    extended.note = Some("root".to_string());
+    assert!(!extended.has_extension_additions());
+}
+
+#[test]
+fn test_present_additions() {
+    let mut extended = extended();
+    extended.priority = Some(3);
+    assert!(extended.has_extension_additions());
+    assert_eq!(vec!["priority"], extended.present_extension_additions());
+
+    extended.urgent = true;
+    let (bits, bytes) = serialize_uper(&extended);
+    let decoded: Extended = deserialize_uper(&bytes, bits);
+    assert_eq!(
+        vec!["priority", "urgent"],
+        decoded.present_extension_additions()
+    );
+    assert_eq!(["priority", "urgent"], Extended::EXTENSION_ADDITIONS);
+}
@@ -0,0 +1,78 @@
+#![recursion_limit = "512"]
+#![cfg(feature = "jsonl")]
+
+mod test_utils;
+
+use asn1rs::jsonl::JsonlLogger;
+use std::time::{Duration, UNIX_EPOCH};
+use test_utils::*;
+
+asn_to_rust!(
+    r"Traffic DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Status ::= ENUMERATED { idle, busy }
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..65535),
+        status  Status,
+        comment UTF8String OPTIONAL
+    }
+
+    END"
+);
+
+#[test]
+fn test_records_are_appended_per_line() {
+    let mut logger = JsonlLogger::new(Vec::new());
+    let timestamp = UNIX_EPOCH + Duration::from_millis(1_715_934_600_250);
+    logger
+        .log_at(
+            timestamp,
+            "Message",
+            &Message {
+                id: 513,
+                status: Status::Busy,
+                comment: Some("first".to_string()),
+            },
+        )
+        .unwrap();
+    logger.log_at(timestamp, "Status", &Status::Idle).unwrap();
+
+    assert_eq!(
+        concat!(
+            r#"{"timestamp":"2024-05-17T08:30:00.250Z","type":"Message","payload":{"id":513,"status":"busy","comment":"first"}}"#,
+            "\n",
+            r#"{"timestamp":"2024-05-17T08:30:00.250Z","type":"Status","payload":"idle"}"#,
+            "\n"
+        ),
+        String::from_utf8(logger.into_inner()).unwrap()
+    );
+}
+
+#[test]
+fn test_decoded_value_is_logged_to_file() {
+    let (bits, bytes) = serialize_uper(&Message {
+        id: 1,
+        status: Status::Idle,
+        comment: None,
+    });
+    let decoded = deserialize_uper::<Message>(&bytes, bits);
+
+    let file = std::env::temp_dir().join(format!("asn1rs-jsonl-{}.jsonl", std::process::id()));
+    let _ = std::fs::remove_file(&file);
+    for _ in 0..2 {
+        let mut logger = JsonlLogger::append(&file).unwrap();
+        logger.log(&decoded).unwrap();
+        logger.flush().unwrap();
+    }
+
+    let log = std::fs::read_to_string(&file).unwrap();
+    std::fs::remove_file(&file).unwrap();
+    let lines = log.lines().collect::<Vec<_>>();
+    assert_eq!(2, lines.len());
+    for line in lines {
+        assert!(line.starts_with(r#"{"timestamp":""#));
+        assert!(line.ends_with(r#"Z","type":"Message","payload":{"id":1,"status":"idle"}}"#));
+    }
+}
@@ -0,0 +1,51 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::codec::Codec;
+use test_utils::*;
+
+asn_to_rust!(
+    r"KeywordFields DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Keywords ::= SEQUENCE {
+        type    INTEGER (0..255),
+        self    BOOLEAN,
+        match   UTF8String OPTIONAL,
+        ...,
+        async   BOOLEAN OPTIONAL
+    }
+
+    END"
+);
+
+fn keywords() -> Keywords {
+    Keywords {
+        r#type: 7,
+        self_: true,
+        r#match: Some("m".to_string()),
+        r#async: Some(false),
+    }
+}
+
+#[test]
+fn test_jer_uses_asn_identifiers() {
+    const JER: &str = r#"{"type":7,"self":true,"match":"m","async":false}"#;
+    assert_eq!(JER.as_bytes(), &Codec::Jer.encode(&keywords()).unwrap()[..]);
+    assert_eq!(
+        keywords(),
+        Codec::Jer.decode::<Keywords>(JER.as_bytes()).unwrap()
+    );
+}
+
+#[test]
+fn test_uper_round_trip() {
+    let bytes = Codec::Uper.encode(&keywords()).unwrap();
+    assert_eq!(keywords(), Codec::Uper.decode::<Keywords>(&bytes).unwrap());
+}
+
+#[test]
+fn test_extension_additions() {
+    assert_eq!(["async"], Keywords::EXTENSION_ADDITIONS);
+}
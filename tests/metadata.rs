@@ -0,0 +1,155 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::descriptor::metadata::{Presence, SizeInfo, TypeInfo, TypeKind, ValueInfo};
+use asn1rs::model::asn::{Charset, Tag};
+use test_utils::*;
+
+asn_to_rust!(
+    r"Metadata DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Level ::= ENUMERATED { low, high, ... }
+
+    Form ::= SEQUENCE {
+        title   PrintableString (SIZE(1..32)),
+        level   Level DEFAULT high,
+        count   INTEGER (0..1000) OPTIONAL,
+        tags    SEQUENCE (SIZE(0..8)) OF UTF8String,
+        child   Node OPTIONAL,
+        ...
+    }
+
+    Node ::= CHOICE {
+        leaf    BOOLEAN,
+        form    Form
+    }
+
+    Percent ::= INTEGER (0..100)
+
+    END"
+);
+
+fn fields(info: TypeInfo) -> &'static [asn1rs::descriptor::metadata::FieldInfo] {
+    match info.kind {
+        TypeKind::Sequence { fields, .. } => fields,
+        TypeKind::Choice { variants, .. } => variants,
+        kind => panic!("Unexpected kind {:?}", kind),
+    }
+}
+
+#[test]
+fn test_sequence_fields() {
+    assert_eq!("Form", Form::METADATA.name);
+    assert_eq!(Tag::DEFAULT_SEQUENCE, Form::METADATA.tag);
+    assert!(matches!(
+        Form::METADATA.kind,
+        TypeKind::Sequence {
+            extended_after_field: Some(4),
+            ..
+        }
+    ));
+
+    let fields = fields(Form::METADATA);
+    assert_eq!(
+        vec!["title", "level", "count", "tags", "child"],
+        fields.iter().map(|f| f.name).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            Tag::ContextSpecific(0),
+            Tag::ContextSpecific(1),
+            Tag::ContextSpecific(2),
+            Tag::ContextSpecific(3),
+            Tag::ContextSpecific(4),
+        ],
+        fields.iter().map(|f| f.tag).collect::<Vec<_>>()
+    );
+    assert_eq!(
+        vec![
+            Presence::Required,
+            Presence::Default("high"),
+            Presence::Optional,
+            Presence::Required,
+            Presence::Optional,
+        ],
+        fields.iter().map(|f| f.presence).collect::<Vec<_>>()
+    );
+}
+
+#[test]
+fn test_value_constraints() {
+    let fields = fields(Form::METADATA);
+    assert!(matches!(
+        fields[0].r#type,
+        ValueInfo::String {
+            charset: Charset::Printable,
+            size: SizeInfo {
+                min: Some(1),
+                max: Some(32),
+                extensible: false
+            },
+            permitted_alphabet: None,
+        }
+    ));
+    assert!(matches!(
+        fields[2].r#type,
+        ValueInfo::Integer {
+            min: Some(0),
+            max: Some(1000),
+            extensible: false
+        }
+    ));
+    match fields[3].r#type {
+        ValueInfo::SequenceOf { item, size } => {
+            assert_eq!(Some(8), size.max);
+            assert!(matches!(
+                item,
+                ValueInfo::String {
+                    charset: Charset::Utf8,
+                    ..
+                }
+            ));
+        }
+        other => panic!("Unexpected type {:?}", other),
+    }
+    assert!(matches!(
+        Percent::METADATA.kind,
+        TypeKind::Wrapped(ValueInfo::Integer {
+            min: Some(0),
+            max: Some(100),
+            ..
+        })
+    ));
+}
+
+#[test]
+fn test_referenced_types() {
+    let fields = fields(Form::METADATA);
+    let ValueInfo::Complex { name, metadata } = fields[1].r#type else {
+        panic!("Unexpected type {:?}", fields[1].r#type)
+    };
+    assert_eq!("Level", name);
+    assert!(matches!(
+        metadata().unwrap().kind,
+        TypeKind::Enumerated {
+            variants: ["low", "high"],
+            extensible: true
+        }
+    ));
+
+    // Form and Node refer to each other
+    let ValueInfo::Complex { metadata, .. } = fields[4].r#type else {
+        panic!("Unexpected type {:?}", fields[4].r#type)
+    };
+    let node = metadata().unwrap();
+    assert_eq!("Node", node.name);
+    let variants = self::fields(node);
+    assert_eq!("leaf", variants[0].name);
+    assert!(matches!(variants[0].r#type, ValueInfo::Boolean));
+    let ValueInfo::Complex { metadata, .. } = variants[1].r#type else {
+        panic!("Unexpected type {:?}", variants[1].r#type)
+    };
+    assert_eq!("Form", metadata().unwrap().name);
+}
@@ -0,0 +1,120 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use test_utils::*;
+
+#[asn(sequence)]
+#[derive(Debug, Default, PartialEq)]
+pub struct MessageV1 {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(utf8string)]
+    pub payload: String,
+}
+
+/// `priority` was inserted before `payload`, which keeps its number
+#[asn(sequence, protobuf_numbers(1, 3, 2))]
+#[derive(Debug, Default, PartialEq)]
+pub struct MessageV2 {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(boolean)]
+    pub priority: bool,
+    #[asn(utf8string)]
+    pub payload: String,
+}
+
+/// As generated with `--rust-protobuf-feature`
+#[cfg_attr(feature = "protobuf", asn(sequence, protobuf_numbers(1, 3, 2)))]
+#[cfg_attr(not(feature = "protobuf"), asn(sequence))]
+#[derive(Debug, Default, PartialEq)]
+pub struct FeatureGatedMessage {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(boolean)]
+    pub priority: bool,
+    #[asn(utf8string)]
+    pub payload: String,
+}
+
+#[asn(choice, protobuf_numbers(4, 2))]
+#[derive(Debug, PartialEq)]
+pub enum Event {
+    #[asn(integer(0..255))]
+    Count(u8),
+    #[asn(utf8string)]
+    Text(String),
+}
+
+#[asn(sequence, protobuf_numbers(9))]
+#[derive(Debug, PartialEq)]
+pub struct Envelope {
+    #[asn(complex(Event, tag(0)))]
+    pub event: Event,
+}
+
+#[test]
+fn test_sequence_with_field_numbers() {
+    serialize_and_deserialize_protobuf(
+        &[8, 1, 24, 1, 18, 1, 97],
+        &MessageV2 {
+            id: 1,
+            priority: true,
+            payload: "a".into(),
+        },
+    );
+}
+
+#[test]
+fn test_feature_gated_field_numbers() {
+    serialize_and_deserialize_protobuf(
+        &[8, 1, 24, 1, 18, 1, 97],
+        &FeatureGatedMessage {
+            id: 1,
+            priority: true,
+            payload: "a".into(),
+        },
+    );
+}
+
+#[test]
+fn test_revisions_read_each_other() {
+    let v1 = MessageV1 {
+        id: 7,
+        payload: "abc".into(),
+    };
+    let v2 = MessageV2 {
+        id: 7,
+        priority: true,
+        payload: "abc".into(),
+    };
+    assert_eq!(
+        v1,
+        deserialize_protobuf::<MessageV1>(&serialize_protobuf(&v2))
+    );
+    assert_eq!(
+        MessageV2 {
+            priority: false,
+            ..v2
+        },
+        deserialize_protobuf::<MessageV2>(&serialize_protobuf(&v1))
+    );
+}
+
+#[test]
+fn test_choice_with_field_numbers() {
+    serialize_and_deserialize_protobuf(&[32, 5], &Event::Count(5));
+    serialize_and_deserialize_protobuf(&[18, 1, 120], &Event::Text("x".into()));
+    serialize_and_deserialize_protobuf(
+        &[74, 2, 32, 5],
+        &Envelope {
+            event: Event::Count(5),
+        },
+    );
+}
+
+#[test]
+fn test_choice_with_unknown_field_number() {
+    assert!(ProtobufReader::from(&[8, 5][..]).read::<Event>().is_err());
+}
@@ -0,0 +1,55 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use test_utils::*;
+
+#[asn(sequence, protobuf_fixed(hash, offset, samples, timestamp))]
+#[derive(Debug, Default, PartialEq)]
+pub struct Telemetry {
+    #[asn(integer(0..4294967295))]
+    pub hash: u32,
+    #[asn(integer(-2147483648..2147483647))]
+    pub offset: i32,
+    #[asn(sequence_of(integer(0..4294967295)))]
+    pub samples: Vec<u32>,
+    #[asn(optional(integer(-9223372036854775808..9223372036854775807)))]
+    pub timestamp: Option<i64>,
+    #[asn(integer(0..4294967295))]
+    pub counter: u32,
+}
+
+#[asn(choice, protobuf_fixed(Id))]
+#[derive(Debug, PartialEq)]
+pub enum Key {
+    #[asn(integer(0..4294967295))]
+    Id(u32),
+    #[asn(utf8string)]
+    Name(String),
+}
+
+#[test]
+fn test_fixed_integers() {
+    serialize_and_deserialize_protobuf(
+        &[
+            13, 1, 0, 0, 0, // hash
+            21, 254, 255, 255, 255, // offset
+            29, 2, 0, 0, 0, 29, 3, 0, 0, 0, // samples
+            33, 4, 0, 0, 0, 0, 0, 0, 128, // timestamp
+            40, 5, // counter
+        ],
+        &Telemetry {
+            hash: 1,
+            offset: -2,
+            samples: vec![2, 3],
+            timestamp: Some(i64::MIN + 4),
+            counter: 5,
+        },
+    );
+}
+
+#[test]
+fn test_fixed_integers_in_choice() {
+    serialize_and_deserialize_protobuf(&[13, 255, 255, 255, 255], &Key::Id(u32::MAX));
+    serialize_and_deserialize_protobuf(&[18, 1, 97], &Key::Name("a".into()));
+}
@@ -0,0 +1,161 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use std::future::Future;
+use std::pin::pin;
+use std::task::{Context, Poll, Waker};
+use test_utils::*;
+
+#[asn(sequence)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReportRequest {
+    #[asn(utf8string)]
+    pub vehicle: String,
+    #[asn(integer(0..255))]
+    pub speed: u8,
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct ReportResponse {
+    #[asn(boolean)]
+    pub accepted: bool,
+}
+
+// generated for `Telemetry.Report = ReportRequest -> ReportResponse` in the module `Telemetry`
+
+/// The gRPC service `telemetry.Telemetry`
+pub trait Telemetry {
+    type Error;
+
+    fn report(
+        &self,
+        request: ReportRequest,
+    ) -> impl ::core::future::Future<Output = Result<ReportResponse, Self::Error>> + Send;
+}
+
+/// The client of the gRPC service `telemetry.Telemetry`
+pub struct TelemetryClient<C> {
+    pub channel: C,
+}
+
+impl<C: ::asn1rs::grpc::Channel> TelemetryClient<C> {
+    pub fn new(channel: C) -> Self {
+        Self { channel }
+    }
+
+    pub async fn report(
+        &mut self,
+        request: &ReportRequest,
+    ) -> Result<ReportResponse, ::asn1rs::grpc::Error<C::Error>> {
+        ::asn1rs::grpc::call(&mut self.channel, "/telemetry.Telemetry/Report", request).await
+    }
+}
+
+/// Dispatches the calls of the gRPC service `telemetry.Telemetry` to its implementation
+pub struct TelemetryServer<S>(pub S);
+
+impl<S: Telemetry> TelemetryServer<S> {
+    pub const PATHS: &'static [&'static str] = &["/telemetry.Telemetry/Report"];
+
+    /// Decodes the request, calls the method of the path and encodes the response, `None`
+    /// if the service has no method with the path
+    pub async fn handle(
+        &self,
+        path: &str,
+        request: &[u8],
+    ) -> Option<Result<Vec<u8>, ::asn1rs::grpc::Error<S::Error>>> {
+        match path {
+            "/telemetry.Telemetry/Report" => {
+                Some(::asn1rs::grpc::handle(request, |r| self.0.report(r)).await)
+            }
+            _ => None,
+        }
+    }
+}
+
+struct SpeedLimit(u8);
+
+impl Telemetry for SpeedLimit {
+    type Error = String;
+
+    async fn report(&self, request: ReportRequest) -> Result<ReportResponse, Self::Error> {
+        if request.vehicle.is_empty() {
+            Err("unknown vehicle".to_string())
+        } else {
+            Ok(ReportResponse {
+                accepted: request.speed <= self.0,
+            })
+        }
+    }
+}
+
+/// Passes the calls directly to the server instead of sending them over HTTP/2
+struct Loopback(TelemetryServer<SpeedLimit>);
+
+impl asn1rs::grpc::Channel for Loopback {
+    type Error = String;
+
+    async fn unary(
+        &mut self,
+        path: &'static str,
+        request: Vec<u8>,
+    ) -> Result<Vec<u8>, Self::Error> {
+        match self.0.handle(path, &request).await {
+            Some(Ok(response)) => Ok(response),
+            Some(Err(e)) => Err(e.to_string()),
+            None => Err(format!("unimplemented: {path}")),
+        }
+    }
+}
+
+fn block_on<F: Future>(future: F) -> F::Output {
+    let mut future = pin!(future);
+    let mut context = Context::from_waker(Waker::noop());
+    loop {
+        if let Poll::Ready(output) = future.as_mut().poll(&mut context) {
+            return output;
+        }
+    }
+}
+
+#[test]
+fn test_client_calls_server() {
+    let mut client = TelemetryClient::new(Loopback(TelemetryServer(SpeedLimit(50))));
+    let request = |speed| ReportRequest {
+        vehicle: "car".into(),
+        speed,
+    };
+    assert_eq!(
+        ReportResponse { accepted: true },
+        block_on(client.report(&request(30))).unwrap()
+    );
+    assert_eq!(
+        ReportResponse { accepted: false },
+        block_on(client.report(&request(80))).unwrap()
+    );
+    assert!(matches!(
+        block_on(client.report(&ReportRequest::default())),
+        Err(asn1rs::grpc::Error::Status(status)) if status == "unknown vehicle"
+    ));
+}
+
+#[test]
+fn test_server_handles_protobuf_messages() {
+    let server = TelemetryServer(SpeedLimit(50));
+    let request = serialize_protobuf(&ReportRequest {
+        vehicle: "car".into(),
+        speed: 30,
+    });
+    let response = block_on(server.handle(TelemetryServer::<SpeedLimit>::PATHS[0], &request));
+    assert_eq!(
+        ReportResponse { accepted: true },
+        deserialize_protobuf::<ReportResponse>(&response.unwrap().unwrap())
+    );
+    assert!(block_on(server.handle("/telemetry.Telemetry/Unknown", &request)).is_none());
+    assert!(matches!(
+        block_on(server.handle(TelemetryServer::<SpeedLimit>::PATHS[0], &[0xFF])),
+        Some(Err(asn1rs::grpc::Error::Protobuf(_)))
+    ));
+}
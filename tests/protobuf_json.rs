@@ -0,0 +1,134 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use test_utils::*;
+
+#[asn(enumerated)]
+#[derive(Debug, Default, Clone, Copy, PartialEq)]
+pub enum Status {
+    #[default]
+    Idle,
+    RunningFast,
+}
+
+#[asn(choice)]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Payload {
+    #[asn(utf8string)]
+    Text(String),
+    #[asn(octet_string)]
+    RawData(Vec<u8>),
+}
+
+#[asn(sequence)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Message {
+    #[asn(integer(0..255))]
+    pub msg_id: u8,
+    #[asn(integer)]
+    pub timestamp: u64,
+    #[asn(integer(-1000..1000))]
+    pub offset: i16,
+    #[asn(optional(utf8string))]
+    pub user_name: Option<String>,
+    #[asn(complex(Status, tag(UNIVERSAL(10))))]
+    pub status: Status,
+    #[asn(sequence_of(complex(Payload, tag(UNIVERSAL(16)))))]
+    pub payloads: Vec<Payload>,
+    #[asn(boolean)]
+    pub urgent: bool,
+    #[asn(bit_string)]
+    pub flags: BitVec,
+}
+
+fn message() -> Message {
+    Message {
+        msg_id: 7,
+        timestamp: u64::MAX,
+        offset: -5,
+        user_name: None,
+        status: Status::RunningFast,
+        payloads: vec![Payload::Text("hi".into()), Payload::RawData(vec![0xFF])],
+        urgent: true,
+        flags: BitVec::from_bytes(vec![0xA0], 3),
+    }
+}
+
+#[test]
+fn test_to_proto_json() {
+    assert_eq!(
+        concat!(
+            r#"{"msgId":7,"timestamp":"18446744073709551615","offset":-5,"#,
+            r#""status":"STATUS_RUNNING_FAST","payloads":[{"text":"hi"},{"rawData":"/w=="}],"#,
+            r#""urgent":true,"flags":"oAAAAAAAAAAD"}"#
+        ),
+        to_proto_json(&message())
+    );
+}
+
+#[test]
+fn test_from_proto_json_round_trip() {
+    let message = Message {
+        user_name: Some("bob".into()),
+        ..message()
+    };
+    assert_eq!(
+        message,
+        from_proto_json::<Message>(&to_proto_json(&message)).unwrap()
+    );
+}
+
+#[test]
+fn test_from_proto_json_accepts_alternative_spellings() {
+    let json = r#"{
+        "msg_id": "7",
+        "timestamp": 18,
+        "offset": "-5",
+        "user_name": null,
+        "status": 1,
+        "payloads": [{"raw_data": "_w"}],
+        "flags": null,
+        "unknown": [1, 2, 3]
+    }"#;
+    assert_eq!(
+        Message {
+            msg_id: 7,
+            timestamp: 18,
+            offset: -5,
+            user_name: None,
+            status: Status::RunningFast,
+            payloads: vec![Payload::RawData(vec![0xFF])],
+            urgent: false,
+            flags: BitVec::default(),
+        },
+        from_proto_json::<Message>(json).unwrap()
+    );
+}
+
+#[test]
+fn test_from_proto_json_defaults_absent_fields() {
+    let message = from_proto_json::<Message>("{}").unwrap();
+    assert_eq!(0, message.msg_id);
+    assert_eq!(0, message.timestamp);
+    assert_eq!(None, message.user_name);
+    assert_eq!(Status::Idle, message.status);
+    assert!(message.payloads.is_empty());
+}
+
+#[test]
+fn test_from_proto_json_errors() {
+    assert_eq!(
+        Err(asn1rs::protocol::jer::Error::UnknownIdentifier(
+            "RUNNING_FAST".into()
+        )),
+        from_proto_json::<Message>(r#"{"status":"RUNNING_FAST"}"#).map(|_| ())
+    );
+    assert_eq!(
+        Err(asn1rs::protocol::jer::Error::InvalidBase64String(
+            "!".into()
+        )),
+        from_proto_json::<Message>(r#"{"payloads":[{"rawData":"!"}]}"#).map(|_| ())
+    );
+    assert!(from_proto_json::<Message>(r#"{"payloads":[{}]}"#).is_err());
+}
@@ -0,0 +1,54 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use test_utils::*;
+
+asn_to_rust!(
+    r"MyDef DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Inventory ::= SEQUENCE {
+        counts SEQUENCE OF SEQUENCE { key UTF8String, value INTEGER (0..255) }
+    }
+
+    END"
+);
+
+fn entry(key: &str, value: u8) -> InventoryCounts {
+    InventoryCounts {
+        key: key.into(),
+        value,
+    }
+}
+
+#[test]
+fn test_map_entries() {
+    serialize_and_deserialize_protobuf(
+        &[10, 5, 10, 1, 97, 16, 1, 10, 5, 10, 1, 98, 16, 2],
+        &Inventory {
+            counts: vec![entry("a", 1), entry("b", 2)],
+        },
+    );
+}
+
+#[test]
+fn test_map_entries_with_default_key_or_value() {
+    // other implementations omit the key or value of an entry, if it is the default value
+    assert_eq!(
+        Inventory {
+            counts: vec![entry("a", 0), entry("", 7)],
+        },
+        deserialize_protobuf::<Inventory>(&[10, 3, 10, 1, 97, 10, 2, 16, 7])
+    );
+}
+
+#[test]
+fn test_map_entries_in_any_field_order() {
+    assert_eq!(
+        Inventory {
+            counts: vec![entry("a", 1)],
+        },
+        deserialize_protobuf::<Inventory>(&[10, 5, 16, 1, 10, 1, 97])
+    );
+}
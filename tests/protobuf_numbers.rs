@@ -14,6 +14,11 @@ asn_to_rust!(
         should-be-uint64 INTEGER (0..4294967296)
     }
 
+    NegativeNumbers ::= SEQUENCE {
+        unbounded INTEGER (MIN..-5),
+        extensible INTEGER (-10..10, ...)
+    }
+
     END"
 );
 
@@ -31,3 +36,45 @@ fn test_numbers() {
         },
     )
 }
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_negative_numbers_are_zigzag_encoded() {
+    serialize_and_deserialize_protobuf(
+        &[8, 2, 16, 3, 24, 1, 32, 0, 40, 0],
+        &ProtobufNumbers {
+            should_be_sint32: 1_i32,
+            should_be_sint64_1: -2_i64,
+            should_be_sint64_2: -1_i64,
+            should_be_uint32: 0_u32,
+            should_be_uint64: 0_u64,
+        },
+    );
+    serialize_and_deserialize_protobuf(
+        &[8, 9, 16, 19],
+        &NegativeNumbers {
+            unbounded: -5,
+            extensible: -10,
+        },
+    );
+    serialize_and_deserialize_protobuf(
+        &[
+            8, 255, 255, 255, 255, 255, 255, 255, 255, 255, 1, 16, 200, 1,
+        ],
+        &NegativeNumbers {
+            unbounded: i64::MIN,
+            extensible: 100,
+        },
+    );
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_integer_type_without_lower_bound() {
+    use asn1rs::descriptor::numbers::NoConstraint;
+    use asn1rs::protocol::protobuf::IntegerType;
+    assert_eq!(IntegerType::SInt64, IntegerType::of::<i64, NoConstraint>());
+    assert_eq!(IntegerType::SInt64, IntegerType::of::<i8, NoConstraint>());
+    assert_eq!(IntegerType::UInt64, IntegerType::of::<u64, NoConstraint>());
+    assert_eq!(IntegerType::UInt64, IntegerType::of::<u8, NoConstraint>());
+}
@@ -0,0 +1,158 @@
+#![cfg(feature = "protobuf")]
+
+mod test_utils;
+
+use asn1rs::protocol::protobuf::{Format, UnknownField};
+use test_utils::*;
+
+#[asn(sequence, protobuf_unknown_fields)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct MessageV1 {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(utf8string)]
+    pub name: String,
+    pub unknown_fields: UnknownFields,
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Inner {
+    #[asn(integer(0..255))]
+    pub value: u8,
+}
+
+/// A newer revision of [`MessageV1`], with a field of every wire type appended
+#[asn(sequence, protobuf_fixed(checksum, timestamp))]
+#[derive(Debug, Default, PartialEq)]
+pub struct MessageV2 {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(utf8string)]
+    pub name: String,
+    #[asn(integer(0..4294967295))]
+    pub checksum: u32,
+    #[asn(integer)]
+    pub timestamp: u64,
+    #[asn(sequence_of(utf8string))]
+    pub tags: Vec<String>,
+    #[asn(complex(Inner, tag(UNIVERSAL(16))))]
+    pub inner: Inner,
+    #[asn(integer(0..255))]
+    pub count: u8,
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Envelope {
+    #[asn(complex(MessageV1, tag(UNIVERSAL(16))))]
+    pub message: MessageV1,
+}
+
+fn message_v2() -> MessageV2 {
+    MessageV2 {
+        id: 7,
+        name: "abc".into(),
+        checksum: 0xDEAD_BEEF,
+        timestamp: 1_700_000_000,
+        tags: vec!["x".into(), "yz".into()],
+        inner: Inner { value: 3 },
+        count: 200,
+    }
+}
+
+#[test]
+fn test_unknown_fields_survive_decode_modify_encode() {
+    let mut v1 = deserialize_protobuf::<MessageV1>(&serialize_protobuf(&message_v2()));
+    assert_eq!(7, v1.id);
+    assert_eq!("abc", v1.name);
+    assert_eq!(
+        vec![
+            (3, Format::Fixed32),
+            (4, Format::Fixed64),
+            (5, Format::LengthDelimited),
+            (5, Format::LengthDelimited),
+            (6, Format::LengthDelimited),
+            (7, Format::VarInt),
+        ],
+        v1.unknown_fields
+            .iter()
+            .map(|field| (field.number, field.format))
+            .collect::<Vec<_>>()
+    );
+
+    v1.name = "modified".into();
+    assert_eq!(
+        MessageV2 {
+            name: "modified".into(),
+            ..message_v2()
+        },
+        deserialize_protobuf::<MessageV2>(&serialize_protobuf(&v1))
+    );
+}
+
+#[test]
+fn test_unknown_fields_of_nested_message() {
+    let bytes = [10, 7, 8, 1, 18, 1, 97, 56, 5];
+    let envelope = deserialize_protobuf::<Envelope>(&bytes);
+    assert_eq!(
+        vec![UnknownField {
+            number: 7,
+            format: Format::VarInt,
+            value: vec![5],
+        }],
+        envelope
+            .message
+            .unknown_fields
+            .iter()
+            .cloned()
+            .collect::<Vec<_>>()
+    );
+    serialize_and_deserialize_protobuf(&bytes, &envelope);
+}
+
+#[test]
+fn test_unknown_groups_with_nested_groups() {
+    // group 5 { field 1 = 9, group 6 { field 2 = 1 } }, field 8 = 2
+    let bytes = [8, 1, 18, 0, 43, 8, 9, 51, 16, 1, 52, 44, 64, 2];
+    let message = deserialize_protobuf::<MessageV1>(&bytes);
+    assert_eq!(
+        vec![
+            UnknownField {
+                number: 5,
+                format: Format::StartGroup,
+                value: vec![8, 9, 51, 16, 1, 52],
+            },
+            UnknownField {
+                number: 8,
+                format: Format::VarInt,
+                value: vec![2],
+            },
+        ],
+        message.unknown_fields.iter().cloned().collect::<Vec<_>>()
+    );
+    serialize_and_deserialize_protobuf(&bytes, &message);
+}
+
+#[test]
+fn test_unterminated_group_is_an_error() {
+    assert!(ProtobufReader::from(&[8, 1, 43, 8, 9][..])
+        .read::<MessageV1>()
+        .is_err());
+    assert!(ProtobufReader::from(&[8, 1, 43, 8, 9, 52][..])
+        .read::<MessageV1>()
+        .is_err());
+}
+
+#[test]
+fn test_other_codecs_ignore_unknown_fields() {
+    let mut message = deserialize_protobuf::<MessageV1>(&[8, 1, 56, 5]);
+    assert_eq!(1, message.unknown_fields.len());
+
+    let mut writer = UperWriter::default();
+    writer.write(&message).unwrap();
+    let read = writer.as_reader().read::<MessageV1>().unwrap();
+
+    message.unknown_fields.clear();
+    assert_eq!(message, read);
+}
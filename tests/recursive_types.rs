@@ -0,0 +1,101 @@
+mod test_utils;
+
+use asn1rs::codec::Codec;
+use test_utils::*;
+
+asn_to_rust!(
+    r"RecursiveTypes DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Node ::= SEQUENCE {
+        value INTEGER (0..255),
+        next Node OPTIONAL
+    }
+
+    Expr ::= CHOICE {
+        literal INTEGER (0..255),
+        negated Expr,
+        sum Sum
+    }
+
+    Sum ::= SEQUENCE {
+        left Expr,
+        right Expr
+    }
+
+    Tree ::= SEQUENCE {
+        children SEQUENCE OF Tree
+    }
+
+    END"
+);
+
+fn list() -> Node {
+    Node {
+        value: 1,
+        next: Some(Box::new(Node {
+            value: 2,
+            next: None,
+        })),
+    }
+}
+
+fn expr() -> Expr {
+    // -(1 + -2)
+    Expr::Negated(Box::new(Expr::Sum(Sum {
+        left: Box::new(Expr::Literal(1)),
+        right: Box::new(Expr::Negated(Box::new(Expr::Literal(2)))),
+    })))
+}
+
+#[test]
+fn test_cycles_are_boxed_once() {
+    // does not compile if the types are not boxed as expected
+    let _ = Expr::Sum(Sum::default());
+    let _ = Tree {
+        children: vec![Tree::default()],
+    };
+}
+
+#[test]
+fn test_uper_optional_recursion() {
+    // presence, value, presence, value
+    serialize_and_deserialize_uper(1 + 8 + 1 + 8, &[0x80, 0x80, 0x80], &list());
+}
+
+#[test]
+fn test_uper_mutual_recursion() {
+    // choice index of negated, of sum, of literal 1, of negated, of literal 2
+    serialize_and_deserialize_uper(
+        2 + 2 + 2 + 8 + 2 + 2 + 8,
+        &[0x60, 0x05, 0x00, 0x80],
+        &expr(),
+    );
+}
+
+#[test]
+fn test_round_trips() {
+    let tree = Tree {
+        children: vec![
+            Tree {
+                children: vec![Tree::default()],
+            },
+            Tree::default(),
+        ],
+    };
+    for codec in [Codec::Uper, Codec::Jer] {
+        let bytes = codec.encode(&list()).unwrap();
+        assert_eq!(list(), codec.decode::<Node>(&bytes).unwrap());
+        let bytes = codec.encode(&expr()).unwrap();
+        assert_eq!(expr(), codec.decode::<Expr>(&bytes).unwrap());
+        let bytes = codec.encode(&tree).unwrap();
+        assert_eq!(tree, codec.decode::<Tree>(&bytes).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_protobuf_round_trip() {
+    serialize_and_deserialize_protobuf(&serialize_protobuf(&list()), &list());
+    serialize_and_deserialize_protobuf(&serialize_protobuf(&expr()), &expr());
+}
@@ -0,0 +1,60 @@
+use asn1rs::descriptor::boolean::NoConstraint;
+use asn1rs::descriptor::Boolean;
+use asn1rs::prelude::basic::DER;
+use asn1rs::prelude::*;
+
+#[asn(choice)]
+#[derive(Debug, PartialEq)]
+pub enum Request {
+    #[asn(utf8string)]
+    Query(String),
+    #[asn(integer(0..255))]
+    Cancel(u8),
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Response {
+    #[asn(integer(0..65535))]
+    pub status: u16,
+    #[asn(utf8string)]
+    pub body: String,
+}
+
+#[test]
+fn test_uper_try_read_restores_the_position_on_failure() {
+    let mut writer = UperWriter::default();
+    writer.write(&Request::Cancel(7)).unwrap();
+    writer.write(&Request::Query("next".to_string())).unwrap();
+
+    let mut reader = writer.as_reader();
+    assert!(reader.try_read::<Response>().is_err());
+    assert_eq!(Request::Cancel(7), reader.try_read::<Request>().unwrap());
+
+    let checkpoint = reader.checkpoint();
+    let remaining = reader.bits_remaining();
+    assert_eq!(
+        Request::Query("next".to_string()),
+        reader.read::<Request>().unwrap()
+    );
+    assert_eq!(0, reader.bits_remaining());
+
+    reader.restore(checkpoint);
+    assert_eq!(remaining, reader.bits_remaining());
+    assert_eq!(
+        Request::Query("next".to_string()),
+        reader.read::<Request>().unwrap()
+    );
+}
+
+#[test]
+fn test_der_restore() {
+    let mut reader = DER::reader(&[0x01, 0x01, 0xFF, 0x01, 0x01, 0x00][..]);
+    let checkpoint = reader.checkpoint();
+    assert!(Boolean::<NoConstraint>::read_value(&mut reader).unwrap());
+
+    reader.restore(checkpoint);
+    assert!(Boolean::<NoConstraint>::read_value(&mut reader).unwrap());
+    assert!(!Boolean::<NoConstraint>::read_value(&mut reader).unwrap());
+    assert!(reader.into_inner().is_empty());
+}
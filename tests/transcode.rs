@@ -0,0 +1,198 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::codec::{transcode, AnyPdu, Codec, Error, PduRegistry};
+use asn1rs::protocol::jer::Error as JerError;
+use test_utils::*;
+
+asn_to_rust!(
+    r"Gateway DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Status ::= ENUMERATED { idle, busy, offline }
+
+    Payload ::= CHOICE {
+        raw     OCTET STRING,
+        text    UTF8String,
+        nothing NULL
+    }
+
+    Message ::= SEQUENCE {
+        id          INTEGER (0..65535),
+        status      Status,
+        payload     Payload,
+        flags       BIT STRING (SIZE(1..16)),
+        tags        SEQUENCE OF IA5String,
+        ack         BOOLEAN,
+        comment     UTF8String OPTIONAL,
+        retries     INTEGER (0..7) DEFAULT 3
+    }
+
+    END"
+);
+
+fn message() -> Message {
+    Message {
+        id: 513,
+        status: Status::Busy,
+        payload: Payload::Text("say \"hi\"".to_string()),
+        flags: BitVec::from_bytes(vec![0xA0], 3),
+        tags: vec!["a".to_string(), "b".to_string()],
+        ack: true,
+        comment: None,
+        retries: 5,
+    }
+}
+
+const MESSAGE_JER: &str = r#"{"id":513,"status":"busy","payload":{"text":"say \"hi\""},"flags":{"value":"a0","length":3},"tags":["a","b"],"ack":true,"retries":5}"#;
+
+#[test]
+fn test_jer_encode_and_decode() {
+    assert_eq!(
+        MESSAGE_JER.as_bytes(),
+        &Codec::Jer.encode(&message()).unwrap()[..]
+    );
+    assert_eq!(
+        message(),
+        Codec::Jer
+            .decode::<Message>(MESSAGE_JER.as_bytes())
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_jer_absent_optional_and_default() {
+    let json = r#"{ "status": "idle", "id": 1, "payload": {"nothing": null}, "unknown": [1, 2],
+        "flags": {"value": "80", "length": 1}, "tags": [], "ack": false, "comment": "x" }"#;
+    assert_eq!(
+        Message {
+            id: 1,
+            status: Status::Idle,
+            payload: Payload::Nothing(Null),
+            flags: BitVec::from_bytes(vec![0x80], 1),
+            tags: Vec::new(),
+            ack: false,
+            comment: Some("x".to_string()),
+            retries: 3,
+        },
+        Codec::Jer.decode::<Message>(json.as_bytes()).unwrap()
+    );
+}
+
+#[test]
+fn test_jer_errors() {
+    assert!(matches!(
+        Codec::Jer.decode::<Message>(br#"{"id":1}"#),
+        Err(Error::Jer(JerError::MissingMember("status")))
+    ));
+    assert!(matches!(
+        Codec::Jer.decode::<Status>(br#""sleeping""#),
+        Err(Error::Jer(JerError::UnknownIdentifier(name))) if name == "sleeping"
+    ));
+    assert!(matches!(
+        Codec::Jer.decode::<Payload>(br#"{"raw":"0g"}"#),
+        Err(Error::Jer(JerError::InvalidHexString(_)))
+    ));
+    assert!(matches!(
+        Codec::Jer.decode::<Payload>(&[0xFF]),
+        Err(Error::InvalidUtf8)
+    ));
+}
+
+#[test]
+fn test_transcode_uper_and_jer() {
+    let uper = Codec::Uper.encode(&message()).unwrap();
+    assert_eq!(serialize_uper(&message()).1, uper);
+
+    let jer = transcode::<Message>(Codec::Uper, Codec::Jer, &uper).unwrap();
+    assert_eq!(MESSAGE_JER.as_bytes(), &jer[..]);
+    assert_eq!(
+        uper,
+        transcode::<Message>(Codec::Jer, Codec::Uper, &jer).unwrap()
+    );
+}
+
+#[test]
+fn test_transcode_der() {
+    // the DER support of the generated types is limited to primitives so far
+    let der = transcode::<Status>(Codec::Jer, Codec::Der, br#""offline""#).unwrap();
+    assert_eq!(vec![0x0A, 0x01, 0x02], der);
+    assert_eq!(
+        br#""offline""#,
+        &transcode::<Status>(Codec::Der, Codec::Jer, &der).unwrap()[..]
+    );
+    assert_eq!(
+        vec![0x80],
+        transcode::<Status>(Codec::Der, Codec::Uper, &der).unwrap()
+    );
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_transcode_protobuf() {
+    let uper = Codec::Uper.encode(&message()).unwrap();
+    let protobuf = transcode::<Message>(Codec::Uper, Codec::Protobuf, &uper).unwrap();
+    assert_eq!(serialize_protobuf(&message()), protobuf);
+    assert_eq!(
+        MESSAGE_JER.as_bytes(),
+        &transcode::<Message>(Codec::Protobuf, Codec::Jer, &protobuf).unwrap()[..]
+    );
+}
+
+#[test]
+fn test_any_pdu_registry() {
+    #[derive(Debug, PartialEq)]
+    enum Received {
+        Message(Box<Message>),
+        Status(Status),
+    }
+
+    let mut registry = PduRegistry::default();
+    registry
+        .register::<Message>(|message| Received::Message(Box::new(message)))
+        .register::<Status>(Received::Status);
+
+    let envelopes = [
+        AnyPdu::encode(Codec::Uper, &message())
+            .unwrap()
+            .to_envelope(),
+        AnyPdu::encode(Codec::Der, &Status::Offline)
+            .unwrap()
+            .to_envelope(),
+        AnyPdu::encode(Codec::Jer, &message())
+            .unwrap()
+            .to_envelope(),
+    ];
+    let received = envelopes
+        .iter()
+        .map(|envelope| registry.decode(&AnyPdu::from_envelope(envelope).unwrap()))
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(
+        vec![
+            Received::Message(Box::new(message())),
+            Received::Status(Status::Offline),
+            Received::Message(Box::new(message())),
+        ],
+        received
+    );
+
+    let pdu = AnyPdu::from_envelope(&envelopes[1]).unwrap();
+    assert_eq!(Codec::Der, pdu.codec());
+    assert!(pdu.is::<Status>());
+    assert_eq!(Status::Offline, pdu.decode::<Status>().unwrap());
+    assert!(matches!(
+        pdu.decode::<Message>(),
+        Err(Error::TypeMismatch { .. })
+    ));
+    assert!(registry
+        .type_name(pdu.fingerprint())
+        .is_some_and(|name| name.ends_with("Status")));
+
+    let unknown = AnyPdu::encode(Codec::Uper, &Payload::Nothing(Null)).unwrap();
+    assert!(matches!(
+        registry.decode(&unknown),
+        Err(Error::UnregisteredType(fingerprint)) if fingerprint == unknown.fingerprint()
+    ));
+}
@@ -0,0 +1,45 @@
+#![cfg(feature = "bytes")]
+
+mod test_utils;
+
+use asn1rs::rw::BytesBits;
+use bytes::Bytes;
+use test_utils::*;
+
+asn_to_rust!(
+    r"Traffic DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..65535),
+        payload OCTET STRING,
+        comment UTF8String OPTIONAL
+    }
+
+    END"
+);
+
+fn message() -> Message {
+    Message {
+        id: 4711,
+        payload: vec![0xDE, 0xAD, 0xBE, 0xEF],
+        comment: Some("hello".to_string()),
+    }
+}
+
+#[test]
+fn test_into_bytes_and_read_from_bytes() {
+    let mut writer = UperWriter::default();
+    writer.write(&message()).unwrap();
+    let expected = writer.byte_content().to_vec();
+
+    let bytes: Bytes = writer.into_bytes();
+    assert_eq!(&expected[..], &bytes[..]);
+
+    // the reader owns a cheap clone and does not borrow from the original
+    let mut reader = UperReader::from(bytes.clone());
+    assert_eq!(message(), reader.read::<Message>().unwrap());
+
+    let mut reader = UperReader::from(BytesBits::from(bytes.slice(..2)));
+    assert!(reader.read::<Message>().is_err());
+}
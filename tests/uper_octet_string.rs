@@ -46,3 +46,58 @@ pub fn octet_string_fragmented(value_len: usize, bits: usize, bytes: &[u8]) {
     };
     serialize_and_deserialize_uper(bits, bytes, &container);
 }
+
+#[test]
+pub fn octet_string_fragmented_63k() {
+    // 48K in the first fragment, the remaining 15K with a two byte length
+    octet_string_fragments(63 * 1024, &[(0, &[0xC3]), (1 + 49152, &[0xBC, 0x00])]);
+}
+
+#[test]
+pub fn octet_string_fragmented_64k() {
+    // an empty fragment terminates a fragment of a 16K multiple
+    octet_string_fragments(64 * 1024, &[(0, &[0xC4]), (1 + 65536, &[0x00])]);
+}
+
+#[test]
+pub fn octet_string_fragmented_65k() {
+    octet_string_fragments(65 * 1024, &[(0, &[0xC4]), (1 + 65536, &[0x84, 0x00])]);
+}
+
+#[test]
+pub fn octet_string_fragmented_300k() {
+    // four fragments of 64K, one of 32K and the remaining 12K
+    let full = 1 + 65536;
+    octet_string_fragments(
+        300 * 1024,
+        &[
+            (0, &[0xC4]),
+            (full, &[0xC4]),
+            (2 * full, &[0xC4]),
+            (3 * full, &[0xC4]),
+            (4 * full, &[0xC2]),
+            (4 * full + 1 + 32768, &[0xB0, 0x00]),
+        ],
+    );
+}
+
+/// Checks the length determinants at the given byte positions of the serialized value, whose
+/// content differs from fragment to fragment
+pub fn octet_string_fragments(value_len: usize, length_determinants: &[(usize, &[u8])]) {
+    let container = Container {
+        value: (0..value_len).map(|i| (i % 251) as u8).collect(),
+    };
+    let (bits, bytes) = serialize_uper(&container);
+    let overhead = length_determinants
+        .iter()
+        .map(|(_, determinant)| determinant.len())
+        .sum::<usize>();
+    assert_eq!((value_len + overhead) * 8, bits);
+    for (position, determinant) in length_determinants {
+        assert_eq!(
+            *determinant,
+            &bytes[*position..*position + determinant.len()]
+        );
+    }
+    assert_eq!(container, deserialize_uper::<Container>(&bytes, bits));
+}
@@ -0,0 +1,72 @@
+use asn1rs::prelude::*;
+
+#[asn(sequence)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Content {
+    #[asn(integer(0..255))]
+    pub id: u8,
+    #[asn(utf8string)]
+    pub text: String,
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, PartialEq)]
+pub struct Trailer {
+    #[asn(boolean)]
+    pub last: bool,
+    #[asn(integer(0..7))]
+    pub checksum: u8,
+}
+
+fn content() -> Content {
+    Content {
+        id: 42,
+        text: "open type".to_string(),
+    }
+}
+
+fn trailer() -> Trailer {
+    Trailer {
+        last: true,
+        checksum: 5,
+    }
+}
+
+/// The content - as if it were an open type - followed by the trailer
+fn content_and_trailer() -> (usize, UperWriter) {
+    let mut writer = UperWriter::default();
+    writer.write(&content()).unwrap();
+    let content_bits = writer.bit_len();
+    writer.write(&trailer()).unwrap();
+    (content_bits, writer)
+}
+
+#[test]
+fn test_scoped_reader_cannot_overrun_the_window() {
+    let (content_bits, writer) = content_and_trailer();
+    let mut reader = writer.as_reader();
+    {
+        let mut scoped = reader.scoped(content_bits).unwrap();
+        assert_eq!(content(), scoped.read::<Content>().unwrap());
+        assert_eq!(0, scoped.bits_remaining());
+        assert!(scoped.read::<Trailer>().is_err());
+    }
+    assert_eq!(trailer(), reader.read::<Trailer>().unwrap());
+}
+
+#[test]
+fn test_scoped_reader_skips_the_unread_window() {
+    let (content_bits, writer) = content_and_trailer();
+    let mut reader = writer.as_reader();
+    drop(reader.scoped(content_bits).unwrap());
+    assert_eq!(trailer(), reader.read::<Trailer>().unwrap());
+}
+
+#[test]
+fn test_scoped_reader_exceeding_the_input() {
+    let (_content_bits, writer) = content_and_trailer();
+    let mut reader = writer.as_reader();
+    let remaining = reader.bits_remaining();
+    assert!(reader.scoped(remaining + 1).is_err());
+    assert_eq!(content(), reader.read::<Content>().unwrap());
+}
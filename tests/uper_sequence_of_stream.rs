@@ -0,0 +1,128 @@
+use asn1rs::descriptor::common;
+use asn1rs::descriptor::sequenceof;
+use asn1rs::model::asn::Tag;
+use asn1rs::prelude::*;
+use asn1rs::rw::{UperSequenceOfReader, UperStreamError};
+use std::io::Read;
+
+#[asn(sequence, extensible_after(name))]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Frame {
+    #[asn(integer(0..65535))]
+    pub id: u16,
+    #[asn(optional(utf8string))]
+    pub name: Option<String>,
+    #[asn(optional(integer(0..255)))]
+    pub extra: Option<u8>,
+}
+
+#[asn(transparent)]
+#[derive(Debug, PartialEq)]
+pub struct Recording(#[asn(sequence_of(complex(Frame, tag(UNIVERSAL(16)))))] Vec<Frame>);
+
+#[asn(transparent)]
+#[derive(Debug, PartialEq)]
+pub struct ShortRecording(
+    #[asn(sequence_of(size(1..4), complex(Frame, tag(UNIVERSAL(16)))))] Vec<Frame>,
+);
+
+#[derive(Default)]
+pub struct Size1To4;
+
+impl common::Constraint for Size1To4 {
+    const TAG: Tag = Tag::DEFAULT_SEQUENCE_OF;
+}
+
+impl sequenceof::Constraint for Size1To4 {
+    const MIN: Option<u64> = Some(1);
+    const MAX: Option<u64> = Some(4);
+}
+
+fn frames(count: u16) -> Vec<Frame> {
+    (0..count)
+        .map(|id| Frame {
+            id,
+            name: (id % 3 == 0).then(|| "x".repeat(usize::from(id % 50))),
+            extra: (id % 2 == 0).then_some((id % 256) as u8),
+        })
+        .collect()
+}
+
+fn encode<T: Writable>(value: &T) -> Vec<u8> {
+    let mut writer = UperWriter::default();
+    writer.write(value).unwrap();
+    writer.into_bytes_vec()
+}
+
+#[test]
+fn test_elements_across_chunk_boundaries() {
+    let frames = frames(1000);
+    let bytes = encode(&Recording(frames.clone()));
+
+    for chunk_len in [1, 7, 4096] {
+        let reader = UperSequenceOfReader::<_, Frame>::new(&bytes[..]).with_chunk_len(chunk_len);
+        let decoded = reader.collect::<Result<Vec<_>, _>>().unwrap();
+        assert_eq!(frames, decoded);
+    }
+}
+
+#[test]
+fn test_remaining_and_empty() {
+    let bytes = encode(&Recording(frames(3)));
+    let mut reader = UperSequenceOfReader::<_, Frame>::new(&bytes[..]);
+    assert_eq!(None, reader.remaining());
+    assert!(reader.next().unwrap().is_ok());
+    assert_eq!(Some(2), reader.remaining());
+    assert_eq!(2, reader.by_ref().count());
+    assert!(reader.next().is_none());
+
+    let bytes = encode(&Recording(Vec::new()));
+    let mut reader = UperSequenceOfReader::<_, Frame>::new(&bytes[..]);
+    assert!(reader.next().is_none());
+    assert_eq!(Some(0), reader.remaining());
+}
+
+#[test]
+fn test_size_constraint() {
+    let frames = frames(4);
+    let bytes = encode(&ShortRecording(frames.clone()));
+    let decoded = UperSequenceOfReader::<_, Frame, Size1To4>::new(&bytes[..])
+        .with_chunk_len(1)
+        .collect::<Result<Vec<_>, _>>()
+        .unwrap();
+    assert_eq!(frames, decoded);
+}
+
+#[test]
+fn test_truncated_input() {
+    let bytes = encode(&Recording(frames(10)));
+    let mut reader = UperSequenceOfReader::<_, Frame>::new(&bytes[..bytes.len() - 1]);
+    assert_eq!(9, reader.by_ref().take(9).filter(Result::is_ok).count());
+    assert!(matches!(reader.next(), Some(Err(UperStreamError::Uper(_)))));
+    assert!(reader.next().is_none());
+}
+
+#[test]
+fn test_io_error() {
+    struct Failing<'a>(&'a [u8]);
+
+    impl Read for Failing<'_> {
+        fn read(&mut self, buf: &mut [u8]) -> std::io::Result<usize> {
+            if self.0.is_empty() {
+                Err(std::io::Error::other("disconnected"))
+            } else {
+                self.0.read(buf)
+            }
+        }
+    }
+
+    let bytes = encode(&Recording(frames(10)));
+    let mut reader =
+        UperSequenceOfReader::<_, Frame>::new(Failing(&bytes[..bytes.len() / 2])).with_chunk_len(1);
+    let decoded = reader.by_ref().take_while(Result::is_ok).count();
+    assert!(decoded > 0 && decoded < 10);
+    assert!(reader.next().is_none());
+
+    let mut reader = UperSequenceOfReader::<_, Frame>::new(Failing(&[]));
+    assert!(matches!(reader.next(), Some(Err(UperStreamError::Io(_)))));
+}
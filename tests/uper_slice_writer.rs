@@ -0,0 +1,139 @@
+mod test_utils;
+
+use asn1rs::protocol::per::ErrorKind;
+use test_utils::*;
+
+asn_to_rust!(
+    r"Traffic DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..65535),
+        payload OCTET STRING,
+        comment UTF8String OPTIONAL,
+        ...,
+        note    UTF8String OPTIONAL,
+        blob    OCTET STRING OPTIONAL
+    }
+
+    END"
+);
+
+fn message() -> Message {
+    Message {
+        id: 4711,
+        payload: vec![0xDE, 0xAD, 0xBE, 0xEF],
+        comment: Some("hello".to_string()),
+        note: Some("extended".to_string()),
+        blob: None,
+    }
+}
+
+fn expected_of(message: &Message) -> (usize, Vec<u8>) {
+    let mut writer = UperWriter::default();
+    writer.write(message).unwrap();
+    (writer.bit_len(), writer.into_bytes_vec())
+}
+
+fn expected() -> (usize, Vec<u8>) {
+    expected_of(&message())
+}
+
+#[test]
+fn test_write_into_slice() {
+    let (bit_len, bytes) = expected();
+    // the padding bits of the last byte are zero even if the slice was not
+    let mut slice = [0xFF_u8; 64];
+    let mut writer = UperWriter::from(&mut slice[..]);
+    writer.write(&message()).unwrap();
+    assert_eq!(bit_len, writer.bit_len());
+    assert_eq!(&bytes[..], writer.byte_content());
+    assert_eq!(message(), writer.as_reader().read::<Message>().unwrap());
+    assert_eq!(&bytes[..], &slice[..bytes.len()]);
+    assert!(slice[bytes.len()..].iter().all(|b| *b == 0));
+}
+
+#[test]
+fn test_write_fragmented_extension_into_slice() {
+    let message = Message {
+        blob: Some((0..40_000).map(|i| i as u8).collect()),
+        ..message()
+    };
+    let (bit_len, bytes) = expected_of(&message);
+    let mut slice = vec![0xFF_u8; bytes.len() + 100];
+    let mut writer = UperWriter::from(&mut slice[..]);
+    writer.write(&message).unwrap();
+    assert_eq!(bit_len, writer.bit_len());
+    assert_eq!(&bytes[..], writer.byte_content());
+    assert!(slice[bytes.len()..].iter().all(|b| *b == 0));
+}
+
+#[test]
+fn test_write_extension_into_exactly_sized_slice() {
+    let message = Message {
+        blob: Some(vec![0xAB; 1000]),
+        ..message()
+    };
+    let (_, bytes) = expected_of(&message);
+    let mut slice = vec![0_u8; bytes.len()];
+    let mut writer = UperWriter::from(&mut slice[..]);
+    writer.write(&message).unwrap();
+    assert_eq!(bytes, slice);
+}
+
+#[test]
+fn test_write_extension_into_too_small_slice() {
+    let message = Message {
+        blob: Some(vec![0xAB; 1000]),
+        ..message()
+    };
+    let (_, bytes) = expected_of(&message);
+    for missing in [1, 2, 500, 1000] {
+        let mut slice = vec![0_u8; bytes.len() - missing];
+        let mut writer = UperWriter::from(&mut slice[..]);
+        let error = writer.write(&message).unwrap_err();
+        assert!(matches!(error.kind(), ErrorKind::BufferTooSmall(..)));
+        writer.clear();
+        assert!(slice.iter().all(|b| *b == 0));
+    }
+}
+
+#[test]
+fn test_write_into_exactly_sized_slice() {
+    let (bit_len, bytes) = expected();
+    let mut slice = vec![0_u8; bytes.len()];
+    let mut writer = UperWriter::from(&mut slice[..]);
+    writer.write(&message()).unwrap();
+    assert_eq!(bit_len, writer.bit_len());
+    assert_eq!(bytes, slice);
+}
+
+#[test]
+fn test_write_into_too_small_slice() {
+    let (_, bytes) = expected();
+    let mut slice = vec![0_u8; bytes.len() - 1];
+    let slice_bits = slice.len() * 8;
+    let mut writer = UperWriter::from(&mut slice[..]);
+    let error = writer.write(&message()).unwrap_err();
+    assert!(matches!(
+        error.kind(),
+        ErrorKind::BufferTooSmall(required, capacity)
+            if *required > *capacity && *capacity == slice_bits
+    ));
+    writer.clear();
+    assert!(slice.iter().all(|b| *b == 0));
+}
+
+#[test]
+fn test_reuse_slice_after_clear() {
+    let (_, bytes) = expected();
+    let mut slice = [0_u8; 64];
+    let mut writer = UperWriter::from(&mut slice[..]);
+    for _ in 0..3 {
+        writer.write(&message()).unwrap();
+        assert_eq!(&bytes[..], writer.byte_content());
+        writer.clear();
+        assert_eq!(0, writer.bit_len());
+    }
+    assert!(slice.iter().all(|b| *b == 0));
+}
@@ -0,0 +1,53 @@
+mod test_utils;
+
+use std::io::{IoSlice, Write};
+use test_utils::*;
+
+asn_to_rust!(
+    r"Traffic DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Message ::= SEQUENCE {
+        id      INTEGER (0..65535),
+        payload OCTET STRING
+    }
+
+    END"
+);
+
+#[test]
+fn test_one_writer_for_many_messages() {
+    let mut writer = UperWriter::default();
+    writer.reserve(8 * 1024);
+    for id in 0..100 {
+        writer.clear();
+        let message = Message {
+            id,
+            payload: vec![id as u8; usize::from(id)],
+        };
+        writer.write(&message).unwrap();
+        assert_eq!(message, writer.as_reader().read::<Message>().unwrap());
+    }
+}
+
+#[test]
+fn test_write_vectored_behind_a_header() {
+    let mut writer = UperWriter::default();
+    writer
+        .write(&Message {
+            id: 1,
+            payload: vec![0x42],
+        })
+        .unwrap();
+    let content = writer.byte_content().to_vec();
+
+    let header = (content.len() as u16).to_be_bytes();
+    let [body] = writer.as_io_slices();
+    let body_len = body.len();
+    let mut frame = Vec::new();
+    let written = frame
+        .write_vectored(&[IoSlice::new(&header), body])
+        .unwrap();
+    assert_eq!(header.len() + body_len, written);
+    assert_eq!([&header[..], &content[..]].concat(), frame);
+}
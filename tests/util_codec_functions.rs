@@ -0,0 +1,46 @@
+use asn1rs::prelude::*;
+use asn1rs::util::{from_der_bytes, from_jer_str, from_uper_bytes};
+use asn1rs::util::{to_der_bytes, to_jer_string, to_uper_bytes};
+
+asn_to_rust!(
+    r"UtilCodecFunctions DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+      Basic ::= SEQUENCE {
+        abc UTF8String,
+        def INTEGER
+      }
+
+      Color ::= ENUMERATED { red, green, blue }
+
+    END"
+);
+
+fn basic() -> Basic {
+    Basic {
+        abc: "hello world".to_string(),
+        def: 778,
+    }
+}
+
+#[test]
+fn test_uper_bytes() {
+    let bytes = to_uper_bytes(&basic()).unwrap();
+    assert_eq!(basic(), from_uper_bytes::<Basic>(&bytes).unwrap());
+}
+
+#[test]
+fn test_der_bytes() {
+    let bytes = to_der_bytes(&Color::Green).unwrap();
+    assert_eq!(vec![0x0A, 0x01, 0x01], bytes);
+    assert_eq!(Color::Green, from_der_bytes::<Color>(&bytes).unwrap());
+    assert!(from_der_bytes::<Color>(&bytes[..2]).is_err());
+}
+
+#[test]
+fn test_jer_string() {
+    let json = to_jer_string(&basic());
+    assert_eq!(r#"{"abc":"hello world","def":778}"#, json);
+    assert_eq!(basic(), from_jer_str::<Basic>(&json).unwrap());
+    assert!(from_jer_str::<Basic>(r#"{"abc":"hello world"}"#).is_err());
+}
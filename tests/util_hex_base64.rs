@@ -0,0 +1,42 @@
+use asn1rs::prelude::*;
+use asn1rs::util::{from_uper_base64, from_uper_hex, to_uper_base64, to_uper_hex};
+
+asn_to_rust!(
+    r"UtilHexBase64 DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+      Basic ::= SEQUENCE {
+        abc UTF8String,
+        def INTEGER
+      }
+
+    END"
+);
+
+fn basic() -> Basic {
+    Basic {
+        abc: "hello world".to_string(),
+        def: 778,
+    }
+}
+
+#[test]
+fn test_uper_hex() {
+    let hex = to_uper_hex(&basic()).unwrap();
+    assert_eq!("0b68656c6c6f20776f726c6402030a", hex);
+    assert_eq!(basic(), from_uper_hex::<Basic>(&hex).unwrap());
+    assert_eq!(
+        basic(),
+        from_uper_hex::<Basic>(
+            "0x0B 0x68 0x65 0x6C 0x6C 0x6F 0x20 0x77 0x6F 0x72 0x6C 0x64 0x02 0x03 0x0A"
+        )
+        .unwrap()
+    );
+}
+
+#[test]
+fn test_uper_base64() {
+    let base64 = to_uper_base64(&basic()).unwrap();
+    assert_eq!("C2hlbGxvIHdvcmxkAgMK", base64);
+    assert_eq!(basic(), from_uper_base64::<Basic>(&base64).unwrap());
+}
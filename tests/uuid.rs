@@ -0,0 +1,96 @@
+#![cfg(feature = "uuid")]
+
+mod test_utils;
+
+use asn1rs::codec::Codec;
+use test_utils::*;
+
+const ID: Uuid = Uuid::from_bytes([
+    0x67, 0xE5, 0x50, 0x44, 0x10, 0xB1, 0x42, 0x6F, 0x92, 0x47, 0xBB, 0x68, 0x0E, 0x5F, 0xE0, 0xC8,
+]);
+
+#[asn(sequence, uuid(id, parent, members))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Session {
+    #[asn(octet_string(size(16)))]
+    pub id: Uuid,
+    #[asn(optional(octet_string(size(16))))]
+    pub parent: Option<Uuid>,
+    #[asn(sequence_of(octet_string(size(16))))]
+    pub members: Vec<Uuid>,
+    #[asn(octet_string(size(16)))]
+    pub token: Vec<u8>,
+}
+
+#[asn(sequence, uuid(id))]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct Identifier {
+    #[asn(octet_string(size(16)))]
+    pub id: Uuid,
+}
+
+#[asn(sequence)]
+#[derive(Debug, Default, Clone, PartialEq)]
+pub struct RawIdentifier {
+    #[asn(octet_string(size(16)))]
+    pub id: Vec<u8>,
+}
+
+#[asn(choice, uuid(Id))]
+#[derive(Debug, Clone, PartialEq)]
+pub enum Key {
+    #[asn(octet_string(size(16)))]
+    Id(Uuid),
+    #[asn(utf8string)]
+    Name(String),
+}
+
+fn session() -> Session {
+    Session {
+        id: ID,
+        parent: None,
+        members: vec![Uuid::nil(), ID],
+        token: ID.as_bytes().to_vec(),
+    }
+}
+
+#[test]
+fn test_uper_encodes_the_raw_bytes() {
+    let raw = RawIdentifier {
+        id: ID.as_bytes().to_vec(),
+    };
+    assert_eq!((128, ID.as_bytes().to_vec()), serialize_uper(&raw));
+    serialize_and_deserialize_uper(128, ID.as_bytes(), &Identifier { id: ID });
+
+    let (bits, bytes) = serialize_uper(&session());
+    // presence of parent, id, length and items of members, token
+    assert_eq!(1 + 128 + 8 + 2 * 128 + 128, bits);
+    assert_eq!(session(), deserialize_uper::<Session>(&bytes, bits));
+}
+
+#[test]
+fn test_round_trips() {
+    let session = Session {
+        parent: Some(ID),
+        ..session()
+    };
+    for codec in [Codec::Uper, Codec::Jer] {
+        let bytes = codec.encode(&session).unwrap();
+        assert_eq!(session, codec.decode::<Session>(&bytes).unwrap());
+    }
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_protobuf_round_trip() {
+    let mut expected = vec![10, 16];
+    expected.extend_from_slice(ID.as_bytes());
+    serialize_and_deserialize_protobuf(&expected, &Key::Id(ID));
+    serialize_and_deserialize_protobuf(&serialize_protobuf(&session()), &session());
+}
+
+#[test]
+fn test_wrong_length_is_an_error() {
+    let json = br#"{"id":"00","members":[],"token":"00000000000000000000000000000000"}"#;
+    assert!(Codec::Jer.decode::<Session>(json).is_err());
+}
@@ -0,0 +1,141 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::descriptor::BitVec;
+use test_utils::*;
+
+asn_to_rust!(
+    r#"Validate DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Contact ::= CHOICE {
+        email IA5String (SIZE(3..64)),
+        phone NumericString (SIZE(1..15))
+    }
+
+    Person ::= SEQUENCE {
+        name PrintableString (SIZE(1..8)),
+        age INTEGER (0..150) OPTIONAL,
+        score INTEGER (0..10, ...),
+        code IA5String (FROM ("ABC")) OPTIONAL,
+        flags BIT STRING (SIZE(4)),
+        contacts SEQUENCE (SIZE(1..2)) OF Contact
+    }
+
+    END"#
+);
+
+fn person() -> Person {
+    Person {
+        name: "Alice".to_string(),
+        age: Some(42),
+        score: 3,
+        code: None,
+        flags: BitVec::from_bytes(vec![0xF0], 4),
+        contacts: vec![
+            Contact::Email("alice@example.com".to_string()),
+            Contact::Phone("0123".to_string()),
+        ],
+    }
+}
+
+#[test]
+fn test_valid() {
+    assert_eq!(Ok(()), person().validate());
+}
+
+#[test]
+fn test_values_outside_of_extensible_ranges_are_valid() {
+    let mut person = person();
+    person.score = 100;
+    assert_eq!(Ok(()), person.validate());
+}
+
+#[test]
+fn test_integer_range() {
+    let mut person = person();
+    person.age = Some(151);
+    let violation = person.validate().unwrap_err();
+    assert_eq!(vec![Segment::Identifier("age")], violation.path);
+    assert_eq!(
+        Violation::ValueNotInRange {
+            value: 151,
+            min: Some(0),
+            max: Some(150)
+        },
+        violation.kind
+    );
+    assert_eq!(
+        "age: The value 151 is not within 0..150",
+        violation.to_string()
+    );
+}
+
+#[test]
+fn test_string_size_and_characters() {
+    let mut person = person();
+    person.name = "Alice Bob".to_string();
+    assert_eq!(
+        Violation::SizeNotInRange {
+            size: 9,
+            min: Some(1),
+            max: Some(8)
+        },
+        person.validate().unwrap_err().kind
+    );
+    person.name = "Alice!".to_string();
+    assert_eq!(
+        Violation::CharacterNotPermitted {
+            character: '!',
+            index: 5
+        },
+        person.validate().unwrap_err().kind
+    );
+}
+
+#[test]
+fn test_permitted_alphabet() {
+    let mut person = person();
+    person.code = Some("ABBA".to_string());
+    assert_eq!(Ok(()), person.validate());
+    person.code = Some("ABBAD".to_string());
+    assert_eq!(
+        "code: The character 'D' at index 4 is not permitted",
+        person.validate().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_bit_string_size() {
+    let mut person = person();
+    person.flags = BitVec::from_bytes(vec![0xFF], 8);
+    assert_eq!(
+        "flags: The size 8 is not within 4..4",
+        person.validate().unwrap_err().to_string()
+    );
+}
+
+#[test]
+fn test_sequence_of_size_and_items() {
+    let mut person = person();
+    person.contacts.clear();
+    assert_eq!(
+        "contacts: The size 0 is not within 1..2",
+        person.validate().unwrap_err().to_string()
+    );
+
+    person.contacts = vec![
+        Contact::Phone("0123".to_string()),
+        Contact::Phone("+49 0123".to_string()),
+    ];
+    let violation = person.validate().unwrap_err();
+    assert_eq!("contacts.1.phone", violation.path_string());
+    assert_eq!(
+        Violation::CharacterNotPermitted {
+            character: '+',
+            index: 0
+        },
+        violation.kind
+    );
+}
@@ -0,0 +1,357 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::descriptor::BitVec;
+use asn1rs::value::{Error, Schema, Value};
+use test_utils::*;
+
+/// Generates the rust types for the given definition and makes the same definition available
+/// as [`Schema`], so that the dynamic encoding can be compared against the generated code
+macro_rules! asn_to_rust_and_schema {
+    ($asn:tt) => {
+        asn_to_rust!($asn);
+
+        fn schema() -> Schema {
+            Schema::parse($asn).unwrap()
+        }
+    };
+}
+
+asn_to_rust_and_schema!(
+    r"ValueDynamic DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Basic ::= [5] SEQUENCE {
+        abc [APPLICATION 7] UTF8String,
+        def INTEGER
+    }
+
+    Extensible ::= [5] SET {
+        abc [APPLICATION 7] UTF8String,
+        def INTEGER,
+        ...,
+        jkl [APPLICATION 3] UTF8String,
+        ghi [APPLICATION 5] UTF8String
+    }
+
+    Color ::= ENUMERATED { red, green, blue, ... }
+
+    Shape ::= CHOICE {
+        circle INTEGER (0..255),
+        label PrintableString (SIZE(1..8)),
+        ...,
+        code INTEGER (0..7)
+    }
+
+    Drawing ::= SEQUENCE {
+        id INTEGER (-5..300),
+        color Color DEFAULT green,
+        shapes SEQUENCE (SIZE(0..4)) OF Shape,
+        digits NumericString (SIZE(2)) OPTIONAL,
+        flags BIT STRING (SIZE(3)),
+        blob OCTET STRING OPTIONAL,
+        visible BOOLEAN
+    }
+
+    END"
+);
+
+fn fields(fields: &[(&str, Value)]) -> Vec<(String, Value)> {
+    fields
+        .iter()
+        .map(|(name, value)| (name.to_string(), value.clone()))
+        .collect()
+}
+
+fn drawing_value() -> Value {
+    Value::Sequence(fields(&[
+        ("id", Value::Integer(-3)),
+        ("color", Value::Enumerated("blue".to_string())),
+        (
+            "shapes",
+            Value::SequenceOf(vec![
+                Value::Choice("circle".to_string(), Box::new(Value::Integer(200))),
+                Value::Choice(
+                    "label".to_string(),
+                    Box::new(Value::PrintableString("abc".to_string())),
+                ),
+                Value::Choice("code".to_string(), Box::new(Value::Integer(6))),
+            ]),
+        ),
+        ("digits", Value::NumericString("42".to_string())),
+        (
+            "flags",
+            Value::BitString(BitVec::from_bytes(vec![0b1010_0000], 3)),
+        ),
+        ("visible", Value::Boolean(true)),
+    ]))
+}
+
+fn drawing() -> Drawing {
+    Drawing {
+        id: -3,
+        color: Color::Blue,
+        shapes: vec![
+            Shape::Circle(200),
+            Shape::Label("abc".to_string()),
+            Shape::Code(6),
+        ],
+        digits: Some("42".to_string()),
+        flags: BitVec::from_bytes(vec![0b1010_0000], 3),
+        blob: None,
+        visible: true,
+    }
+}
+
+#[test]
+fn test_sequence_matches_generated_code() {
+    let schema = schema();
+    let value = Value::Sequence(fields(&[
+        ("abc", Value::Utf8String("hello world".to_string())),
+        ("def", Value::Integer(778)),
+    ]));
+    let (_bits, bytes) = serialize_uper(&Basic {
+        abc: "hello world".to_string(),
+        def: 778,
+    });
+
+    assert_eq!(bytes, schema.encode_uper("Basic", &value).unwrap());
+    assert_eq!(value, schema.decode_uper("Basic", &bytes).unwrap());
+}
+
+#[test]
+fn test_extensible_set_matches_generated_code() {
+    let schema = schema();
+    let value = Value::Set(fields(&[
+        ("abc", Value::Utf8String("bye bye".to_string())),
+        ("def", Value::Integer(774)),
+        ("jkl", Value::Utf8String("jkl".to_string())),
+        ("ghi", Value::Utf8String("ghi".to_string())),
+    ]));
+    let (bits, bytes) = serialize_uper(&Extensible {
+        def: 774,
+        abc: "bye bye".to_string(),
+        jkl: Some("jkl".to_string()),
+        ghi: Some("ghi".to_string()),
+    });
+
+    assert_eq!(8 * 22 + 2, bits);
+    assert_eq!(bytes, schema.encode_uper("Extensible", &value).unwrap());
+    assert_eq!(
+        value,
+        schema.decode_uper_bits("Extensible", &bytes, bits).unwrap()
+    );
+}
+
+#[test]
+fn test_nested_types_match_generated_code() {
+    let schema = schema();
+    let (bits, bytes) = serialize_uper(&drawing());
+
+    assert_eq!(
+        bytes,
+        schema.encode_uper("Drawing", &drawing_value()).unwrap()
+    );
+    assert_eq!(
+        drawing_value(),
+        schema.decode_uper_bits("Drawing", &bytes, bits).unwrap()
+    );
+}
+
+#[test]
+fn test_default_value_is_decoded_and_omitted() {
+    let schema = schema();
+    let mut drawing = drawing();
+    drawing.color = Color::Green;
+    let (bits, bytes) = serialize_uper(&drawing);
+
+    let value = schema.decode_uper_bits("Drawing", &bytes, bits).unwrap();
+    assert_eq!(
+        Some(&Value::Enumerated("green".to_string())),
+        value.get("color")
+    );
+    assert_eq!(bytes, schema.encode_uper("Drawing", &value).unwrap());
+
+    let Value::Sequence(mut fields) = value else {
+        panic!("Not a sequence: {value:?}");
+    };
+    fields.retain(|(name, _)| name != "color");
+    assert_eq!(
+        bytes,
+        schema
+            .encode_uper("Drawing", &Value::Sequence(fields))
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_der_round_trip() {
+    let schema = schema();
+    let bytes = schema.encode_der("Drawing", &drawing_value()).unwrap();
+    assert_eq!(
+        drawing_value(),
+        schema.decode_der("Drawing", &bytes).unwrap()
+    );
+
+    let value = Value::Sequence(fields(&[
+        ("abc", Value::Utf8String("hi".to_string())),
+        ("def", Value::Integer(128)),
+    ]));
+    let bytes = schema.encode_der("Basic", &value).unwrap();
+    assert_eq!(
+        &[0xA5, 0x08, 0x47, 0x02, b'h', b'i', 0x02, 0x02, 0x00, 0x80][..],
+        &bytes[..]
+    );
+    assert_eq!(value, schema.decode_der("Basic", &bytes).unwrap());
+}
+
+#[test]
+fn test_invalid_values_are_rejected() {
+    let schema = schema();
+    assert!(matches!(
+        schema.encode_uper("Potato", &Value::Null),
+        Err(Error::UnknownType(_))
+    ));
+    assert!(matches!(
+        schema.encode_uper("Basic", &Value::Integer(5)),
+        Err(Error::TypeMismatch("SEQUENCE", "INTEGER"))
+    ));
+    assert!(matches!(
+        schema.encode_uper(
+            "Basic",
+            &Value::Sequence(fields(&[("def", Value::Integer(5))]))
+        ),
+        Err(Error::MissingField(name)) if name == "abc"
+    ));
+    assert!(matches!(
+        schema.encode_der("Color", &Value::Enumerated("purple".to_string())),
+        Err(Error::UnknownVariant(name)) if name == "purple"
+    ));
+    assert!(matches!(
+        schema.encode_uper("Drawing", &Value::Sequence(Vec::new())),
+        Err(Error::MissingField(_))
+    ));
+}
+
+/// Replaces the first occurrence of the bit pattern of `find` with the bits of `replace`,
+/// regardless of the alignment
+fn replace_bits(bytes: &[u8], find: &[u8], find_bits: usize, replace: &[u8]) -> Vec<u8> {
+    let bit = |bytes: &[u8], index: usize| bytes[index / 8] & (0x80 >> (index % 8)) != 0;
+    let offset = (0..=bytes.len() * 8 - find_bits)
+        .find(|offset| (0..find_bits).all(|i| bit(bytes, offset + i) == bit(find, i)))
+        .expect("Pattern not found");
+    let mut bytes = bytes.to_vec();
+    for i in 0..find_bits {
+        let mask = 0x80 >> ((offset + i) % 8);
+        if bit(replace, i) {
+            bytes[(offset + i) / 8] |= mask;
+        } else {
+            bytes[(offset + i) / 8] &= !mask;
+        }
+    }
+    bytes
+}
+
+#[test]
+fn test_der_best_effort_skips_failing_fields_and_elements() {
+    let schema = schema();
+    let bytes = schema.encode_der("Drawing", &drawing_value()).unwrap();
+    let bytes = replace_bits(&bytes, b"abc", 24, b"a@c");
+    let bytes = replace_bits(&bytes, b"42", 16, b"4x");
+
+    let result = schema.decode_der_best_effort("Drawing", &bytes);
+    assert!(!result.is_complete());
+    assert_eq!(
+        vec!["shapes[1]", "digits"],
+        result
+            .errors
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect::<Vec<_>>()
+    );
+
+    let Value::Sequence(mut expected) = drawing_value() else {
+        unreachable!()
+    };
+    expected.retain(|(name, _)| name != "digits");
+    if let Value::SequenceOf(shapes) = &mut expected[2].1 {
+        shapes.remove(1);
+    }
+    assert_eq!(Some(Value::Sequence(expected)), result.value);
+    assert!(schema.decode_der("Drawing", &bytes).is_err());
+}
+
+#[test]
+fn test_der_best_effort_records_missing_fields() {
+    let schema = schema();
+    let result = schema.decode_der_best_effort("Basic", &[0xA5, 0x04, 0x47, 0x02, b'h', b'i']);
+    assert_eq!(
+        Some(Value::Sequence(fields(&[(
+            "abc",
+            Value::Utf8String("hi".to_string())
+        )]))),
+        result.value
+    );
+    assert_eq!(1, result.errors.len());
+    assert_eq!("def", result.errors[0].path);
+    assert!(matches!(&result.errors[0].error, Error::MissingField(name) if name == "def"));
+
+    let result = schema.decode_der_best_effort("Basic", &[0x30, 0x00]);
+    assert_eq!(None, result.value);
+    assert_eq!("", result.errors[0].path);
+}
+
+#[test]
+fn test_uper_best_effort_returns_partial_value() {
+    let schema = schema();
+    let (bits, bytes) = serialize_uper(&drawing());
+    // the digits '4' and '2' are encoded as their index in " 0123456789"
+    let bytes = replace_bits(&bytes, &[0b0101_0011], 8, &[0b1111_0011]);
+
+    let result = schema.decode_uper_best_effort("Drawing", &bytes[..bits.div_ceil(8)]);
+    assert_eq!(
+        vec!["digits"],
+        result
+            .errors
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect::<Vec<_>>()
+    );
+    let Value::Sequence(mut expected) = drawing_value() else {
+        unreachable!()
+    };
+    expected.truncate(3);
+    assert_eq!(Some(Value::Sequence(expected)), result.value);
+}
+
+#[test]
+fn test_uper_best_effort_skips_failing_extension_additions() {
+    let schema = schema();
+    let (bits, bytes) = serialize_uper(&Extensible {
+        def: 774,
+        abc: "bye bye".to_string(),
+        jkl: Some("jkl".to_string()),
+        ghi: Some("ghi".to_string()),
+    });
+    assert_eq!(bytes.len(), bits.div_ceil(8));
+    let bytes = replace_bits(&bytes, b"jkl", 24, &[0xFF, b'k', b'l']);
+
+    let result = schema.decode_uper_best_effort("Extensible", &bytes);
+    assert_eq!(
+        vec!["jkl"],
+        result
+            .errors
+            .iter()
+            .map(|e| e.path.as_str())
+            .collect::<Vec<_>>()
+    );
+    assert_eq!(
+        Some(Value::Set(fields(&[
+            ("abc", Value::Utf8String("bye bye".to_string())),
+            ("def", Value::Integer(774)),
+            ("ghi", Value::Utf8String("ghi".to_string())),
+        ]))),
+        result.value
+    );
+}
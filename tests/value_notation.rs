@@ -0,0 +1,94 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::descriptor::BitVec;
+use test_utils::*;
+
+asn_to_rust!(
+    r"ValueNotation DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Color ::= ENUMERATED { red, dark-green, blue, ... }
+
+    Shape ::= CHOICE {
+        circle INTEGER (0..255),
+        label PrintableString (SIZE(1..8)),
+        polygon SEQUENCE OF INTEGER
+    }
+
+    Drawing ::= SEQUENCE {
+        id INTEGER (-5..300),
+        fill-color Color DEFAULT blue,
+        shapes SEQUENCE OF Shape,
+        digits NumericString OPTIONAL,
+        flags BIT STRING,
+        blob OCTET STRING OPTIONAL,
+        visible BOOLEAN,
+        nothing NULL
+    }
+
+    END"
+);
+
+fn drawing() -> Drawing {
+    Drawing {
+        id: -3,
+        fill_color: Color::DarkGreen,
+        shapes: vec![
+            Shape::Circle(200),
+            Shape::Label("say \"hi\"".to_string()),
+            Shape::Polygon(vec![1, 2]),
+        ],
+        digits: None,
+        flags: BitVec::from_bytes(vec![0b1010_0000], 3),
+        blob: Some(vec![0x0A, 0xFF]),
+        visible: true,
+        nothing: Null,
+    }
+}
+
+#[test]
+fn test_asn1_value_notation() {
+    assert_eq!(
+        r#"{
+  id -3,
+  fillColor darkGreen,
+  shapes {
+    circle : 200,
+    label : "say ""hi""",
+    polygon : {
+      1,
+      2
+    }
+  },
+  flags '101'B,
+  blob '0AFF'H,
+  visible TRUE,
+  nothing NULL
+}"#,
+        ValueNotation::new(&drawing()).to_string()
+    );
+}
+
+#[test]
+fn test_gser() {
+    assert_eq!(
+        r#"{ id -3, fillColor darkGreen, shapes { circle:200, label:"say ""hi""", polygon:{ 1, 2 } }, flags '101'B, blob '0AFF'H, visible TRUE, nothing NULL }"#,
+        ValueNotation::gser(&drawing()).to_string()
+    );
+}
+
+#[test]
+fn test_empty_and_primitive_values() {
+    let mut drawing = drawing();
+    drawing.shapes.clear();
+    assert!(ValueNotation::gser(&drawing)
+        .to_string()
+        .contains(", shapes {}, "));
+    assert_eq!("blue", ValueNotation::new(&Color::Blue).to_string());
+    assert_eq!(
+        "label : \"x\"",
+        ValueNotation::new(&Shape::Label("x".to_string())).to_string()
+    );
+}
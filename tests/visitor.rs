@@ -0,0 +1,107 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::model::asn::Tag;
+use test_utils::*;
+
+asn_to_rust!(
+    r"Visitor DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Status ::= ENUMERATED { online, offline }
+
+    Contact ::= CHOICE {
+        email IA5String,
+        phone NumericString
+    }
+
+    Person ::= SEQUENCE {
+        name UTF8String,
+        age INTEGER (0..255) OPTIONAL,
+        status Status,
+        contacts SEQUENCE OF Contact
+    }
+
+    END"
+);
+
+fn person() -> Person {
+    Person {
+        name: "Alice".to_string(),
+        age: None,
+        status: Status::Offline,
+        contacts: vec![
+            Contact::Email("alice@example.com".to_string()),
+            Contact::Phone("0123".to_string()),
+        ],
+    }
+}
+
+#[derive(Default)]
+struct Recorder(Vec<String>);
+
+impl AsnVisitor for Recorder {
+    fn visit(&mut self, field: &Field<'_>, value: VisitedValue<'_>) {
+        self.0.push(format!(
+            "{} {:?} {:?}",
+            field.path_string(),
+            field.kind,
+            value
+        ));
+    }
+
+    fn leave(&mut self, field: &Field<'_>) {
+        self.0.push(format!("/{}", field.path_string()));
+    }
+}
+
+#[test]
+fn test_visits_present_values_in_order() {
+    let mut recorder = Recorder::default();
+    person().accept(&mut recorder);
+    assert_eq!(
+        vec![
+            " Sequence Constructed(4)",
+            "name Utf8String String(\"Alice\")",
+            "status Enumerated Identifier { index: 1, name: Some(\"offline\") }",
+            "contacts SequenceOf Constructed(2)",
+            "contacts.0 Choice Identifier { index: 0, name: Some(\"email\") }",
+            "contacts.0.email Ia5String String(\"alice@example.com\")",
+            "/contacts.0",
+            "contacts.1 Choice Identifier { index: 1, name: Some(\"phone\") }",
+            "contacts.1.phone NumericString String(\"0123\")",
+            "/contacts.1",
+            "/contacts",
+            "/",
+        ],
+        recorder.0
+    );
+}
+
+/// Collects the identifiers and tags of all named values
+struct Tags(Vec<(&'static str, Tag)>);
+
+impl AsnVisitor for Tags {
+    fn visit(&mut self, field: &Field<'_>, _value: VisitedValue<'_>) {
+        if let Some(name) = field.name() {
+            self.0.push((name, field.tag));
+        }
+    }
+}
+
+#[test]
+fn test_reports_identifiers_and_tags() {
+    let mut tags = Tags(Vec::new());
+    person().accept(&mut tags);
+    assert_eq!(
+        vec![
+            ("name", Tag::ContextSpecific(0)),
+            ("status", Tag::Universal(10)),
+            ("contacts", Tag::ContextSpecific(3)),
+            ("email", Tag::ContextSpecific(0)),
+            ("phone", Tag::ContextSpecific(1)),
+        ],
+        tags.0
+    );
+}
@@ -0,0 +1,64 @@
+#![cfg(feature = "wasm")]
+
+use asn1rs::codec::{Codec, Error};
+use asn1rs::prelude::*;
+
+#[asn(sequence)]
+#[derive(Debug, PartialEq)]
+pub struct Measurement {
+    #[asn(integer(0..4095))]
+    pub value: u16,
+    #[asn(optional(utf8string))]
+    pub unit: Option<String>,
+}
+
+#[asn(choice)]
+#[derive(Debug, PartialEq)]
+pub enum Report {
+    #[asn(complex(Measurement, tag(UNIVERSAL(16))))]
+    Measurement(Measurement),
+    #[asn(boolean)]
+    Alive(bool),
+}
+
+asn1rs::wasm_bindings!(
+    Measurement => encode_measurement, decode_measurement;
+    Report => encode_report, decode_report;
+);
+
+#[test]
+fn test_exported_functions_round_trip() {
+    let json = r#"{"value":1234,"unit":"mV"}"#;
+    let uper = encode_measurement(Codec::Uper.id(), json).unwrap();
+    assert_eq!(
+        Codec::Uper
+            .encode(&Measurement {
+                value: 1234,
+                unit: Some("mV".to_string()),
+            })
+            .unwrap(),
+        uper
+    );
+    assert_eq!(json, decode_measurement(Codec::Uper.id(), &uper).unwrap());
+
+    let json = r#"{"alive":true}"#;
+    let jer = encode_report(Codec::Jer.id(), json).unwrap();
+    assert_eq!(json.as_bytes(), &jer[..]);
+    assert_eq!(json, decode_report(Codec::Jer.id(), &jer).unwrap());
+}
+
+#[test]
+fn test_errors() {
+    assert!(matches!(
+        asn1rs::wasm::encode::<Measurement>(0xFF, r#"{"value":1}"#),
+        Err(Error::UnknownCodec(0xFF))
+    ));
+    assert!(matches!(
+        asn1rs::wasm::encode::<Measurement>(Codec::Uper.id(), r#"{"unit":"mV"}"#),
+        Err(Error::Jer(_))
+    ));
+    assert!(matches!(
+        asn1rs::wasm::decode::<Report>(Codec::Uper.id(), &[]),
+        Err(Error::Uper(_))
+    ));
+}
@@ -0,0 +1,112 @@
+mod test_utils;
+
+use asn1rs::codec::Codec;
+use test_utils::*;
+
+asn_to_rust!(
+    r"WideInteger DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Counter ::= INTEGER (0..79228162514264337593543950335)
+
+    Offset ::= INTEGER (-79228162514264337593543950335..79228162514264337593543950335, ...)
+
+    Totals ::= SEQUENCE {
+        counter INTEGER (0..79228162514264337593543950335),
+        big INTEGER (18446744073709551616..MAX),
+        small INTEGER (0..255)
+    }
+
+    END"
+);
+
+const COUNTER_MAX: u128 = (1 << 96) - 1;
+
+#[test]
+fn test_types_and_bounds() {
+    let _ = Counter(0_u128); // does not compile if the inner type is not u128
+    let _ = Offset(0_i128); // does not compile if the inner type is not i128
+    assert_eq!(COUNTER_MAX, Counter::value_max());
+    assert_eq!(-(COUNTER_MAX as i128), Offset::value_min());
+    assert_eq!(1 << 64, Totals::big_min());
+}
+
+#[test]
+fn test_uper_constrained() {
+    serialize_and_deserialize_uper(96, &[0xFF; 12], &Counter(COUNTER_MAX));
+    let mut one = [0x00; 12];
+    one[11] = 0x01;
+    serialize_and_deserialize_uper(96, &one, &Counter(1));
+
+    // not extended, the offset to the lower bound needs 97 bits
+    let mut zero = vec![0x3F];
+    zero.extend_from_slice(&[0xFF; 11]);
+    zero.push(0xC0);
+    serialize_and_deserialize_uper(98, &zero, &Offset(0));
+}
+
+#[test]
+fn test_uper_semi_constrained() {
+    let totals = Totals {
+        counter: 0,
+        big: (1 << 64) + 0x0102,
+        small: 7,
+    };
+    let mut expected = vec![0x00; 12];
+    expected.extend_from_slice(&[0x02, 0x01, 0x02, 0x07]);
+    serialize_and_deserialize_uper(96 + 8 + 16 + 8, &expected, &totals);
+
+    // the lower bound itself still takes one octet
+    let (bits, _) = serialize_uper(&Totals {
+        big: 1 << 64,
+        ..totals
+    });
+    assert_eq!(96 + 8 + 8 + 8, bits);
+}
+
+#[test]
+fn test_uper_extended() {
+    let (bits, bytes) = serialize_uper(&Offset(i128::MAX));
+    // extension bit, length and 16 octets
+    assert_eq!(1 + 8 + 128, bits);
+    assert_eq!(Offset(i128::MAX), deserialize_uper::<Offset>(&bytes, bits));
+    assert_eq!(
+        Offset(i128::MIN),
+        Codec::Uper
+            .decode(&Codec::Uper.encode(&Offset(i128::MIN)).unwrap())
+            .unwrap()
+    );
+}
+
+#[test]
+fn test_uper_value_not_in_range() {
+    let mut writer = UperWriter::default();
+    assert!(writer.write(&Counter(1 << 96)).is_err());
+}
+
+#[test]
+fn test_jer() {
+    let totals = Totals {
+        counter: COUNTER_MAX,
+        big: u128::from(u64::MAX) + 1,
+        small: 255,
+    };
+    let json = Codec::Jer.encode(&totals).unwrap();
+    assert_eq!(
+        br#"{"counter":79228162514264337593543950335,"big":18446744073709551616,"small":255}"#,
+        &json[..]
+    );
+    assert_eq!(totals, Codec::Jer.decode::<Totals>(&json).unwrap());
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_protobuf_bytes() {
+    let mut expected = vec![10, 16];
+    expected.extend_from_slice(&COUNTER_MAX.to_be_bytes());
+    serialize_and_deserialize_protobuf(&expected, &Counter(COUNTER_MAX));
+
+    let mut expected = vec![10, 16];
+    expected.extend_from_slice(&(-5_i128).to_be_bytes());
+    serialize_and_deserialize_protobuf(&expected, &Offset(-5));
+}
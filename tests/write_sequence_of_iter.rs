@@ -0,0 +1,70 @@
+use asn1rs::descriptor::sequenceof::NoConstraint;
+use asn1rs::descriptor::Utf8String;
+use asn1rs::prelude::*;
+use asn1rs::protocol::per::ErrorKind;
+use asn1rs::rw::JerWriter;
+
+#[asn(sequence)]
+#[derive(Debug, Clone, PartialEq)]
+pub struct Sample {
+    #[asn(integer(0..65535))]
+    pub id: u16,
+    #[asn(utf8string)]
+    pub label: String,
+}
+
+#[asn(transparent)]
+#[derive(Debug, PartialEq)]
+pub struct Samples(#[asn(sequence_of(complex(Sample, tag(UNIVERSAL(16)))))] Vec<Sample>);
+
+fn sample(id: u16) -> Sample {
+    Sample {
+        id,
+        label: format!("sample-{}", id),
+    }
+}
+
+#[test]
+fn test_uper_from_channel() {
+    let (sender, receiver) = std::sync::mpsc::channel();
+    let producer = std::thread::spawn(move || {
+        (0..100).for_each(|id| sender.send(sample(id)).unwrap());
+    });
+
+    let mut writer = UperWriter::default();
+    writer
+        .write_sequence_of_iter::<NoConstraint, Sample, _>(100, receiver.iter())
+        .unwrap();
+    producer.join().unwrap();
+
+    let mut expected = UperWriter::default();
+    expected
+        .write(&Samples((0..100).map(sample).collect()))
+        .unwrap();
+    assert_eq!(expected.into_bytes_vec(), writer.into_bytes_vec());
+}
+
+#[test]
+fn test_jer_from_borrowed_values() {
+    let labels = ["a", "b", "c"].map(String::from);
+    let mut writer = JerWriter::default();
+    writer
+        .write_sequence_of_iter::<NoConstraint, Utf8String, _>(labels.len(), labels.iter())
+        .unwrap();
+    assert_eq!(r#"["a","b","c"]"#, writer.as_str());
+}
+
+#[test]
+fn test_uper_value_count_mismatch() {
+    let mut writer = UperWriter::default();
+    let error = writer
+        .write_sequence_of_iter::<NoConstraint, Sample, _>(3, (0..2).map(sample))
+        .unwrap_err();
+    assert_eq!(&ErrorKind::ValueCountMismatch(3, 2), error.kind());
+
+    let mut writer = UperWriter::default();
+    let error = writer
+        .write_sequence_of_iter::<NoConstraint, Sample, _>(1, (0..2).map(sample))
+        .unwrap_err();
+    assert_eq!(&ErrorKind::ValueCountMismatch(1, 2), error.kind());
+}
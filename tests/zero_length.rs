@@ -0,0 +1,205 @@
+#![recursion_limit = "512"]
+
+mod test_utils;
+
+use asn1rs::value::{Schema, Value};
+use test_utils::*;
+
+const ZERO_LENGTH: &str = r"ZeroLength DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Empty ::= SEQUENCE {
+        octets      OCTET STRING,
+        bits        BIT STRING,
+        numbers     SEQUENCE OF INTEGER,
+        tags        SET OF BOOLEAN,
+        text        UTF8String,
+        ia5         IA5String
+    }
+
+    FixedZero ::= SEQUENCE {
+        octets      OCTET STRING (SIZE(0)),
+        bits        BIT STRING (SIZE(0)),
+        numbers     SEQUENCE (SIZE(0)) OF INTEGER,
+        ia5         IA5String (SIZE(0)),
+        flag        BOOLEAN
+    }
+
+    ExtensibleZero ::= SEQUENCE {
+        octets      OCTET STRING (SIZE(0, ...)),
+        bits        BIT STRING (SIZE(0, ...)),
+        numbers     SEQUENCE (SIZE(0, ...)) OF INTEGER
+    }
+
+    BelowExtensible ::= SEQUENCE {
+        octets      OCTET STRING (SIZE(1..4, ...)),
+        bits        BIT STRING (SIZE(1..4, ...)),
+        numbers     SEQUENCE (SIZE(1..4, ...)) OF INTEGER
+    }
+
+    END";
+
+asn_to_rust!(
+    r"ZeroLength DEFINITIONS AUTOMATIC TAGS ::=
+    BEGIN
+
+    Empty ::= SEQUENCE {
+        octets      OCTET STRING,
+        bits        BIT STRING,
+        numbers     SEQUENCE OF INTEGER,
+        tags        SET OF BOOLEAN,
+        text        UTF8String,
+        ia5         IA5String
+    }
+
+    FixedZero ::= SEQUENCE {
+        octets      OCTET STRING (SIZE(0)),
+        bits        BIT STRING (SIZE(0)),
+        numbers     SEQUENCE (SIZE(0)) OF INTEGER,
+        ia5         IA5String (SIZE(0)),
+        flag        BOOLEAN
+    }
+
+    ExtensibleZero ::= SEQUENCE {
+        octets      OCTET STRING (SIZE(0, ...)),
+        bits        BIT STRING (SIZE(0, ...)),
+        numbers     SEQUENCE (SIZE(0, ...)) OF INTEGER
+    }
+
+    BelowExtensible ::= SEQUENCE {
+        octets      OCTET STRING (SIZE(1..4, ...)),
+        bits        BIT STRING (SIZE(1..4, ...)),
+        numbers     SEQUENCE (SIZE(1..4, ...)) OF INTEGER
+    }
+
+    END"
+);
+
+fn empty() -> Empty {
+    Empty {
+        octets: Vec::new(),
+        bits: BitVec::default(),
+        numbers: Vec::new(),
+        tags: Vec::new(),
+        text: String::new(),
+        ia5: String::new(),
+    }
+}
+
+#[test]
+fn test_uper_empty_unconstrained() {
+    // every field is a single length determinant of zero
+    serialize_and_deserialize_uper(8 * 6, &[0x00; 6], &empty());
+}
+
+#[test]
+fn test_uper_fixed_size_zero_has_no_length() {
+    serialize_and_deserialize_uper(
+        1,
+        &[0x80],
+        &FixedZero {
+            octets: Vec::new(),
+            bits: BitVec::default(),
+            numbers: Vec::new(),
+            ia5: String::new(),
+            flag: true,
+        },
+    );
+}
+
+#[test]
+fn test_uper_extensible_size_zero() {
+    // only the extension bits, SIZE(0) is within the root
+    serialize_and_deserialize_uper(
+        3,
+        &[0x00],
+        &ExtensibleZero {
+            octets: Vec::new(),
+            bits: BitVec::default(),
+            numbers: Vec::new(),
+        },
+    );
+    // outside of the root, the length is encoded as semi-constrained whole number
+    serialize_and_deserialize_uper(
+        17 + 10 + 25,
+        &[0x80, 0x80, 0xC0, 0x70, 0x10, 0x10, 0x00],
+        &ExtensibleZero {
+            octets: vec![0x01],
+            bits: BitVec::from_bytes(vec![0x80], 1),
+            numbers: vec![0],
+        },
+    );
+}
+
+#[test]
+fn test_uper_empty_below_extensible_root() {
+    // the extension bit is set and the length of zero is written as semi-constrained number
+    serialize_and_deserialize_uper(
+        3 * 9,
+        &[0x80, 0x40, 0x20, 0x00],
+        &BelowExtensible {
+            octets: Vec::new(),
+            bits: BitVec::default(),
+            numbers: Vec::new(),
+        },
+    );
+}
+
+#[test]
+#[should_panic(expected = "SizeNotInRange(0, 1, 4)")]
+fn test_uper_empty_below_root_is_rejected_without_extension_marker() {
+    #[asn(sequence)]
+    #[derive(Debug, PartialEq)]
+    pub struct NotExtensible {
+        #[asn(octet_string(size(1..4)))]
+        pub octets: Vec<u8>,
+    }
+    serialize_uper(&NotExtensible { octets: Vec::new() });
+}
+
+#[test]
+fn test_empty_value_model() {
+    let schema = Schema::parse(ZERO_LENGTH).unwrap();
+    let value = Value::Sequence(vec![
+        ("octets".into(), Value::OctetString(Vec::new())),
+        ("bits".into(), Value::BitString(BitVec::default())),
+        ("numbers".into(), Value::SequenceOf(Vec::new())),
+        ("tags".into(), Value::SetOf(Vec::new())),
+        ("text".into(), Value::Utf8String(String::new())),
+        ("ia5".into(), Value::Ia5String(String::new())),
+    ]);
+    let bytes = schema.encode_uper("Empty", &value).unwrap();
+    assert_eq!(serialize_uper(&empty()).1, bytes);
+    assert_eq!(value, schema.decode_uper("Empty", &bytes).unwrap());
+
+    let bytes = schema.encode_der("Empty", &value).unwrap();
+    assert_eq!(
+        vec![
+            0x30, 0x0D, 0x80, 0x00, 0x81, 0x01, 0x00, 0xA2, 0x00, 0xA3, 0x00, 0x84, 0x00, 0x85,
+            0x00
+        ],
+        bytes
+    );
+    assert_eq!(value, schema.decode_der("Empty", &bytes).unwrap());
+
+    let value = Value::Sequence(vec![
+        ("octets".into(), Value::OctetString(Vec::new())),
+        ("bits".into(), Value::BitString(BitVec::default())),
+        ("numbers".into(), Value::SequenceOf(Vec::new())),
+        ("ia5".into(), Value::Ia5String(String::new())),
+        ("flag".into(), Value::Boolean(true)),
+    ]);
+    let bytes = schema.encode_uper("FixedZero", &value).unwrap();
+    assert_eq!(vec![0x80], bytes);
+    assert_eq!(value, schema.decode_uper("FixedZero", &bytes).unwrap());
+}
+
+#[test]
+#[cfg(feature = "protobuf")]
+fn test_protobuf_empty() {
+    // empty sequences are omitted, empty strings are written with a length of zero
+    serialize_and_deserialize_protobuf(
+        &[10, 0, 18, 8, 0, 0, 0, 0, 0, 0, 0, 0, 42, 0, 50, 0],
+        &empty(),
+    );
+}